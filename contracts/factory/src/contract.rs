@@ -1,28 +1,36 @@
 use cosmwasm_std::{
-    attr, entry_point, to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Order, Reply,
-    ReplyOn, Response, StdError, StdResult, SubMsg, WasmMsg,
+    attr, entry_point, to_binary, Addr, Api, Binary, CosmosMsg, Decimal, Deps, DepsMut, Env,
+    MessageInfo, Order, Reply, ReplyOn, Response, StdError, StdResult, SubMsg, Uint128, WasmMsg,
 };
 
 use crate::error::ContractError;
 use crate::migration;
+#[cfg(feature = "terra")]
+use crate::querier::query_contract_info;
 use crate::querier::query_pair_info;
 
 use crate::state::{
-    pair_key, read_pairs, Config, TmpPairInfo, CONFIG, OWNERSHIP_PROPOSAL, PAIRS, PAIR_CONFIGS,
-    TMP_PAIR_INFO,
+    index_pair, load_pair, pair_key, read_pair_variants, read_pairs, read_pairs_by_asset,
+    read_pairs_by_type, read_pairs_to_migrate, unindex_pair, Config, InitialLiquidity, TmpPairInfo,
+    CONFIG, NATIVE_TOKEN_DECIMALS, OWNERSHIP_PROPOSAL, PAIRS, PAIRS_COUNT, PAIR_CODE_IDS,
+    PAIR_CONFIGS, PAIR_FEES, PAUSED, PAUSED_PAIRS, TMP_PAIR_INFO,
 };
 
 use crate::response::MsgInstantiateContractResponse;
 
-use astroport::asset::{addr_validate_to_lower, AssetInfo, PairInfo};
+use astroport::asset::{addr_validate_to_lower, validate_native_denom, Asset, AssetInfo, PairInfo};
 use astroport::factory::{
-    ConfigResponse, ExecuteMsg, FeeInfoResponse, InstantiateMsg, MigrateMsg, PairConfig, PairType,
-    PairsResponse, QueryMsg,
+    ConfigResponse, CreationFeeResponse, ExecuteMsg, FeeInfoResponse, InstantiateMsg, MigrateMsg,
+    NativeTokenDecimalsResponse, PairCodeId, PairConfig, PairFees, PairType, PairsCountResponse,
+    PairsResponse, PairsToMigrateResponse, PauseStatusResponse, QueryMsg, UpdateAddr,
 };
+use astroport::querier::NATIVE_TOKEN_PRECISION;
 
 use astroport::common::{claim_ownership, drop_ownership_proposal, propose_new_owner};
-use astroport::pair::InstantiateMsg as PairInstantiateMsg;
+use astroport::generator::ExecuteMsg as GeneratorExecuteMsg;
+use astroport::pair::{ExecuteMsg as PairExecuteMsg, InstantiateMsg as PairInstantiateMsg};
 use cw2::{get_contract_version, set_contract_version};
+use cw20::Cw20ExecuteMsg;
 use protobuf::Message;
 use std::collections::HashSet;
 
@@ -32,6 +40,8 @@ const CONTRACT_NAME: &str = "astroport-factory";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 /// A `reply` call code ID of sub-message.
 const INSTANTIATE_PAIR_REPLY_ID: u64 = 1;
+/// The maximum number of decimals a native token can have.
+const MAX_NATIVE_TOKEN_DECIMALS: u8 = 18;
 
 /// ## Description
 /// Creates a new contract with the specified parameters in the `msg` variable.
@@ -59,6 +69,7 @@ pub fn instantiate(
         fee_address: None,
         generator_address: None,
         whitelist_code_id: msg.whitelist_code_id,
+        guardian: None,
     };
 
     if let Some(generator_address) = msg.generator_address {
@@ -83,10 +94,7 @@ pub fn instantiate(
     }
 
     for pc in msg.pair_configs.iter() {
-        // validate total and maker fee bps
-        if !pc.valid_fee_bps() {
-            return Err(ContractError::PairConfigInvalidFeeBps {});
-        }
+        validate_pair_config(deps.api, pc)?;
         PAIR_CONFIGS.save(deps.storage, pc.clone().pair_type.to_string(), pc)?;
     }
     CONFIG.save(deps.storage, &config)?;
@@ -105,6 +113,8 @@ pub struct UpdateConfig {
     generator_address: Option<String>,
     /// cw1 whitelist contract code id used to store 3rd party rewards in pools
     whitelist_code_id: Option<u64>,
+    /// Sets or removes contract address that is allowed to pause pairs
+    guardian: Option<UpdateAddr>,
 }
 
 /// ## Description
@@ -123,17 +133,44 @@ pub struct UpdateConfig {
 ///             token_code_id,
 ///             fee_address,
 ///             generator_address,
+///             whitelist_code_id,
+///             guardian,
 ///         }** Updates general settings.
 ///
 /// * **ExecuteMsg::UpdatePairConfig { config }** Updates pair configuration.
 ///
+/// * **ExecuteMsg::UpdatePairFees { pair_addr, fees }** Sets or removes the fee override of a pair.
+///
+/// * **ExecuteMsg::UpdatePauseStatus { pair_addr, paused }** Pauses or unpauses a pair or all the pairs.
+///
+/// * **ExecuteMsg::UpdateNativeTokenDecimals { denom, decimals }** Sets or removes the decimals of a native token.
+///
 /// * **ExecuteMsg::CreatePair {
 ///             pair_type,
 ///             asset_infos,
 ///             init_params,
 ///         }** Creates a new pair with the specified input parameters
 ///
-/// * **ExecuteMsg::Deregister { asset_infos }** Removes a exists pair with the specified input parameters.
+/// * **ExecuteMsg::CreatePairAndProvide {
+///             pair_type,
+///             assets,
+///             init_params,
+///             slippage_tolerance,
+///             auto_stake,
+///             receiver,
+///         }** Creates a new pair and provides its initial liquidity
+///
+/// * **ExecuteMsg::Deregister { asset_infos, pair_type }** Removes a exists pair with the specified input parameters.
+///
+/// * **ExecuteMsg::MigratePairs {
+///             code_id,
+///             pair_type,
+///             start_after,
+///             limit,
+///             msg,
+///         }** Migrates a page of the pairs of a pair type to a new code ID.
+///
+/// * **ExecuteMsg::MigratePairKeys { limit }** Moves a page of the pairs registered before the pairs were keyed by type.
 ///
 /// * **ExecuteMsg::ProposeNewOwner { owner, expires_in }** Creates a request to change ownership.
 ///
@@ -153,6 +190,7 @@ pub fn execute(
             fee_address,
             generator_address,
             whitelist_code_id,
+            guardian,
         } => execute_update_config(
             deps,
             env,
@@ -162,15 +200,54 @@ pub fn execute(
                 fee_address,
                 generator_address,
                 whitelist_code_id,
+                guardian,
             },
         ),
         ExecuteMsg::UpdatePairConfig { config } => execute_update_pair_config(deps, info, config),
+        ExecuteMsg::UpdatePairFees { pair_addr, fees } => {
+            execute_update_pair_fees(deps, info, pair_addr, fees)
+        }
+        ExecuteMsg::UpdatePauseStatus { pair_addr, paused } => {
+            execute_update_pause_status(deps, info, pair_addr, paused)
+        }
+        ExecuteMsg::UpdateNativeTokenDecimals { denom, decimals } => {
+            execute_update_native_token_decimals(deps, info, denom, decimals)
+        }
         ExecuteMsg::CreatePair {
             pair_type,
             asset_infos,
             init_params,
-        } => execute_create_pair(deps, env, pair_type, asset_infos, init_params),
-        ExecuteMsg::Deregister { asset_infos } => deregister(deps, info, asset_infos),
+        } => execute_create_pair(deps, env, info, pair_type, asset_infos, init_params, None),
+        ExecuteMsg::CreatePairAndProvide {
+            pair_type,
+            assets,
+            init_params,
+            slippage_tolerance,
+            auto_stake,
+            receiver,
+        } => execute_create_pair_and_provide(
+            deps,
+            env,
+            info,
+            pair_type,
+            assets,
+            init_params,
+            slippage_tolerance,
+            auto_stake,
+            receiver,
+        ),
+        ExecuteMsg::Deregister {
+            asset_infos,
+            pair_type,
+        } => deregister(deps, info, asset_infos, pair_type),
+        ExecuteMsg::MigratePairs {
+            code_id,
+            pair_type,
+            start_after,
+            limit,
+            msg,
+        } => execute_migrate_pairs(deps, env, info, code_id, pair_type, start_after, limit, msg),
+        ExecuteMsg::MigratePairKeys { limit } => execute_migrate_pair_keys(deps, info, limit),
         ExecuteMsg::ProposeNewOwner { owner, expires_in } => {
             let config: Config = CONFIG.load(deps.storage)?;
 
@@ -254,6 +331,15 @@ pub fn execute_update_config(
         config.whitelist_code_id = code_id;
     }
 
+    if let Some(guardian) = param.guardian {
+        match guardian {
+            UpdateAddr::Set(guardian) => {
+                config.guardian = Some(addr_validate_to_lower(deps.api, &guardian)?);
+            }
+            UpdateAddr::Remove {} => config.guardian = None,
+        }
+    }
+
     CONFIG.save(deps.storage, &config)?;
 
     Ok(Response::new().add_attribute("action", "update_config"))
@@ -284,10 +370,7 @@ pub fn execute_update_pair_config(
         return Err(ContractError::Unauthorized {});
     }
 
-    // validate total and maker fee bps
-    if !pair_config.valid_fee_bps() {
-        return Err(ContractError::PairConfigInvalidFeeBps {});
-    }
+    validate_pair_config(deps.api, &pair_config)?;
 
     PAIR_CONFIGS.save(
         deps.storage,
@@ -298,6 +381,200 @@ pub fn execute_update_pair_config(
     Ok(Response::new().add_attribute("action", "update_pair_config"))
 }
 
+/// ## Description
+/// Validates the fees and the creation fee of a pair type. Returns an [`ContractError`] if they are invalid.
+///
+/// ## Params
+/// * **api** is the object of type [`Api`].
+///
+/// * **pair_config** is the object of type [`PairConfig`] to validate.
+fn validate_pair_config(api: &dyn Api, pair_config: &PairConfig) -> Result<(), ContractError> {
+    // validate total and maker fee bps
+    if !pair_config.valid_fee_bps() {
+        return Err(ContractError::PairConfigInvalidFeeBps {});
+    }
+
+    if let Some(creation_fee) = &pair_config.creation_fee {
+        creation_fee.info.check(api)?;
+        if creation_fee.amount.is_zero() {
+            return Err(ContractError::InvalidCreationFee {});
+        }
+    }
+
+    Ok(())
+}
+
+/// ## Description
+/// Sets the fee override of a pair if `fees` is set, or removes it otherwise. Returns an [`ContractError`] on failure.
+///
+/// ## Params
+/// * **deps** is the object of type [`DepsMut`].
+///
+/// * **info** is the object of type [`MessageInfo`]
+///
+/// * **pair_addr** is the object of type [`String`]. This is the pair for which we set the fees.
+///
+/// * **fees** is an [`Option`] field of type [`PairFees`]. These are the new fees of the pair.
+///
+/// ## Executor
+/// Only owner can execute it
+pub fn execute_update_pair_fees(
+    deps: DepsMut,
+    info: MessageInfo,
+    pair_addr: String,
+    fees: Option<PairFees>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let pair_addr = addr_validate_to_lower(deps.api, &pair_addr)?;
+
+    // only pairs registered in the factory can have their fees overridden
+    assert_pair_registered(deps.as_ref(), &pair_addr)?;
+
+    match fees {
+        Some(fees) => {
+            // validate total and maker fee bps
+            if !fees.valid_fee_bps() {
+                return Err(ContractError::PairConfigInvalidFeeBps {});
+            }
+            PAIR_FEES.save(deps.storage, &pair_addr, &fees)?;
+        }
+        None => PAIR_FEES.remove(deps.storage, &pair_addr),
+    }
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "update_pair_fees"),
+        attr("pair_contract_addr", pair_addr),
+    ]))
+}
+
+/// ## Description
+/// Returns a [`ContractError`] if the specified pair isn't registered in the factory.
+///
+/// ## Params
+/// * **deps** is the object of type [`Deps`].
+///
+/// * **pair_addr** is the object of type [`Addr`]. This is the pair to check.
+fn assert_pair_registered(deps: Deps, pair_addr: &Addr) -> Result<(), ContractError> {
+    let pair_info = query_pair_info(deps, pair_addr)?;
+    let pair_key = pair_key(&pair_info.asset_infos);
+    let pair_type = pair_info.pair_type.to_string();
+    if PAIRS
+        .may_load(deps.storage, (&pair_key, &pair_type))?
+        .as_ref()
+        != Some(pair_addr)
+    {
+        return Err(ContractError::PairNotFound {});
+    }
+
+    Ok(())
+}
+
+/// ## Description
+/// Pauses or unpauses the specified pair, or all the pairs if `pair_addr` isn't set.
+/// Returns an [`ContractError`] on failure.
+///
+/// ## Params
+/// * **deps** is the object of type [`DepsMut`].
+///
+/// * **info** is the object of type [`MessageInfo`]
+///
+/// * **pair_addr** is an [`Option`] field of type [`String`]. This is the pair to pause or unpause.
+///
+/// * **paused** is the object of type [`bool`]. Whether the pair is paused.
+///
+/// ## Executor
+/// Only owner can execute it. The guardian can pause pairs but not unpause them
+pub fn execute_update_pause_status(
+    deps: DepsMut,
+    info: MessageInfo,
+    pair_addr: Option<String>,
+    paused: bool,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    let is_guardian = config.guardian.as_ref() == Some(&info.sender);
+    if info.sender != config.owner && !(paused && is_guardian) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut attrs = vec![
+        attr("action", "update_pause_status"),
+        attr("paused", paused.to_string()),
+    ];
+
+    match pair_addr {
+        Some(pair_addr) => {
+            let pair_addr = addr_validate_to_lower(deps.api, &pair_addr)?;
+            assert_pair_registered(deps.as_ref(), &pair_addr)?;
+
+            if paused {
+                PAUSED_PAIRS.save(deps.storage, &pair_addr, &())?;
+            } else {
+                PAUSED_PAIRS.remove(deps.storage, &pair_addr);
+            }
+            attrs.push(attr("pair_contract_addr", pair_addr));
+        }
+        None => PAUSED.save(deps.storage, &paused)?,
+    }
+
+    Ok(Response::new().add_attributes(attrs))
+}
+
+/// ## Description
+/// Sets the decimals of a native token if `decimals` is set, or removes them otherwise.
+/// Returns an [`ContractError`] on failure.
+///
+/// ## Params
+/// * **deps** is the object of type [`DepsMut`].
+///
+/// * **info** is the object of type [`MessageInfo`]
+///
+/// * **denom** is the object of type [`String`]. This is the native token for which we set the decimals.
+///
+/// * **decimals** is an [`Option`] field of type [`u8`]. These are the new decimals of the token.
+///
+/// ## Executor
+/// Only owner can execute it
+pub fn execute_update_native_token_decimals(
+    deps: DepsMut,
+    info: MessageInfo,
+    denom: String,
+    decimals: Option<u8>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    validate_native_denom(deps.api, &denom)?;
+
+    match decimals {
+        Some(decimals) => {
+            if decimals > MAX_NATIVE_TOKEN_DECIMALS {
+                return Err(ContractError::InvalidNativeTokenDecimals(
+                    MAX_NATIVE_TOKEN_DECIMALS,
+                ));
+            }
+            NATIVE_TOKEN_DECIMALS.save(deps.storage, &denom, &decimals)?;
+        }
+        None => NATIVE_TOKEN_DECIMALS.remove(deps.storage, &denom),
+    }
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "update_native_token_decimals"),
+        attr("denom", denom),
+    ]))
+}
+
 /// ## Description
 /// Creates a new pair with the specified parameters in the `asset_infos` variable. Returns an [`ContractError`] on failure or
 /// returns the address of the contract if the creation was successful.
@@ -307,30 +584,49 @@ pub fn execute_update_pair_config(
 ///
 /// * **env** is the object of type [`Env`].
 ///
+/// * **info** is the object of type [`MessageInfo`].
+///
 /// * **pair_type** is the object of type [`PairType`].
 ///
-/// * **asset_infos** is an array with two items the type of [`AssetInfo`].
+/// * **asset_infos** is a vector with items of type [`AssetInfo`].
 ///
 /// * **init_params** is an [`Option`] type. Receive a binary data.
+///
+/// * **initial_liquidity** is an [`Option`] field of type [`InitialLiquidity`]. This is the liquidity
+/// provided to the pair once it is instantiated.
+///
+/// ## Executor
+/// Anyone can create pairs of permissionless types by paying the creation fee.
+/// Pairs of other types can only be created by the owner, who doesn't pay the fee
 pub fn execute_create_pair(
     deps: DepsMut,
     env: Env,
+    info: MessageInfo,
     pair_type: PairType,
-    asset_infos: [AssetInfo; 2],
+    asset_infos: Vec<AssetInfo>,
     init_params: Option<Binary>,
+    initial_liquidity: Option<InitialLiquidity>,
 ) -> Result<Response, ContractError> {
-    asset_infos[0].check(deps.api)?;
-    asset_infos[1].check(deps.api)?;
+    for (i, asset_info) in asset_infos.iter().enumerate() {
+        asset_info.check(deps.api)?;
 
-    if asset_infos[0] == asset_infos[1] {
-        return Err(ContractError::DoublingAssets {});
+        if asset_infos[i + 1..].contains(asset_info) {
+            return Err(ContractError::DoublingAssets {});
+        }
     }
 
     let config = CONFIG.load(deps.storage)?;
 
+    // The pairs that weren't moved by MigratePairKeys yet may have the same type
     if PAIRS
-        .may_load(deps.storage, &pair_key(&asset_infos))?
+        .may_load(
+            deps.storage,
+            (&pair_key(&asset_infos), &pair_type.to_string()),
+        )?
         .is_some()
+        || migration::PAIRSV110
+            .may_load(deps.storage, &pair_key(&asset_infos))?
+            .is_some()
     {
         return Err(ContractError::PairWasCreated {});
     }
@@ -345,13 +641,74 @@ pub fn execute_create_pair(
         return Err(ContractError::PairConfigDisabled {});
     }
 
+    // Only the owner can create pairs of restricted types
+    if !pair_config.permissionless && info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // The creation fee is forwarded to the Maker
+    let mut messages: Vec<CosmosMsg> = vec![];
+    let mut native_assets: Vec<Asset> = vec![];
+    let mut native_fee = None;
+    if let Some(creation_fee) = pair_config.creation_fee {
+        if info.sender != config.owner {
+            let fee_address = config
+                .fee_address
+                .clone()
+                .ok_or(ContractError::FeeAddressNotSet {})?;
+
+            match &creation_fee.info {
+                AssetInfo::NativeToken { .. } => {
+                    native_assets.push(creation_fee.clone());
+                    native_fee = Some((creation_fee, fee_address));
+                }
+                AssetInfo::Token { contract_addr } => messages.push(transfer_from_msg(
+                    contract_addr,
+                    &info.sender,
+                    &fee_address,
+                    creation_fee.amount,
+                )?),
+            }
+        }
+    }
+
+    // The initial liquidity is held by the factory until the pair is instantiated
+    if let Some(initial_liquidity) = &initial_liquidity {
+        for asset in initial_liquidity.assets.iter() {
+            match &asset.info {
+                AssetInfo::NativeToken { .. } => native_assets.push(asset.clone()),
+                AssetInfo::Token { contract_addr } => messages.push(transfer_from_msg(
+                    contract_addr,
+                    &info.sender,
+                    &env.contract.address,
+                    asset.amount,
+                )?),
+            }
+        }
+    }
+
+    assert_sent_native_assets(&info, native_assets)?;
+    if let Some((creation_fee, fee_address)) = native_fee {
+        messages.push(creation_fee.into_msg(&deps.querier, fee_address)?);
+    }
+
     let pair_key = pair_key(&asset_infos);
-    TMP_PAIR_INFO.save(deps.storage, &TmpPairInfo { pair_key })?;
+    TMP_PAIR_INFO.save(
+        deps.storage,
+        &TmpPairInfo {
+            pair_key,
+            asset_infos: asset_infos.clone(),
+            pair_type: pair_type.clone(),
+            code_id: pair_config.code_id,
+            initial_liquidity,
+        },
+    )?;
 
     let sub_msg: Vec<SubMsg> = vec![SubMsg {
         id: INSTANTIATE_PAIR_REPLY_ID,
         msg: WasmMsg::Instantiate {
-            admin: Some(config.owner.to_string()),
+            // the factory is the admin so it can migrate the pairs with MigratePairs
+            admin: Some(env.contract.address.to_string()),
             code_id: pair_config.code_id,
             msg: to_binary(&PairInstantiateMsg {
                 asset_infos: asset_infos.clone(),
@@ -368,13 +725,144 @@ pub fn execute_create_pair(
     }];
 
     Ok(Response::new()
+        .add_messages(messages)
         .add_submessages(sub_msg)
         .add_attributes(vec![
             attr("action", "create_pair"),
-            attr("pair", format!("{}-{}", asset_infos[0], asset_infos[1])),
+            attr(
+                "pair",
+                asset_infos
+                    .iter()
+                    .map(|asset_info| asset_info.to_string())
+                    .collect::<Vec<String>>()
+                    .join("-"),
+            ),
         ]))
 }
 
+/// ## Description
+/// Creates a new pair with the assets specified in the `assets` variable and provides them as the
+/// initial liquidity of the pair once it is instantiated, so nobody can seed the pool before.
+/// Returns an [`ContractError`] on failure.
+///
+/// ## Params
+/// * **deps** is the object of type [`DepsMut`].
+///
+/// * **env** is the object of type [`Env`].
+///
+/// * **info** is the object of type [`MessageInfo`].
+///
+/// * **pair_type** is the object of type [`PairType`].
+///
+/// * **assets** is a vector with items of type [`Asset`]. These are the pool assets and their initial amounts.
+///
+/// * **init_params** is an [`Option`] type. Receive a binary data.
+///
+/// * **slippage_tolerance** is an [`Option`] field of type [`Decimal`]. Sets the slippage tolerance of the provision.
+///
+/// * **auto_stake** is an [`Option`] field of type [`bool`]. Determines whether the LP tokens are auto_staked.
+///
+/// * **receiver** is an [`Option`] field of type [`String`]. Sets the receiver of the LP tokens.
+#[allow(clippy::too_many_arguments)]
+pub fn execute_create_pair_and_provide(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    pair_type: PairType,
+    assets: Vec<Asset>,
+    init_params: Option<Binary>,
+    slippage_tolerance: Option<Decimal>,
+    auto_stake: Option<bool>,
+    receiver: Option<String>,
+) -> Result<Response, ContractError> {
+    let receiver = match receiver {
+        Some(receiver) => addr_validate_to_lower(deps.api, &receiver)?,
+        None => info.sender.clone(),
+    };
+    let asset_infos = assets.iter().map(|asset| asset.info.clone()).collect();
+
+    execute_create_pair(
+        deps,
+        env,
+        info,
+        pair_type,
+        asset_infos,
+        init_params,
+        Some(InitialLiquidity {
+            assets,
+            slippage_tolerance,
+            auto_stake,
+            receiver,
+        }),
+    )
+}
+
+/// ## Description
+/// Returns a message that transfers CW20 tokens approved by the `owner` to the `recipient`.
+/// ## Params
+/// * **contract_addr** is the object of type [`Addr`]. This is the token contract.
+///
+/// * **owner** is the object of type [`Addr`]. This is the owner of the tokens.
+///
+/// * **recipient** is the object of type [`Addr`]. This is the recipient of the tokens.
+///
+/// * **amount** is the object of type [`Uint128`]. This is the amount of tokens to transfer.
+fn transfer_from_msg(
+    contract_addr: &Addr,
+    owner: &Addr,
+    recipient: &Addr,
+    amount: Uint128,
+) -> StdResult<CosmosMsg> {
+    Ok(WasmMsg::Execute {
+        contract_addr: contract_addr.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
+            owner: owner.to_string(),
+            recipient: recipient.to_string(),
+            amount,
+        })?,
+        funds: vec![],
+    }
+    .into())
+}
+
+/// ## Description
+/// Checks that exactly the native assets were sent along with the message. The amounts of the
+/// assets with the same denom are added up before they are compared with the sent coins.
+/// Coins of other denoms would be stuck in the factory, so they are rejected.
+/// ## Params
+/// * **info** is the object of type [`MessageInfo`].
+///
+/// * **assets** is a vector with items of type [`Asset`]. These are the native assets to check.
+fn assert_sent_native_assets(info: &MessageInfo, assets: Vec<Asset>) -> Result<(), ContractError> {
+    let mut total_assets: Vec<Asset> = vec![];
+    for asset in assets {
+        match total_assets
+            .iter_mut()
+            .find(|total| total.info.equal(&asset.info))
+        {
+            Some(total) => total.amount = total.amount.checked_add(asset.amount)?,
+            None => total_assets.push(asset),
+        }
+    }
+
+    for coin in info.funds.iter() {
+        let expected = total_assets.iter().any(|asset| {
+            asset.info.equal(&AssetInfo::NativeToken {
+                denom: coin.denom.clone(),
+            })
+        });
+        if !expected && !coin.amount.is_zero() {
+            return Err(ContractError::UnexpectedFunds {});
+        }
+    }
+
+    total_assets
+        .iter()
+        .try_for_each(|asset| asset.assert_sent_native_token_balance(info))?;
+
+    Ok(())
+}
+
 /// # Description
 /// The entry point to the contract for processing the reply from the submessage
 /// # Params
@@ -386,7 +874,11 @@ pub fn execute_create_pair(
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
     let tmp = TMP_PAIR_INFO.load(deps.storage)?;
-    if PAIRS.may_load(deps.storage, &tmp.pair_key)?.is_some() {
+    let pair_type = tmp.pair_type.to_string();
+    if PAIRS
+        .may_load(deps.storage, (&tmp.pair_key, &pair_type))?
+        .is_some()
+    {
         return Err(ContractError::PairWasRegistered {});
     }
 
@@ -398,16 +890,76 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractE
 
     let pair_contract = addr_validate_to_lower(deps.api, res.get_contract_address())?;
 
-    PAIRS.save(deps.storage, &tmp.pair_key, &pair_contract)?;
+    PAIRS.save(deps.storage, (&tmp.pair_key, &pair_type), &pair_contract)?;
+    PAIR_CODE_IDS.save(deps.storage, &pair_contract, &tmp.code_id)?;
+    index_pair(
+        deps.storage,
+        &pair_contract,
+        &tmp.asset_infos,
+        &tmp.pair_type,
+    )?;
+
+    // Provide the initial liquidity of pairs created with CreatePairAndProvide
+    let mut messages: Vec<CosmosMsg> = vec![];
+    if let Some(initial_liquidity) = tmp.initial_liquidity {
+        let mut assets = vec![];
+        let mut funds = vec![];
+        for asset in initial_liquidity.assets {
+            match &asset.info {
+                AssetInfo::NativeToken { .. } => {
+                    // The factory pays the tax on the transfer
+                    let coin = asset.deduct_tax(&deps.querier)?;
+                    assets.push(Asset {
+                        info: asset.info,
+                        amount: coin.amount,
+                    });
+                    funds.push(coin);
+                }
+                AssetInfo::Token { contract_addr } => {
+                    messages.push(
+                        WasmMsg::Execute {
+                            contract_addr: contract_addr.to_string(),
+                            msg: to_binary(&Cw20ExecuteMsg::IncreaseAllowance {
+                                spender: pair_contract.to_string(),
+                                amount: asset.amount,
+                                expires: None,
+                            })?,
+                            funds: vec![],
+                        }
+                        .into(),
+                    );
+                    assets.push(asset);
+                }
+            }
+        }
+        funds.sort_by(|a, b| a.denom.cmp(&b.denom));
+
+        messages.push(
+            WasmMsg::Execute {
+                contract_addr: pair_contract.to_string(),
+                msg: to_binary(&PairExecuteMsg::ProvideLiquidity {
+                    assets,
+                    slippage_tolerance: initial_liquidity.slippage_tolerance,
+                    auto_stake: initial_liquidity.auto_stake,
+                    receiver: Some(initial_liquidity.receiver.to_string()),
+                    min_lp_to_receive: None,
+                    deadline: None,
+                })?,
+                funds,
+            }
+            .into(),
+        );
+    }
 
-    Ok(Response::new().add_attributes(vec![
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
         attr("action", "register"),
         attr("pair_contract_addr", pair_contract),
     ]))
 }
 
 /// ## Description
-/// Removes a exists pair with the specified parameters in the `asset_infos` variable.
+/// Removes a exists pair with the specified parameters in the `asset_infos` and `pair_type` variables.
+/// The pair is left paused so it only accepts withdrawals, and its pool in the generator is deactivated.
 /// Returns an [`ContractError`] on failure or returns the [`Response`] with the specified attributes
 /// if the operation was successful.
 ///
@@ -416,17 +968,22 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractE
 ///
 /// * **info** is the object of type [`MessageInfo`].
 ///
-/// * **asset_infos** are an array with two items the type of [`AssetInfo`].
+/// * **asset_infos** is a vector with items of type [`AssetInfo`].
+///
+/// * **pair_type** is an [`Option`] field of type [`PairType`]. If it isn't specified,
+/// the first pair of the assets ordered by type is removed.
 ///
 /// ## Executor
 /// Only owner can execute it
 pub fn deregister(
     deps: DepsMut,
     info: MessageInfo,
-    asset_infos: [AssetInfo; 2],
+    asset_infos: Vec<AssetInfo>,
+    pair_type: Option<PairType>,
 ) -> Result<Response, ContractError> {
-    asset_infos[0].check(deps.api)?;
-    asset_infos[1].check(deps.api)?;
+    for asset_info in &asset_infos {
+        asset_info.check(deps.api)?;
+    }
 
     let config = CONFIG.load(deps.storage)?;
 
@@ -434,15 +991,187 @@ pub fn deregister(
         return Err(ContractError::Unauthorized {});
     }
 
-    let pair_addr: Addr = PAIRS.load(deps.storage, &pair_key(&asset_infos))?;
-    PAIRS.remove(deps.storage, &pair_key(&asset_infos));
+    let (pair_type, pair_addr) = load_pair(deps.storage, &asset_infos, pair_type)?;
+    PAIRS.remove(deps.storage, (&pair_key(&asset_infos), &pair_type));
+    unindex_pair(deps.storage, &pair_addr, &asset_infos)?;
+    PAIR_FEES.remove(deps.storage, &pair_addr);
+    PAIR_CODE_IDS.remove(deps.storage, &pair_addr);
+    // The pair stays paused forever, so LPs can only withdraw their liquidity
+    PAUSED_PAIRS.save(deps.storage, &pair_addr, &())?;
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    if let Some(generator) = config.generator_address {
+        let pair_info = query_pair_info(deps.as_ref(), &pair_addr)?;
+        // Pairs without an LP token have no generator pool to deactivate
+        if !pair_info.liquidity_token.as_str().is_empty() {
+            messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: generator.to_string(),
+                msg: to_binary(&GeneratorExecuteMsg::DeactivatePool {
+                    lp_token: pair_info.liquidity_token.to_string(),
+                })?,
+                funds: vec![],
+            }));
+        }
+    }
 
-    Ok(Response::new().add_attributes(vec![
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
         attr("action", "deregister"),
         attr("pair_contract_addr", pair_addr),
     ]))
 }
 
+/// ## Description
+/// Migrates a page of the registered pairs of the specified type to a new code ID and records the
+/// code ID the pairs run. Returns an [`ContractError`] on failure.
+///
+/// ## Params
+/// * **deps** is the object of type [`DepsMut`].
+///
+/// * **env** is the object of type [`Env`].
+///
+/// * **info** is the object of type [`MessageInfo`].
+///
+/// * **code_id** is the object of type [`u64`]. This is the code ID to migrate the pairs to.
+///
+/// * **pair_type** is the object of type [`PairType`]. This is the type of the pairs to migrate.
+///
+/// * **start_after** is an [`Option`] field of type [`String`]. This is the pair address to start migrating after.
+///
+/// * **limit** is a [`Option`] type. Sets the number of pairs to migrate.
+///
+/// * **msg** is the object of type [`Binary`]. This is the migrate message sent to every pair.
+///
+/// ## Executor
+/// Only owner can execute it
+#[allow(clippy::too_many_arguments)]
+pub fn execute_migrate_pairs(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    code_id: u64,
+    pair_type: PairType,
+    start_after: Option<String>,
+    limit: Option<u32>,
+    msg: Binary,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let start_after = start_after
+        .map(|addr| addr_validate_to_lower(deps.api, &addr))
+        .transpose()?;
+    let pairs = read_pairs_by_type(deps.as_ref(), &pair_type, start_after, limit)?;
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    let mut migrated: Vec<Addr> = vec![];
+    let mut skipped: Vec<Addr> = vec![];
+    for pair_addr in pairs {
+        // The pairs created before the factory became their admin can't be migrated by it
+        if !is_pair_admin(deps.as_ref(), &env, &pair_addr)? {
+            skipped.push(pair_addr);
+            continue;
+        }
+
+        PAIR_CODE_IDS.save(deps.storage, &pair_addr, &code_id)?;
+        messages.push(
+            WasmMsg::Migrate {
+                contract_addr: pair_addr.to_string(),
+                new_code_id: code_id,
+                msg: msg.clone(),
+            }
+            .into(),
+        );
+        migrated.push(pair_addr);
+    }
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        attr("action", "migrate_pairs"),
+        attr("pair_type", pair_type.to_string()),
+        attr("code_id", code_id.to_string()),
+        attr(
+            "pairs",
+            migrated
+                .iter()
+                .map(|pair_addr| pair_addr.to_string())
+                .collect::<Vec<String>>()
+                .join(","),
+        ),
+        attr(
+            "skipped_pairs",
+            skipped
+                .iter()
+                .map(|pair_addr| pair_addr.to_string())
+                .collect::<Vec<String>>()
+                .join(","),
+        ),
+    ]))
+}
+
+/// ## Description
+/// Returns `true` if the factory is the admin of the pair, so it can migrate the pair. Without the
+/// Terra contract info query, the factory only knows the pairs it instantiated as their admin,
+/// which are the pairs with a recorded code ID.
+/// ## Params
+/// * **deps** is the object of type [`Deps`].
+///
+/// * **env** is the object of type [`Env`].
+///
+/// * **pair_addr** is the object of type [`Addr`].
+fn is_pair_admin(deps: Deps, env: &Env, pair_addr: &Addr) -> StdResult<bool> {
+    #[cfg(feature = "terra")]
+    {
+        let contract_info = query_contract_info(deps, pair_addr)?;
+        Ok(contract_info.admin == Some(env.contract.address.to_string()))
+    }
+    #[cfg(not(feature = "terra"))]
+    {
+        let _ = env;
+        Ok(PAIR_CODE_IDS.may_load(deps.storage, pair_addr)?.is_some())
+    }
+}
+
+/// ## Description
+/// Moves a page of the pairs registered before the pairs were keyed by type. The type of every
+/// pair is read from the pair contract, so the pairs are moved in pages after the contract migration.
+/// Returns an [`ContractError`] on failure.
+/// ## Params
+/// * **deps** is the object of type [`DepsMut`].
+///
+/// * **info** is the object of type [`MessageInfo`].
+///
+/// * **limit** is a [`Option`] type. Sets the number of pairs to move.
+///
+/// ## Executor
+/// Only owner can execute it
+pub fn execute_migrate_pair_keys(
+    deps: DepsMut,
+    info: MessageInfo,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let pairs = migration::migrate_pairs(deps, limit)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "migrate_pair_keys"),
+        attr(
+            "pairs",
+            pairs
+                .iter()
+                .map(|pair_addr| pair_addr.to_string())
+                .collect::<Vec<String>>()
+                .join(","),
+        ),
+    ]))
+}
+
 /// ## Description
 /// Available the query messages of the contract.
 /// ## Params
@@ -455,22 +1184,87 @@ pub fn deregister(
 /// ## Queries
 /// * **QueryMsg::Config {}** Returns controls settings that specified in custom [`ConfigResponse`] structure.
 ///
-/// * **QueryMsg::Pair { asset_infos }** Returns the [`PairInfo`] object with the specified input parameters
+/// * **QueryMsg::Pair { asset_infos, pair_type }** Returns the [`PairInfo`] object with the specified input parameters
+///
+/// * **QueryMsg::PairVariants { asset_infos }** Returns an array that contains items of [`PairInfo`]
+/// of all the pairs with the specified assets.
 ///
-/// * **QueryMsg::Pairs { start_after, limit }** Returns an array that contains items of [`PairInfo`]
+/// * **QueryMsg::Pairs { start_after, start_after_pair_type, limit }** Returns an array that contains items of [`PairInfo`]
 /// according to the specified input parameters.
 ///
-/// * **QueryMsg::FeeInfo { pair_type }** Returns the settings specified in the custom
+/// * **QueryMsg::FeeInfo { pair_type, pair_addr }** Returns the settings specified in the custom
 /// structure [`FeeInfoResponse`].
+///
+/// * **QueryMsg::CreationFee { pair_type }** Returns the creation fee of a pair type in a
+/// [`CreationFeeResponse`] object.
+///
+/// * **QueryMsg::NativeTokenDecimals { denom }** Returns the decimals of a native token in a
+/// [`NativeTokenDecimalsResponse`] object.
+///
+/// * **QueryMsg::PairsByAsset { asset_info, start_after, limit }** Returns an array that contains
+/// items of [`PairInfo`] of the pairs with the specified asset.
+///
+/// * **QueryMsg::PairsByType { pair_type, start_after, limit }** Returns an array that contains
+/// items of [`PairInfo`] of the pairs with the specified type.
+///
+/// * **QueryMsg::PairsCount {}** Returns the number of registered pairs in a [`PairsCountResponse`] object.
+///
+/// * **QueryMsg::PauseStatus { pair_addr }** Returns whether a pair or all the pairs are paused in a
+/// [`PauseStatusResponse`] object.
+///
+/// * **QueryMsg::PairsToMigrate { pair_type, start_after, limit }** Returns the pairs of the specified type
+/// which don't run the code ID of their pair type in a [`PairsToMigrateResponse`] object.
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Config {} => to_binary(&query_config(deps)?),
-        QueryMsg::Pair { asset_infos } => to_binary(&query_pair(deps, asset_infos)?),
-        QueryMsg::Pairs { start_after, limit } => {
-            to_binary(&query_pairs(deps, start_after, limit)?)
+        QueryMsg::Pair {
+            asset_infos,
+            pair_type,
+        } => to_binary(&query_pair(deps, asset_infos, pair_type)?),
+        QueryMsg::PairVariants { asset_infos } => {
+            to_binary(&query_pair_variants(deps, asset_infos)?)
+        }
+        QueryMsg::Pairs {
+            start_after,
+            start_after_pair_type,
+            limit,
+        } => to_binary(&query_pairs(
+            deps,
+            start_after,
+            start_after_pair_type,
+            limit,
+        )?),
+        QueryMsg::FeeInfo {
+            pair_type,
+            pair_addr,
+        } => to_binary(&query_fee_info(deps, pair_type, pair_addr)?),
+        QueryMsg::CreationFee { pair_type } => to_binary(&query_creation_fee(deps, pair_type)?),
+        QueryMsg::NativeTokenDecimals { denom } => {
+            to_binary(&query_native_token_decimals(deps, denom)?)
         }
-        QueryMsg::FeeInfo { pair_type } => to_binary(&query_fee_info(deps, pair_type)?),
+        QueryMsg::PairsByAsset {
+            asset_info,
+            start_after,
+            limit,
+        } => to_binary(&query_pairs_by_asset(deps, asset_info, start_after, limit)?),
+        QueryMsg::PairsByType {
+            pair_type,
+            start_after,
+            limit,
+        } => to_binary(&query_pairs_by_type(deps, pair_type, start_after, limit)?),
+        QueryMsg::PairsCount {} => to_binary(&query_pairs_count(deps)?),
+        QueryMsg::PauseStatus { pair_addr } => to_binary(&query_pause_status(deps, pair_addr)?),
+        QueryMsg::PairsToMigrate {
+            pair_type,
+            start_after,
+            limit,
+        } => to_binary(&query_pairs_to_migrate(
+            deps,
+            pair_type,
+            start_after,
+            limit,
+        )?),
     }
 }
 
@@ -494,36 +1288,62 @@ pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
         fee_address: config.fee_address,
         generator_address: config.generator_address,
         whitelist_code_id: config.whitelist_code_id,
+        guardian: config.guardian,
     };
 
     Ok(resp)
 }
 
 /// ## Description
-/// Returns a pair with the specified parameters in the `asset_infos` variable.
+/// Returns a pair with the specified parameters in the `asset_infos` and `pair_type` variables.
+/// If `pair_type` isn't specified, the first pair of the assets ordered by type is returned.
 /// ## Params
 /// * **deps** is the object of type [`Deps`].
 ///
-/// * **asset_infos** it is array with two items the type of [`AssetInfo`].
-pub fn query_pair(deps: Deps, asset_infos: [AssetInfo; 2]) -> StdResult<PairInfo> {
-    let pair_addr = PAIRS.load(deps.storage, &pair_key(&asset_infos))?;
+/// * **asset_infos** it is a vector with items of type [`AssetInfo`].
+///
+/// * **pair_type** is an [`Option`] field of type [`PairType`].
+pub fn query_pair(
+    deps: Deps,
+    asset_infos: Vec<AssetInfo>,
+    pair_type: Option<PairType>,
+) -> StdResult<PairInfo> {
+    let (_, pair_addr) = load_pair(deps.storage, &asset_infos, pair_type)?;
     query_pair_info(deps, &pair_addr)
 }
 
+/// ## Description
+/// Returns an array that contains items of [`PairInfo`] of all the pairs with the specified assets.
+/// ## Params
+/// * **deps** is the object of type [`Deps`].
+///
+/// * **asset_infos** it is a vector with items of type [`AssetInfo`].
+pub fn query_pair_variants(deps: Deps, asset_infos: Vec<AssetInfo>) -> StdResult<PairsResponse> {
+    let pairs = read_pair_variants(deps.storage, &asset_infos)?
+        .iter()
+        .map(|pair_addr| query_pair_info(deps, pair_addr))
+        .collect::<StdResult<Vec<PairInfo>>>()?;
+
+    Ok(PairsResponse { pairs })
+}
+
 /// ## Description
 /// Returns an array that contains items of [`PairInfo`] according to the specified parameters in `start_after` and `limit` variables.
 /// ## Params
 /// * **deps** is the object of type [`Deps`].
 ///
-/// * **start_after** is an [`Option`] field that accepts an array with two items the type of [`AssetInfo`].
+/// * **start_after** is an [`Option`] field that accepts a vector with items of type [`AssetInfo`].
+///
+/// * **start_after_pair_type** is an [`Option`] field of type [`PairType`].
 ///
 /// * **limit** is a [`Option`] type. Sets the number of items to be read.
 pub fn query_pairs(
     deps: Deps,
-    start_after: Option<[AssetInfo; 2]>,
+    start_after: Option<Vec<AssetInfo>>,
+    start_after_pair_type: Option<PairType>,
     limit: Option<u32>,
 ) -> StdResult<PairsResponse> {
-    let pairs: Vec<PairInfo> = read_pairs(deps, start_after, limit)
+    let pairs: Vec<PairInfo> = read_pairs(deps, start_after, start_after_pair_type, limit)
         .iter()
         .map(|pair_addr| query_pair_info(deps, pair_addr).unwrap())
         .collect();
@@ -533,21 +1353,192 @@ pub fn query_pairs(
 
 /// ## Description
 /// Returns the settings specified in the custom structure [`FeeInfoResponse`] for the specified parameters in the `pair_type` variable.
+/// If `pair_addr` is set and the pair has a fee override, the override is returned instead of the pair type fees.
 /// ## Params
 /// * **deps** is the object of type [`Deps`].
 ///
 /// * **pair_type** is the type of pair available in [`PairType`]
-pub fn query_fee_info(deps: Deps, pair_type: PairType) -> StdResult<FeeInfoResponse> {
+///
+/// * **pair_addr** is an [`Option`] field of type [`String`]. This is the pair for which we return the fees.
+pub fn query_fee_info(
+    deps: Deps,
+    pair_type: PairType,
+    pair_addr: Option<String>,
+) -> StdResult<FeeInfoResponse> {
     let config = CONFIG.load(deps.storage)?;
-    let pair_config = PAIR_CONFIGS.load(deps.storage, pair_type.to_string())?;
+
+    let pair_fees = match pair_addr {
+        Some(pair_addr) => {
+            PAIR_FEES.may_load(deps.storage, &addr_validate_to_lower(deps.api, &pair_addr)?)?
+        }
+        None => None,
+    };
+
+    let (total_fee_bps, maker_fee_bps) = match pair_fees {
+        Some(fees) => (fees.total_fee_bps, fees.maker_fee_bps),
+        None => {
+            let pair_config = PAIR_CONFIGS.load(deps.storage, pair_type.to_string())?;
+            (pair_config.total_fee_bps, pair_config.maker_fee_bps)
+        }
+    };
 
     Ok(FeeInfoResponse {
         fee_address: config.fee_address,
-        total_fee_bps: pair_config.total_fee_bps,
-        maker_fee_bps: pair_config.maker_fee_bps,
+        total_fee_bps,
+        maker_fee_bps,
+    })
+}
+
+/// ## Description
+/// Returns the creation fee of a pair type in a [`CreationFeeResponse`] object.
+/// ## Params
+/// * **deps** is the object of type [`Deps`].
+///
+/// * **pair_type** is the type of pair available in [`PairType`]
+pub fn query_creation_fee(deps: Deps, pair_type: PairType) -> StdResult<CreationFeeResponse> {
+    let pair_config = PAIR_CONFIGS.load(deps.storage, pair_type.to_string())?;
+
+    Ok(CreationFeeResponse {
+        permissionless: pair_config.permissionless,
+        creation_fee: pair_config.creation_fee,
     })
 }
 
+/// ## Description
+/// Returns the decimals of a native token in a [`NativeTokenDecimalsResponse`] object.
+/// Tokens which aren't registered have the default precision of native tokens.
+/// ## Params
+/// * **deps** is the object of type [`Deps`].
+///
+/// * **denom** is the object of type [`String`]. This is the native token for which we return the decimals.
+pub fn query_native_token_decimals(
+    deps: Deps,
+    denom: String,
+) -> StdResult<NativeTokenDecimalsResponse> {
+    let decimals = NATIVE_TOKEN_DECIMALS
+        .may_load(deps.storage, &denom)?
+        .unwrap_or(NATIVE_TOKEN_PRECISION);
+
+    Ok(NativeTokenDecimalsResponse { decimals })
+}
+
+/// ## Description
+/// Returns an array that contains items of [`PairInfo`] of the pairs with the specified asset.
+/// ## Params
+/// * **deps** is the object of type [`Deps`].
+///
+/// * **asset_info** is the object of type [`AssetInfo`]. This is the asset for which we return the pairs.
+///
+/// * **start_after** is an [`Option`] field of type [`String`]. This is the pair address to start reading after.
+///
+/// * **limit** is a [`Option`] type. Sets the number of items to be read.
+pub fn query_pairs_by_asset(
+    deps: Deps,
+    asset_info: AssetInfo,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<PairsResponse> {
+    let start_after = start_after
+        .map(|addr| addr_validate_to_lower(deps.api, &addr))
+        .transpose()?;
+
+    let pairs = read_pairs_by_asset(deps, &asset_info, start_after, limit)?
+        .iter()
+        .map(|pair_addr| query_pair_info(deps, pair_addr))
+        .collect::<StdResult<Vec<PairInfo>>>()?;
+
+    Ok(PairsResponse { pairs })
+}
+
+/// ## Description
+/// Returns an array that contains items of [`PairInfo`] of the pairs with the specified type.
+/// ## Params
+/// * **deps** is the object of type [`Deps`].
+///
+/// * **pair_type** is the type of pair available in [`PairType`]
+///
+/// * **start_after** is an [`Option`] field of type [`String`]. This is the pair address to start reading after.
+///
+/// * **limit** is a [`Option`] type. Sets the number of items to be read.
+pub fn query_pairs_by_type(
+    deps: Deps,
+    pair_type: PairType,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<PairsResponse> {
+    let start_after = start_after
+        .map(|addr| addr_validate_to_lower(deps.api, &addr))
+        .transpose()?;
+
+    let pairs = read_pairs_by_type(deps, &pair_type, start_after, limit)?
+        .iter()
+        .map(|pair_addr| query_pair_info(deps, pair_addr))
+        .collect::<StdResult<Vec<PairInfo>>>()?;
+
+    Ok(PairsResponse { pairs })
+}
+
+/// ## Description
+/// Returns the number of registered pairs in a [`PairsCountResponse`] object.
+/// ## Params
+/// * **deps** is the object of type [`Deps`].
+pub fn query_pairs_count(deps: Deps) -> StdResult<PairsCountResponse> {
+    let count = PAIRS_COUNT.may_load(deps.storage)?.unwrap_or_default();
+
+    Ok(PairsCountResponse { count })
+}
+
+/// ## Description
+/// Returns whether the specified pair is paused in a [`PauseStatusResponse`] object. A pair is paused
+/// if it is paused by itself or if all the pairs are paused.
+/// ## Params
+/// * **deps** is the object of type [`Deps`].
+///
+/// * **pair_addr** is an [`Option`] field of type [`String`]. If it isn't set, returns whether all the pairs are paused.
+pub fn query_pause_status(deps: Deps, pair_addr: Option<String>) -> StdResult<PauseStatusResponse> {
+    let mut paused = PAUSED.may_load(deps.storage)?.unwrap_or_default();
+
+    if let Some(pair_addr) = pair_addr {
+        let pair_addr = addr_validate_to_lower(deps.api, &pair_addr)?;
+        paused = paused || PAUSED_PAIRS.may_load(deps.storage, &pair_addr)?.is_some();
+    }
+
+    Ok(PauseStatusResponse { paused })
+}
+
+/// ## Description
+/// Returns the pairs of the specified type which don't run the code ID of their pair type in a
+/// [`PairsToMigrateResponse`] object. Pairs with an unknown code ID are always returned.
+/// ## Params
+/// * **deps** is the object of type [`Deps`].
+///
+/// * **pair_type** is the type of pair available in [`PairType`]
+///
+/// * **start_after** is an [`Option`] field of type [`String`]. This is the pair address to start reading after.
+///
+/// * **limit** is a [`Option`] type. Sets the number of items to be read.
+pub fn query_pairs_to_migrate(
+    deps: Deps,
+    pair_type: PairType,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<PairsToMigrateResponse> {
+    let pair_config = PAIR_CONFIGS.load(deps.storage, pair_type.to_string())?;
+    let start_after = start_after
+        .map(|addr| addr_validate_to_lower(deps.api, &addr))
+        .transpose()?;
+
+    let pairs = read_pairs_to_migrate(deps, &pair_type, pair_config.code_id, start_after, limit)?
+        .into_iter()
+        .map(|(contract_addr, code_id)| PairCodeId {
+            contract_addr,
+            code_id,
+        })
+        .collect();
+
+    Ok(PairsToMigrateResponse { pairs })
+}
+
 /// ## Description
 /// Used for migration of contract. Returns the default object of type [`Response`].
 /// ## Params
@@ -571,9 +1562,14 @@ pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, Co
                     generator_address: config_v100.generator_address,
                     owner: config_v100.owner,
                     token_code_id: config_v100.token_code_id,
+                    guardian: None,
                 };
 
                 CONFIG.save(deps.storage, &new_config)?;
+                migration::migrate_pair_configs(deps.storage)?;
+            }
+            "1.1.0" => {
+                migration::migrate_pair_configs(deps.storage)?;
             }
             _ => return Err(ContractError::MigrationError {}),
         },
@@ -23,15 +23,30 @@ pub enum ContractError {
     #[error("Fee bps in pair config must be smaller than or equal to 10,000")]
     PairConfigInvalidFeeBps {},
 
+    #[error("Native token decimals must be smaller than or equal to {0}")]
+    InvalidNativeTokenDecimals(u8),
+
+    #[error("Pair not found")]
+    PairNotFound {},
+
     #[error("Pair config not found")]
     PairConfigNotFound {},
 
     #[error("Pair config disabled")]
     PairConfigDisabled {},
 
+    #[error("Creation fee must be greater than zero")]
+    InvalidCreationFee {},
+
+    #[error("Creation fee can't be collected as the fee address is not set")]
+    FeeAddressNotSet {},
+
     #[error("Doubling assets in asset infos")]
     DoublingAssets {},
 
+    #[error("Native tokens were sent in addition to the creation fee and the initial liquidity")]
+    UnexpectedFunds {},
+
     #[error("Contract can't be migrated!")]
     MigrationError {},
 }
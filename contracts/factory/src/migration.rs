@@ -1,5 +1,12 @@
-use cosmwasm_std::Addr;
-use cw_storage_plus::Item;
+#[cfg(feature = "terra")]
+use crate::querier::query_contract_info;
+use crate::querier::query_pair_info;
+#[cfg(feature = "terra")]
+use crate::state::PAIR_CODE_IDS;
+use crate::state::{index_pair, PAIRS, PAIR_CONFIGS};
+use astroport::factory::PairConfig;
+use cosmwasm_std::{Addr, DepsMut, Order, StdResult, Storage};
+use cw_storage_plus::{Item, Map};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -17,3 +24,75 @@ pub struct ConfigV100 {
 }
 
 pub const CONFIGV100: Item<ConfigV100> = Item::new("config");
+
+/// Saves the pairs created before they were keyed by their type
+pub const PAIRSV110: Map<&[u8], Addr> = Map::new("pair_info");
+
+/// ## Description
+/// Makes the stored pair types permissionless. Anyone could create pairs before the pair configs
+/// got the `permissionless` flag, so the stored configs keep that behaviour.
+/// ## Params
+/// * **storage** is the object of type [`Storage`].
+pub fn migrate_pair_configs(storage: &mut dyn Storage) -> StdResult<()> {
+    let pair_configs = PAIR_CONFIGS
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<(Vec<u8>, PairConfig)>>>()?;
+
+    for (_, mut pair_config) in pair_configs {
+        pair_config.permissionless = true;
+        PAIR_CONFIGS.save(storage, pair_config.pair_type.to_string(), &pair_config)?;
+    }
+
+    Ok(())
+}
+
+/// The maximum number of pairs moved at once. Every pair is queried for its type
+const MAX_LIMIT: u32 = 30;
+
+/// The default number of pairs moved at once
+const DEFAULT_LIMIT: u32 = 10;
+
+/// ## Description
+/// Moves a page of the pairs from [`PAIRSV110`] to the [`PAIRS`] keyed by their assets and type
+/// and fills the pair indexes and the pair count. On Terra the code IDs of the pairs are recorded
+/// from their contract info. Returns the addresses of the moved pairs.
+/// ## Params
+/// * **deps** is the object of type [`DepsMut`].
+///
+/// * **limit** is a [`Option`] type. Sets the number of pairs to move.
+pub fn migrate_pairs(deps: DepsMut, limit: Option<u32>) -> StdResult<Vec<Addr>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let pairs = PAIRSV110
+        .range(deps.storage, None, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<(Vec<u8>, Addr)>>>()?;
+
+    let mut migrated = vec![];
+    for (pair_key, pair_addr) in pairs {
+        let pair_info = query_pair_info(deps.as_ref(), &pair_addr)?;
+
+        PAIRSV110.remove(deps.storage, &pair_key);
+        PAIRS.save(
+            deps.storage,
+            (&pair_key, &pair_info.pair_type.to_string()),
+            &pair_addr,
+        )?;
+        index_pair(
+            deps.storage,
+            &pair_addr,
+            &pair_info.asset_infos,
+            &pair_info.pair_type,
+        )?;
+
+        // The pairs registered before weren't instantiated with their code ID recorded
+        #[cfg(feature = "terra")]
+        {
+            let contract_info = query_contract_info(deps.as_ref(), &pair_addr)?;
+            PAIR_CODE_IDS.save(deps.storage, &pair_addr, &contract_info.code_id)?;
+        }
+
+        migrated.push(pair_addr);
+    }
+
+    Ok(migrated)
+}
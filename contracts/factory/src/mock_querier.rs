@@ -2,10 +2,17 @@ use astroport::asset::PairInfo;
 use astroport::pair::QueryMsg;
 use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR};
 use cosmwasm_std::{
-    from_binary, from_slice, to_binary, Coin, Empty, OwnedDeps, Querier, QuerierResult,
-    QueryRequest, SystemError, SystemResult, WasmQuery,
+    from_binary, from_slice, to_binary, Coin, OwnedDeps, Querier, QuerierResult, QueryRequest,
+    SystemError, SystemResult, WasmQuery,
 };
 use std::collections::HashMap;
+#[cfg(feature = "terra")]
+use terra_cosmwasm::{ContractInfoResponse, TerraQuery, TerraQueryWrapper, TerraRoute};
+
+#[cfg(feature = "terra")]
+type CustomQuery = TerraQueryWrapper;
+#[cfg(not(feature = "terra"))]
+type CustomQuery = cosmwasm_std::Empty;
 
 /// mock_dependencies is a drop-in replacement for cosmwasm_std::testing::mock_dependencies
 /// this uses our CustomQuerier.
@@ -23,8 +30,11 @@ pub fn mock_dependencies(
 }
 
 pub struct WasmMockQuerier {
-    base: MockQuerier<Empty>,
+    base: MockQuerier<CustomQuery>,
     astroport_pair_querier: AstroportPairQuerier,
+    // the code ID and the admin of every contract
+    #[cfg_attr(not(feature = "terra"), allow(dead_code))]
+    contract_infos: HashMap<String, (u64, Option<String>)>,
 }
 
 #[derive(Clone, Default)]
@@ -51,7 +61,7 @@ pub(crate) fn pairs_to_map(pairs: &[(&String, &PairInfo)]) -> HashMap<String, Pa
 impl Querier for WasmMockQuerier {
     fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
         // MockQuerier doesn't support Custom, so we ignore it completely here
-        let request: QueryRequest<Empty> = match from_slice(bin_request) {
+        let request: QueryRequest<CustomQuery> = match from_slice(bin_request) {
             Ok(v) => v,
             Err(e) => {
                 return SystemResult::Err(SystemError::InvalidRequest {
@@ -65,8 +75,26 @@ impl Querier for WasmMockQuerier {
 }
 
 impl WasmMockQuerier {
-    pub fn handle_query(&self, request: &QueryRequest<Empty>) -> QuerierResult {
+    pub fn handle_query(&self, request: &QueryRequest<CustomQuery>) -> QuerierResult {
         match &request {
+            #[cfg(feature = "terra")]
+            QueryRequest::Custom(TerraQueryWrapper {
+                route: TerraRoute::Wasm,
+                query_data: TerraQuery::ContractInfo { contract_address },
+            }) => match self.contract_infos.get(contract_address) {
+                Some((code_id, admin)) => SystemResult::Ok(
+                    to_binary(&ContractInfoResponse {
+                        address: contract_address.clone(),
+                        creator: MOCK_CONTRACT_ADDR.to_string(),
+                        code_id: *code_id,
+                        admin: admin.clone(),
+                    })
+                    .into(),
+                ),
+                None => SystemResult::Err(SystemError::NoSuchContract {
+                    addr: contract_address.clone(),
+                }),
+            },
             QueryRequest::Wasm(WasmQuery::Smart {contract_addr, msg})// => {
                 => match from_binary(&msg).unwrap() {
                     QueryMsg::Pair {} => {
@@ -90,10 +118,11 @@ impl WasmMockQuerier {
 }
 
 impl WasmMockQuerier {
-    pub fn new(base: MockQuerier<Empty>) -> Self {
+    pub fn new(base: MockQuerier<CustomQuery>) -> Self {
         WasmMockQuerier {
             base,
             astroport_pair_querier: AstroportPairQuerier::default(),
+            contract_infos: HashMap::new(),
         }
     }
 
@@ -102,6 +131,19 @@ impl WasmMockQuerier {
         self.astroport_pair_querier = AstroportPairQuerier::new(pairs);
     }
 
+    // configure the code IDs and the admins of the contracts
+    pub fn with_contract_infos(&mut self, contract_infos: &[(&str, u64, Option<&str>)]) {
+        self.contract_infos = contract_infos
+            .iter()
+            .map(|(addr, code_id, admin)| {
+                (
+                    addr.to_string(),
+                    (*code_id, admin.map(|admin| admin.to_string())),
+                )
+            })
+            .collect();
+    }
+
     // pub fn with_balance(&mut self, balances: &[(&String, &[Coin])]) {
     //     for (addr, balance) in balances {
     //         self.base.update_balance(addr, balance.to_vec());
@@ -1,6 +1,8 @@
 use astroport::asset::PairInfo;
 use astroport::pair::QueryMsg;
 use cosmwasm_std::{to_binary, Addr, Deps, QueryRequest, StdResult, WasmQuery};
+#[cfg(feature = "terra")]
+use terra_cosmwasm::{ContractInfoResponse, TerraQuerier};
 
 /// ## Description
 /// Returns information about the pair described in the structure [`PairInfo`] according to the specified parameters in the `pair_contract` variable.
@@ -12,3 +14,12 @@ pub fn query_pair_info(deps: Deps, pair_contract: &Addr) -> StdResult<PairInfo>
         msg: to_binary(&QueryMsg::Pair {})?,
     }))
 }
+
+/// ## Description
+/// Returns the code ID and the admin of a contract in a [`ContractInfoResponse`] object.
+/// ## Params
+/// `contract_addr` it is the type of [`Addr`].
+#[cfg(feature = "terra")]
+pub fn query_contract_info(deps: Deps, contract_addr: &Addr) -> StdResult<ContractInfoResponse> {
+    TerraQuerier::new(&deps.querier).query_contract_info(contract_addr.to_string())
+}
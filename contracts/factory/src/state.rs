@@ -1,13 +1,13 @@
-use cw_storage_plus::{Bound, Item, Map};
+use cw_storage_plus::{Bound, Item, Map, PrimaryKey};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{Addr, Deps, Order};
+use cosmwasm_std::{Addr, Decimal, Deps, Order, StdError, StdResult, Storage};
 
-use astroport::asset::AssetInfo;
+use astroport::asset::{Asset, AssetInfo};
 
 use astroport::common::OwnershipProposal;
-use astroport::factory::PairConfig;
+use astroport::factory::{PairConfig, PairFees, PairType};
 
 /// ## Description
 /// This structure describes the main control config of factory.
@@ -23,6 +23,8 @@ pub struct Config {
     pub fee_address: Option<Addr>,
     /// cw1 whitelist contract code id used to store 3rd party rewards in pools
     pub whitelist_code_id: u64,
+    /// contract address that is allowed to pause pairs
+    pub guardian: Option<Addr>,
 }
 
 /// ## Description
@@ -30,6 +32,28 @@ pub struct Config {
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct TmpPairInfo {
     pub pair_key: Vec<u8>,
+    /// The assets of the pair
+    pub asset_infos: Vec<AssetInfo>,
+    /// The pair type
+    pub pair_type: PairType,
+    /// The code ID the pair is instantiated with
+    pub code_id: u64,
+    /// The liquidity to provide once the pair is instantiated
+    pub initial_liquidity: Option<InitialLiquidity>,
+}
+
+/// ## Description
+/// This structure describes the initial liquidity of a pair created with `CreatePairAndProvide`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InitialLiquidity {
+    /// The assets held by the factory until they are provided
+    pub assets: Vec<Asset>,
+    /// The slippage tolerance of the liquidity provision
+    pub slippage_tolerance: Option<Decimal>,
+    /// Determines whether the LP tokens are auto_staked in the Generator contract
+    pub auto_stake: Option<bool>,
+    /// The receiver of the LP tokens
+    pub receiver: Addr,
 }
 
 /// Saves a key of pair
@@ -38,23 +62,157 @@ pub const TMP_PAIR_INFO: Item<TmpPairInfo> = Item::new("tmp_pair_info");
 /// Saves factory settings
 pub const CONFIG: Item<Config> = Item::new("config");
 
-/// Saves created pairs
-pub const PAIRS: Map<&[u8], Addr> = Map::new("pair_info");
+/// Saves created pairs by the key of their assets and their type
+pub const PAIRS: Map<(&[u8], &str), Addr> = Map::new("pairs");
+
+/// ## Description
+/// Loads the pair with the specified assets and type from the [`PAIRS`]. If `pair_type` isn't specified,
+/// the first pair of the assets ordered by type is loaded. Returns the type and the address of the pair.
+/// ## Params
+/// `storage` is the object of type [`Storage`].
+///
+/// `asset_infos` is a slice with items of type [`AssetInfo`]. These are the assets of the pair.
+///
+/// `pair_type` is an [`Option`] field of type [`PairType`]. This is the type of the pair.
+pub fn load_pair(
+    storage: &dyn Storage,
+    asset_infos: &[AssetInfo],
+    pair_type: Option<PairType>,
+) -> StdResult<(String, Addr)> {
+    let pair_key = pair_key(asset_infos);
+
+    match pair_type {
+        Some(pair_type) => {
+            let pair_type = pair_type.to_string();
+            let pair_addr = PAIRS.load(storage, (&pair_key, &pair_type))?;
+            Ok((pair_type, pair_addr))
+        }
+        None => {
+            let (pair_type, pair_addr) = PAIRS
+                .prefix(&pair_key)
+                .range(storage, None, None, Order::Ascending)
+                .next()
+                .ok_or_else(|| StdError::not_found("pair"))??;
+            Ok((String::from_utf8(pair_type)?, pair_addr))
+        }
+    }
+}
+
+/// ## Description
+/// Reads all the pairs with the specified assets from the [`PAIRS`] ordered by type.
+/// ## Params
+/// `storage` is the object of type [`Storage`].
+///
+/// `asset_infos` is a slice with items of type [`AssetInfo`]. These are the assets of the pairs.
+pub fn read_pair_variants(
+    storage: &dyn Storage,
+    asset_infos: &[AssetInfo],
+) -> StdResult<Vec<Addr>> {
+    PAIRS
+        .prefix(&pair_key(asset_infos))
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| Ok(item?.1))
+        .collect()
+}
+
+/// Indexes the created pairs by each of their assets and saves their type
+pub const PAIRS_BY_ASSET: Map<(&[u8], &Addr), PairType> = Map::new("pairs_by_asset");
+
+/// Indexes the created pairs by their type
+pub const PAIRS_BY_TYPE: Map<(&str, &Addr), ()> = Map::new("pairs_by_type");
+
+/// Saves the number of created pairs
+pub const PAIRS_COUNT: Item<u64> = Item::new("pairs_count");
+
+/// ## Description
+/// Adds a pair to the [`PAIRS_BY_ASSET`] and [`PAIRS_BY_TYPE`] indexes and increases the [`PAIRS_COUNT`].
+/// ## Params
+/// `storage` is the object of type [`Storage`].
+///
+/// `pair_addr` is the object of type [`Addr`]. This is the pair to index.
+///
+/// `asset_infos` is a slice with items of type [`AssetInfo`]. These are the assets of the pair.
+///
+/// `pair_type` is the object of type [`PairType`]. This is the type of the pair.
+pub fn index_pair(
+    storage: &mut dyn Storage,
+    pair_addr: &Addr,
+    asset_infos: &[AssetInfo],
+    pair_type: &PairType,
+) -> StdResult<()> {
+    for asset_info in asset_infos {
+        PAIRS_BY_ASSET.save(storage, (asset_info.as_bytes(), pair_addr), pair_type)?;
+    }
+    PAIRS_BY_TYPE.save(storage, (&pair_type.to_string(), pair_addr), &())?;
+
+    let count = PAIRS_COUNT.may_load(storage)?.unwrap_or_default();
+    PAIRS_COUNT.save(storage, &(count + 1))
+}
+
+/// ## Description
+/// Removes a pair from the [`PAIRS_BY_ASSET`] and [`PAIRS_BY_TYPE`] indexes and decreases the [`PAIRS_COUNT`].
+/// ## Params
+/// `storage` is the object of type [`Storage`].
+///
+/// `pair_addr` is the object of type [`Addr`]. This is the pair to remove.
+///
+/// `asset_infos` is a slice with items of type [`AssetInfo`]. These are the assets of the pair.
+pub fn unindex_pair(
+    storage: &mut dyn Storage,
+    pair_addr: &Addr,
+    asset_infos: &[AssetInfo],
+) -> StdResult<()> {
+    let mut pair_type = None;
+    for asset_info in asset_infos {
+        let key = (asset_info.as_bytes(), pair_addr);
+        pair_type = pair_type.or(PAIRS_BY_ASSET.may_load(storage, key)?);
+        PAIRS_BY_ASSET.remove(storage, key);
+    }
+
+    // pairs which were never indexed aren't part of the count
+    if let Some(pair_type) = pair_type {
+        PAIRS_BY_TYPE.remove(storage, (&pair_type.to_string(), pair_addr));
+
+        let count = PAIRS_COUNT.may_load(storage)?.unwrap_or_default();
+        PAIRS_COUNT.save(storage, &count.saturating_sub(1))?;
+    }
+
+    Ok(())
+}
 
 /// ## Description
 /// Calculates key of pair from the specified parameters in the `asset_infos` variable.
 /// ## Params
-/// `asset_infos` it is array with two items the type of [`AssetInfo`].
-pub fn pair_key(asset_infos: &[AssetInfo; 2]) -> Vec<u8> {
+/// `asset_infos` it is a slice with items of type [`AssetInfo`].
+pub fn pair_key(asset_infos: &[AssetInfo]) -> Vec<u8> {
     let mut asset_infos = asset_infos.to_vec();
     asset_infos.sort_by(|a, b| a.as_bytes().cmp(b.as_bytes()));
 
-    [asset_infos[0].as_bytes(), asset_infos[1].as_bytes()].concat()
+    asset_infos
+        .iter()
+        .map(AssetInfo::as_bytes)
+        .collect::<Vec<&[u8]>>()
+        .concat()
 }
 
 /// Saves the settings of the created pairs
 pub const PAIR_CONFIGS: Map<String, PairConfig> = Map::new("pair_configs");
 
+/// Saves the fee overrides of specific pairs
+pub const PAIR_FEES: Map<&Addr, PairFees> = Map::new("pair_fees");
+
+/// Saves whether all the pairs are paused
+pub const PAUSED: Item<bool> = Item::new("paused");
+
+/// Saves the pairs which are paused by themselves, including the deregistered pairs which are wound down
+pub const PAUSED_PAIRS: Map<&Addr, ()> = Map::new("paused_pairs");
+
+/// Saves the code ID each pair runs
+pub const PAIR_CODE_IDS: Map<&Addr, u64> = Map::new("pair_code_ids");
+
+/// Saves the decimals of native tokens
+pub const NATIVE_TOKEN_DECIMALS: Map<&str, u8> = Map::new("native_token_decimals");
+
 //settings for pagination
 /// The maximum limit for reading pairs from a [`PAIRS`]
 const MAX_LIMIT: u32 = 30;
@@ -68,14 +226,17 @@ const DEFAULT_LIMIT: u32 = 10;
 /// ## Params
 /// `start_after` is a [`Option`] type. Sets the item to start reading from.
 ///
+/// `start_after_pair_type` is a [`Option`] type. Sets the type of the item to start reading from.
+///
 /// `limit` is a [`Option`] type. Sets the number of items to be read.
 pub fn read_pairs(
     deps: Deps,
-    start_after: Option<[AssetInfo; 2]>,
+    start_after: Option<Vec<AssetInfo>>,
+    start_after_pair_type: Option<PairType>,
     limit: Option<u32>,
 ) -> Vec<Addr> {
     let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
-    let start = calc_range_start(start_after).map(Bound::exclusive);
+    let start = calc_range_start(start_after, start_after_pair_type).map(Bound::exclusive);
 
     PAIRS
         .range(deps.storage, start, None, Order::Ascending)
@@ -87,22 +248,126 @@ pub fn read_pairs(
         .collect()
 }
 
-// this will set the first key after the provided key, by appending a 1 byte
 /// ## Description
-/// Calculates the key of the pair from which to start reading.
+/// Reads the pairs that contain the specified asset from the [`PAIRS_BY_ASSET`] index.
 /// ## Params
-/// `start_after` is an [`Option`] type that accepts two [`AssetInfo`] elements.
-fn calc_range_start(start_after: Option<[AssetInfo; 2]>) -> Option<Vec<u8>> {
+/// `asset_info` is the object of type [`AssetInfo`]. This is the asset for which we read the pairs.
+///
+/// `start_after` is an [`Option`] type. Sets the pair address to start reading after.
+///
+/// `limit` is a [`Option`] type. Sets the number of items to be read.
+pub fn read_pairs_by_asset(
+    deps: Deps,
+    asset_info: &AssetInfo,
+    start_after: Option<Addr>,
+    limit: Option<u32>,
+) -> StdResult<Vec<Addr>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|addr| Bound::exclusive(addr.as_bytes()));
+
+    PAIRS_BY_ASSET
+        .prefix(asset_info.as_bytes())
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (pair_addr, _) = item?;
+            Ok(Addr::unchecked(String::from_utf8(pair_addr)?))
+        })
+        .collect()
+}
+
+/// ## Description
+/// Reads the pairs of the specified type from the [`PAIRS_BY_TYPE`] index.
+/// ## Params
+/// `pair_type` is the object of type [`PairType`]. This is the type for which we read the pairs.
+///
+/// `start_after` is an [`Option`] type. Sets the pair address to start reading after.
+///
+/// `limit` is a [`Option`] type. Sets the number of items to be read.
+pub fn read_pairs_by_type(
+    deps: Deps,
+    pair_type: &PairType,
+    start_after: Option<Addr>,
+    limit: Option<u32>,
+) -> StdResult<Vec<Addr>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|addr| Bound::exclusive(addr.as_bytes()));
+
+    PAIRS_BY_TYPE
+        .prefix(&pair_type.to_string())
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (pair_addr, _) = item?;
+            Ok(Addr::unchecked(String::from_utf8(pair_addr)?))
+        })
+        .collect()
+}
+
+/// ## Description
+/// Reads the pairs of the specified type which don't run the specified code ID from the [`PAIRS_BY_TYPE`] index.
+/// Returns the pairs along with the code IDs they run.
+/// ## Params
+/// `pair_type` is the object of type [`PairType`]. This is the type for which we read the pairs.
+///
+/// `code_id` is the object of type [`u64`]. This is the current code ID of the pair type.
+///
+/// `start_after` is an [`Option`] type. Sets the pair address to start reading after.
+///
+/// `limit` is a [`Option`] type. Sets the number of items to be read.
+pub fn read_pairs_to_migrate(
+    deps: Deps,
+    pair_type: &PairType,
+    code_id: u64,
+    start_after: Option<Addr>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(Addr, Option<u64>)>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|addr| Bound::exclusive(addr.as_bytes()));
+
+    let mut pairs = vec![];
+    for item in PAIRS_BY_TYPE.prefix(&pair_type.to_string()).keys(
+        deps.storage,
+        start,
+        None,
+        Order::Ascending,
+    ) {
+        if pairs.len() == limit {
+            break;
+        }
+
+        let pair_addr = Addr::unchecked(String::from_utf8(item)?);
+        let pair_code_id = PAIR_CODE_IDS.may_load(deps.storage, &pair_addr)?;
+        if pair_code_id != Some(code_id) {
+            pairs.push((pair_addr, pair_code_id));
+        }
+    }
+
+    Ok(pairs)
+}
+
+/// ## Description
+/// Calculates the key of the pair from which to start reading. If the pair type isn't specified,
+/// reading starts after all the pairs of the assets.
+/// ## Params
+/// `start_after` is an [`Option`] type that accepts a vector of [`AssetInfo`] elements.
+///
+/// `start_after_pair_type` is an [`Option`] field of type [`PairType`].
+fn calc_range_start(
+    start_after: Option<Vec<AssetInfo>>,
+    start_after_pair_type: Option<PairType>,
+) -> Option<Vec<u8>> {
     start_after.map(|asset_infos| {
-        let mut asset_infos = asset_infos.to_vec();
-        asset_infos.sort_by(|a, b| a.as_bytes().cmp(b.as_bytes()));
-
-        let mut v = [asset_infos[0].as_bytes(), asset_infos[1].as_bytes()]
-            .concat()
-            .as_slice()
-            .to_vec();
-        v.push(1);
-        v
+        let pair_key = pair_key(&asset_infos);
+        match start_after_pair_type {
+            Some(pair_type) => (pair_key.as_slice(), pair_type.to_string().as_str()).joined_key(),
+            None => {
+                // pair types are ascii strings, so this key comes after all the types of the assets
+                let mut v = (pair_key.as_slice(), "").joined_key();
+                v.push(u8::MAX);
+                v
+            }
+        }
     })
 }
 
@@ -1,30 +1,38 @@
 use cosmwasm_std::{
-    attr, from_binary, to_binary, Addr, ContractResult, Reply, ReplyOn, SubMsg,
-    SubMsgExecutionResponse, WasmMsg,
+    attr, from_binary, to_binary, Addr, Coin, ContractResult, Decimal, Deps, Empty, Reply, ReplyOn,
+    StdError, SubMsg, SubMsgExecutionResponse, Uint128, WasmMsg,
 };
 
 use crate::mock_querier::mock_dependencies;
-use crate::state::CONFIG;
 use crate::{
-    contract::{execute, instantiate, query},
+    contract::{execute, instantiate, migrate, query},
     error::ContractError,
 };
 
-use astroport::asset::{AssetInfo, PairInfo};
+use astroport::asset::{Asset, AssetInfo, PairInfo};
 use astroport::factory::{
-    ConfigResponse, ExecuteMsg, InstantiateMsg, PairConfig, PairType, PairsResponse, QueryMsg,
+    ConfigResponse, CreationFeeResponse, ExecuteMsg, FeeInfoResponse, InstantiateMsg, MigrateMsg,
+    NativeTokenDecimalsResponse, PairCodeId, PairConfig, PairFees, PairType, PairsCountResponse,
+    PairsResponse, PairsToMigrateResponse, PauseStatusResponse, QueryMsg, UpdateAddr,
 };
 
 use crate::contract::reply;
+use crate::migration::PAIRSV110;
 use crate::response::MsgInstantiateContractResponse;
-use astroport::pair::InstantiateMsg as PairInstantiateMsg;
+use crate::state::pair_key;
+use astroport::generator::ExecuteMsg as GeneratorExecuteMsg;
+use astroport::pair::{ExecuteMsg as PairExecuteMsg, InstantiateMsg as PairInstantiateMsg};
 use cosmwasm_std::testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR};
+use cw2::set_contract_version;
+use cw20::Cw20ExecuteMsg;
 use protobuf::Message;
 
 #[test]
 fn pair_type_to_string() {
     assert_eq!(PairType::Xyk {}.to_string(), "xyk");
     assert_eq!(PairType::Stable {}.to_string(), "stable");
+    assert_eq!(PairType::Concentrated {}.to_string(), "concentrated");
+    assert_eq!(PairType::Weighted {}.to_string(), "weighted");
 }
 
 #[test]
@@ -41,6 +49,8 @@ fn proper_initialization() {
                 total_fee_bps: 100,
                 maker_fee_bps: 10,
                 is_disabled: None,
+                permissionless: true,
+                creation_fee: None,
             },
             PairConfig {
                 code_id: 325u64,
@@ -48,6 +58,8 @@ fn proper_initialization() {
                 total_fee_bps: 100,
                 maker_fee_bps: 10,
                 is_disabled: None,
+                permissionless: true,
+                creation_fee: None,
             },
         ],
         token_code_id: 123u64,
@@ -70,6 +82,8 @@ fn proper_initialization() {
             total_fee_bps: 10_001,
             maker_fee_bps: 10,
             is_disabled: None,
+            permissionless: true,
+            creation_fee: None,
         }],
         token_code_id: 123u64,
         fee_address: None,
@@ -94,6 +108,8 @@ fn proper_initialization() {
                 total_fee_bps: 100,
                 maker_fee_bps: 10,
                 is_disabled: None,
+                permissionless: true,
+                creation_fee: None,
             },
             PairConfig {
                 code_id: 123u64,
@@ -101,6 +117,8 @@ fn proper_initialization() {
                 total_fee_bps: 100,
                 maker_fee_bps: 10,
                 is_disabled: None,
+                permissionless: true,
+                creation_fee: None,
             },
         ],
         token_code_id: 123u64,
@@ -133,6 +151,8 @@ fn update_config() {
         total_fee_bps: 3,
         maker_fee_bps: 166,
         is_disabled: None,
+        permissionless: true,
+        creation_fee: None,
     }];
 
     let msg = InstantiateMsg {
@@ -158,6 +178,7 @@ fn update_config() {
         fee_address: Some(String::from("new_fee_addr")),
         generator_address: Some(String::from("new_generator_addr")),
         whitelist_code_id: None,
+        guardian: None,
     };
 
     let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
@@ -185,6 +206,7 @@ fn update_config() {
         fee_address: None,
         generator_address: None,
         whitelist_code_id: None,
+        guardian: None,
     };
 
     let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
@@ -279,6 +301,8 @@ fn update_pair_config() {
         total_fee_bps: 100,
         maker_fee_bps: 10,
         is_disabled: None,
+        permissionless: true,
+        creation_fee: None,
     }];
 
     let msg = InstantiateMsg {
@@ -308,6 +332,8 @@ fn update_pair_config() {
         total_fee_bps: 1,
         maker_fee_bps: 2,
         is_disabled: None,
+        permissionless: true,
+        creation_fee: None,
     };
 
     // Unauthorized err
@@ -330,6 +356,8 @@ fn update_pair_config() {
             total_fee_bps: 3,
             maker_fee_bps: 10_001,
             is_disabled: None,
+            permissionless: true,
+            creation_fee: None,
         },
     };
 
@@ -356,6 +384,8 @@ fn update_pair_config() {
         total_fee_bps: 10,
         maker_fee_bps: 20,
         is_disabled: None,
+        permissionless: true,
+        creation_fee: None,
     };
 
     let info = mock_info(owner.clone(), &[]);
@@ -384,6 +414,8 @@ fn create_pair() {
         total_fee_bps: 100,
         maker_fee_bps: 10,
         is_disabled: None,
+        permissionless: true,
+        creation_fee: None,
     };
 
     let msg = InstantiateMsg {
@@ -401,7 +433,7 @@ fn create_pair() {
     // we can just call .unwrap() to assert this was a success
     let _res = instantiate(deps.as_mut(), env, info, msg.clone()).unwrap();
 
-    let asset_infos = [
+    let asset_infos = vec![
         AssetInfo::Token {
             contract_addr: Addr::unchecked("asset0000"),
         },
@@ -410,7 +442,6 @@ fn create_pair() {
         },
     ];
 
-    let config = CONFIG.load(&deps.storage);
     let env = mock_env();
     let info = mock_info("addr0000", &[]);
 
@@ -461,7 +492,7 @@ fn create_pair() {
                 .unwrap(),
                 code_id: pair_config.code_id,
                 funds: vec![],
-                admin: Some(config.unwrap().owner.to_string()),
+                admin: Some(String::from(MOCK_CONTRACT_ADDR)),
                 label: String::from("Astroport pair"),
             }
             .into(),
@@ -470,6 +501,117 @@ fn create_pair() {
             reply_on: ReplyOn::Success
         }]
     );
+
+    // Check creating a pool with a duplicated asset
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::CreatePair {
+            pair_type: PairType::Xyk {},
+            asset_infos: vec![
+                asset_infos[0].clone(),
+                asset_infos[1].clone(),
+                asset_infos[0].clone(),
+            ],
+            init_params: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(res, ContractError::DoublingAssets {});
+
+    // Pools with more than two assets are keyed by the whole asset set
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        ExecuteMsg::UpdatePairConfig {
+            config: PairConfig {
+                code_id: 322u64,
+                pair_type: PairType::Stable {},
+                total_fee_bps: 5,
+                maker_fee_bps: 10,
+                is_disabled: None,
+                permissionless: true,
+                creation_fee: None,
+            },
+        },
+    )
+    .unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::CreatePair {
+            pair_type: PairType::Stable {},
+            asset_infos: vec![
+                asset_infos[0].clone(),
+                asset_infos[1].clone(),
+                AssetInfo::NativeToken {
+                    denom: "uusd".to_string(),
+                },
+            ],
+            init_params: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "create_pair"),
+            attr("pair", "asset0000-asset0001-uusd")
+        ]
+    );
+
+    // IBC denoms can be pooled, malformed ones are rejected
+    let ibc_denom = "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2";
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::CreatePair {
+            pair_type: PairType::Xyk {},
+            asset_infos: vec![
+                asset_infos[0].clone(),
+                AssetInfo::NativeToken {
+                    denom: ibc_denom.to_string(),
+                },
+            ],
+            init_params: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "create_pair"),
+            attr("pair", format!("asset0000-{}", ibc_denom))
+        ]
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::CreatePair {
+            pair_type: PairType::Xyk {},
+            asset_infos: vec![
+                asset_infos[1].clone(),
+                AssetInfo::NativeToken {
+                    denom: "ibc/27394fb0".to_string(),
+                },
+            ],
+            init_params: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::Std(StdError::generic_err(
+            "Native token denom ibc/27394fb0 is invalid: the IBC hash should be 64 uppercase hex characters"
+        ))
+    );
 }
 
 #[test]
@@ -484,6 +626,8 @@ fn register() {
             total_fee_bps: 100,
             maker_fee_bps: 10,
             is_disabled: None,
+            permissionless: true,
+            creation_fee: None,
         }],
         token_code_id: 123u64,
         fee_address: None,
@@ -496,7 +640,7 @@ fn register() {
     let info = mock_info("addr0000", &[]);
     let _res = instantiate(deps.as_mut(), env, info, msg).unwrap();
 
-    let asset_infos = [
+    let asset_infos = vec![
         AssetInfo::Token {
             contract_addr: Addr::unchecked("asset0000"),
         },
@@ -553,6 +697,7 @@ fn register() {
         env.clone(),
         QueryMsg::Pair {
             asset_infos: asset_infos.clone(),
+            pair_type: None,
         },
     )
     .unwrap();
@@ -573,7 +718,7 @@ fn register() {
     assert_eq!(res, ContractError::PairWasRegistered {});
 
     // Store one more item to test query pairs
-    let asset_infos_2 = [
+    let asset_infos_2 = vec![
         AssetInfo::Token {
             contract_addr: Addr::unchecked("asset0000"),
         },
@@ -627,6 +772,7 @@ fn register() {
 
     let query_msg = QueryMsg::Pairs {
         start_after: None,
+        start_after_pair_type: None,
         limit: None,
     };
 
@@ -652,6 +798,7 @@ fn register() {
 
     let query_msg = QueryMsg::Pairs {
         start_after: None,
+        start_after_pair_type: None,
         limit: Some(1),
     };
 
@@ -669,6 +816,7 @@ fn register() {
 
     let query_msg = QueryMsg::Pairs {
         start_after: Some(asset_infos.clone()),
+        start_after_pair_type: None,
         limit: None,
     };
 
@@ -684,6 +832,62 @@ fn register() {
         }]
     );
 
+    // Pairs are indexed by each of their assets and by their type
+    let query_msg = QueryMsg::PairsByAsset {
+        asset_info: asset_infos[0].clone(),
+        start_after: None,
+        limit: None,
+    };
+    let res = query(deps.as_ref(), env.clone(), query_msg).unwrap();
+    let pairs_res: PairsResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        pairs_res.pairs,
+        vec![pair0_info.clone(), pair1_info.clone()]
+    );
+
+    let query_msg = QueryMsg::PairsByAsset {
+        asset_info: asset_infos[0].clone(),
+        start_after: Some(pair0_addr.clone()),
+        limit: Some(1),
+    };
+    let res = query(deps.as_ref(), env.clone(), query_msg).unwrap();
+    let pairs_res: PairsResponse = from_binary(&res).unwrap();
+    assert_eq!(pairs_res.pairs, vec![pair1_info.clone()]);
+
+    let query_msg = QueryMsg::PairsByAsset {
+        asset_info: asset_infos_2[1].clone(),
+        start_after: None,
+        limit: None,
+    };
+    let res = query(deps.as_ref(), env.clone(), query_msg).unwrap();
+    let pairs_res: PairsResponse = from_binary(&res).unwrap();
+    assert_eq!(pairs_res.pairs, vec![pair1_info.clone()]);
+
+    let query_msg = QueryMsg::PairsByType {
+        pair_type: PairType::Xyk {},
+        start_after: None,
+        limit: None,
+    };
+    let res = query(deps.as_ref(), env.clone(), query_msg).unwrap();
+    let pairs_res: PairsResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        pairs_res.pairs,
+        vec![pair0_info.clone(), pair1_info.clone()]
+    );
+
+    let query_msg = QueryMsg::PairsByType {
+        pair_type: PairType::Stable {},
+        start_after: None,
+        limit: None,
+    };
+    let res = query(deps.as_ref(), env.clone(), query_msg).unwrap();
+    let pairs_res: PairsResponse = from_binary(&res).unwrap();
+    assert_eq!(pairs_res.pairs, vec![]);
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::PairsCount {}).unwrap();
+    let count_res: PairsCountResponse = from_binary(&res).unwrap();
+    assert_eq!(count_res.count, 2);
+
     // Deregister from wrong acc
     let env = mock_env();
     let info = mock_info("wrong_addr0000", &[]);
@@ -693,6 +897,7 @@ fn register() {
         info,
         ExecuteMsg::Deregister {
             asset_infos: asset_infos_2.clone(),
+            pair_type: None,
         },
     )
     .unwrap_err();
@@ -708,6 +913,7 @@ fn register() {
         info,
         ExecuteMsg::Deregister {
             asset_infos: asset_infos_2.clone(),
+            pair_type: None,
         },
     )
     .unwrap();
@@ -716,6 +922,7 @@ fn register() {
 
     let query_msg = QueryMsg::Pairs {
         start_after: None,
+        start_after_pair_type: None,
         limit: None,
     };
 
@@ -730,4 +937,1590 @@ fn register() {
             pair_type: PairType::Xyk {},
         },]
     );
+
+    // Deregistered pairs are removed from the indexes
+    let query_msg = QueryMsg::PairsByAsset {
+        asset_info: asset_infos[0].clone(),
+        start_after: None,
+        limit: None,
+    };
+    let res = query(deps.as_ref(), env.clone(), query_msg).unwrap();
+    let pairs_res: PairsResponse = from_binary(&res).unwrap();
+    assert_eq!(pairs_res.pairs, vec![pair0_info.clone()]);
+
+    let query_msg = QueryMsg::PairsByAsset {
+        asset_info: asset_infos_2[1].clone(),
+        start_after: None,
+        limit: None,
+    };
+    let res = query(deps.as_ref(), env.clone(), query_msg).unwrap();
+    let pairs_res: PairsResponse = from_binary(&res).unwrap();
+    assert_eq!(pairs_res.pairs, vec![]);
+
+    let query_msg = QueryMsg::PairsByType {
+        pair_type: PairType::Xyk {},
+        start_after: None,
+        limit: None,
+    };
+    let res = query(deps.as_ref(), env.clone(), query_msg).unwrap();
+    let pairs_res: PairsResponse = from_binary(&res).unwrap();
+    assert_eq!(pairs_res.pairs, vec![pair0_info]);
+
+    let res = query(deps.as_ref(), env, QueryMsg::PairsCount {}).unwrap();
+    let count_res: PairsCountResponse = from_binary(&res).unwrap();
+    assert_eq!(count_res.count, 1);
+}
+
+#[test]
+fn update_pair_fees() {
+    let mut deps = mock_dependencies(&[]);
+    let owner = "owner0000";
+
+    let msg = InstantiateMsg {
+        pair_configs: vec![PairConfig {
+            code_id: 123u64,
+            pair_type: PairType::Xyk {},
+            total_fee_bps: 30,
+            maker_fee_bps: 1660,
+            is_disabled: None,
+            permissionless: true,
+            creation_fee: None,
+        }],
+        token_code_id: 123u64,
+        fee_address: Some(String::from("fee_address")),
+        generator_address: Some(String::from("generator")),
+        owner: owner.to_string(),
+        whitelist_code_id: 234u64,
+    };
+
+    let env = mock_env();
+    instantiate(deps.as_mut(), env.clone(), mock_info("addr0000", &[]), msg).unwrap();
+
+    let asset_infos = vec![
+        AssetInfo::Token {
+            contract_addr: Addr::unchecked("asset0000"),
+        },
+        AssetInfo::Token {
+            contract_addr: Addr::unchecked("asset0001"),
+        },
+    ];
+
+    let msg = ExecuteMsg::CreatePair {
+        pair_type: PairType::Xyk {},
+        asset_infos: asset_infos.clone(),
+        init_params: None,
+    };
+    execute(deps.as_mut(), env.clone(), mock_info("addr0000", &[]), msg).unwrap();
+
+    // pair0001 was never registered in the factory
+    let pair0_addr = "pair0000".to_string();
+    let pair0_info = PairInfo {
+        asset_infos: asset_infos.clone(),
+        contract_addr: Addr::unchecked("pair0000"),
+        liquidity_token: Addr::unchecked("liquidity0000"),
+        pair_type: PairType::Xyk {},
+    };
+    let pair1_addr = "pair0001".to_string();
+    let pair1_info = PairInfo {
+        contract_addr: Addr::unchecked("pair0001"),
+        liquidity_token: Addr::unchecked("liquidity0001"),
+        ..pair0_info.clone()
+    };
+    deps.querier
+        .with_astroport_pairs(&[(&pair0_addr, &pair0_info), (&pair1_addr, &pair1_info)]);
+
+    let data = MsgInstantiateContractResponse {
+        contract_address: pair0_addr.clone(),
+        data: vec![],
+        unknown_fields: Default::default(),
+        cached_size: Default::default(),
+    }
+    .write_to_bytes()
+    .unwrap();
+
+    let reply_msg = Reply {
+        id: 1,
+        result: ContractResult::Ok(SubMsgExecutionResponse {
+            events: vec![],
+            data: Some(data.into()),
+        }),
+    };
+    reply(deps.as_mut(), mock_env(), reply_msg).unwrap();
+
+    let query_fees = |deps: Deps, pair_addr: Option<String>| -> FeeInfoResponse {
+        let res = query(
+            deps,
+            mock_env(),
+            QueryMsg::FeeInfo {
+                pair_type: PairType::Xyk {},
+                pair_addr,
+            },
+        )
+        .unwrap();
+        from_binary(&res).unwrap()
+    };
+
+    let msg = ExecuteMsg::UpdatePairFees {
+        pair_addr: pair0_addr.clone(),
+        fees: Some(PairFees {
+            total_fee_bps: 5,
+            maker_fee_bps: 1000,
+        }),
+    };
+
+    // Unauthorized check
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner, &[]),
+        ExecuteMsg::UpdatePairFees {
+            pair_addr: pair1_addr.clone(),
+            fees: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::PairNotFound {});
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner, &[]),
+        ExecuteMsg::UpdatePairFees {
+            pair_addr: pair0_addr.clone(),
+            fees: Some(PairFees {
+                total_fee_bps: 10_001,
+                maker_fee_bps: 1000,
+            }),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::PairConfigInvalidFeeBps {});
+
+    execute(deps.as_mut(), env.clone(), mock_info(owner, &[]), msg).unwrap();
+
+    let fees = query_fees(deps.as_ref(), Some(pair0_addr.clone()));
+    assert_eq!(
+        fees,
+        FeeInfoResponse {
+            fee_address: Some(Addr::unchecked("fee_address")),
+            total_fee_bps: 5,
+            maker_fee_bps: 1000,
+        }
+    );
+
+    // Other pairs and queries without a pair address use the pair type fees
+    let fees = query_fees(deps.as_ref(), Some(pair1_addr));
+    assert_eq!(fees.total_fee_bps, 30);
+    assert_eq!(fees.maker_fee_bps, 1660);
+    let fees = query_fees(deps.as_ref(), None);
+    assert_eq!(fees.total_fee_bps, 30);
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner, &[]),
+        ExecuteMsg::UpdatePairFees {
+            pair_addr: pair0_addr.clone(),
+            fees: None,
+        },
+    )
+    .unwrap();
+    let fees = query_fees(deps.as_ref(), Some(pair0_addr.clone()));
+    assert_eq!(fees.total_fee_bps, 30);
+
+    // Deregistering a pair removes its fee override
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner, &[]),
+        ExecuteMsg::UpdatePairFees {
+            pair_addr: pair0_addr.clone(),
+            fees: Some(PairFees {
+                total_fee_bps: 100,
+                maker_fee_bps: 0,
+            }),
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        env,
+        mock_info(owner, &[]),
+        ExecuteMsg::Deregister {
+            asset_infos,
+            pair_type: None,
+        },
+    )
+    .unwrap();
+    let fees = query_fees(deps.as_ref(), Some(pair0_addr));
+    assert_eq!(fees.total_fee_bps, 30);
+}
+
+#[test]
+fn update_native_token_decimals() {
+    let mut deps = mock_dependencies(&[]);
+    let owner = "owner0000";
+
+    let msg = InstantiateMsg {
+        pair_configs: vec![],
+        token_code_id: 123u64,
+        fee_address: None,
+        generator_address: None,
+        owner: owner.to_string(),
+        whitelist_code_id: 234u64,
+    };
+
+    let env = mock_env();
+    instantiate(deps.as_mut(), env.clone(), mock_info("addr0000", &[]), msg).unwrap();
+
+    let ibc_denom = "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2";
+    let query_decimals = |deps: Deps, denom: &str| -> u8 {
+        let res = query(
+            deps,
+            mock_env(),
+            QueryMsg::NativeTokenDecimals {
+                denom: denom.to_string(),
+            },
+        )
+        .unwrap();
+        let res: NativeTokenDecimalsResponse = from_binary(&res).unwrap();
+        res.decimals
+    };
+
+    // Tokens which aren't registered have 6 decimals
+    assert_eq!(query_decimals(deps.as_ref(), ibc_denom), 6);
+
+    let msg = ExecuteMsg::UpdateNativeTokenDecimals {
+        denom: ibc_denom.to_string(),
+        decimals: Some(18),
+    };
+
+    // Unauthorized check
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner, &[]),
+        ExecuteMsg::UpdateNativeTokenDecimals {
+            denom: ibc_denom.to_string(),
+            decimals: Some(19),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidNativeTokenDecimals(18));
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner, &[]),
+        ExecuteMsg::UpdateNativeTokenDecimals {
+            denom: "UUSD".to_string(),
+            decimals: Some(6),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Std(StdError::generic_err(
+            "Native token denom UUSD should be lowercase"
+        ))
+    );
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info(owner, &[]), msg).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "update_native_token_decimals"),
+            attr("denom", ibc_denom),
+        ]
+    );
+    assert_eq!(query_decimals(deps.as_ref(), ibc_denom), 18);
+    assert_eq!(query_decimals(deps.as_ref(), "uusd"), 6);
+
+    // Removing the decimals restores the default precision
+    execute(
+        deps.as_mut(),
+        env,
+        mock_info(owner, &[]),
+        ExecuteMsg::UpdateNativeTokenDecimals {
+            denom: ibc_denom.to_string(),
+            decimals: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(query_decimals(deps.as_ref(), ibc_denom), 6);
+}
+
+#[test]
+fn create_pair_permissions_and_fee() {
+    let mut deps = mock_dependencies(&[]);
+    let owner = "owner0000";
+
+    let creation_fee = Asset {
+        info: AssetInfo::Token {
+            contract_addr: Addr::unchecked("astro0000"),
+        },
+        amount: Uint128::new(100_000000),
+    };
+    let pair_configs = vec![
+        PairConfig {
+            code_id: 123u64,
+            pair_type: PairType::Xyk {},
+            total_fee_bps: 30,
+            maker_fee_bps: 1660,
+            is_disabled: None,
+            permissionless: true,
+            creation_fee: Some(creation_fee.clone()),
+        },
+        PairConfig {
+            code_id: 325u64,
+            pair_type: PairType::Stable {},
+            total_fee_bps: 5,
+            maker_fee_bps: 5000,
+            is_disabled: None,
+            permissionless: false,
+            creation_fee: None,
+        },
+    ];
+
+    let mut msg = InstantiateMsg {
+        pair_configs: vec![PairConfig {
+            creation_fee: Some(Asset {
+                info: creation_fee.info.clone(),
+                amount: Uint128::zero(),
+            }),
+            ..pair_configs[0].clone()
+        }],
+        token_code_id: 123u64,
+        fee_address: None,
+        generator_address: None,
+        owner: owner.to_string(),
+        whitelist_code_id: 234u64,
+    };
+
+    let env = mock_env();
+    let err = instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidCreationFee {});
+
+    msg.pair_configs = pair_configs;
+    instantiate(deps.as_mut(), env.clone(), mock_info("addr0000", &[]), msg).unwrap();
+
+    let res = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::CreationFee {
+            pair_type: PairType::Xyk {},
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        from_binary::<CreationFeeResponse>(&res).unwrap(),
+        CreationFeeResponse {
+            permissionless: true,
+            creation_fee: Some(creation_fee.clone()),
+        }
+    );
+
+    let create_pair_msg = |pair_type: PairType| ExecuteMsg::CreatePair {
+        pair_type,
+        asset_infos: vec![
+            AssetInfo::Token {
+                contract_addr: Addr::unchecked("asset0000"),
+            },
+            AssetInfo::Token {
+                contract_addr: Addr::unchecked("asset0001"),
+            },
+        ],
+        init_params: None,
+    };
+
+    // Only the owner can create stable pairs
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr0000", &[]),
+        create_pair_msg(PairType::Stable {}),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // The fee can't be collected without the Maker address
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr0000", &[]),
+        create_pair_msg(PairType::Xyk {}),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::FeeAddressNotSet {});
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner, &[]),
+        ExecuteMsg::UpdateConfig {
+            token_code_id: None,
+            fee_address: Some(String::from("maker0000")),
+            generator_address: None,
+            whitelist_code_id: None,
+            guardian: None,
+        },
+    )
+    .unwrap();
+
+    // The creation fee is transferred to the Maker
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr0000", &[]),
+        create_pair_msg(PairType::Xyk {}),
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 2);
+    assert_eq!(
+        res.messages[0],
+        SubMsg::new(WasmMsg::Execute {
+            contract_addr: "astro0000".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
+                owner: "addr0000".to_string(),
+                recipient: "maker0000".to_string(),
+                amount: Uint128::new(100_000000),
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+
+    // The owner creates pairs for free
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner, &[]),
+        create_pair_msg(PairType::Xyk {}),
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 1);
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner, &[]),
+        create_pair_msg(PairType::Stable {}),
+    )
+    .unwrap();
+
+    // A native creation fee must be sent along with the message
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner, &[]),
+        ExecuteMsg::UpdatePairConfig {
+            config: PairConfig {
+                code_id: 123u64,
+                pair_type: PairType::Xyk {},
+                total_fee_bps: 30,
+                maker_fee_bps: 1660,
+                is_disabled: None,
+                permissionless: true,
+                creation_fee: Some(Asset {
+                    info: AssetInfo::NativeToken {
+                        denom: "uusd".to_string(),
+                    },
+                    amount: Uint128::new(100_000000),
+                }),
+            },
+        },
+    )
+    .unwrap();
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(
+            "addr0000",
+            &[Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::new(99_000000),
+            }],
+        ),
+        create_pair_msg(PairType::Xyk {}),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Std(StdError::generic_err(
+            "Native token balance mismatch between the argument and the transferred"
+        ))
+    );
+
+    // Coins beyond the creation fee would be stuck in the factory
+    let err = execute(
+        deps.as_mut(),
+        env,
+        mock_info(
+            "addr0000",
+            &[
+                Coin {
+                    denom: "uusd".to_string(),
+                    amount: Uint128::new(100_000000),
+                },
+                Coin {
+                    denom: "uluna".to_string(),
+                    amount: Uint128::new(1_000000),
+                },
+            ],
+        ),
+        create_pair_msg(PairType::Xyk {}),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::UnexpectedFunds {});
+}
+
+#[test]
+fn migrate_pair_keys() {
+    let mut deps = mock_dependencies(&[]);
+    let owner = "owner0000";
+
+    let msg = InstantiateMsg {
+        pair_configs: vec![PairConfig {
+            code_id: 123u64,
+            pair_type: PairType::Xyk {},
+            total_fee_bps: 30,
+            maker_fee_bps: 1660,
+            is_disabled: None,
+            permissionless: true,
+            creation_fee: None,
+        }],
+        token_code_id: 123u64,
+        fee_address: None,
+        generator_address: None,
+        owner: owner.to_string(),
+        whitelist_code_id: 234u64,
+    };
+
+    let env = mock_env();
+    instantiate(deps.as_mut(), env.clone(), mock_info(owner, &[]), msg).unwrap();
+
+    // The pairs registered before the pairs were keyed by type
+    let pairs = ["pair0000".to_string(), "pair0001".to_string()];
+    let pair_infos: Vec<PairInfo> = pairs
+        .iter()
+        .enumerate()
+        .map(|(i, pair_addr)| PairInfo {
+            asset_infos: vec![
+                AssetInfo::NativeToken {
+                    denom: "uusd".to_string(),
+                },
+                AssetInfo::Token {
+                    contract_addr: Addr::unchecked(format!("asset000{}", i)),
+                },
+            ],
+            contract_addr: Addr::unchecked(pair_addr),
+            liquidity_token: Addr::unchecked(format!("liquidity000{}", i)),
+            pair_type: PairType::Xyk {},
+        })
+        .collect();
+    deps.querier
+        .with_astroport_pairs(&[(&pairs[0], &pair_infos[0]), (&pairs[1], &pair_infos[1])]);
+    deps.querier.with_contract_infos(&[
+        ("pair0000", 123u64, Some(MOCK_CONTRACT_ADDR)),
+        ("pair0001", 123u64, Some(MOCK_CONTRACT_ADDR)),
+    ]);
+    for pair_info in &pair_infos {
+        PAIRSV110
+            .save(
+                deps.as_mut().storage,
+                &pair_key(&pair_info.asset_infos),
+                &pair_info.contract_addr,
+            )
+            .unwrap();
+    }
+
+    let migrate_msg = ExecuteMsg::MigratePairKeys { limit: Some(1) };
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr0000", &[]),
+        migrate_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // The pairs are moved page by page
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner, &[]),
+        migrate_msg.clone(),
+    )
+    .unwrap();
+    assert_eq!(res.attributes[1], attr("pairs", "pair0000"));
+
+    // A pair that wasn't moved yet can't be created again
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner, &[]),
+        ExecuteMsg::CreatePair {
+            pair_type: PairType::Xyk {},
+            asset_infos: pair_infos[1].asset_infos.clone(),
+            init_params: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::PairWasCreated {});
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner, &[]),
+        migrate_msg.clone(),
+    )
+    .unwrap();
+    assert_eq!(res.attributes[1], attr("pairs", "pair0001"));
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner, &[]),
+        migrate_msg,
+    )
+    .unwrap();
+    assert_eq!(res.attributes[1], attr("pairs", ""));
+
+    let res: PairsCountResponse =
+        from_binary(&query(deps.as_ref(), env.clone(), QueryMsg::PairsCount {}).unwrap()).unwrap();
+    assert_eq!(res.count, 2);
+
+    let res: PairInfo = from_binary(
+        &query(
+            deps.as_ref(),
+            env,
+            QueryMsg::Pair {
+                asset_infos: pair_infos[1].asset_infos.clone(),
+                pair_type: Some(PairType::Xyk {}),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res, pair_infos[1]);
+}
+
+#[test]
+#[cfg(feature = "terra")]
+fn migrate_pairs_of_other_admins() {
+    let mut deps = mock_dependencies(&[]);
+    let owner = "owner0000";
+
+    let msg = InstantiateMsg {
+        pair_configs: vec![PairConfig {
+            code_id: 123u64,
+            pair_type: PairType::Xyk {},
+            total_fee_bps: 30,
+            maker_fee_bps: 1660,
+            is_disabled: None,
+            permissionless: true,
+            creation_fee: None,
+        }],
+        token_code_id: 123u64,
+        fee_address: None,
+        generator_address: None,
+        owner: owner.to_string(),
+        whitelist_code_id: 234u64,
+    };
+
+    let env = mock_env();
+    instantiate(deps.as_mut(), env.clone(), mock_info(owner, &[]), msg).unwrap();
+
+    // The pairs registered before the factory became the admin of the pairs it creates
+    let pairs = ["pair0000".to_string(), "pair0001".to_string()];
+    let pair_infos: Vec<PairInfo> = pairs
+        .iter()
+        .enumerate()
+        .map(|(i, pair_addr)| PairInfo {
+            asset_infos: vec![
+                AssetInfo::NativeToken {
+                    denom: "uusd".to_string(),
+                },
+                AssetInfo::Token {
+                    contract_addr: Addr::unchecked(format!("asset000{}", i)),
+                },
+            ],
+            contract_addr: Addr::unchecked(pair_addr),
+            liquidity_token: Addr::unchecked(format!("liquidity000{}", i)),
+            pair_type: PairType::Xyk {},
+        })
+        .collect();
+    deps.querier
+        .with_astroport_pairs(&[(&pairs[0], &pair_infos[0]), (&pairs[1], &pair_infos[1])]);
+    deps.querier.with_contract_infos(&[
+        ("pair0000", 100u64, Some(MOCK_CONTRACT_ADDR)),
+        ("pair0001", 100u64, Some(owner)),
+    ]);
+    for pair_info in &pair_infos {
+        PAIRSV110
+            .save(
+                deps.as_mut().storage,
+                &pair_key(&pair_info.asset_infos),
+                &pair_info.contract_addr,
+            )
+            .unwrap();
+    }
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner, &[]),
+        ExecuteMsg::MigratePairKeys { limit: None },
+    )
+    .unwrap();
+
+    let query_pairs_to_migrate = |deps: Deps| -> Vec<PairCodeId> {
+        let msg = QueryMsg::PairsToMigrate {
+            pair_type: PairType::Xyk {},
+            start_after: None,
+            limit: None,
+        };
+        let res: PairsToMigrateResponse =
+            from_binary(&query(deps, mock_env(), msg).unwrap()).unwrap();
+        res.pairs
+    };
+
+    // The code IDs of the moved pairs are read from their contract info
+    assert_eq!(
+        query_pairs_to_migrate(deps.as_ref()),
+        vec![
+            PairCodeId {
+                contract_addr: Addr::unchecked("pair0000"),
+                code_id: Some(100u64),
+            },
+            PairCodeId {
+                contract_addr: Addr::unchecked("pair0001"),
+                code_id: Some(100u64),
+            },
+        ]
+    );
+
+    // The pairs whose admin isn't the factory are skipped
+    let res = execute(
+        deps.as_mut(),
+        env,
+        mock_info(owner, &[]),
+        ExecuteMsg::MigratePairs {
+            code_id: 123u64,
+            pair_type: PairType::Xyk {},
+            start_after: None,
+            limit: None,
+            msg: to_binary(&Empty {}).unwrap(),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(WasmMsg::Migrate {
+            contract_addr: "pair0000".to_string(),
+            new_code_id: 123u64,
+            msg: to_binary(&Empty {}).unwrap(),
+        })]
+    );
+    assert_eq!(
+        res.attributes[3..].to_vec(),
+        vec![attr("pairs", "pair0000"), attr("skipped_pairs", "pair0001")]
+    );
+    assert_eq!(
+        query_pairs_to_migrate(deps.as_ref()),
+        vec![PairCodeId {
+            contract_addr: Addr::unchecked("pair0001"),
+            code_id: Some(100u64),
+        }]
+    );
+}
+
+#[test]
+fn migrate_pair_configs() {
+    let mut deps = mock_dependencies(&[]);
+    let owner = "owner0000";
+
+    let msg = InstantiateMsg {
+        pair_configs: vec![PairConfig {
+            code_id: 123u64,
+            pair_type: PairType::Xyk {},
+            total_fee_bps: 30,
+            maker_fee_bps: 1660,
+            is_disabled: None,
+            permissionless: false,
+            creation_fee: None,
+        }],
+        token_code_id: 123u64,
+        fee_address: None,
+        generator_address: None,
+        owner: owner.to_string(),
+        whitelist_code_id: 234u64,
+    };
+
+    let env = mock_env();
+    instantiate(deps.as_mut(), env.clone(), mock_info(owner, &[]), msg).unwrap();
+
+    // The configs stored before the migration read `permissionless` as false
+    set_contract_version(deps.as_mut().storage, "astroport-factory", "1.1.0").unwrap();
+    migrate(
+        deps.as_mut(),
+        env.clone(),
+        MigrateMsg {
+            whitelist_code_id: 234u64,
+        },
+    )
+    .unwrap();
+
+    // Anyone could create pairs before, so the pair types stay permissionless
+    let res = query(
+        deps.as_ref(),
+        env,
+        QueryMsg::CreationFee {
+            pair_type: PairType::Xyk {},
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        from_binary::<CreationFeeResponse>(&res).unwrap(),
+        CreationFeeResponse {
+            permissionless: true,
+            creation_fee: None,
+        }
+    );
+}
+
+#[test]
+fn create_pair_and_provide() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        pair_configs: vec![PairConfig {
+            code_id: 321u64,
+            pair_type: PairType::Xyk {},
+            total_fee_bps: 100,
+            maker_fee_bps: 10,
+            is_disabled: None,
+            permissionless: true,
+            creation_fee: None,
+        }],
+        token_code_id: 123u64,
+        fee_address: None,
+        owner: "owner0000".to_string(),
+        generator_address: Some(String::from("generator")),
+        whitelist_code_id: 234u64,
+    };
+
+    let env = mock_env();
+    instantiate(deps.as_mut(), env.clone(), mock_info("addr0000", &[]), msg).unwrap();
+
+    let native_assets = vec![
+        Asset {
+            info: AssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            },
+            amount: Uint128::new(100_000000),
+        },
+        Asset {
+            info: AssetInfo::Token {
+                contract_addr: Addr::unchecked("asset0000"),
+            },
+            amount: Uint128::new(200_000000),
+        },
+    ];
+    let create_msg = |assets: Vec<Asset>| ExecuteMsg::CreatePairAndProvide {
+        pair_type: PairType::Xyk {},
+        assets,
+        init_params: None,
+        slippage_tolerance: Some(Decimal::percent(1)),
+        auto_stake: Some(true),
+        receiver: Some("receiver0000".to_string()),
+    };
+
+    // The native assets must be sent along with the message
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr0000", &[]),
+        create_msg(native_assets.clone()),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Std(StdError::generic_err(
+            "Native token balance mismatch between the argument and the transferred"
+        ))
+    );
+
+    // The CW20 assets are transferred to the factory before the pair is created
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(
+            "addr0000",
+            &[Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::new(100_000000),
+            }],
+        ),
+        create_msg(native_assets),
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 2);
+    assert_eq!(
+        res.messages[0],
+        SubMsg::new(WasmMsg::Execute {
+            contract_addr: "asset0000".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
+                owner: "addr0000".to_string(),
+                recipient: MOCK_CONTRACT_ADDR.to_string(),
+                amount: Uint128::new(200_000000),
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+    assert_eq!(res.messages[1].id, 1);
+
+    let token_assets = vec![
+        Asset {
+            info: AssetInfo::Token {
+                contract_addr: Addr::unchecked("asset0000"),
+            },
+            amount: Uint128::new(200_000000),
+        },
+        Asset {
+            info: AssetInfo::Token {
+                contract_addr: Addr::unchecked("asset0001"),
+            },
+            amount: Uint128::new(300_000000),
+        },
+    ];
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr0000", &[]),
+        create_msg(token_assets.clone()),
+    )
+    .unwrap();
+
+    // The initial liquidity is provided once the pair is registered
+    let data = MsgInstantiateContractResponse {
+        contract_address: String::from("pair0000"),
+        data: vec![],
+        unknown_fields: Default::default(),
+        cached_size: Default::default(),
+    }
+    .write_to_bytes()
+    .unwrap();
+    let reply_msg = Reply {
+        id: 1,
+        result: ContractResult::Ok(SubMsgExecutionResponse {
+            events: vec![],
+            data: Some(data.into()),
+        }),
+    };
+    let res = reply(deps.as_mut(), env, reply_msg).unwrap();
+
+    let increase_allowance_msg = |contract_addr: &str, amount: u128| {
+        SubMsg::new(WasmMsg::Execute {
+            contract_addr: contract_addr.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::IncreaseAllowance {
+                spender: "pair0000".to_string(),
+                amount: Uint128::new(amount),
+                expires: None,
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    };
+    assert_eq!(
+        res.messages,
+        vec![
+            increase_allowance_msg("asset0000", 200_000000),
+            increase_allowance_msg("asset0001", 300_000000),
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: "pair0000".to_string(),
+                msg: to_binary(&PairExecuteMsg::ProvideLiquidity {
+                    assets: token_assets,
+                    slippage_tolerance: Some(Decimal::percent(1)),
+                    auto_stake: Some(true),
+                    receiver: Some("receiver0000".to_string()),
+                    min_lp_to_receive: None,
+                    deadline: None,
+                })
+                .unwrap(),
+                funds: vec![],
+            }),
+        ]
+    );
+}
+
+#[test]
+fn pair_variants() {
+    let mut deps = mock_dependencies(&[]);
+    let owner = "owner0000";
+
+    let pair_config = |code_id: u64, pair_type: PairType| PairConfig {
+        code_id,
+        pair_type,
+        total_fee_bps: 100,
+        maker_fee_bps: 10,
+        is_disabled: None,
+        permissionless: true,
+        creation_fee: None,
+    };
+    let msg = InstantiateMsg {
+        pair_configs: vec![
+            pair_config(123u64, PairType::Xyk {}),
+            pair_config(321u64, PairType::Stable {}),
+        ],
+        token_code_id: 123u64,
+        fee_address: None,
+        generator_address: None,
+        owner: owner.to_string(),
+        whitelist_code_id: 234u64,
+    };
+
+    let env = mock_env();
+    instantiate(deps.as_mut(), env.clone(), mock_info(owner, &[]), msg).unwrap();
+
+    let asset_infos = vec![
+        AssetInfo::Token {
+            contract_addr: Addr::unchecked("asset0000"),
+        },
+        AssetInfo::Token {
+            contract_addr: Addr::unchecked("asset0001"),
+        },
+    ];
+
+    let xyk_addr = "pair0000".to_string();
+    let xyk_info = PairInfo {
+        asset_infos: asset_infos.clone(),
+        contract_addr: Addr::unchecked("pair0000"),
+        liquidity_token: Addr::unchecked("liquidity0000"),
+        pair_type: PairType::Xyk {},
+    };
+    let stable_addr = "pair0001".to_string();
+    let stable_info = PairInfo {
+        asset_infos: asset_infos.clone(),
+        contract_addr: Addr::unchecked("pair0001"),
+        liquidity_token: Addr::unchecked("liquidity0001"),
+        pair_type: PairType::Stable {},
+    };
+    deps.querier
+        .with_astroport_pairs(&[(&xyk_addr, &xyk_info), (&stable_addr, &stable_info)]);
+
+    // The same assets can have one pair of each type
+    for (pair_type, pair_addr) in [
+        (PairType::Xyk {}, &xyk_addr),
+        (PairType::Stable {}, &stable_addr),
+    ] {
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(owner, &[]),
+            ExecuteMsg::CreatePair {
+                pair_type,
+                asset_infos: asset_infos.clone(),
+                init_params: None,
+            },
+        )
+        .unwrap();
+
+        let data = MsgInstantiateContractResponse {
+            contract_address: pair_addr.clone(),
+            data: vec![],
+            unknown_fields: Default::default(),
+            cached_size: Default::default(),
+        }
+        .write_to_bytes()
+        .unwrap();
+        let reply_msg = Reply {
+            id: 1,
+            result: ContractResult::Ok(SubMsgExecutionResponse {
+                events: vec![],
+                data: Some(data.into()),
+            }),
+        };
+        reply(deps.as_mut(), env.clone(), reply_msg).unwrap();
+    }
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner, &[]),
+        ExecuteMsg::CreatePair {
+            pair_type: PairType::Xyk {},
+            asset_infos: asset_infos.clone(),
+            init_params: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::PairWasCreated {});
+
+    let query_pair = |deps: Deps, pair_type: Option<PairType>| -> PairInfo {
+        let msg = QueryMsg::Pair {
+            asset_infos: asset_infos.clone(),
+            pair_type,
+        };
+        from_binary(&query(deps, mock_env(), msg).unwrap()).unwrap()
+    };
+    assert_eq!(query_pair(deps.as_ref(), Some(PairType::Xyk {})), xyk_info);
+    assert_eq!(
+        query_pair(deps.as_ref(), Some(PairType::Stable {})),
+        stable_info
+    );
+    // Without a pair type the first pair ordered by type is returned
+    assert_eq!(query_pair(deps.as_ref(), None), stable_info);
+
+    let msg = QueryMsg::PairVariants {
+        asset_infos: asset_infos.clone(),
+    };
+    let res: PairsResponse = from_binary(&query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    assert_eq!(res.pairs, vec![stable_info.clone(), xyk_info.clone()]);
+
+    // Pagination can start after a specific variant of the assets
+    let msg = QueryMsg::Pairs {
+        start_after: Some(asset_infos.clone()),
+        start_after_pair_type: Some(PairType::Stable {}),
+        limit: None,
+    };
+    let res: PairsResponse = from_binary(&query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    assert_eq!(res.pairs, vec![xyk_info.clone()]);
+
+    let msg = QueryMsg::Pairs {
+        start_after: Some(asset_infos.clone()),
+        start_after_pair_type: None,
+        limit: None,
+    };
+    let res: PairsResponse = from_binary(&query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    assert_eq!(res.pairs, vec![]);
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner, &[]),
+        ExecuteMsg::Deregister {
+            asset_infos: asset_infos.clone(),
+            pair_type: Some(PairType::Xyk {}),
+        },
+    )
+    .unwrap();
+
+    let msg = QueryMsg::PairVariants { asset_infos };
+    let res: PairsResponse = from_binary(&query(deps.as_ref(), env, msg).unwrap()).unwrap();
+    assert_eq!(res.pairs, vec![stable_info]);
+}
+
+#[test]
+fn migrate_pairs() {
+    let mut deps = mock_dependencies(&[]);
+    let owner = "owner0000";
+
+    let mut pair_config = PairConfig {
+        code_id: 123u64,
+        pair_type: PairType::Xyk {},
+        total_fee_bps: 100,
+        maker_fee_bps: 10,
+        is_disabled: None,
+        permissionless: true,
+        creation_fee: None,
+    };
+    let msg = InstantiateMsg {
+        pair_configs: vec![pair_config.clone()],
+        token_code_id: 123u64,
+        fee_address: None,
+        generator_address: None,
+        owner: owner.to_string(),
+        whitelist_code_id: 234u64,
+    };
+
+    let env = mock_env();
+    instantiate(deps.as_mut(), env.clone(), mock_info(owner, &[]), msg).unwrap();
+
+    let pairs = ["pair0000".to_string(), "pair0001".to_string()];
+    let pair_infos: Vec<PairInfo> = pairs
+        .iter()
+        .enumerate()
+        .map(|(i, pair_addr)| PairInfo {
+            asset_infos: vec![
+                AssetInfo::NativeToken {
+                    denom: "uusd".to_string(),
+                },
+                AssetInfo::Token {
+                    contract_addr: Addr::unchecked(format!("asset000{}", i)),
+                },
+            ],
+            contract_addr: Addr::unchecked(pair_addr),
+            liquidity_token: Addr::unchecked(format!("liquidity000{}", i)),
+            pair_type: PairType::Xyk {},
+        })
+        .collect();
+    deps.querier
+        .with_astroport_pairs(&[(&pairs[0], &pair_infos[0]), (&pairs[1], &pair_infos[1])]);
+    deps.querier.with_contract_infos(&[
+        ("pair0000", 123u64, Some(MOCK_CONTRACT_ADDR)),
+        ("pair0001", 123u64, Some(MOCK_CONTRACT_ADDR)),
+    ]);
+
+    for pair_info in &pair_infos {
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(owner, &[]),
+            ExecuteMsg::CreatePair {
+                pair_type: PairType::Xyk {},
+                asset_infos: pair_info.asset_infos.clone(),
+                init_params: None,
+            },
+        )
+        .unwrap();
+
+        let data = MsgInstantiateContractResponse {
+            contract_address: pair_info.contract_addr.to_string(),
+            data: vec![],
+            unknown_fields: Default::default(),
+            cached_size: Default::default(),
+        }
+        .write_to_bytes()
+        .unwrap();
+        let reply_msg = Reply {
+            id: 1,
+            result: ContractResult::Ok(SubMsgExecutionResponse {
+                events: vec![],
+                data: Some(data.into()),
+            }),
+        };
+        reply(deps.as_mut(), env.clone(), reply_msg).unwrap();
+    }
+
+    let query_pairs_to_migrate = |deps: Deps| -> Vec<PairCodeId> {
+        let msg = QueryMsg::PairsToMigrate {
+            pair_type: PairType::Xyk {},
+            start_after: None,
+            limit: None,
+        };
+        let res: PairsToMigrateResponse =
+            from_binary(&query(deps, mock_env(), msg).unwrap()).unwrap();
+        res.pairs
+    };
+
+    // All the pairs run the code ID of their pair type
+    assert_eq!(query_pairs_to_migrate(deps.as_ref()), vec![]);
+
+    pair_config.code_id = 456u64;
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner, &[]),
+        ExecuteMsg::UpdatePairConfig {
+            config: pair_config,
+        },
+    )
+    .unwrap();
+
+    assert_eq!(
+        query_pairs_to_migrate(deps.as_ref()),
+        vec![
+            PairCodeId {
+                contract_addr: Addr::unchecked("pair0000"),
+                code_id: Some(123u64),
+            },
+            PairCodeId {
+                contract_addr: Addr::unchecked("pair0001"),
+                code_id: Some(123u64),
+            },
+        ]
+    );
+
+    let migrate_msg = |start_after: Option<String>| ExecuteMsg::MigratePairs {
+        code_id: 456u64,
+        pair_type: PairType::Xyk {},
+        start_after,
+        limit: Some(1),
+        msg: to_binary(&Empty {}).unwrap(),
+    };
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr0000", &[]),
+        migrate_msg(None),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // Pairs are migrated page by page
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner, &[]),
+        migrate_msg(None),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(WasmMsg::Migrate {
+            contract_addr: "pair0000".to_string(),
+            new_code_id: 456u64,
+            msg: to_binary(&Empty {}).unwrap(),
+        })]
+    );
+    assert_eq!(
+        query_pairs_to_migrate(deps.as_ref()),
+        vec![PairCodeId {
+            contract_addr: Addr::unchecked("pair0001"),
+            code_id: Some(123u64),
+        }]
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        env,
+        mock_info(owner, &[]),
+        migrate_msg(Some("pair0000".to_string())),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(WasmMsg::Migrate {
+            contract_addr: "pair0001".to_string(),
+            new_code_id: 456u64,
+            msg: to_binary(&Empty {}).unwrap(),
+        })]
+    );
+    assert_eq!(query_pairs_to_migrate(deps.as_ref()), vec![]);
+}
+
+#[test]
+fn update_pause_status() {
+    let mut deps = mock_dependencies(&[]);
+    let owner = "owner0000";
+    let guardian = "guardian0000";
+
+    let msg = InstantiateMsg {
+        pair_configs: vec![PairConfig {
+            code_id: 123u64,
+            pair_type: PairType::Xyk {},
+            total_fee_bps: 30,
+            maker_fee_bps: 1660,
+            is_disabled: None,
+            permissionless: true,
+            creation_fee: None,
+        }],
+        token_code_id: 123u64,
+        fee_address: None,
+        generator_address: None,
+        owner: owner.to_string(),
+        whitelist_code_id: 234u64,
+    };
+
+    let env = mock_env();
+    instantiate(deps.as_mut(), env.clone(), mock_info(owner, &[]), msg).unwrap();
+
+    let asset_infos = vec![
+        AssetInfo::Token {
+            contract_addr: Addr::unchecked("asset0000"),
+        },
+        AssetInfo::Token {
+            contract_addr: Addr::unchecked("asset0001"),
+        },
+    ];
+
+    let msg = ExecuteMsg::CreatePair {
+        pair_type: PairType::Xyk {},
+        asset_infos: asset_infos.clone(),
+        init_params: None,
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(owner, &[]), msg).unwrap();
+
+    let pair_addr = "pair0000".to_string();
+    let pair_info = PairInfo {
+        asset_infos,
+        contract_addr: Addr::unchecked("pair0000"),
+        liquidity_token: Addr::unchecked("liquidity0000"),
+        pair_type: PairType::Xyk {},
+    };
+    deps.querier
+        .with_astroport_pairs(&[(&pair_addr, &pair_info)]);
+
+    let data = MsgInstantiateContractResponse {
+        contract_address: pair_addr.clone(),
+        data: vec![],
+        unknown_fields: Default::default(),
+        cached_size: Default::default(),
+    }
+    .write_to_bytes()
+    .unwrap();
+
+    let reply_msg = Reply {
+        id: 1,
+        result: ContractResult::Ok(SubMsgExecutionResponse {
+            events: vec![],
+            data: Some(data.into()),
+        }),
+    };
+    reply(deps.as_mut(), env.clone(), reply_msg).unwrap();
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner, &[]),
+        ExecuteMsg::UpdateConfig {
+            token_code_id: None,
+            fee_address: None,
+            generator_address: None,
+            whitelist_code_id: None,
+            guardian: Some(UpdateAddr::Set(guardian.to_string())),
+        },
+    )
+    .unwrap();
+
+    let query_res = query(deps.as_ref(), env.clone(), QueryMsg::Config {}).unwrap();
+    let config_res: ConfigResponse = from_binary(&query_res).unwrap();
+    assert_eq!(config_res.guardian, Some(Addr::unchecked(guardian)));
+
+    let query_paused = |deps: Deps, pair_addr: Option<String>| -> bool {
+        let res = query(deps, mock_env(), QueryMsg::PauseStatus { pair_addr }).unwrap();
+        let res: PauseStatusResponse = from_binary(&res).unwrap();
+        res.paused
+    };
+    let pause_msg = |pair_addr: Option<String>, paused: bool| ExecuteMsg::UpdatePauseStatus {
+        pair_addr,
+        paused,
+    };
+
+    assert!(!query_paused(deps.as_ref(), Some(pair_addr.clone())));
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr0000", &[]),
+        pause_msg(Some(pair_addr.clone()), true),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // The guardian can pause a pair but can't unpause it
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(guardian, &[]),
+        pause_msg(Some(pair_addr.clone()), true),
+    )
+    .unwrap();
+    assert!(query_paused(deps.as_ref(), Some(pair_addr.clone())));
+    assert!(!query_paused(deps.as_ref(), None));
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(guardian, &[]),
+        pause_msg(Some(pair_addr.clone()), false),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner, &[]),
+        pause_msg(Some(pair_addr.clone()), false),
+    )
+    .unwrap();
+    assert!(!query_paused(deps.as_ref(), Some(pair_addr.clone())));
+
+    // Pausing all the pairs pauses every pair regardless of its own status
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(guardian, &[]),
+        pause_msg(None, true),
+    )
+    .unwrap();
+    assert!(query_paused(deps.as_ref(), None));
+    assert!(query_paused(deps.as_ref(), Some(pair_addr.clone())));
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner, &[]),
+        pause_msg(None, false),
+    )
+    .unwrap();
+    assert!(!query_paused(deps.as_ref(), None));
+    assert!(!query_paused(deps.as_ref(), Some(pair_addr.clone())));
+
+    // Deregistering a pair winds it down and deactivates its generator pool
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner, &[]),
+        ExecuteMsg::UpdateConfig {
+            token_code_id: None,
+            fee_address: None,
+            generator_address: Some(String::from("generator0000")),
+            whitelist_code_id: None,
+            guardian: None,
+        },
+    )
+    .unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner, &[]),
+        ExecuteMsg::Deregister {
+            asset_infos: pair_info.asset_infos.clone(),
+            pair_type: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(WasmMsg::Execute {
+            contract_addr: String::from("generator0000"),
+            msg: to_binary(&GeneratorExecuteMsg::DeactivatePool {
+                lp_token: String::from("liquidity0000"),
+            })
+            .unwrap(),
+            funds: vec![],
+        })]
+    );
+    assert!(query_paused(deps.as_ref(), Some(pair_addr.clone())));
+
+    // A deregistered pair can't be unpaused
+    let err = execute(
+        deps.as_mut(),
+        env,
+        mock_info(owner, &[]),
+        pause_msg(Some(pair_addr.clone()), false),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::PairNotFound {});
+    assert!(query_paused(deps.as_ref(), Some(pair_addr)));
 }
@@ -1,14 +1,22 @@
 use cosmwasm_std::testing::{mock_env, MockApi, MockStorage};
-use cosmwasm_std::{attr, Addr, to_binary};
+use cosmwasm_std::{
+    attr, from_binary, to_binary, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response,
+    StdError, StdResult, Uint128,
+};
 
-use astroport::asset::{AssetInfo, PairInfo};
+use astroport::asset::{Asset, AssetInfo, PairInfo};
 use astroport::factory::{
     ConfigResponse, ExecuteMsg, InstantiateMsg, PairConfig, PairType, QueryMsg,
 };
+use astroport::generator::Cw20HookMsg as GeneratorHookMsg;
 use astroport::pair::{XykPoolParamsForProxy};
 
 use astroport::token::InstantiateMsg as TokenInstantiateMsg;
-use cw20::MinterResponse;
+use cw20::{
+    BalanceResponse, Cw20Coin, Cw20ExecuteMsg, Cw20QueryMsg, Cw20ReceiveMsg, MinterResponse,
+};
+use cw_storage_plus::Map;
+use serde::{Deserialize, Serialize};
 
 use terra_multi_test::{AppBuilder, BankKeeper, ContractWrapper, Executor, TerraApp, TerraMock};
 
@@ -64,6 +72,74 @@ fn store_token_code(app: &mut TerraApp) -> u64 {
     app.store_code(token_contract)
 }
 
+/// The LP tokens deposited in the generator mock for each user
+const GENERATOR_DEPOSITS: Map<&Addr, Uint128> = Map::new("deposits");
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum GeneratorExecuteMsg {
+    Receive(Cw20ReceiveMsg),
+    DeactivatePool { lp_token: String },
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum GeneratorQueryMsg {
+    Deposit { user: String },
+}
+
+fn generator_instantiate(
+    _deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: Empty,
+) -> StdResult<Response> {
+    Ok(Response::new())
+}
+
+fn generator_execute(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: GeneratorExecuteMsg,
+) -> StdResult<Response> {
+    match msg {
+        GeneratorExecuteMsg::Receive(cw20_msg) => match from_binary(&cw20_msg.msg)? {
+            GeneratorHookMsg::DepositFor(user) => {
+                GENERATOR_DEPOSITS.update(deps.storage, &user, |deposit| -> StdResult<_> {
+                    Ok(deposit.unwrap_or_default() + cw20_msg.amount)
+                })?;
+                Ok(Response::new())
+            }
+            GeneratorHookMsg::Deposit {} => Err(StdError::generic_err("Unexpected deposit")),
+        },
+        // Like a generator whose config doesn't have the factory set
+        GeneratorExecuteMsg::DeactivatePool { .. } => Err(StdError::generic_err("Unauthorized")),
+    }
+}
+
+fn generator_query(deps: Deps, _env: Env, msg: GeneratorQueryMsg) -> StdResult<Binary> {
+    match msg {
+        GeneratorQueryMsg::Deposit { user } => to_binary(
+            &GENERATOR_DEPOSITS
+                .may_load(deps.storage, &Addr::unchecked(user))?
+                .unwrap_or_default(),
+        ),
+    }
+}
+
+/// Stores a generator mock that records the LP tokens deposited on behalf of the users and rejects
+/// the pool deactivations
+fn store_generator_code(app: &mut TerraApp) -> u64 {
+    let generator_contract = Box::new(ContractWrapper::new_with_empty(
+        generator_execute,
+        generator_instantiate,
+        generator_query,
+    ));
+
+    app.store_code(generator_contract)
+}
+
 #[test]
 fn proper_initialization() {
     let mut app = mock_app();
@@ -78,6 +154,8 @@ fn proper_initialization() {
         total_fee_bps: 100,
         maker_fee_bps: 10,
         is_disabled: None,
+        permissionless: true,
+        creation_fee: None,
     }];
 
     let msg = InstantiateMsg {
@@ -130,6 +208,7 @@ fn update_config() {
         fee_address: fee_address.clone(),
         generator_address: generator_address.clone(),
         whitelist_code_id: None,
+        guardian: None,
     };
 
     app.execute_contract(
@@ -162,6 +241,7 @@ fn update_config() {
         fee_address: None,
         generator_address: None,
         whitelist_code_id: None,
+        guardian: None,
     };
 
     let res = app
@@ -185,6 +265,8 @@ fn instantiate_contract(app: &mut TerraApp, owner: &Addr, token_code_id: u64) ->
         total_fee_bps: 100,
         maker_fee_bps: 10,
         is_disabled: None,
+        permissionless: true,
+        creation_fee: None,
     }];
 
     let msg = InstantiateMsg {
@@ -268,7 +350,7 @@ fn create_pair() {
         )
         .unwrap();
 
-    let asset_infos = [
+    let asset_infos = vec![
         AssetInfo::Token {
             contract_addr: token_instance0.clone(),
         },
@@ -299,6 +381,7 @@ fn create_pair() {
             factory_instance.clone(),
             &QueryMsg::Pair {
                 asset_infos: asset_infos.clone(),
+                pair_type: None,
             },
         )
         .unwrap();
@@ -308,3 +391,247 @@ fn create_pair() {
     assert_eq!("contract #3", res.contract_addr.to_string());
     assert_eq!("contract #4", res.liquidity_token.to_string());
 }
+
+/// Instantiates the generator mock and sets it in the factory config
+fn instantiate_generator(app: &mut TerraApp, owner: &Addr, factory_instance: &Addr) -> Addr {
+    let generator_code_id = store_generator_code(app);
+    let generator_instance = app
+        .instantiate_contract(
+            generator_code_id,
+            owner.clone(),
+            &Empty {},
+            &[],
+            "generator",
+            None,
+        )
+        .unwrap();
+
+    app.execute_contract(
+        owner.clone(),
+        factory_instance.clone(),
+        &ExecuteMsg::UpdateConfig {
+            token_code_id: None,
+            fee_address: None,
+            generator_address: Some(generator_instance.to_string()),
+            whitelist_code_id: None,
+            guardian: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    generator_instance
+}
+
+fn instantiate_token(app: &mut TerraApp, token_code_id: u64, name: &str, owner: &Addr) -> Addr {
+    let init_msg = TokenInstantiateMsg {
+        name: name.to_string(),
+        symbol: name.to_string(),
+        decimals: 6,
+        initial_balances: vec![Cw20Coin {
+            address: owner.to_string(),
+            amount: Uint128::new(1_000_000_000),
+        }],
+        mint: None,
+    };
+
+    app.instantiate_contract(token_code_id, owner.clone(), &init_msg, &[], name, None)
+        .unwrap()
+}
+
+fn query_token_balance(app: &TerraApp, token: &Addr, address: &Addr) -> Uint128 {
+    let res: BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            token,
+            &Cw20QueryMsg::Balance {
+                address: address.to_string(),
+            },
+        )
+        .unwrap();
+    res.balance
+}
+
+#[test]
+fn create_pair_and_provide() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked("owner");
+    let receiver = Addr::unchecked("receiver");
+
+    let token_code_id = store_token_code(&mut app);
+    let factory_instance = instantiate_contract(&mut app, &owner, token_code_id);
+
+    let generator_instance = instantiate_generator(&mut app, &owner, &factory_instance);
+
+    let tokens = ["tokenX", "tokenY", "tokenZ"]
+        .iter()
+        .map(|name| instantiate_token(&mut app, token_code_id, name, &owner))
+        .collect::<Vec<_>>();
+    for token in &tokens {
+        app.execute_contract(
+            owner.clone(),
+            token.clone(),
+            &Cw20ExecuteMsg::IncreaseAllowance {
+                spender: factory_instance.to_string(),
+                amount: Uint128::new(1_000_000_000),
+                expires: None,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    let init_params = Some(
+        to_binary(&XykPoolParamsForProxy {
+            proxy: String::from("proxy_contract_addr"),
+        })
+        .unwrap(),
+    );
+
+    // The factory provides the initial liquidity even though it isn't an allowed proxy of the pair
+    for (token0, token1, auto_stake) in [
+        (&tokens[0], &tokens[1], None),
+        (&tokens[0], &tokens[2], Some(true)),
+    ] {
+        let assets = vec![
+            Asset {
+                info: AssetInfo::Token {
+                    contract_addr: token0.clone(),
+                },
+                amount: Uint128::new(1_000_000),
+            },
+            Asset {
+                info: AssetInfo::Token {
+                    contract_addr: token1.clone(),
+                },
+                amount: Uint128::new(4_000_000),
+            },
+        ];
+
+        app.execute_contract(
+            owner.clone(),
+            factory_instance.clone(),
+            &ExecuteMsg::CreatePairAndProvide {
+                pair_type: PairType::Xyk {},
+                assets: assets.clone(),
+                init_params: init_params.clone(),
+                slippage_tolerance: None,
+                auto_stake,
+                receiver: Some(receiver.to_string()),
+            },
+            &[],
+        )
+        .unwrap();
+
+        let pair_info: PairInfo = app
+            .wrap()
+            .query_wasm_smart(
+                factory_instance.clone(),
+                &QueryMsg::Pair {
+                    asset_infos: assets.iter().map(|asset| asset.info.clone()).collect(),
+                    pair_type: Some(PairType::Xyk {}),
+                },
+            )
+            .unwrap();
+
+        assert_eq!(
+            query_token_balance(&app, token0, &pair_info.contract_addr),
+            Uint128::new(1_000_000)
+        );
+        assert_eq!(
+            query_token_balance(&app, token1, &pair_info.contract_addr),
+            Uint128::new(4_000_000)
+        );
+
+        // sqrt(1_000_000 * 4_000_000)
+        let share = Uint128::new(2_000_000);
+        let generator_deposit: Uint128 = app
+            .wrap()
+            .query_wasm_smart(
+                generator_instance.clone(),
+                &GeneratorQueryMsg::Deposit {
+                    user: receiver.to_string(),
+                },
+            )
+            .unwrap();
+        if auto_stake == Some(true) {
+            assert_eq!(
+                query_token_balance(&app, &pair_info.liquidity_token, &receiver),
+                Uint128::zero()
+            );
+            assert_eq!(
+                query_token_balance(&app, &pair_info.liquidity_token, &generator_instance),
+                share
+            );
+            assert_eq!(generator_deposit, share);
+        } else {
+            assert_eq!(
+                query_token_balance(&app, &pair_info.liquidity_token, &receiver),
+                share
+            );
+            assert_eq!(generator_deposit, Uint128::zero());
+        }
+    }
+}
+
+#[test]
+fn deregister_with_rejected_pool_deactivation() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked("owner");
+
+    let token_code_id = store_token_code(&mut app);
+    let factory_instance = instantiate_contract(&mut app, &owner, token_code_id);
+    instantiate_generator(&mut app, &owner, &factory_instance);
+
+    let asset_infos = ["tokenX", "tokenY"]
+        .iter()
+        .map(|name| AssetInfo::Token {
+            contract_addr: instantiate_token(&mut app, token_code_id, name, &owner),
+        })
+        .collect::<Vec<_>>();
+
+    app.execute_contract(
+        owner.clone(),
+        factory_instance.clone(),
+        &ExecuteMsg::CreatePair {
+            pair_type: PairType::Xyk {},
+            asset_infos: asset_infos.clone(),
+            init_params: Some(
+                to_binary(&XykPoolParamsForProxy {
+                    proxy: String::from("proxy_contract_addr"),
+                })
+                .unwrap(),
+            ),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // The deregistration fails with the deactivation, so the pool never keeps its allocation points
+    let err = app
+        .execute_contract(
+            owner.clone(),
+            factory_instance.clone(),
+            &ExecuteMsg::Deregister {
+                asset_infos: asset_infos.clone(),
+                pair_type: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert!(err.to_string().contains("Generic error: Unauthorized"));
+
+    let res: PairInfo = app
+        .wrap()
+        .query_wasm_smart(
+            factory_instance,
+            &QueryMsg::Pair {
+                asset_infos,
+                pair_type: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(res.pair_type, PairType::Xyk {});
+}
@@ -1,25 +1,34 @@
 use crate::error::ContractError;
-use crate::state::{Config, CONFIG};
+use crate::migration;
+use crate::state::{Config, PriceSnapshot, CONFIG, DYNAMIC_FEE_PARAMS, PRICE_SNAPSHOT};
 
 use cosmwasm_bignumber::{Decimal256, Uint256};
 use cosmwasm_std::{
     attr, entry_point, from_binary, to_binary, Addr, Binary, Coin, CosmosMsg, Decimal, Deps,
-    DepsMut, Env, MessageInfo, Reply, ReplyOn, Response, StdError, StdResult, SubMsg, Uint128,
-    WasmMsg,
+    DepsMut, Env, MessageInfo, Reply, ReplyOn, Response, StdError, StdResult, Storage, SubMsg,
+    Uint128, WasmMsg,
 };
 
 use crate::response::MsgInstantiateContractResponse;
 use astroport::asset::{addr_validate_to_lower, format_lp_token_name, Asset, AssetInfo, PairInfo};
 use astroport::factory::PairType;
+use astroport::flash_loan;
 use astroport::generator::Cw20HookMsg as GeneratorHookMsg;
+use astroport::observation::{observe, store_observation};
+use astroport::pair::assert_deadline;
 use astroport::pair::{ConfigResponse, DEFAULT_SLIPPAGE, MAX_ALLOWED_SLIPPAGE};
 use astroport::pair::{
-    CumulativePricesResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, PoolResponse,
-    QueryMsg, ReverseSimulationResponse, SimulationResponse, XykPoolParamsForProxy, TWAP_PRECISION,
+    CumulativePricesResponse, Cw20HookMsg, DynamicFeeParams, ExecuteMsg, InstantiateMsg,
+    MigrateMsg, Observation, ObserveResponse, PoolResponse, ProvideResponse, QueryMsg,
+    ReverseSimulationResponse, SimulateWithdrawResponse, SimulationResponse, SwapResponse,
+    WithdrawResponse, WithdrawType, XykPoolConfig, XykPoolParamsForProxy, XykPoolUpdateParams,
+    TWAP_PRECISION,
+};
+use astroport::querier::{
+    assert_not_paused, query_factory_config, query_fee_info, query_pair_paused, query_supply,
 };
-use astroport::querier::{query_factory_config, query_fee_info, query_supply};
 use astroport::{token::InstantiateMsg as TokenInstantiateMsg, U256};
-use cw2::set_contract_version;
+use cw2::{get_contract_version, set_contract_version};
 use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg, MinterResponse};
 use protobuf::Message;
 use std::str::FromStr;
@@ -31,6 +40,8 @@ const CONTRACT_NAME: &str = "astroport-pair";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 /// A `reply` call code ID of sub-message.
 const INSTANTIATE_TOKEN_REPLY_ID: u64 = 1;
+/// The precision of the commission rate used in the single-sided provision computation.
+const COMMISSION_RATE_PRECISION: u128 = 1_000_000;
 
 /// ## Description
 /// Creates a new contract with the specified parameters in the [`InstantiateMsg`].
@@ -53,6 +64,10 @@ pub fn instantiate(
     // if true {
     //     return Err(ContractError::Std(StdError::generic_err(format!("received {:?} in init_params of InstantiateMessage", params))))
     // }
+    if msg.asset_infos.len() != 2 {
+        return Err(ContractError::InvalidNumberOfAssets {});
+    }
+
     msg.asset_infos[0].check(deps.api)?;
     msg.asset_infos[1].check(deps.api)?;
 
@@ -73,7 +88,10 @@ pub fn instantiate(
         block_time_last: 0,
         price0_cumulative_last: Uint128::zero(),
         price1_cumulative_last: Uint128::zero(),
-        proxy_contract_addr: addr_validate_to_lower(deps.api, params.proxy.as_str())?,
+        proxy_contracts: Some(vec![addr_validate_to_lower(
+            deps.api,
+            params.proxy.as_str(),
+        )?]),
     };
 
     CONFIG.save(deps.storage, &config)?;
@@ -149,7 +167,7 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractE
 /// * **msg** is the object of type [`ExecuteMsg`].
 ///
 /// ## Queries
-/// * **ExecuteMsg::UpdateConfig { params: Binary }** Not supported.
+/// * **ExecuteMsg::UpdateConfig { params: Binary }** Updates the pool configuration.
 ///
 /// * **ExecuteMsg::Receive(msg)** Receives a message of type [`Cw20ReceiveMsg`] and processes
 /// it depending on the received template.
@@ -159,14 +177,39 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractE
 ///             slippage_tolerance,
 ///             auto_stake,
 ///             receiver,
+///             min_lp_to_receive,
+///             deadline,
 ///         }** Provides liquidity with the specified input parameters.
 ///
+/// * **ExecuteMsg::ProvideSingleSided {
+///             asset,
+///             slippage_tolerance,
+///             auto_stake,
+///             receiver,
+///         }** Provides liquidity with a single pool asset.
+///
 /// * **ExecuteMsg::Swap {
 ///             offer_asset,
 ///             belief_price,
 ///             max_spread,
 ///             to,
+///             minimum_receive,
+///             deadline,
 ///         }** Performs an swap operation with the specified parameters.
+///
+/// * **ExecuteMsg::SwapExactOut {
+///             ask_asset,
+///             max_offer_amount,
+///             to,
+///         }** Swaps the sent native coin for exactly the specified ask asset.
+///
+/// * **ExecuteMsg::FlashLoan { assets, callback }** Lends the pool assets to the sender within
+/// the transaction.
+///
+/// * **ExecuteMsg::AssertFlashLoanRepaid {
+///             expected_balances,
+///             fees,
+///         }** Checks that a flash loan was repaid. Only the pair itself can execute it.
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
@@ -174,14 +217,27 @@ pub fn execute(
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
+    if flash_loan::is_flash_loan_locked(deps.storage)?
+        && !matches!(msg, ExecuteMsg::AssertFlashLoanRepaid { .. })
+    {
+        return Err(ContractError::FlashLoanInProgress {});
+    }
+
     match msg {
-        ExecuteMsg::UpdateConfig { .. } => Err(ContractError::NonSupported {}),
+        ExecuteMsg::UpdateConfig { params } => update_config(deps, info, params),
         ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
+        ExecuteMsg::FlashLoan { assets, callback } => flash_loan(deps, env, info, assets, callback),
+        ExecuteMsg::AssertFlashLoanRepaid {
+            expected_balances,
+            fees,
+        } => assert_flash_loan_repaid(deps, env, info, expected_balances, fees),
         ExecuteMsg::ProvideLiquidity {
             assets,
             slippage_tolerance,
             auto_stake,
             receiver,
+            min_lp_to_receive,
+            deadline,
         } => provide_liquidity(
             deps,
             env,
@@ -190,22 +246,40 @@ pub fn execute(
             slippage_tolerance,
             auto_stake,
             receiver,
+            min_lp_to_receive,
+            deadline,
+        ),
+        ExecuteMsg::ProvideSingleSided {
+            asset,
+            slippage_tolerance,
+            auto_stake,
+            receiver,
+        } => provide_single_sided(
+            deps,
+            env,
+            info,
+            asset,
+            slippage_tolerance,
+            auto_stake,
+            receiver,
         ),
         ExecuteMsg::Swap {
             offer_asset,
+            ask_asset_info,
             belief_price,
             max_spread,
             to,
+            minimum_receive,
+            deadline,
         } => {
             let config = CONFIG.load(deps.storage)?;
-            if config.proxy_contract_addr != info.sender {
-                return Err(ContractError::Std(StdError::generic_err(format!("proxy_addr = {:?} and sender = {:?}", config.proxy_contract_addr, info.sender))));
-            }
-        
+            assert_allowed_proxy(&config, &info.sender)?;
+
             offer_asset.info.check(deps.api)?;
             if !offer_asset.is_native_token() {
                 return Err(ContractError::Unauthorized {});
             }
+            assert_ask_asset(&config, &offer_asset.info, ask_asset_info.as_ref())?;
 
             let to_addr = if let Some(to_addr) = to {
                 Some(addr_validate_to_lower(deps.api, &to_addr)?)
@@ -222,6 +296,45 @@ pub fn execute(
                 belief_price,
                 max_spread,
                 to_addr,
+                minimum_receive,
+                deadline,
+            )
+        }
+        ExecuteMsg::SwapExactOut {
+            ask_asset,
+            max_offer_amount,
+            to,
+        } => {
+            let config = CONFIG.load(deps.storage)?;
+            assert_allowed_proxy(&config, &info.sender)?;
+
+            ask_asset.info.check(deps.api)?;
+
+            // The offer asset is the only native coin sent along with the message
+            let offer_asset = match &info.funds[..] {
+                [coin] => Asset {
+                    info: AssetInfo::NativeToken {
+                        denom: coin.denom.clone(),
+                    },
+                    amount: coin.amount,
+                },
+                _ => return Err(ContractError::AssetMismatch {}),
+            };
+
+            let to_addr = if let Some(to_addr) = to {
+                Some(addr_validate_to_lower(deps.api, &to_addr)?)
+            } else {
+                None
+            };
+
+            swap_exact_out(
+                deps,
+                env,
+                info.sender,
+                offer_asset,
+                ask_asset,
+                max_offer_amount,
+                to_addr,
             )
         }
     }
@@ -248,16 +361,19 @@ pub fn receive_cw20(
     let contract_addr = info.sender.clone();
     match from_binary(&cw20_msg.msg) {
         Ok(Cw20HookMsg::Swap {
+            ask_asset_info,
             belief_price,
             max_spread,
             to,
+            minimum_receive,
+            deadline,
         }) => {
             // only asset contract can execute this message
             let mut authorized: bool = false;
             let config: Config = CONFIG.load(deps.storage)?;
 
-            for pool in config.pair_info.asset_infos {
-                if let AssetInfo::Token { contract_addr, .. } = &pool {
+            for pool in &config.pair_info.asset_infos {
+                if let AssetInfo::Token { contract_addr, .. } = pool {
                     if contract_addr == &info.sender {
                         authorized = true;
                     }
@@ -268,6 +384,14 @@ pub fn receive_cw20(
                 return Err(ContractError::Unauthorized {});
             }
 
+            assert_ask_asset(
+                &config,
+                &AssetInfo::Token {
+                    contract_addr: contract_addr.clone(),
+                },
+                ask_asset_info.as_ref(),
+            )?;
+
             let to_addr = if let Some(to_addr) = to {
                 Some(addr_validate_to_lower(deps.api, to_addr.as_str())?)
             } else {
@@ -286,19 +410,111 @@ pub fn receive_cw20(
                 belief_price,
                 max_spread,
                 to_addr,
+                minimum_receive,
+                deadline,
+            )
+        }
+        Ok(Cw20HookMsg::SwapExactOut {
+            ask_asset,
+            max_offer_amount,
+            to,
+        }) => {
+            // only asset contract can execute this message
+            let mut authorized: bool = false;
+            let config: Config = CONFIG.load(deps.storage)?;
+
+            for pool in config.pair_info.asset_infos {
+                if let AssetInfo::Token { contract_addr, .. } = &pool {
+                    if contract_addr == &info.sender {
+                        authorized = true;
+                    }
+                }
+            }
+
+            if !authorized {
+                return Err(ContractError::Unauthorized {});
+            }
+
+            ask_asset.info.check(deps.api)?;
+
+            let to_addr = if let Some(to_addr) = to {
+                Some(addr_validate_to_lower(deps.api, to_addr.as_str())?)
+            } else {
+                None
+            };
+
+            swap_exact_out(
+                deps,
+                env,
+                Addr::unchecked(cw20_msg.sender),
+                Asset {
+                    info: AssetInfo::Token { contract_addr },
+                    amount: cw20_msg.amount,
+                },
+                ask_asset,
+                max_offer_amount,
+                to_addr,
             )
         }
-        Ok(Cw20HookMsg::WithdrawLiquidity {}) => withdraw_liquidity(
+        Ok(Cw20HookMsg::WithdrawLiquidity {
+            withdraw_type: None,
+        }) => withdraw_liquidity(
             deps,
             env,
             info,
             Addr::unchecked(cw20_msg.sender),
             cw20_msg.amount,
         ),
+        Ok(Cw20HookMsg::WithdrawLiquidity { .. }) => Err(ContractError::NonSupported {}),
         Err(err) => Err(ContractError::Std(err)),
     }
 }
 
+/// ## Description
+/// Returns a [`ContractError::AssetMismatch`] if the ask asset is specified and it is not the pool
+/// asset opposite to the offer asset.
+/// ## Params
+/// * **config** is the object of type [`Config`].
+///
+/// * **offer_asset_info** is the object of type [`AssetInfo`]. This is the asset to swap.
+///
+/// * **ask_asset_info** is an [`Option`] field of type [`AssetInfo`]. This is the asset to receive.
+fn assert_ask_asset(
+    config: &Config,
+    offer_asset_info: &AssetInfo,
+    ask_asset_info: Option<&AssetInfo>,
+) -> Result<(), ContractError> {
+    match ask_asset_info {
+        Some(ask_asset_info)
+            if ask_asset_info.equal(offer_asset_info)
+                || !config
+                    .pair_info
+                    .asset_infos
+                    .iter()
+                    .any(|asset_info| asset_info.equal(ask_asset_info)) =>
+        {
+            Err(ContractError::AssetMismatch {})
+        }
+        _ => Ok(()),
+    }
+}
+
+/// ## Description
+/// Returns a [`ContractError::Unauthorized`] if the proxy mode is enabled and the sender is not one of
+/// the allowed proxy contracts.
+/// ## Params
+/// * **config** is the object of type [`Config`].
+///
+/// * **sender** is the object of type [`Addr`]. This is the sender to check.
+fn assert_allowed_proxy(config: &Config, sender: &Addr) -> Result<(), ContractError> {
+    match &config.proxy_contracts {
+        Some(proxy_contracts) if !proxy_contracts.contains(sender) => {
+            Err(ContractError::Unauthorized {})
+        }
+        _ => Ok(()),
+    }
+}
+
 /// ## Description
 /// Provides liquidity with the specified input parameters.
 /// Returns an [`ContractError`] on failure, otherwise returns the [`Response`] with the specified
@@ -317,19 +533,41 @@ pub fn receive_cw20(
 /// be performed on the generator.
 ///
 /// * **receiver** is an [`Option`] field of type  [`String`]. Sets the receiver of liquidity.
+///
+/// * **min_lp_to_receive** is an [`Option`] field of type [`Uint128`]. Sets the minimum amount of
+/// LP tokens to mint.
+///
+/// * **deadline** is an [`Option`] field of type [`u64`]. Sets the block time after which the
+/// provision fails.
 // CONTRACT - should approve contract to use the amount of token.
+#[allow(clippy::too_many_arguments)]
 pub fn provide_liquidity(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    assets: [Asset; 2],
+    assets: Vec<Asset>,
     slippage_tolerance: Option<Decimal>,
     auto_stake: Option<bool>,
     receiver: Option<String>,
+    min_lp_to_receive: Option<Uint128>,
+    deadline: Option<u64>,
 ) -> Result<Response, ContractError> {
-    let config = CONFIG.load(deps.storage)?;
-    if config.proxy_contract_addr != info.sender {
-        return Err(ContractError::Std(StdError::generic_err(format!("proxy_addr = {:?} and sender = {:?}", config.proxy_contract_addr, info.sender))));
+    assert_deadline(&env, deadline)?;
+
+    let mut config: Config = CONFIG.load(deps.storage)?;
+    // The factory provides the initial liquidity of the pairs created with CreatePairAndProvide
+    if info.sender != config.factory_addr {
+        assert_allowed_proxy(&config, &info.sender)?;
+    }
+
+    assert_not_paused(
+        &deps.querier,
+        config.factory_addr.clone(),
+        env.contract.address.clone(),
+    )?;
+
+    if assets.len() != 2 {
+        return Err(ContractError::InvalidNumberOfAssets {});
     }
 
     assets[0].info.check(deps.api)?;
@@ -340,8 +578,7 @@ pub fn provide_liquidity(
         asset.assert_sent_native_token_balance(&info)?;
     }
 
-    let mut config: Config = CONFIG.load(deps.storage)?;
-    let mut pools: [Asset; 2] = config
+    let mut pools: Vec<Asset> = config
         .pair_info
         .query_pools(&deps.querier, env.contract.address.clone())?;
     let deposits: [Uint128; 2] = [
@@ -404,6 +641,10 @@ pub fn provide_liquidity(
         )
     };
 
+    if share < min_lp_to_receive.unwrap_or_default() {
+        return Err(ContractError::MinLpToReceiveAssertion {});
+    }
+
     // mint LP token for sender or receiver if set
     let receiver = receiver.unwrap_or_else(|| info.sender.to_string());
     messages.extend(mint_liquidity_token_message(
@@ -416,22 +657,204 @@ pub fn provide_liquidity(
     )?);
 
     // Accumulate prices for oracle
-    if let Some((price0_cumulative_new, price1_cumulative_new, block_time)) =
-        accumulate_prices(env, &config, pools[0].amount, pools[1].amount)?
-    {
-        config.price0_cumulative_last = price0_cumulative_new;
-        config.price1_cumulative_last = price1_cumulative_new;
-        config.block_time_last = block_time;
-        CONFIG.save(deps.storage, &config)?;
+    let prices = accumulate_prices(env, &config, pools[0].amount, pools[1].amount)?;
+    save_cumulative_prices(deps.storage, &mut config, prices)?;
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .set_data(to_binary(&ProvideResponse {
+            assets: assets.clone(),
+            share,
+        })?)
+        .add_attributes(vec![
+            attr("action", "provide_liquidity"),
+            attr("sender", info.sender.as_str()),
+            attr("receiver", receiver.as_str()),
+            attr("assets", format!("{}, {}", assets[0], assets[1])),
+            attr("share", share.to_string()),
+        ]))
+}
+
+/// ## Description
+/// Provides liquidity with a single pool asset. The optimal part of the asset is swapped to the
+/// other pool asset so that the rest of the asset and the swap result are provided in the pool ratio.
+/// Returns an [`ContractError`] on failure, otherwise returns the [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Params
+/// * **deps** is the object of type [`DepsMut`].
+///
+/// * **env** is the object of type [`Env`].
+///
+/// * **info** is the object of type [`MessageInfo`].
+///
+/// * **asset** is the object of type [`Asset`]. Sets the asset to provide.
+///
+/// * **slippage_tolerance** is an [`Option`] field of type [`Decimal`]. Used for sets the maximum
+/// percent of price movement. Also sets the maximum spread of the internal swap.
+///
+/// * **auto_stake** is an [`Option`] field of type [`bool`]. Determines whether an autostake will
+/// be performed on the generator.
+///
+/// * **receiver** is an [`Option`] field of type  [`String`]. Sets the receiver of liquidity.
+// CONTRACT - should approve contract to use the amount of token.
+pub fn provide_single_sided(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    asset: Asset,
+    slippage_tolerance: Option<Decimal>,
+    auto_stake: Option<bool>,
+    receiver: Option<String>,
+) -> Result<Response, ContractError> {
+    let mut config: Config = CONFIG.load(deps.storage)?;
+    assert_allowed_proxy(&config, &info.sender)?;
+
+    assert_not_paused(
+        &deps.querier,
+        config.factory_addr.clone(),
+        env.contract.address.clone(),
+    )?;
+
+    asset.info.check(deps.api)?;
+    asset.assert_sent_native_token_balance(&info)?;
+
+    if asset.amount.is_zero() {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+
+    let auto_stake = auto_stake.unwrap_or(false);
+
+    let mut pools: Vec<Asset> = config
+        .pair_info
+        .query_pools(&deps.querier, env.contract.address.clone())?;
+    let (offer_index, ask_index) = if asset.info.equal(&pools[0].info) {
+        (0, 1)
+    } else if asset.info.equal(&pools[1].info) {
+        (1, 0)
+    } else {
+        return Err(ContractError::AssetMismatch {});
+    };
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    if let AssetInfo::Token { contract_addr, .. } = &asset.info {
+        // If the asset is token contract, then we need to execute TransferFrom msg to receive funds
+        messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: contract_addr.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
+                owner: info.sender.to_string(),
+                recipient: env.contract.address.to_string(),
+                amount: asset.amount,
+            })?,
+            funds: vec![],
+        }));
+    } else {
+        // If the asset is native token, balance is already increased
+        // To calculated properly we should subtract user deposit from the pool
+        pools[offer_index].amount = pools[offer_index].amount.checked_sub(asset.amount)?;
+    }
+
+    let total_share = query_supply(&deps.querier, config.pair_info.liquidity_token.clone())?;
+    if total_share.is_zero() {
+        return Err(ContractError::EmptyPool {});
+    }
+
+    // Get fee info from factory
+    let fee_info = query_fee_info(
+        &deps.querier,
+        config.factory_addr.clone(),
+        config.pair_info.pair_type.clone(),
+        Some(config.pair_info.contract_addr.clone()),
+    )?;
+
+    // The internal swap pays the same dynamic fee as a regular swap
+    update_price_snapshot(deps.storage, &env, &pools)?;
+    let fee_rate = swap_fee_rate(deps.storage, &env, &pools, fee_info.total_fee_rate)?;
+
+    let swap_amount = compute_zap_swap_amount(pools[offer_index].amount, asset.amount, fee_rate)?;
+    let (return_amount, spread_amount, commission_amount) = compute_swap(
+        pools[offer_index].amount,
+        pools[ask_index].amount,
+        swap_amount,
+        fee_rate,
+    )?;
+
+    // check the spread of the internal swap
+    assert_max_spread(
+        None,
+        slippage_tolerance,
+        swap_amount,
+        return_amount + commission_amount,
+        spread_amount,
+    )?;
+
+    // Maker fee
+    let mut maker_fee_amount = Uint128::new(0);
+    if let Some(fee_address) = fee_info.fee_address {
+        if let Some(f) = calculate_maker_fee(
+            pools[ask_index].info.clone(),
+            commission_amount,
+            fee_info.maker_fee_rate,
+        ) {
+            messages.push(f.clone().into_msg(&deps.querier, fee_address)?);
+            maker_fee_amount = f.amount;
+        }
+    }
+
+    // Accumulate prices for oracle
+    let prices = accumulate_prices(env.clone(), &config, pools[0].amount, pools[1].amount)?;
+    save_cumulative_prices(deps.storage, &mut config, prices)?;
+
+    // The pools after the internal swap
+    let mut deposits = [Uint128::zero(); 2];
+    deposits[offer_index] = asset.amount.checked_sub(swap_amount)?;
+    deposits[ask_index] = return_amount;
+    pools[offer_index].amount = pools[offer_index].amount.checked_add(swap_amount)?;
+    pools[ask_index].amount = pools[ask_index]
+        .amount
+        .checked_sub(return_amount)?
+        .checked_sub(maker_fee_amount)?;
+
+    if deposits[0].is_zero() || deposits[1].is_zero() {
+        return Err(ContractError::InvalidZeroAmount {});
     }
 
-    Ok(Response::new().add_messages(messages).add_attributes(vec![
-        attr("action", "provide_liquidity"),
-        attr("sender", info.sender.as_str()),
-        attr("receiver", receiver.as_str()),
-        attr("assets", format!("{}, {}", assets[0], assets[1])),
-        attr("share", share.to_string()),
-    ]))
+    // assert slippage tolerance
+    assert_slippage_tolerance(slippage_tolerance, &deposits, &pools)?;
+
+    let share = std::cmp::min(
+        deposits[0].multiply_ratio(total_share, pools[0].amount),
+        deposits[1].multiply_ratio(total_share, pools[1].amount),
+    );
+
+    // mint LP token for sender or receiver if set
+    let receiver = receiver.unwrap_or_else(|| info.sender.to_string());
+    messages.extend(mint_liquidity_token_message(
+        deps.as_ref(),
+        &config,
+        env,
+        addr_validate_to_lower(deps.api, receiver.as_str())?,
+        share,
+        auto_stake,
+    )?);
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .set_data(to_binary(&ProvideResponse {
+            assets: vec![asset.clone()],
+            share,
+        })?)
+        .add_attributes(vec![
+            attr("action", "provide_single_sided"),
+            attr("sender", info.sender.as_str()),
+            attr("receiver", receiver.as_str()),
+            attr("asset", asset.to_string()),
+            attr("swap_amount", swap_amount.to_string()),
+            attr("return_amount", return_amount.to_string()),
+            attr("commission_amount", commission_amount.to_string()),
+            attr("maker_fee_amount", maker_fee_amount.to_string()),
+            attr("fee_rate", fee_rate.to_string()),
+            attr("share", share.to_string()),
+        ]))
 }
 
 /// # Description
@@ -529,14 +952,8 @@ pub fn withdraw_liquidity(
     let refund_assets = get_share_in_assets(&pools, amount, total_share);
 
     // Accumulate prices for oracle
-    if let Some((price0_cumulative_new, price1_cumulative_new, block_time)) =
-        accumulate_prices(env, &config, pools[0].amount, pools[1].amount)?
-    {
-        config.price0_cumulative_last = price0_cumulative_new;
-        config.price1_cumulative_last = price1_cumulative_new;
-        config.block_time_last = block_time;
-        CONFIG.save(deps.storage, &config)?;
-    }
+    let prices = accumulate_prices(env, &config, pools[0].amount, pools[1].amount)?;
+    save_cumulative_prices(deps.storage, &mut config, prices)?;
 
     // update pool info
     let messages: Vec<CosmosMsg> = vec![
@@ -565,6 +982,10 @@ pub fn withdraw_liquidity(
 
     Ok(Response::new()
         .add_messages(messages)
+        .set_data(to_binary(&WithdrawResponse {
+            refund_assets,
+            withdrawn_share: amount,
+        })?)
         .add_attributes(attributes))
 }
 
@@ -576,11 +997,7 @@ pub fn withdraw_liquidity(
 /// * **amount** is the object of type [`Uint128`].
 ///
 /// * **total_share** is the object of type [`Uint128`].
-pub fn get_share_in_assets(
-    pools: &[Asset; 2],
-    amount: Uint128,
-    total_share: Uint128,
-) -> Vec<Asset> {
+pub fn get_share_in_assets(pools: &[Asset], amount: Uint128, total_share: Uint128) -> Vec<Asset> {
     let mut share_ratio = Decimal::zero();
     if !total_share.is_zero() {
         share_ratio = Decimal::from_ratio(amount, total_share);
@@ -614,6 +1031,11 @@ pub fn get_share_in_assets(
 /// * **max_spread** is the object of type [`Option<Decimal>`]. Sets the maximum spread of the swap operation.
 ///
 /// * **to** is the object of type [`Option<Addr>`]. Sets the recipient of the swap operation.
+///
+/// * **minimum_receive** is the object of type [`Option<Uint128>`]. Sets the minimum amount of the
+/// ask asset to receive.
+///
+/// * **deadline** is the object of type [`Option<u64>`]. Sets the block time after which the swap fails.
 #[allow(clippy::too_many_arguments)]
 pub fn swap(
     deps: DepsMut,
@@ -624,10 +1046,18 @@ pub fn swap(
     belief_price: Option<Decimal>,
     max_spread: Option<Decimal>,
     to: Option<Addr>,
+    minimum_receive: Option<Uint128>,
+    deadline: Option<u64>,
 ) -> Result<Response, ContractError> {
+    assert_deadline(&env, deadline)?;
     offer_asset.assert_sent_native_token_balance(&info)?;
 
     let mut config: Config = CONFIG.load(deps.storage)?;
+    assert_not_paused(
+        &deps.querier,
+        config.factory_addr.clone(),
+        env.contract.address.clone(),
+    )?;
 
     // If the asset balance is already increased
     // To calculated properly we should subtract user deposit from the pool
@@ -663,15 +1093,16 @@ pub fn swap(
         &deps.querier,
         config.factory_addr.clone(),
         config.pair_info.pair_type.clone(),
+        Some(config.pair_info.contract_addr.clone()),
     )?;
 
+    // Start a new dynamic fee window if the previous one ended
+    update_price_snapshot(deps.storage, &env, &pools)?;
+    let fee_rate = swap_fee_rate(deps.storage, &env, &pools, fee_info.total_fee_rate)?;
+
     let offer_amount = offer_asset.amount;
-    let (return_amount, spread_amount, commission_amount) = compute_swap(
-        offer_pool.amount,
-        ask_pool.amount,
-        offer_amount,
-        fee_info.total_fee_rate,
-    )?;
+    let (return_amount, spread_amount, commission_amount) =
+        compute_swap(offer_pool.amount, ask_pool.amount, offer_amount, fee_rate)?;
 
     // check max spread limit if exist
     assert_max_spread(
@@ -689,6 +1120,10 @@ pub fn swap(
     };
 
     let tax_amount = return_asset.compute_tax(&deps.querier)?;
+    if return_amount.checked_sub(tax_amount)? < minimum_receive.unwrap_or_default() {
+        return Err(ContractError::MinimumReceiveAssertion {});
+    }
+
     let receiver = to.unwrap_or_else(|| sender.clone());
     let mut messages: Vec<CosmosMsg> =
         vec![return_asset.into_msg(&deps.querier, receiver.clone())?];
@@ -707,14 +1142,8 @@ pub fn swap(
     }
 
     // Accumulate prices for oracle
-    if let Some((price0_cumulative_new, price1_cumulative_new, block_time)) =
-        accumulate_prices(env, &config, pools[0].amount, pools[1].amount)?
-    {
-        config.price0_cumulative_last = price0_cumulative_new;
-        config.price1_cumulative_last = price1_cumulative_new;
-        config.block_time_last = block_time;
-        CONFIG.save(deps.storage, &config)?;
-    }
+    let prices = accumulate_prices(env, &config, pools[0].amount, pools[1].amount)?;
+    save_cumulative_prices(deps.storage, &mut config, prices)?;
 
     Ok(Response::new()
         .add_messages(
@@ -722,6 +1151,17 @@ pub fn swap(
             // 2. send inactive commission to collector
             messages,
         )
+        .set_data(to_binary(&SwapResponse {
+            offer_asset: offer_asset.clone(),
+            return_asset: Asset {
+                info: ask_pool.info.clone(),
+                amount: return_amount,
+            },
+            tax_amount,
+            spread_amount,
+            commission_amount,
+            maker_fee_amount,
+        })?)
         .add_attribute("action", "swap")
         .add_attribute("sender", sender.as_str())
         .add_attribute("receiver", receiver.as_str())
@@ -732,17 +1172,432 @@ pub fn swap(
         .add_attribute("tax_amount", tax_amount.to_string())
         .add_attribute("spread_amount", spread_amount.to_string())
         .add_attribute("commission_amount", commission_amount.to_string())
-        .add_attribute("maker_fee_amount", maker_fee_amount.to_string()))
+        .add_attribute("maker_fee_amount", maker_fee_amount.to_string())
+        .add_attribute("fee_rate", fee_rate.to_string()))
 }
 
 /// ## Description
-/// Shifts block_time when any price is zero to not fill an accumulator with a new price to that period.
+/// Swaps the offer asset for exactly the specified ask asset and refunds the unused part of the
+/// offer asset to the sender. Returns an [`ContractError`] on failure, otherwise returns the
+/// [`Response`] with the specified attributes if the operation was successful.
 /// ## Params
+/// * **deps** is the object of type [`DepsMut`].
+///
 /// * **env** is the object of type [`Env`].
 ///
-/// * **config** is the object of type [`Config`].
+/// * **sender** is the object of type [`Addr`]. Sets the default recipient of the swap operation.
 ///
-/// * **x** is the balance of asset[0] within a pool
+/// * **offer_asset** is the object of type [`Asset`]. This is the asset sent for swapping.
+///
+/// * **ask_asset** is the object of type [`Asset`]. This is the asset to receive.
+///
+/// * **max_offer_amount** is the object of type [`Uint128`]. Sets the maximum amount of the offer asset to swap.
+///
+/// * **to** is the object of type [`Option<Addr>`]. Sets the recipient of the swap operation.
+pub fn swap_exact_out(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    offer_asset: Asset,
+    ask_asset: Asset,
+    max_offer_amount: Uint128,
+    to: Option<Addr>,
+) -> Result<Response, ContractError> {
+    if ask_asset.amount.is_zero() {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+
+    let mut config: Config = CONFIG.load(deps.storage)?;
+    assert_not_paused(
+        &deps.querier,
+        config.factory_addr.clone(),
+        env.contract.address.clone(),
+    )?;
+
+    // If the asset balance is already increased
+    // To calculated properly we should subtract user deposit from the pool
+    let pools: Vec<Asset> = config
+        .pair_info
+        .query_pools(&deps.querier, env.clone().contract.address)?
+        .iter()
+        .map(|p| {
+            let mut p = p.clone();
+            if p.info.equal(&offer_asset.info) {
+                p.amount = p.amount.checked_sub(offer_asset.amount).unwrap();
+            }
+
+            p
+        })
+        .collect();
+
+    let offer_pool: Asset;
+    let ask_pool: Asset;
+
+    if offer_asset.info.equal(&pools[0].info) && ask_asset.info.equal(&pools[1].info) {
+        offer_pool = pools[0].clone();
+        ask_pool = pools[1].clone();
+    } else if offer_asset.info.equal(&pools[1].info) && ask_asset.info.equal(&pools[0].info) {
+        offer_pool = pools[1].clone();
+        ask_pool = pools[0].clone();
+    } else {
+        return Err(ContractError::AssetMismatch {});
+    }
+
+    // Get fee info from factory
+    let fee_info = query_fee_info(
+        &deps.querier,
+        config.factory_addr.clone(),
+        config.pair_info.pair_type.clone(),
+        Some(config.pair_info.contract_addr.clone()),
+    )?;
+
+    // Start a new dynamic fee window if the previous one ended
+    update_price_snapshot(deps.storage, &env, &pools)?;
+    let fee_rate = swap_fee_rate(deps.storage, &env, &pools, fee_info.total_fee_rate)?;
+
+    // compute_offer_amount rounds down, so the offer is computed for one more ask unit and rounded up
+    // to make sure the pool doesn't lose on rounding
+    let (offer_amount, _, _) = compute_offer_amount(
+        offer_pool.amount,
+        ask_pool.amount,
+        ask_asset.amount + Uint128::new(1),
+        fee_rate,
+    )?;
+    let offer_amount = offer_amount + Uint128::new(1);
+
+    if offer_amount > max_offer_amount || offer_amount > offer_asset.amount {
+        return Err(ContractError::MaxOfferAmountAssertion {});
+    }
+
+    let (return_amount, spread_amount, commission_amount) =
+        compute_swap(offer_pool.amount, ask_pool.amount, offer_amount, fee_rate)?;
+    if return_amount < ask_asset.amount {
+        return Err(ContractError::MaxOfferAmountAssertion {});
+    }
+
+    let tax_amount = ask_asset.compute_tax(&deps.querier)?;
+    let receiver = to.unwrap_or_else(|| sender.clone());
+    let return_msg = ask_asset
+        .clone()
+        .into_msg(&deps.querier, receiver.clone())?;
+    let mut messages: Vec<CosmosMsg> = vec![return_msg];
+
+    // Refund the unused part of the offer asset
+    let refund_amount = offer_asset.amount - offer_amount;
+    if !refund_amount.is_zero() {
+        let refund_asset = Asset {
+            info: offer_asset.info.clone(),
+            amount: refund_amount,
+        };
+        messages.push(refund_asset.into_msg(&deps.querier, sender.clone())?);
+    }
+
+    // Maker fee
+    let mut maker_fee_amount = Uint128::new(0);
+    if let Some(fee_address) = fee_info.fee_address {
+        if let Some(f) = calculate_maker_fee(
+            ask_pool.info.clone(),
+            commission_amount,
+            fee_info.maker_fee_rate,
+        ) {
+            messages.push(f.clone().into_msg(&deps.querier, fee_address)?);
+            maker_fee_amount = f.amount;
+        }
+    }
+
+    // Accumulate prices for oracle
+    let prices = accumulate_prices(env, &config, pools[0].amount, pools[1].amount)?;
+    save_cumulative_prices(deps.storage, &mut config, prices)?;
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .set_data(to_binary(&SwapResponse {
+            offer_asset: Asset {
+                info: offer_asset.info.clone(),
+                amount: offer_amount,
+            },
+            return_asset: ask_asset.clone(),
+            tax_amount,
+            spread_amount,
+            commission_amount,
+            maker_fee_amount,
+        })?)
+        .add_attribute("action", "swap_exact_out")
+        .add_attribute("sender", sender.as_str())
+        .add_attribute("receiver", receiver.as_str())
+        .add_attribute("offer_asset", offer_asset.info.to_string())
+        .add_attribute("ask_asset", ask_pool.info.to_string())
+        .add_attribute("offer_amount", offer_amount.to_string())
+        .add_attribute("return_amount", ask_asset.amount.to_string())
+        .add_attribute("refund_amount", refund_amount.to_string())
+        .add_attribute("tax_amount", tax_amount.to_string())
+        .add_attribute("spread_amount", spread_amount.to_string())
+        .add_attribute("commission_amount", commission_amount.to_string())
+        .add_attribute("maker_fee_amount", maker_fee_amount.to_string())
+        .add_attribute("fee_rate", fee_rate.to_string()))
+}
+
+/// ## Description
+/// Updates the pool configuration with the specified parameters in the `params` variable.
+/// Returns an [`ContractError`] on failure, otherwise returns the [`Response`] if the operation was successful.
+/// ## Params
+/// * **deps** is the object of type [`DepsMut`].
+///
+/// * **info** is the object of type [`MessageInfo`].
+///
+/// * **params** is the object of type [`Binary`]. These are the [`XykPoolUpdateParams`] to apply.
+///
+/// ## Executor
+/// Only the factory or its owner can execute it
+pub fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    params: Binary,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    let factory_config = query_factory_config(&deps.querier, config.factory_addr.clone())?;
+
+    if info.sender != factory_config.owner && info.sender != config.factory_addr {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match from_binary::<XykPoolUpdateParams>(&params)? {
+        XykPoolUpdateParams::UpdateDynamicFee { params } => {
+            update_dynamic_fee(deps.storage, params)?
+        }
+        XykPoolUpdateParams::UpdateProxies { proxies } => {
+            config.proxy_contracts = match proxies {
+                Some(proxies) => {
+                    let mut proxy_contracts: Vec<Addr> = vec![];
+                    for proxy in proxies {
+                        let proxy = addr_validate_to_lower(deps.api, &proxy)?;
+                        if !proxy_contracts.contains(&proxy) {
+                            proxy_contracts.push(proxy);
+                        }
+                    }
+                    Some(proxy_contracts)
+                }
+                None => None,
+            };
+            CONFIG.save(deps.storage, &config)?;
+        }
+    }
+
+    Ok(Response::default())
+}
+
+/// ## Description
+/// Enables the dynamic fee mode with the specified settings, or disables it if `params` is not set.
+/// Returns an [`ContractError`] on failure, otherwise returns [`Ok`].
+/// ## Params
+/// * **storage** is the object of type [`Storage`].
+///
+/// * **params** is an [`Option`] field of type [`DynamicFeeParams`].
+fn update_dynamic_fee(
+    storage: &mut dyn Storage,
+    params: Option<DynamicFeeParams>,
+) -> Result<(), ContractError> {
+    match params {
+        Some(params) => {
+            if !params.is_valid() {
+                return Err(ContractError::InvalidDynamicFeeParams {});
+            }
+            DYNAMIC_FEE_PARAMS.save(storage, &params)?;
+        }
+        None => DYNAMIC_FEE_PARAMS.remove(storage),
+    }
+
+    // The price movement is measured from the next swap on
+    PRICE_SNAPSHOT.remove(storage);
+
+    Ok(())
+}
+
+/// ## Description
+/// Returns the fee rate charged on swaps. If the dynamic fee mode is enabled, the fee depends on how far
+/// the pool price moved since the start of the current window, otherwise `total_fee_rate` is returned.
+/// ## Params
+/// * **storage** is the object of type [`Storage`].
+///
+/// * **env** is the object of type [`Env`].
+///
+/// * **pools** is an array of [`Asset`] type items. These are the assets available in the pool.
+///
+/// * **total_fee_rate** is the object of type [`Decimal`]. This is the fee rate set in the factory.
+pub fn swap_fee_rate(
+    storage: &dyn Storage,
+    env: &Env,
+    pools: &[Asset],
+    total_fee_rate: Decimal,
+) -> StdResult<Decimal> {
+    let params = match DYNAMIC_FEE_PARAMS.may_load(storage)? {
+        Some(params) => params,
+        None => return Ok(total_fee_rate),
+    };
+
+    let price = pool_price(pools);
+    let snapshot_price = match PRICE_SNAPSHOT.may_load(storage)? {
+        Some(snapshot) if env.block.time.seconds() < snapshot.block_time + params.window => {
+            snapshot.price
+        }
+        // A new window starts with the current price
+        _ => price,
+    };
+
+    Ok(params.fee_rate(snapshot_price, price))
+}
+
+/// ## Description
+/// Starts a new dynamic fee window with the current pool price if the dynamic fee mode is enabled
+/// and the previous window ended.
+/// ## Params
+/// * **storage** is the object of type [`Storage`].
+///
+/// * **env** is the object of type [`Env`].
+///
+/// * **pools** is an array of [`Asset`] type items. These are the assets available in the pool.
+fn update_price_snapshot(storage: &mut dyn Storage, env: &Env, pools: &[Asset]) -> StdResult<()> {
+    if let Some(params) = DYNAMIC_FEE_PARAMS.may_load(storage)? {
+        let block_time = env.block.time.seconds();
+        let window_ended = match PRICE_SNAPSHOT.may_load(storage)? {
+            Some(snapshot) => block_time >= snapshot.block_time + params.window,
+            None => true,
+        };
+
+        if window_ended {
+            PRICE_SNAPSHOT.save(
+                storage,
+                &PriceSnapshot {
+                    price: pool_price(pools),
+                    block_time,
+                },
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+/// ## Description
+/// Returns the price of the first pool asset denominated in the second one, or zero if the pool is empty.
+/// ## Params
+/// * **pools** is an array of [`Asset`] type items. These are the assets available in the pool.
+fn pool_price(pools: &[Asset]) -> Decimal {
+    if pools[0].amount.is_zero() {
+        Decimal::zero()
+    } else {
+        Decimal::from_ratio(pools[1].amount, pools[0].amount)
+    }
+}
+
+/// ## Description
+/// Lends the specified pool assets to the sender within the transaction. Sends the assets to the
+/// sender, executes the `callback` message on the sender contract and then the
+/// `AssertFlashLoanRepaid` callback which checks that the loan was repaid with the fee.
+/// The pair is locked until the loan is repaid.
+/// Returns an [`ContractError`] on failure, otherwise returns the [`Response`] with the specified attributes if the operation was successful.
+/// ## Params
+/// * **deps** is the object of type [`DepsMut`].
+///
+/// * **env** is the object of type [`Env`].
+///
+/// * **info** is the object of type [`MessageInfo`].
+///
+/// * **assets** is a vector of [`Asset`] type items. Sets the assets to borrow.
+///
+/// * **callback** is the object of type [`Binary`]. Sets the message executed on the borrower contract.
+pub fn flash_loan(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    assets: Vec<Asset>,
+    callback: Binary,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    assert_not_paused(
+        &deps.querier,
+        config.factory_addr.clone(),
+        env.contract.address.clone(),
+    )?;
+
+    let pools: Vec<Asset> = config
+        .pair_info
+        .query_pools(&deps.querier, env.contract.address.clone())?;
+
+    // Get fee info from factory
+    let fee_info = query_fee_info(
+        &deps.querier,
+        config.factory_addr.clone(),
+        config.pair_info.pair_type.clone(),
+        Some(config.pair_info.contract_addr.clone()),
+    )?;
+
+    Ok(flash_loan::flash_loan(
+        deps,
+        &env,
+        &info,
+        &pools,
+        assets,
+        &fee_info,
+        callback,
+        |expected_balances, fees| {
+            to_binary(&ExecuteMsg::AssertFlashLoanRepaid {
+                expected_balances,
+                fees,
+            })
+        },
+    )?)
+}
+
+/// ## Description
+/// Checks that the pool balances reached the expected ones, sends the Maker its share of the
+/// flash loan fees and unlocks the pair. Only the pair itself can execute it.
+/// Returns an [`ContractError`] on failure, otherwise returns the [`Response`] with the specified attributes if the operation was successful.
+/// ## Params
+/// * **deps** is the object of type [`DepsMut`].
+///
+/// * **env** is the object of type [`Env`].
+///
+/// * **info** is the object of type [`MessageInfo`].
+///
+/// * **expected_balances** is a vector of [`Asset`] type items. Sets the pool balances required once the loan is repaid.
+///
+/// * **fees** is a vector of [`Asset`] type items. Sets the flash loan fees.
+pub fn assert_flash_loan_repaid(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    expected_balances: Vec<Asset>,
+    fees: Vec<Asset>,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // Get fee info from factory
+    let fee_info = query_fee_info(
+        &deps.querier,
+        config.factory_addr.clone(),
+        config.pair_info.pair_type.clone(),
+        Some(config.pair_info.contract_addr.clone()),
+    )?;
+
+    Ok(flash_loan::assert_flash_loan_repaid(
+        deps,
+        &env,
+        &info,
+        expected_balances,
+        fees,
+        &fee_info,
+    )?)
+}
+
+/// ## Description
+/// Shifts block_time when any price is zero to not fill an accumulator with a new price to that period.
+/// ## Params
+/// * **env** is the object of type [`Env`].
+///
+/// * **config** is the object of type [`Config`].
+///
+/// * **x** is the balance of asset[0] within a pool
 ///
 /// * **y** is the balance of asset[1] within a pool
 pub fn accumulate_prices(
@@ -780,6 +1635,39 @@ pub fn accumulate_prices(
     Ok(Some((pcl0, pcl1, block_time)))
 }
 
+/// ## Description
+/// Saves the cumulative prices returned by [`accumulate_prices`] in the config and records them as a
+/// new price observation. Does nothing if the prices were already accumulated in the current block.
+/// ## Params
+/// * **storage** is the object of type [`Storage`].
+///
+/// * **config** is the object of type [`Config`]. This is the pair config to update.
+///
+/// * **prices** is an [`Option`] with the cumulative prices and the block time returned by
+/// [`accumulate_prices`].
+pub fn save_cumulative_prices(
+    storage: &mut dyn Storage,
+    config: &mut Config,
+    prices: Option<(Uint128, Uint128, u64)>,
+) -> StdResult<()> {
+    if let Some((price0_cumulative_last, price1_cumulative_last, block_time)) = prices {
+        config.price0_cumulative_last = price0_cumulative_last;
+        config.price1_cumulative_last = price1_cumulative_last;
+        config.block_time_last = block_time;
+        CONFIG.save(storage, config)?;
+        store_observation(
+            storage,
+            &Observation {
+                timestamp: block_time,
+                price0_cumulative: price0_cumulative_last,
+                price1_cumulative: price1_cumulative_last,
+            },
+        )?;
+    }
+
+    Ok(())
+}
+
 /// ## Description
 /// Calculates the maker commission according to the specified parameters.
 /// Returns an [`None`] if maker fee is zero, otherwise returns the [`Asset`] with the specified attributes.
@@ -822,29 +1710,54 @@ pub fn calculate_maker_fee(
 /// * **QueryMsg::Share { amount }** Returns information about the share of the pool in a vector
 /// that contains objects of type [`Asset`].
 ///
-/// * **QueryMsg::Simulation { offer_asset }** Returns information about the simulation of the
-/// swap in a [`SimulationResponse`] object.
+/// * **QueryMsg::Simulation { offer_asset, ask_asset_info }** Returns information about the
+/// simulation of the swap in a [`SimulationResponse`] object.
 ///
-/// * **QueryMsg::ReverseSimulation { ask_asset }** Returns information about the reverse simulation
-/// in a [`ReverseSimulationResponse`] object.
+/// * **QueryMsg::ReverseSimulation { offer_asset_info, ask_asset }** Returns information about the
+/// reverse simulation in a [`ReverseSimulationResponse`] object.
 ///
 /// * **QueryMsg::CumulativePrices {}** Returns information about the cumulative prices in a
 /// [`CumulativePricesResponse`] object.
 ///
 /// * **QueryMsg::Config {}** Returns information about the controls settings in a
 /// [`ConfigResponse`] object.
+///
+/// * **QueryMsg::SimulateWithdraw { lp_amount, withdraw_type }** Returns the assets returned and
+/// the LP tokens burned for a pro-rata withdrawal in a [`SimulateWithdrawResponse`] object.
+///
+/// * **QueryMsg::SimulateProvide { .. }** Is not supported by the XYK pool.
+///
+/// * **QueryMsg::Observe { seconds_ago }** Returns the cumulative prices at each of the specified
+/// number of seconds before the current block in an [`ObserveResponse`] object.
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Pair {} => to_binary(&query_pair_info(deps)?),
         QueryMsg::Pool {} => to_binary(&query_pool(deps)?),
         QueryMsg::Share { amount } => to_binary(&query_share(deps, amount)?),
-        QueryMsg::Simulation { offer_asset } => to_binary(&query_simulation(deps, offer_asset)?),
-        QueryMsg::ReverseSimulation { ask_asset } => {
-            to_binary(&query_reverse_simulation(deps, ask_asset)?)
-        }
+        QueryMsg::Simulation {
+            offer_asset,
+            ask_asset_info,
+        } => to_binary(&query_simulation(deps, env, offer_asset, ask_asset_info)?),
+        QueryMsg::ReverseSimulation {
+            offer_asset_info,
+            ask_asset,
+        } => to_binary(&query_reverse_simulation(
+            deps,
+            env,
+            ask_asset,
+            offer_asset_info,
+        )?),
         QueryMsg::CumulativePrices {} => to_binary(&query_cumulative_prices(deps, env)?),
-        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Config {} => to_binary(&query_config(deps, env)?),
+        QueryMsg::SimulateWithdraw {
+            lp_amount,
+            withdraw_type,
+        } => to_binary(&query_simulate_withdraw(deps, lp_amount, withdraw_type)?),
+        QueryMsg::SimulateProvide { .. } => Err(StdError::generic_err(
+            ContractError::NonSupported {}.to_string(),
+        )),
+        QueryMsg::Observe { seconds_ago } => to_binary(&query_observe(deps, env, seconds_ago)?),
     }
 }
 
@@ -887,17 +1800,55 @@ pub fn query_share(deps: Deps, amount: Uint128) -> StdResult<Vec<Asset>> {
     Ok(refund_assets)
 }
 
+/// ## Description
+/// Returns the assets returned and the LP tokens burned for a withdrawal in a [`SimulateWithdrawResponse`] object.
+/// Only pro-rata withdrawals are supported.
+/// ## Params
+/// * **deps** is the object of type [`Deps`].
+///
+/// * **lp_amount** is the object of type [`Uint128`]. Sets the amount of LP tokens sent for the withdrawal.
+///
+/// * **withdraw_type** is the object of type [`Option<WithdrawType>`]. Must be empty.
+pub fn query_simulate_withdraw(
+    deps: Deps,
+    lp_amount: Uint128,
+    withdraw_type: Option<WithdrawType>,
+) -> StdResult<SimulateWithdrawResponse> {
+    if withdraw_type.is_some() {
+        return Err(StdError::generic_err(
+            ContractError::NonSupported {}.to_string(),
+        ));
+    }
+
+    Ok(SimulateWithdrawResponse {
+        assets: query_share(deps, lp_amount)?,
+        burn_amount: lp_amount,
+    })
+}
+
 /// ## Description
 /// Returns information about the simulation of the swap in a [`SimulationResponse`] object.
 /// ## Params
 /// * **deps** is the object of type [`Deps`].
 ///
+/// * **env** is the object of type [`Env`].
+///
 /// * **offer_asset** is the object of type [`Asset`].
-pub fn query_simulation(deps: Deps, offer_asset: Asset) -> StdResult<SimulationResponse> {
+///
+/// * **ask_asset_info** is the object of type [`Option<AssetInfo>`]. If it is specified it must be
+/// the pool asset opposite to the offer asset.
+pub fn query_simulation(
+    deps: Deps,
+    env: Env,
+    offer_asset: Asset,
+    ask_asset_info: Option<AssetInfo>,
+) -> StdResult<SimulationResponse> {
     let config: Config = CONFIG.load(deps.storage)?;
+    assert_ask_asset(&config, &offer_asset.info, ask_asset_info.as_ref())
+        .map_err(|err| StdError::generic_err(err.to_string()))?;
     let contract_addr = config.pair_info.contract_addr.clone();
 
-    let pools: [Asset; 2] = config.pair_info.query_pools(&deps.querier, contract_addr)?;
+    let pools: Vec<Asset> = config.pair_info.query_pools(&deps.querier, contract_addr)?;
 
     let offer_pool: Asset;
     let ask_pool: Asset;
@@ -918,19 +1869,23 @@ pub fn query_simulation(deps: Deps, offer_asset: Asset) -> StdResult<SimulationR
         &deps.querier,
         config.factory_addr,
         config.pair_info.pair_type,
+        Some(config.pair_info.contract_addr),
     )?;
 
+    let fee_rate = swap_fee_rate(deps.storage, &env, &pools, fee_info.total_fee_rate)?;
+
     let (return_amount, spread_amount, commission_amount) = compute_swap(
         offer_pool.amount,
         ask_pool.amount,
         offer_asset.amount,
-        fee_info.total_fee_rate,
+        fee_rate,
     )?;
 
     Ok(SimulationResponse {
         return_amount,
         spread_amount,
         commission_amount,
+        fee_rate,
     })
 }
 
@@ -939,15 +1894,25 @@ pub fn query_simulation(deps: Deps, offer_asset: Asset) -> StdResult<SimulationR
 /// ## Params
 /// * **deps** is the object of type [`Deps`].
 ///
+/// * **env** is the object of type [`Env`].
+///
 /// * **ask_asset** is the object of type [`Asset`].
+///
+/// * **offer_asset_info** is the object of type [`Option<AssetInfo>`]. If it is specified it must be
+/// the pool asset opposite to the ask asset.
 pub fn query_reverse_simulation(
     deps: Deps,
+    env: Env,
     ask_asset: Asset,
+    offer_asset_info: Option<AssetInfo>,
 ) -> StdResult<ReverseSimulationResponse> {
     let config: Config = CONFIG.load(deps.storage)?;
+    // The offer asset must be the pool asset opposite to the ask asset, as in a swap
+    assert_ask_asset(&config, &ask_asset.info, offer_asset_info.as_ref())
+        .map_err(|err| StdError::generic_err(err.to_string()))?;
     let contract_addr = config.pair_info.contract_addr.clone();
 
-    let pools: [Asset; 2] = config.pair_info.query_pools(&deps.querier, contract_addr)?;
+    let pools: Vec<Asset> = config.pair_info.query_pools(&deps.querier, contract_addr)?;
 
     let offer_pool: Asset;
     let ask_pool: Asset;
@@ -968,13 +1933,16 @@ pub fn query_reverse_simulation(
         &deps.querier,
         config.factory_addr,
         config.pair_info.pair_type,
+        Some(config.pair_info.contract_addr),
     )?;
 
+    let fee_rate = swap_fee_rate(deps.storage, &env, &pools, fee_info.total_fee_rate)?;
+
     let (offer_amount, spread_amount, commission_amount) = compute_offer_amount(
         offer_pool.amount,
         ask_pool.amount,
         ask_asset.amount,
-        fee_info.total_fee_rate,
+        fee_rate,
     )?;
 
     Ok(ReverseSimulationResponse {
@@ -1004,25 +1972,82 @@ pub fn query_cumulative_prices(deps: Deps, env: Env) -> StdResult<CumulativePric
         price1_cumulative_last = price1_cumulative_new;
     }
 
+    let cumulative_prices = vec![
+        (
+            assets[0].info.clone(),
+            assets[1].info.clone(),
+            price0_cumulative_last,
+        ),
+        (
+            assets[1].info.clone(),
+            assets[0].info.clone(),
+            price1_cumulative_last,
+        ),
+    ];
+
     let resp = CumulativePricesResponse {
         assets,
         total_share,
         price0_cumulative_last,
         price1_cumulative_last,
+        cumulative_prices,
     };
 
     Ok(resp)
 }
 
+/// ## Description
+/// Returns the cumulative prices at each of the specified number of seconds before the current
+/// block in an [`ObserveResponse`] object.
+/// ## Params
+/// * **deps** is the object of type [`Deps`].
+///
+/// * **env** is the object of type [`Env`].
+///
+/// * **seconds_ago** is a vector of type [`u64`]. These are the numbers of seconds before the current block.
+pub fn query_observe(deps: Deps, env: Env, seconds_ago: Vec<u64>) -> StdResult<ObserveResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let (assets, _) = pool_info(deps, config.clone())?;
+
+    let mut current = Observation {
+        timestamp: config.block_time_last,
+        price0_cumulative: config.price0_cumulative_last,
+        price1_cumulative: config.price1_cumulative_last,
+    };
+
+    if let Some((price0_cumulative_new, price1_cumulative_new, block_time)) =
+        accumulate_prices(env, &config, assets[0].amount, assets[1].amount)?
+    {
+        current = Observation {
+            timestamp: block_time,
+            price0_cumulative: price0_cumulative_new,
+            price1_cumulative: price1_cumulative_new,
+        };
+    }
+
+    let observations = seconds_ago
+        .into_iter()
+        .map(|seconds_ago| observe(deps.storage, &current, seconds_ago))
+        .collect::<StdResult<Vec<Observation>>>()?;
+
+    Ok(ObserveResponse { observations })
+}
+
 /// ## Description
 /// Returns information about the controls settings in a [`ConfigResponse`] object.
 /// ## Params
 /// * **deps** is the object of type [`Deps`].
-pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+///
+/// * **env** is the object of type [`Env`].
+pub fn query_config(deps: Deps, env: Env) -> StdResult<ConfigResponse> {
     let config: Config = CONFIG.load(deps.storage)?;
     Ok(ConfigResponse {
         block_time_last: config.block_time_last,
-        params: None,
+        params: Some(to_binary(&XykPoolConfig {
+            dynamic_fee: DYNAMIC_FEE_PARAMS.may_load(deps.storage)?,
+            proxies: config.proxy_contracts,
+        })?),
+        paused: query_pair_paused(&deps.querier, config.factory_addr, env.contract.address)?,
     })
 }
 
@@ -1081,6 +2106,57 @@ pub fn compute_swap(
     ))
 }
 
+/// ## Description
+/// Returns the amount of the provided asset that should be swapped before the provision so that
+/// the rest of the asset and the swap result are in the pool ratio after the swap.
+/// The commission is taken from the return amount and stays in the pool, so the amount `s` is
+/// the positive root of `s^2 + ((2 - fee) * offer_pool - fee * amount) * s - amount * offer_pool = 0`.
+/// ## Params
+/// * **offer_pool** is the object of type [`Uint128`]. Sets the pool of the provided asset.
+///
+/// * **amount** is the object of type [`Uint128`]. Sets the provided amount.
+///
+/// * **commission_rate** is the object of type [`Decimal`]. Sets the commission rate.
+pub fn compute_zap_swap_amount(
+    offer_pool: Uint128,
+    amount: Uint128,
+    commission_rate: Decimal,
+) -> StdResult<Uint128> {
+    let overflow = || StdError::generic_err("Overflow in the swap amount computation");
+
+    // The commission rate is scaled by the precision to keep the computation in integers
+    let precision = U256::from(COMMISSION_RATE_PRECISION);
+    let fee = U256::from((commission_rate * Uint128::from(COMMISSION_RATE_PRECISION)).u128());
+    let offer_pool = U256::from(offer_pool.u128());
+    let amount = U256::from(amount.u128());
+
+    // b = (2 - fee) * offer_pool - fee * amount, scaled by the precision
+    let b_pos = (U256::from(2u8) * precision - fee)
+        .checked_mul(offer_pool)
+        .ok_or_else(overflow)?;
+    let b_neg = fee.checked_mul(amount).ok_or_else(overflow)?;
+    let b = if b_pos > b_neg {
+        b_pos - b_neg
+    } else {
+        b_neg - b_pos
+    };
+
+    // 4 * amount * offer_pool, scaled by the squared precision
+    let c = (amount * offer_pool)
+        .checked_mul(U256::from(4u8) * precision * precision)
+        .ok_or_else(overflow)?;
+    let root = b
+        .checked_mul(b)
+        .and_then(|b_square| b_square.checked_add(c))
+        .ok_or_else(overflow)?
+        .integer_sqrt();
+
+    let swap_amount =
+        if b_pos > b_neg { root - b } else { root + b } / (U256::from(2u8) * precision);
+
+    Ok(Uint128::new(swap_amount.as_u128()))
+}
+
 /// ## Description
 /// Returns computed offer amount for the pool with specified parameters.
 /// ## Params
@@ -1175,8 +2251,8 @@ pub fn assert_max_spread(
 /// * **pools** are an array of [`Asset`] type items.
 fn assert_slippage_tolerance(
     slippage_tolerance: Option<Decimal>,
-    deposits: &[Uint128; 2],
-    pools: &[Asset; 2],
+    deposits: &[Uint128],
+    pools: &[Asset],
 ) -> Result<(), ContractError> {
     let default_slippage = Decimal::from_str(DEFAULT_SLIPPAGE)?;
     let max_allowed_slippage = Decimal::from_str(MAX_ALLOWED_SLIPPAGE)?;
@@ -1206,14 +2282,43 @@ fn assert_slippage_tolerance(
 /// ## Description
 /// Used for migration of contract. Returns the default object of type [`Response`].
 /// ## Params
-/// * **_deps** is the object of type [`DepsMut`].
+/// * **deps** is the object of type [`DepsMut`].
 ///
 /// * **_env** is the object of type [`Env`].
 ///
 /// * **_msg** is the object of type [`MigrateMsg`].
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
-    Ok(Response::default())
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let contract_version = get_contract_version(deps.storage)?;
+
+    match contract_version.contract.as_ref() {
+        "astroport-pair" => match contract_version.version.as_ref() {
+            "1.0.0" => {
+                let config_v100 = migration::CONFIGV100.load(deps.storage)?;
+
+                let new_config = Config {
+                    pair_info: config_v100.pair_info,
+                    factory_addr: config_v100.factory_addr,
+                    block_time_last: config_v100.block_time_last,
+                    price0_cumulative_last: config_v100.price0_cumulative_last,
+                    price1_cumulative_last: config_v100.price1_cumulative_last,
+                    proxy_contracts: Some(vec![config_v100.proxy_contract_addr]),
+                };
+
+                CONFIG.save(deps.storage, &new_config)?;
+            }
+            _ => return Err(ContractError::MigrationError {}),
+        },
+        _ => return Err(ContractError::MigrationError {}),
+    }
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("previous_contract_name", &contract_version.contract)
+        .add_attribute("previous_contract_version", &contract_version.version)
+        .add_attribute("new_contract_name", CONTRACT_NAME)
+        .add_attribute("new_contract_version", CONTRACT_VERSION))
 }
 
 /// ## Description
@@ -1222,9 +2327,9 @@ pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Respons
 /// * **deps** is the object of type [`Deps`].
 ///
 /// * **config** is the object of type [`Config`].
-pub fn pool_info(deps: Deps, config: Config) -> StdResult<([Asset; 2], Uint128)> {
+pub fn pool_info(deps: Deps, config: Config) -> StdResult<(Vec<Asset>, Uint128)> {
     let contract_addr = config.pair_info.contract_addr.clone();
-    let pools: [Asset; 2] = config.pair_info.query_pools(&deps.querier, contract_addr)?;
+    let pools: Vec<Asset> = config.pair_info.query_pools(&deps.querier, contract_addr)?;
     let total_share: Uint128 = query_supply(&deps.querier, config.pair_info.liquidity_token)?;
 
     Ok((pools, total_share))
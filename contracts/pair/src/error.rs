@@ -1,3 +1,6 @@
+use astroport::flash_loan::FlashLoanError;
+use astroport::pair::DeadlineExpired;
+use astroport::querier::PauseError;
 use cosmwasm_std::{OverflowError, StdError};
 use thiserror::Error;
 
@@ -26,9 +29,24 @@ pub enum ContractError {
     #[error("Operation exceeds max splippage tolerance")]
     MaxSlippageAssertion {},
 
+    #[error("The required offer amount exceeds the max offer amount or the sent amount")]
+    MaxOfferAmountAssertion {},
+
+    #[error("The return amount is less than the minimum receive amount")]
+    MinimumReceiveAssertion {},
+
+    #[error("The minted LP amount is less than the minimum LP amount to receive")]
+    MinLpToReceiveAssertion {},
+
+    #[error("The deadline has passed")]
+    DeadlineExpired {},
+
     #[error("Doubling assets in asset infos")]
     DoublingAssets {},
 
+    #[error("The pool must consist of exactly 2 assets")]
+    InvalidNumberOfAssets {},
+
     #[error("Asset mismatch between the requested and stored in contract")]
     AssetMismatch {},
 
@@ -37,6 +55,24 @@ pub enum ContractError {
 
     #[error("GeneratorAddress is not set in factory. Cannot autostake")]
     AutoStakeError {},
+
+    #[error("The pool has no liquidity")]
+    EmptyPool {},
+
+    #[error("Invalid dynamic fee parameters")]
+    InvalidDynamicFeeParams {},
+
+    #[error("A flash loan is in progress")]
+    FlashLoanInProgress {},
+
+    #[error("The flash loan was not repaid with the fee")]
+    FlashLoanNotRepaid {},
+
+    #[error("The pair is paused")]
+    Paused {},
+
+    #[error("Contract can't be migrated!")]
+    MigrationError {},
 }
 
 impl From<OverflowError> for ContractError {
@@ -44,3 +80,31 @@ impl From<OverflowError> for ContractError {
         StdError::from(o).into()
     }
 }
+
+impl From<FlashLoanError> for ContractError {
+    fn from(err: FlashLoanError) -> Self {
+        match err {
+            FlashLoanError::Std(err) => ContractError::Std(err),
+            FlashLoanError::Unauthorized => ContractError::Unauthorized {},
+            FlashLoanError::InvalidZeroAmount => ContractError::InvalidZeroAmount {},
+            FlashLoanError::DoublingAssets => ContractError::DoublingAssets {},
+            FlashLoanError::AssetMismatch => ContractError::AssetMismatch {},
+            FlashLoanError::NotRepaid => ContractError::FlashLoanNotRepaid {},
+        }
+    }
+}
+
+impl From<DeadlineExpired> for ContractError {
+    fn from(_: DeadlineExpired) -> Self {
+        ContractError::DeadlineExpired {}
+    }
+}
+
+impl From<PauseError> for ContractError {
+    fn from(err: PauseError) -> Self {
+        match err {
+            PauseError::Std(err) => ContractError::Std(err),
+            PauseError::Paused => ContractError::Paused {},
+        }
+    }
+}
@@ -2,6 +2,7 @@ pub mod contract;
 pub mod state;
 
 mod error;
+mod migration;
 
 mod response;
 
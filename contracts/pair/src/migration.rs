@@ -0,0 +1,24 @@
+use astroport::asset::PairInfo;
+use cosmwasm_std::{Addr, Uint128};
+use cw_storage_plus::Item;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// This structure describes the main control config of pair.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigV100 {
+    /// the type of pair info available in [`PairInfo`]
+    pub pair_info: PairInfo,
+    /// the factory contract address
+    pub factory_addr: Addr,
+    /// The last time block
+    pub block_time_last: u64,
+    /// The last cumulative price 0 asset in pool
+    pub price0_cumulative_last: Uint128,
+    /// The last cumulative price 1 asset in pool
+    pub price1_cumulative_last: Uint128,
+    /// The proxy contract address
+    pub proxy_contract_addr: Addr,
+}
+
+pub const CONFIGV100: Item<ConfigV100> = Item::new("config");
@@ -1,5 +1,6 @@
 use astroport::asset::PairInfo;
-use cosmwasm_std::{Addr, Uint128};
+use astroport::pair::DynamicFeeParams;
+use cosmwasm_std::{Addr, Decimal, Uint128};
 use cw_storage_plus::Item;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -18,10 +19,28 @@ pub struct Config {
     pub price0_cumulative_last: Uint128,
     /// The last cumulative price 1 asset in pool
     pub price1_cumulative_last: Uint128,
-    /// The proxy contract address
-    pub proxy_contract_addr: Addr,
+    /// The proxy contracts allowed to swap and provide liquidity. Any sender is allowed if it is not set
+    pub proxy_contracts: Option<Vec<Addr>>,
 }
 
 /// ## Description
 /// Stores config at the given key
 pub const CONFIG: Item<Config> = Item::new("config");
+
+/// ## Description
+/// This structure describes the pool price at the start of the current dynamic fee window.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PriceSnapshot {
+    /// The price of the first pool asset denominated in the second one
+    pub price: Decimal,
+    /// The time at which the window started
+    pub block_time: u64,
+}
+
+/// ## Description
+/// Stores the dynamic fee settings. The fee set in the factory is charged if they are not set
+pub const DYNAMIC_FEE_PARAMS: Item<DynamicFeeParams> = Item::new("dynamic_fee_params");
+
+/// ## Description
+/// Stores the pool price at the start of the current dynamic fee window
+pub const PRICE_SNAPSHOT: Item<PriceSnapshot> = Item::new("price_snapshot");
@@ -1,7 +1,8 @@
 use crate::contract::reply;
 use crate::contract::{
-    accumulate_prices, assert_max_spread, compute_swap, execute, instantiate, query_pair_info,
-    query_pool, query_reverse_simulation, query_share, query_simulation,
+    accumulate_prices, assert_max_spread, compute_swap, compute_zap_swap_amount, execute,
+    instantiate, query_config, query_observe, query_pair_info, query_pool,
+    query_reverse_simulation, query_share, query_simulation,
 };
 use crate::error::ContractError;
 use crate::mock_querier::mock_dependencies;
@@ -11,19 +12,22 @@ use astroport::asset::{Asset, AssetInfo, PairInfo};
 use astroport::factory::PairType;
 
 use astroport::pair::{
-    Cw20HookMsg, ExecuteMsg, InstantiateMsg, PoolResponse, ReverseSimulationResponse,
-    SimulationResponse, XykPoolParamsForProxy, TWAP_PRECISION,
+    Cw20HookMsg, DynamicFeeParams, ExecuteMsg, InstantiateMsg, Observation, PoolResponse,
+    ProvideResponse, ReverseSimulationResponse, SimulationResponse, SwapResponse, WithdrawResponse,
+    XykPoolConfig, XykPoolParamsForProxy, XykPoolUpdateParams, TWAP_PRECISION,
 };
 use astroport::token::InstantiateMsg as TokenInstantiateMsg;
+use astroport::U256;
 use cosmwasm_std::testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR};
 use cosmwasm_std::{
-    attr, to_binary, Addr, BankMsg, BlockInfo, Coin, ContractResult, CosmosMsg, Decimal, DepsMut,
-    Env, Reply, ReplyOn, Response, StdError, SubMsg, SubMsgExecutionResponse, Timestamp, Uint128,
-    WasmMsg,
+    attr, from_binary, to_binary, Addr, BankMsg, Binary, BlockInfo, Coin, ContractResult,
+    CosmosMsg, Decimal, Deps, DepsMut, Env, Reply, ReplyOn, Response, StdError, SubMsg,
+    SubMsgExecutionResponse, Timestamp, Uint128, WasmMsg,
 };
 use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg, MinterResponse};
 use proptest::prelude::*;
 use protobuf::Message;
+use std::str::FromStr;
 
 fn store_liquidity_token(deps: DepsMut, msg_id: u64, contract_addr: String) {
     let data = MsgInstantiateContractResponse {
@@ -57,7 +61,7 @@ fn proper_initialization() {
 
     let msg = InstantiateMsg {
         factory_addr: String::from("factory"),
-        asset_infos: [
+        asset_infos: vec![
             AssetInfo::NativeToken {
                 denom: "uusd".to_string(),
             },
@@ -612,7 +616,7 @@ fn proper_initialization() {
 //     // withdraw liquidity
 //     let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
 //         sender: String::from("addr0000"),
-//         msg: to_binary(&Cw20HookMsg::WithdrawLiquidity {}).unwrap(),
+//         msg: to_binary(&Cw20HookMsg::WithdrawLiquidity { withdraw_type: None }).unwrap(),
 //         amount: Uint128::new(100u128),
 //     });
 
@@ -1132,6 +1136,7 @@ fn test_max_spread() {
     .unwrap();
 }
 
+#[cfg(feature = "terra")]
 #[test]
 fn test_deduct() {
     let mut deps = mock_dependencies(&[]);
@@ -1183,7 +1188,7 @@ fn test_query_pool() {
     ]);
 
     let msg = InstantiateMsg {
-        asset_infos: [
+        asset_infos: vec![
             AssetInfo::NativeToken {
                 denom: "uusd".to_string(),
             },
@@ -1254,7 +1259,7 @@ fn test_query_share() {
     ]);
 
     let msg = InstantiateMsg {
-        asset_infos: [
+        asset_infos: vec![
             AssetInfo::NativeToken {
                 denom: "uusd".to_string(),
             },
@@ -1367,7 +1372,7 @@ fn test_accumulate_prices() {
             env,
             &Config {
                 pair_info: PairInfo {
-                    asset_infos: [
+                    asset_infos: vec![
                         AssetInfo::NativeToken {
                             denom: "uusd".to_string(),
                         },
@@ -1383,7 +1388,7 @@ fn test_accumulate_prices() {
                 block_time_last: case.block_time_last,
                 price0_cumulative_last: Uint128::new(case.last0),
                 price1_cumulative_last: Uint128::new(case.last1),
-                proxy_contract_addr: Addr::unchecked("proxy"),
+                proxy_contracts: Some(vec![Addr::unchecked("proxy")]),
             },
             Uint128::new(case.x_amount),
             Uint128::new(case.y_amount),
@@ -1416,6 +1421,153 @@ fn mock_env_with_block_time(time: u64) -> Env {
     env
 }
 
+#[test]
+fn provide_single_sided() {
+    let mut deps = mock_dependencies(&[Coin {
+        denom: "uusd".to_string(),
+        amount: Uint128::new(100_000_000000u128 + 2_000_000000u128),
+    }]);
+
+    deps.querier.with_token_balances(&[
+        (
+            &String::from("liquidity0000"),
+            &[(&String::from("addr0000"), &Uint128::new(100_000_000000u128))],
+        ),
+        (
+            &String::from("asset0000"),
+            &[(
+                &String::from(MOCK_CONTRACT_ADDR),
+                &Uint128::new(100_000_000000u128),
+            )],
+        ),
+    ]);
+
+    let msg = InstantiateMsg {
+        factory_addr: String::from("factory"),
+        asset_infos: vec![
+            AssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            },
+            AssetInfo::Token {
+                contract_addr: Addr::unchecked("asset0000"),
+            },
+        ],
+        token_code_id: 10u64,
+        init_params: Some(
+            to_binary(&XykPoolParamsForProxy {
+                proxy: String::from("proxy_contract_addr"),
+            })
+            .unwrap(),
+        ),
+    };
+
+    let env = mock_env();
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), env, info, msg).unwrap();
+
+    // store liquidity token
+    store_liquidity_token(deps.as_mut(), 1, "liquidity0000".to_string());
+
+    let asset = Asset {
+        info: AssetInfo::NativeToken {
+            denom: "uusd".to_string(),
+        },
+        amount: Uint128::new(2_000_000000u128),
+    };
+    let funds = [Coin {
+        denom: "uusd".to_string(),
+        amount: Uint128::new(2_000_000000u128),
+    }];
+
+    // only the proxy can provide liquidity
+    let msg = ExecuteMsg::ProvideSingleSided {
+        asset: asset.clone(),
+        slippage_tolerance: Some(Decimal::percent(2)),
+        auto_stake: None,
+        receiver: Some(String::from("addr0000")),
+    };
+    let info = mock_info("addr0000", &funds);
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+
+    // the spread of the internal swap exceeds the default limit
+    let msg = ExecuteMsg::ProvideSingleSided {
+        asset: asset.clone(),
+        slippage_tolerance: None,
+        auto_stake: None,
+        receiver: Some(String::from("addr0000")),
+    };
+    let info = mock_info("proxy_contract_addr", &funds);
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+    assert_eq!(res, ContractError::MaxSpreadAssertion {});
+
+    let msg = ExecuteMsg::ProvideSingleSided {
+        asset,
+        slippage_tolerance: Some(Decimal::percent(2)),
+        auto_stake: None,
+        receiver: Some(String::from("addr0000")),
+    };
+    let info = mock_info("proxy_contract_addr", &funds);
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let swap_amount = compute_zap_swap_amount(
+        Uint128::new(100_000_000000u128),
+        Uint128::new(2_000_000000u128),
+        Decimal::permille(3),
+    )
+    .unwrap();
+    let (return_amount, _, commission_amount) = compute_swap(
+        Uint128::new(100_000_000000u128),
+        Uint128::new(100_000_000000u128),
+        swap_amount,
+        Decimal::permille(3),
+    )
+    .unwrap();
+    let maker_fee_amount = commission_amount * Decimal::from_ratio(1660u64, 10000u64);
+
+    // roughly half of the asset is swapped
+    assert!(swap_amount > Uint128::new(985_000000u128));
+    assert!(swap_amount < Uint128::new(1_000_000000u128));
+
+    let share = std::cmp::min(
+        (Uint128::new(2_000_000000u128) - swap_amount)
+            .multiply_ratio(100_000_000000u128, 100_000_000000u128 + swap_amount.u128()),
+        return_amount.multiply_ratio(
+            100_000_000000u128,
+            100_000_000000u128 - return_amount.u128() - maker_fee_amount.u128(),
+        ),
+    );
+    assert!(share > Uint128::new(985_000000u128));
+    assert!(share < Uint128::new(1_000_000000u128));
+
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: String::from("asset0000"),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: String::from("fee_address"),
+                    amount: maker_fee_amount,
+                })
+                .unwrap(),
+                funds: vec![],
+            }),
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: String::from("liquidity0000"),
+                msg: to_binary(&Cw20ExecuteMsg::Mint {
+                    recipient: String::from("addr0000"),
+                    amount: share,
+                })
+                .unwrap(),
+                funds: vec![],
+            }),
+        ]
+    );
+    assert_eq!(
+        res.attributes.get(4).expect("no log"),
+        &attr("swap_amount", swap_amount.to_string())
+    );
+}
+
 #[test]
 fn compute_swap_rounding() {
     let offer_pool = Uint128::from(5_000_000_000_000_u128);
@@ -1453,4 +1605,1556 @@ proptest! {
             commission_amount,
         ).unwrap();
     }
+
+    #[test]
+    fn compute_zap_swap_amount_test(
+        offer_pool in 1_000_000_000..9_000_000_000_000_000_000u128,
+        price_bps in 1..100_000_000u128,
+        amount in 1_000_000_000..100_000_000_000_000u128,
+        fee_bps in 0..1000u64,
+    ) {
+        let ask_pool = offer_pool * price_bps / 10000;
+        let commission_rate = Decimal::from_ratio(fee_bps, 10000u64);
+
+        let swap_amount = compute_zap_swap_amount(
+            Uint128::from(offer_pool),
+            Uint128::from(amount),
+            commission_rate,
+        ).unwrap();
+        let (return_amount, _, _) = compute_swap(
+            Uint128::from(offer_pool),
+            Uint128::from(ask_pool),
+            swap_amount,
+            commission_rate,
+        ).unwrap();
+
+        // The rest of the asset and the swap result are in the pool ratio after the swap
+        let deposit_side = U256::from(amount - swap_amount.u128())
+            * U256::from(ask_pool - return_amount.u128());
+        let return_side = U256::from(return_amount.u128())
+            * U256::from(offer_pool + swap_amount.u128());
+        let diff = if deposit_side > return_side {
+            deposit_side - return_side
+        } else {
+            return_side - deposit_side
+        };
+
+        assert!(
+            diff * U256::from(10000u64) <= deposit_side,
+            "swap_amount={}, return_amount={}, offer_pool={}, ask_pool={}, amount={}, fee_bps={}",
+            swap_amount,
+            return_amount,
+            offer_pool,
+            ask_pool,
+            amount,
+            fee_bps
+        );
+    }
+}
+
+#[test]
+fn flash_loan() {
+    let mut deps = mock_dependencies(&[Coin {
+        denom: "uusd".to_string(),
+        amount: Uint128::new(100_000_000000u128),
+    }]);
+
+    deps.querier.with_token_balances(&[
+        (
+            &String::from("liquidity0000"),
+            &[(&String::from("addr0000"), &Uint128::new(100_000_000000u128))],
+        ),
+        (
+            &String::from("asset0000"),
+            &[(
+                &String::from(MOCK_CONTRACT_ADDR),
+                &Uint128::new(100_000_000000u128),
+            )],
+        ),
+    ]);
+
+    let msg = InstantiateMsg {
+        factory_addr: String::from("factory"),
+        asset_infos: vec![
+            AssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            },
+            AssetInfo::Token {
+                contract_addr: Addr::unchecked("asset0000"),
+            },
+        ],
+        token_code_id: 10u64,
+        init_params: Some(
+            to_binary(&XykPoolParamsForProxy {
+                proxy: String::from("proxy_contract_addr"),
+            })
+            .unwrap(),
+        ),
+    };
+
+    let env = mock_env();
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), env, info, msg).unwrap();
+
+    // store liquidity token
+    store_liquidity_token(deps.as_mut(), 1, "liquidity0000".to_string());
+
+    let assets = vec![
+        Asset {
+            info: AssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            },
+            amount: Uint128::new(10_000_000000u128),
+        },
+        Asset {
+            info: AssetInfo::Token {
+                contract_addr: Addr::unchecked("asset0000"),
+            },
+            amount: Uint128::new(1_000_000000u128),
+        },
+    ];
+
+    // Only pool assets can be borrowed
+    let msg = ExecuteMsg::FlashLoan {
+        assets: vec![Asset {
+            info: AssetInfo::NativeToken {
+                denom: "uluna".to_string(),
+            },
+            amount: Uint128::new(1_000000u128),
+        }],
+        callback: Binary::default(),
+    };
+    let err = execute(deps.as_mut(), mock_env(), mock_info("borrower", &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::AssetMismatch {});
+
+    let msg = ExecuteMsg::FlashLoan {
+        assets: assets.clone(),
+        callback: Binary::from(b"callback".to_vec()),
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("borrower", &[]), msg).unwrap();
+
+    // 0.3% fee
+    let expected_balances = vec![
+        Asset {
+            info: assets[0].info.clone(),
+            amount: Uint128::new(100_030_000000u128),
+        },
+        Asset {
+            info: assets[1].info.clone(),
+            amount: Uint128::new(100_003_000000u128),
+        },
+    ];
+    let fees = vec![
+        Asset {
+            info: assets[0].info.clone(),
+            amount: Uint128::new(30_000000u128),
+        },
+        Asset {
+            info: assets[1].info.clone(),
+            amount: Uint128::new(3_000000u128),
+        },
+    ];
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(BankMsg::Send {
+                to_address: String::from("borrower"),
+                amount: vec![Coin {
+                    denom: "uusd".to_string(),
+                    amount: Uint128::new(10_000_000000u128),
+                }],
+            }),
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: String::from("asset0000"),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: String::from("borrower"),
+                    amount: Uint128::new(1_000_000000u128),
+                })
+                .unwrap(),
+                funds: vec![],
+            }),
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: String::from("borrower"),
+                msg: Binary::from(b"callback".to_vec()),
+                funds: vec![],
+            }),
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: String::from(MOCK_CONTRACT_ADDR),
+                msg: to_binary(&ExecuteMsg::AssertFlashLoanRepaid {
+                    expected_balances: expected_balances.clone(),
+                    fees: fees.clone(),
+                })
+                .unwrap(),
+                funds: vec![],
+            }),
+        ]
+    );
+
+    // The pair is locked until the loan is repaid
+    let msg = ExecuteMsg::ProvideLiquidity {
+        assets: assets.clone(),
+        slippage_tolerance: None,
+        auto_stake: None,
+        receiver: None,
+        min_lp_to_receive: None,
+        deadline: None,
+    };
+    let err = execute(deps.as_mut(), mock_env(), mock_info("borrower", &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::FlashLoanInProgress {});
+
+    let msg = ExecuteMsg::AssertFlashLoanRepaid {
+        expected_balances: expected_balances.clone(),
+        fees: fees.clone(),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("borrower", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // Repaid without the fee
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::FlashLoanNotRepaid {});
+
+    // Repaid with the fee, the maker gets its share of the fee
+    deps.querier.with_balance(&[(
+        &String::from(MOCK_CONTRACT_ADDR),
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(100_030_000000u128),
+        }],
+    )]);
+    deps.querier.with_token_balances(&[(
+        &String::from("asset0000"),
+        &[(
+            &String::from(MOCK_CONTRACT_ADDR),
+            &Uint128::new(100_003_000000u128),
+        )],
+    )]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        msg,
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(BankMsg::Send {
+                to_address: String::from("fee_address"),
+                amount: vec![Coin {
+                    denom: "uusd".to_string(),
+                    amount: Uint128::new(4_980000u128),
+                }],
+            }),
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: String::from("asset0000"),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: String::from("fee_address"),
+                    amount: Uint128::new(498000u128),
+                })
+                .unwrap(),
+                funds: vec![],
+            }),
+        ]
+    );
+
+    // The pair is unlocked
+    let msg = ExecuteMsg::FlashLoan {
+        assets: vec![assets[0].clone(), assets[0].clone()],
+        callback: Binary::default(),
+    };
+    let err = execute(deps.as_mut(), mock_env(), mock_info("borrower", &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::DoublingAssets {});
+}
+
+#[test]
+fn dynamic_fee() {
+    let mut deps = mock_dependencies(&[Coin {
+        denom: "uusd".to_string(),
+        amount: Uint128::new(1_000_000000),
+    }]);
+    deps.querier.with_token_balances(&[(
+        &String::from("asset0000"),
+        &[(
+            &String::from(MOCK_CONTRACT_ADDR),
+            &Uint128::new(1_000_000000),
+        )],
+    )]);
+
+    let msg = InstantiateMsg {
+        factory_addr: String::from("factory"),
+        asset_infos: vec![
+            AssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            },
+            AssetInfo::Token {
+                contract_addr: Addr::unchecked("asset0000"),
+            },
+        ],
+        token_code_id: 10u64,
+        init_params: Some(
+            to_binary(&XykPoolParamsForProxy {
+                proxy: String::from("proxy_contract_addr"),
+            })
+            .unwrap(),
+        ),
+    };
+
+    let env = mock_env_with_block_time(1000);
+    instantiate(deps.as_mut(), env.clone(), mock_info("addr0000", &[]), msg).unwrap();
+    store_liquidity_token(deps.as_mut(), 1, "liquidity0000".to_string());
+
+    let params = DynamicFeeParams {
+        min_fee_bps: 10,
+        max_fee_bps: 100,
+        max_price_change: Decimal::percent(10),
+        window: 600,
+    };
+    let msg = ExecuteMsg::UpdateConfig {
+        params: to_binary(&XykPoolUpdateParams::UpdateDynamicFee {
+            params: Some(params.clone()),
+        })
+        .unwrap(),
+    };
+
+    // Only the factory owner can update the config
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let invalid_msg = ExecuteMsg::UpdateConfig {
+        params: to_binary(&XykPoolUpdateParams::UpdateDynamicFee {
+            params: Some(DynamicFeeParams {
+                min_fee_bps: 200,
+                ..params.clone()
+            }),
+        })
+        .unwrap(),
+    };
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("owner", &[]),
+        invalid_msg,
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidDynamicFeeParams {});
+
+    execute(deps.as_mut(), env.clone(), mock_info("owner", &[]), msg).unwrap();
+
+    let config = query_config(deps.as_ref(), env.clone()).unwrap();
+    let pool_config: XykPoolConfig = from_binary(&config.params.unwrap()).unwrap();
+    assert_eq!(pool_config.dynamic_fee, Some(params));
+
+    let offer_asset = Asset {
+        info: AssetInfo::NativeToken {
+            denom: "uusd".to_string(),
+        },
+        amount: Uint128::new(10_000000),
+    };
+
+    // The price didn't move yet
+    let res = query_simulation(deps.as_ref(), env.clone(), offer_asset.clone(), None).unwrap();
+    assert_eq!(res.fee_rate, Decimal::permille(1));
+
+    deps.querier.with_balance(&[(
+        &String::from(MOCK_CONTRACT_ADDR),
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(1_010_000000),
+        }],
+    )]);
+    let msg = ExecuteMsg::Swap {
+        offer_asset: offer_asset.clone(),
+        ask_asset_info: None,
+        belief_price: None,
+        max_spread: Some(Decimal::percent(2)),
+        to: None,
+        minimum_receive: None,
+        deadline: None,
+    };
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(
+            "proxy_contract_addr",
+            &[Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::new(10_000000),
+            }],
+        ),
+        msg,
+    )
+    .unwrap();
+    assert!(res.attributes.contains(&attr("fee_rate", "0.001")));
+
+    // The price moved by 5% since the start of the window
+    deps.querier.with_balance(&[(
+        &String::from(MOCK_CONTRACT_ADDR),
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(1_000_000000),
+        }],
+    )]);
+    deps.querier.with_token_balances(&[(
+        &String::from("asset0000"),
+        &[(
+            &String::from(MOCK_CONTRACT_ADDR),
+            &Uint128::new(1_050_000000),
+        )],
+    )]);
+    let res = query_simulation(
+        deps.as_ref(),
+        mock_env_with_block_time(1300),
+        offer_asset.clone(),
+        None,
+    )
+    .unwrap();
+    assert_eq!(res.fee_rate, Decimal::from_ratio(55u128, 10_000u128));
+    assert_eq!(
+        res.commission_amount,
+        (res.return_amount + res.commission_amount) * Decimal::from_ratio(55u128, 10_000u128)
+    );
+
+    // The fee is capped once the price moved by more than 10%
+    deps.querier.with_token_balances(&[(
+        &String::from("asset0000"),
+        &[(
+            &String::from(MOCK_CONTRACT_ADDR),
+            &Uint128::new(1_300_000000),
+        )],
+    )]);
+    let res = query_simulation(
+        deps.as_ref(),
+        mock_env_with_block_time(1300),
+        offer_asset.clone(),
+        None,
+    )
+    .unwrap();
+    assert_eq!(res.fee_rate, Decimal::percent(1));
+
+    // The internal swap of a single sided provide pays the same dynamic fee
+    deps.querier.with_balance(&[(
+        &String::from(MOCK_CONTRACT_ADDR),
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(1_010_000000),
+        }],
+    )]);
+    deps.querier.with_token_balances(&[
+        (
+            &String::from("liquidity0000"),
+            &[(&String::from("addr0000"), &Uint128::new(1_000_000000))],
+        ),
+        (
+            &String::from("asset0000"),
+            &[(
+                &String::from(MOCK_CONTRACT_ADDR),
+                &Uint128::new(1_300_000000),
+            )],
+        ),
+    ]);
+    let msg = ExecuteMsg::ProvideSingleSided {
+        asset: offer_asset.clone(),
+        slippage_tolerance: Some(Decimal::percent(2)),
+        auto_stake: None,
+        receiver: None,
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env_with_block_time(1300),
+        mock_info(
+            "proxy_contract_addr",
+            &[Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::new(10_000000),
+            }],
+        ),
+        msg,
+    )
+    .unwrap();
+    assert!(res.attributes.contains(&attr("fee_rate", "0.01")));
+
+    // A new window starts with the current price
+    let res = query_simulation(
+        deps.as_ref(),
+        mock_env_with_block_time(1600),
+        offer_asset.clone(),
+        None,
+    )
+    .unwrap();
+    assert_eq!(res.fee_rate, Decimal::permille(1));
+
+    // The factory fee applies once the dynamic fee mode is disabled
+    let msg = ExecuteMsg::UpdateConfig {
+        params: to_binary(&XykPoolUpdateParams::UpdateDynamicFee { params: None }).unwrap(),
+    };
+    execute(deps.as_mut(), env.clone(), mock_info("owner", &[]), msg).unwrap();
+    let res = query_simulation(deps.as_ref(), env, offer_asset, None).unwrap();
+    assert_eq!(res.fee_rate, Decimal::permille(3));
+}
+
+#[test]
+fn pause() {
+    let mut deps = mock_dependencies(&[Coin {
+        denom: "uusd".to_string(),
+        amount: Uint128::new(100_000_000000u128),
+    }]);
+
+    deps.querier.with_token_balances(&[
+        (
+            &String::from("liquidity0000"),
+            &[(&String::from("addr0000"), &Uint128::new(100_000_000000u128))],
+        ),
+        (
+            &String::from("asset0000"),
+            &[(
+                &String::from(MOCK_CONTRACT_ADDR),
+                &Uint128::new(100_000_000000u128),
+            )],
+        ),
+    ]);
+
+    let msg = InstantiateMsg {
+        factory_addr: String::from("factory"),
+        asset_infos: vec![
+            AssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            },
+            AssetInfo::Token {
+                contract_addr: Addr::unchecked("asset0000"),
+            },
+        ],
+        token_code_id: 10u64,
+        init_params: Some(
+            to_binary(&XykPoolParamsForProxy {
+                proxy: String::from("proxy_contract_addr"),
+            })
+            .unwrap(),
+        ),
+    };
+
+    let env = mock_env();
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    // store liquidity token
+    store_liquidity_token(deps.as_mut(), 1, "liquidity0000".to_string());
+
+    let config = query_config(deps.as_ref(), env.clone()).unwrap();
+    assert!(!config.paused);
+
+    deps.querier.with_paused(true);
+
+    let config = query_config(deps.as_ref(), env.clone()).unwrap();
+    assert!(config.paused);
+
+    let offer_asset = Asset {
+        info: AssetInfo::NativeToken {
+            denom: "uusd".to_string(),
+        },
+        amount: Uint128::new(1_000000u128),
+    };
+
+    // Swaps are rejected
+    let msg = ExecuteMsg::Swap {
+        offer_asset: offer_asset.clone(),
+        ask_asset_info: None,
+        belief_price: None,
+        max_spread: None,
+        to: None,
+        minimum_receive: None,
+        deadline: None,
+    };
+    let info = mock_info(
+        "proxy_contract_addr",
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(1_000000u128),
+        }],
+    );
+    let err = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
+    assert_eq!(err, ContractError::Paused {});
+
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: String::from("addr0000"),
+        amount: Uint128::new(1_000000u128),
+        msg: to_binary(&Cw20HookMsg::Swap {
+            ask_asset_info: None,
+            belief_price: None,
+            max_spread: None,
+            to: None,
+            minimum_receive: None,
+            deadline: None,
+        })
+        .unwrap(),
+    });
+    let err = execute(deps.as_mut(), env.clone(), mock_info("asset0000", &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::Paused {});
+
+    // Liquidity provisions and flash loans are rejected
+    let msg = ExecuteMsg::ProvideLiquidity {
+        assets: vec![
+            offer_asset.clone(),
+            Asset {
+                info: AssetInfo::Token {
+                    contract_addr: Addr::unchecked("asset0000"),
+                },
+                amount: Uint128::new(1_000000u128),
+            },
+        ],
+        slippage_tolerance: None,
+        auto_stake: None,
+        receiver: None,
+        min_lp_to_receive: None,
+        deadline: None,
+    };
+    let info = mock_info(
+        "proxy_contract_addr",
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(1_000000u128),
+        }],
+    );
+    let err = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
+    assert_eq!(err, ContractError::Paused {});
+
+    let msg = ExecuteMsg::FlashLoan {
+        assets: vec![offer_asset],
+        callback: Binary::from(b"callback".to_vec()),
+    };
+    let err = execute(deps.as_mut(), env.clone(), mock_info("borrower", &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::Paused {});
+
+    // Withdrawals are still allowed
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: String::from("addr0000"),
+        amount: Uint128::new(1_000000u128),
+        msg: to_binary(&Cw20HookMsg::WithdrawLiquidity {
+            withdraw_type: None,
+        })
+        .unwrap(),
+    });
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("liquidity0000", &[]),
+        msg,
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 3);
+
+    deps.querier.with_paused(false);
+
+    let config = query_config(deps.as_ref(), env).unwrap();
+    assert!(!config.paused);
+}
+
+#[test]
+fn update_proxies() {
+    let mut deps = mock_dependencies(&[Coin {
+        denom: "uusd".to_string(),
+        amount: Uint128::new(100_000_000000u128),
+    }]);
+
+    deps.querier.with_token_balances(&[
+        (
+            &String::from("liquidity0000"),
+            &[(&String::from("addr0000"), &Uint128::new(100_000_000000u128))],
+        ),
+        (
+            &String::from("asset0000"),
+            &[(
+                &String::from(MOCK_CONTRACT_ADDR),
+                &Uint128::new(100_000_000000u128),
+            )],
+        ),
+    ]);
+
+    let msg = InstantiateMsg {
+        factory_addr: String::from("factory"),
+        asset_infos: vec![
+            AssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            },
+            AssetInfo::Token {
+                contract_addr: Addr::unchecked("asset0000"),
+            },
+        ],
+        token_code_id: 10u64,
+        init_params: Some(
+            to_binary(&XykPoolParamsForProxy {
+                proxy: String::from("proxy_contract_addr"),
+            })
+            .unwrap(),
+        ),
+    };
+
+    let env = mock_env();
+    instantiate(deps.as_mut(), env.clone(), mock_info("addr0000", &[]), msg).unwrap();
+    store_liquidity_token(deps.as_mut(), 1, "liquidity0000".to_string());
+
+    let query_proxies = |deps: Deps| -> Option<Vec<Addr>> {
+        let config = query_config(deps, mock_env()).unwrap();
+        let pool_config: XykPoolConfig = from_binary(&config.params.unwrap()).unwrap();
+        pool_config.proxies
+    };
+    assert_eq!(
+        query_proxies(deps.as_ref()),
+        Some(vec![Addr::unchecked("proxy_contract_addr")])
+    );
+
+    let swap_msg = ExecuteMsg::Swap {
+        offer_asset: Asset {
+            info: AssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            },
+            amount: Uint128::new(1_000000u128),
+        },
+        ask_asset_info: None,
+        belief_price: None,
+        max_spread: None,
+        to: None,
+        minimum_receive: None,
+        deadline: None,
+    };
+    let swap_info = |sender: &str| {
+        mock_info(
+            sender,
+            &[Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::new(1_000000u128),
+            }],
+        )
+    };
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        swap_info("proxy0001"),
+        swap_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let msg = ExecuteMsg::UpdateConfig {
+        params: to_binary(&XykPoolUpdateParams::UpdateProxies {
+            proxies: Some(vec![
+                String::from("proxy_contract_addr"),
+                String::from("proxy0001"),
+                String::from("proxy0001"),
+            ]),
+        })
+        .unwrap(),
+    };
+
+    // Only the factory or its owner can update the proxies
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    execute(deps.as_mut(), env.clone(), mock_info("factory", &[]), msg).unwrap();
+    assert_eq!(
+        query_proxies(deps.as_ref()),
+        Some(vec![
+            Addr::unchecked("proxy_contract_addr"),
+            Addr::unchecked("proxy0001"),
+        ])
+    );
+
+    // Every allowed proxy can swap
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        swap_info("proxy_contract_addr"),
+        swap_msg.clone(),
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        swap_info("proxy0001"),
+        swap_msg.clone(),
+    )
+    .unwrap();
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        swap_info("addr0000"),
+        swap_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // In the open mode any sender can swap
+    let msg = ExecuteMsg::UpdateConfig {
+        params: to_binary(&XykPoolUpdateParams::UpdateProxies { proxies: None }).unwrap(),
+    };
+    execute(deps.as_mut(), env.clone(), mock_info("owner", &[]), msg).unwrap();
+    assert_eq!(query_proxies(deps.as_ref()), None);
+
+    execute(deps.as_mut(), env, swap_info("addr0000"), swap_msg).unwrap();
+}
+
+#[test]
+fn swap_exact_out() {
+    let mut deps = mock_dependencies(&[Coin {
+        denom: "uusd".to_string(),
+        amount: Uint128::new(100_000_000000u128 + 2_000000u128),
+    }]);
+
+    deps.querier.with_token_balances(&[
+        (
+            &String::from("liquidity0000"),
+            &[(&String::from("addr0000"), &Uint128::new(100_000_000000u128))],
+        ),
+        (
+            &String::from("asset0000"),
+            &[(
+                &String::from(MOCK_CONTRACT_ADDR),
+                &Uint128::new(100_000_000000u128),
+            )],
+        ),
+    ]);
+
+    let msg = InstantiateMsg {
+        factory_addr: String::from("factory"),
+        asset_infos: vec![
+            AssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            },
+            AssetInfo::Token {
+                contract_addr: Addr::unchecked("asset0000"),
+            },
+        ],
+        token_code_id: 10u64,
+        init_params: Some(
+            to_binary(&XykPoolParamsForProxy {
+                proxy: String::from("proxy_contract_addr"),
+            })
+            .unwrap(),
+        ),
+    };
+
+    let env = mock_env();
+    instantiate(deps.as_mut(), env.clone(), mock_info("addr0000", &[]), msg).unwrap();
+    store_liquidity_token(deps.as_mut(), 1, "liquidity0000".to_string());
+
+    let ask_asset = Asset {
+        info: AssetInfo::Token {
+            contract_addr: Addr::unchecked("asset0000"),
+        },
+        amount: Uint128::new(1_000000u128),
+    };
+    let funds = [Coin {
+        denom: "uusd".to_string(),
+        amount: Uint128::new(2_000000u128),
+    }];
+    let swap_msg = |max_offer_amount: u128| ExecuteMsg::SwapExactOut {
+        ask_asset: ask_asset.clone(),
+        max_offer_amount: Uint128::new(max_offer_amount),
+        to: Some(String::from("addr0000")),
+    };
+
+    // Only the proxy can swap native coins
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr0000", &funds),
+        swap_msg(2_000000u128),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // Exactly one coin must be sent
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("proxy_contract_addr", &[]),
+        swap_msg(2_000000u128),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::AssetMismatch {});
+
+    // The required offer exceeds the maximum
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("proxy_contract_addr", &funds),
+        swap_msg(1_000000u128),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::MaxOfferAmountAssertion {});
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("proxy_contract_addr", &funds),
+        swap_msg(2_000000u128),
+    )
+    .unwrap();
+
+    let offer_amount = Uint128::from_str(
+        &res.attributes
+            .iter()
+            .find(|attr| attr.key == "offer_amount")
+            .unwrap()
+            .value,
+    )
+    .unwrap();
+
+    // The offer is just enough to receive the ask amount
+    let (return_amount, _, commission_amount) = compute_swap(
+        Uint128::new(100_000_000000u128),
+        Uint128::new(100_000_000000u128),
+        offer_amount,
+        Decimal::permille(3),
+    )
+    .unwrap();
+    assert!(return_amount >= ask_asset.amount);
+    let (return_amount, _, _) = compute_swap(
+        Uint128::new(100_000_000000u128),
+        Uint128::new(100_000_000000u128),
+        offer_amount - Uint128::new(2u128),
+        Decimal::permille(3),
+    )
+    .unwrap();
+    assert!(return_amount < ask_asset.amount);
+
+    let maker_fee_amount = commission_amount * Decimal::from_ratio(1660u64, 10000u64);
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: String::from("asset0000"),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: String::from("addr0000"),
+                    amount: ask_asset.amount,
+                })
+                .unwrap(),
+                funds: vec![],
+            }),
+            SubMsg::new(BankMsg::Send {
+                to_address: String::from("proxy_contract_addr"),
+                amount: vec![Coin {
+                    denom: "uusd".to_string(),
+                    amount: Uint128::new(2_000000u128) - offer_amount,
+                }],
+            }),
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: String::from("asset0000"),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: String::from("fee_address"),
+                    amount: maker_fee_amount,
+                })
+                .unwrap(),
+                funds: vec![],
+            }),
+        ]
+    );
+
+    // Only the pool tokens can be swapped through the hook
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: String::from("addr0000"),
+        amount: Uint128::new(2_000000u128),
+        msg: to_binary(&Cw20HookMsg::SwapExactOut {
+            ask_asset: Asset {
+                info: AssetInfo::NativeToken {
+                    denom: "uusd".to_string(),
+                },
+                amount: Uint128::new(1_000000u128),
+            },
+            max_offer_amount: Uint128::new(2_000000u128),
+            to: None,
+        })
+        .unwrap(),
+    });
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("asset0001", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let res = execute(deps.as_mut(), env, mock_info("asset0000", &[]), msg).unwrap();
+    assert_eq!(
+        res.messages[0],
+        SubMsg::new(BankMsg::Send {
+            to_address: String::from("addr0000"),
+            amount: vec![Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::new(1_000000u128),
+            }],
+        })
+    );
+}
+
+#[test]
+fn deadline_and_minimum_outputs() {
+    let mut deps = mock_dependencies(&[Coin {
+        denom: "uusd".to_string(),
+        amount: Uint128::new(100_000_000000u128 + 1_000000u128),
+    }]);
+
+    deps.querier.with_token_balances(&[
+        (
+            &String::from("liquidity0000"),
+            &[(&String::from("addr0000"), &Uint128::new(100_000_000000u128))],
+        ),
+        (
+            &String::from("asset0000"),
+            &[(
+                &String::from(MOCK_CONTRACT_ADDR),
+                &Uint128::new(100_000_000000u128),
+            )],
+        ),
+    ]);
+
+    let msg = InstantiateMsg {
+        factory_addr: String::from("factory"),
+        asset_infos: vec![
+            AssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            },
+            AssetInfo::Token {
+                contract_addr: Addr::unchecked("asset0000"),
+            },
+        ],
+        token_code_id: 10u64,
+        init_params: Some(
+            to_binary(&XykPoolParamsForProxy {
+                proxy: String::from("proxy_contract_addr"),
+            })
+            .unwrap(),
+        ),
+    };
+
+    let env = mock_env();
+    instantiate(deps.as_mut(), env.clone(), mock_info("addr0000", &[]), msg).unwrap();
+    store_liquidity_token(deps.as_mut(), 1, "liquidity0000".to_string());
+
+    let offer_asset = Asset {
+        info: AssetInfo::NativeToken {
+            denom: "uusd".to_string(),
+        },
+        amount: Uint128::new(1_000000u128),
+    };
+    let info = mock_info(
+        "proxy_contract_addr",
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(1_000000u128),
+        }],
+    );
+    let swap_msg = |minimum_receive: u128, deadline: u64| ExecuteMsg::Swap {
+        offer_asset: offer_asset.clone(),
+        ask_asset_info: None,
+        belief_price: None,
+        max_spread: None,
+        to: None,
+        minimum_receive: Some(Uint128::new(minimum_receive)),
+        deadline: Some(deadline),
+    };
+
+    // Swaps fail once the deadline has passed
+    let now = env.block.time.seconds();
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        swap_msg(990000u128, now - 1),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::DeadlineExpired {});
+
+    // The return amount is less than the minimum to receive
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        swap_msg(1_000000u128, now),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::MinimumReceiveAssertion {});
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        swap_msg(990000u128, now),
+    )
+    .unwrap();
+
+    let provide_msg = |min_lp_to_receive: u128, deadline: u64| ExecuteMsg::ProvideLiquidity {
+        assets: vec![
+            offer_asset.clone(),
+            Asset {
+                info: AssetInfo::Token {
+                    contract_addr: Addr::unchecked("asset0000"),
+                },
+                amount: Uint128::new(1_000000u128),
+            },
+        ],
+        slippage_tolerance: None,
+        auto_stake: None,
+        receiver: None,
+        min_lp_to_receive: Some(Uint128::new(min_lp_to_receive)),
+        deadline: Some(deadline),
+    };
+
+    // Provisions fail once the deadline has passed
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        provide_msg(1_000000u128, now - 1),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::DeadlineExpired {});
+
+    // The minted share is less than the minimum LP amount to receive
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        provide_msg(1_000001u128, now),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::MinLpToReceiveAssertion {});
+
+    let res = execute(deps.as_mut(), env, info, provide_msg(1_000000u128, now)).unwrap();
+    assert_eq!(
+        res.attributes
+            .iter()
+            .find(|attr| attr.key == "share")
+            .unwrap()
+            .value,
+        "1000000"
+    );
+}
+
+#[test]
+fn response_data() {
+    let mut deps = mock_dependencies(&[Coin {
+        denom: "uusd".to_string(),
+        amount: Uint128::new(100_000_000000u128 + 1_000000u128),
+    }]);
+
+    deps.querier.with_token_balances(&[
+        (
+            &String::from("liquidity0000"),
+            &[(&String::from("addr0000"), &Uint128::new(100_000_000000u128))],
+        ),
+        (
+            &String::from("asset0000"),
+            &[(
+                &String::from(MOCK_CONTRACT_ADDR),
+                &Uint128::new(100_000_000000u128),
+            )],
+        ),
+    ]);
+
+    let msg = InstantiateMsg {
+        factory_addr: String::from("factory"),
+        asset_infos: vec![
+            AssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            },
+            AssetInfo::Token {
+                contract_addr: Addr::unchecked("asset0000"),
+            },
+        ],
+        token_code_id: 10u64,
+        init_params: Some(
+            to_binary(&XykPoolParamsForProxy {
+                proxy: String::from("proxy_contract_addr"),
+            })
+            .unwrap(),
+        ),
+    };
+
+    let env = mock_env();
+    instantiate(deps.as_mut(), env.clone(), mock_info("addr0000", &[]), msg).unwrap();
+    store_liquidity_token(deps.as_mut(), 1, "liquidity0000".to_string());
+
+    let offer_asset = Asset {
+        info: AssetInfo::NativeToken {
+            denom: "uusd".to_string(),
+        },
+        amount: Uint128::new(1_000000u128),
+    };
+    let info = mock_info(
+        "proxy_contract_addr",
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(1_000000u128),
+        }],
+    );
+
+    // Swaps return the amounts of the swap
+    let msg = ExecuteMsg::Swap {
+        offer_asset: offer_asset.clone(),
+        ask_asset_info: None,
+        belief_price: None,
+        max_spread: None,
+        to: None,
+        minimum_receive: None,
+        deadline: None,
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+    let (return_amount, spread_amount, commission_amount) = compute_swap(
+        Uint128::new(100_000_000000u128),
+        Uint128::new(100_000_000000u128),
+        offer_asset.amount,
+        Decimal::permille(3),
+    )
+    .unwrap();
+    assert_eq!(
+        from_binary::<SwapResponse>(&res.data.unwrap()).unwrap(),
+        SwapResponse {
+            offer_asset: offer_asset.clone(),
+            return_asset: Asset {
+                info: AssetInfo::Token {
+                    contract_addr: Addr::unchecked("asset0000"),
+                },
+                amount: return_amount,
+            },
+            tax_amount: Uint128::zero(),
+            spread_amount,
+            commission_amount,
+            maker_fee_amount: commission_amount * Decimal::from_ratio(1660u64, 10000u64),
+        }
+    );
+
+    // Provisions return the minted share
+    let assets = vec![
+        offer_asset,
+        Asset {
+            info: AssetInfo::Token {
+                contract_addr: Addr::unchecked("asset0000"),
+            },
+            amount: Uint128::new(1_000000u128),
+        },
+    ];
+    let msg = ExecuteMsg::ProvideLiquidity {
+        assets: assets.clone(),
+        slippage_tolerance: None,
+        auto_stake: None,
+        receiver: None,
+        min_lp_to_receive: None,
+        deadline: None,
+    };
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    assert_eq!(
+        from_binary::<ProvideResponse>(&res.data.unwrap()).unwrap(),
+        ProvideResponse {
+            assets,
+            share: Uint128::new(1_000000u128),
+        }
+    );
+
+    // Withdrawals return the refunded assets
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: String::from("addr0000"),
+        amount: Uint128::new(1_000000u128),
+        msg: to_binary(&Cw20HookMsg::WithdrawLiquidity {
+            withdraw_type: None,
+        })
+        .unwrap(),
+    });
+    let res = execute(deps.as_mut(), env, mock_info("liquidity0000", &[]), msg).unwrap();
+    assert_eq!(
+        from_binary::<WithdrawResponse>(&res.data.unwrap()).unwrap(),
+        WithdrawResponse {
+            refund_assets: vec![
+                Asset {
+                    info: AssetInfo::NativeToken {
+                        denom: "uusd".to_string(),
+                    },
+                    amount: Uint128::new(1_000010u128),
+                },
+                Asset {
+                    info: AssetInfo::Token {
+                        contract_addr: Addr::unchecked("asset0000"),
+                    },
+                    amount: Uint128::new(1_000000u128),
+                },
+            ],
+            withdrawn_share: Uint128::new(1_000000u128),
+        }
+    );
+}
+
+#[test]
+fn observe() {
+    let mut deps = mock_dependencies(&[Coin {
+        denom: "uusd".to_string(),
+        amount: Uint128::new(100_000_000000u128 + 1_000000u128),
+    }]);
+
+    deps.querier.with_token_balances(&[
+        (
+            &String::from("liquidity0000"),
+            &[(&String::from("addr0000"), &Uint128::new(100_000_000000u128))],
+        ),
+        (
+            &String::from("asset0000"),
+            &[(
+                &String::from(MOCK_CONTRACT_ADDR),
+                &Uint128::new(200_000_000000u128),
+            )],
+        ),
+    ]);
+
+    let msg = InstantiateMsg {
+        factory_addr: String::from("factory"),
+        asset_infos: vec![
+            AssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            },
+            AssetInfo::Token {
+                contract_addr: Addr::unchecked("asset0000"),
+            },
+        ],
+        token_code_id: 10u64,
+        init_params: Some(
+            to_binary(&XykPoolParamsForProxy {
+                proxy: String::from("proxy_contract_addr"),
+            })
+            .unwrap(),
+        ),
+    };
+
+    instantiate(
+        deps.as_mut(),
+        mock_env_with_block_time(0),
+        mock_info("addr0000", &[]),
+        msg,
+    )
+    .unwrap();
+    store_liquidity_token(deps.as_mut(), 1, "liquidity0000".to_string());
+
+    // The pool price stays at 2 uusd per asset0000, so the cumulative prices grow linearly
+    let price_precision = 10u64.pow(TWAP_PRECISION.into());
+    let observation = |timestamp: u64| Observation {
+        timestamp,
+        price0_cumulative: Uint128::from(timestamp * price_precision * 2),
+        price1_cumulative: Uint128::from(timestamp * price_precision / 2),
+    };
+
+    // Every swap records an observation
+    for time in [1000u64, 2000u64] {
+        let msg = ExecuteMsg::Swap {
+            offer_asset: Asset {
+                info: AssetInfo::NativeToken {
+                    denom: "uusd".to_string(),
+                },
+                amount: Uint128::new(1_000000u128),
+            },
+            ask_asset_info: None,
+            belief_price: None,
+            max_spread: None,
+            to: None,
+            minimum_receive: None,
+            deadline: None,
+        };
+        let info = mock_info(
+            "proxy_contract_addr",
+            &[Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::new(1_000000u128),
+            }],
+        );
+        execute(deps.as_mut(), mock_env_with_block_time(time), info, msg).unwrap();
+    }
+
+    let res = query_observe(
+        deps.as_ref(),
+        mock_env_with_block_time(2000),
+        vec![0, 500, 1000],
+    )
+    .unwrap();
+    assert_eq!(
+        res.observations,
+        vec![observation(2000), observation(1500), observation(1000)]
+    );
+
+    // Older than the first observation
+    let err = query_observe(deps.as_ref(), mock_env_with_block_time(2000), vec![1500]).unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("The requested time is older than the oldest observation")
+    );
+}
+
+#[test]
+fn swap_ask_asset_mismatch() {
+    let mut deps = mock_dependencies(&[Coin {
+        denom: "uusd".to_string(),
+        amount: Uint128::new(100_000_000000u128 + 1_000000u128),
+    }]);
+
+    deps.querier.with_token_balances(&[(
+        &String::from("asset0000"),
+        &[(
+            &String::from(MOCK_CONTRACT_ADDR),
+            &Uint128::new(100_000_000000u128),
+        )],
+    )]);
+
+    let msg = InstantiateMsg {
+        factory_addr: String::from("factory"),
+        asset_infos: vec![
+            AssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            },
+            AssetInfo::Token {
+                contract_addr: Addr::unchecked("asset0000"),
+            },
+        ],
+        token_code_id: 10u64,
+        init_params: Some(
+            to_binary(&XykPoolParamsForProxy {
+                proxy: String::from("proxy_contract_addr"),
+            })
+            .unwrap(),
+        ),
+    };
+
+    let env = mock_env();
+    instantiate(deps.as_mut(), env.clone(), mock_info("addr0000", &[]), msg).unwrap();
+    store_liquidity_token(deps.as_mut(), 1, "liquidity0000".to_string());
+
+    // The ask asset can be neither the offer asset nor an asset outside the pool
+    for denom in ["uusd", "uluna"] {
+        let msg = ExecuteMsg::Swap {
+            offer_asset: Asset {
+                info: AssetInfo::NativeToken {
+                    denom: "uusd".to_string(),
+                },
+                amount: Uint128::new(1_000000u128),
+            },
+            ask_asset_info: Some(AssetInfo::NativeToken {
+                denom: denom.to_string(),
+            }),
+            belief_price: None,
+            max_spread: None,
+            to: None,
+            minimum_receive: None,
+            deadline: None,
+        };
+        let info = mock_info(
+            "proxy_contract_addr",
+            &[Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::new(1_000000u128),
+            }],
+        );
+        let err = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
+        assert_eq!(err, ContractError::AssetMismatch {});
+    }
+
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: String::from("addr0000"),
+        amount: Uint128::new(1_000000u128),
+        msg: to_binary(&Cw20HookMsg::Swap {
+            ask_asset_info: Some(AssetInfo::Token {
+                contract_addr: Addr::unchecked("asset0000"),
+            }),
+            belief_price: None,
+            max_spread: None,
+            to: None,
+            minimum_receive: None,
+            deadline: None,
+        })
+        .unwrap(),
+    });
+    let err = execute(deps.as_mut(), env.clone(), mock_info("asset0000", &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::AssetMismatch {});
+
+    // The simulations check the specified assets in the same way
+    let uusd_asset = Asset {
+        info: AssetInfo::NativeToken {
+            denom: "uusd".to_string(),
+        },
+        amount: Uint128::new(1_000000u128),
+    };
+    let token_info = AssetInfo::Token {
+        contract_addr: Addr::unchecked("asset0000"),
+    };
+    for denom in ["uusd", "uluna"] {
+        let asset_info = AssetInfo::NativeToken {
+            denom: denom.to_string(),
+        };
+        let err = query_simulation(
+            deps.as_ref(),
+            env.clone(),
+            uusd_asset.clone(),
+            Some(asset_info.clone()),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            StdError::generic_err(ContractError::AssetMismatch {}.to_string())
+        );
+
+        let err = query_reverse_simulation(
+            deps.as_ref(),
+            env.clone(),
+            uusd_asset.clone(),
+            Some(asset_info),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            StdError::generic_err(ContractError::AssetMismatch {}.to_string())
+        );
+    }
+
+    let simulation = query_simulation(
+        deps.as_ref(),
+        env.clone(),
+        uusd_asset.clone(),
+        Some(token_info.clone()),
+    )
+    .unwrap();
+    assert_eq!(
+        simulation,
+        query_simulation(deps.as_ref(), env.clone(), uusd_asset.clone(), None).unwrap()
+    );
+    let reverse_simulation = query_reverse_simulation(
+        deps.as_ref(),
+        env.clone(),
+        uusd_asset.clone(),
+        Some(token_info),
+    )
+    .unwrap();
+    assert_eq!(
+        reverse_simulation,
+        query_reverse_simulation(deps.as_ref(), env, uusd_asset, None).unwrap()
+    );
 }
@@ -0,0 +1,1928 @@
+use crate::error::ContractError;
+use crate::math::{
+    add_delta, compute_swap_step, get_amount0_delta, get_amount1_delta, get_liquidity_for_amounts,
+    get_sqrt_ratio_at_tick, get_tick_at_sqrt_ratio, low_u128, mul_div, price_from_sqrt_price,
+    quote, sqrt_price_from_price, to_u128, wrapping_add, wrapping_sub, FEE_PIPS_PRECISION,
+    MAX_SQRT_RATIO, MAX_TICK_SPACING, MIN_SQRT_RATIO, Q128,
+};
+use crate::state::{
+    next_initialized_tick, next_position_id, read_owner_positions, tick_key, Config, PoolState,
+    Position, CONFIG, OWNER_POSITIONS, POOL_STATE, POSITIONS, TICKS,
+};
+
+use cosmwasm_bignumber::Decimal256;
+use cosmwasm_std::{
+    entry_point, from_binary, to_binary, Addr, Binary, CosmosMsg, Decimal, Deps, DepsMut, Empty,
+    Env, Fraction, MessageInfo, Response, StdError, StdResult, Storage, Uint128, Uint256, WasmMsg,
+};
+
+use astroport::asset::{addr_validate_to_lower, Asset, AssetInfo, PairInfo};
+use astroport::factory::PairType;
+use astroport::observation::{observe, store_observation};
+use astroport::pair::{
+    ConfigResponse, CumulativePricesResponse, InstantiateMsg, MigrateMsg, Observation,
+    ObserveResponse, PoolResponse, ProvideResponse, ReverseSimulationResponse, SimulationResponse,
+    SwapResponse, WithdrawResponse, DEFAULT_SLIPPAGE, MAX_ALLOWED_SLIPPAGE, TWAP_PRECISION,
+};
+use astroport::pair_concentrated::{
+    ConcentratedPoolConfig, ConcentratedPoolParams, Cw20HookMsg, ExecuteMsg, PoolStateResponse,
+    PositionResponse, QueryMsg, MAX_TICK, MIN_TICK,
+};
+use astroport::querier::{assert_not_paused, query_fee_info, query_pair_paused};
+use cw2::set_contract_version;
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw_storage_plus::U64Key;
+use std::cmp::{max, min};
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+/// Contract name that is used for migration.
+const CONTRACT_NAME: &str = "astroport-pair-concentrated";
+/// Contract version that is used for migration.
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// ## Description
+/// Creates a new contract with the specified parameters in the [`InstantiateMsg`].
+/// Returns the [`Response`] with the specified attributes if the operation was successful, or a [`ContractError`] if the contract was not created
+/// ## Params
+/// * **deps** is the object of type [`DepsMut`].
+///
+/// * **env** is the object of type [`Env`].
+///
+/// * **_info** is the object of type [`MessageInfo`].
+///
+/// * **msg** is a message of type [`InstantiateMsg`] which contains the basic settings for creating a contract.
+/// The `init_params` field must contain the [`ConcentratedPoolParams`].
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    if msg.asset_infos.len() != 2 {
+        return Err(ContractError::InvalidNumberOfAssets {});
+    }
+
+    msg.asset_infos[0].check(deps.api)?;
+    msg.asset_infos[1].check(deps.api)?;
+
+    if msg.asset_infos[0] == msg.asset_infos[1] {
+        return Err(ContractError::DoublingAssets {});
+    }
+
+    let params: ConcentratedPoolParams = match msg.init_params {
+        Some(params) => from_binary(&params)?,
+        None => return Err(ContractError::InitParamsNotFound {}),
+    };
+
+    if params.tick_spacing == 0 || params.tick_spacing > MAX_TICK_SPACING {
+        return Err(ContractError::InvalidTickSpacing(MAX_TICK_SPACING));
+    }
+
+    let sqrt_price_x96 = sqrt_price_from_price(params.initial_price.numerator().into())?;
+    if sqrt_price_x96 < MIN_SQRT_RATIO || sqrt_price_x96 >= MAX_SQRT_RATIO {
+        return Err(ContractError::InvalidInitialPrice {});
+    }
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let config = Config {
+        pair_info: PairInfo {
+            contract_addr: env.contract.address.clone(),
+            // Positions are tracked by the pair itself, so there is no LP token
+            liquidity_token: Addr::unchecked(""),
+            asset_infos: msg.asset_infos,
+            pair_type: PairType::Concentrated {},
+        },
+        factory_addr: addr_validate_to_lower(deps.api, msg.factory_addr.as_str())?,
+        tick_spacing: params.tick_spacing,
+        block_time_last: env.block.time.seconds(),
+        price0_cumulative_last: Uint128::zero(),
+        price1_cumulative_last: Uint128::zero(),
+    };
+    CONFIG.save(deps.storage, &config)?;
+
+    let pool = PoolState {
+        sqrt_price_x96,
+        tick: get_tick_at_sqrt_ratio(sqrt_price_x96)?,
+        liquidity: Uint128::zero(),
+        fee_growth_global0_x128: Uint256::zero(),
+        fee_growth_global1_x128: Uint256::zero(),
+    };
+    POOL_STATE.save(deps.storage, &pool)?;
+
+    Ok(Response::new()
+        .add_attribute("sqrt_price_x96", sqrt_price_x96.to_string())
+        .add_attribute("tick", pool.tick.to_string()))
+}
+
+/// ## Description
+/// Available the execute messages of the contract.
+/// ## Params
+/// * **deps** is the object of type [`Deps`].
+///
+/// * **env** is the object of type [`Env`].
+///
+/// * **info** is the object of type [`MessageInfo`].
+///
+/// * **msg** is the object of type [`ExecuteMsg`].
+///
+/// ## Queries
+/// * **ExecuteMsg::Receive(msg)** Receives a message of type [`Cw20ReceiveMsg`] and processes
+/// it depending on the received template.
+///
+/// * **ExecuteMsg::CreatePosition {
+///             tick_lower,
+///             tick_upper,
+///             assets,
+///             min_liquidity,
+///             receiver,
+///         }** Creates a new liquidity position in the specified tick range.
+///
+/// * **ExecuteMsg::IncreaseLiquidity {
+///             position_id,
+///             assets,
+///             min_liquidity,
+///         }** Adds liquidity to an existing position.
+///
+/// * **ExecuteMsg::DecreaseLiquidity {
+///             position_id,
+///             liquidity,
+///             receiver,
+///         }** Withdraws liquidity from a position together with the accrued fees.
+///
+/// * **ExecuteMsg::CollectFees { position_id, receiver }** Sends the fees accrued by a position.
+///
+/// * **ExecuteMsg::TransferPosition { position_id, recipient }** Changes the owner of a position.
+///
+/// * **ExecuteMsg::Swap {
+///             offer_asset,
+///             belief_price,
+///             max_spread,
+///             to,
+///         }** Performs an swap operation with the specified parameters.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
+        ExecuteMsg::CreatePosition {
+            tick_lower,
+            tick_upper,
+            assets,
+            min_liquidity,
+            receiver,
+        } => create_position(
+            deps,
+            env,
+            info,
+            tick_lower,
+            tick_upper,
+            assets,
+            min_liquidity,
+            receiver,
+        ),
+        ExecuteMsg::IncreaseLiquidity {
+            position_id,
+            assets,
+            min_liquidity,
+        } => increase_liquidity(deps, env, info, position_id, assets, min_liquidity),
+        ExecuteMsg::DecreaseLiquidity {
+            position_id,
+            liquidity,
+            receiver,
+        } => decrease_liquidity(deps, info, position_id, liquidity, receiver),
+        ExecuteMsg::CollectFees {
+            position_id,
+            receiver,
+        } => collect_fees(deps, info, position_id, receiver),
+        ExecuteMsg::TransferPosition {
+            position_id,
+            recipient,
+        } => transfer_position(deps, info, position_id, recipient),
+        ExecuteMsg::Swap {
+            offer_asset,
+            ask_asset_info,
+            belief_price,
+            max_spread,
+            to,
+        } => {
+            offer_asset.info.check(deps.api)?;
+            if !offer_asset.is_native_token() {
+                return Err(ContractError::Unauthorized {});
+            }
+
+            let config = CONFIG.load(deps.storage)?;
+            assert_ask_asset(&config, &offer_asset.info, ask_asset_info.as_ref())?;
+
+            let to_addr = if let Some(to_addr) = to {
+                Some(addr_validate_to_lower(deps.api, &to_addr)?)
+            } else {
+                None
+            };
+
+            swap(
+                deps,
+                env,
+                info.clone(),
+                info.sender,
+                offer_asset,
+                belief_price,
+                max_spread,
+                to_addr,
+            )
+        }
+    }
+}
+
+/// ## Description
+/// Receives a message of type [`Cw20ReceiveMsg`] and processes it depending on the received template.
+/// If the template is not found in the received message, then an [`ContractError`] is returned,
+/// otherwise returns the [`Response`] with the specified attributes if the operation was successful
+/// ## Params
+/// * **deps** is the object of type [`DepsMut`].
+///
+/// * **env** is the object of type [`Env`].
+///
+/// * **info** is the object of type [`MessageInfo`].
+///
+/// * **cw20_msg** is the object of type [`Cw20ReceiveMsg`].
+pub fn receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let contract_addr = info.sender.clone();
+    match from_binary(&cw20_msg.msg)? {
+        Cw20HookMsg::Swap {
+            ask_asset_info,
+            belief_price,
+            max_spread,
+            to,
+        } => {
+            // only asset contract can execute this message
+            let mut authorized: bool = false;
+            let config: Config = CONFIG.load(deps.storage)?;
+
+            for pool in &config.pair_info.asset_infos {
+                if let AssetInfo::Token { contract_addr, .. } = pool {
+                    if contract_addr == &info.sender {
+                        authorized = true;
+                    }
+                }
+            }
+
+            if !authorized {
+                return Err(ContractError::Unauthorized {});
+            }
+
+            assert_ask_asset(
+                &config,
+                &AssetInfo::Token {
+                    contract_addr: contract_addr.clone(),
+                },
+                ask_asset_info.as_ref(),
+            )?;
+
+            let to_addr = if let Some(to_addr) = to {
+                Some(addr_validate_to_lower(deps.api, to_addr.as_str())?)
+            } else {
+                None
+            };
+
+            swap(
+                deps,
+                env,
+                info,
+                Addr::unchecked(cw20_msg.sender),
+                Asset {
+                    info: AssetInfo::Token { contract_addr },
+                    amount: cw20_msg.amount,
+                },
+                belief_price,
+                max_spread,
+                to_addr,
+            )
+        }
+    }
+}
+
+/// ## Description
+/// Creates a new liquidity position in the range between `tick_lower` and `tick_upper`.
+/// The largest liquidity that can be backed by the provided assets is minted, the unused native
+/// tokens are refunded and only the used amounts of CW20 tokens are transferred from the sender.
+/// Returns a [`ContractError`] on failure, otherwise returns the [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Params
+/// * **deps** is the object of type [`DepsMut`].
+///
+/// * **env** is the object of type [`Env`].
+///
+/// * **info** is the object of type [`MessageInfo`].
+///
+/// * **tick_lower** is the lower bound of the position range.
+///
+/// * **tick_upper** is the upper bound of the position range.
+///
+/// * **assets** is a vector with the maximum amounts of the assets to deposit.
+///
+/// * **min_liquidity** is an [`Option`] field of type [`Uint128`]. The minimum liquidity to receive.
+///
+/// * **receiver** is an [`Option`] field of type [`String`]. Sets the owner of the position.
+// CONTRACT - should approve contract to use the amount of token.
+#[allow(clippy::too_many_arguments)]
+pub fn create_position(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    tick_lower: i32,
+    tick_upper: i32,
+    assets: Vec<Asset>,
+    min_liquidity: Option<Uint128>,
+    receiver: Option<String>,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    assert_not_paused(
+        &deps.querier,
+        config.factory_addr.clone(),
+        env.contract.address.clone(),
+    )?;
+
+    if tick_lower >= tick_upper
+        || tick_lower < MIN_TICK
+        || tick_upper > MAX_TICK
+        || tick_lower % config.tick_spacing as i32 != 0
+        || tick_upper % config.tick_spacing as i32 != 0
+    {
+        return Err(ContractError::InvalidTickRange {});
+    }
+
+    let owner = if let Some(receiver) = receiver {
+        addr_validate_to_lower(deps.api, &receiver)?
+    } else {
+        info.sender.clone()
+    };
+
+    let mut position = Position {
+        owner,
+        tick_lower,
+        tick_upper,
+        liquidity: Uint128::zero(),
+        fee_growth_inside0_last_x128: Uint256::zero(),
+        fee_growth_inside1_last_x128: Uint256::zero(),
+        tokens_owed0: Uint128::zero(),
+        tokens_owed1: Uint128::zero(),
+    };
+
+    let position_id = next_position_id(deps.storage)?;
+    let (liquidity, messages, deposits) = add_liquidity(
+        deps.branch(),
+        &env,
+        &info,
+        &config,
+        &mut position,
+        assets,
+        min_liquidity,
+    )?;
+
+    POSITIONS.save(deps.storage, U64Key::new(position_id), &position)?;
+    OWNER_POSITIONS.save(
+        deps.storage,
+        (&position.owner, U64Key::new(position_id)),
+        &Empty {},
+    )?;
+
+    let attributes = vec![
+        ("action", "create_position".to_string()),
+        ("position_id", position_id.to_string()),
+        ("owner", position.owner.to_string()),
+        ("tick_lower", tick_lower.to_string()),
+        ("tick_upper", tick_upper.to_string()),
+        ("liquidity", liquidity.to_string()),
+        ("assets", format!("{}, {}", deposits[0], deposits[1])),
+    ];
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .set_data(to_binary(&ProvideResponse {
+            assets: deposits.to_vec(),
+            share: liquidity,
+        })?)
+        .add_attributes(attributes))
+}
+
+/// ## Description
+/// Adds liquidity to an existing position. Only the position owner can execute this.
+/// Returns a [`ContractError`] on failure, otherwise returns the [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Params
+/// * **deps** is the object of type [`DepsMut`].
+///
+/// * **env** is the object of type [`Env`].
+///
+/// * **info** is the object of type [`MessageInfo`].
+///
+/// * **position_id** is the identifier of the position.
+///
+/// * **assets** is a vector with the maximum amounts of the assets to deposit.
+///
+/// * **min_liquidity** is an [`Option`] field of type [`Uint128`]. The minimum liquidity to receive.
+// CONTRACT - should approve contract to use the amount of token.
+pub fn increase_liquidity(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    position_id: u64,
+    assets: Vec<Asset>,
+    min_liquidity: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    assert_not_paused(
+        &deps.querier,
+        config.factory_addr.clone(),
+        env.contract.address.clone(),
+    )?;
+
+    let mut position = load_owned_position(deps.storage, position_id, &info.sender)?;
+
+    let (liquidity, messages, deposits) = add_liquidity(
+        deps.branch(),
+        &env,
+        &info,
+        &config,
+        &mut position,
+        assets,
+        min_liquidity,
+    )?;
+
+    POSITIONS.save(deps.storage, U64Key::new(position_id), &position)?;
+
+    let attributes = vec![
+        ("action", "increase_liquidity".to_string()),
+        ("position_id", position_id.to_string()),
+        ("liquidity", liquidity.to_string()),
+        ("assets", format!("{}, {}", deposits[0], deposits[1])),
+    ];
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .set_data(to_binary(&ProvideResponse {
+            assets: deposits.to_vec(),
+            share: liquidity,
+        })?)
+        .add_attributes(attributes))
+}
+
+/// ## Description
+/// Withdraws liquidity from a position and sends the withdrawn assets together with all the fees
+/// accrued by the position. The position is removed once all of its liquidity is withdrawn.
+/// Only the position owner can execute this.
+/// Returns a [`ContractError`] on failure, otherwise returns the [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Params
+/// * **deps** is the object of type [`DepsMut`].
+///
+/// * **info** is the object of type [`MessageInfo`].
+///
+/// * **position_id** is the identifier of the position.
+///
+/// * **liquidity** is the amount of liquidity to withdraw.
+///
+/// * **receiver** is an [`Option`] field of type [`String`]. Sets the receiver of the assets.
+pub fn decrease_liquidity(
+    deps: DepsMut,
+    info: MessageInfo,
+    position_id: u64,
+    liquidity: Uint128,
+    receiver: Option<String>,
+) -> Result<Response, ContractError> {
+    if liquidity.is_zero() {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+
+    let config: Config = CONFIG.load(deps.storage)?;
+    let mut position = load_owned_position(deps.storage, position_id, &info.sender)?;
+    if liquidity > position.liquidity {
+        return Err(ContractError::InsufficientPositionLiquidity {});
+    }
+
+    let receiver = if let Some(receiver) = receiver {
+        addr_validate_to_lower(deps.api, &receiver)?
+    } else {
+        info.sender
+    };
+
+    let mut pool = POOL_STATE.load(deps.storage)?;
+    let (amount0, amount1) = modify_position(
+        deps.storage,
+        &mut pool,
+        &mut position,
+        -liquidity_delta(liquidity)?,
+    )?;
+    POOL_STATE.save(deps.storage, &pool)?;
+
+    position.tokens_owed0 = position.tokens_owed0.checked_add(amount0)?;
+    position.tokens_owed1 = position.tokens_owed1.checked_add(amount1)?;
+    let (messages, collected) = collect_owed(&deps.querier, &config, &mut position, &receiver)?;
+
+    if position.liquidity.is_zero() {
+        POSITIONS.remove(deps.storage, U64Key::new(position_id));
+        OWNER_POSITIONS.remove(deps.storage, (&position.owner, U64Key::new(position_id)));
+    } else {
+        POSITIONS.save(deps.storage, U64Key::new(position_id), &position)?;
+    }
+
+    let attributes = vec![
+        ("action", "decrease_liquidity".to_string()),
+        ("position_id", position_id.to_string()),
+        ("receiver", receiver.to_string()),
+        ("liquidity", liquidity.to_string()),
+        ("withdrawn_share", format!("{}, {}", amount0, amount1)),
+        (
+            "refund_assets",
+            format!("{}, {}", collected[0], collected[1]),
+        ),
+    ];
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .set_data(to_binary(&WithdrawResponse {
+            refund_assets: collected.to_vec(),
+            withdrawn_share: liquidity,
+        })?)
+        .add_attributes(attributes))
+}
+
+/// ## Description
+/// Sends the fees accrued by a position. Only the position owner can execute this.
+/// Returns a [`ContractError`] on failure, otherwise returns the [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Params
+/// * **deps** is the object of type [`DepsMut`].
+///
+/// * **info** is the object of type [`MessageInfo`].
+///
+/// * **position_id** is the identifier of the position.
+///
+/// * **receiver** is an [`Option`] field of type [`String`]. Sets the receiver of the fees.
+pub fn collect_fees(
+    deps: DepsMut,
+    info: MessageInfo,
+    position_id: u64,
+    receiver: Option<String>,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let mut position = load_owned_position(deps.storage, position_id, &info.sender)?;
+
+    let receiver = if let Some(receiver) = receiver {
+        addr_validate_to_lower(deps.api, &receiver)?
+    } else {
+        info.sender
+    };
+
+    if !position.liquidity.is_zero() {
+        let mut pool = POOL_STATE.load(deps.storage)?;
+        modify_position(deps.storage, &mut pool, &mut position, 0)?;
+    }
+
+    let (messages, collected) = collect_owed(&deps.querier, &config, &mut position, &receiver)?;
+    POSITIONS.save(deps.storage, U64Key::new(position_id), &position)?;
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("action", "collect_fees"),
+        ("position_id", &position_id.to_string()),
+        ("receiver", receiver.as_str()),
+        ("fees", &format!("{}, {}", collected[0], collected[1])),
+    ]))
+}
+
+/// ## Description
+/// Changes the owner of a position. Only the position owner can execute this.
+/// Returns a [`ContractError`] on failure, otherwise returns the [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Params
+/// * **deps** is the object of type [`DepsMut`].
+///
+/// * **info** is the object of type [`MessageInfo`].
+///
+/// * **position_id** is the identifier of the position.
+///
+/// * **recipient** is the new owner of the position.
+pub fn transfer_position(
+    deps: DepsMut,
+    info: MessageInfo,
+    position_id: u64,
+    recipient: String,
+) -> Result<Response, ContractError> {
+    let mut position = load_owned_position(deps.storage, position_id, &info.sender)?;
+    let recipient = addr_validate_to_lower(deps.api, &recipient)?;
+
+    OWNER_POSITIONS.remove(deps.storage, (&position.owner, U64Key::new(position_id)));
+    OWNER_POSITIONS.save(
+        deps.storage,
+        (&recipient, U64Key::new(position_id)),
+        &Empty {},
+    )?;
+
+    position.owner = recipient;
+    POSITIONS.save(deps.storage, U64Key::new(position_id), &position)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "transfer_position"),
+        ("position_id", &position_id.to_string()),
+        ("sender", info.sender.as_str()),
+        ("recipient", position.owner.as_str()),
+    ]))
+}
+
+/// ## Description
+/// Loads a position and checks that it belongs to the specified address.
+fn load_owned_position(
+    storage: &dyn Storage,
+    position_id: u64,
+    owner: &Addr,
+) -> Result<Position, ContractError> {
+    let position = POSITIONS
+        .may_load(storage, U64Key::new(position_id))?
+        .ok_or(ContractError::PositionNotFound(position_id))?;
+
+    if position.owner != *owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    Ok(position)
+}
+
+/// ## Description
+/// Converts an amount of liquidity to a signed liquidity delta.
+fn liquidity_delta(liquidity: Uint128) -> StdResult<i128> {
+    i128::try_from(liquidity.u128()).map_err(|_| StdError::generic_err("Liquidity overflow"))
+}
+
+/// ## Description
+/// Adds the largest liquidity backed by the provided assets to a position. Returns the added
+/// liquidity, the messages that take the deposit and refund the unused native tokens, and the
+/// deposited assets.
+fn add_liquidity(
+    deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    config: &Config,
+    position: &mut Position,
+    assets: Vec<Asset>,
+    min_liquidity: Option<Uint128>,
+) -> Result<(Uint128, Vec<CosmosMsg>, [Asset; 2]), ContractError> {
+    if assets.len() > 2 {
+        return Err(ContractError::InvalidNumberOfAssets {});
+    }
+
+    let mut desired = [Uint128::zero(); 2];
+    for asset in &assets {
+        asset.assert_sent_native_token_balance(info)?;
+        let index = config
+            .pair_info
+            .asset_infos
+            .iter()
+            .position(|info| info.equal(&asset.info))
+            .ok_or(ContractError::AssetMismatch {})?;
+        desired[index] = asset.amount;
+    }
+
+    let mut pool = POOL_STATE.load(deps.storage)?;
+    let liquidity = get_liquidity_for_amounts(
+        pool.sqrt_price_x96,
+        get_sqrt_ratio_at_tick(position.tick_lower)?,
+        get_sqrt_ratio_at_tick(position.tick_upper)?,
+        desired[0],
+        desired[1],
+    )?;
+
+    if liquidity.is_zero() {
+        return Err(ContractError::ZeroLiquidity {});
+    }
+    if let Some(min_liquidity) = min_liquidity {
+        if liquidity < min_liquidity {
+            return Err(ContractError::MaxSlippageAssertion {});
+        }
+    }
+
+    let used = modify_position(
+        deps.storage,
+        &mut pool,
+        position,
+        liquidity_delta(liquidity)?,
+    )?;
+    POOL_STATE.save(deps.storage, &pool)?;
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    let deposits = [
+        Asset {
+            info: config.pair_info.asset_infos[0].clone(),
+            amount: used.0,
+        },
+        Asset {
+            info: config.pair_info.asset_infos[1].clone(),
+            amount: used.1,
+        },
+    ];
+    for (deposit, desired) in deposits.iter().zip(desired.iter()) {
+        let refund_amount = desired
+            .checked_sub(deposit.amount)
+            .map_err(|_| ContractError::MaxSlippageAssertion {})?;
+
+        match &deposit.info {
+            AssetInfo::Token { contract_addr } => {
+                if !deposit.amount.is_zero() {
+                    messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                        contract_addr: contract_addr.to_string(),
+                        msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
+                            owner: info.sender.to_string(),
+                            recipient: env.contract.address.to_string(),
+                            amount: deposit.amount,
+                        })?,
+                        funds: vec![],
+                    }))
+                }
+            }
+            AssetInfo::NativeToken { .. } => {
+                if !refund_amount.is_zero() {
+                    messages.push(
+                        Asset {
+                            info: deposit.info.clone(),
+                            amount: refund_amount,
+                        }
+                        .into_msg(&deps.querier, info.sender.clone())?,
+                    )
+                }
+            }
+        }
+    }
+
+    Ok((liquidity, messages, deposits))
+}
+
+/// ## Description
+/// Sends the assets owed to a position and resets them. Returns the messages and the sent assets.
+fn collect_owed(
+    querier: &cosmwasm_std::QuerierWrapper,
+    config: &Config,
+    position: &mut Position,
+    receiver: &Addr,
+) -> StdResult<(Vec<CosmosMsg>, [Asset; 2])> {
+    let collected = [
+        Asset {
+            info: config.pair_info.asset_infos[0].clone(),
+            amount: position.tokens_owed0,
+        },
+        Asset {
+            info: config.pair_info.asset_infos[1].clone(),
+            amount: position.tokens_owed1,
+        },
+    ];
+    position.tokens_owed0 = Uint128::zero();
+    position.tokens_owed1 = Uint128::zero();
+
+    let messages = collected
+        .iter()
+        .filter(|asset| !asset.amount.is_zero())
+        .map(|asset| asset.clone().into_msg(querier, receiver.clone()))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok((messages, collected))
+}
+
+/// ## Description
+/// Updates the state of a tick bounding a position whose liquidity changes by `liquidity_delta`.
+/// ## Params
+/// * **storage** is the object of type [`Storage`].
+///
+/// * **tick** is the tick to update.
+///
+/// * **pool** is the object of type [`PoolState`]. The current pool state.
+///
+/// * **liquidity_delta** is the change of the position liquidity.
+///
+/// * **upper** determines whether the tick is the upper bound of the position.
+fn update_tick(
+    storage: &mut dyn Storage,
+    tick: i32,
+    pool: &PoolState,
+    liquidity_delta: i128,
+    upper: bool,
+) -> StdResult<()> {
+    let mut info = TICKS.may_load(storage, tick_key(tick))?.unwrap_or_default();
+
+    if info.liquidity_gross.is_zero() && tick <= pool.tick {
+        // By convention, all the fee growth before a tick is initialized happened below it
+        info.fee_growth_outside0_x128 = pool.fee_growth_global0_x128;
+        info.fee_growth_outside1_x128 = pool.fee_growth_global1_x128;
+    }
+
+    info.liquidity_gross = add_delta(info.liquidity_gross, liquidity_delta)?;
+    info.liquidity_net = if upper {
+        info.liquidity_net.checked_sub(liquidity_delta)
+    } else {
+        info.liquidity_net.checked_add(liquidity_delta)
+    }
+    .ok_or_else(|| StdError::generic_err("Tick liquidity overflow"))?;
+
+    if info.liquidity_gross.is_zero() {
+        TICKS.remove(storage, tick_key(tick));
+    } else {
+        TICKS.save(storage, tick_key(tick), &info)?;
+    }
+
+    Ok(())
+}
+
+/// ## Description
+/// Returns the fee growth of both assets inside the specified tick range.
+fn get_fee_growth_inside(
+    storage: &dyn Storage,
+    pool: &PoolState,
+    tick_lower: i32,
+    tick_upper: i32,
+) -> StdResult<(Uint256, Uint256)> {
+    let lower = TICKS
+        .may_load(storage, tick_key(tick_lower))?
+        .unwrap_or_default();
+    let upper = TICKS
+        .may_load(storage, tick_key(tick_upper))?
+        .unwrap_or_default();
+
+    let growth_inside = |global: Uint256, lower_outside: Uint256, upper_outside: Uint256| {
+        let below = if pool.tick >= tick_lower {
+            lower_outside
+        } else {
+            wrapping_sub(global, lower_outside)
+        };
+        let above = if pool.tick < tick_upper {
+            upper_outside
+        } else {
+            wrapping_sub(global, upper_outside)
+        };
+        wrapping_sub(wrapping_sub(global, below), above)
+    };
+
+    Ok((
+        growth_inside(
+            pool.fee_growth_global0_x128,
+            lower.fee_growth_outside0_x128,
+            upper.fee_growth_outside0_x128,
+        ),
+        growth_inside(
+            pool.fee_growth_global1_x128,
+            lower.fee_growth_outside1_x128,
+            upper.fee_growth_outside1_x128,
+        ),
+    ))
+}
+
+/// ## Description
+/// Accrues the fees earned by a position since the last accrual to the amounts owed to it.
+fn accrue_fees(
+    position: &mut Position,
+    fee_growth_inside0_x128: Uint256,
+    fee_growth_inside1_x128: Uint256,
+) -> StdResult<()> {
+    let fees0 = mul_div(
+        wrapping_sub(
+            fee_growth_inside0_x128,
+            position.fee_growth_inside0_last_x128,
+        ),
+        position.liquidity.into(),
+        Q128,
+    )?;
+    let fees1 = mul_div(
+        wrapping_sub(
+            fee_growth_inside1_x128,
+            position.fee_growth_inside1_last_x128,
+        ),
+        position.liquidity.into(),
+        Q128,
+    )?;
+
+    position.tokens_owed0 = position.tokens_owed0.checked_add(to_u128(fees0)?)?;
+    position.tokens_owed1 = position.tokens_owed1.checked_add(to_u128(fees1)?)?;
+    position.fee_growth_inside0_last_x128 = fee_growth_inside0_x128;
+    position.fee_growth_inside1_last_x128 = fee_growth_inside1_x128;
+
+    Ok(())
+}
+
+/// ## Description
+/// Returns the amounts of the pool assets backing the specified liquidity in a tick range at the current price.
+fn position_amounts(
+    pool: &PoolState,
+    tick_lower: i32,
+    tick_upper: i32,
+    liquidity: Uint128,
+    round_up: bool,
+) -> StdResult<(Uint128, Uint128)> {
+    let sqrt_ratio_lower = get_sqrt_ratio_at_tick(tick_lower)?;
+    let sqrt_ratio_upper = get_sqrt_ratio_at_tick(tick_upper)?;
+
+    let (amount0, amount1) = if pool.tick < tick_lower {
+        (
+            get_amount0_delta(sqrt_ratio_lower, sqrt_ratio_upper, liquidity, round_up)?,
+            Uint256::zero(),
+        )
+    } else if pool.tick < tick_upper {
+        (
+            get_amount0_delta(pool.sqrt_price_x96, sqrt_ratio_upper, liquidity, round_up)?,
+            get_amount1_delta(sqrt_ratio_lower, pool.sqrt_price_x96, liquidity, round_up)?,
+        )
+    } else {
+        (
+            Uint256::zero(),
+            get_amount1_delta(sqrt_ratio_lower, sqrt_ratio_upper, liquidity, round_up)?,
+        )
+    };
+
+    Ok((to_u128(amount0)?, to_u128(amount1)?))
+}
+
+/// ## Description
+/// Changes the liquidity of a position by `liquidity_delta`, updating the bounding ticks and the
+/// active liquidity and accruing the fees earned by the position. Returns the amounts of the pool
+/// assets to deposit (rounded up) for a positive delta or to withdraw (rounded down) for a negative one.
+/// ## Params
+/// * **storage** is the object of type [`Storage`].
+///
+/// * **pool** is the object of type [`PoolState`]. The current pool state, updated in place.
+///
+/// * **position** is the object of type [`Position`]. The position to modify, updated in place.
+///
+/// * **liquidity_delta** is the change of the position liquidity.
+fn modify_position(
+    storage: &mut dyn Storage,
+    pool: &mut PoolState,
+    position: &mut Position,
+    liquidity_delta: i128,
+) -> StdResult<(Uint128, Uint128)> {
+    if liquidity_delta != 0 {
+        update_tick(storage, position.tick_lower, pool, liquidity_delta, false)?;
+        update_tick(storage, position.tick_upper, pool, liquidity_delta, true)?;
+    }
+
+    let (fee_growth_inside0, fee_growth_inside1) =
+        get_fee_growth_inside(storage, pool, position.tick_lower, position.tick_upper)?;
+    accrue_fees(position, fee_growth_inside0, fee_growth_inside1)?;
+    position.liquidity = add_delta(position.liquidity, liquidity_delta)?;
+
+    if liquidity_delta == 0 {
+        return Ok((Uint128::zero(), Uint128::zero()));
+    }
+
+    if pool.tick >= position.tick_lower && pool.tick < position.tick_upper {
+        pool.liquidity = add_delta(pool.liquidity, liquidity_delta)?;
+    }
+
+    position_amounts(
+        pool,
+        position.tick_lower,
+        position.tick_upper,
+        Uint128::new(liquidity_delta.unsigned_abs()),
+        liquidity_delta > 0,
+    )
+}
+
+/// ## Description
+/// This structure describes the fee settings used by swaps.
+struct FeeParams {
+    /// The total fee in hundredths of a bip
+    fee_pips: u32,
+    /// The share of the total fee sent to the Maker
+    maker_fee_rate: Decimal,
+    fee_address: Option<Addr>,
+}
+
+/// ## Description
+/// Queries the fee settings of the pair type from the factory.
+fn query_fee_params(deps: Deps, config: &Config) -> StdResult<FeeParams> {
+    let fee_info = query_fee_info(
+        &deps.querier,
+        config.factory_addr.clone(),
+        config.pair_info.pair_type.clone(),
+        Some(config.pair_info.contract_addr.clone()),
+    )?;
+
+    let fee_pips = (Uint128::from(FEE_PIPS_PRECISION) * fee_info.total_fee_rate).u128() as u32;
+
+    Ok(FeeParams {
+        fee_pips: min(fee_pips, FEE_PIPS_PRECISION - 1),
+        maker_fee_rate: if fee_info.fee_address.is_some() {
+            fee_info.maker_fee_rate
+        } else {
+            Decimal::zero()
+        },
+        fee_address: fee_info.fee_address,
+    })
+}
+
+/// ## Description
+/// This structure describes the result of a swap computation.
+struct SwapResult {
+    /// The input amount including the fee
+    amount_in: Uint128,
+    amount_out: Uint128,
+    /// The total fee taken from the input
+    fee_amount: Uint128,
+    /// The part of the fee sent to the Maker
+    maker_fee_amount: Uint128,
+    /// The pool state after the swap
+    pool: PoolState,
+    /// The crossed ticks with the global fee growth values at the moment of crossing
+    crossed_ticks: Vec<(i32, Uint256, Uint256)>,
+}
+
+/// ## Description
+/// Computes a swap without changing the contract state. The price moves tick range by tick range,
+/// changing the active liquidity whenever an initialized tick is crossed. Returns a
+/// [`ContractError::InsufficientLiquidity`] if the swap can't be filled completely.
+/// ## Params
+/// * **storage** is the object of type [`Storage`].
+///
+/// * **pool** is the object of type [`PoolState`]. The pool state before the swap.
+///
+/// * **zero_for_one** is the swap direction: true if the first asset is swapped for the second one.
+///
+/// * **amount** is the input amount (including the fee) if `exact_in` is true, otherwise the output amount.
+///
+/// * **fee_params** is the object of type [`FeeParams`].
+fn compute_swap(
+    storage: &dyn Storage,
+    pool: &PoolState,
+    zero_for_one: bool,
+    amount: Uint128,
+    exact_in: bool,
+    fee_params: &FeeParams,
+) -> Result<SwapResult, ContractError> {
+    let mut state = pool.clone();
+    let sqrt_price_limit = if zero_for_one {
+        MIN_SQRT_RATIO + Uint256::from(1u8)
+    } else {
+        MAX_SQRT_RATIO - Uint256::from(1u8)
+    };
+
+    let mut amount_remaining = Uint256::from(amount);
+    let mut amount_in = Uint256::zero();
+    let mut amount_out = Uint256::zero();
+    let mut fee_amount = Uint128::zero();
+    let mut maker_fee_amount = Uint128::zero();
+    let mut crossed_ticks = vec![];
+
+    while !amount_remaining.is_zero() && state.sqrt_price_x96 != sqrt_price_limit {
+        let (tick_next, initialized) =
+            match next_initialized_tick(storage, state.tick, zero_for_one)? {
+                Some(tick) => (tick, true),
+                None if zero_for_one => (MIN_TICK, false),
+                None => (MAX_TICK, false),
+            };
+
+        let sqrt_price_next = get_sqrt_ratio_at_tick(tick_next)?;
+        let sqrt_price_target = if zero_for_one {
+            max(sqrt_price_next, sqrt_price_limit)
+        } else {
+            min(sqrt_price_next, sqrt_price_limit)
+        };
+        let sqrt_price_start = state.sqrt_price_x96;
+
+        let step = compute_swap_step(
+            state.sqrt_price_x96,
+            sqrt_price_target,
+            state.liquidity,
+            amount_remaining,
+            exact_in,
+            fee_params.fee_pips,
+        )?;
+        state.sqrt_price_x96 = step.sqrt_price_next_x96;
+
+        if exact_in {
+            amount_remaining = amount_remaining - step.amount_in - step.fee_amount;
+        } else {
+            amount_remaining -= step.amount_out;
+        }
+        amount_in = amount_in + step.amount_in + step.fee_amount;
+        amount_out += step.amount_out;
+
+        let step_fee = to_u128(step.fee_amount)?;
+        let step_maker_fee = step_fee * fee_params.maker_fee_rate;
+        fee_amount += step_fee;
+        maker_fee_amount += step_maker_fee;
+
+        if !state.liquidity.is_zero() {
+            let growth = mul_div(
+                (step_fee - step_maker_fee).into(),
+                Q128,
+                state.liquidity.into(),
+            )?;
+            if zero_for_one {
+                state.fee_growth_global0_x128 = wrapping_add(state.fee_growth_global0_x128, growth);
+            } else {
+                state.fee_growth_global1_x128 = wrapping_add(state.fee_growth_global1_x128, growth);
+            }
+        }
+
+        if state.sqrt_price_x96 == sqrt_price_next {
+            if initialized {
+                crossed_ticks.push((
+                    tick_next,
+                    state.fee_growth_global0_x128,
+                    state.fee_growth_global1_x128,
+                ));
+                let liquidity_net = TICKS.load(storage, tick_key(tick_next))?.liquidity_net;
+                state.liquidity = add_delta(
+                    state.liquidity,
+                    if zero_for_one {
+                        -liquidity_net
+                    } else {
+                        liquidity_net
+                    },
+                )?;
+            }
+            state.tick = if zero_for_one {
+                tick_next - 1
+            } else {
+                tick_next
+            };
+        } else if state.sqrt_price_x96 != sqrt_price_start {
+            state.tick = get_tick_at_sqrt_ratio(state.sqrt_price_x96)?;
+        }
+    }
+
+    if !amount_remaining.is_zero() {
+        return Err(ContractError::InsufficientLiquidity {});
+    }
+
+    Ok(SwapResult {
+        amount_in: to_u128(amount_in)?,
+        amount_out: to_u128(amount_out)?,
+        fee_amount,
+        maker_fee_amount,
+        pool: state,
+        crossed_ticks,
+    })
+}
+
+/// ## Description
+/// Returns the spread and the commission of a swap, both denominated in the ask asset.
+/// The spread is the difference between the return at the price before the swap and the actual return.
+/// ## Params
+/// * **sqrt_price_x96** is the square root price before the swap.
+///
+/// * **zero_for_one** is the swap direction.
+///
+/// * **offer_amount** is the input amount including the fee.
+///
+/// * **fee_amount** is the fee taken from the input.
+///
+/// * **return_amount** is the output amount.
+fn compute_spread(
+    sqrt_price_x96: Uint256,
+    zero_for_one: bool,
+    offer_amount: Uint128,
+    fee_amount: Uint128,
+    return_amount: Uint128,
+) -> StdResult<(Uint128, Uint128)> {
+    let expected_return = to_u128(quote(
+        offer_amount.checked_sub(fee_amount)?,
+        sqrt_price_x96,
+        zero_for_one,
+    )?)?;
+    let commission_amount = to_u128(quote(offer_amount, sqrt_price_x96, zero_for_one)?)?
+        .saturating_sub(expected_return);
+    let spread_amount = expected_return.saturating_sub(return_amount);
+
+    Ok((spread_amount, commission_amount))
+}
+
+/// ## Description
+/// Performs a swap operation with the specified parameters. The fee is taken from the offered
+/// asset and the part of it defined by the factory is sent to the Maker.
+/// Returns a [`ContractError`] on failure, otherwise returns the [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Params
+/// * **deps** is the object of type [`DepsMut`].
+///
+/// * **env** is the object of type [`Env`].
+///
+/// * **info** is the object of type [`MessageInfo`].
+///
+/// * **sender** is the object of type [`Addr`]. Sets the default recipient of the swap operation.
+///
+/// * **offer_asset** is the object of type [`Asset`]. Sets the asset to swap and its amount.
+///
+/// * **belief_price** is the object of type [`Option<Decimal>`]. Sets the belief price of the swap.
+///
+/// * **max_spread** is the object of type [`Option<Decimal>`]. Sets the maximum spread of the swap operation.
+///
+/// * **to** is the object of type [`Option<Addr>`]. Sets the recipient of the swap operation.
+#[allow(clippy::too_many_arguments)]
+pub fn swap(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    sender: Addr,
+    offer_asset: Asset,
+    belief_price: Option<Decimal>,
+    max_spread: Option<Decimal>,
+    to: Option<Addr>,
+) -> Result<Response, ContractError> {
+    offer_asset.assert_sent_native_token_balance(&info)?;
+
+    if offer_asset.amount.is_zero() {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+
+    let mut config: Config = CONFIG.load(deps.storage)?;
+    assert_not_paused(
+        &deps.querier,
+        config.factory_addr.clone(),
+        env.contract.address.clone(),
+    )?;
+
+    let zero_for_one = swap_direction(&config, &offer_asset.info)?;
+    let ask_asset_info = config.pair_info.asset_infos[zero_for_one as usize].clone();
+
+    let pool = POOL_STATE.load(deps.storage)?;
+    let fee_params = query_fee_params(deps.as_ref(), &config)?;
+    let result = compute_swap(
+        deps.storage,
+        &pool,
+        zero_for_one,
+        offer_asset.amount,
+        true,
+        &fee_params,
+    )?;
+
+    let (spread_amount, commission_amount) = compute_spread(
+        pool.sqrt_price_x96,
+        zero_for_one,
+        offer_asset.amount,
+        result.fee_amount,
+        result.amount_out,
+    )?;
+
+    // check max spread limit if exist
+    assert_max_spread(
+        belief_price,
+        max_spread,
+        offer_asset.amount,
+        result.amount_out + commission_amount,
+        spread_amount,
+    )?;
+
+    for (tick, fee_growth_global0, fee_growth_global1) in &result.crossed_ticks {
+        TICKS.update(deps.storage, tick_key(*tick), |info| -> StdResult<_> {
+            let mut info = info.unwrap_or_default();
+            info.fee_growth_outside0_x128 =
+                wrapping_sub(*fee_growth_global0, info.fee_growth_outside0_x128);
+            info.fee_growth_outside1_x128 =
+                wrapping_sub(*fee_growth_global1, info.fee_growth_outside1_x128);
+            Ok(info)
+        })?;
+    }
+    POOL_STATE.save(deps.storage, &result.pool)?;
+
+    // Accumulate prices for oracle
+    let prices = accumulate_prices(env, &config, pool.sqrt_price_x96)?;
+    save_cumulative_prices(deps.storage, &mut config, prices)?;
+
+    let return_asset = Asset {
+        info: ask_asset_info.clone(),
+        amount: result.amount_out,
+    };
+
+    let tax_amount = return_asset.compute_tax(&deps.querier)?;
+    let receiver = to.unwrap_or_else(|| sender.clone());
+    let mut messages: Vec<CosmosMsg> = vec![];
+    if !return_asset.amount.is_zero() {
+        messages.push(
+            return_asset
+                .clone()
+                .into_msg(&deps.querier, receiver.clone())?,
+        );
+    }
+
+    // Maker fee
+    if let Some(fee_address) = fee_params.fee_address {
+        if !result.maker_fee_amount.is_zero() {
+            messages.push(
+                Asset {
+                    info: offer_asset.info.clone(),
+                    amount: result.maker_fee_amount,
+                }
+                .into_msg(&deps.querier, fee_address)?,
+            );
+        }
+    }
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .set_data(to_binary(&SwapResponse {
+            offer_asset: offer_asset.clone(),
+            return_asset,
+            tax_amount,
+            spread_amount,
+            commission_amount,
+            maker_fee_amount: result.maker_fee_amount,
+        })?)
+        .add_attribute("action", "swap")
+        .add_attribute("sender", sender.as_str())
+        .add_attribute("receiver", receiver.as_str())
+        .add_attribute("offer_asset", offer_asset.info.to_string())
+        .add_attribute("ask_asset", ask_asset_info.to_string())
+        .add_attribute("offer_amount", offer_asset.amount.to_string())
+        .add_attribute("return_amount", result.amount_out.to_string())
+        .add_attribute("tax_amount", tax_amount.to_string())
+        .add_attribute("spread_amount", spread_amount.to_string())
+        .add_attribute("commission_amount", commission_amount.to_string())
+        .add_attribute("maker_fee_amount", result.maker_fee_amount.to_string()))
+}
+
+/// ## Description
+/// Returns a [`ContractError::AssetMismatch`] if the ask asset is specified and it is not the pool
+/// asset opposite to the offer asset.
+/// ## Params
+/// * **config** is the object of type [`Config`].
+///
+/// * **offer_asset_info** is the object of type [`AssetInfo`]. This is the asset to swap.
+///
+/// * **ask_asset_info** is an [`Option`] field of type [`AssetInfo`]. This is the asset to receive.
+fn assert_ask_asset(
+    config: &Config,
+    offer_asset_info: &AssetInfo,
+    ask_asset_info: Option<&AssetInfo>,
+) -> Result<(), ContractError> {
+    match ask_asset_info {
+        Some(ask_asset_info)
+            if ask_asset_info.equal(offer_asset_info)
+                || !config
+                    .pair_info
+                    .asset_infos
+                    .iter()
+                    .any(|asset_info| asset_info.equal(ask_asset_info)) =>
+        {
+            Err(ContractError::AssetMismatch {})
+        }
+        _ => Ok(()),
+    }
+}
+
+/// ## Description
+/// Returns true if the specified asset is the first pool asset, false if it is the second one
+/// or a [`ContractError::AssetMismatch`] if it doesn't belong to the pool.
+fn swap_direction(config: &Config, offer_asset_info: &AssetInfo) -> Result<bool, ContractError> {
+    if offer_asset_info.equal(&config.pair_info.asset_infos[0]) {
+        Ok(true)
+    } else if offer_asset_info.equal(&config.pair_info.asset_infos[1]) {
+        Ok(false)
+    } else {
+        Err(ContractError::AssetMismatch {})
+    }
+}
+
+/// ## Description
+/// Accumulate token prices for the pool assets using the square root price before the current block.
+/// Returns an [`Option`] with the new cumulative prices and the block time, or [`None`] if the
+/// prices were already accumulated in the current block.
+/// ## Params
+/// * **env** is the object of type [`Env`].
+///
+/// * **config** is the object of type [`Config`].
+///
+/// * **sqrt_price_x96** is the square root price of the pool.
+pub fn accumulate_prices(
+    env: Env,
+    config: &Config,
+    sqrt_price_x96: Uint256,
+) -> StdResult<Option<(Uint128, Uint128, u64)>> {
+    let block_time = env.block.time.seconds();
+    if block_time <= config.block_time_last {
+        return Ok(None);
+    }
+
+    let time_elapsed = Uint128::from(block_time - config.block_time_last);
+    let price_precision = Uint128::from(10u128.pow(TWAP_PRECISION.into()));
+    let scale = time_elapsed.checked_mul(price_precision)?;
+
+    // The accumulators are allowed to wrap around, only the differences between them matter
+    let pcl0 =
+        config
+            .price0_cumulative_last
+            .wrapping_add(low_u128(quote(scale, sqrt_price_x96, true)?));
+    let pcl1 =
+        config
+            .price1_cumulative_last
+            .wrapping_add(low_u128(quote(scale, sqrt_price_x96, false)?));
+
+    Ok(Some((pcl0, pcl1, block_time)))
+}
+
+/// ## Description
+/// Saves the cumulative prices returned by [`accumulate_prices`] in the config and records them as a
+/// new price observation. Does nothing if the prices were already accumulated in the current block.
+/// ## Params
+/// * **storage** is the object of type [`Storage`].
+///
+/// * **config** is the object of type [`Config`]. This is the pair config to update.
+///
+/// * **prices** is an [`Option`] with the cumulative prices and the block time returned by
+/// [`accumulate_prices`].
+pub fn save_cumulative_prices(
+    storage: &mut dyn Storage,
+    config: &mut Config,
+    prices: Option<(Uint128, Uint128, u64)>,
+) -> StdResult<()> {
+    if let Some((price0_cumulative_last, price1_cumulative_last, block_time)) = prices {
+        config.price0_cumulative_last = price0_cumulative_last;
+        config.price1_cumulative_last = price1_cumulative_last;
+        config.block_time_last = block_time;
+        CONFIG.save(storage, config)?;
+        store_observation(
+            storage,
+            &Observation {
+                timestamp: block_time,
+                price0_cumulative: price0_cumulative_last,
+                price1_cumulative: price1_cumulative_last,
+            },
+        )?;
+    }
+
+    Ok(())
+}
+
+/// ## Description
+/// Returns a [`ContractError`] on failure.
+/// If `belief_price` and `max_spread` both are given,
+/// we compute new spread else we just use pool network
+/// spread to check `max_spread`
+/// ## Params
+/// * **belief_price** is the object of type [`Option<Decimal>`]. This is the belief price used in the swap.
+///
+/// * **max_spread** is the object of type [`Option<Decimal>`]. This is the
+/// max spread allowed so that the swap can be executed successfuly.
+///
+/// * **offer_amount** is the object of type [`Uint128`]. This is the amount of assets to swap.
+///
+/// * **return_amount** is the object of type [`Uint128`]. This is the amount of assets to receive from the swap.
+///
+/// * **spread_amount** is the object of type [`Uint128`]. This is the spread used in the swap.
+pub fn assert_max_spread(
+    belief_price: Option<Decimal>,
+    max_spread: Option<Decimal>,
+    offer_amount: Uint128,
+    return_amount: Uint128,
+    spread_amount: Uint128,
+) -> Result<(), ContractError> {
+    let default_spread = Decimal::from_str(DEFAULT_SLIPPAGE)?;
+    let max_allowed_spread = Decimal::from_str(MAX_ALLOWED_SLIPPAGE)?;
+
+    let max_spread = max_spread.unwrap_or(default_spread);
+    if max_spread.gt(&max_allowed_spread) {
+        return Err(ContractError::AllowedSpreadAssertion {});
+    }
+
+    if let Some(belief_price) = belief_price {
+        let expected_return =
+            offer_amount * Decimal::from(Decimal256::one() / Decimal256::from(belief_price));
+        let spread_amount = expected_return
+            .checked_sub(return_amount)
+            .unwrap_or_else(|_| Uint128::zero());
+
+        if return_amount < expected_return
+            && Decimal::from_ratio(spread_amount, expected_return) > max_spread
+        {
+            return Err(ContractError::MaxSpreadAssertion {});
+        }
+    } else if Decimal::from_ratio(spread_amount, return_amount + spread_amount) > max_spread {
+        return Err(ContractError::MaxSpreadAssertion {});
+    }
+
+    Ok(())
+}
+
+/// ## Description
+/// Available the query messages of the contract.
+/// ## Params
+/// * **deps** is the object of type [`Deps`].
+///
+/// * **env** is the object of type [`Env`].
+///
+/// * **msg** is the object of type [`QueryMsg`].
+///
+/// ## Queries
+/// * **QueryMsg::Pair {}** Returns information about a pair in an object of type [`PairInfo`].
+///
+/// * **QueryMsg::Pool {}** Returns the pool balances and the active liquidity in a [`PoolResponse`] object.
+///
+/// * **QueryMsg::Config {}** Returns information about the controls settings in a
+/// [`ConfigResponse`] object.
+///
+/// * **QueryMsg::Simulation { offer_asset, ask_asset_info }** Returns information about the
+/// simulation of the swap in a [`SimulationResponse`] object.
+///
+/// * **QueryMsg::ReverseSimulation { offer_asset_info, ask_asset }** Returns information about the
+/// reverse simulation in a [`ReverseSimulationResponse`] object.
+///
+/// * **QueryMsg::CumulativePrices {}** Returns information about the cumulative prices in a
+/// [`CumulativePricesResponse`] object.
+///
+/// * **QueryMsg::Observe { seconds_ago }** Returns the cumulative prices at each of the specified
+/// number of seconds before the current block in an [`ObserveResponse`] object.
+///
+/// * **QueryMsg::PoolState {}** Returns the current price, tick and active liquidity in a
+/// [`PoolStateResponse`] object.
+///
+/// * **QueryMsg::Position { position_id }** Returns information about a position in a
+/// [`PositionResponse`] object.
+///
+/// * **QueryMsg::Positions { owner, start_after, limit }** Returns the positions of an owner in a
+/// vector that contains objects of type [`PositionResponse`].
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Pair {} => to_binary(&query_pair_info(deps)?),
+        QueryMsg::Pool {} => to_binary(&query_pool(deps)?),
+        QueryMsg::Config {} => to_binary(&query_config(deps, env)?),
+        QueryMsg::Simulation {
+            offer_asset,
+            ask_asset_info,
+        } => to_binary(&query_simulation(deps, offer_asset, ask_asset_info)?),
+        QueryMsg::ReverseSimulation {
+            offer_asset_info,
+            ask_asset,
+        } => to_binary(&query_reverse_simulation(
+            deps,
+            ask_asset,
+            offer_asset_info,
+        )?),
+        QueryMsg::CumulativePrices {} => to_binary(&query_cumulative_prices(deps, env)?),
+        QueryMsg::Observe { seconds_ago } => to_binary(&query_observe(deps, env, seconds_ago)?),
+        QueryMsg::PoolState {} => to_binary(&query_pool_state(deps)?),
+        QueryMsg::Position { position_id } => to_binary(&query_position(deps, position_id)?),
+        QueryMsg::Positions {
+            owner,
+            start_after,
+            limit,
+        } => to_binary(&query_positions(deps, owner, start_after, limit)?),
+    }
+}
+
+/// ## Description
+/// Returns information about a pair in an object of type [`PairInfo`].
+/// ## Params
+/// * **deps** is the object of type [`Deps`].
+pub fn query_pair_info(deps: Deps) -> StdResult<PairInfo> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    Ok(config.pair_info)
+}
+
+/// ## Description
+/// Returns the pool balances and the active liquidity in a [`PoolResponse`] object.
+/// ## Params
+/// * **deps** is the object of type [`Deps`].
+pub fn query_pool(deps: Deps) -> StdResult<PoolResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let pool = POOL_STATE.load(deps.storage)?;
+
+    Ok(PoolResponse {
+        assets: config
+            .pair_info
+            .query_pools(&deps.querier, config.pair_info.contract_addr.clone())?,
+        total_share: pool.liquidity,
+    })
+}
+
+/// ## Description
+/// Returns information about the controls settings in a [`ConfigResponse`] object.
+/// ## Params
+/// * **deps** is the object of type [`Deps`].
+///
+/// * **env** is the object of type [`Env`].
+pub fn query_config(deps: Deps, env: Env) -> StdResult<ConfigResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        block_time_last: config.block_time_last,
+        params: Some(to_binary(&ConcentratedPoolConfig {
+            tick_spacing: config.tick_spacing,
+        })?),
+        paused: query_pair_paused(&deps.querier, config.factory_addr, env.contract.address)?,
+    })
+}
+
+/// ## Description
+/// Returns information about a swap simulation in a [`SimulationResponse`] object.
+/// The commission is denominated in the ask asset.
+/// ## Params
+/// * **deps** is the object of type [`Deps`].
+///
+/// * **offer_asset** is the object of type [`Asset`].
+///
+/// * **ask_asset_info** is the object of type [`Option<AssetInfo>`]. If it is specified it must be
+/// the pool asset opposite to the offer asset.
+pub fn query_simulation(
+    deps: Deps,
+    offer_asset: Asset,
+    ask_asset_info: Option<AssetInfo>,
+) -> StdResult<SimulationResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    assert_ask_asset(&config, &offer_asset.info, ask_asset_info.as_ref())
+        .map_err(|e| StdError::generic_err(e.to_string()))?;
+    let zero_for_one = swap_direction(&config, &offer_asset.info)
+        .map_err(|e| StdError::generic_err(e.to_string()))?;
+
+    let pool = POOL_STATE.load(deps.storage)?;
+    let fee_params = query_fee_params(deps, &config)?;
+    let result = compute_swap(
+        deps.storage,
+        &pool,
+        zero_for_one,
+        offer_asset.amount,
+        true,
+        &fee_params,
+    )
+    .map_err(|e| StdError::generic_err(e.to_string()))?;
+
+    let (spread_amount, commission_amount) = compute_spread(
+        pool.sqrt_price_x96,
+        zero_for_one,
+        offer_asset.amount,
+        result.fee_amount,
+        result.amount_out,
+    )?;
+
+    Ok(SimulationResponse {
+        return_amount: result.amount_out,
+        spread_amount,
+        commission_amount,
+        fee_rate: Decimal::from_ratio(fee_params.fee_pips, FEE_PIPS_PRECISION),
+    })
+}
+
+/// ## Description
+/// Returns information about a reverse swap simulation in a [`ReverseSimulationResponse`] object.
+/// The commission is denominated in the ask asset.
+/// ## Params
+/// * **deps** is the object of type [`Deps`].
+///
+/// * **ask_asset** is the object of type [`Asset`].
+///
+/// * **offer_asset_info** is the object of type [`Option<AssetInfo>`]. If it is specified it must be
+/// the pool asset opposite to the ask asset.
+pub fn query_reverse_simulation(
+    deps: Deps,
+    ask_asset: Asset,
+    offer_asset_info: Option<AssetInfo>,
+) -> StdResult<ReverseSimulationResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    // The offer asset must be the pool asset opposite to the ask asset, as in a swap
+    assert_ask_asset(&config, &ask_asset.info, offer_asset_info.as_ref())
+        .map_err(|e| StdError::generic_err(e.to_string()))?;
+    let zero_for_one = !swap_direction(&config, &ask_asset.info)
+        .map_err(|e| StdError::generic_err(e.to_string()))?;
+
+    let pool = POOL_STATE.load(deps.storage)?;
+    let fee_params = query_fee_params(deps, &config)?;
+    let result = compute_swap(
+        deps.storage,
+        &pool,
+        zero_for_one,
+        ask_asset.amount,
+        false,
+        &fee_params,
+    )
+    .map_err(|e| StdError::generic_err(e.to_string()))?;
+
+    let (spread_amount, commission_amount) = compute_spread(
+        pool.sqrt_price_x96,
+        zero_for_one,
+        result.amount_in,
+        result.fee_amount,
+        result.amount_out,
+    )?;
+
+    Ok(ReverseSimulationResponse {
+        offer_amount: result.amount_in,
+        spread_amount,
+        commission_amount,
+    })
+}
+
+/// ## Description
+/// Returns information about the cumulative prices in a [`CumulativePricesResponse`] object.
+/// ## Params
+/// * **deps** is the object of type [`Deps`].
+///
+/// * **env** is the object of type [`Env`].
+pub fn query_cumulative_prices(deps: Deps, env: Env) -> StdResult<CumulativePricesResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let pool_state = POOL_STATE.load(deps.storage)?;
+    let PoolResponse {
+        assets,
+        total_share,
+    } = query_pool(deps)?;
+
+    let mut price0_cumulative_last = config.price0_cumulative_last;
+    let mut price1_cumulative_last = config.price1_cumulative_last;
+
+    if let Some((price0_cumulative_new, price1_cumulative_new, _)) =
+        accumulate_prices(env, &config, pool_state.sqrt_price_x96)?
+    {
+        price0_cumulative_last = price0_cumulative_new;
+        price1_cumulative_last = price1_cumulative_new;
+    }
+
+    let cumulative_prices = vec![
+        (
+            assets[0].info.clone(),
+            assets[1].info.clone(),
+            price0_cumulative_last,
+        ),
+        (
+            assets[1].info.clone(),
+            assets[0].info.clone(),
+            price1_cumulative_last,
+        ),
+    ];
+
+    Ok(CumulativePricesResponse {
+        assets,
+        total_share,
+        price0_cumulative_last,
+        price1_cumulative_last,
+        cumulative_prices,
+    })
+}
+
+/// ## Description
+/// Returns the cumulative prices at each of the specified number of seconds before the current
+/// block in an [`ObserveResponse`] object.
+/// ## Params
+/// * **deps** is the object of type [`Deps`].
+///
+/// * **env** is the object of type [`Env`].
+///
+/// * **seconds_ago** is a vector with items of type [`u64`]. These are the numbers of seconds
+/// before the current block time to return the cumulative prices for.
+pub fn query_observe(deps: Deps, env: Env, seconds_ago: Vec<u64>) -> StdResult<ObserveResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let pool_state = POOL_STATE.load(deps.storage)?;
+
+    let mut current = Observation {
+        timestamp: config.block_time_last,
+        price0_cumulative: config.price0_cumulative_last,
+        price1_cumulative: config.price1_cumulative_last,
+    };
+
+    if let Some((price0_cumulative_new, price1_cumulative_new, block_time)) =
+        accumulate_prices(env, &config, pool_state.sqrt_price_x96)?
+    {
+        current = Observation {
+            timestamp: block_time,
+            price0_cumulative: price0_cumulative_new,
+            price1_cumulative: price1_cumulative_new,
+        };
+    }
+
+    let observations = seconds_ago
+        .into_iter()
+        .map(|seconds_ago| observe(deps.storage, &current, seconds_ago))
+        .collect::<StdResult<Vec<Observation>>>()?;
+
+    Ok(ObserveResponse { observations })
+}
+
+/// ## Description
+/// Returns the current price, tick and active liquidity in a [`PoolStateResponse`] object.
+/// ## Params
+/// * **deps** is the object of type [`Deps`].
+pub fn query_pool_state(deps: Deps) -> StdResult<PoolStateResponse> {
+    let pool = POOL_STATE.load(deps.storage)?;
+
+    Ok(PoolStateResponse {
+        sqrt_price_x96: pool.sqrt_price_x96,
+        price: Decimal::from_ratio(
+            price_from_sqrt_price(pool.sqrt_price_x96)?,
+            Decimal::one().numerator(),
+        ),
+        tick: pool.tick,
+        liquidity: pool.liquidity,
+        fee_growth_global0_x128: pool.fee_growth_global0_x128,
+        fee_growth_global1_x128: pool.fee_growth_global1_x128,
+    })
+}
+
+/// ## Description
+/// Returns information about a position in a [`PositionResponse`] object.
+/// ## Params
+/// * **deps** is the object of type [`Deps`].
+///
+/// * **position_id** is the identifier of the position.
+pub fn query_position(deps: Deps, position_id: u64) -> StdResult<PositionResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let pool = POOL_STATE.load(deps.storage)?;
+    let position = POSITIONS.load(deps.storage, U64Key::new(position_id))?;
+
+    position_response(deps.storage, &config, &pool, position_id, position)
+}
+
+/// ## Description
+/// Returns the positions of an owner in a vector that contains objects of type [`PositionResponse`].
+/// ## Params
+/// * **deps** is the object of type [`Deps`].
+///
+/// * **owner** is the owner of the positions.
+///
+/// * **start_after** is an [`Option`] type. Sets the position identifier to start reading after.
+///
+/// * **limit** is an [`Option`] type. Sets the number of items to be read.
+pub fn query_positions(
+    deps: Deps,
+    owner: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<PositionResponse>> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let pool = POOL_STATE.load(deps.storage)?;
+    let owner = addr_validate_to_lower(deps.api, &owner)?;
+
+    read_owner_positions(deps.storage, &owner, start_after, limit)?
+        .into_iter()
+        .map(|position_id| {
+            let position = POSITIONS.load(deps.storage, U64Key::new(position_id))?;
+            position_response(deps.storage, &config, &pool, position_id, position)
+        })
+        .collect()
+}
+
+/// ## Description
+/// Builds a [`PositionResponse`] with the current position amounts and the uncollected fees.
+fn position_response(
+    storage: &dyn Storage,
+    config: &Config,
+    pool: &PoolState,
+    position_id: u64,
+    mut position: Position,
+) -> StdResult<PositionResponse> {
+    let (amount0, amount1) = position_amounts(
+        pool,
+        position.tick_lower,
+        position.tick_upper,
+        position.liquidity,
+        false,
+    )?;
+
+    let (fee_growth_inside0, fee_growth_inside1) =
+        get_fee_growth_inside(storage, pool, position.tick_lower, position.tick_upper)?;
+    accrue_fees(&mut position, fee_growth_inside0, fee_growth_inside1)?;
+
+    let asset_infos = &config.pair_info.asset_infos;
+    Ok(PositionResponse {
+        position_id,
+        owner: position.owner,
+        tick_lower: position.tick_lower,
+        tick_upper: position.tick_upper,
+        liquidity: position.liquidity,
+        assets: vec![
+            Asset {
+                info: asset_infos[0].clone(),
+                amount: amount0,
+            },
+            Asset {
+                info: asset_infos[1].clone(),
+                amount: amount1,
+            },
+        ],
+        fees: vec![
+            Asset {
+                info: asset_infos[0].clone(),
+                amount: position.tokens_owed0,
+            },
+            Asset {
+                info: asset_infos[1].clone(),
+                amount: position.tokens_owed1,
+            },
+        ],
+    })
+}
+
+/// ## Description
+/// Used for migration of contract. Returns the default object of type [`Response`].
+/// ## Params
+/// * **_deps** is the object of type [`DepsMut`].
+///
+/// * **_env** is the object of type [`Env`].
+///
+/// * **_msg** is the object of type [`MigrateMsg`].
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    Ok(Response::default())
+}
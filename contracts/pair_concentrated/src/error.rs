@@ -0,0 +1,77 @@
+use astroport::querier::PauseError;
+use cosmwasm_std::{OverflowError, StdError};
+use thiserror::Error;
+
+/// ## Description
+/// This enum describes concentrated liquidity pair contract errors!
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Event of zero transfer")]
+    InvalidZeroAmount {},
+
+    #[error("Operation exceeds max spread limit")]
+    MaxSpreadAssertion {},
+
+    #[error("Provided spread amount exceeds allowed limit")]
+    AllowedSpreadAssertion {},
+
+    #[error("Operation exceeds max splippage tolerance")]
+    MaxSlippageAssertion {},
+
+    #[error("Doubling assets in asset infos")]
+    DoublingAssets {},
+
+    #[error("The pool must consist of exactly 2 assets")]
+    InvalidNumberOfAssets {},
+
+    #[error("Asset mismatch between the requested and stored in contract")]
+    AssetMismatch {},
+
+    #[error("You need to provide init params")]
+    InitParamsNotFound {},
+
+    #[error("Tick spacing must be between 1 and {0}")]
+    InvalidTickSpacing(u32),
+
+    #[error("The initial price is out of the supported range")]
+    InvalidInitialPrice {},
+
+    #[error("Invalid tick range: the ticks must be within the supported range, divisible by the tick spacing and the lower tick must be below the upper one")]
+    InvalidTickRange {},
+
+    #[error("The provided amounts result in zero liquidity")]
+    ZeroLiquidity {},
+
+    #[error("Position {0} not found")]
+    PositionNotFound(u64),
+
+    #[error("The position doesn't have enough liquidity")]
+    InsufficientPositionLiquidity {},
+
+    #[error("The pool doesn't have enough liquidity to fill the swap")]
+    InsufficientLiquidity {},
+
+    #[error("The pair is paused")]
+    Paused {},
+}
+
+impl From<OverflowError> for ContractError {
+    fn from(o: OverflowError) -> Self {
+        StdError::from(o).into()
+    }
+}
+
+impl From<PauseError> for ContractError {
+    fn from(err: PauseError) -> Self {
+        match err {
+            PauseError::Std(err) => ContractError::Std(err),
+            PauseError::Paused => ContractError::Paused {},
+        }
+    }
+}
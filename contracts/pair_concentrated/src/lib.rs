@@ -0,0 +1,11 @@
+pub mod contract;
+pub mod math;
+pub mod state;
+
+mod error;
+
+#[cfg(test)]
+mod testing;
+
+#[cfg(test)]
+mod mock_querier;
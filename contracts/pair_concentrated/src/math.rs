@@ -0,0 +1,594 @@
+use astroport::pair_concentrated::{MAX_TICK, MIN_TICK};
+use astroport::U256;
+use cosmwasm_std::{StdError, StdResult, Uint128, Uint256, Uint512};
+use std::convert::TryFrom;
+
+/// The fee denominator used in the swap step computation (fees are expressed in hundredths of a bip)
+pub const FEE_PIPS_PRECISION: u32 = 1_000_000;
+
+/// The maximum tick spacing allowed for a pool
+pub const MAX_TICK_SPACING: u32 = 16384;
+
+/// 2^96, the denominator of the Q64.96 square root prices
+pub const Q96: Uint256 = u256(0, 1 << 96);
+/// 2^128, the denominator of the Q128.128 fee growth values
+pub const Q128: Uint256 = u256(1, 0);
+
+/// The square root price at [`MIN_TICK`]
+pub const MIN_SQRT_RATIO: Uint256 = u256(0, 4295128739);
+/// The square root price at [`MAX_TICK`]
+pub const MAX_SQRT_RATIO: Uint256 = u256(0xfffd8963, 0xefd1fc6a506488495d951d5263988d26);
+
+/// The multipliers applied for each bit of the absolute tick value in [`get_sqrt_ratio_at_tick`]
+/// (1.0001^(-2^i / 2) as Q128.128 numbers)
+const TICK_RATIOS: [(u32, u128); 19] = [
+    (0x2, 0xfff97272373d413259a46990580e213a),
+    (0x4, 0xfff2e50f5f656932ef12357cf3c7fdcc),
+    (0x8, 0xffe5caca7e10e4e61c3624eaa0941cd0),
+    (0x10, 0xffcb9843d60f6159c9db58835c926644),
+    (0x20, 0xff973b41fa98c081472e6896dfb254c0),
+    (0x40, 0xff2ea16466c96a3843ec78b326b52861),
+    (0x80, 0xfe5dee046a99a2a811c461f1969c3053),
+    (0x100, 0xfcbe86c7900a88aedcffc83b479aa3a4),
+    (0x200, 0xf987a7253ac413176f2b074cf7815e54),
+    (0x400, 0xf3392b0822b70005940c7a398e4b70f3),
+    (0x800, 0xe7159475a2c29b7443b29c7fa6e889d9),
+    (0x1000, 0xd097f3bdfd2022b8845ad8f792aa5825),
+    (0x2000, 0xa9f746462d870fdf8a65dc1f90e061e5),
+    (0x4000, 0x70d869a156d2a1b890bb3df62baf32f7),
+    (0x8000, 0x31be135f97d08fd981231505542fcfa6),
+    (0x10000, 0x9aa508b5b7a84e1c677de54f3e99bc9),
+    (0x20000, 0x5d6af8dedb81196699c329225ee604),
+    (0x40000, 0x2216e584f5fa1ea926041bedfe98),
+    (0x80000, 0x48a170391f7dc42444e8fa2),
+];
+
+/// ## Description
+/// Builds a [`Uint256`] from its high and low 128 bit halves.
+const fn u256(high: u128, low: u128) -> Uint256 {
+    let high = high.to_be_bytes();
+    let low = low.to_be_bytes();
+    let mut bytes = [0u8; 32];
+    let mut i = 0;
+    while i < 16 {
+        bytes[i] = high[i];
+        bytes[i + 16] = low[i];
+        i += 1;
+    }
+    Uint256::from_be_bytes(bytes)
+}
+
+/// ## Description
+/// Converts a [`Uint512`] to a [`Uint256`] returning a [`StdError`] on overflow.
+fn to_u256(value: Uint512) -> StdResult<Uint256> {
+    Uint256::try_from(value).map_err(|_| StdError::generic_err("Uint256 overflow"))
+}
+
+/// ## Description
+/// Converts a [`Uint256`] to a [`Uint128`] returning a [`StdError`] on overflow.
+pub fn to_u128(value: Uint256) -> StdResult<Uint128> {
+    Uint128::try_from(value).map_err(|_| StdError::generic_err("Uint128 overflow"))
+}
+
+/// ## Description
+/// Returns the lowest 128 bits of a [`Uint256`]. Used for the values that are allowed to wrap around.
+pub fn low_u128(value: Uint256) -> Uint128 {
+    let bytes = value.to_be_bytes();
+    let mut low = [0u8; 16];
+    low.copy_from_slice(&bytes[16..]);
+    Uint128::new(u128::from_be_bytes(low))
+}
+
+/// ## Description
+/// Calculates `a * b / denominator` with a 512 bit intermediate result rounding down.
+pub fn mul_div(a: Uint256, b: Uint256, denominator: Uint256) -> StdResult<Uint256> {
+    let result = a
+        .full_mul(b)
+        .checked_div(Uint512::from(denominator))
+        .map_err(|e| StdError::generic_err(e.to_string()))?;
+    to_u256(result)
+}
+
+/// ## Description
+/// Calculates `a * b / denominator` with a 512 bit intermediate result rounding up.
+pub fn mul_div_rounding_up(a: Uint256, b: Uint256, denominator: Uint256) -> StdResult<Uint256> {
+    let result = mul_div(a, b, denominator)?;
+    if a.full_mul(b)
+        .checked_rem(Uint512::from(denominator))?
+        .is_zero()
+    {
+        Ok(result)
+    } else {
+        Ok(result.checked_add(Uint256::from(1u8))?)
+    }
+}
+
+/// ## Description
+/// Calculates `a / b` rounding up.
+fn div_rounding_up(a: Uint256, b: Uint256) -> StdResult<Uint256> {
+    let result = a.checked_div(b)?;
+    if a.checked_rem(b)?.is_zero() {
+        Ok(result)
+    } else {
+        Ok(result + Uint256::from(1u8))
+    }
+}
+
+/// ## Description
+/// Adds two [`Uint256`] values wrapping around on overflow. Fee growth values rely on modular arithmetic.
+pub fn wrapping_add(a: Uint256, b: Uint256) -> Uint256 {
+    match a.checked_add(b) {
+        Ok(sum) => sum,
+        Err(_) => a - (Uint256::MAX - b) - Uint256::from(1u8),
+    }
+}
+
+/// ## Description
+/// Subtracts two [`Uint256`] values wrapping around on underflow. Fee growth values rely on modular arithmetic.
+pub fn wrapping_sub(a: Uint256, b: Uint256) -> Uint256 {
+    if a >= b {
+        a - b
+    } else {
+        Uint256::MAX - (b - a) + Uint256::from(1u8)
+    }
+}
+
+/// ## Description
+/// Applies a signed liquidity delta to an amount of liquidity.
+pub fn add_delta(liquidity: Uint128, delta: i128) -> StdResult<Uint128> {
+    if delta < 0 {
+        Ok(liquidity.checked_sub(Uint128::new(delta.unsigned_abs()))?)
+    } else {
+        Ok(liquidity.checked_add(Uint128::new(delta as u128))?)
+    }
+}
+
+/// ## Description
+/// Calculates the integer square root of a [`Uint256`].
+fn sqrt(value: Uint256) -> Uint256 {
+    let mut bytes = [0u8; 32];
+    U256::from_big_endian(&value.to_be_bytes())
+        .integer_sqrt()
+        .to_big_endian(&mut bytes);
+    Uint256::from_be_bytes(bytes)
+}
+
+/// ## Description
+/// Returns the square root price as a Q64.96 number for the specified price given as an 18 digits fixed point number.
+/// ## Params
+/// * **price** is the price multiplied by 10^18.
+pub fn sqrt_price_from_price(price: Uint128) -> StdResult<Uint256> {
+    // sqrt(price * 2^128 / 10^18) * 2^32 = sqrt(price) * 2^96
+    let value = mul_div(
+        Uint256::from(price),
+        Q128,
+        Uint256::from(1_000_000_000_000_000_000u128),
+    )?;
+    Ok(sqrt(value) << 32)
+}
+
+/// ## Description
+/// Returns the price given as an 18 digits fixed point number for the specified Q64.96 square root price.
+pub fn price_from_sqrt_price(sqrt_price_x96: Uint256) -> StdResult<Uint128> {
+    let price_x96 = mul_div(sqrt_price_x96, sqrt_price_x96, Q96)?;
+    to_u128(mul_div(
+        price_x96,
+        Uint256::from(1_000_000_000_000_000_000u128),
+        Q96,
+    )?)
+}
+
+/// ## Description
+/// Returns the amount of the second asset equal in value to the specified amount of the first one
+/// at the specified price, or vice versa if `zero_for_one` is false.
+pub fn quote(amount: Uint128, sqrt_price_x96: Uint256, zero_for_one: bool) -> StdResult<Uint256> {
+    let amount = Uint256::from(amount);
+    if zero_for_one {
+        mul_div(mul_div(amount, sqrt_price_x96, Q96)?, sqrt_price_x96, Q96)
+    } else {
+        mul_div(mul_div(amount, Q96, sqrt_price_x96)?, Q96, sqrt_price_x96)
+    }
+}
+
+/// ## Description
+/// Returns sqrt(1.0001^tick) as a Q64.96 number.
+/// ## Params
+/// * **tick** is the tick for which to compute the square root price.
+pub fn get_sqrt_ratio_at_tick(tick: i32) -> StdResult<Uint256> {
+    if !(MIN_TICK..=MAX_TICK).contains(&tick) {
+        return Err(StdError::generic_err(format!("Tick {} out of range", tick)));
+    }
+    let abs_tick = tick.unsigned_abs();
+
+    let mut ratio = if abs_tick & 0x1 != 0 {
+        u256(0, 0xfffcb933bd6fad37aa2d162d1a594001)
+    } else {
+        Q128
+    };
+    for (bit, multiplier) in TICK_RATIOS.iter() {
+        if abs_tick & bit != 0 {
+            ratio = (ratio * Uint256::from(*multiplier)) >> 128;
+        }
+    }
+
+    if tick > 0 {
+        ratio = Uint256::MAX / ratio;
+    }
+
+    // Convert from Q128.128 to Q64.96 rounding up so that get_tick_at_sqrt_ratio is consistent
+    let shifted = ratio >> 32;
+    if (shifted << 32) == ratio {
+        Ok(shifted)
+    } else {
+        Ok(shifted + Uint256::from(1u8))
+    }
+}
+
+/// ## Description
+/// Returns the greatest tick for which [`get_sqrt_ratio_at_tick`] is less than or equal to the specified price.
+/// ## Params
+/// * **sqrt_price_x96** is the square root price as a Q64.96 number.
+pub fn get_tick_at_sqrt_ratio(sqrt_price_x96: Uint256) -> StdResult<i32> {
+    if sqrt_price_x96 < MIN_SQRT_RATIO || sqrt_price_x96 >= MAX_SQRT_RATIO {
+        return Err(StdError::generic_err("Square root price out of range"));
+    }
+
+    let (mut low, mut high) = (MIN_TICK, MAX_TICK);
+    while low < high {
+        let mid = low + (high - low + 1) / 2;
+        if get_sqrt_ratio_at_tick(mid)? <= sqrt_price_x96 {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
+    }
+
+    Ok(low)
+}
+
+/// ## Description
+/// Returns the amount of the first asset between two prices for the specified liquidity.
+/// ## Params
+/// * **sqrt_ratio_a** and **sqrt_ratio_b** are the range bounds in any order.
+///
+/// * **liquidity** is the liquidity in the range.
+///
+/// * **round_up** determines whether the result is rounded up or down.
+pub fn get_amount0_delta(
+    sqrt_ratio_a: Uint256,
+    sqrt_ratio_b: Uint256,
+    liquidity: Uint128,
+    round_up: bool,
+) -> StdResult<Uint256> {
+    let (sqrt_ratio_a, sqrt_ratio_b) = if sqrt_ratio_a > sqrt_ratio_b {
+        (sqrt_ratio_b, sqrt_ratio_a)
+    } else {
+        (sqrt_ratio_a, sqrt_ratio_b)
+    };
+    if sqrt_ratio_a.is_zero() {
+        return Err(StdError::generic_err("Square root price must be positive"));
+    }
+
+    let numerator1 = Uint256::from(liquidity) << 96;
+    let numerator2 = sqrt_ratio_b - sqrt_ratio_a;
+
+    if round_up {
+        div_rounding_up(
+            mul_div_rounding_up(numerator1, numerator2, sqrt_ratio_b)?,
+            sqrt_ratio_a,
+        )
+    } else {
+        Ok(mul_div(numerator1, numerator2, sqrt_ratio_b)? / sqrt_ratio_a)
+    }
+}
+
+/// ## Description
+/// Returns the amount of the second asset between two prices for the specified liquidity.
+/// ## Params
+/// * **sqrt_ratio_a** and **sqrt_ratio_b** are the range bounds in any order.
+///
+/// * **liquidity** is the liquidity in the range.
+///
+/// * **round_up** determines whether the result is rounded up or down.
+pub fn get_amount1_delta(
+    sqrt_ratio_a: Uint256,
+    sqrt_ratio_b: Uint256,
+    liquidity: Uint128,
+    round_up: bool,
+) -> StdResult<Uint256> {
+    let (sqrt_ratio_a, sqrt_ratio_b) = if sqrt_ratio_a > sqrt_ratio_b {
+        (sqrt_ratio_b, sqrt_ratio_a)
+    } else {
+        (sqrt_ratio_a, sqrt_ratio_b)
+    };
+
+    if round_up {
+        mul_div_rounding_up(liquidity.into(), sqrt_ratio_b - sqrt_ratio_a, Q96)
+    } else {
+        mul_div(liquidity.into(), sqrt_ratio_b - sqrt_ratio_a, Q96)
+    }
+}
+
+/// ## Description
+/// Returns the next square root price after adding or removing an amount of the first asset.
+/// The result is rounded up so that the price moves no further than the amount allows.
+fn get_next_sqrt_price_from_amount0_rounding_up(
+    sqrt_price_x96: Uint256,
+    liquidity: Uint128,
+    amount: Uint256,
+    add: bool,
+) -> StdResult<Uint256> {
+    if amount.is_zero() {
+        return Ok(sqrt_price_x96);
+    }
+    let numerator1 = Uint256::from(liquidity) << 96;
+
+    if add {
+        if let Ok(product) = amount.checked_mul(sqrt_price_x96) {
+            if let Ok(denominator) = numerator1.checked_add(product) {
+                return mul_div_rounding_up(numerator1, sqrt_price_x96, denominator);
+            }
+        }
+        div_rounding_up(
+            numerator1,
+            (numerator1 / sqrt_price_x96).checked_add(amount)?,
+        )
+    } else {
+        let product = amount.checked_mul(sqrt_price_x96)?;
+        if numerator1 <= product {
+            return Err(StdError::generic_err("Not enough liquidity for the output"));
+        }
+        mul_div_rounding_up(numerator1, sqrt_price_x96, numerator1 - product)
+    }
+}
+
+/// ## Description
+/// Returns the next square root price after adding or removing an amount of the second asset.
+/// The result is rounded down so that the price moves no further than the amount allows.
+fn get_next_sqrt_price_from_amount1_rounding_down(
+    sqrt_price_x96: Uint256,
+    liquidity: Uint128,
+    amount: Uint256,
+    add: bool,
+) -> StdResult<Uint256> {
+    if add {
+        let quotient = mul_div(amount, Q96, liquidity.into())?;
+        Ok(sqrt_price_x96.checked_add(quotient)?)
+    } else {
+        let quotient = mul_div_rounding_up(amount, Q96, liquidity.into())?;
+        if sqrt_price_x96 <= quotient {
+            return Err(StdError::generic_err("Not enough liquidity for the output"));
+        }
+        Ok(sqrt_price_x96 - quotient)
+    }
+}
+
+/// ## Description
+/// Returns the next square root price after swapping in the specified input amount.
+fn get_next_sqrt_price_from_input(
+    sqrt_price_x96: Uint256,
+    liquidity: Uint128,
+    amount_in: Uint256,
+    zero_for_one: bool,
+) -> StdResult<Uint256> {
+    if zero_for_one {
+        get_next_sqrt_price_from_amount0_rounding_up(sqrt_price_x96, liquidity, amount_in, true)
+    } else {
+        get_next_sqrt_price_from_amount1_rounding_down(sqrt_price_x96, liquidity, amount_in, true)
+    }
+}
+
+/// ## Description
+/// Returns the next square root price after swapping out the specified output amount.
+fn get_next_sqrt_price_from_output(
+    sqrt_price_x96: Uint256,
+    liquidity: Uint128,
+    amount_out: Uint256,
+    zero_for_one: bool,
+) -> StdResult<Uint256> {
+    if zero_for_one {
+        get_next_sqrt_price_from_amount1_rounding_down(sqrt_price_x96, liquidity, amount_out, false)
+    } else {
+        get_next_sqrt_price_from_amount0_rounding_up(sqrt_price_x96, liquidity, amount_out, false)
+    }
+}
+
+/// ## Description
+/// The result of a single swap step within one tick range.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SwapStep {
+    /// The square root price after the step
+    pub sqrt_price_next_x96: Uint256,
+    /// The input amount without the fee
+    pub amount_in: Uint256,
+    pub amount_out: Uint256,
+    /// The fee taken from the input
+    pub fee_amount: Uint256,
+}
+
+/// ## Description
+/// Computes the result of swapping within a single tick range, moving the price from the current
+/// one towards the target price until the remaining amount is exhausted.
+/// ## Params
+/// * **sqrt_price_current_x96** is the current square root price.
+///
+/// * **sqrt_price_target_x96** is the price that cannot be exceeded, it determines the swap direction.
+///
+/// * **liquidity** is the usable liquidity.
+///
+/// * **amount_remaining** is the input amount (including the fee) if `exact_in` is true, otherwise the output amount.
+///
+/// * **fee_pips** is the fee charged on the input in hundredths of a bip.
+pub fn compute_swap_step(
+    sqrt_price_current_x96: Uint256,
+    sqrt_price_target_x96: Uint256,
+    liquidity: Uint128,
+    amount_remaining: Uint256,
+    exact_in: bool,
+    fee_pips: u32,
+) -> StdResult<SwapStep> {
+    let zero_for_one = sqrt_price_current_x96 >= sqrt_price_target_x96;
+    let fee_precision = Uint256::from(FEE_PIPS_PRECISION);
+    let fee = Uint256::from(fee_pips);
+
+    let mut amount_in = Uint256::zero();
+    let mut amount_out = Uint256::zero();
+
+    let sqrt_price_next_x96 = if exact_in {
+        let amount_remaining_less_fee =
+            mul_div(amount_remaining, fee_precision - fee, fee_precision)?;
+        amount_in = if zero_for_one {
+            get_amount0_delta(
+                sqrt_price_target_x96,
+                sqrt_price_current_x96,
+                liquidity,
+                true,
+            )?
+        } else {
+            get_amount1_delta(
+                sqrt_price_current_x96,
+                sqrt_price_target_x96,
+                liquidity,
+                true,
+            )?
+        };
+        if amount_remaining_less_fee >= amount_in {
+            sqrt_price_target_x96
+        } else {
+            get_next_sqrt_price_from_input(
+                sqrt_price_current_x96,
+                liquidity,
+                amount_remaining_less_fee,
+                zero_for_one,
+            )?
+        }
+    } else {
+        amount_out = if zero_for_one {
+            get_amount1_delta(
+                sqrt_price_target_x96,
+                sqrt_price_current_x96,
+                liquidity,
+                false,
+            )?
+        } else {
+            get_amount0_delta(
+                sqrt_price_current_x96,
+                sqrt_price_target_x96,
+                liquidity,
+                false,
+            )?
+        };
+        if amount_remaining >= amount_out {
+            sqrt_price_target_x96
+        } else {
+            get_next_sqrt_price_from_output(
+                sqrt_price_current_x96,
+                liquidity,
+                amount_remaining,
+                zero_for_one,
+            )?
+        }
+    };
+
+    let max = sqrt_price_target_x96 == sqrt_price_next_x96;
+
+    if zero_for_one {
+        if !(max && exact_in) {
+            amount_in =
+                get_amount0_delta(sqrt_price_next_x96, sqrt_price_current_x96, liquidity, true)?;
+        }
+        if !max || exact_in {
+            amount_out = get_amount1_delta(
+                sqrt_price_next_x96,
+                sqrt_price_current_x96,
+                liquidity,
+                false,
+            )?;
+        }
+    } else {
+        if !(max && exact_in) {
+            amount_in =
+                get_amount1_delta(sqrt_price_current_x96, sqrt_price_next_x96, liquidity, true)?;
+        }
+        if !max || exact_in {
+            amount_out = get_amount0_delta(
+                sqrt_price_current_x96,
+                sqrt_price_next_x96,
+                liquidity,
+                false,
+            )?;
+        }
+    }
+
+    // The output can't exceed the requested amount
+    if !exact_in && amount_out > amount_remaining {
+        amount_out = amount_remaining;
+    }
+
+    let fee_amount = if exact_in && sqrt_price_next_x96 != sqrt_price_target_x96 {
+        // The target wasn't reached, so the remainder of the input is taken as the fee
+        amount_remaining - amount_in
+    } else {
+        mul_div_rounding_up(amount_in, fee, fee_precision - fee)?
+    };
+
+    Ok(SwapStep {
+        sqrt_price_next_x96,
+        amount_in,
+        amount_out,
+        fee_amount,
+    })
+}
+
+/// ## Description
+/// Returns the liquidity received for an amount of the first asset in the specified price range.
+fn get_liquidity_for_amount0(
+    sqrt_ratio_a: Uint256,
+    sqrt_ratio_b: Uint256,
+    amount0: Uint128,
+) -> StdResult<Uint256> {
+    let intermediate = mul_div(sqrt_ratio_a, sqrt_ratio_b, Q96)?;
+    mul_div(amount0.into(), intermediate, sqrt_ratio_b - sqrt_ratio_a)
+}
+
+/// ## Description
+/// Returns the liquidity received for an amount of the second asset in the specified price range.
+fn get_liquidity_for_amount1(
+    sqrt_ratio_a: Uint256,
+    sqrt_ratio_b: Uint256,
+    amount1: Uint128,
+) -> StdResult<Uint256> {
+    mul_div(amount1.into(), Q96, sqrt_ratio_b - sqrt_ratio_a)
+}
+
+/// ## Description
+/// Returns the maximum liquidity that can be received for the specified amounts of both assets
+/// in the price range between `sqrt_ratio_a` and `sqrt_ratio_b` at the current price.
+/// ## Params
+/// * **sqrt_price_x96** is the current square root price.
+///
+/// * **sqrt_ratio_a** is the square root price at the lower bound of the range.
+///
+/// * **sqrt_ratio_b** is the square root price at the upper bound of the range.
+///
+/// * **amount0** and **amount1** are the maximum amounts of the pool assets to deposit.
+pub fn get_liquidity_for_amounts(
+    sqrt_price_x96: Uint256,
+    sqrt_ratio_a: Uint256,
+    sqrt_ratio_b: Uint256,
+    amount0: Uint128,
+    amount1: Uint128,
+) -> StdResult<Uint128> {
+    let liquidity = if sqrt_price_x96 <= sqrt_ratio_a {
+        get_liquidity_for_amount0(sqrt_ratio_a, sqrt_ratio_b, amount0)?
+    } else if sqrt_price_x96 < sqrt_ratio_b {
+        std::cmp::min(
+            get_liquidity_for_amount0(sqrt_price_x96, sqrt_ratio_b, amount0)?,
+            get_liquidity_for_amount1(sqrt_ratio_a, sqrt_price_x96, amount1)?,
+        )
+    } else {
+        get_liquidity_for_amount1(sqrt_ratio_a, sqrt_ratio_b, amount1)?
+    };
+
+    to_u128(liquidity)
+}
@@ -0,0 +1,206 @@
+use astroport::asset::PairInfo;
+use astroport::pair_concentrated::MIN_TICK;
+use cosmwasm_std::{Addr, Empty, Order, StdResult, Storage, Uint128, Uint256};
+use cw_storage_plus::{Bound, Item, Map, U32Key, U64Key};
+use schemars::JsonSchema;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::convert::TryInto;
+
+/// ## Description
+/// This structure describes the main control config of pair.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// the type of pair info available in [`PairInfo`]
+    pub pair_info: PairInfo,
+    /// the factory contract address
+    pub factory_addr: Addr,
+    /// Only ticks divisible by the tick spacing can be used as position bounds
+    pub tick_spacing: u32,
+    /// The last time block
+    pub block_time_last: u64,
+    /// The last cumulative price 0 asset in pool
+    pub price0_cumulative_last: Uint128,
+    /// The last cumulative price 1 asset in pool
+    pub price1_cumulative_last: Uint128,
+}
+
+/// ## Description
+/// This structure describes the current state of the pool.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PoolState {
+    /// The square root of the current price as a Q64.96 number
+    pub sqrt_price_x96: Uint256,
+    /// The tick of the current price
+    pub tick: i32,
+    /// The liquidity of the positions which ranges contain the current price
+    pub liquidity: Uint128,
+    /// The fee growth per unit of liquidity of the first asset as a Q128.128 number
+    pub fee_growth_global0_x128: Uint256,
+    /// The fee growth per unit of liquidity of the second asset as a Q128.128 number
+    pub fee_growth_global1_x128: Uint256,
+}
+
+/// ## Description
+/// This structure describes an initialized tick, i.e. a tick that bounds at least one position.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct TickInfo {
+    /// The total liquidity of the positions that use the tick as a bound
+    pub liquidity_gross: Uint128,
+    /// The liquidity added to the active liquidity when the price crosses the tick upwards
+    #[serde(
+        serialize_with = "serialize_i128",
+        deserialize_with = "deserialize_i128"
+    )]
+    #[schemars(with = "String")]
+    pub liquidity_net: i128,
+    /// The fee growth of the first asset on the other side of the tick from the current price
+    pub fee_growth_outside0_x128: Uint256,
+    /// The fee growth of the second asset on the other side of the tick from the current price
+    pub fee_growth_outside1_x128: Uint256,
+}
+
+/// ## Description
+/// Serializes an [`i128`] as a string, the same way as [`Uint128`] is serialized.
+fn serialize_i128<S: Serializer>(value: &i128, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&value.to_string())
+}
+
+/// ## Description
+/// Deserializes an [`i128`] serialized with [`serialize_i128`].
+fn deserialize_i128<'de, D: Deserializer<'de>>(deserializer: D) -> Result<i128, D::Error> {
+    let value = String::deserialize(deserializer)?;
+    value.parse().map_err(serde::de::Error::custom)
+}
+
+/// ## Description
+/// This structure describes a liquidity position.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Position {
+    pub owner: Addr,
+    pub tick_lower: i32,
+    pub tick_upper: i32,
+    pub liquidity: Uint128,
+    /// The fee growth of the first asset inside the position range as of the last fee accrual
+    pub fee_growth_inside0_last_x128: Uint256,
+    /// The fee growth of the second asset inside the position range as of the last fee accrual
+    pub fee_growth_inside1_last_x128: Uint256,
+    /// The amount of the first asset owed to the position owner
+    pub tokens_owed0: Uint128,
+    /// The amount of the second asset owed to the position owner
+    pub tokens_owed1: Uint128,
+}
+
+/// ## Description
+/// Stores config at the given key
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// ## Description
+/// Stores the current pool state
+pub const POOL_STATE: Item<PoolState> = Item::new("pool_state");
+
+/// ## Description
+/// Stores the initialized ticks. The keys are the ticks shifted by [`MIN_TICK`] so that they are
+/// ordered correctly, use [`tick_key`] to build them
+pub const TICKS: Map<U32Key, TickInfo> = Map::new("ticks");
+
+/// ## Description
+/// Stores positions by their identifiers
+pub const POSITIONS: Map<U64Key, Position> = Map::new("positions");
+
+/// ## Description
+/// Stores the identifiers of the positions of every owner
+pub const OWNER_POSITIONS: Map<(&Addr, U64Key), Empty> = Map::new("owner_positions");
+
+/// ## Description
+/// Stores the identifier of the last created position
+pub const LAST_POSITION_ID: Item<u64> = Item::new("last_position_id");
+
+/// ## Description
+/// Returns the storage key of a tick.
+pub fn tick_key(tick: i32) -> U32Key {
+    U32Key::new((tick as i64 - MIN_TICK as i64) as u32)
+}
+
+/// ## Description
+/// Returns the tick stored under the specified raw key.
+fn tick_from_key(key: &[u8]) -> i32 {
+    let shifted = u32::from_be_bytes(key.try_into().unwrap());
+    (shifted as i64 + MIN_TICK as i64) as i32
+}
+
+/// ## Description
+/// Returns the nearest initialized tick below or equal to the specified tick if `lte` is true,
+/// otherwise the nearest initialized tick above it.
+/// ## Params
+/// * **storage** is the object of type [`Storage`].
+///
+/// * **tick** is the tick to start the search from.
+///
+/// * **lte** is the search direction.
+pub fn next_initialized_tick(
+    storage: &dyn Storage,
+    tick: i32,
+    lte: bool,
+) -> StdResult<Option<i32>> {
+    let key: Vec<u8> = tick_key(tick).into();
+    let mut range = if lte {
+        TICKS.range(
+            storage,
+            None,
+            Some(Bound::inclusive(key)),
+            Order::Descending,
+        )
+    } else {
+        TICKS.range(storage, Some(Bound::exclusive(key)), None, Order::Ascending)
+    };
+
+    match range.next() {
+        Some(item) => Ok(Some(tick_from_key(&item?.0))),
+        None => Ok(None),
+    }
+}
+
+/// ## Description
+/// Returns the next position identifier and stores it as the last one.
+pub fn next_position_id(storage: &mut dyn Storage) -> StdResult<u64> {
+    let id = LAST_POSITION_ID.may_load(storage)?.unwrap_or_default() + 1;
+    LAST_POSITION_ID.save(storage, &id)?;
+    Ok(id)
+}
+
+//settings for pagination
+/// The maximum limit for reading positions from [`OWNER_POSITIONS`]
+const MAX_LIMIT: u32 = 30;
+
+/// The default limit for reading positions from [`OWNER_POSITIONS`]
+const DEFAULT_LIMIT: u32 = 10;
+
+/// ## Description
+/// Reads the position identifiers of an owner according to the specified parameters in `start_after` and `limit` variables.
+/// ## Params
+/// * **storage** is the object of type [`Storage`].
+///
+/// * **owner** is the object of type [`Addr`]. The owner of the positions.
+///
+/// * **start_after** is an [`Option`] type. Sets the position identifier to start reading after.
+///
+/// * **limit** is an [`Option`] type. Sets the number of items to be read.
+pub fn read_owner_positions(
+    storage: &dyn Storage,
+    owner: &Addr,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<u64>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive_int);
+
+    OWNER_POSITIONS
+        .prefix(owner)
+        .range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (key, _) = item?;
+            Ok(u64::from_be_bytes(key.as_slice().try_into().unwrap()))
+        })
+        .collect()
+}
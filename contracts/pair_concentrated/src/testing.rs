@@ -0,0 +1,688 @@
+use crate::contract::{
+    execute, instantiate, query_cumulative_prices, query_observe, query_pool_state, query_position,
+    query_positions, query_reverse_simulation, query_simulation,
+};
+use crate::error::ContractError;
+use crate::math::{
+    get_sqrt_ratio_at_tick, get_tick_at_sqrt_ratio, MAX_SQRT_RATIO, MIN_SQRT_RATIO, Q96,
+};
+use crate::mock_querier::mock_dependencies;
+use astroport::asset::{Asset, AssetInfo};
+use astroport::pair::{
+    CumulativePricesResponse, InstantiateMsg, Observation, ProvideResponse, SwapResponse,
+    WithdrawResponse,
+};
+use astroport::pair_concentrated::{ConcentratedPoolParams, ExecuteMsg, MAX_TICK, MIN_TICK};
+use cosmwasm_std::testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    attr, coin, from_binary, to_binary, Addr, BankMsg, Coin, CosmosMsg, Decimal, DepsMut, Env,
+    Response, StdError, SubMsg, Uint128, Uint256, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+use proptest::prelude::*;
+use std::str::FromStr;
+
+fn native_asset(denom: &str, amount: u128) -> Asset {
+    Asset {
+        info: AssetInfo::NativeToken {
+            denom: denom.to_string(),
+        },
+        amount: Uint128::new(amount),
+    }
+}
+
+fn instantiate_pair(deps: DepsMut, asset_infos: Vec<AssetInfo>, initial_price: Decimal) {
+    let msg = InstantiateMsg {
+        asset_infos,
+        token_code_id: 10u64,
+        factory_addr: String::from("factory"),
+        init_params: Some(
+            to_binary(&ConcentratedPoolParams {
+                tick_spacing: 60,
+                initial_price,
+            })
+            .unwrap(),
+        ),
+    };
+
+    instantiate(deps, mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+}
+
+fn native_pair_infos() -> Vec<AssetInfo> {
+    vec![
+        AssetInfo::NativeToken {
+            denom: "uusd".to_string(),
+        },
+        AssetInfo::NativeToken {
+            denom: "uluna".to_string(),
+        },
+    ]
+}
+
+/// Creates a position depositing the same amount of both native pool assets
+fn create_position(
+    deps: DepsMut,
+    owner: &str,
+    tick_lower: i32,
+    tick_upper: i32,
+    amount: u128,
+) -> Response {
+    let msg = ExecuteMsg::CreatePosition {
+        tick_lower,
+        tick_upper,
+        assets: vec![native_asset("uusd", amount), native_asset("uluna", amount)],
+        min_liquidity: None,
+        receiver: None,
+    };
+    let info = mock_info(owner, &[coin(amount, "uusd"), coin(amount, "uluna")]);
+    execute(deps, mock_env(), info, msg).unwrap()
+}
+
+fn swap_uusd(deps: DepsMut, amount: u128) -> Response {
+    let msg = ExecuteMsg::Swap {
+        offer_asset: native_asset("uusd", amount),
+        ask_asset_info: None,
+        belief_price: None,
+        max_spread: Some(Decimal::percent(50)),
+        to: None,
+    };
+    let info = mock_info("trader", &[coin(amount, "uusd")]);
+    execute(deps, mock_env(), info, msg).unwrap()
+}
+
+#[test]
+fn tick_math_bounds() {
+    assert_eq!(get_sqrt_ratio_at_tick(0).unwrap(), Q96);
+    assert_eq!(get_sqrt_ratio_at_tick(MIN_TICK).unwrap(), MIN_SQRT_RATIO);
+    assert_eq!(get_sqrt_ratio_at_tick(MAX_TICK).unwrap(), MAX_SQRT_RATIO);
+    get_sqrt_ratio_at_tick(MIN_TICK - 1).unwrap_err();
+    get_sqrt_ratio_at_tick(MAX_TICK + 1).unwrap_err();
+
+    assert_eq!(get_tick_at_sqrt_ratio(MIN_SQRT_RATIO).unwrap(), MIN_TICK);
+    assert_eq!(
+        get_tick_at_sqrt_ratio(MAX_SQRT_RATIO - Uint256::from(1u8)).unwrap(),
+        MAX_TICK - 1
+    );
+    get_tick_at_sqrt_ratio(MAX_SQRT_RATIO).unwrap_err();
+
+    for tick in [-887271, -100000, -60, -1, 0, 1, 60, 100000, 887271] {
+        let sqrt_ratio = get_sqrt_ratio_at_tick(tick).unwrap();
+        assert_eq!(get_tick_at_sqrt_ratio(sqrt_ratio).unwrap(), tick);
+        assert_eq!(
+            get_tick_at_sqrt_ratio(sqrt_ratio - Uint256::from(1u8)).unwrap(),
+            tick - 1
+        );
+    }
+}
+
+#[test]
+fn proper_initialization() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        asset_infos: native_pair_infos(),
+        token_code_id: 10u64,
+        factory_addr: String::from("factory"),
+        init_params: None,
+    };
+    let err = instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::InitParamsNotFound {});
+
+    let msg = InstantiateMsg {
+        asset_infos: native_pair_infos(),
+        token_code_id: 10u64,
+        factory_addr: String::from("factory"),
+        init_params: Some(
+            to_binary(&ConcentratedPoolParams {
+                tick_spacing: 0,
+                initial_price: Decimal::one(),
+            })
+            .unwrap(),
+        ),
+    };
+    let err = instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidTickSpacing(16384));
+
+    instantiate_pair(
+        deps.as_mut(),
+        native_pair_infos(),
+        Decimal::from_str("2.5").unwrap(),
+    );
+
+    let state = query_pool_state(deps.as_ref()).unwrap();
+    // 1.0001^9163 = 2.49991..., 1.0001^9164 = 2.50016...
+    assert_eq!(state.tick, 9163);
+    assert_eq!(
+        state.price,
+        Decimal::from_str("2.499999999999999999").unwrap()
+    );
+    assert_eq!(state.liquidity, Uint128::zero());
+}
+
+#[test]
+fn create_position_deposits() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_pair(
+        deps.as_mut(),
+        vec![
+            AssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            },
+            AssetInfo::Token {
+                contract_addr: Addr::unchecked("asset0000"),
+            },
+        ],
+        Decimal::one(),
+    );
+
+    // Ticks must be divisible by the tick spacing
+    let msg = ExecuteMsg::CreatePosition {
+        tick_lower: -50,
+        tick_upper: 60,
+        assets: vec![],
+        min_liquidity: None,
+        receiver: None,
+    };
+    let err = execute(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidTickRange {});
+
+    let msg = ExecuteMsg::CreatePosition {
+        tick_lower: 600,
+        tick_upper: -600,
+        assets: vec![],
+        min_liquidity: None,
+        receiver: None,
+    };
+    let err = execute(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidTickRange {});
+
+    // The range is symmetric around the current price, so equal amounts are used.
+    // The excess of the native asset is refunded and only the used amount of the token is taken
+    let msg = ExecuteMsg::CreatePosition {
+        tick_lower: -600,
+        tick_upper: 600,
+        assets: vec![
+            native_asset("uusd", 2_000_000),
+            Asset {
+                info: AssetInfo::Token {
+                    contract_addr: Addr::unchecked("asset0000"),
+                },
+                amount: Uint128::new(1_000_000),
+            },
+        ],
+        min_liquidity: None,
+        receiver: Some("owner0000".to_string()),
+    };
+    let info = mock_info("addr0000", &[coin(2_000_000, "uusd")]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "addr0000".to_string(),
+                amount: vec![coin(1_000_000, "uusd")],
+            })),
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: "asset0000".to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
+                    owner: "addr0000".to_string(),
+                    recipient: MOCK_CONTRACT_ADDR.to_string(),
+                    amount: Uint128::new(1_000_000),
+                })
+                .unwrap(),
+                funds: vec![],
+            }),
+        ]
+    );
+    assert_eq!(res.attributes[0], attr("action", "create_position"));
+    assert_eq!(res.attributes[1], attr("position_id", "1"));
+    assert_eq!(res.attributes[2], attr("owner", "owner0000"));
+    assert_eq!(
+        from_binary::<ProvideResponse>(&res.data.unwrap()).unwrap(),
+        ProvideResponse {
+            assets: vec![
+                native_asset("uusd", 1_000_000),
+                Asset {
+                    info: AssetInfo::Token {
+                        contract_addr: Addr::unchecked("asset0000"),
+                    },
+                    amount: Uint128::new(1_000_000),
+                },
+            ],
+            share: Uint128::new(33_837_499),
+        }
+    );
+
+    let position = query_position(deps.as_ref(), 1).unwrap();
+    assert_eq!(position.owner, Addr::unchecked("owner0000"));
+    assert_eq!(position.liquidity, Uint128::new(33_837_499));
+    // Deposits are rounded up and withdrawals are rounded down
+    assert_eq!(position.assets[0].amount, Uint128::new(999_999));
+    assert_eq!(position.assets[1].amount, Uint128::new(999_999));
+    assert_eq!(position.fees[0].amount, Uint128::zero());
+
+    let state = query_pool_state(deps.as_ref()).unwrap();
+    assert_eq!(state.liquidity, position.liquidity);
+
+    // The minimum liquidity is enforced
+    let msg = ExecuteMsg::CreatePosition {
+        tick_lower: -600,
+        tick_upper: 600,
+        assets: vec![
+            native_asset("uusd", 1_000_000),
+            Asset {
+                info: AssetInfo::Token {
+                    contract_addr: Addr::unchecked("asset0000"),
+                },
+                amount: Uint128::new(1_000_000),
+            },
+        ],
+        min_liquidity: Some(Uint128::new(33_837_500)),
+        receiver: None,
+    };
+    let info = mock_info("addr0000", &[coin(1_000_000, "uusd")]);
+    let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+    assert_eq!(err, ContractError::MaxSlippageAssertion {});
+
+    // A single asset can't back liquidity in a range containing the current price
+    let msg = ExecuteMsg::CreatePosition {
+        tick_lower: -600,
+        tick_upper: 600,
+        assets: vec![native_asset("uusd", 1_000_000)],
+        min_liquidity: None,
+        receiver: None,
+    };
+    let info = mock_info("addr0000", &[coin(1_000_000, "uusd")]);
+    let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+    assert_eq!(err, ContractError::ZeroLiquidity {});
+}
+
+#[test]
+fn swap_crosses_ticks() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_pair(deps.as_mut(), native_pair_infos(), Decimal::one());
+
+    create_position(deps.as_mut(), "addr0000", -6000, 6000, 1_000_000_000);
+    create_position(deps.as_mut(), "addr0001", -60, 60, 1_000_000_000);
+
+    let wide = query_position(deps.as_ref(), 1).unwrap();
+    let narrow = query_position(deps.as_ref(), 2).unwrap();
+    let state = query_pool_state(deps.as_ref()).unwrap();
+    assert_eq!(state.liquidity, wide.liquidity + narrow.liquidity);
+
+    let simulation =
+        query_simulation(deps.as_ref(), native_asset("uusd", 2_000_000_000), None).unwrap();
+    let reverse_simulation = query_reverse_simulation(
+        deps.as_ref(),
+        native_asset("uluna", simulation.return_amount.u128()),
+        Some(AssetInfo::NativeToken {
+            denom: "uusd".to_string(),
+        }),
+    )
+    .unwrap();
+    assert!(reverse_simulation.offer_amount <= Uint128::new(2_000_000_000));
+    assert!(reverse_simulation.offer_amount >= Uint128::new(1_999_999_990));
+    assert_eq!(reverse_simulation.spread_amount, simulation.spread_amount);
+    let res = swap_uusd(deps.as_mut(), 2_000_000_000);
+
+    // The narrow position is fully converted and only the wide one stays active
+    let state = query_pool_state(deps.as_ref()).unwrap();
+    assert!(state.tick < -60);
+    assert_eq!(state.liquidity, wide.liquidity);
+    let narrow = query_position(deps.as_ref(), 2).unwrap();
+    assert_eq!(narrow.assets[1].amount, Uint128::zero());
+
+    assert_eq!(
+        res.attributes[6],
+        attr("return_amount", simulation.return_amount.to_string())
+    );
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "trader".to_string(),
+                amount: vec![Coin {
+                    denom: "uluna".to_string(),
+                    amount: simulation.return_amount,
+                }],
+            })),
+            // 16.6% of the 0.3% fee goes to the Maker
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "fee_address".to_string(),
+                amount: vec![coin(995_999, "uusd")],
+            })),
+        ]
+    );
+    let data = from_binary::<SwapResponse>(&res.data.unwrap()).unwrap();
+    assert_eq!(
+        res.attributes[7],
+        attr("tax_amount", data.tax_amount.to_string())
+    );
+    assert_eq!(
+        data,
+        SwapResponse {
+            offer_asset: native_asset("uusd", 2_000_000_000),
+            return_asset: native_asset("uluna", simulation.return_amount.u128()),
+            tax_amount: data.tax_amount,
+            spread_amount: simulation.spread_amount,
+            commission_amount: simulation.commission_amount,
+            maker_fee_amount: Uint128::new(995_999),
+        }
+    );
+
+    // Swapping back moves the price into the narrow range again
+    let msg = ExecuteMsg::Swap {
+        offer_asset: native_asset("uluna", simulation.return_amount.u128()),
+        ask_asset_info: None,
+        belief_price: None,
+        max_spread: Some(Decimal::percent(50)),
+        to: None,
+    };
+    let info = mock_info("trader", &[coin(simulation.return_amount.u128(), "uluna")]);
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let state = query_pool_state(deps.as_ref()).unwrap();
+    assert!(state.tick >= -60 && state.tick < 60);
+    assert_eq!(state.liquidity, wide.liquidity + narrow.liquidity);
+
+    // The pool can't fill a swap larger than its liquidity
+    let msg = ExecuteMsg::Swap {
+        offer_asset: native_asset("uluna", 1_000_000_000_000),
+        ask_asset_info: None,
+        belief_price: None,
+        max_spread: Some(Decimal::percent(50)),
+        to: None,
+    };
+    let info = mock_info("trader", &[coin(1_000_000_000_000, "uluna")]);
+    let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+    assert_eq!(err, ContractError::InsufficientLiquidity {});
+
+    // The ask asset must be the other pool asset
+    for denom in ["uusd", "ukrw"] {
+        let msg = ExecuteMsg::Swap {
+            offer_asset: native_asset("uusd", 1_000_000),
+            ask_asset_info: Some(AssetInfo::NativeToken {
+                denom: denom.to_string(),
+            }),
+            belief_price: None,
+            max_spread: Some(Decimal::percent(50)),
+            to: None,
+        };
+        let info = mock_info("trader", &[coin(1_000_000, "uusd")]);
+        let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+        assert_eq!(err, ContractError::AssetMismatch {});
+
+        // The simulations check the specified assets in the same way
+        let asset_info = AssetInfo::NativeToken {
+            denom: denom.to_string(),
+        };
+        let err = query_simulation(
+            deps.as_ref(),
+            native_asset("uusd", 1_000_000),
+            Some(asset_info.clone()),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            StdError::generic_err(ContractError::AssetMismatch {}.to_string())
+        );
+        let err = query_reverse_simulation(
+            deps.as_ref(),
+            native_asset("uusd", 1_000_000),
+            Some(asset_info),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            StdError::generic_err(ContractError::AssetMismatch {}.to_string())
+        );
+    }
+}
+
+#[test]
+fn fees_accrue_per_position() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_pair(deps.as_mut(), native_pair_infos(), Decimal::one());
+
+    create_position(deps.as_mut(), "addr0000", -600, 600, 1_000_000_000);
+    create_position(deps.as_mut(), "addr0001", -600, 600, 3_000_000_000);
+    // The range is above the current price, so the position only holds the first asset
+    let msg = ExecuteMsg::CreatePosition {
+        tick_lower: 600,
+        tick_upper: 1200,
+        assets: vec![native_asset("uusd", 1_000_000_000)],
+        min_liquidity: None,
+        receiver: None,
+    };
+    let info = mock_info("addr0002", &[coin(1_000_000_000, "uusd")]);
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    swap_uusd(deps.as_mut(), 100_000_000);
+
+    // 100_000_000 * 0.3% = 300_000 total fee, 16.6% of it goes to the Maker
+    let fees: Vec<Uint128> = (1..=3)
+        .map(|id| query_position(deps.as_ref(), id).unwrap().fees[0].amount)
+        .collect();
+    assert_eq!(
+        fees,
+        vec![Uint128::new(62_549), Uint128::new(187_650), Uint128::zero()]
+    );
+    assert!(fees[0] + fees[1] <= Uint128::new(300_000 - 49_800));
+
+    // Fees are only paid in the offered asset
+    let position = query_position(deps.as_ref(), 1).unwrap();
+    assert_eq!(position.fees[1].amount, Uint128::zero());
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0001", &[]),
+        ExecuteMsg::CollectFees {
+            position_id: 1,
+            receiver: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::CollectFees {
+            position_id: 1,
+            receiver: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "addr0000".to_string(),
+            amount: vec![coin(62_549, "uusd")],
+        }))]
+    );
+    let position = query_position(deps.as_ref(), 1).unwrap();
+    assert_eq!(position.fees[0].amount, Uint128::zero());
+
+    // Fees accrued after collecting are tracked from the new baseline
+    swap_uusd(deps.as_mut(), 100_000_000);
+    let position = query_position(deps.as_ref(), 1).unwrap();
+    assert_eq!(position.fees[0].amount, Uint128::new(62_549));
+}
+
+#[test]
+fn decrease_and_transfer_position() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_pair(deps.as_mut(), native_pair_infos(), Decimal::one());
+
+    create_position(deps.as_mut(), "addr0000", -600, 600, 1_000_000_000);
+    swap_uusd(deps.as_mut(), 10_000_000);
+
+    let position = query_position(deps.as_ref(), 1).unwrap();
+    assert_eq!(
+        query_positions(deps.as_ref(), "addr0000".to_string(), None, None).unwrap(),
+        vec![position.clone()]
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0001", &[]),
+        ExecuteMsg::TransferPosition {
+            position_id: 1,
+            recipient: "addr0001".to_string(),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::TransferPosition {
+            position_id: 1,
+            recipient: "addr0001".to_string(),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        query_positions(deps.as_ref(), "addr0000".to_string(), None, None).unwrap(),
+        vec![]
+    );
+    assert_eq!(
+        query_positions(deps.as_ref(), "addr0001".to_string(), None, None).unwrap()[0].owner,
+        Addr::unchecked("addr0001")
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0001", &[]),
+        ExecuteMsg::DecreaseLiquidity {
+            position_id: 1,
+            liquidity: position.liquidity + Uint128::new(1),
+            receiver: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InsufficientPositionLiquidity {});
+
+    // Withdrawing everything returns the principal together with the fees and removes the position
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0001", &[]),
+        ExecuteMsg::DecreaseLiquidity {
+            position_id: 1,
+            liquidity: position.liquidity,
+            receiver: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "addr0001".to_string(),
+                amount: vec![Coin {
+                    denom: "uusd".to_string(),
+                    amount: position.assets[0].amount + position.fees[0].amount,
+                }],
+            })),
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "addr0001".to_string(),
+                amount: vec![Coin {
+                    denom: "uluna".to_string(),
+                    amount: position.assets[1].amount,
+                }],
+            })),
+        ]
+    );
+    assert_eq!(
+        from_binary::<WithdrawResponse>(&res.data.unwrap()).unwrap(),
+        WithdrawResponse {
+            refund_assets: vec![
+                native_asset(
+                    "uusd",
+                    (position.assets[0].amount + position.fees[0].amount).u128()
+                ),
+                native_asset("uluna", position.assets[1].amount.u128()),
+            ],
+            withdrawn_share: position.liquidity,
+        }
+    );
+
+    query_position(deps.as_ref(), 1).unwrap_err();
+    let state = query_pool_state(deps.as_ref()).unwrap();
+    assert_eq!(state.liquidity, Uint128::zero());
+}
+
+/// Returns the mock environment moved the specified number of seconds forward
+fn env_after(seconds: u64) -> Env {
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(seconds);
+    env
+}
+
+#[test]
+fn observe() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_pair(deps.as_mut(), native_pair_infos(), Decimal::one());
+    create_position(deps.as_mut(), "addr0000", -6000, 6000, 1_000_000_000);
+
+    // Every swap records an observation of the cumulative prices
+    let mut cumulative_prices = vec![];
+    for (seconds, denom) in [(1000u64, "uusd"), (2000u64, "uluna")] {
+        let msg = ExecuteMsg::Swap {
+            offer_asset: native_asset(denom, 1_000_000),
+            ask_asset_info: None,
+            belief_price: None,
+            max_spread: Some(Decimal::percent(50)),
+            to: None,
+        };
+        let info = mock_info("trader", &[coin(1_000_000, denom)]);
+        execute(deps.as_mut(), env_after(seconds), info, msg).unwrap();
+        cumulative_prices.push(query_cumulative_prices(deps.as_ref(), env_after(seconds)).unwrap());
+    }
+
+    let observation = |seconds: u64, prices: &CumulativePricesResponse| Observation {
+        timestamp: env_after(seconds).block.time.seconds(),
+        price0_cumulative: prices.price0_cumulative_last,
+        price1_cumulative: prices.price1_cumulative_last,
+    };
+    let first = observation(1000, &cumulative_prices[0]);
+    let second = observation(2000, &cumulative_prices[1]);
+    // The price doesn't change between the swaps, so the cumulative prices grow linearly
+    let middle = Observation {
+        timestamp: env_after(1500).block.time.seconds(),
+        price0_cumulative: first.price0_cumulative
+            + (second.price0_cumulative - first.price0_cumulative).multiply_ratio(1u128, 2u128),
+        price1_cumulative: first.price1_cumulative
+            + (second.price1_cumulative - first.price1_cumulative).multiply_ratio(1u128, 2u128),
+    };
+
+    let res = query_observe(deps.as_ref(), env_after(2000), vec![0, 500, 1000]).unwrap();
+    assert_eq!(res.observations, vec![second, middle, first]);
+
+    // Older than the first observation
+    let err = query_observe(deps.as_ref(), env_after(2000), vec![1500]).unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("The requested time is older than the oldest observation")
+    );
+}
+
+proptest! {
+    #[test]
+    fn sqrt_ratio_at_tick_matches_float(tick in MIN_TICK..MAX_TICK) {
+        let sqrt_ratio = get_sqrt_ratio_at_tick(tick).unwrap();
+        let expected = 1.0001f64.powf(tick as f64 / 2.0) * 2f64.powi(96);
+        let actual = f64::from_str(&sqrt_ratio.to_string()).unwrap();
+
+        assert!(((actual - expected) / expected).abs() < 1e-9);
+        assert_eq!(get_tick_at_sqrt_ratio(sqrt_ratio).unwrap(), tick);
+    }
+}
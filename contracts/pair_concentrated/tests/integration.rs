@@ -0,0 +1,262 @@
+// The Terra treasury tax is queried from the Terra chain, which the multi-test app doesn't
+// mock, so these tests run without the `terra` feature
+#![cfg(not(feature = "terra"))]
+
+use astroport::asset::{Asset, AssetInfo, PairInfo};
+use astroport::factory::{
+    ExecuteMsg as FactoryExecuteMsg, InstantiateMsg as FactoryInstantiateMsg, PairConfig, PairType,
+    QueryMsg as FactoryQueryMsg,
+};
+use astroport::pair::{PoolResponse, SimulationResponse};
+use astroport::pair_concentrated::{
+    ConcentratedPoolParams, ExecuteMsg, PoolStateResponse, PositionResponse, QueryMsg,
+};
+use cosmwasm_std::testing::{mock_env, MockApi, MockStorage};
+use cosmwasm_std::{to_binary, Addr, Coin, Decimal, Uint128};
+use cw_multi_test::{App, AppBuilder, BankKeeper, ContractWrapper, Executor};
+
+const OWNER: &str = "owner";
+
+fn mock_app() -> App {
+    let env = mock_env();
+    let api = MockApi::default();
+    let bank = BankKeeper::new();
+    let storage = MockStorage::new();
+
+    AppBuilder::new()
+        .with_api(api)
+        .with_block(env.block)
+        .with_bank(bank)
+        .with_storage(storage)
+        .build()
+}
+
+fn store_pair_code(app: &mut App) -> u64 {
+    let pair_contract = Box::new(ContractWrapper::new_with_empty(
+        astroport_pair_concentrated::contract::execute,
+        astroport_pair_concentrated::contract::instantiate,
+        astroport_pair_concentrated::contract::query,
+    ));
+
+    app.store_code(pair_contract)
+}
+
+fn store_token_code(app: &mut App) -> u64 {
+    let astro_token_contract = Box::new(ContractWrapper::new_with_empty(
+        astroport_token::contract::execute,
+        astroport_token::contract::instantiate,
+        astroport_token::contract::query,
+    ));
+
+    app.store_code(astro_token_contract)
+}
+
+fn store_factory_code(app: &mut App) -> u64 {
+    let factory_contract = Box::new(
+        ContractWrapper::new_with_empty(
+            astroport_factory::contract::execute,
+            astroport_factory::contract::instantiate,
+            astroport_factory::contract::query,
+        )
+        .with_reply_empty(astroport_factory::contract::reply),
+    );
+
+    app.store_code(factory_contract)
+}
+
+fn native_asset_infos() -> Vec<AssetInfo> {
+    vec![
+        AssetInfo::NativeToken {
+            denom: "uusd".to_string(),
+        },
+        AssetInfo::NativeToken {
+            denom: "uluna".to_string(),
+        },
+    ]
+}
+
+#[test]
+fn create_pair_through_factory_and_swap() {
+    let mut app = mock_app();
+    let owner = Addr::unchecked(OWNER);
+
+    app.init_bank_balance(
+        &owner,
+        vec![
+            Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::new(100_000_000_000u128),
+            },
+            Coin {
+                denom: "uluna".to_string(),
+                amount: Uint128::new(100_000_000_000u128),
+            },
+        ],
+    )
+    .unwrap();
+
+    let token_code_id = store_token_code(&mut app);
+    let pair_code_id = store_pair_code(&mut app);
+    let factory_code_id = store_factory_code(&mut app);
+
+    let init_msg = FactoryInstantiateMsg {
+        fee_address: None,
+        pair_configs: vec![PairConfig {
+            code_id: pair_code_id,
+            maker_fee_bps: 0,
+            total_fee_bps: 30,
+            pair_type: PairType::Concentrated {},
+            is_disabled: None,
+            permissionless: true,
+            creation_fee: None,
+        }],
+        token_code_id,
+        generator_address: Some(String::from("generator")),
+        owner: owner.to_string(),
+        whitelist_code_id: 234u64,
+    };
+
+    let factory_instance = app
+        .instantiate_contract(
+            factory_code_id,
+            owner.clone(),
+            &init_msg,
+            &[],
+            "FACTORY",
+            None,
+        )
+        .unwrap();
+
+    let msg = FactoryExecuteMsg::CreatePair {
+        pair_type: PairType::Concentrated {},
+        asset_infos: native_asset_infos(),
+        init_params: Some(
+            to_binary(&ConcentratedPoolParams {
+                tick_spacing: 60,
+                initial_price: Decimal::one(),
+            })
+            .unwrap(),
+        ),
+    };
+
+    app.execute_contract(owner.clone(), factory_instance.clone(), &msg, &[])
+        .unwrap();
+
+    let pair_info: PairInfo = app
+        .wrap()
+        .query_wasm_smart(
+            &factory_instance,
+            &FactoryQueryMsg::Pair {
+                asset_infos: native_asset_infos(),
+                pair_type: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(pair_info.pair_type, PairType::Concentrated {});
+    let pair_instance = pair_info.contract_addr;
+
+    let msg = ExecuteMsg::CreatePosition {
+        tick_lower: -600,
+        tick_upper: 600,
+        assets: vec![
+            Asset {
+                info: native_asset_infos()[0].clone(),
+                amount: Uint128::new(1_000_000_000u128),
+            },
+            Asset {
+                info: native_asset_infos()[1].clone(),
+                amount: Uint128::new(1_000_000_000u128),
+            },
+        ],
+        min_liquidity: None,
+        receiver: None,
+    };
+    app.execute_contract(
+        owner.clone(),
+        pair_instance.clone(),
+        &msg,
+        &[
+            Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::new(1_000_000_000u128),
+            },
+            Coin {
+                denom: "uluna".to_string(),
+                amount: Uint128::new(1_000_000_000u128),
+            },
+        ],
+    )
+    .unwrap();
+
+    let positions: Vec<PositionResponse> = app
+        .wrap()
+        .query_wasm_smart(
+            &pair_instance,
+            &QueryMsg::Positions {
+                owner: owner.to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(positions.len(), 1);
+    assert_eq!(positions[0].position_id, 1);
+    assert!(!positions[0].liquidity.is_zero());
+
+    let pool: PoolResponse = app
+        .wrap()
+        .query_wasm_smart(&pair_instance, &QueryMsg::Pool {})
+        .unwrap();
+    assert_eq!(pool.total_share, positions[0].liquidity);
+
+    let offer_asset = Asset {
+        info: native_asset_infos()[0].clone(),
+        amount: Uint128::new(10_000_000u128),
+    };
+    let simulation: SimulationResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &pair_instance,
+            &QueryMsg::Simulation {
+                offer_asset: offer_asset.clone(),
+                ask_asset_info: None,
+            },
+        )
+        .unwrap();
+
+    let luna_before = app.wrap().query_balance(&owner, "uluna").unwrap().amount;
+    let msg = ExecuteMsg::Swap {
+        offer_asset,
+        ask_asset_info: None,
+        belief_price: None,
+        max_spread: Some(Decimal::percent(50)),
+        to: None,
+    };
+    app.execute_contract(
+        owner.clone(),
+        pair_instance.clone(),
+        &msg,
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(10_000_000u128),
+        }],
+    )
+    .unwrap();
+    let luna_after = app.wrap().query_balance(&owner, "uluna").unwrap().amount;
+    assert_eq!(luna_after - luna_before, simulation.return_amount);
+
+    // Selling uusd for uluna lowers the uluna price of uusd
+    let state: PoolStateResponse = app
+        .wrap()
+        .query_wasm_smart(&pair_instance, &QueryMsg::PoolState {})
+        .unwrap();
+    assert!(state.price < Decimal::one());
+    assert!(state.tick < 0);
+
+    let position: PositionResponse = app
+        .wrap()
+        .query_wasm_smart(&pair_instance, &QueryMsg::Position { position_id: 1 })
+        .unwrap();
+    assert!(!position.fees[0].amount.is_zero());
+    assert!(position.fees[1].amount.is_zero());
+}
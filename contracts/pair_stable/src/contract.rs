@@ -1,36 +1,45 @@
 use crate::error::ContractError;
 use crate::math::{
-    calc_ask_amount, calc_offer_amount, compute_d, AMP_PRECISION, MAX_AMP, MAX_AMP_CHANGE,
-    MIN_AMP_CHANGING_TIME, N_COINS,
+    calc_ask_amount, calc_offer_amount, calc_withdraw_imbalanced, calc_withdraw_one_coin,
+    compute_d, AMP_PRECISION, MAX_AMP, MAX_AMP_CHANGE, MAX_N_COINS, MIN_AMP_CHANGING_TIME,
 };
-use crate::state::{Config, CONFIG};
+use crate::migration;
+use crate::state::{Config, PriceSnapshot, CONFIG, DYNAMIC_FEE_PARAMS, PRICE_SNAPSHOT};
 
 use cosmwasm_bignumber::Decimal256;
 use cosmwasm_std::{
     attr, entry_point, from_binary, to_binary, Addr, Binary, Coin, CosmosMsg, Decimal, Deps,
-    DepsMut, Env, MessageInfo, Reply, ReplyOn, Response, StdError, StdResult, SubMsg, Uint128,
-    WasmMsg,
+    DepsMut, Env, MessageInfo, QuerierWrapper, Reply, ReplyOn, Response, StdError, StdResult,
+    Storage, SubMsg, Uint128, WasmMsg,
 };
 
 use crate::response::MsgInstantiateContractResponse;
-use astroport::asset::{addr_validate_to_lower, format_lp_token_name, Asset, AssetInfo, PairInfo};
+use astroport::asset::{
+    addr_validate_to_lower, format_assets, format_lp_token_name, Asset, AssetInfo, PairInfo,
+};
 use astroport::factory::PairType;
+use astroport::flash_loan;
 
 use astroport::generator::Cw20HookMsg as GeneratorHookMsg;
+use astroport::observation::{observe, store_observation};
+use astroport::pair::assert_deadline;
 use astroport::pair::{
     ConfigResponse, InstantiateMsg, StablePoolParams, StablePoolUpdateParams, DEFAULT_SLIPPAGE,
     MAX_ALLOWED_SLIPPAGE, TWAP_PRECISION,
 };
 
 use astroport::pair::{
-    CumulativePricesResponse, Cw20HookMsg, ExecuteMsg, MigrateMsg, PoolResponse, QueryMsg,
-    ReverseSimulationResponse, SimulationResponse, StablePoolConfig,
+    CumulativePricesResponse, Cw20HookMsg, DynamicFeeParams, ExecuteMsg, MigrateMsg, Observation,
+    ObserveResponse, PoolResponse, ProvideResponse, QueryMsg, ReverseSimulationResponse,
+    SimulateWithdrawResponse, SimulationResponse, StablePoolConfig, SwapResponse, WithdrawResponse,
+    WithdrawType,
 };
 use astroport::querier::{
-    query_factory_config, query_fee_info, query_supply, query_token_precision,
+    assert_not_paused, query_factory_config, query_fee_info, query_pair_paused, query_supply,
+    query_token_precision,
 };
 use astroport::{token::InstantiateMsg as TokenInstantiateMsg, U256};
-use cw2::set_contract_version;
+use cw2::{get_contract_version, set_contract_version};
 use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg, MinterResponse};
 use protobuf::Message;
 use std::cmp::Ordering;
@@ -63,11 +72,16 @@ pub fn instantiate(
     _info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
-    msg.asset_infos[0].check(deps.api)?;
-    msg.asset_infos[1].check(deps.api)?;
+    if msg.asset_infos.len() < 2 || msg.asset_infos.len() > MAX_N_COINS {
+        return Err(ContractError::InvalidNumberOfAssets {});
+    }
 
-    if msg.asset_infos[0] == msg.asset_infos[1] {
-        return Err(ContractError::DoublingAssets {});
+    for (i, asset_info) in msg.asset_infos.iter().enumerate() {
+        asset_info.check(deps.api)?;
+
+        if msg.asset_infos[i + 1..].contains(asset_info) {
+            return Err(ContractError::DoublingAssets {});
+        }
     }
 
     if msg.init_params.is_none() {
@@ -82,6 +96,18 @@ pub fn instantiate(
 
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
+    let mut cumulative_prices = vec![];
+    for offer_asset in &msg.asset_infos {
+        for ask_asset in &msg.asset_infos {
+            if offer_asset != ask_asset {
+                cumulative_prices.push((offer_asset.clone(), ask_asset.clone(), Uint128::zero()));
+            }
+        }
+    }
+
+    let factory_addr = addr_validate_to_lower(deps.api, msg.factory_addr.as_str())?;
+    let precisions = query_precisions(&deps.querier, &msg.asset_infos, &factory_addr)?;
+
     let config = Config {
         pair_info: PairInfo {
             contract_addr: env.contract.address.clone(),
@@ -89,10 +115,10 @@ pub fn instantiate(
             asset_infos: msg.asset_infos.clone(),
             pair_type: PairType::Stable {},
         },
-        factory_addr: addr_validate_to_lower(deps.api, msg.factory_addr.as_str())?,
+        factory_addr,
         block_time_last: 0,
-        price0_cumulative_last: Uint128::zero(),
-        price1_cumulative_last: Uint128::zero(),
+        cumulative_prices,
+        precisions,
         init_amp: params.amp * AMP_PRECISION,
         init_amp_time: env.block.time.seconds(),
         next_amp: params.amp * AMP_PRECISION,
@@ -183,14 +209,35 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractE
 ///             slippage_tolerance,
 ///             auto_stake,
 ///             receiver,
+///             min_lp_to_receive,
+///             deadline,
 ///         }** Provides liquidity with the specified input parameters.
 ///
+/// * **ExecuteMsg::ProvideSingleSided { .. }** Not supported.
+///
 /// * **ExecuteMsg::Swap {
 ///             offer_asset,
+///             ask_asset_info,
 ///             belief_price,
 ///             max_spread,
 ///             to,
+///             minimum_receive,
+///             deadline,
 ///         }** Performs an swap operation with the specified parameters.
+///
+/// * **ExecuteMsg::SwapExactOut {
+///             ask_asset,
+///             max_offer_amount,
+///             to,
+///         }** Swaps the sent native coin for exactly the specified ask asset.
+///
+/// * **ExecuteMsg::FlashLoan { assets, callback }** Lends the pool assets to the sender within
+/// the transaction.
+///
+/// * **ExecuteMsg::AssertFlashLoanRepaid {
+///             expected_balances,
+///             fees,
+///         }** Checks that a flash loan was repaid. Only the pair itself can execute it.
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
@@ -198,14 +245,28 @@ pub fn execute(
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
+    if flash_loan::is_flash_loan_locked(deps.storage)?
+        && !matches!(msg, ExecuteMsg::AssertFlashLoanRepaid { .. })
+    {
+        return Err(ContractError::FlashLoanInProgress {});
+    }
+
     match msg {
         ExecuteMsg::UpdateConfig { params } => update_config(deps, env, info, params),
         ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
+        ExecuteMsg::FlashLoan { assets, callback } => flash_loan(deps, env, info, assets, callback),
+        ExecuteMsg::AssertFlashLoanRepaid {
+            expected_balances,
+            fees,
+        } => assert_flash_loan_repaid(deps, env, info, expected_balances, fees),
+        ExecuteMsg::ProvideSingleSided { .. } => Err(ContractError::NonSupported {}),
         ExecuteMsg::ProvideLiquidity {
             assets,
             slippage_tolerance,
             auto_stake,
             receiver,
+            min_lp_to_receive,
+            deadline,
         } => provide_liquidity(
             deps,
             env,
@@ -214,12 +275,17 @@ pub fn execute(
             slippage_tolerance,
             auto_stake,
             receiver,
+            min_lp_to_receive,
+            deadline,
         ),
         ExecuteMsg::Swap {
             offer_asset,
+            ask_asset_info,
             belief_price,
             max_spread,
             to,
+            minimum_receive,
+            deadline,
         } => {
             offer_asset.info.check(deps.api)?;
             if !offer_asset.is_native_token() {
@@ -238,9 +304,46 @@ pub fn execute(
                 info.clone(),
                 info.sender,
                 offer_asset,
+                ask_asset_info,
                 belief_price,
                 max_spread,
                 to_addr,
+                minimum_receive,
+                deadline,
+            )
+        }
+        ExecuteMsg::SwapExactOut {
+            ask_asset,
+            max_offer_amount,
+            to,
+        } => {
+            ask_asset.info.check(deps.api)?;
+
+            // The offer asset is the only native coin sent along with the message
+            let offer_asset = match &info.funds[..] {
+                [coin] => Asset {
+                    info: AssetInfo::NativeToken {
+                        denom: coin.denom.clone(),
+                    },
+                    amount: coin.amount,
+                },
+                _ => return Err(ContractError::AssetMismatch {}),
+            };
+
+            let to_addr = if let Some(to_addr) = to {
+                Some(addr_validate_to_lower(deps.api, &to_addr)?)
+            } else {
+                None
+            };
+
+            swap_exact_out(
+                deps,
+                env,
+                info.sender,
+                offer_asset,
+                ask_asset,
+                max_offer_amount,
+                to_addr,
             )
         }
     }
@@ -267,9 +370,12 @@ pub fn receive_cw20(
     let contract_addr = info.sender.clone();
     match from_binary(&cw20_msg.msg) {
         Ok(Cw20HookMsg::Swap {
+            ask_asset_info,
             belief_price,
             max_spread,
             to,
+            minimum_receive,
+            deadline,
         }) => {
             // only asset contract can execute this message
             let mut authorized: bool = false;
@@ -302,17 +408,63 @@ pub fn receive_cw20(
                     info: AssetInfo::Token { contract_addr },
                     amount: cw20_msg.amount,
                 },
+                ask_asset_info,
                 belief_price,
                 max_spread,
                 to_addr,
+                minimum_receive,
+                deadline,
+            )
+        }
+        Ok(Cw20HookMsg::SwapExactOut {
+            ask_asset,
+            max_offer_amount,
+            to,
+        }) => {
+            // only asset contract can execute this message
+            let mut authorized: bool = false;
+            let config: Config = CONFIG.load(deps.storage)?;
+
+            for pool in config.pair_info.asset_infos {
+                if let AssetInfo::Token { contract_addr, .. } = &pool {
+                    if contract_addr == &info.sender {
+                        authorized = true;
+                    }
+                }
+            }
+
+            if !authorized {
+                return Err(ContractError::Unauthorized {});
+            }
+
+            ask_asset.info.check(deps.api)?;
+
+            let to_addr = if let Some(to_addr) = to {
+                Some(addr_validate_to_lower(deps.api, to_addr.as_str())?)
+            } else {
+                None
+            };
+
+            swap_exact_out(
+                deps,
+                env,
+                Addr::unchecked(cw20_msg.sender),
+                Asset {
+                    info: AssetInfo::Token { contract_addr },
+                    amount: cw20_msg.amount,
+                },
+                ask_asset,
+                max_offer_amount,
+                to_addr,
             )
         }
-        Ok(Cw20HookMsg::WithdrawLiquidity {}) => withdraw_liquidity(
+        Ok(Cw20HookMsg::WithdrawLiquidity { withdraw_type }) => withdraw_liquidity(
             deps,
             env,
             info,
             Addr::unchecked(cw20_msg.sender),
             cw20_msg.amount,
+            withdraw_type,
         ),
         Err(err) => Err(ContractError::Std(err)),
     }
@@ -335,43 +487,42 @@ pub fn receive_cw20(
 /// * **auto_stake** is object of type [`Option<bool>`]. Determines whether an autostake will be performed on the generator.
 ///
 /// * **receiver** is object of type [`Option<String>`]. Sets the receiver of liquidity.
+///
+/// * **min_lp_to_receive** is object of type [`Option<Uint128>`]. Sets the minimum amount of LP tokens to mint.
+///
+/// * **deadline** is object of type [`Option<u64>`]. Sets the block time after which the provision fails.
+#[allow(clippy::too_many_arguments)]
 pub fn provide_liquidity(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    assets: [Asset; 2],
+    assets: Vec<Asset>,
     slippage_tolerance: Option<Decimal>,
     auto_stake: Option<bool>,
     receiver: Option<String>,
+    min_lp_to_receive: Option<Uint128>,
+    deadline: Option<u64>,
 ) -> Result<Response, ContractError> {
-    assets[0].info.check(deps.api)?;
-    assets[1].info.check(deps.api)?;
+    assert_deadline(&env, deadline)?;
+
+    let mut config: Config = CONFIG.load(deps.storage)?;
+    assert_not_paused(
+        &deps.querier,
+        config.factory_addr.clone(),
+        env.contract.address.clone(),
+    )?;
 
-    let auto_stake = auto_stake.unwrap_or(false);
     for asset in assets.iter() {
+        asset.info.check(deps.api)?;
         asset.assert_sent_native_token_balance(&info)?;
     }
 
-    let mut config: Config = CONFIG.load(deps.storage)?;
-    let mut pools: [Asset; 2] = config
+    let auto_stake = auto_stake.unwrap_or(false);
+
+    let mut pools: Vec<Asset> = config
         .pair_info
         .query_pools(&deps.querier, env.contract.address.clone())?;
-    let deposits: [Uint128; 2] = [
-        assets
-            .iter()
-            .find(|a| a.info.equal(&pools[0].info))
-            .map(|a| a.amount)
-            .expect("Wrong asset info is given"),
-        assets
-            .iter()
-            .find(|a| a.info.equal(&pools[1].info))
-            .map(|a| a.amount)
-            .expect("Wrong asset info is given"),
-    ];
-
-    if deposits[0].is_zero() || deposits[1].is_zero() {
-        return Err(ContractError::InvalidZeroAmount {});
-    }
+    let deposits = get_deposits(&assets, &pools)?;
 
     let mut messages: Vec<CosmosMsg> = vec![];
     for (i, pool) in pools.iter_mut().enumerate() {
@@ -396,13 +547,102 @@ pub fn provide_liquidity(
     // assert slippage tolerance
     assert_slippage_tolerance(&slippage_tolerance, &deposits, &pools)?;
 
-    let token_precision_0 = query_token_precision(&deps.querier, pools[0].info.clone())?;
-    let token_precision_1 = query_token_precision(&deps.querier, pools[1].info.clone())?;
+    let precisions = config.precisions.clone();
+    let share = compute_share(deps.as_ref(), &env, &config, &pools, &precisions, &deposits)?;
+    if share < min_lp_to_receive.unwrap_or_default() {
+        return Err(ContractError::MinLpToReceiveAssertion {});
+    }
+
+    // mint LP token for sender or receiver if set
+    let receiver = receiver.unwrap_or_else(|| info.sender.to_string());
+    messages.extend(mint_liquidity_token_message(
+        deps.as_ref(),
+        &config,
+        env.clone(),
+        addr_validate_to_lower(deps.api, receiver.as_str())?,
+        share,
+        auto_stake,
+    )?);
+
+    // Accumulate prices for oracle
+    if accumulate_prices(env, &mut config, &pools, &precisions)? {
+        save_cumulative_prices(deps.storage, &config)?;
+    }
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .set_data(to_binary(&ProvideResponse {
+            assets: assets.clone(),
+            share,
+        })?)
+        .add_attributes(vec![
+            attr("action", "provide_liquidity"),
+            attr("sender", info.sender.as_str()),
+            attr("receiver", receiver.as_str()),
+            attr("assets", format_assets(&assets)),
+            attr("share", share.to_string()),
+        ]))
+}
+
+/// ## Description
+/// Returns the deposits ordered as the pool assets. Returns a [`ContractError`] if not every pool
+/// asset is provided with a non-zero amount.
+/// ## Params
+/// * **assets** are a slice of [`Asset`] type items. Sets the provided assets.
+///
+/// * **pools** are a slice of [`Asset`] type items. Sets the pool balances.
+fn get_deposits(assets: &[Asset], pools: &[Asset]) -> Result<Vec<Uint128>, ContractError> {
+    if assets.len() != pools.len() {
+        return Err(ContractError::InvalidNumberOfAssets {});
+    }
+
+    let deposits: Vec<Uint128> = pools
+        .iter()
+        .map(|pool| {
+            assets
+                .iter()
+                .find(|a| a.info.equal(&pool.info))
+                .map(|a| a.amount)
+                .ok_or_else(|| StdError::generic_err("Wrong asset info is given"))
+        })
+        .collect::<StdResult<_>>()?;
+
+    if deposits.iter().any(|deposit| deposit.is_zero()) {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+
+    Ok(deposits)
+}
+
+/// ## Description
+/// Returns the amount of LP tokens minted for the specified deposits.
+/// ## Params
+/// * **deps** is the object of type [`Deps`].
+///
+/// * **env** is the object of type [`Env`].
+///
+/// * **config** is the object of type [`Config`].
+///
+/// * **pools** are a slice of [`Asset`] type items. Sets the pool balances before the deposit.
+///
+/// * **precisions** are a slice of [`u8`] type items. Sets the precisions of the pool assets.
+///
+/// * **deposits** are a slice of [`Uint128`] type items. Sets the deposits ordered as the pools.
+fn compute_share(
+    deps: Deps,
+    env: &Env,
+    config: &Config,
+    pools: &[Asset],
+    precisions: &[u8],
+    deposits: &[Uint128],
+) -> Result<Uint128, ContractError> {
+    let greater_precision = precisions.iter().copied().max().unwrap_or_default();
 
-    let greater_precision = token_precision_0.max(token_precision_1);
+    let deposit_amounts = adjust_precisions(deposits, precisions, greater_precision)?;
 
-    let deposit_amount_0 = adjust_precision(deposits[0], token_precision_0, greater_precision)?;
-    let deposit_amount_1 = adjust_precision(deposits[1], token_precision_1, greater_precision)?;
+    let leverage = compute_current_amp(config, env)?
+        .checked_mul(pools.len() as u64)
+        .unwrap();
 
     let total_share = query_supply(&deps.querier, config.pair_info.liquidity_token.clone())?;
     let share = if total_share.is_zero() {
@@ -411,36 +651,40 @@ pub fn provide_liquidity(
             AssetInfo::Token {
                 contract_addr: config.pair_info.liquidity_token.clone(),
             },
+            &config.factory_addr,
         )?;
 
-        // Initial share = collateral amount
+        let initial_share = if let [deposit_amount_0, deposit_amount_1] = deposit_amounts[..] {
+            // Initial share = collateral amount
+            (U256::from(deposit_amount_0) * U256::from(deposit_amount_1))
+                .integer_sqrt()
+                .as_u128()
+        } else {
+            // Initial share = the invariant of the deposits divided by the number of assets
+            compute_d(leverage, &deposit_amounts).unwrap() / pools.len() as u128
+        };
+
         adjust_precision(
-            Uint128::new(
-                (U256::from(deposit_amount_0.u128()) * U256::from(deposit_amount_1.u128()))
-                    .integer_sqrt()
-                    .as_u128(),
-            ),
+            Uint128::new(initial_share),
             greater_precision,
             liquidity_token_precision,
         )?
     } else {
-        let leverage = compute_current_amp(&config, &env)?
-            .checked_mul(u64::from(N_COINS))
-            .unwrap();
-
-        let mut pool_amount_0 =
-            adjust_precision(pools[0].amount, token_precision_0, greater_precision)?;
-        let mut pool_amount_1 =
-            adjust_precision(pools[1].amount, token_precision_1, greater_precision)?;
+        let mut pool_amounts = adjust_precisions(
+            &pools.iter().map(|pool| pool.amount).collect::<Vec<_>>(),
+            precisions,
+            greater_precision,
+        )?;
 
-        let d_before_addition_liquidity =
-            compute_d(leverage, pool_amount_0.u128(), pool_amount_1.u128()).unwrap();
+        let d_before_addition_liquidity = compute_d(leverage, &pool_amounts).unwrap();
 
-        pool_amount_0 = pool_amount_0.checked_add(deposit_amount_0)?;
-        pool_amount_1 = pool_amount_1.checked_add(deposit_amount_1)?;
+        for (pool_amount, deposit_amount) in pool_amounts.iter_mut().zip(deposit_amounts) {
+            *pool_amount = Uint128::new(*pool_amount)
+                .checked_add(Uint128::new(deposit_amount))?
+                .u128();
+        }
 
-        let d_after_addition_liquidity =
-            compute_d(leverage, pool_amount_0.u128(), pool_amount_1.u128()).unwrap();
+        let d_after_addition_liquidity = compute_d(leverage, &pool_amounts).unwrap();
 
         // d after addition liquidity may be less than or equal to d before addition liquidity due to rounding
         if d_before_addition_liquidity >= d_after_addition_liquidity {
@@ -457,39 +701,7 @@ pub fn provide_liquidity(
         return Err(ContractError::LiquidityAmountTooSmall {});
     }
 
-    // mint LP token for sender or receiver if set
-    let receiver = receiver.unwrap_or_else(|| info.sender.to_string());
-    messages.extend(mint_liquidity_token_message(
-        deps.as_ref(),
-        &config,
-        env.clone(),
-        addr_validate_to_lower(deps.api, receiver.as_str())?,
-        share,
-        auto_stake,
-    )?);
-
-    // Accumulate prices for oracle
-    if let Some((price0_cumulative_new, price1_cumulative_new, block_time)) = accumulate_prices(
-        env,
-        &config,
-        pools[0].amount,
-        token_precision_0,
-        pools[1].amount,
-        token_precision_1,
-    )? {
-        config.price0_cumulative_last = price0_cumulative_new;
-        config.price1_cumulative_last = price1_cumulative_new;
-        config.block_time_last = block_time;
-        CONFIG.save(deps.storage, &config)?;
-    }
-
-    Ok(Response::new().add_messages(messages).add_attributes(vec![
-        attr("action", "provide_liquidity"),
-        attr("sender", info.sender.as_str()),
-        attr("receiver", receiver.as_str()),
-        attr("assets", format!("{}, {}", assets[0], assets[1])),
-        attr("share", share.to_string()),
-    ]))
+    Ok(share)
 }
 
 /// # Description
@@ -570,12 +782,16 @@ fn mint_liquidity_token_message(
 /// * **sender** is the object of type [`Addr`]. Sets where liquidity will be withdrawn.
 ///
 /// * **amount** is the object of type [`Uint128`]. Sets the withdrawal amount.
+///
+/// * **withdraw_type** is the object of type [`Option<WithdrawType>`]. Sets the way the liquidity
+/// is withdrawn. The assets are returned pro-rata if not specified.
 pub fn withdraw_liquidity(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
     sender: Addr,
     amount: Uint128,
+    withdraw_type: Option<WithdrawType>,
 ) -> Result<Response, ContractError> {
     let mut config: Config = CONFIG.load(deps.storage).unwrap();
 
@@ -584,80 +800,242 @@ pub fn withdraw_liquidity(
     }
 
     let (pools, total_share) = pool_info(deps.as_ref(), config.clone())?;
-    let refund_assets = get_share_in_assets(&pools, amount, total_share);
+    let precisions = config.precisions.clone();
+    let (refund_assets, burn_amount) = compute_withdraw(
+        deps.as_ref(),
+        &env,
+        &config,
+        &pools,
+        &precisions,
+        total_share,
+        amount,
+        withdraw_type,
+    )?;
 
     // Accumulate prices for oracle
-    if let Some((price0_cumulative_new, price1_cumulative_new, block_time)) = accumulate_prices(
-        env,
-        &config,
-        pools[0].amount,
-        query_token_precision(&deps.querier, pools[0].info.clone())?,
-        pools[1].amount,
-        query_token_precision(&deps.querier, pools[1].info.clone())?,
-    )? {
-        config.price0_cumulative_last = price0_cumulative_new;
-        config.price1_cumulative_last = price1_cumulative_new;
-        config.block_time_last = block_time;
-        CONFIG.save(deps.storage, &config)?;
+    if accumulate_prices(env, &mut config, &pools, &precisions)? {
+        save_cumulative_prices(deps.storage, &config)?;
     }
 
-    let messages: Vec<CosmosMsg> = vec![
-        refund_assets[0]
-            .clone()
-            .into_msg(&deps.querier, sender.clone())?,
-        refund_assets[1]
-            .clone()
-            .into_msg(&deps.querier, sender.clone())?,
-        CosmosMsg::Wasm(WasmMsg::Execute {
+    let mut messages: Vec<CosmosMsg> = refund_assets
+        .iter()
+        .map(|asset| asset.clone().into_msg(&deps.querier, sender.clone()))
+        .collect::<StdResult<_>>()?;
+    messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: config.pair_info.liquidity_token.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Burn {
+            amount: burn_amount,
+        })?,
+        funds: vec![],
+    }));
+
+    // Return the LP tokens that were not burned
+    let returned_amount = amount.checked_sub(burn_amount)?;
+    if !returned_amount.is_zero() {
+        messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
             contract_addr: config.pair_info.liquidity_token.to_string(),
-            msg: to_binary(&Cw20ExecuteMsg::Burn { amount })?,
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: sender.to_string(),
+                amount: returned_amount,
+            })?,
             funds: vec![],
-        }),
-    ];
+        }));
+    }
 
     let attributes = vec![
         attr("action", "withdraw_liquidity"),
         attr("sender", sender.as_str()),
-        attr("withdrawn_share", &amount.to_string()),
-        attr(
-            "refund_assets",
-            format!("{}, {}", refund_assets[0], refund_assets[1]),
-        ),
+        attr("withdrawn_share", &burn_amount.to_string()),
+        attr("refund_assets", format_assets(&refund_assets)),
     ];
 
     Ok(Response::new()
         .add_messages(messages)
+        .set_data(to_binary(&WithdrawResponse {
+            refund_assets,
+            withdrawn_share: burn_amount,
+        })?)
         .add_attributes(attributes))
 }
 
+/// ## Description
+/// Returns the assets to refund and the amount of LP tokens to burn for a withdrawal.
+/// ## Params
+/// * **deps** is the object of type [`Deps`].
+///
+/// * **env** is the object of type [`Env`].
+///
+/// * **config** is the object of type [`Config`].
+///
+/// * **pools** are a slice of [`Asset`] type items. Sets the pool balances.
+///
+/// * **precisions** are a slice of [`u8`] type items. Sets the precisions of the pool assets.
+///
+/// * **total_share** is the object of type [`Uint128`]. Sets the total amount of LP tokens.
+///
+/// * **amount** is the object of type [`Uint128`]. Sets the amount of LP tokens sent for the withdrawal.
+///
+/// * **withdraw_type** is the object of type [`Option<WithdrawType>`]. Sets the way the liquidity
+/// is withdrawn. The assets are returned pro-rata if not specified.
+#[allow(clippy::too_many_arguments)]
+fn compute_withdraw(
+    deps: Deps,
+    env: &Env,
+    config: &Config,
+    pools: &[Asset],
+    precisions: &[u8],
+    total_share: Uint128,
+    amount: Uint128,
+    withdraw_type: Option<WithdrawType>,
+) -> Result<(Vec<Asset>, Uint128), ContractError> {
+    let withdraw_type = match withdraw_type {
+        Some(withdraw_type) => withdraw_type,
+        None => return Ok((get_share_in_assets(pools, amount, total_share), amount)),
+    };
+
+    if amount.is_zero() {
+        return Err(ContractError::LiquidityAmountTooSmall {});
+    }
+    if amount >= total_share {
+        return Err(ContractError::WithdrawAmountTooLarge {});
+    }
+
+    let greater_precision = precisions.iter().copied().max().unwrap_or_default();
+    let xs = adjust_precisions(
+        &pools.iter().map(|pool| pool.amount).collect::<Vec<_>>(),
+        precisions,
+        greater_precision,
+    )?;
+
+    let fee_rate = query_fee_info(
+        &deps.querier,
+        config.factory_addr.clone(),
+        config.pair_info.pair_type.clone(),
+        Some(config.pair_info.contract_addr.clone()),
+    )?
+    .total_fee_rate;
+    let amp = compute_current_amp(config, env)?;
+
+    match withdraw_type {
+        WithdrawType::SingleAsset {
+            asset_info,
+            min_amount,
+        } => {
+            let ask_index = pools
+                .iter()
+                .position(|pool| pool.info.equal(&asset_info))
+                .ok_or(ContractError::AssetMismatch {})?;
+
+            let return_amount = calc_withdraw_one_coin(
+                &xs,
+                ask_index,
+                amount.u128(),
+                total_share.u128(),
+                amp,
+                fee_rate,
+            )
+            .ok_or(ContractError::LiquidityAmountTooSmall {})?;
+            let return_amount = adjust_precision(
+                Uint128::new(return_amount),
+                greater_precision,
+                precisions[ask_index],
+            )?;
+
+            if return_amount.is_zero() {
+                return Err(ContractError::LiquidityAmountTooSmall {});
+            }
+            if return_amount < min_amount.unwrap_or_default() {
+                return Err(ContractError::MinimumReceiveAssertion {});
+            }
+
+            Ok((
+                vec![Asset {
+                    info: asset_info,
+                    amount: return_amount,
+                }],
+                amount,
+            ))
+        }
+        WithdrawType::Imbalanced {
+            assets,
+            max_burn_amount,
+        } => {
+            for (i, asset) in assets.iter().enumerate() {
+                if assets[i + 1..].iter().any(|a| a.info.equal(&asset.info)) {
+                    return Err(ContractError::DoublingAssets {});
+                }
+                if !pools.iter().any(|pool| pool.info.equal(&asset.info)) {
+                    return Err(ContractError::AssetMismatch {});
+                }
+            }
+
+            let amounts: Vec<Uint128> = pools
+                .iter()
+                .map(|pool| {
+                    assets
+                        .iter()
+                        .find(|a| a.info.equal(&pool.info))
+                        .map(|a| a.amount)
+                        .unwrap_or_default()
+                })
+                .collect();
+
+            if amounts.iter().all(|amount| amount.is_zero()) {
+                return Err(ContractError::InvalidZeroAmount {});
+            }
+            if pools
+                .iter()
+                .zip(&amounts)
+                .any(|(pool, amount)| *amount >= pool.amount)
+            {
+                return Err(ContractError::WithdrawAmountTooLarge {});
+            }
+
+            let burn_amount = calc_withdraw_imbalanced(
+                &xs,
+                &adjust_precisions(&amounts, precisions, greater_precision)?,
+                total_share.u128(),
+                amp,
+                fee_rate,
+            )
+            .map(Uint128::new)
+            .ok_or(ContractError::LiquidityAmountTooSmall {})?;
+
+            if burn_amount > max_burn_amount.unwrap_or(amount).min(amount) {
+                return Err(ContractError::MaxBurnAssertion {});
+            }
+
+            let refund_assets = assets
+                .into_iter()
+                .filter(|asset| !asset.amount.is_zero())
+                .collect();
+
+            Ok((refund_assets, burn_amount))
+        }
+    }
+}
+
 /// ## Description
 /// Returns the share of assets.
 /// ## Params
-/// * **pools** are an array of [`Asset`] type items.
+/// * **pools** are a slice of [`Asset`] type items.
 ///
 /// * **amount** is the object of type [`Uint128`].
 ///
 /// * **total_share** is the object of type [`Uint128`].
-pub fn get_share_in_assets(
-    pools: &[Asset; 2],
-    amount: Uint128,
-    total_share: Uint128,
-) -> [Asset; 2] {
+pub fn get_share_in_assets(pools: &[Asset], amount: Uint128, total_share: Uint128) -> Vec<Asset> {
     let mut share_ratio = Decimal::zero();
     if !total_share.is_zero() {
         share_ratio = Decimal::from_ratio(amount, total_share);
     }
 
-    [
-        Asset {
-            info: pools[0].info.clone(),
-            amount: pools[0].amount * share_ratio,
-        },
-        Asset {
-            info: pools[1].info.clone(),
-            amount: pools[1].amount * share_ratio,
-        },
-    ]
+    pools
+        .iter()
+        .map(|pool| Asset {
+            info: pool.info.clone(),
+            amount: pool.amount * share_ratio,
+        })
+        .collect()
 }
 
 /// ## Description
@@ -675,11 +1053,19 @@ pub fn get_share_in_assets(
 ///
 /// * **offer_asset** is the object of type [`Asset`]. Proposed asset for swapping.
 ///
+/// * **ask_asset_info** is the object of type [`Option<AssetInfo>`]. The asset to receive.
+/// It is required for pools with more than two assets.
+///
 /// * **belief_price** is the object of type [`Option<Decimal>`]. Used to calculate the maximum spread.
 ///
 /// * **max_spread** is the object of type [`Option<Decimal>`]. Sets the maximum spread of the swap operation.
 ///
 /// * **to** is the object of type [`Option<Addr>`]. Sets the recipient of the swap operation.
+///
+/// * **minimum_receive** is the object of type [`Option<Uint128>`]. Sets the minimum amount of the
+/// ask asset to receive.
+///
+/// * **deadline** is the object of type [`Option<u64>`]. Sets the block time after which the swap fails.
 #[allow(clippy::too_many_arguments)]
 pub fn swap(
     deps: DepsMut,
@@ -687,13 +1073,22 @@ pub fn swap(
     info: MessageInfo,
     sender: Addr,
     offer_asset: Asset,
+    ask_asset_info: Option<AssetInfo>,
     belief_price: Option<Decimal>,
     max_spread: Option<Decimal>,
     to: Option<Addr>,
+    minimum_receive: Option<Uint128>,
+    deadline: Option<u64>,
 ) -> Result<Response, ContractError> {
+    assert_deadline(&env, deadline)?;
     offer_asset.assert_sent_native_token_balance(&info)?;
 
     let mut config: Config = CONFIG.load(deps.storage)?;
+    assert_not_paused(
+        &deps.querier,
+        config.factory_addr.clone(),
+        env.contract.address.clone(),
+    )?;
 
     // If the asset balance is already increased
     // To calculated properly we should subtract user deposit from the pool
@@ -711,34 +1106,41 @@ pub fn swap(
         })
         .collect();
 
-    let offer_pool: Asset;
-    let ask_pool: Asset;
-
-    if offer_asset.info.equal(&pools[0].info) {
-        offer_pool = pools[0].clone();
-        ask_pool = pools[1].clone();
-    } else if offer_asset.info.equal(&pools[1].info) {
-        offer_pool = pools[1].clone();
-        ask_pool = pools[0].clone();
-    } else {
-        return Err(ContractError::AssetMismatch {});
-    }
+    let (offer_index, ask_index) =
+        select_pools(Some(&offer_asset.info), ask_asset_info.as_ref(), &pools)?;
+    let ask_pool = pools[ask_index].clone();
 
     // Get fee info from factory
     let fee_info = query_fee_info(
         &deps.querier,
         config.factory_addr.clone(),
         config.pair_info.pair_type.clone(),
+        Some(config.pair_info.contract_addr.clone()),
+    )?;
+
+    let precisions = config.precisions.clone();
+
+    // Start a new dynamic fee window if the previous one ended
+    update_price_snapshot(deps.storage, &env, &config, &pools, &precisions)?;
+    let fee_rate = swap_fee_rate(
+        deps.storage,
+        &env,
+        &config,
+        &pools,
+        &precisions,
+        offer_index,
+        ask_index,
+        fee_info.total_fee_rate,
     )?;
 
     let offer_amount = offer_asset.amount;
     let (return_amount, spread_amount, commission_amount) = compute_swap(
-        offer_pool.amount,
-        query_token_precision(&deps.querier, offer_pool.info)?,
-        ask_pool.amount,
-        query_token_precision(&deps.querier, ask_pool.info.clone())?,
+        &pools,
+        &precisions,
+        offer_index,
+        ask_index,
         offer_amount,
-        fee_info.total_fee_rate,
+        fee_rate,
         compute_current_amp(&config, &env)?,
     )?;
 
@@ -758,6 +1160,9 @@ pub fn swap(
     };
 
     let tax_amount = return_asset.compute_tax(&deps.querier)?;
+    if return_amount.checked_sub(tax_amount)? < minimum_receive.unwrap_or_default() {
+        return Err(ContractError::MinimumReceiveAssertion {});
+    }
 
     let receiver = to.unwrap_or_else(|| sender.clone());
 
@@ -778,18 +1183,8 @@ pub fn swap(
     }
 
     // Accumulate prices for oracle
-    if let Some((price0_cumulative_new, price1_cumulative_new, block_time)) = accumulate_prices(
-        env,
-        &config,
-        pools[0].amount,
-        query_token_precision(&deps.querier, pools[0].info.clone())?,
-        pools[1].amount,
-        query_token_precision(&deps.querier, pools[1].info.clone())?,
-    )? {
-        config.price0_cumulative_last = price0_cumulative_new;
-        config.price1_cumulative_last = price1_cumulative_new;
-        config.block_time_last = block_time;
-        CONFIG.save(deps.storage, &config)?;
+    if accumulate_prices(env, &mut config, &pools, &precisions)? {
+        save_cumulative_prices(deps.storage, &config)?;
     }
 
     Ok(Response::new()
@@ -798,6 +1193,17 @@ pub fn swap(
             // 2. send inactive commission to collector
             messages,
         )
+        .set_data(to_binary(&SwapResponse {
+            offer_asset: offer_asset.clone(),
+            return_asset: Asset {
+                info: ask_pool.info.clone(),
+                amount: return_amount,
+            },
+            tax_amount,
+            spread_amount,
+            commission_amount,
+            maker_fee_amount,
+        })?)
         .add_attribute("action", "swap")
         .add_attribute("sender", sender.as_str())
         .add_attribute("receiver", receiver.as_str())
@@ -808,74 +1214,575 @@ pub fn swap(
         .add_attribute("tax_amount", tax_amount.to_string())
         .add_attribute("spread_amount", spread_amount.to_string())
         .add_attribute("commission_amount", commission_amount.to_string())
-        .add_attribute("maker_fee_amount", maker_fee_amount.to_string()))
+        .add_attribute("maker_fee_amount", maker_fee_amount.to_string())
+        .add_attribute("fee_rate", fee_rate.to_string()))
 }
 
 /// ## Description
-/// Shifts block_time when any price is zero to not fill an accumulator with a new price to that period.
+/// Swaps the offer asset for exactly the specified ask asset and refunds the unused part of the
+/// offer asset to the sender. Returns an [`ContractError`] on failure, otherwise returns the
+/// [`Response`] with the specified attributes if the operation was successful.
 /// ## Params
+/// * **deps** is the object of type [`DepsMut`].
+///
 /// * **env** is the object of type [`Env`].
 ///
-/// * **config** is the object of type [`Config`].
+/// * **sender** is the object of type [`Addr`]. Sets the default recipient of the swap operation.
 ///
-/// * **x** is the balance of asset[0] within a pool
+/// * **offer_asset** is the object of type [`Asset`]. This is the asset sent for swapping.
 ///
-/// * **y** is the balance of asset[1] within a pool
-pub fn accumulate_prices(
+/// * **ask_asset** is the object of type [`Asset`]. This is the asset to receive.
+///
+/// * **max_offer_amount** is the object of type [`Uint128`]. Sets the maximum amount of the offer asset to swap.
+///
+/// * **to** is the object of type [`Option<Addr>`]. Sets the recipient of the swap operation.
+pub fn swap_exact_out(
+    deps: DepsMut,
     env: Env,
+    sender: Addr,
+    offer_asset: Asset,
+    ask_asset: Asset,
+    max_offer_amount: Uint128,
+    to: Option<Addr>,
+) -> Result<Response, ContractError> {
+    if ask_asset.amount.is_zero() {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+
+    let mut config: Config = CONFIG.load(deps.storage)?;
+    assert_not_paused(
+        &deps.querier,
+        config.factory_addr.clone(),
+        env.contract.address.clone(),
+    )?;
+
+    // If the asset balance is already increased
+    // To calculated properly we should subtract user deposit from the pool
+    let pools: Vec<Asset> = config
+        .pair_info
+        .query_pools(&deps.querier, env.clone().contract.address)?
+        .iter()
+        .map(|p| {
+            let mut p = p.clone();
+            if p.info.equal(&offer_asset.info) {
+                p.amount = p.amount.checked_sub(offer_asset.amount).unwrap();
+            }
+
+            p
+        })
+        .collect();
+
+    let (offer_index, ask_index) =
+        select_pools(Some(&offer_asset.info), Some(&ask_asset.info), &pools)?;
+    let ask_pool = pools[ask_index].clone();
+
+    // Get fee info from factory
+    let fee_info = query_fee_info(
+        &deps.querier,
+        config.factory_addr.clone(),
+        config.pair_info.pair_type.clone(),
+        Some(config.pair_info.contract_addr.clone()),
+    )?;
+
+    let precisions = config.precisions.clone();
+
+    // Start a new dynamic fee window if the previous one ended
+    update_price_snapshot(deps.storage, &env, &config, &pools, &precisions)?;
+    let fee_rate = swap_fee_rate(
+        deps.storage,
+        &env,
+        &config,
+        &pools,
+        &precisions,
+        offer_index,
+        ask_index,
+        fee_info.total_fee_rate,
+    )?;
+    let amp = compute_current_amp(&config, &env)?;
+
+    // compute_offer_amount rounds down, so the offer is computed for one more ask unit and rounded up
+    // to make sure the pool doesn't lose on rounding
+    let (offer_amount, _, _) = compute_offer_amount(
+        &pools,
+        &precisions,
+        offer_index,
+        ask_index,
+        ask_asset.amount + Uint128::new(1),
+        fee_rate,
+        amp,
+    )?;
+    let offer_amount = offer_amount + Uint128::new(1);
+
+    if offer_amount > max_offer_amount || offer_amount > offer_asset.amount {
+        return Err(ContractError::MaxOfferAmountAssertion {});
+    }
+
+    let (return_amount, spread_amount, commission_amount) = compute_swap(
+        &pools,
+        &precisions,
+        offer_index,
+        ask_index,
+        offer_amount,
+        fee_rate,
+        amp,
+    )?;
+    if return_amount < ask_asset.amount {
+        return Err(ContractError::MaxOfferAmountAssertion {});
+    }
+
+    let tax_amount = ask_asset.compute_tax(&deps.querier)?;
+    let receiver = to.unwrap_or_else(|| sender.clone());
+    let return_msg = ask_asset
+        .clone()
+        .into_msg(&deps.querier, receiver.clone())?;
+    let mut messages: Vec<CosmosMsg> = vec![return_msg];
+
+    // Refund the unused part of the offer asset
+    let refund_amount = offer_asset.amount - offer_amount;
+    if !refund_amount.is_zero() {
+        let refund_asset = Asset {
+            info: offer_asset.info.clone(),
+            amount: refund_amount,
+        };
+        messages.push(refund_asset.into_msg(&deps.querier, sender.clone())?);
+    }
+
+    // Maker fee
+    let mut maker_fee_amount = Uint128::new(0);
+    if let Some(fee_address) = fee_info.fee_address {
+        if let Some(f) = calculate_maker_fee(
+            ask_pool.info.clone(),
+            commission_amount,
+            fee_info.maker_fee_rate,
+        ) {
+            messages.push(f.clone().into_msg(&deps.querier, fee_address)?);
+            maker_fee_amount = f.amount;
+        }
+    }
+
+    // Accumulate prices for oracle
+    if accumulate_prices(env, &mut config, &pools, &precisions)? {
+        save_cumulative_prices(deps.storage, &config)?;
+    }
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .set_data(to_binary(&SwapResponse {
+            offer_asset: Asset {
+                info: offer_asset.info.clone(),
+                amount: offer_amount,
+            },
+            return_asset: ask_asset.clone(),
+            tax_amount,
+            spread_amount,
+            commission_amount,
+            maker_fee_amount,
+        })?)
+        .add_attribute("action", "swap_exact_out")
+        .add_attribute("sender", sender.as_str())
+        .add_attribute("receiver", receiver.as_str())
+        .add_attribute("offer_asset", offer_asset.info.to_string())
+        .add_attribute("ask_asset", ask_pool.info.to_string())
+        .add_attribute("offer_amount", offer_amount.to_string())
+        .add_attribute("return_amount", ask_asset.amount.to_string())
+        .add_attribute("refund_amount", refund_amount.to_string())
+        .add_attribute("tax_amount", tax_amount.to_string())
+        .add_attribute("spread_amount", spread_amount.to_string())
+        .add_attribute("commission_amount", commission_amount.to_string())
+        .add_attribute("maker_fee_amount", maker_fee_amount.to_string())
+        .add_attribute("fee_rate", fee_rate.to_string()))
+}
+
+/// ## Description
+/// Enables the dynamic fee mode with the specified settings, or disables it if `params` is not set.
+/// Returns an [`ContractError`] on failure, otherwise returns [`Ok`].
+/// ## Params
+/// * **storage** is the object of type [`Storage`].
+///
+/// * **params** is an [`Option`] field of type [`DynamicFeeParams`].
+fn update_dynamic_fee(
+    storage: &mut dyn Storage,
+    params: Option<DynamicFeeParams>,
+) -> Result<(), ContractError> {
+    match params {
+        Some(params) => {
+            if !params.is_valid() {
+                return Err(ContractError::InvalidDynamicFeeParams {});
+            }
+            DYNAMIC_FEE_PARAMS.save(storage, &params)?;
+        }
+        None => DYNAMIC_FEE_PARAMS.remove(storage),
+    }
+
+    // The price movement is measured from the next swap on
+    PRICE_SNAPSHOT.remove(storage);
+
+    Ok(())
+}
+
+/// ## Description
+/// Returns the fee rate charged on swaps from the `offer_index` pool to the `ask_index` pool.
+/// If the dynamic fee mode is enabled, the fee depends on how far the price of that pair of assets moved
+/// since the start of the current window, otherwise `total_fee_rate` is returned.
+/// ## Params
+/// * **storage** is the object of type [`Storage`].
+///
+/// * **env** is the object of type [`Env`].
+///
+/// * **config** is the object of type [`Config`].
+///
+/// * **pools** is an array of [`Asset`] type items. These are the assets available in the pool.
+///
+/// * **precisions** is an array of [`u8`] type items. These are the precisions of the pool assets.
+///
+/// * **offer_index** is the index of the offer pool.
+///
+/// * **ask_index** is the index of the ask pool.
+///
+/// * **total_fee_rate** is the object of type [`Decimal`]. This is the fee rate set in the factory.
+#[allow(clippy::too_many_arguments)]
+pub fn swap_fee_rate(
+    storage: &dyn Storage,
+    env: &Env,
     config: &Config,
-    x: Uint128,
-    x_precision: u8,
-    y: Uint128,
-    y_precision: u8,
-) -> StdResult<Option<(Uint128, Uint128, u64)>> {
+    pools: &[Asset],
+    precisions: &[u8],
+    offer_index: usize,
+    ask_index: usize,
+    total_fee_rate: Decimal,
+) -> StdResult<Decimal> {
+    let params = match DYNAMIC_FEE_PARAMS.may_load(storage)? {
+        Some(params) => params,
+        None => return Ok(total_fee_rate),
+    };
+
+    let price = spot_price(env, config, pools, precisions, offer_index, ask_index)?;
+    let snapshot_price = match PRICE_SNAPSHOT.may_load(storage)? {
+        Some(snapshot) if env.block.time.seconds() < snapshot.block_time + params.window => {
+            snapshot
+                .prices
+                .into_iter()
+                .find(|(offer, ask, _)| {
+                    offer.equal(&pools[offer_index].info) && ask.equal(&pools[ask_index].info)
+                })
+                .map_or(price, |(_, _, snapshot_price)| snapshot_price)
+        }
+        // A new window starts with the current price
+        _ => price,
+    };
+
+    Ok(params.fee_rate(snapshot_price, price))
+}
+
+/// ## Description
+/// Starts a new dynamic fee window with the current prices of every (offer, ask) combination of the pool
+/// assets if the dynamic fee mode is enabled and the previous window ended.
+/// ## Params
+/// * **storage** is the object of type [`Storage`].
+///
+/// * **env** is the object of type [`Env`].
+///
+/// * **config** is the object of type [`Config`].
+///
+/// * **pools** is an array of [`Asset`] type items. These are the assets available in the pool.
+///
+/// * **precisions** is an array of [`u8`] type items. These are the precisions of the pool assets.
+fn update_price_snapshot(
+    storage: &mut dyn Storage,
+    env: &Env,
+    config: &Config,
+    pools: &[Asset],
+    precisions: &[u8],
+) -> StdResult<()> {
+    if let Some(params) = DYNAMIC_FEE_PARAMS.may_load(storage)? {
+        let block_time = env.block.time.seconds();
+        let window_ended = match PRICE_SNAPSHOT.may_load(storage)? {
+            Some(snapshot) => block_time >= snapshot.block_time + params.window,
+            None => true,
+        };
+
+        if window_ended {
+            let mut prices = vec![];
+            for offer_index in 0..pools.len() {
+                for ask_index in 0..pools.len() {
+                    if offer_index != ask_index {
+                        prices.push((
+                            pools[offer_index].info.clone(),
+                            pools[ask_index].info.clone(),
+                            spot_price(env, config, pools, precisions, offer_index, ask_index)?,
+                        ));
+                    }
+                }
+            }
+
+            PRICE_SNAPSHOT.save(storage, &PriceSnapshot { prices, block_time })?;
+        }
+    }
+
+    Ok(())
+}
+
+/// ## Description
+/// Returns the amount of the `ask_index` asset received for one `offer_index` asset without fees,
+/// or zero if any pool is empty.
+/// ## Params
+/// * **env** is the object of type [`Env`].
+///
+/// * **config** is the object of type [`Config`].
+///
+/// * **pools** is an array of [`Asset`] type items. These are the assets available in the pool.
+///
+/// * **precisions** is an array of [`u8`] type items. These are the precisions of the pool assets.
+///
+/// * **offer_index** is the index of the offer pool.
+///
+/// * **ask_index** is the index of the ask pool.
+fn spot_price(
+    env: &Env,
+    config: &Config,
+    pools: &[Asset],
+    precisions: &[u8],
+    offer_index: usize,
+    ask_index: usize,
+) -> StdResult<Decimal> {
+    let greater_precision = precisions
+        .iter()
+        .copied()
+        .max()
+        .unwrap_or_default()
+        .max(TWAP_PRECISION);
+    let xs = adjust_precisions(
+        &pools.iter().map(|pool| pool.amount).collect::<Vec<_>>(),
+        precisions,
+        greater_precision,
+    )?;
+
+    if xs.contains(&0) {
+        return Ok(Decimal::zero());
+    }
+
+    let one = adjust_precision(Uint128::new(1), 0, greater_precision)?.u128();
+    let ask_amount = calc_ask_amount(
+        &xs,
+        offer_index,
+        ask_index,
+        one,
+        compute_current_amp(config, env)?,
+    )
+    .ok_or_else(|| StdError::generic_err("Failed to compute the spot price"))?;
+
+    Ok(Decimal::from_ratio(ask_amount, one))
+}
+
+/// ## Description
+/// Lends the specified pool assets to the sender within the transaction. Sends the assets to the
+/// sender, executes the `callback` message on the sender contract and then the
+/// `AssertFlashLoanRepaid` callback which checks that the loan was repaid with the fee.
+/// The pair is locked until the loan is repaid.
+/// Returns an [`ContractError`] on failure, otherwise returns the [`Response`] with the specified attributes if the operation was successful.
+/// ## Params
+/// * **deps** is the object of type [`DepsMut`].
+///
+/// * **env** is the object of type [`Env`].
+///
+/// * **info** is the object of type [`MessageInfo`].
+///
+/// * **assets** is a vector of [`Asset`] type items. Sets the assets to borrow.
+///
+/// * **callback** is the object of type [`Binary`]. Sets the message executed on the borrower contract.
+pub fn flash_loan(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    assets: Vec<Asset>,
+    callback: Binary,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    assert_not_paused(
+        &deps.querier,
+        config.factory_addr.clone(),
+        env.contract.address.clone(),
+    )?;
+
+    let pools: Vec<Asset> = config
+        .pair_info
+        .query_pools(&deps.querier, env.contract.address.clone())?;
+
+    // Get fee info from factory
+    let fee_info = query_fee_info(
+        &deps.querier,
+        config.factory_addr.clone(),
+        config.pair_info.pair_type.clone(),
+        Some(config.pair_info.contract_addr.clone()),
+    )?;
+
+    Ok(flash_loan::flash_loan(
+        deps,
+        &env,
+        &info,
+        &pools,
+        assets,
+        &fee_info,
+        callback,
+        |expected_balances, fees| {
+            to_binary(&ExecuteMsg::AssertFlashLoanRepaid {
+                expected_balances,
+                fees,
+            })
+        },
+    )?)
+}
+
+/// ## Description
+/// Checks that the pool balances reached the expected ones, sends the Maker its share of the
+/// flash loan fees and unlocks the pair. Only the pair itself can execute it.
+/// Returns an [`ContractError`] on failure, otherwise returns the [`Response`] with the specified attributes if the operation was successful.
+/// ## Params
+/// * **deps** is the object of type [`DepsMut`].
+///
+/// * **env** is the object of type [`Env`].
+///
+/// * **info** is the object of type [`MessageInfo`].
+///
+/// * **expected_balances** is a vector of [`Asset`] type items. Sets the pool balances required once the loan is repaid.
+///
+/// * **fees** is a vector of [`Asset`] type items. Sets the flash loan fees.
+pub fn assert_flash_loan_repaid(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    expected_balances: Vec<Asset>,
+    fees: Vec<Asset>,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // Get fee info from factory
+    let fee_info = query_fee_info(
+        &deps.querier,
+        config.factory_addr.clone(),
+        config.pair_info.pair_type.clone(),
+        Some(config.pair_info.contract_addr.clone()),
+    )?;
+
+    Ok(flash_loan::assert_flash_loan_repaid(
+        deps,
+        &env,
+        &info,
+        expected_balances,
+        fees,
+        &fee_info,
+    )?)
+}
+
+/// ## Description
+/// Accumulates the cumulative prices for every (offer, ask) combination of the pool assets.
+/// Returns `true` if the config was updated, otherwise returns `false`.
+/// Shifts block_time when any price is zero to not fill an accumulator with a new price to that period.
+/// ## Params
+/// * **env** is the object of type [`Env`].
+///
+/// * **config** is the object of type [`Config`].
+///
+/// * **pools** are a slice of [`Asset`] type items. The balances of the pool assets.
+///
+/// * **precisions** are a slice of [`u8`] type items. The precisions of the pool assets.
+pub fn accumulate_prices(
+    env: Env,
+    config: &mut Config,
+    pools: &[Asset],
+    precisions: &[u8],
+) -> StdResult<bool> {
     let block_time = env.block.time.seconds();
     if block_time <= config.block_time_last {
-        return Ok(None);
+        return Ok(false);
     }
 
     // we have to shift block_time when any price is zero to not fill an accumulator with a new price to that period
 
-    let greater_precision = x_precision.max(y_precision).max(TWAP_PRECISION);
-    let x = adjust_precision(x, x_precision, greater_precision)?;
-    let y = adjust_precision(y, y_precision, greater_precision)?;
+    let greater_precision = precisions
+        .iter()
+        .copied()
+        .max()
+        .unwrap_or_default()
+        .max(TWAP_PRECISION);
+    let xs = adjust_precisions(
+        &pools.iter().map(|pool| pool.amount).collect::<Vec<_>>(),
+        precisions,
+        greater_precision,
+    )?;
 
     let time_elapsed = Uint128::from(block_time - config.block_time_last);
 
-    let mut pcl0 = config.price0_cumulative_last;
-    let mut pcl1 = config.price1_cumulative_last;
-
-    if !x.is_zero() && !y.is_zero() {
+    if xs.iter().all(|x| *x != 0) {
         let current_amp = compute_current_amp(config, &env)?;
-        pcl0 = config.price0_cumulative_last.wrapping_add(adjust_precision(
-            time_elapsed.checked_mul(Uint128::new(
-                calc_ask_amount(
-                    x.u128(),
-                    y.u128(),
-                    adjust_precision(Uint128::new(1), 0, greater_precision)?.u128(),
-                    current_amp,
-                )
-                .unwrap(),
-            ))?,
-            greater_precision,
-            TWAP_PRECISION,
-        )?);
-        pcl1 = config.price1_cumulative_last.wrapping_add(adjust_precision(
-            time_elapsed.checked_mul(Uint128::new(
-                calc_ask_amount(
-                    y.u128(),
-                    x.u128(),
-                    adjust_precision(Uint128::new(1), 0, greater_precision)?.u128(),
-                    current_amp,
-                )
-                .unwrap(),
-            ))?,
-            greater_precision,
-            TWAP_PRECISION,
-        )?)
-    };
+        let one = adjust_precision(Uint128::new(1), 0, greater_precision)?.u128();
+
+        for (offer_asset, ask_asset, price_cumulative_last) in config.cumulative_prices.iter_mut() {
+            let (offer_index, ask_index) = select_pools(Some(offer_asset), Some(ask_asset), pools)
+                .map_err(|err| StdError::generic_err(err.to_string()))?;
+
+            *price_cumulative_last = price_cumulative_last.wrapping_add(adjust_precision(
+                time_elapsed.checked_mul(Uint128::new(
+                    calc_ask_amount(&xs, offer_index, ask_index, one, current_amp).unwrap(),
+                ))?,
+                greater_precision,
+                TWAP_PRECISION,
+            )?);
+        }
+    }
+
+    config.block_time_last = block_time;
 
-    Ok(Some((pcl0, pcl1, block_time)))
+    Ok(true)
+}
+
+/// ## Description
+/// Returns the cumulative price of the offer asset denominated in the ask asset. Returns zero if
+/// the assets aren't a pair of the pool assets.
+/// ## Params
+/// * **config** is the object of type [`Config`].
+///
+/// * **offer_asset** is the object of type [`AssetInfo`].
+///
+/// * **ask_asset** is the object of type [`AssetInfo`].
+fn find_cumulative_price(
+    config: &Config,
+    offer_asset: &AssetInfo,
+    ask_asset: &AssetInfo,
+) -> Uint128 {
+    config
+        .cumulative_prices
+        .iter()
+        .find(|(offer, ask, _)| offer.equal(offer_asset) && ask.equal(ask_asset))
+        .map(|(_, _, price)| *price)
+        .unwrap_or_default()
+}
+
+/// ## Description
+/// Returns the observation of the cumulative prices of the first two pool assets at the time of the
+/// last price accumulation. These are the `price0_cumulative_last` and `price1_cumulative_last`
+/// prices returned by the [`QueryMsg::CumulativePrices`] query.
+/// ## Params
+/// * **config** is the object of type [`Config`].
+fn current_observation(config: &Config) -> Observation {
+    let asset_infos = &config.pair_info.asset_infos;
+    Observation {
+        timestamp: config.block_time_last,
+        price0_cumulative: find_cumulative_price(config, &asset_infos[0], &asset_infos[1]),
+        price1_cumulative: find_cumulative_price(config, &asset_infos[1], &asset_infos[0]),
+    }
+}
+
+/// ## Description
+/// Saves the config with the prices accumulated by [`accumulate_prices`] and records the cumulative
+/// prices of the first two pool assets as a new price observation.
+/// ## Params
+/// * **storage** is the object of type [`Storage`].
+///
+/// * **config** is the object of type [`Config`]. This is the pair config to save.
+pub fn save_cumulative_prices(storage: &mut dyn Storage, config: &Config) -> StdResult<()> {
+    CONFIG.save(storage, config)?;
+    store_observation(storage, &current_observation(config))
 }
 
 /// ## Description
@@ -920,10 +1827,10 @@ pub fn calculate_maker_fee(
 /// * **QueryMsg::Share { amount }** Returns information about the share of the pool in a vector
 /// that contains objects of type [`Asset`].
 ///
-/// * **QueryMsg::Simulation { offer_asset }** Returns information about the simulation of the
+/// * **QueryMsg::Simulation { offer_asset, ask_asset_info }** Returns information about the simulation of the
 /// swap in a [`SimulationResponse`] object.
 ///
-/// * **QueryMsg::ReverseSimulation { ask_asset }** Returns information about the reverse simulation
+/// * **QueryMsg::ReverseSimulation { offer_asset_info, ask_asset }** Returns information about the reverse simulation
 /// in a [`ReverseSimulationResponse`] object.
 ///
 /// * **QueryMsg::CumulativePrices {}** Returns information about the cumulative prices in a
@@ -931,20 +1838,50 @@ pub fn calculate_maker_fee(
 ///
 /// * **QueryMsg::Config {}** Returns information about the controls settings in a
 /// [`ConfigResponse`] object.
+///
+/// * **QueryMsg::SimulateWithdraw { lp_amount, withdraw_type }** Returns the assets returned and
+/// the LP tokens burned for a withdrawal in a [`SimulateWithdrawResponse`] object.
+///
+/// * **QueryMsg::SimulateProvide { assets }** Returns the amount of LP tokens minted for providing
+/// the specified assets.
+///
+/// * **QueryMsg::Observe { seconds_ago }** Returns the cumulative prices of the first two pool
+/// assets at each of the specified number of seconds before the current block in an
+/// [`ObserveResponse`] object.
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Pair {} => to_binary(&query_pair_info(deps)?),
         QueryMsg::Pool {} => to_binary(&query_pool(deps)?),
         QueryMsg::Share { amount } => to_binary(&query_share(deps, amount)?),
-        QueryMsg::Simulation { offer_asset } => {
-            to_binary(&query_simulation(deps, env, offer_asset)?)
-        }
-        QueryMsg::ReverseSimulation { ask_asset } => {
-            to_binary(&query_reverse_simulation(deps, env, ask_asset)?)
-        }
+        QueryMsg::Simulation {
+            offer_asset,
+            ask_asset_info,
+        } => to_binary(&query_simulation(deps, env, offer_asset, ask_asset_info)?),
+        QueryMsg::ReverseSimulation {
+            offer_asset_info,
+            ask_asset,
+        } => to_binary(&query_reverse_simulation(
+            deps,
+            env,
+            ask_asset,
+            offer_asset_info,
+        )?),
         QueryMsg::CumulativePrices {} => to_binary(&query_cumulative_prices(deps, env)?),
         QueryMsg::Config {} => to_binary(&query_config(deps, env)?),
+        QueryMsg::SimulateWithdraw {
+            lp_amount,
+            withdraw_type,
+        } => to_binary(&query_simulate_withdraw(
+            deps,
+            env,
+            lp_amount,
+            withdraw_type,
+        )?),
+        QueryMsg::SimulateProvide { assets } => {
+            to_binary(&query_simulate_provide(deps, env, assets)?)
+        }
+        QueryMsg::Observe { seconds_ago } => to_binary(&query_observe(deps, env, seconds_ago)?),
     }
 }
 
@@ -979,7 +1916,7 @@ pub fn query_pool(deps: Deps) -> StdResult<PoolResponse> {
 /// * **deps** is the object of type [`Deps`].
 ///
 /// * **amount** is the object of type [`Uint128`]. Sets the amount for which a share in the pool will be requested.
-pub fn query_share(deps: Deps, amount: Uint128) -> StdResult<[Asset; 2]> {
+pub fn query_share(deps: Deps, amount: Uint128) -> StdResult<Vec<Asset>> {
     let config: Config = CONFIG.load(deps.storage)?;
     let (pools, total_share) = pool_info(deps, config)?;
     let refund_assets = get_share_in_assets(&pools, amount, total_share);
@@ -993,40 +1930,51 @@ pub fn query_share(deps: Deps, amount: Uint128) -> StdResult<[Asset; 2]> {
 /// * **deps** is the object of type [`Deps`].
 ///
 /// * **offer_asset** is the object of type [`Asset`].
-pub fn query_simulation(deps: Deps, env: Env, offer_asset: Asset) -> StdResult<SimulationResponse> {
+///
+/// * **ask_asset_info** is the object of type [`Option<AssetInfo>`]. It is required for pools
+/// with more than two assets.
+pub fn query_simulation(
+    deps: Deps,
+    env: Env,
+    offer_asset: Asset,
+    ask_asset_info: Option<AssetInfo>,
+) -> StdResult<SimulationResponse> {
     let config: Config = CONFIG.load(deps.storage)?;
     let contract_addr = config.pair_info.contract_addr.clone();
 
-    let pools: [Asset; 2] = config.pair_info.query_pools(&deps.querier, contract_addr)?;
+    let pools: Vec<Asset> = config.pair_info.query_pools(&deps.querier, contract_addr)?;
 
-    let offer_pool: Asset;
-    let ask_pool: Asset;
-    if offer_asset.info.equal(&pools[0].info) {
-        offer_pool = pools[0].clone();
-        ask_pool = pools[1].clone();
-    } else if offer_asset.info.equal(&pools[1].info) {
-        offer_pool = pools[1].clone();
-        ask_pool = pools[0].clone();
-    } else {
-        return Err(StdError::generic_err(
-            "Given offer asset doesn't belong to pairs",
-        ));
-    }
+    let (offer_index, ask_index) =
+        select_pools(Some(&offer_asset.info), ask_asset_info.as_ref(), &pools)
+            .map_err(|err| StdError::generic_err(err.to_string()))?;
 
     // Get fee info from factory
     let fee_info = query_fee_info(
         &deps.querier,
         config.factory_addr.clone(),
         config.pair_info.pair_type.clone(),
+        Some(config.pair_info.contract_addr.clone()),
+    )?;
+
+    let precisions = config.precisions.clone();
+    let fee_rate = swap_fee_rate(
+        deps.storage,
+        &env,
+        &config,
+        &pools,
+        &precisions,
+        offer_index,
+        ask_index,
+        fee_info.total_fee_rate,
     )?;
 
     let (return_amount, spread_amount, commission_amount) = compute_swap(
-        offer_pool.amount,
-        query_token_precision(&deps.querier, offer_pool.info)?,
-        ask_pool.amount,
-        query_token_precision(&deps.querier, ask_pool.info)?,
+        &pools,
+        &precisions,
+        offer_index,
+        ask_index,
         offer_asset.amount,
-        fee_info.total_fee_rate,
+        fee_rate,
         compute_current_amp(&config, &env)?,
     )?;
 
@@ -1034,6 +1982,7 @@ pub fn query_simulation(deps: Deps, env: Env, offer_asset: Asset) -> StdResult<S
         return_amount,
         spread_amount,
         commission_amount,
+        fee_rate,
     })
 }
 
@@ -1043,44 +1992,51 @@ pub fn query_simulation(deps: Deps, env: Env, offer_asset: Asset) -> StdResult<S
 /// * **deps** is the object of type [`Deps`].
 ///
 /// * **ask_asset** is the object of type [`Asset`].
+///
+/// * **offer_asset_info** is the object of type [`Option<AssetInfo>`]. It is required for pools
+/// with more than two assets.
 pub fn query_reverse_simulation(
     deps: Deps,
     env: Env,
     ask_asset: Asset,
+    offer_asset_info: Option<AssetInfo>,
 ) -> StdResult<ReverseSimulationResponse> {
     let config: Config = CONFIG.load(deps.storage)?;
     let contract_addr = config.pair_info.contract_addr.clone();
 
-    let pools: [Asset; 2] = config.pair_info.query_pools(&deps.querier, contract_addr)?;
+    let pools: Vec<Asset> = config.pair_info.query_pools(&deps.querier, contract_addr)?;
 
-    let offer_pool: Asset;
-    let ask_pool: Asset;
-    if ask_asset.info.equal(&pools[0].info) {
-        ask_pool = pools[0].clone();
-        offer_pool = pools[1].clone();
-    } else if ask_asset.info.equal(&pools[1].info) {
-        ask_pool = pools[1].clone();
-        offer_pool = pools[0].clone();
-    } else {
-        return Err(StdError::generic_err(
-            "Given ask asset doesn't belong to pairs",
-        ));
-    }
+    let (offer_index, ask_index) =
+        select_pools(offer_asset_info.as_ref(), Some(&ask_asset.info), &pools)
+            .map_err(|err| StdError::generic_err(err.to_string()))?;
 
     // Get fee info from factory
     let fee_info = query_fee_info(
         &deps.querier,
         config.factory_addr.clone(),
         config.pair_info.pair_type.clone(),
+        Some(config.pair_info.contract_addr.clone()),
+    )?;
+
+    let precisions = config.precisions.clone();
+    let fee_rate = swap_fee_rate(
+        deps.storage,
+        &env,
+        &config,
+        &pools,
+        &precisions,
+        offer_index,
+        ask_index,
+        fee_info.total_fee_rate,
     )?;
 
     let (offer_amount, spread_amount, commission_amount) = compute_offer_amount(
-        offer_pool.amount,
-        query_token_precision(&deps.querier, offer_pool.info)?,
-        ask_pool.amount,
-        query_token_precision(&deps.querier, ask_pool.info)?,
+        &pools,
+        &precisions,
+        offer_index,
+        ask_index,
         ask_asset.amount,
-        fee_info.total_fee_rate,
+        fee_rate,
         compute_current_amp(&config, &env)?,
     )?;
 
@@ -1098,45 +2054,123 @@ pub fn query_reverse_simulation(
 ///
 /// * **env** is the object of type [`Env`].
 pub fn query_cumulative_prices(deps: Deps, env: Env) -> StdResult<CumulativePricesResponse> {
-    let config: Config = CONFIG.load(deps.storage)?;
+    let mut config: Config = CONFIG.load(deps.storage)?;
     let (assets, total_share) = pool_info(deps, config.clone())?;
 
-    let mut price0_cumulative_last = config.price0_cumulative_last;
-    let mut price1_cumulative_last = config.price1_cumulative_last;
-
-    if let Some((price0_cumulative_new, price1_cumulative_new, _)) = accumulate_prices(
-        env,
-        &config,
-        assets[0].amount,
-        query_token_precision(&deps.querier, assets[0].info.clone())?,
-        assets[1].amount,
-        query_token_precision(&deps.querier, assets[1].info.clone())?,
-    )? {
-        price0_cumulative_last = price0_cumulative_new;
-        price1_cumulative_last = price1_cumulative_new;
-    }
+    let precisions = config.precisions.clone();
+    accumulate_prices(env, &mut config, &assets, &precisions)?;
 
     let resp = CumulativePricesResponse {
+        price0_cumulative_last: find_cumulative_price(&config, &assets[0].info, &assets[1].info),
+        price1_cumulative_last: find_cumulative_price(&config, &assets[1].info, &assets[0].info),
+        cumulative_prices: config.cumulative_prices.clone(),
         assets,
         total_share,
-        price0_cumulative_last,
-        price1_cumulative_last,
     };
 
     Ok(resp)
 }
 
+/// ## Description
+/// Returns the cumulative prices of the first two pool assets at each of the specified number of
+/// seconds before the current block in an [`ObserveResponse`] object.
+/// ## Params
+/// * **deps** is the object of type [`Deps`].
+///
+/// * **env** is the object of type [`Env`].
+///
+/// * **seconds_ago** is a vector with items of type [`u64`]. These are the numbers of seconds
+/// before the current block time to return the cumulative prices for.
+pub fn query_observe(deps: Deps, env: Env, seconds_ago: Vec<u64>) -> StdResult<ObserveResponse> {
+    let mut config: Config = CONFIG.load(deps.storage)?;
+    let (assets, _) = pool_info(deps, config.clone())?;
+
+    let precisions = config.precisions.clone();
+    accumulate_prices(env, &mut config, &assets, &precisions)?;
+    let current = current_observation(&config);
+
+    let observations = seconds_ago
+        .into_iter()
+        .map(|seconds_ago| observe(deps.storage, &current, seconds_ago))
+        .collect::<StdResult<Vec<Observation>>>()?;
+
+    Ok(ObserveResponse { observations })
+}
+
+/// ## Description
+/// Returns the assets returned and the LP tokens burned for a withdrawal in a [`SimulateWithdrawResponse`] object.
+/// ## Params
+/// * **deps** is the object of type [`Deps`].
+///
+/// * **env** is the object of type [`Env`].
+///
+/// * **lp_amount** is the object of type [`Uint128`]. Sets the amount of LP tokens sent for the withdrawal.
+///
+/// * **withdraw_type** is the object of type [`Option<WithdrawType>`]. Sets the way the liquidity
+/// is withdrawn. The assets are returned pro-rata if not specified.
+pub fn query_simulate_withdraw(
+    deps: Deps,
+    env: Env,
+    lp_amount: Uint128,
+    withdraw_type: Option<WithdrawType>,
+) -> StdResult<SimulateWithdrawResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let (pools, total_share) = pool_info(deps, config.clone())?;
+    let precisions = config.precisions.clone();
+
+    let (assets, burn_amount) = compute_withdraw(
+        deps,
+        &env,
+        &config,
+        &pools,
+        &precisions,
+        total_share,
+        lp_amount,
+        withdraw_type,
+    )
+    .map_err(|err| StdError::generic_err(err.to_string()))?;
+
+    Ok(SimulateWithdrawResponse {
+        assets,
+        burn_amount,
+    })
+}
+
+/// ## Description
+/// Returns the amount of LP tokens minted for providing the specified assets.
+/// ## Params
+/// * **deps** is the object of type [`Deps`].
+///
+/// * **env** is the object of type [`Env`].
+///
+/// * **assets** is a vector of [`Asset`] type items. Sets the assets to provide.
+pub fn query_simulate_provide(deps: Deps, env: Env, assets: Vec<Asset>) -> StdResult<Uint128> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let pools: Vec<Asset> = config
+        .pair_info
+        .query_pools(&deps.querier, env.contract.address.clone())?;
+    let precisions = config.precisions.clone();
+
+    get_deposits(&assets, &pools)
+        .and_then(|deposits| compute_share(deps, &env, &config, &pools, &precisions, &deposits))
+        .map_err(|err| StdError::generic_err(err.to_string()))
+}
+
 /// ## Description
 /// Returns information about the controls settings in a [`ConfigResponse`] object.
 /// ## Params
 /// * **deps** is the object of type [`Deps`].
+///
+/// * **env** is the object of type [`Env`].
 pub fn query_config(deps: Deps, env: Env) -> StdResult<ConfigResponse> {
     let config: Config = CONFIG.load(deps.storage)?;
     Ok(ConfigResponse {
         block_time_last: config.block_time_last,
         params: Some(to_binary(&StablePoolConfig {
             amp: Decimal::from_ratio(compute_current_amp(&config, &env)?, AMP_PRECISION),
+            dynamic_fee: DYNAMIC_FEE_PARAMS.may_load(deps.storage)?,
         })?),
+        paused: query_pair_paused(&deps.querier, config.factory_addr, env.contract.address)?,
     })
 }
 
@@ -1156,31 +2190,38 @@ pub fn amount_of(coins: &[Coin], denom: String) -> Uint128 {
 /// ## Description
 /// Returns computed swap for the pool with specified parameters
 /// ## Params
-/// * **offer_pool** is the object of type [`Uint128`]. Sets the offer pool.
+/// * **pools** are a slice of [`Asset`] type items. Sets the pool balances.
+///
+/// * **precisions** are a slice of [`u8`] type items. Sets the precisions of the pool assets.
+///
+/// * **offer_index** is the object of type [`usize`]. Sets the index of the offer pool.
 ///
-/// * **ask_pool** is the object of type [`Uint128`]. Sets the ask pool.
+/// * **ask_index** is the object of type [`usize`]. Sets the index of the ask pool.
 ///
 /// * **offer_amount** is the object of type [`Uint128`]. Sets the offer amount.
 ///
 /// * **commission_rate** is the object of type [`Decimal`]. Sets the commission rate.
 fn compute_swap(
-    offer_pool: Uint128,
-    offer_precision: u8,
-    ask_pool: Uint128,
-    ask_precision: u8,
+    pools: &[Asset],
+    precisions: &[u8],
+    offer_index: usize,
+    ask_index: usize,
     offer_amount: Uint128,
     commission_rate: Decimal,
     amp: u64,
 ) -> StdResult<(Uint128, Uint128, Uint128)> {
     // offer => ask
 
-    let greater_precision = offer_precision.max(ask_precision);
-    let offer_pool = adjust_precision(offer_pool, offer_precision, greater_precision)?;
-    let ask_pool = adjust_precision(ask_pool, ask_precision, greater_precision)?;
-    let offer_amount = adjust_precision(offer_amount, offer_precision, greater_precision)?;
+    let greater_precision = precisions.iter().copied().max().unwrap_or_default();
+    let xs = adjust_precisions(
+        &pools.iter().map(|pool| pool.amount).collect::<Vec<_>>(),
+        precisions,
+        greater_precision,
+    )?;
+    let offer_amount = adjust_precision(offer_amount, precisions[offer_index], greater_precision)?;
 
     let return_amount = Uint128::new(
-        calc_ask_amount(offer_pool.u128(), ask_pool.u128(), offer_amount.u128(), amp).unwrap(),
+        calc_ask_amount(&xs, offer_index, ask_index, offer_amount.u128(), amp).unwrap(),
     );
 
     // We assume the assets should stay in a 1:1 ratio, the true exchange rate is 1. So any exchange rate <1 could be considered the spread
@@ -1191,6 +2232,7 @@ fn compute_swap(
     // commission will be absorbed to pool
     let return_amount: Uint128 = return_amount.checked_sub(commission_amount).unwrap();
 
+    let ask_precision = precisions[ask_index];
     let return_amount = adjust_precision(return_amount, greater_precision, ask_precision)?;
     let spread_amount = adjust_precision(spread_amount, greater_precision, ask_precision)?;
     let commission_amount = adjust_precision(commission_amount, greater_precision, ask_precision)?;
@@ -1201,27 +2243,35 @@ fn compute_swap(
 /// ## Description
 /// Returns computed offer amount for the pool with specified parameters.
 /// ## Params
-/// * **offer_pool** is the object of type [`Uint128`]. Sets the offer pool.
+/// * **pools** are a slice of [`Asset`] type items. Sets the pool balances.
+///
+/// * **precisions** are a slice of [`u8`] type items. Sets the precisions of the pool assets.
+///
+/// * **offer_index** is the object of type [`usize`]. Sets the index of the offer pool.
 ///
-/// * **ask_pool** is the object of type [`Uint128`]. Sets the ask pool.
+/// * **ask_index** is the object of type [`usize`]. Sets the index of the ask pool.
 ///
-/// * **offer_amount** is the object of type [`Uint128`]. Sets the ask amount.
+/// * **ask_amount** is the object of type [`Uint128`]. Sets the ask amount.
 ///
 /// * **commission_rate** is the object of type [`Decimal`]. Sets the commission rate.
 fn compute_offer_amount(
-    offer_pool: Uint128,
-    offer_precision: u8,
-    ask_pool: Uint128,
-    ask_precision: u8,
+    pools: &[Asset],
+    precisions: &[u8],
+    offer_index: usize,
+    ask_index: usize,
     ask_amount: Uint128,
     commission_rate: Decimal,
     amp: u64,
 ) -> StdResult<(Uint128, Uint128, Uint128)> {
     // ask => offer
 
-    let greater_precision = offer_precision.max(ask_precision);
-    let offer_pool = adjust_precision(offer_pool, offer_precision, greater_precision)?;
-    let ask_pool = adjust_precision(ask_pool, ask_precision, greater_precision)?;
+    let greater_precision = precisions.iter().copied().max().unwrap_or_default();
+    let xs = adjust_precisions(
+        &pools.iter().map(|pool| pool.amount).collect::<Vec<_>>(),
+        precisions,
+        greater_precision,
+    )?;
+    let ask_precision = precisions[ask_index];
     let ask_amount = adjust_precision(ask_amount, ask_precision, greater_precision)?;
 
     let one_minus_commission = Decimal256::one() - Decimal256::from(commission_rate);
@@ -1230,8 +2280,9 @@ fn compute_offer_amount(
 
     let offer_amount = Uint128::new(
         calc_offer_amount(
-            offer_pool.u128(),
-            ask_pool.u128(),
+            &xs,
+            offer_index,
+            ask_index,
             before_commission_deduction.u128(),
             amp,
         )
@@ -1243,13 +2294,95 @@ fn compute_offer_amount(
 
     let commission_amount = before_commission_deduction * commission_rate;
 
-    let offer_amount = adjust_precision(offer_amount, greater_precision, offer_precision)?;
+    let offer_amount = adjust_precision(offer_amount, greater_precision, precisions[offer_index])?;
     let spread_amount = adjust_precision(spread_amount, greater_precision, ask_precision)?;
     let commission_amount = adjust_precision(commission_amount, greater_precision, ask_precision)?;
 
     Ok((offer_amount, spread_amount, commission_amount))
 }
 
+/// ## Description
+/// Returns the indexes of the offer and ask pools. The asset that is not specified is resolved
+/// automatically only for pools with two assets.
+/// ## Params
+/// * **offer_asset_info** is the object of type [`Option<&AssetInfo>`]. Sets the offer asset.
+///
+/// * **ask_asset_info** is the object of type [`Option<&AssetInfo>`]. Sets the ask asset.
+///
+/// * **pools** are a slice of [`Asset`] type items. Sets the pool balances.
+fn select_pools(
+    offer_asset_info: Option<&AssetInfo>,
+    ask_asset_info: Option<&AssetInfo>,
+    pools: &[Asset],
+) -> Result<(usize, usize), ContractError> {
+    let position = |asset_info: &AssetInfo| {
+        pools
+            .iter()
+            .position(|pool| pool.info.equal(asset_info))
+            .ok_or(ContractError::AssetMismatch {})
+    };
+
+    match (offer_asset_info, ask_asset_info) {
+        (Some(offer_asset_info), Some(ask_asset_info)) => {
+            let offer_index = position(offer_asset_info)?;
+            let ask_index = position(ask_asset_info)?;
+            if offer_index == ask_index {
+                return Err(ContractError::DoublingAssets {});
+            }
+
+            Ok((offer_index, ask_index))
+        }
+        (Some(offer_asset_info), None) if pools.len() == 2 => {
+            let offer_index = position(offer_asset_info)?;
+            Ok((offer_index, 1 - offer_index))
+        }
+        (None, Some(ask_asset_info)) if pools.len() == 2 => {
+            let ask_index = position(ask_asset_info)?;
+            Ok((1 - ask_index, ask_index))
+        }
+        _ => Err(ContractError::VariableAssetMissed {}),
+    }
+}
+
+/// ## Description
+/// Returns the precisions of the pool assets.
+/// ## Params
+/// * **querier** is the object of type [`QuerierWrapper`].
+///
+/// * **asset_infos** are a slice of [`AssetInfo`] type items.
+///
+/// * **factory_addr** is the object of type [`Addr`]. The factory keeps the decimals of native tokens.
+fn query_precisions(
+    querier: &QuerierWrapper,
+    asset_infos: &[AssetInfo],
+    factory_addr: &Addr,
+) -> StdResult<Vec<u8>> {
+    asset_infos
+        .iter()
+        .map(|asset_info| query_token_precision(querier, asset_info.clone(), factory_addr))
+        .collect()
+}
+
+/// ## Description
+/// Returns the amounts adjusted to the new precision.
+/// ## Params
+/// * **values** are a slice of [`Uint128`] type items. The values for which the precision is adjusted.
+///
+/// * **current_precisions** are a slice of [`u8`] type items. Sets the current precision of each value.
+///
+/// * **new_precision** is the object of type [`u8`]. Sets the new precision.
+fn adjust_precisions(
+    values: &[Uint128],
+    current_precisions: &[u8],
+    new_precision: u8,
+) -> StdResult<Vec<u128>> {
+    values
+        .iter()
+        .zip(current_precisions)
+        .map(|(value, precision)| Ok(adjust_precision(*value, *precision, new_precision)?.u128()))
+        .collect()
+}
+
 /// ## Description
 /// Returns adjust precision.
 /// ## Params
@@ -1327,13 +2460,13 @@ pub fn assert_max_spread(
 /// ## Params
 /// * **slippage_tolerance** is the object of type [`Option<Decimal>`].
 ///
-/// * **deposits** are an array of [`Uint128`] type items.
+/// * **deposits** are a slice of [`Uint128`] type items.
 ///
-/// * **pools** are an array of [`Asset`] type items.
+/// * **pools** are a slice of [`Asset`] type items.
 fn assert_slippage_tolerance(
     _slippage_tolerance: &Option<Decimal>,
-    _deposits: &[Uint128; 2],
-    _pools: &[Asset; 2],
+    _deposits: &[Uint128],
+    _pools: &[Asset],
 ) -> Result<(), ContractError> {
     //There is no slippage in the stable pool
     Ok(())
@@ -1342,14 +2475,60 @@ fn assert_slippage_tolerance(
 /// ## Description
 /// Used for migration of contract. Returns the default object of type [`Response`].
 /// ## Params
-/// * **_deps** is the object of type [`DepsMut`].
+/// * **deps** is the object of type [`DepsMut`].
 ///
 /// * **_env** is the object of type [`Env`].
 ///
 /// * **_msg** is the object of type [`MigrateMsg`].
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
-    Ok(Response::default())
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let contract_version = get_contract_version(deps.storage)?;
+
+    match contract_version.contract.as_ref() {
+        "astroport-pair-stable" => match contract_version.version.as_ref() {
+            "1.0.0" => {
+                let config_v100 = migration::CONFIGV100.load(deps.storage)?;
+                let asset_infos = config_v100.pair_info.asset_infos.clone();
+                let precisions =
+                    query_precisions(&deps.querier, &asset_infos, &config_v100.factory_addr)?;
+
+                let new_config = Config {
+                    cumulative_prices: vec![
+                        (
+                            asset_infos[0].clone(),
+                            asset_infos[1].clone(),
+                            config_v100.price0_cumulative_last,
+                        ),
+                        (
+                            asset_infos[1].clone(),
+                            asset_infos[0].clone(),
+                            config_v100.price1_cumulative_last,
+                        ),
+                    ],
+                    precisions,
+                    pair_info: config_v100.pair_info,
+                    factory_addr: config_v100.factory_addr,
+                    block_time_last: config_v100.block_time_last,
+                    init_amp: config_v100.init_amp,
+                    init_amp_time: config_v100.init_amp_time,
+                    next_amp: config_v100.next_amp,
+                    next_amp_time: config_v100.next_amp_time,
+                };
+
+                CONFIG.save(deps.storage, &new_config)?;
+            }
+            _ => return Err(ContractError::MigrationError {}),
+        },
+        _ => return Err(ContractError::MigrationError {}),
+    }
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("previous_contract_name", &contract_version.contract)
+        .add_attribute("previous_contract_version", &contract_version.version)
+        .add_attribute("new_contract_name", CONTRACT_NAME)
+        .add_attribute("new_contract_version", CONTRACT_VERSION))
 }
 
 /// ## Description
@@ -1358,9 +2537,9 @@ pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Respons
 /// * **deps** is the object of type [`Deps`].
 ///
 /// * **config** is the object of type [`Config`].
-pub fn pool_info(deps: Deps, config: Config) -> StdResult<([Asset; 2], Uint128)> {
+pub fn pool_info(deps: Deps, config: Config) -> StdResult<(Vec<Asset>, Uint128)> {
     let contract_addr = config.pair_info.contract_addr.clone();
-    let pools: [Asset; 2] = config.pair_info.query_pools(&deps.querier, contract_addr)?;
+    let pools: Vec<Asset> = config.pair_info.query_pools(&deps.querier, contract_addr)?;
     let total_share: Uint128 = query_supply(&deps.querier, config.pair_info.liquidity_token)?;
 
     Ok((pools, total_share))
@@ -1397,6 +2576,9 @@ pub fn update_config(
             next_amp_time,
         } => start_changing_amp(config, deps, env, next_amp, next_amp_time)?,
         StablePoolUpdateParams::StopChangingAmp {} => stop_changing_amp(config, deps, env)?,
+        StablePoolUpdateParams::UpdateDynamicFee { params } => {
+            update_dynamic_fee(deps.storage, params)?
+        }
     }
 
     Ok(Response::default())
@@ -1,4 +1,7 @@
-use crate::math::{MAX_AMP, MAX_AMP_CHANGE, MIN_AMP_CHANGING_TIME};
+use crate::math::{MAX_AMP, MAX_AMP_CHANGE, MAX_N_COINS, MIN_AMP_CHANGING_TIME};
+use astroport::flash_loan::FlashLoanError;
+use astroport::pair::DeadlineExpired;
+use astroport::querier::PauseError;
 use cosmwasm_std::{OverflowError, StdError};
 use thiserror::Error;
 
@@ -12,6 +15,9 @@ pub enum ContractError {
     #[error("Unauthorized")]
     Unauthorized {},
 
+    #[error("Operation non supported")]
+    NonSupported {},
+
     #[error("Doubling assets in asset infos")]
     DoublingAssets {},
 
@@ -30,6 +36,24 @@ pub enum ContractError {
     #[error("Operation exceeds max splippage tolerance")]
     MaxSlippageAssertion {},
 
+    #[error("The required offer amount exceeds the max offer amount or the sent amount")]
+    MaxOfferAmountAssertion {},
+
+    #[error("The return amount is less than the minimum receive amount")]
+    MinimumReceiveAssertion {},
+
+    #[error("The minted LP amount is less than the minimum LP amount to receive")]
+    MinLpToReceiveAssertion {},
+
+    #[error("The deadline has passed")]
+    DeadlineExpired {},
+
+    #[error("Operation exceeds max LP burn amount")]
+    MaxBurnAssertion {},
+
+    #[error("The withdrawn amount exceeds the pool liquidity")]
+    WithdrawAmountTooLarge {},
+
     #[error("Native token balance mismatch between the argument and the transferred")]
     AssetMismatch {},
 
@@ -59,6 +83,30 @@ pub enum ContractError {
 
     #[error("GeneratorAddress is not set in factory. Cannot autostake")]
     AutoStakeError {},
+
+    #[error(
+        "The pool must consist of 2 to {} assets and all of them must be provided",
+        MAX_N_COINS
+    )]
+    InvalidNumberOfAssets {},
+
+    #[error("The ask or offer asset must be specified for pools with more than two assets")]
+    VariableAssetMissed {},
+
+    #[error("Contract can't be migrated!")]
+    MigrationError {},
+
+    #[error("Invalid dynamic fee parameters")]
+    InvalidDynamicFeeParams {},
+
+    #[error("A flash loan is in progress")]
+    FlashLoanInProgress {},
+
+    #[error("The flash loan was not repaid with the fee")]
+    FlashLoanNotRepaid {},
+
+    #[error("The pair is paused")]
+    Paused {},
 }
 
 impl From<OverflowError> for ContractError {
@@ -66,3 +114,31 @@ impl From<OverflowError> for ContractError {
         StdError::from(o).into()
     }
 }
+
+impl From<FlashLoanError> for ContractError {
+    fn from(err: FlashLoanError) -> Self {
+        match err {
+            FlashLoanError::Std(err) => ContractError::Std(err),
+            FlashLoanError::Unauthorized => ContractError::Unauthorized {},
+            FlashLoanError::InvalidZeroAmount => ContractError::InvalidZeroAmount {},
+            FlashLoanError::DoublingAssets => ContractError::DoublingAssets {},
+            FlashLoanError::AssetMismatch => ContractError::AssetMismatch {},
+            FlashLoanError::NotRepaid => ContractError::FlashLoanNotRepaid {},
+        }
+    }
+}
+
+impl From<DeadlineExpired> for ContractError {
+    fn from(_: DeadlineExpired) -> Self {
+        ContractError::DeadlineExpired {}
+    }
+}
+
+impl From<PauseError> for ContractError {
+    fn from(err: PauseError) -> Self {
+        match err {
+            PauseError::Std(err) => ContractError::Std(err),
+            PauseError::Paused => ContractError::Paused {},
+        }
+    }
+}
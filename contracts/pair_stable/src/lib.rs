@@ -3,6 +3,7 @@ pub mod math;
 pub mod state;
 
 mod error;
+mod migration;
 
 mod response;
 
@@ -1,9 +1,10 @@
 use std::convert::TryFrom;
 
 use astroport::U256;
+use cosmwasm_std::{Decimal, Uint128};
 
-pub const N_COINS: u8 = 2;
-const N_COINS_SQUARED: u8 = 4;
+/// The maximum number of assets in a stable pool
+pub const MAX_N_COINS: usize = 5;
 const ITERATIONS: u8 = 32;
 pub const MAX_AMP: u64 = 1_000_000;
 pub const MAX_AMP_CHANGE: u64 = 10;
@@ -13,55 +14,183 @@ pub const AMP_PRECISION: u64 = 100;
 /// ## Description
 /// Calculates swapped amount.
 /// ## Params
-/// * **offer_pool** is the object of type [`u128`].
+/// * **pools** is a slice of [`u128`] items. Sets the pool balances.
 ///
-/// * **ask_pool** is the object of type [`u128`].
+/// * **offer_index** is the object of type [`usize`]. The index of the offer pool.
+///
+/// * **ask_index** is the object of type [`usize`]. The index of the ask pool.
 ///
 /// * **offer_amount** is the object of type [`u128`].
 ///
 /// * **amp** is the object of type [`u64`].
 pub fn calc_ask_amount(
-    offer_pool: u128,
-    ask_pool: u128,
+    pools: &[u128],
+    offer_index: usize,
+    ask_index: usize,
     offer_amount: u128,
     amp: u64,
 ) -> Option<u128> {
-    let leverage = amp.checked_mul(u64::from(N_COINS)).unwrap();
-    let new_offer_pool = offer_pool + offer_amount;
+    let leverage = amp.checked_mul(pools.len() as u64)?;
+
+    let d = compute_d(leverage, pools)?;
 
-    let d = compute_d(leverage, offer_pool, ask_pool).unwrap();
+    let mut new_pools = pools.to_vec();
+    new_pools[offer_index] = pools[offer_index].checked_add(offer_amount)?;
 
-    let new_ask_pool = compute_new_balance(leverage, new_offer_pool, d)?;
+    let new_ask_pool = compute_new_balance(leverage, &new_pools, ask_index, d)?;
 
-    let amount_swapped = ask_pool - new_ask_pool;
-    Some(amount_swapped)
+    pools[ask_index].checked_sub(new_ask_pool)
 }
 
 /// ## Description
 /// Calculates swapped amount.
 /// ## Params
-/// * **offer_pool** is the object of type [`u128`].
+/// * **pools** is a slice of [`u128`] items. Sets the pool balances.
 ///
-/// * **ask_pool** is the object of type [`u128`].
+/// * **offer_index** is the object of type [`usize`]. The index of the offer pool.
+///
+/// * **ask_index** is the object of type [`usize`]. The index of the ask pool.
 ///
 /// * **ask_amount** is the object of type [`u128`].
 ///
 /// * **amp** is the object of type [`u64`].
 pub fn calc_offer_amount(
-    offer_pool: u128,
-    ask_pool: u128,
+    pools: &[u128],
+    offer_index: usize,
+    ask_index: usize,
     ask_amount: u128,
     amp: u64,
 ) -> Option<u128> {
-    let leverage = amp.checked_mul(u64::from(N_COINS)).unwrap();
-    let new_ask_pool = ask_pool - ask_amount;
+    let leverage = amp.checked_mul(pools.len() as u64)?;
+
+    let d = compute_d(leverage, pools)?;
 
-    let d = compute_d(leverage, offer_pool, ask_pool).unwrap();
+    let mut new_pools = pools.to_vec();
+    new_pools[ask_index] = pools[ask_index].checked_sub(ask_amount)?;
 
-    let new_offer_pool = compute_new_balance(leverage, new_ask_pool, d)?;
+    let new_offer_pool = compute_new_balance(leverage, &new_pools, offer_index, d)?;
 
-    let amount_swapped = new_offer_pool - offer_pool;
-    Some(amount_swapped)
+    new_offer_pool.checked_sub(pools[offer_index])
+}
+
+/// ## Description
+/// Calculates the amount of the asset with index `ask_index` returned for burning `lp_amount`
+/// LP tokens. The imbalance fee is charged on the difference to a pro-rata withdrawal.
+/// ## Params
+/// * **pools** is a slice of [`u128`] items. Sets the pool balances.
+///
+/// * **ask_index** is the object of type [`usize`]. The index of the asset to withdraw.
+///
+/// * **lp_amount** is the object of type [`u128`]. The amount of LP tokens to burn.
+///
+/// * **total_share** is the object of type [`u128`]. The total amount of LP tokens.
+///
+/// * **amp** is the object of type [`u64`].
+///
+/// * **fee** is the object of type [`Decimal`]. The pool commission rate.
+pub fn calc_withdraw_one_coin(
+    pools: &[u128],
+    ask_index: usize,
+    lp_amount: u128,
+    total_share: u128,
+    amp: u64,
+    fee: Decimal,
+) -> Option<u128> {
+    let leverage = amp.checked_mul(pools.len() as u64)?;
+
+    let d_before = compute_d(leverage, pools)?;
+    let d_after = d_before.checked_sub(mul_div(lp_amount, d_before, total_share)?)?;
+
+    let new_ask_pool = compute_new_balance(leverage, pools, ask_index, d_after)?;
+
+    let mut reduced_pools = pools.to_vec();
+    for (i, pool) in reduced_pools.iter_mut().enumerate() {
+        let ideal_pool = mul_div(*pool, d_after, d_before)?;
+        let expected_change = if i == ask_index {
+            ideal_pool.checked_sub(new_ask_pool)?
+        } else {
+            pool.checked_sub(ideal_pool)?
+        };
+        *pool = pool.checked_sub(imbalance_fee(expected_change, fee, pools.len())?)?;
+    }
+
+    let new_reduced_ask_pool = compute_new_balance(leverage, &reduced_pools, ask_index, d_after)?;
+
+    // Round down in favour of the pool
+    reduced_pools[ask_index]
+        .checked_sub(new_reduced_ask_pool)?
+        .checked_sub(1)
+}
+
+/// ## Description
+/// Calculates the amount of LP tokens to burn for withdrawing exactly the specified `amounts`.
+/// The imbalance fee is charged on the difference to a pro-rata withdrawal.
+/// ## Params
+/// * **pools** is a slice of [`u128`] items. Sets the pool balances.
+///
+/// * **amounts** is a slice of [`u128`] items. Sets the amounts to withdraw from each pool.
+///
+/// * **total_share** is the object of type [`u128`]. The total amount of LP tokens.
+///
+/// * **amp** is the object of type [`u64`].
+///
+/// * **fee** is the object of type [`Decimal`]. The pool commission rate.
+pub fn calc_withdraw_imbalanced(
+    pools: &[u128],
+    amounts: &[u128],
+    total_share: u128,
+    amp: u64,
+    fee: Decimal,
+) -> Option<u128> {
+    let leverage = amp.checked_mul(pools.len() as u64)?;
+
+    let d_before = compute_d(leverage, pools)?;
+
+    let mut new_pools = pools
+        .iter()
+        .zip(amounts)
+        .map(|(pool, amount)| pool.checked_sub(*amount))
+        .collect::<Option<Vec<u128>>>()?;
+
+    let d_after = compute_d(leverage, &new_pools)?;
+
+    for (pool, new_pool) in pools.iter().zip(new_pools.iter_mut()) {
+        let ideal_pool = mul_div(*pool, d_after, d_before)?;
+        let difference = if ideal_pool > *new_pool {
+            ideal_pool - *new_pool
+        } else {
+            *new_pool - ideal_pool
+        };
+        *new_pool = new_pool.checked_sub(imbalance_fee(difference, fee, pools.len())?)?;
+    }
+
+    let d_after_fee = compute_d(leverage, &new_pools)?;
+
+    // Round up in favour of the pool
+    mul_div(d_before.checked_sub(d_after_fee)?, total_share, d_before)?.checked_add(1)
+}
+
+/// ## Description
+/// Returns the imbalance fee for the specified amount. As in the reference stableswap
+/// implementation it is the commission rate multiplied by `n / (4 * (n - 1))`.
+fn imbalance_fee(amount: u128, fee: Decimal, n_coins: usize) -> Option<u128> {
+    let n_coins = n_coins as u128;
+    Some(
+        (Uint128::new(amount) * fee)
+            .multiply_ratio(n_coins, n_coins.checked_sub(1)?.checked_mul(4)?)
+            .u128(),
+    )
+}
+
+/// ## Description
+/// Returns `a * b / c` calculated with the 256-bit intermediate result.
+fn mul_div(a: u128, b: u128, c: u128) -> Option<u128> {
+    u128::try_from(
+        U256::from(a)
+            .checked_mul(U256::from(b))?
+            .checked_div(U256::from(c))?,
+    )
+    .ok()
 }
 
 /// ## Description
@@ -74,15 +203,17 @@ pub fn calc_offer_amount(
 /// ## Params
 /// * **leverage** is the object of type [`u128`].
 ///
-/// * **amount_a** is the object of type [`u128`].
-///
-/// * **amount_b** is the object of type [`u128`].
-pub fn compute_d(leverage: u64, amount_a: u128, amount_b: u128) -> Option<u128> {
-    let amount_a_times_coins =
-        checked_u8_mul(&U256::from(amount_a), N_COINS)?.checked_add(U256::one())?;
-    let amount_b_times_coins =
-        checked_u8_mul(&U256::from(amount_b), N_COINS)?.checked_add(U256::one())?;
-    let sum_x = amount_a.checked_add(amount_b)?; // sum(x_i), a.k.a S
+/// * **pools** is a slice of [`u128`] items. Sets the pool balances.
+pub fn compute_d(leverage: u64, pools: &[u128]) -> Option<u128> {
+    let n_coins = u8::try_from(pools.len()).ok()?;
+    let pools_times_coins = pools
+        .iter()
+        .map(|pool| checked_u8_mul(&U256::from(*pool), n_coins)?.checked_add(U256::one()))
+        .collect::<Option<Vec<U256>>>()?;
+    // sum(x_i), a.k.a S
+    let sum_x = pools
+        .iter()
+        .try_fold(0u128, |acc, pool| acc.checked_add(*pool))?;
     if sum_x == 0 {
         Some(0)
     } else {
@@ -92,15 +223,12 @@ pub fn compute_d(leverage: u64, amount_a: u128, amount_b: u128) -> Option<u128>
         // Newton's method to approximate D
         for _ in 0..ITERATIONS {
             let mut d_product = d;
-            d_product = d_product
-                .checked_mul(d)?
-                .checked_div(amount_a_times_coins)?;
-            d_product = d_product
-                .checked_mul(d)?
-                .checked_div(amount_b_times_coins)?;
+            for pool_times_coins in &pools_times_coins {
+                d_product = d_product.checked_mul(d)?.checked_div(*pool_times_coins)?;
+            }
             d_previous = d;
             //d = (leverage * sum_x + d_p * n_coins) * d / ((leverage - 1) * d + (n_coins + 1) * d_p);
-            d = calculate_step(&d, leverage, sum_x, &d_product)?;
+            d = calculate_step(&d, leverage, sum_x, &d_product, n_coins)?;
             // Equality with the precision of 1
             if d == d_previous {
                 break;
@@ -116,15 +244,21 @@ pub fn compute_d(leverage: u64, amount_a: u128, amount_b: u128) -> Option<u128>
 /// * **Equation**:
 ///
 /// d = (leverage * sum_x + d_product * n_coins) * initial_d / ((leverage - 1) * initial_d + (n_coins + 1) * d_product)
-fn calculate_step(initial_d: &U256, leverage: u64, sum_x: u128, d_product: &U256) -> Option<U256> {
+fn calculate_step(
+    initial_d: &U256,
+    leverage: u64,
+    sum_x: u128,
+    d_product: &U256,
+    n_coins: u8,
+) -> Option<U256> {
     let leverage_mul = U256::from(leverage).checked_mul(sum_x.into())? / AMP_PRECISION;
-    let d_p_mul = checked_u8_mul(d_product, N_COINS)?;
+    let d_p_mul = checked_u8_mul(d_product, n_coins)?;
 
     let l_val = leverage_mul.checked_add(d_p_mul)?.checked_mul(*initial_d)?;
 
     let leverage_sub =
         initial_d.checked_mul((leverage.checked_sub(AMP_PRECISION)?).into())? / AMP_PRECISION;
-    let n_coins_sum = checked_u8_mul(d_product, N_COINS.checked_add(1)?)?;
+    let n_coins_sum = checked_u8_mul(d_product, n_coins.checked_add(1)?)?;
 
     let r_val = leverage_sub.checked_add(n_coins_sum)?;
 
@@ -132,27 +266,49 @@ fn calculate_step(initial_d: &U256, leverage: u64, sum_x: u128, d_product: &U256
 }
 
 /// ## Description
-/// Compute swap amount `y` in proportion to `x`
+/// Compute the new balance `y` of the pool with index `ask_index` so that the invariant is kept
+/// for the other (already updated) pool balances.
 ///
 /// * **Solve for y**
 ///
 /// y**2 + y * (sum' - (A*n**n - 1) * D / (A * n**n)) = D ** (n + 1) / (n ** (2 * n) * prod' * A)
 ///
 /// y**2 + b*y = c
-fn compute_new_balance(leverage: u64, new_source_amount: u128, d_val: u128) -> Option<u128> {
+///
+/// where sum' and prod' are the sum and the product of all balances except y.
+fn compute_new_balance(
+    leverage: u64,
+    pools: &[u128],
+    ask_index: usize,
+    d_val: u128,
+) -> Option<u128> {
+    let n_coins = u8::try_from(pools.len()).ok()?;
+
     // Upscale to U256
     let leverage: U256 = leverage.into();
-    let new_source_amount: U256 = new_source_amount.into();
     let d_val: U256 = d_val.into();
 
-    // sum' = prod' = x
-    // c =  D ** (n + 1) / (n ** (2 * n) * prod' * A)
-    let c = checked_u8_power(&d_val, N_COINS.checked_add(1)?)?
+    // sum' = sum of x_i for i != ask_index
+    // c = D ** (n + 1) / (n ** (2 * n) * prod' * A)
+    let mut sum = U256::zero();
+    let mut c = d_val;
+    for (i, pool) in pools.iter().enumerate() {
+        if i == ask_index {
+            continue;
+        }
+        let pool = U256::from(*pool);
+        sum = sum.checked_add(pool)?;
+        c = c
+            .checked_mul(d_val)?
+            .checked_div(checked_u8_mul(&pool, n_coins)?)?;
+    }
+    let c = c
+        .checked_mul(d_val)?
         .checked_mul(U256::from(AMP_PRECISION))?
-        .checked_div(checked_u8_mul(&new_source_amount, N_COINS_SQUARED)?.checked_mul(leverage)?)?;
+        .checked_div(checked_u8_mul(&leverage, n_coins)?)?;
 
     // b = sum' - (A*n**n - 1) * D / (A * n**n)
-    let b = new_source_amount.checked_add(
+    let b = sum.checked_add(
         d_val
             .checked_mul(U256::from(AMP_PRECISION))?
             .checked_div(leverage)?,
@@ -1,5 +1,6 @@
-use astroport::asset::PairInfo;
-use cosmwasm_std::{Addr, Uint128};
+use astroport::asset::{AssetInfo, PairInfo};
+use astroport::pair::DynamicFeeParams;
+use cosmwasm_std::{Addr, Decimal, Uint128};
 use cw_storage_plus::Item;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -14,10 +15,11 @@ pub struct Config {
     pub factory_addr: Addr,
     /// The last time block
     pub block_time_last: u64,
-    /// The last cumulative price 0 asset in pool
-    pub price0_cumulative_last: Uint128,
-    /// The last cumulative price 1 asset in pool
-    pub price1_cumulative_last: Uint128,
+    /// The last cumulative prices for every (offer, ask) combination of the pool assets
+    pub cumulative_prices: Vec<(AssetInfo, AssetInfo, Uint128)>,
+    /// The precisions of the pool assets in the `pair_info.asset_infos` order. They are read once
+    /// at instantiation, so changing the native token decimals in the factory doesn't rescale the pool
+    pub precisions: Vec<u8>,
     pub init_amp: u64,
     pub init_amp_time: u64,
     pub next_amp: u64,
@@ -25,3 +27,21 @@ pub struct Config {
 }
 
 pub const CONFIG: Item<Config> = Item::new("config");
+
+/// ## Description
+/// This structure describes the pool prices at the start of the current dynamic fee window.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PriceSnapshot {
+    /// The prices for every (offer, ask) combination of the pool assets
+    pub prices: Vec<(AssetInfo, AssetInfo, Decimal)>,
+    /// The time at which the window started
+    pub block_time: u64,
+}
+
+/// ## Description
+/// Stores the dynamic fee settings. The fee set in the factory is charged if they are not set
+pub const DYNAMIC_FEE_PARAMS: Item<DynamicFeeParams> = Item::new("dynamic_fee_params");
+
+/// ## Description
+/// Stores the pool prices at the start of the current dynamic fee window
+pub const PRICE_SNAPSHOT: Item<PriceSnapshot> = Item::new("price_snapshot");
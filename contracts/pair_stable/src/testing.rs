@@ -1,9 +1,13 @@
 use crate::contract::{
-    accumulate_prices, assert_max_spread, execute, instantiate, query_pair_info, query_pool,
-    query_share, query_simulation, reply,
+    accumulate_prices, assert_max_spread, execute, instantiate, query_config,
+    query_cumulative_prices, query_observe, query_pair_info, query_pool, query_share,
+    query_simulate_provide, query_simulate_withdraw, query_simulation, reply,
 };
 use crate::error::ContractError;
-use crate::math::{calc_ask_amount, calc_offer_amount, AMP_PRECISION};
+use crate::math::{
+    calc_ask_amount, calc_offer_amount, calc_withdraw_imbalanced, calc_withdraw_one_coin,
+    AMP_PRECISION,
+};
 use crate::mock_querier::mock_dependencies;
 
 use crate::response::MsgInstantiateContractResponse;
@@ -11,15 +15,16 @@ use crate::state::Config;
 use astroport::asset::{Asset, AssetInfo, PairInfo};
 
 use astroport::pair::{
-    Cw20HookMsg, ExecuteMsg, InstantiateMsg, PoolResponse, SimulationResponse, StablePoolParams,
+    Cw20HookMsg, DynamicFeeParams, ExecuteMsg, InstantiateMsg, Observation, PoolResponse,
+    SimulationResponse, StablePoolConfig, StablePoolParams, StablePoolUpdateParams, WithdrawType,
     TWAP_PRECISION,
 };
 use astroport::token::InstantiateMsg as TokenInstantiateMsg;
 use cosmwasm_std::testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR};
 use cosmwasm_std::{
-    attr, to_binary, Addr, BankMsg, BlockInfo, Coin, ContractResult, CosmosMsg, Decimal, DepsMut,
-    Env, Reply, ReplyOn, Response, StdError, SubMsg, SubMsgExecutionResponse, Timestamp, Uint128,
-    WasmMsg,
+    attr, from_binary, to_binary, Addr, BankMsg, Binary, BlockInfo, Coin, ContractResult,
+    CosmosMsg, Decimal, DepsMut, Env, Reply, ReplyOn, Response, StdError, SubMsg,
+    SubMsgExecutionResponse, Timestamp, Uint128, WasmMsg,
 };
 use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg, MinterResponse};
 use protobuf::Message;
@@ -56,7 +61,7 @@ fn proper_initialization() {
 
     let msg = InstantiateMsg {
         factory_addr: String::from("factory"),
-        asset_infos: [
+        asset_infos: vec![
             AssetInfo::NativeToken {
                 denom: "uusd".to_string(),
             },
@@ -119,6 +124,323 @@ fn proper_initialization() {
     );
 }
 
+#[test]
+fn multi_asset_pool() {
+    let mut deps = mock_dependencies(&[
+        Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(1_000_000_000000),
+        },
+        Coin {
+            denom: "ukrw".to_string(),
+            amount: Uint128::new(1_000_000_000000),
+        },
+    ]);
+
+    deps.querier.with_token_balances(&[
+        (
+            &String::from("asset0000"),
+            &[(
+                &String::from(MOCK_CONTRACT_ADDR),
+                &Uint128::new(1_000_000_000000),
+            )],
+        ),
+        (
+            &String::from("liquidity0000"),
+            &[(&String::from(MOCK_CONTRACT_ADDR), &Uint128::new(0))],
+        ),
+    ]);
+
+    let asset_infos = vec![
+        AssetInfo::NativeToken {
+            denom: "uusd".to_string(),
+        },
+        AssetInfo::NativeToken {
+            denom: "ukrw".to_string(),
+        },
+        AssetInfo::Token {
+            contract_addr: Addr::unchecked("asset0000"),
+        },
+    ];
+
+    let msg = InstantiateMsg {
+        factory_addr: String::from("factory"),
+        asset_infos: vec![asset_infos[0].clone()],
+        token_code_id: 10u64,
+        init_params: Some(to_binary(&StablePoolParams { amp: 100 }).unwrap()),
+    };
+
+    let env = mock_env();
+    let info = mock_info("addr0000", &[]);
+    let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+    assert_eq!(res, ContractError::InvalidNumberOfAssets {});
+
+    let msg = InstantiateMsg {
+        factory_addr: String::from("factory"),
+        asset_infos: asset_infos.clone(),
+        token_code_id: 10u64,
+        init_params: Some(to_binary(&StablePoolParams { amp: 100 }).unwrap()),
+    };
+
+    instantiate(deps.as_mut(), env.clone(), info, msg).unwrap();
+    store_liquidity_token(deps.as_mut(), 1, "liquidity0000".to_string());
+
+    let pair_info: PairInfo = query_pair_info(deps.as_ref()).unwrap();
+    assert_eq!(pair_info.asset_infos, asset_infos);
+
+    let offer_asset = Asset {
+        info: asset_infos[0].clone(),
+        amount: Uint128::new(1_000000),
+    };
+
+    // The ask asset can't be inferred in a pool with more than two assets
+    let res = query_simulation(deps.as_ref(), env.clone(), offer_asset.clone(), None).unwrap_err();
+    assert_eq!(
+        res,
+        StdError::generic_err(ContractError::VariableAssetMissed {}.to_string())
+    );
+
+    let res = query_simulation(
+        deps.as_ref(),
+        env.clone(),
+        offer_asset.clone(),
+        Some(asset_infos[0].clone()),
+    )
+    .unwrap_err();
+    assert_eq!(
+        res,
+        StdError::generic_err(ContractError::DoublingAssets {}.to_string())
+    );
+
+    let simulation_res: SimulationResponse = query_simulation(
+        deps.as_ref(),
+        env,
+        offer_asset,
+        Some(asset_infos[2].clone()),
+    )
+    .unwrap();
+    assert!(simulation_res.return_amount > Uint128::zero());
+    assert!(simulation_res.return_amount < Uint128::new(1_000000));
+}
+
+#[test]
+fn native_token_decimals() {
+    let mut deps = mock_dependencies(&[
+        Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(1_000_000_000_000),
+        },
+        Coin {
+            denom: "aevmos".to_string(),
+            amount: Uint128::new(1_000_000_000_000_000_000_000_000),
+        },
+    ]);
+    deps.querier.with_token_balances(&[(
+        &String::from("liquidity0000"),
+        &[(&String::from(MOCK_CONTRACT_ADDR), &Uint128::new(0))],
+    )]);
+    deps.querier.with_native_decimals(&[("aevmos", 18)]);
+
+    let asset_infos = vec![
+        AssetInfo::NativeToken {
+            denom: "uusd".to_string(),
+        },
+        AssetInfo::NativeToken {
+            denom: "aevmos".to_string(),
+        },
+    ];
+
+    let msg = InstantiateMsg {
+        factory_addr: String::from("factory"),
+        asset_infos: asset_infos.clone(),
+        token_code_id: 10u64,
+        init_params: Some(to_binary(&StablePoolParams { amp: 100 }).unwrap()),
+    };
+
+    let env = mock_env();
+    instantiate(deps.as_mut(), env.clone(), mock_info("addr0000", &[]), msg).unwrap();
+    store_liquidity_token(deps.as_mut(), 1, "liquidity0000".to_string());
+
+    // The pool is balanced once the amounts are adjusted to 18 decimals,
+    // so 1 UST is swapped for almost 1 EVMOS minus the 0.3% fee
+    let simulation_res: SimulationResponse = query_simulation(
+        deps.as_ref(),
+        env.clone(),
+        Asset {
+            info: asset_infos[0].clone(),
+            amount: Uint128::new(1_000000),
+        },
+        None,
+    )
+    .unwrap();
+    assert!(simulation_res.return_amount > Uint128::new(996_000_000_000_000_000));
+    assert!(simulation_res.return_amount < Uint128::new(997_000_000_000_000_000));
+
+    // Changing the decimals in the factory doesn't rescale an existing pool
+    deps.querier.with_native_decimals(&[("aevmos", 6)]);
+    let res: SimulationResponse = query_simulation(
+        deps.as_ref(),
+        env,
+        Asset {
+            info: asset_infos[0].clone(),
+            amount: Uint128::new(1_000000),
+        },
+        None,
+    )
+    .unwrap();
+    assert_eq!(res, simulation_res);
+}
+
+#[test]
+fn flash_loan() {
+    let mut deps = mock_dependencies(&[
+        Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(1_000_000_000000),
+        },
+        Coin {
+            denom: "ukrw".to_string(),
+            amount: Uint128::new(1_000_000_000000),
+        },
+    ]);
+
+    deps.querier.with_token_balances(&[
+        (
+            &String::from("asset0000"),
+            &[(
+                &String::from(MOCK_CONTRACT_ADDR),
+                &Uint128::new(1_000_000_000000),
+            )],
+        ),
+        (
+            &String::from("liquidity0000"),
+            &[(&String::from("addr0000"), &Uint128::new(3_000_000_000000))],
+        ),
+    ]);
+
+    let asset_infos = vec![
+        AssetInfo::NativeToken {
+            denom: "uusd".to_string(),
+        },
+        AssetInfo::NativeToken {
+            denom: "ukrw".to_string(),
+        },
+        AssetInfo::Token {
+            contract_addr: Addr::unchecked("asset0000"),
+        },
+    ];
+
+    let msg = InstantiateMsg {
+        factory_addr: String::from("factory"),
+        asset_infos: asset_infos.clone(),
+        token_code_id: 10u64,
+        init_params: Some(to_binary(&StablePoolParams { amp: 100 }).unwrap()),
+    };
+
+    let env = mock_env();
+    let info = mock_info("addr0000", &[]);
+    instantiate(deps.as_mut(), env.clone(), info, msg).unwrap();
+    store_liquidity_token(deps.as_mut(), 1, "liquidity0000".to_string());
+
+    let msg = ExecuteMsg::FlashLoan {
+        assets: vec![Asset {
+            info: asset_infos[1].clone(),
+            amount: Uint128::new(500_000_000000),
+        }],
+        callback: Binary::from(b"callback".to_vec()),
+    };
+    let res = execute(deps.as_mut(), env.clone(), mock_info("borrower", &[]), msg).unwrap();
+
+    let repay_msg = ExecuteMsg::AssertFlashLoanRepaid {
+        expected_balances: vec![Asset {
+            info: asset_infos[1].clone(),
+            amount: Uint128::new(1_001_500_000000),
+        }],
+        fees: vec![Asset {
+            info: asset_infos[1].clone(),
+            amount: Uint128::new(1_500_000000),
+        }],
+    };
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(BankMsg::Send {
+                to_address: String::from("borrower"),
+                amount: vec![Coin {
+                    denom: "ukrw".to_string(),
+                    amount: Uint128::new(500_000_000000),
+                }],
+            }),
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: String::from("borrower"),
+                msg: Binary::from(b"callback".to_vec()),
+                funds: vec![],
+            }),
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: String::from(MOCK_CONTRACT_ADDR),
+                msg: to_binary(&repay_msg).unwrap(),
+                funds: vec![],
+            }),
+        ]
+    );
+
+    // Swaps are rejected until the loan is repaid
+    let msg = ExecuteMsg::Swap {
+        offer_asset: Asset {
+            info: asset_infos[0].clone(),
+            amount: Uint128::new(1_000000),
+        },
+        ask_asset_info: Some(asset_infos[1].clone()),
+        belief_price: None,
+        max_spread: None,
+        to: None,
+        minimum_receive: None,
+        deadline: None,
+    };
+    let err = execute(deps.as_mut(), env.clone(), mock_info("borrower", &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::FlashLoanInProgress {});
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        repay_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::FlashLoanNotRepaid {});
+
+    deps.querier.with_balance(&[(
+        &String::from(MOCK_CONTRACT_ADDR),
+        &[
+            Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::new(1_000_000_000000),
+            },
+            Coin {
+                denom: "ukrw".to_string(),
+                amount: Uint128::new(1_001_500_000000),
+            },
+        ],
+    )]);
+    let res = execute(
+        deps.as_mut(),
+        env,
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        repay_msg,
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(BankMsg::Send {
+            to_address: String::from("fee_address"),
+            amount: vec![Coin {
+                denom: "ukrw".to_string(),
+                amount: Uint128::new(249_000000),
+            }],
+        })]
+    );
+}
+
 #[test]
 fn provide_liquidity() {
     let mut deps = mock_dependencies(&[Coin {
@@ -138,7 +460,7 @@ fn provide_liquidity() {
     ]);
 
     let msg = InstantiateMsg {
-        asset_infos: [
+        asset_infos: vec![
             AssetInfo::NativeToken {
                 denom: "uusd".to_string(),
             },
@@ -161,7 +483,7 @@ fn provide_liquidity() {
 
     // successfully provide liquidity for the exist pool
     let msg = ExecuteMsg::ProvideLiquidity {
-        assets: [
+        assets: vec![
             Asset {
                 info: AssetInfo::Token {
                     contract_addr: Addr::unchecked("asset0000"),
@@ -178,6 +500,8 @@ fn provide_liquidity() {
         slippage_tolerance: None,
         auto_stake: None,
         receiver: None,
+        min_lp_to_receive: None,
+        deadline: None,
     };
 
     let env = mock_env();
@@ -256,7 +580,7 @@ fn provide_liquidity() {
     ]);
 
     let msg = ExecuteMsg::ProvideLiquidity {
-        assets: [
+        assets: vec![
             Asset {
                 info: AssetInfo::Token {
                     contract_addr: Addr::unchecked("asset0000"),
@@ -273,6 +597,8 @@ fn provide_liquidity() {
         slippage_tolerance: None,
         auto_stake: None,
         receiver: None,
+        min_lp_to_receive: None,
+        deadline: None,
     };
 
     let env = mock_env_with_block_time(env.block.time.seconds() + 1000);
@@ -327,7 +653,7 @@ fn provide_liquidity() {
 
     // check wrong argument
     let msg = ExecuteMsg::ProvideLiquidity {
-        assets: [
+        assets: vec![
             Asset {
                 info: AssetInfo::Token {
                     contract_addr: Addr::unchecked("asset0000"),
@@ -344,6 +670,8 @@ fn provide_liquidity() {
         slippage_tolerance: None,
         auto_stake: None,
         receiver: None,
+        min_lp_to_receive: None,
+        deadline: None,
     };
 
     let env = mock_env();
@@ -409,7 +737,7 @@ fn provide_liquidity() {
 
     // successfully provides
     let msg = ExecuteMsg::ProvideLiquidity {
-        assets: [
+        assets: vec![
             Asset {
                 info: AssetInfo::Token {
                     contract_addr: Addr::unchecked("asset0000"),
@@ -426,6 +754,8 @@ fn provide_liquidity() {
         slippage_tolerance: Some(Decimal::percent(1)),
         auto_stake: None,
         receiver: None,
+        min_lp_to_receive: None,
+        deadline: None,
     };
 
     let env = mock_env_with_block_time(env.block.time.seconds() + 1000);
@@ -449,7 +779,7 @@ fn provide_liquidity() {
 
     // successfully provides
     let msg = ExecuteMsg::ProvideLiquidity {
-        assets: [
+        assets: vec![
             Asset {
                 info: AssetInfo::Token {
                     contract_addr: Addr::unchecked("asset0000"),
@@ -466,6 +796,8 @@ fn provide_liquidity() {
         slippage_tolerance: Some(Decimal::percent(1)),
         auto_stake: None,
         receiver: None,
+        min_lp_to_receive: None,
+        deadline: None,
     };
 
     let env = mock_env_with_block_time(env.block.time.seconds() + 1000);
@@ -502,7 +834,7 @@ fn withdraw_liquidity() {
     ]);
 
     let msg = InstantiateMsg {
-        asset_infos: [
+        asset_infos: vec![
             AssetInfo::NativeToken {
                 denom: "uusd".to_string(),
             },
@@ -526,7 +858,10 @@ fn withdraw_liquidity() {
     // withdraw liquidity
     let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
         sender: String::from("addr0000"),
-        msg: to_binary(&Cw20HookMsg::WithdrawLiquidity {}).unwrap(),
+        msg: to_binary(&Cw20HookMsg::WithdrawLiquidity {
+            withdraw_type: None,
+        })
+        .unwrap(),
         amount: Uint128::new(100u128),
     });
 
@@ -600,35 +935,35 @@ fn withdraw_liquidity() {
 }
 
 #[test]
-fn try_native_to_token() {
-    let total_share = Uint128::new(30000000000u128);
-    let asset_pool_amount = Uint128::new(20000000000u128);
-    let collateral_pool_amount = Uint128::new(30000000000u128);
-    let offer_amount = Uint128::new(1500000000u128);
-
+fn withdraw_liquidity_imbalanced() {
     let mut deps = mock_dependencies(&[Coin {
         denom: "uusd".to_string(),
-        amount: collateral_pool_amount + offer_amount, /* user deposit must be pre-applied */
+        amount: Uint128::new(1_000_000_000000u128),
     }]);
 
     deps.querier.with_tax(
         Decimal::zero(),
         &[(&"uusd".to_string(), &Uint128::from(1000000u128))],
     );
-
     deps.querier.with_token_balances(&[
         (
             &String::from("liquidity0000"),
-            &[(&String::from(MOCK_CONTRACT_ADDR), &total_share)],
+            &[(
+                &String::from("addr0000"),
+                &Uint128::new(1_000_000_000000u128),
+            )],
         ),
         (
             &String::from("asset0000"),
-            &[(&String::from(MOCK_CONTRACT_ADDR), &asset_pool_amount)],
+            &[(
+                &String::from(MOCK_CONTRACT_ADDR),
+                &Uint128::new(1_000_000_000000u128),
+            )],
         ),
     ]);
 
     let msg = InstantiateMsg {
-        asset_infos: [
+        asset_infos: vec![
             AssetInfo::NativeToken {
                 denom: "uusd".to_string(),
             },
@@ -641,52 +976,372 @@ fn try_native_to_token() {
         init_params: Some(to_binary(&StablePoolParams { amp: 100 }).unwrap()),
     };
 
-    let env = mock_env_with_block_time(100);
+    let env = mock_env();
     let info = mock_info("addr0000", &[]);
-    // we can just call .unwrap() to assert this was a success
     let _res = instantiate(deps.as_mut(), env, info, msg).unwrap();
 
     // store liquidity token
     store_liquidity_token(deps.as_mut(), 1, "liquidity0000".to_string());
 
-    // normal swap
-    let msg = ExecuteMsg::Swap {
-        offer_asset: Asset {
+    let withdraw_type = WithdrawType::Imbalanced {
+        assets: vec![Asset {
             info: AssetInfo::NativeToken {
                 denom: "uusd".to_string(),
             },
-            amount: offer_amount,
-        },
-        belief_price: None,
-        max_spread: Some(Decimal::percent(50)),
-        to: None,
-    };
-    let env = mock_env_with_block_time(1000);
-    let info = mock_info(
-        "addr0000",
-        &[Coin {
-            denom: "uusd".to_string(),
-            amount: offer_amount,
+            amount: Uint128::new(1000_000000u128),
         }],
-    );
+        max_burn_amount: None,
+    };
 
-    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
-    let msg_transfer = res.messages.get(0).expect("no message");
+    let simulation = query_simulate_withdraw(
+        deps.as_ref(),
+        mock_env(),
+        Uint128::new(2000_000000u128),
+        Some(withdraw_type.clone()),
+    )
+    .unwrap();
+    let burn_amount = simulation.burn_amount;
+    // one LP token is backed by two pool tokens
+    assert!(burn_amount > Uint128::new(500_000000u128));
+    assert!(burn_amount < Uint128::new(501_000000u128));
 
-    let model: StableSwapModel = StableSwapModel::new(
-        100,
-        vec![collateral_pool_amount.into(), asset_pool_amount.into()],
-        2,
-    );
+    // max burn amount is lower than the required burn
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: String::from("addr0000"),
+        msg: to_binary(&Cw20HookMsg::WithdrawLiquidity {
+            withdraw_type: Some(WithdrawType::Imbalanced {
+                assets: vec![Asset {
+                    info: AssetInfo::NativeToken {
+                        denom: "uusd".to_string(),
+                    },
+                    amount: Uint128::new(1000_000000u128),
+                }],
+                max_burn_amount: Some(Uint128::new(500_000000u128)),
+            }),
+        })
+        .unwrap(),
+        amount: Uint128::new(2000_000000u128),
+    });
+    let info = mock_info("liquidity0000", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+    assert_eq!(res, ContractError::MaxBurnAssertion {});
 
-    let sim_result = model.sim_exchange(0, 1, offer_amount.into());
+    // unknown asset
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: String::from("addr0000"),
+        msg: to_binary(&Cw20HookMsg::WithdrawLiquidity {
+            withdraw_type: Some(WithdrawType::Imbalanced {
+                assets: vec![Asset {
+                    info: AssetInfo::NativeToken {
+                        denom: "ukrw".to_string(),
+                    },
+                    amount: Uint128::new(1000_000000u128),
+                }],
+                max_burn_amount: None,
+            }),
+        })
+        .unwrap(),
+        amount: Uint128::new(2000_000000u128),
+    });
+    let info = mock_info("liquidity0000", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+    assert_eq!(res, ContractError::AssetMismatch {});
 
-    let expected_ret_amount = Uint128::new(sim_result);
-    let expected_spread_amount = offer_amount.saturating_sub(expected_ret_amount);
-    let expected_commission_amount = expected_ret_amount.multiply_ratio(3u128, 1000u128); // 0.3%
-    let expected_maker_fee_amount = expected_commission_amount.multiply_ratio(166u128, 1000u128);
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: String::from("addr0000"),
+        msg: to_binary(&Cw20HookMsg::WithdrawLiquidity {
+            withdraw_type: Some(withdraw_type),
+        })
+        .unwrap(),
+        amount: Uint128::new(2000_000000u128),
+    });
+    let info = mock_info("liquidity0000", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
 
-    let expected_return_amount = expected_ret_amount
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: String::from("addr0000"),
+                amount: vec![Coin {
+                    denom: "uusd".to_string(),
+                    amount: Uint128::from(1000_000000u128),
+                }],
+            })),
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: String::from("liquidity0000"),
+                msg: to_binary(&Cw20ExecuteMsg::Burn {
+                    amount: burn_amount,
+                })
+                .unwrap(),
+                funds: vec![],
+            }),
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: String::from("liquidity0000"),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: String::from("addr0000"),
+                    amount: Uint128::new(2000_000000u128) - burn_amount,
+                })
+                .unwrap(),
+                funds: vec![],
+            }),
+        ]
+    );
+    assert_eq!(
+        res.attributes.get(2).expect("no log"),
+        &attr("withdrawn_share", burn_amount.to_string())
+    );
+}
+
+#[test]
+fn withdraw_liquidity_single_asset() {
+    let mut deps = mock_dependencies(&[Coin {
+        denom: "uusd".to_string(),
+        amount: Uint128::new(1_000_000_000000u128),
+    }]);
+
+    deps.querier.with_tax(
+        Decimal::zero(),
+        &[(&"uusd".to_string(), &Uint128::from(1000000u128))],
+    );
+    deps.querier.with_token_balances(&[
+        (
+            &String::from("liquidity0000"),
+            &[(
+                &String::from("addr0000"),
+                &Uint128::new(1_000_000_000000u128),
+            )],
+        ),
+        (
+            &String::from("asset0000"),
+            &[(
+                &String::from(MOCK_CONTRACT_ADDR),
+                &Uint128::new(1_000_000_000000u128),
+            )],
+        ),
+    ]);
+
+    let msg = InstantiateMsg {
+        asset_infos: vec![
+            AssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            },
+            AssetInfo::Token {
+                contract_addr: Addr::unchecked("asset0000"),
+            },
+        ],
+        token_code_id: 10u64,
+        factory_addr: String::from("factory"),
+        init_params: Some(to_binary(&StablePoolParams { amp: 100 }).unwrap()),
+    };
+
+    let env = mock_env();
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), env, info, msg).unwrap();
+
+    // store liquidity token
+    store_liquidity_token(deps.as_mut(), 1, "liquidity0000".to_string());
+
+    let withdraw_type = WithdrawType::SingleAsset {
+        asset_info: AssetInfo::Token {
+            contract_addr: Addr::unchecked("asset0000"),
+        },
+        min_amount: Some(Uint128::new(1990_000000u128)),
+    };
+
+    let simulation = query_simulate_withdraw(
+        deps.as_ref(),
+        mock_env(),
+        Uint128::new(1000_000000u128),
+        Some(withdraw_type.clone()),
+    )
+    .unwrap();
+    assert_eq!(simulation.burn_amount, Uint128::new(1000_000000u128));
+    assert_eq!(simulation.assets.len(), 1);
+    let return_amount = simulation.assets[0].amount;
+    // one LP token is backed by two pool tokens
+    assert!(return_amount < Uint128::new(2000_000000u128));
+    assert!(return_amount > Uint128::new(1990_000000u128));
+
+    // The withdrawal fails if less than the minimum amount is returned
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: String::from("addr0000"),
+        msg: to_binary(&Cw20HookMsg::WithdrawLiquidity {
+            withdraw_type: Some(WithdrawType::SingleAsset {
+                asset_info: AssetInfo::Token {
+                    contract_addr: Addr::unchecked("asset0000"),
+                },
+                min_amount: Some(return_amount + Uint128::new(1u128)),
+            }),
+        })
+        .unwrap(),
+        amount: Uint128::new(1000_000000u128),
+    });
+    let info = mock_info("liquidity0000", &[]);
+    let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+    assert_eq!(err, ContractError::MinimumReceiveAssertion {});
+
+    // The whole pool liquidity can't be withdrawn in a single asset
+    let err = query_simulate_withdraw(
+        deps.as_ref(),
+        mock_env(),
+        Uint128::new(1_000_000_000000u128),
+        Some(withdraw_type.clone()),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err(ContractError::WithdrawAmountTooLarge {}.to_string())
+    );
+
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: String::from("addr0000"),
+        msg: to_binary(&Cw20HookMsg::WithdrawLiquidity {
+            withdraw_type: Some(withdraw_type),
+        })
+        .unwrap(),
+        amount: Uint128::new(1000_000000u128),
+    });
+    let info = mock_info("liquidity0000", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: String::from("asset0000"),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: String::from("addr0000"),
+                    amount: return_amount,
+                })
+                .unwrap(),
+                funds: vec![],
+            }),
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: String::from("liquidity0000"),
+                msg: to_binary(&Cw20ExecuteMsg::Burn {
+                    amount: Uint128::new(1000_000000u128),
+                })
+                .unwrap(),
+                funds: vec![],
+            }),
+        ]
+    );
+
+    // a balanced deposit mints the pro-rata share
+    let share = query_simulate_provide(
+        deps.as_ref(),
+        mock_env(),
+        vec![
+            Asset {
+                info: AssetInfo::NativeToken {
+                    denom: "uusd".to_string(),
+                },
+                amount: Uint128::new(1000_000000u128),
+            },
+            Asset {
+                info: AssetInfo::Token {
+                    contract_addr: Addr::unchecked("asset0000"),
+                },
+                amount: Uint128::new(1000_000000u128),
+            },
+        ],
+    )
+    .unwrap();
+    assert_eq!(share, Uint128::new(1000_000000u128));
+}
+
+#[test]
+fn try_native_to_token() {
+    let total_share = Uint128::new(30000000000u128);
+    let asset_pool_amount = Uint128::new(20000000000u128);
+    let collateral_pool_amount = Uint128::new(30000000000u128);
+    let offer_amount = Uint128::new(1500000000u128);
+
+    let mut deps = mock_dependencies(&[Coin {
+        denom: "uusd".to_string(),
+        amount: collateral_pool_amount + offer_amount, /* user deposit must be pre-applied */
+    }]);
+
+    deps.querier.with_tax(
+        Decimal::zero(),
+        &[(&"uusd".to_string(), &Uint128::from(1000000u128))],
+    );
+
+    deps.querier.with_token_balances(&[
+        (
+            &String::from("liquidity0000"),
+            &[(&String::from(MOCK_CONTRACT_ADDR), &total_share)],
+        ),
+        (
+            &String::from("asset0000"),
+            &[(&String::from(MOCK_CONTRACT_ADDR), &asset_pool_amount)],
+        ),
+    ]);
+
+    let msg = InstantiateMsg {
+        asset_infos: vec![
+            AssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            },
+            AssetInfo::Token {
+                contract_addr: Addr::unchecked("asset0000"),
+            },
+        ],
+        token_code_id: 10u64,
+        factory_addr: String::from("factory"),
+        init_params: Some(to_binary(&StablePoolParams { amp: 100 }).unwrap()),
+    };
+
+    let env = mock_env_with_block_time(100);
+    let info = mock_info("addr0000", &[]);
+    // we can just call .unwrap() to assert this was a success
+    let _res = instantiate(deps.as_mut(), env, info, msg).unwrap();
+
+    // store liquidity token
+    store_liquidity_token(deps.as_mut(), 1, "liquidity0000".to_string());
+
+    // normal swap
+    let msg = ExecuteMsg::Swap {
+        offer_asset: Asset {
+            info: AssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            },
+            amount: offer_amount,
+        },
+        ask_asset_info: None,
+        belief_price: None,
+        max_spread: Some(Decimal::percent(50)),
+        to: None,
+        minimum_receive: None,
+        deadline: None,
+    };
+    let env = mock_env_with_block_time(1000);
+    let info = mock_info(
+        "addr0000",
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: offer_amount,
+        }],
+    );
+
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    let msg_transfer = res.messages.get(0).expect("no message");
+
+    let model: StableSwapModel = StableSwapModel::new(
+        100,
+        vec![collateral_pool_amount.into(), asset_pool_amount.into()],
+        2,
+    );
+
+    let sim_result = model.sim_exchange(0, 1, offer_amount.into());
+
+    let expected_ret_amount = Uint128::new(sim_result);
+    let expected_spread_amount = offer_amount.saturating_sub(expected_ret_amount);
+    let expected_commission_amount = expected_ret_amount.multiply_ratio(3u128, 1000u128); // 0.3%
+    let expected_maker_fee_amount = expected_commission_amount.multiply_ratio(166u128, 1000u128);
+
+    let expected_return_amount = expected_ret_amount
         .checked_sub(expected_commission_amount)
         .unwrap();
     let expected_tax_amount = Uint128::zero(); // no tax for token
@@ -709,6 +1364,7 @@ fn try_native_to_token() {
             },
             amount: offer_amount,
         },
+        None,
     )
     .unwrap();
     assert_eq!(expected_return_amount, simulation_res.return_amount);
@@ -729,6 +1385,7 @@ fn try_native_to_token() {
             attr("spread_amount", expected_spread_amount.to_string()),
             attr("commission_amount", expected_commission_amount.to_string()),
             attr("maker_fee_amount", expected_maker_fee_amount.to_string()),
+            attr("fee_rate", "0.003"),
         ]
     );
 
@@ -782,7 +1439,7 @@ fn try_token_to_native() {
     ]);
 
     let msg = InstantiateMsg {
-        asset_infos: [
+        asset_infos: vec![
             AssetInfo::NativeToken {
                 denom: "uusd".to_string(),
             },
@@ -811,9 +1468,12 @@ fn try_token_to_native() {
             },
             amount: offer_amount,
         },
+        ask_asset_info: None,
         belief_price: None,
         max_spread: None,
         to: None,
+        minimum_receive: None,
+        deadline: None,
     };
     let env = mock_env_with_block_time(1000);
     let info = mock_info("addr0000", &[]);
@@ -825,9 +1485,12 @@ fn try_token_to_native() {
         sender: String::from("addr0000"),
         amount: offer_amount,
         msg: to_binary(&Cw20HookMsg::Swap {
+            ask_asset_info: None,
             belief_price: None,
             max_spread: None,
             to: None,
+            minimum_receive: None,
+            deadline: None,
         })
         .unwrap(),
     });
@@ -853,14 +1516,19 @@ fn try_token_to_native() {
     let expected_return_amount = expected_ret_amount
         .checked_sub(expected_commission_amount)
         .unwrap();
-    let expected_tax_amount = std::cmp::min(
-        Uint128::new(1000000u128),
-        expected_return_amount
-            .checked_sub(
-                expected_return_amount.multiply_ratio(Uint128::new(100u128), Uint128::new(101u128)),
-            )
-            .unwrap(),
-    );
+    let expected_tax_amount = if cfg!(feature = "terra") {
+        std::cmp::min(
+            Uint128::new(1000000u128),
+            expected_return_amount
+                .checked_sub(
+                    expected_return_amount
+                        .multiply_ratio(Uint128::new(100u128), Uint128::new(101u128)),
+                )
+                .unwrap(),
+        )
+    } else {
+        Uint128::zero()
+    };
     // check simulation res
     // return asset token balance as normal
     deps.querier.with_token_balances(&[
@@ -883,6 +1551,7 @@ fn try_token_to_native() {
                 contract_addr: Addr::unchecked("asset0000"),
             },
         },
+        None,
     )
     .unwrap();
     assert_eq!(expected_return_amount, simulation_res.return_amount);
@@ -903,6 +1572,7 @@ fn try_token_to_native() {
             attr("spread_amount", expected_spread_amount.to_string()),
             attr("commission_amount", expected_commission_amount.to_string()),
             attr("maker_fee_amount", expected_maker_fee_amount.to_string()),
+            attr("fee_rate", "0.003"),
         ]
     );
 
@@ -930,9 +1600,12 @@ fn try_token_to_native() {
         sender: String::from("addr0000"),
         amount: offer_amount,
         msg: to_binary(&Cw20HookMsg::Swap {
+            ask_asset_info: None,
             belief_price: None,
             max_spread: None,
             to: None,
+            minimum_receive: None,
+            deadline: None,
         })
         .unwrap(),
     });
@@ -981,6 +1654,7 @@ fn test_max_spread() {
     .unwrap();
 }
 
+#[cfg(feature = "terra")]
 #[test]
 fn test_deduct() {
     let mut deps = mock_dependencies(&[]);
@@ -1032,7 +1706,7 @@ fn test_query_pool() {
     ]);
 
     let msg = InstantiateMsg {
-        asset_infos: [
+        asset_infos: vec![
             AssetInfo::NativeToken {
                 denom: "uusd".to_string(),
             },
@@ -1097,7 +1771,7 @@ fn test_query_share() {
     ]);
 
     let msg = InstantiateMsg {
-        asset_infos: [
+        asset_infos: vec![
             AssetInfo::NativeToken {
                 denom: "uusd".to_string(),
             },
@@ -1200,67 +1874,460 @@ fn test_accumulate_prices() {
         let (case, result) = test_case;
 
         let env = mock_env_with_block_time(case.block_time);
-        let config = accumulate_prices(
-            env.clone(),
-            &Config {
-                pair_info: PairInfo {
-                    asset_infos: [
-                        AssetInfo::NativeToken {
-                            denom: "uusd".to_string(),
-                        },
-                        AssetInfo::Token {
-                            contract_addr: Addr::unchecked("asset0000"),
-                        },
-                    ],
-                    contract_addr: Addr::unchecked("pair"),
-                    liquidity_token: Addr::unchecked("lp_token"),
-                    pair_type: PairType::Stable {},
-                },
-                factory_addr: Addr::unchecked("factory"),
-                block_time_last: case.block_time_last,
-                price0_cumulative_last: Uint128::new(case.last0),
-                price1_cumulative_last: Uint128::new(case.last1),
-                init_amp: 100 * AMP_PRECISION,
-                init_amp_time: env.block.time.seconds(),
-                next_amp: 100 * AMP_PRECISION,
-                next_amp_time: env.block.time.seconds(),
+        let asset_x = AssetInfo::NativeToken {
+            denom: "uusd".to_string(),
+        };
+        let asset_y = AssetInfo::Token {
+            contract_addr: Addr::unchecked("asset0000"),
+        };
+        let mut config = Config {
+            pair_info: PairInfo {
+                asset_infos: vec![asset_x.clone(), asset_y.clone()],
+                contract_addr: Addr::unchecked("pair"),
+                liquidity_token: Addr::unchecked("lp_token"),
+                pair_type: PairType::Stable {},
             },
-            Uint128::new(case.x_amount),
-            6,
-            Uint128::new(case.y_amount),
-            6,
-        )
-        .unwrap();
+            factory_addr: Addr::unchecked("factory"),
+            block_time_last: case.block_time_last,
+            cumulative_prices: vec![
+                (asset_x.clone(), asset_y.clone(), Uint128::new(case.last0)),
+                (asset_y.clone(), asset_x.clone(), Uint128::new(case.last1)),
+            ],
+            precisions: vec![6, 6],
+            init_amp: 100 * AMP_PRECISION,
+            init_amp_time: env.block.time.seconds(),
+            next_amp: 100 * AMP_PRECISION,
+            next_amp_time: env.block.time.seconds(),
+        };
+        let pools = vec![
+            Asset {
+                info: asset_x,
+                amount: Uint128::new(case.x_amount),
+            },
+            Asset {
+                info: asset_y,
+                amount: Uint128::new(case.y_amount),
+            },
+        ];
 
-        assert_eq!(result.is_some, config.is_some());
-
-        if let Some(config) = config {
-            assert_eq!(config.2, result.block_time_last);
-            assert_eq!(
-                config.0 / Uint128::from(price_precision),
-                Uint128::new(result.cumulative_price_x)
-            );
-            assert_eq!(
-                config.1 / Uint128::from(price_precision),
-                Uint128::new(result.cumulative_price_y)
-            );
-        }
-    }
-}
+        let is_updated = accumulate_prices(env.clone(), &mut config, &pools, &[6, 6]).unwrap();
 
-fn mock_env_with_block_time(time: u64) -> Env {
-    let mut env = mock_env();
-    env.block = BlockInfo {
-        height: 1,
-        time: Timestamp::from_seconds(time),
-        chain_id: "columbus".to_string(),
-    };
-    env
+        assert_eq!(result.is_some, is_updated);
+        assert_eq!(config.block_time_last, result.block_time_last);
+        assert_eq!(
+            config.cumulative_prices[0].2 / Uint128::from(price_precision),
+            Uint128::new(result.cumulative_price_x)
+        );
+        assert_eq!(
+            config.cumulative_prices[1].2 / Uint128::from(price_precision),
+            Uint128::new(result.cumulative_price_y)
+        );
+    }
 }
 
-use astroport::factory::PairType;
-use proptest::prelude::*;
-use sim::StableSwapModel;
+#[test]
+fn observe() {
+    let offer_amount = Uint128::new(1_000000u128);
+    let mut deps = mock_dependencies(&[Coin {
+        denom: "uusd".to_string(),
+        amount: Uint128::new(100_000_000000u128) + offer_amount,
+    }]);
+
+    deps.querier.with_token_balances(&[
+        (
+            &String::from("liquidity0000"),
+            &[(
+                &String::from(MOCK_CONTRACT_ADDR),
+                &Uint128::new(100_000_000000u128),
+            )],
+        ),
+        (
+            &String::from("asset0000"),
+            &[(
+                &String::from(MOCK_CONTRACT_ADDR),
+                &Uint128::new(200_000_000000u128),
+            )],
+        ),
+    ]);
+
+    let msg = InstantiateMsg {
+        asset_infos: vec![
+            AssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            },
+            AssetInfo::Token {
+                contract_addr: Addr::unchecked("asset0000"),
+            },
+        ],
+        token_code_id: 10u64,
+        factory_addr: String::from("factory"),
+        init_params: Some(to_binary(&StablePoolParams { amp: 100 }).unwrap()),
+    };
+
+    instantiate(
+        deps.as_mut(),
+        mock_env_with_block_time(0),
+        mock_info("addr0000", &[]),
+        msg,
+    )
+    .unwrap();
+    store_liquidity_token(deps.as_mut(), 1, "liquidity0000".to_string());
+
+    // Every swap records an observation
+    for time in [1000u64, 2000u64] {
+        let msg = ExecuteMsg::Swap {
+            offer_asset: Asset {
+                info: AssetInfo::NativeToken {
+                    denom: "uusd".to_string(),
+                },
+                amount: offer_amount,
+            },
+            ask_asset_info: None,
+            belief_price: None,
+            max_spread: Some(Decimal::percent(50)),
+            to: None,
+            minimum_receive: None,
+            deadline: None,
+        };
+        let info = mock_info(
+            "addr0000",
+            &[Coin {
+                denom: "uusd".to_string(),
+                amount: offer_amount,
+            }],
+        );
+        execute(deps.as_mut(), mock_env_with_block_time(time), info, msg).unwrap();
+    }
+
+    // The pool balances don't change between the swaps, so the cumulative prices grow linearly
+    let cumulative_prices =
+        query_cumulative_prices(deps.as_ref(), mock_env_with_block_time(2000)).unwrap();
+    assert!(!cumulative_prices.price0_cumulative_last.is_zero());
+    let observation = |timestamp: u64| Observation {
+        timestamp,
+        price0_cumulative: cumulative_prices
+            .price0_cumulative_last
+            .multiply_ratio(timestamp, 2000u64),
+        price1_cumulative: cumulative_prices
+            .price1_cumulative_last
+            .multiply_ratio(timestamp, 2000u64),
+    };
+
+    let res = query_observe(
+        deps.as_ref(),
+        mock_env_with_block_time(2000),
+        vec![0, 500, 1000],
+    )
+    .unwrap();
+    assert_eq!(
+        res.observations,
+        vec![observation(2000), observation(1500), observation(1000)]
+    );
+
+    // Older than the first observation
+    let err = query_observe(deps.as_ref(), mock_env_with_block_time(2000), vec![1500]).unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("The requested time is older than the oldest observation")
+    );
+}
+
+#[test]
+fn dynamic_fee() {
+    let mut deps = mock_dependencies(&[
+        Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(1_000_000_000000),
+        },
+        Coin {
+            denom: "ukrw".to_string(),
+            amount: Uint128::new(1_000_000_000000),
+        },
+    ]);
+    deps.querier.with_token_balances(&[(
+        &String::from("asset0000"),
+        &[(
+            &String::from(MOCK_CONTRACT_ADDR),
+            &Uint128::new(1_000_000_000000),
+        )],
+    )]);
+
+    let asset_infos = vec![
+        AssetInfo::NativeToken {
+            denom: "uusd".to_string(),
+        },
+        AssetInfo::NativeToken {
+            denom: "ukrw".to_string(),
+        },
+        AssetInfo::Token {
+            contract_addr: Addr::unchecked("asset0000"),
+        },
+    ];
+
+    let msg = InstantiateMsg {
+        factory_addr: String::from("factory"),
+        asset_infos: asset_infos.clone(),
+        token_code_id: 10u64,
+        init_params: Some(to_binary(&StablePoolParams { amp: 100 }).unwrap()),
+    };
+
+    let env = mock_env_with_block_time(1000);
+    instantiate(deps.as_mut(), env.clone(), mock_info("addr0000", &[]), msg).unwrap();
+    store_liquidity_token(deps.as_mut(), 1, "liquidity0000".to_string());
+
+    let params = DynamicFeeParams {
+        min_fee_bps: 10,
+        max_fee_bps: 100,
+        max_price_change: Decimal::percent(1),
+        window: 600,
+    };
+    let msg = ExecuteMsg::UpdateConfig {
+        params: to_binary(&StablePoolUpdateParams::UpdateDynamicFee {
+            params: Some(params.clone()),
+        })
+        .unwrap(),
+    };
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), env.clone(), mock_info("owner", &[]), msg).unwrap();
+
+    let config = query_config(deps.as_ref(), env.clone()).unwrap();
+    let pool_config: StablePoolConfig = from_binary(&config.params.unwrap()).unwrap();
+    assert_eq!(pool_config.dynamic_fee, Some(params));
+
+    // The first swap starts the window
+    deps.querier.with_balance(&[(
+        &String::from(MOCK_CONTRACT_ADDR),
+        &[
+            Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::new(1_000_001_000000),
+            },
+            Coin {
+                denom: "ukrw".to_string(),
+                amount: Uint128::new(1_000_000_000000),
+            },
+        ],
+    )]);
+    let msg = ExecuteMsg::Swap {
+        offer_asset: Asset {
+            info: asset_infos[0].clone(),
+            amount: Uint128::new(1_000000),
+        },
+        ask_asset_info: Some(asset_infos[1].clone()),
+        belief_price: None,
+        max_spread: None,
+        to: None,
+        minimum_receive: None,
+        deadline: None,
+    };
+    let info = mock_info(
+        "addr0000",
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(1_000000),
+        }],
+    );
+    let res = execute(deps.as_mut(), env, info, msg).unwrap();
+    assert!(res.attributes.contains(&attr("fee_rate", "0.001")));
+
+    // The ukrw price moves while the uusd/token price stays the same
+    deps.querier.with_balance(&[(
+        &String::from(MOCK_CONTRACT_ADDR),
+        &[
+            Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::new(1_000_000_000000),
+            },
+            Coin {
+                denom: "ukrw".to_string(),
+                amount: Uint128::new(1_500_000_000000),
+            },
+        ],
+    )]);
+
+    let offer_asset = Asset {
+        info: asset_infos[0].clone(),
+        amount: Uint128::new(1_000000),
+    };
+    let ukrw_fee_rate = query_simulation(
+        deps.as_ref(),
+        mock_env_with_block_time(1300),
+        offer_asset.clone(),
+        Some(asset_infos[1].clone()),
+    )
+    .unwrap()
+    .fee_rate;
+    let token_fee_rate = query_simulation(
+        deps.as_ref(),
+        mock_env_with_block_time(1300),
+        offer_asset.clone(),
+        Some(asset_infos[2].clone()),
+    )
+    .unwrap()
+    .fee_rate;
+    assert!(ukrw_fee_rate > Decimal::permille(1));
+    assert!(ukrw_fee_rate <= Decimal::percent(1));
+    assert!(token_fee_rate < ukrw_fee_rate);
+
+    // A new window starts with the current prices
+    let res = query_simulation(
+        deps.as_ref(),
+        mock_env_with_block_time(1600),
+        offer_asset,
+        Some(asset_infos[1].clone()),
+    )
+    .unwrap();
+    assert_eq!(res.fee_rate, Decimal::permille(1));
+}
+
+#[test]
+fn swap_exact_out() {
+    let mut deps = mock_dependencies(&[
+        Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(1_000_000_000000 + 2_000000),
+        },
+        Coin {
+            denom: "ukrw".to_string(),
+            amount: Uint128::new(1_000_000_000000),
+        },
+    ]);
+    deps.querier.with_token_balances(&[(
+        &String::from("asset0000"),
+        &[(
+            &String::from(MOCK_CONTRACT_ADDR),
+            &Uint128::new(1_000_000_000000),
+        )],
+    )]);
+
+    let msg = InstantiateMsg {
+        factory_addr: String::from("factory"),
+        asset_infos: vec![
+            AssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            },
+            AssetInfo::NativeToken {
+                denom: "ukrw".to_string(),
+            },
+            AssetInfo::Token {
+                contract_addr: Addr::unchecked("asset0000"),
+            },
+        ],
+        token_code_id: 10u64,
+        init_params: Some(to_binary(&StablePoolParams { amp: 100 }).unwrap()),
+    };
+
+    let env = mock_env();
+    instantiate(deps.as_mut(), env.clone(), mock_info("addr0000", &[]), msg).unwrap();
+    store_liquidity_token(deps.as_mut(), 1, "liquidity0000".to_string());
+
+    let ask_asset = Asset {
+        info: AssetInfo::Token {
+            contract_addr: Addr::unchecked("asset0000"),
+        },
+        amount: Uint128::new(1_000000),
+    };
+    let swap_msg = |max_offer_amount: u128| ExecuteMsg::SwapExactOut {
+        ask_asset: ask_asset.clone(),
+        max_offer_amount: Uint128::new(max_offer_amount),
+        to: None,
+    };
+
+    // Exactly one coin must be sent
+    let funds = [
+        Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(2_000000),
+        },
+        Coin {
+            denom: "ukrw".to_string(),
+            amount: Uint128::new(2_000000),
+        },
+    ];
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr0000", &funds),
+        swap_msg(2_000000),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::AssetMismatch {});
+
+    // The offer exceeds the maximum because of the fee
+    let funds = [Coin {
+        denom: "uusd".to_string(),
+        amount: Uint128::new(2_000000),
+    }];
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr0000", &funds),
+        swap_msg(1_000000),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::MaxOfferAmountAssertion {});
+
+    let res = execute(
+        deps.as_mut(),
+        env,
+        mock_info("addr0000", &funds),
+        swap_msg(2_000000),
+    )
+    .unwrap();
+
+    let offer_amount: u128 = res
+        .attributes
+        .iter()
+        .find(|attr| attr.key == "offer_amount")
+        .unwrap()
+        .value
+        .parse()
+        .unwrap();
+    assert!(offer_amount > 1_003000 && offer_amount < 1_004000);
+
+    assert_eq!(
+        res.messages[0..2],
+        [
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: String::from("asset0000"),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: String::from("addr0000"),
+                    amount: ask_asset.amount,
+                })
+                .unwrap(),
+                funds: vec![],
+            }),
+            SubMsg::new(BankMsg::Send {
+                to_address: String::from("addr0000"),
+                amount: vec![Coin {
+                    denom: "uusd".to_string(),
+                    amount: Uint128::new(2_000000 - offer_amount),
+                }],
+            }),
+        ]
+    );
+}
+
+fn mock_env_with_block_time(time: u64) -> Env {
+    let mut env = mock_env();
+    env.block = BlockInfo {
+        height: 1,
+        time: Timestamp::from_seconds(time),
+        chain_id: "columbus".to_string(),
+    };
+    env
+}
+
+use astroport::factory::PairType;
+use proptest::prelude::*;
+use sim::StableSwapModel;
 
 proptest! {
     #[test]
@@ -1279,8 +2346,9 @@ proptest! {
         );
 
         let result = calc_ask_amount(
-            balance_in,
-            balance_out,
+            &[balance_in, balance_out],
+            0,
+            1,
             amount_in,
             amp * AMP_PRECISION
         ).unwrap();
@@ -1302,8 +2370,9 @@ proptest! {
         );
 
         let reverse_result = calc_offer_amount(
-            balance_in,
-            balance_out,
+            &[balance_in, balance_out],
+            0,
+            1,
             result,
             amp * AMP_PRECISION
         ).unwrap();
@@ -1323,4 +2392,151 @@ proptest! {
             reverse_diff
         );
     }
+
+    #[test]
+    fn multi_asset_swap_no_fee(
+        balance_in in 100..1_000_000_000_000_000_000u128,
+        balance_mid in 100..1_000_000_000_000_000_000u128,
+        balance_out in 100..1_000_000_000_000_000_000u128,
+        amount_in in 100..100_000_000_000u128,
+        amp in 1..150u64
+    ) {
+        prop_assume!(amount_in < balance_in);
+
+        let model: StableSwapModel = StableSwapModel::new(
+            amp.into(),
+            vec![balance_in, balance_mid, balance_out],
+            3,
+        );
+
+        let result = calc_ask_amount(
+            &[balance_in, balance_mid, balance_out],
+            0,
+            2,
+            amount_in,
+            amp * AMP_PRECISION
+        ).unwrap();
+
+        let sim_result = model.sim_exchange(0, 2, amount_in);
+
+        let diff = (sim_result as i128 - result as i128).abs();
+
+        // The model stops iterating once consecutive approximations differ by one, which can
+        // leave it two units away from the exact invariant for three imbalanced pools
+        assert!(
+            diff <= 2,
+            "result={}, sim_result={}, amp={}, amount_in={}, balances=[{}, {}, {}], diff={}",
+            result,
+            sim_result,
+            amp,
+            amount_in,
+            balance_in,
+            balance_mid,
+            balance_out,
+            diff
+        );
+    }
+
+    #[test]
+    fn withdraw_one_coin_no_fee(
+        balance_0 in 1_000_000..1_000_000_000_000_000u128,
+        balance_1 in 1_000_000..1_000_000_000_000_000u128,
+        balance_2 in 1_000_000..1_000_000_000_000_000u128,
+        lp_amount in 1..1_000_000u128,
+        amp in 1..150u64
+    ) {
+        // The initial share of the pool is the invariant divided by the number of assets
+        let total_share = StableSwapModel::new(
+            amp.into(),
+            vec![balance_0, balance_1, balance_2],
+            3,
+        ).sim_d() / 3;
+
+        let model: StableSwapModel = StableSwapModel::new_with_pool_tokens(
+            amp.into(),
+            vec![balance_0, balance_1, balance_2],
+            3,
+            total_share,
+        );
+
+        let result = calc_withdraw_one_coin(
+            &[balance_0, balance_1, balance_2],
+            1,
+            lp_amount,
+            total_share,
+            amp * AMP_PRECISION,
+            Decimal::zero(),
+        ).unwrap();
+
+        // The contract rounds the withdrawn amount down by one unit in favour of the pool
+        let sim_result = model.sim_calc_withdraw_one_coin(lp_amount, 1).saturating_sub(1);
+
+        let diff = (sim_result as i128 - result as i128).abs();
+
+        assert!(
+            diff <= 1,
+            "result={}, sim_result={}, amp={}, lp_amount={}, total_share={}, balances=[{}, {}, {}], diff={}",
+            result,
+            sim_result,
+            amp,
+            lp_amount,
+            total_share,
+            balance_0,
+            balance_1,
+            balance_2,
+            diff
+        );
+    }
+
+    #[test]
+    fn withdraw_imbalanced_no_fee(
+        balance_0 in 1_000_000..1_000_000_000_000_000u128,
+        balance_1 in 1_000_000..1_000_000_000_000_000u128,
+        balance_2 in 1_000_000..1_000_000_000_000_000u128,
+        amount_0 in 0..1_000_000u128,
+        amount_2 in 1..1_000_000u128,
+        amp in 1..150u64
+    ) {
+        // The initial share of the pool is the invariant divided by the number of assets
+        let total_share = StableSwapModel::new(
+            amp.into(),
+            vec![balance_0, balance_1, balance_2],
+            3,
+        ).sim_d() / 3;
+
+        let model: StableSwapModel = StableSwapModel::new_with_pool_tokens(
+            amp.into(),
+            vec![balance_0, balance_1, balance_2],
+            3,
+            total_share,
+        );
+
+        let result = calc_withdraw_imbalanced(
+            &[balance_0, balance_1, balance_2],
+            &[amount_0, 0, amount_2],
+            total_share,
+            amp * AMP_PRECISION,
+            Decimal::zero(),
+        ).unwrap();
+
+        // The contract rounds the burn amount up by one unit in favour of the pool
+        let sim_result = model.sim_remove_liquidity_imbalance(vec![amount_0, 0, amount_2]) + 1;
+
+        let diff = (sim_result as i128 - result as i128).abs();
+
+        assert!(
+            diff <= 1,
+            "result={}, sim_result={}, amp={}, amounts=[{}, 0, {}], total_share={}, balances=[{}, {}, {}], diff={}",
+            result,
+            sim_result,
+            amp,
+            amount_0,
+            amount_2,
+            total_share,
+            balance_0,
+            balance_1,
+            balance_2,
+            diff
+        );
+    }
 }
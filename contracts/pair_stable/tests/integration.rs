@@ -1,3 +1,7 @@
+// The Terra treasury tax is queried from the Terra chain, which the multi-test app doesn't
+// mock, so these tests run without the `terra` feature
+#![cfg(not(feature = "terra"))]
+
 use astroport::asset::{Asset, AssetInfo, PairInfo};
 use astroport::factory::{
     ExecuteMsg as FactoryExecuteMsg, InstantiateMsg as FactoryInstantiateMsg, PairConfig, PairType,
@@ -15,27 +19,25 @@ use cosmwasm_std::{
     attr, from_binary, to_binary, Addr, Coin, Decimal, QueryRequest, Uint128, WasmQuery,
 };
 use cw20::{BalanceResponse, Cw20Coin, Cw20ExecuteMsg, Cw20QueryMsg, MinterResponse};
-use terra_multi_test::{AppBuilder, BankKeeper, ContractWrapper, Executor, TerraApp, TerraMock};
+use cw_multi_test::{App, AppBuilder, BankKeeper, ContractWrapper, Executor};
 
 const OWNER: &str = "owner";
 
-fn mock_app() -> TerraApp {
+fn mock_app() -> App {
     let env = mock_env();
     let api = MockApi::default();
     let bank = BankKeeper::new();
     let storage = MockStorage::new();
-    let custom = TerraMock::luna_ust_case();
 
     AppBuilder::new()
         .with_api(api)
         .with_block(env.block)
         .with_bank(bank)
         .with_storage(storage)
-        .with_custom(custom)
         .build()
 }
 
-fn store_token_code(app: &mut TerraApp) -> u64 {
+fn store_token_code(app: &mut App) -> u64 {
     let astro_token_contract = Box::new(ContractWrapper::new_with_empty(
         astroport_token::contract::execute,
         astroport_token::contract::instantiate,
@@ -45,7 +47,7 @@ fn store_token_code(app: &mut TerraApp) -> u64 {
     app.store_code(astro_token_contract)
 }
 
-fn store_pair_code(app: &mut TerraApp) -> u64 {
+fn store_pair_code(app: &mut App) -> u64 {
     let pair_contract = Box::new(
         ContractWrapper::new_with_empty(
             astroport_pair_stable::contract::execute,
@@ -58,7 +60,7 @@ fn store_pair_code(app: &mut TerraApp) -> u64 {
     app.store_code(pair_contract)
 }
 
-fn store_factory_code(app: &mut TerraApp) -> u64 {
+fn store_factory_code(app: &mut App) -> u64 {
     let factory_contract = Box::new(
         ContractWrapper::new_with_empty(
             astroport_factory::contract::execute,
@@ -71,13 +73,39 @@ fn store_factory_code(app: &mut TerraApp) -> u64 {
     app.store_code(factory_contract)
 }
 
-fn instantiate_pair(mut router: &mut TerraApp, owner: &Addr) -> Addr {
+fn instantiate_factory(router: &mut App, owner: &Addr, token_code_id: u64) -> Addr {
+    let factory_code_id = store_factory_code(router);
+
+    let init_msg = FactoryInstantiateMsg {
+        fee_address: None,
+        pair_configs: vec![],
+        token_code_id,
+        generator_address: None,
+        owner: owner.to_string(),
+        whitelist_code_id: 234u64,
+    };
+
+    router
+        .instantiate_contract(
+            factory_code_id,
+            owner.clone(),
+            &init_msg,
+            &[],
+            "FACTORY",
+            None,
+        )
+        .unwrap()
+}
+
+fn instantiate_pair(mut router: &mut App, owner: &Addr) -> Addr {
     let token_contract_code_id = store_token_code(&mut router);
+    // The pair reads the decimals of native tokens from the factory
+    let factory_instance = instantiate_factory(&mut router, owner, token_contract_code_id);
 
     let pair_contract_code_id = store_pair_code(&mut router);
 
     let msg = InstantiateMsg {
-        asset_infos: [
+        asset_infos: vec![
             AssetInfo::NativeToken {
                 denom: "uusd".to_string(),
             },
@@ -86,7 +114,7 @@ fn instantiate_pair(mut router: &mut TerraApp, owner: &Addr) -> Addr {
             },
         ],
         token_code_id: token_contract_code_id,
-        factory_addr: String::from("factory"),
+        factory_addr: factory_instance.to_string(),
         init_params: None,
     };
 
@@ -103,7 +131,7 @@ fn instantiate_pair(mut router: &mut TerraApp, owner: &Addr) -> Addr {
     assert_eq!("You need to provide init params", resp.to_string());
 
     let msg = InstantiateMsg {
-        asset_infos: [
+        asset_infos: vec![
             AssetInfo::NativeToken {
                 denom: "uusd".to_string(),
             },
@@ -112,7 +140,7 @@ fn instantiate_pair(mut router: &mut TerraApp, owner: &Addr) -> Addr {
             },
         ],
         token_code_id: token_contract_code_id,
-        factory_addr: String::from("factory"),
+        factory_addr: factory_instance.to_string(),
         init_params: Some(to_binary(&StablePoolParams { amp: 100 }).unwrap()),
     };
 
@@ -131,8 +159,8 @@ fn instantiate_pair(mut router: &mut TerraApp, owner: &Addr) -> Addr {
         .wrap()
         .query_wasm_smart(pair.clone(), &QueryMsg::Pair {})
         .unwrap();
-    assert_eq!("contract #0", res.contract_addr);
-    assert_eq!("contract #1", res.liquidity_token);
+    assert_eq!("contract #1", res.contract_addr);
+    assert_eq!("contract #2", res.liquidity_token);
 
     pair
 }
@@ -258,7 +286,7 @@ fn provide_liquidity_msg(
     receiver: Option<String>,
 ) -> (ExecuteMsg, [Coin; 2]) {
     let msg = ExecuteMsg::ProvideLiquidity {
-        assets: [
+        assets: vec![
             Asset {
                 info: AssetInfo::NativeToken {
                     denom: "uusd".to_string(),
@@ -275,6 +303,8 @@ fn provide_liquidity_msg(
         slippage_tolerance: None,
         auto_stake: None,
         receiver,
+        min_lp_to_receive: None,
+        deadline: None,
     };
 
     let coins = [
@@ -369,6 +399,8 @@ fn test_compatibility_of_tokens_with_different_precision() {
             total_fee_bps: 0,
             pair_type: PairType::Stable {},
             is_disabled: None,
+            permissionless: true,
+            creation_fee: None,
         }],
         token_code_id,
         generator_address: Some(String::from("generator")),
@@ -389,7 +421,7 @@ fn test_compatibility_of_tokens_with_different_precision() {
 
     let msg = FactoryExecuteMsg::CreatePair {
         pair_type: PairType::Stable {},
-        asset_infos: [
+        asset_infos: vec![
             AssetInfo::Token {
                 contract_addr: token_x_instance.clone(),
             },
@@ -404,7 +436,7 @@ fn test_compatibility_of_tokens_with_different_precision() {
         .unwrap();
 
     let msg = FactoryQueryMsg::Pair {
-        asset_infos: [
+        asset_infos: vec![
             AssetInfo::Token {
                 contract_addr: token_x_instance.clone(),
             },
@@ -412,6 +444,7 @@ fn test_compatibility_of_tokens_with_different_precision() {
                 contract_addr: token_y_instance.clone(),
             },
         ],
+        pair_type: None,
     };
 
     let res: PairInfo = app
@@ -440,7 +473,7 @@ fn test_compatibility_of_tokens_with_different_precision() {
         .unwrap();
 
     let msg = ExecuteMsg::ProvideLiquidity {
-        assets: [
+        assets: vec![
             Asset {
                 info: AssetInfo::Token {
                     contract_addr: token_x_instance.clone(),
@@ -457,6 +490,8 @@ fn test_compatibility_of_tokens_with_different_precision() {
         slippage_tolerance: None,
         auto_stake: None,
         receiver: None,
+        min_lp_to_receive: None,
+        deadline: None,
     };
 
     app.execute_contract(owner.clone(), pair_instance.clone(), &msg, &[])
@@ -467,9 +502,12 @@ fn test_compatibility_of_tokens_with_different_precision() {
     let msg = Cw20ExecuteMsg::Send {
         contract: pair_instance.to_string(),
         msg: to_binary(&Cw20HookMsg::Swap {
+            ask_asset_info: None,
             belief_price: None,
             max_spread: None,
             to: Some(user.to_string()),
+            minimum_receive: None,
+            deadline: None,
         })
         .unwrap(),
         amount: x_offer,
@@ -575,7 +613,7 @@ fn create_pair_with_same_assets() {
     let pair_contract_code_id = store_pair_code(&mut router);
 
     let msg = InstantiateMsg {
-        asset_infos: [
+        asset_infos: vec![
             AssetInfo::NativeToken {
                 denom: "uusd".to_string(),
             },
@@ -633,7 +671,7 @@ fn update_pair_config() {
         .unwrap();
 
     let msg = InstantiateMsg {
-        asset_infos: [
+        asset_infos: vec![
             AssetInfo::NativeToken {
                 denom: "uusd".to_string(),
             },
@@ -5,8 +5,8 @@ use cosmwasm_schema::{export_schema_with_title, remove_schemas, schema_for};
 
 use astroport::asset::PairInfo;
 use astroport::pair::{
-    CumulativePricesResponse, Cw20HookMsg, InstantiateMsg, PoolResponse, ReverseSimulationResponse,
-    SimulationResponse,
+    CumulativePricesResponse, Cw20HookMsg, InstantiateMsg, ObserveResponse, PoolResponse,
+    ProvideResponse, ReverseSimulationResponse, SimulationResponse, SwapResponse, WithdrawResponse,
 };
 use astroport::pair_stable_bluna::{ExecuteMsg, MigrateMsg, QueryMsg};
 
@@ -38,4 +38,8 @@ fn main() {
         &out_dir,
         "CumulativePricesResponse",
     );
+    export_schema_with_title(&schema_for!(SwapResponse), &out_dir, "SwapResponse");
+    export_schema_with_title(&schema_for!(ProvideResponse), &out_dir, "ProvideResponse");
+    export_schema_with_title(&schema_for!(WithdrawResponse), &out_dir, "WithdrawResponse");
+    export_schema_with_title(&schema_for!(ObserveResponse), &out_dir, "ObserveResponse");
 }
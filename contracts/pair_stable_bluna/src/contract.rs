@@ -10,21 +10,24 @@ use crate::state::{
 use cosmwasm_bignumber::Decimal256;
 use cosmwasm_std::{
     attr, entry_point, from_binary, to_binary, Addr, Binary, Coin, CosmosMsg, Decimal, Deps,
-    DepsMut, Env, MessageInfo, Reply, ReplyOn, Response, StdError, StdResult, SubMsg, Uint128,
-    Uint256, WasmMsg,
+    DepsMut, Env, MessageInfo, QuerierWrapper, Reply, ReplyOn, Response, StdError, StdResult,
+    Storage, SubMsg, Uint128, Uint256, WasmMsg,
 };
 
 use crate::response::MsgInstantiateContractResponse;
 use astroport::asset::{addr_validate_to_lower, format_lp_token_name, Asset, AssetInfo, PairInfo};
 use astroport::factory::PairType;
+use astroport::flash_loan;
 
 use astroport::generator::{
     Cw20HookMsg as GeneratorHookMsg, PoolInfoResponse, QueryMsg as GeneratorQueryMsg,
 };
+use astroport::observation::{observe, store_observation};
+use astroport::pair::assert_deadline;
 use astroport::pair::{
-    ConfigResponse, CumulativePricesResponse, Cw20HookMsg, InstantiateMsg, PoolResponse,
-    ReverseSimulationResponse, SimulationResponse, DEFAULT_SLIPPAGE, MAX_ALLOWED_SLIPPAGE,
-    TWAP_PRECISION,
+    ConfigResponse, CumulativePricesResponse, Cw20HookMsg, InstantiateMsg, Observation,
+    ObserveResponse, PoolResponse, ProvideResponse, ReverseSimulationResponse, SimulationResponse,
+    SwapResponse, WithdrawResponse, DEFAULT_SLIPPAGE, MAX_ALLOWED_SLIPPAGE, TWAP_PRECISION,
 };
 use astroport::pair_stable_bluna::{
     ExecuteMsg, MigrateMsg, QueryMsg, StablePoolConfig, StablePoolParams, StablePoolUpdateParams,
@@ -32,7 +35,8 @@ use astroport::pair_stable_bluna::{
 use astroport::whitelist::InstantiateMsg as WhitelistInstantiateMsg;
 
 use astroport::querier::{
-    query_factory_config, query_fee_info, query_supply, query_token_precision,
+    assert_not_paused, query_factory_config, query_fee_info, query_pair_paused, query_supply,
+    query_token_precision,
 };
 use astroport::{token::InstantiateMsg as TokenInstantiateMsg, U256};
 use cw2::{get_contract_version, set_contract_version};
@@ -70,6 +74,10 @@ pub fn instantiate(
     _info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
+    if msg.asset_infos.len() != 2 {
+        return Err(ContractError::InvalidNumberOfAssets {});
+    }
+
     msg.asset_infos[0].check(deps.api)?;
     msg.asset_infos[1].check(deps.api)?;
 
@@ -95,6 +103,9 @@ pub fn instantiate(
 
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
+    let factory_addr = addr_validate_to_lower(deps.api, msg.factory_addr.as_str())?;
+    let precisions = query_precisions(&deps.querier, &msg.asset_infos, &factory_addr)?;
+
     let config = Config {
         pair_info: PairInfo {
             contract_addr: env.contract.address.clone(),
@@ -102,10 +113,11 @@ pub fn instantiate(
             asset_infos: msg.asset_infos.clone(),
             pair_type: PairType::Stable {},
         },
-        factory_addr: addr_validate_to_lower(deps.api, msg.factory_addr.as_str())?,
+        factory_addr,
         block_time_last: 0,
         price0_cumulative_last: Uint128::zero(),
         price1_cumulative_last: Uint128::zero(),
+        precisions,
         init_amp: params.amp * AMP_PRECISION,
         init_amp_time: env.block.time.seconds(),
         next_amp: params.amp * AMP_PRECISION,
@@ -214,6 +226,8 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractE
 ///             slippage_tolerance,
 ///             auto_stake,
 ///             receiver,
+///             min_lp_to_receive,
+///             deadline,
 ///         }** Provides liquidity with the specified input parameters.
 ///
 /// * **ExecuteMsg::Swap {
@@ -221,6 +235,8 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractE
 ///             belief_price,
 ///             max_spread,
 ///             to,
+///             minimum_receive,
+///             deadline,
 ///         }** Performs an swap operation with the specified parameters.
 ///
 /// * **ExecuteMsg::ClaimReward {
@@ -235,6 +251,14 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractE
 ///             total_share,
 ///             user,
 ///         }** Handles and distributes reward
+///
+/// * **ExecuteMsg::FlashLoan { assets, callback }** Lends the pool assets to the sender within
+/// the transaction.
+///
+/// * **ExecuteMsg::AssertFlashLoanRepaid {
+///             expected_balances,
+///             fees,
+///         }** Checks that a flash loan was repaid. Only the pair itself can execute it.
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
@@ -242,14 +266,27 @@ pub fn execute(
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
+    if flash_loan::is_flash_loan_locked(deps.storage)?
+        && !matches!(msg, ExecuteMsg::AssertFlashLoanRepaid { .. })
+    {
+        return Err(ContractError::FlashLoanInProgress {});
+    }
+
     match msg {
         ExecuteMsg::UpdateConfig { params } => update_config(deps, env, info, params),
         ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
+        ExecuteMsg::FlashLoan { assets, callback } => flash_loan(deps, env, info, assets, callback),
+        ExecuteMsg::AssertFlashLoanRepaid {
+            expected_balances,
+            fees,
+        } => assert_flash_loan_repaid(deps, env, info, expected_balances, fees),
         ExecuteMsg::ProvideLiquidity {
             assets,
             slippage_tolerance,
             auto_stake,
             receiver,
+            min_lp_to_receive,
+            deadline,
         } => provide_liquidity(
             deps,
             env,
@@ -258,12 +295,16 @@ pub fn execute(
             slippage_tolerance,
             auto_stake,
             receiver,
+            min_lp_to_receive,
+            deadline,
         ),
         ExecuteMsg::Swap {
             offer_asset,
             belief_price,
             max_spread,
             to,
+            minimum_receive,
+            deadline,
         } => {
             offer_asset.info.check(deps.api)?;
             if !offer_asset.is_native_token() {
@@ -285,6 +326,8 @@ pub fn execute(
                 belief_price,
                 max_spread,
                 to_addr,
+                minimum_receive,
+                deadline,
             )
         }
         ExecuteMsg::ClaimReward { receiver } => claim_reward(deps, env, info, receiver),
@@ -333,9 +376,12 @@ pub fn receive_cw20(
     let contract_addr = info.sender.clone();
     match from_binary(&cw20_msg.msg) {
         Ok(Cw20HookMsg::Swap {
+            ask_asset_info: _,
             belief_price,
             max_spread,
             to,
+            minimum_receive,
+            deadline,
         }) => {
             // only asset contract can execute this message
             let mut authorized: bool = false;
@@ -371,15 +417,21 @@ pub fn receive_cw20(
                 belief_price,
                 max_spread,
                 to_addr,
+                minimum_receive,
+                deadline,
             )
         }
-        Ok(Cw20HookMsg::WithdrawLiquidity {}) => withdraw_liquidity(
+        Ok(Cw20HookMsg::WithdrawLiquidity {
+            withdraw_type: None,
+        }) => withdraw_liquidity(
             deps,
             env,
             info,
             Addr::unchecked(cw20_msg.sender),
             cw20_msg.amount,
         ),
+        Ok(Cw20HookMsg::WithdrawLiquidity { .. }) => Err(ContractError::NonSupported {}),
+        Ok(Cw20HookMsg::SwapExactOut { .. }) => Err(ContractError::NonSupported {}),
         Err(err) => Err(ContractError::Std(err)),
     }
 }
@@ -401,6 +453,11 @@ pub fn receive_cw20(
 /// * **auto_stake** is object of type [`Option<bool>`]. Determines whether an autostake will be performed on the generator.
 ///
 /// * **receiver** is object of type [`Option<String>`]. Sets the receiver of liquidity.
+///
+/// * **min_lp_to_receive** is object of type [`Option<Uint128>`]. Sets the minimum amount of LP tokens to mint.
+///
+/// * **deadline** is object of type [`Option<u64>`]. Sets the block time after which the provision fails.
+#[allow(clippy::too_many_arguments)]
 pub fn provide_liquidity(
     deps: DepsMut,
     env: Env,
@@ -409,7 +466,11 @@ pub fn provide_liquidity(
     slippage_tolerance: Option<Decimal>,
     auto_stake: Option<bool>,
     receiver: Option<String>,
+    min_lp_to_receive: Option<Uint128>,
+    deadline: Option<u64>,
 ) -> Result<Response, ContractError> {
+    assert_deadline(&env, deadline)?;
+
     assets[0].info.check(deps.api)?;
     assets[1].info.check(deps.api)?;
 
@@ -419,7 +480,13 @@ pub fn provide_liquidity(
     }
 
     let mut config: Config = CONFIG.load(deps.storage)?;
-    let mut pools: [Asset; 2] = config
+    assert_not_paused(
+        &deps.querier,
+        config.factory_addr.clone(),
+        env.contract.address.clone(),
+    )?;
+
+    let mut pools: Vec<Asset> = config
         .pair_info
         .query_pools(&deps.querier, env.contract.address.clone())?;
     let deposits: [Uint128; 2] = [
@@ -462,8 +529,8 @@ pub fn provide_liquidity(
     // assert slippage tolerance
     assert_slippage_tolerance(&slippage_tolerance, &deposits, &pools)?;
 
-    let token_precision_0 = query_token_precision(&deps.querier, pools[0].info.clone())?;
-    let token_precision_1 = query_token_precision(&deps.querier, pools[1].info.clone())?;
+    let token_precision_0 = config.precisions[0];
+    let token_precision_1 = config.precisions[1];
 
     let greater_precision = token_precision_0.max(token_precision_1);
 
@@ -477,6 +544,7 @@ pub fn provide_liquidity(
             AssetInfo::Token {
                 contract_addr: config.pair_info.liquidity_token.clone(),
             },
+            &config.factory_addr,
         )?;
 
         // Initial share = collateral amount
@@ -523,6 +591,10 @@ pub fn provide_liquidity(
         return Err(ContractError::LiquidityAmountTooSmall {});
     }
 
+    if share < min_lp_to_receive.unwrap_or_default() {
+        return Err(ContractError::MinLpToReceiveAssertion {});
+    }
+
     // mint LP token for sender or receiver if set
     let receiver = receiver.unwrap_or_else(|| info.sender.to_string());
     messages.extend(mint_liquidity_token_message(
@@ -535,27 +607,29 @@ pub fn provide_liquidity(
     )?);
 
     // Accumulate prices for oracle
-    if let Some((price0_cumulative_new, price1_cumulative_new, block_time)) = accumulate_prices(
+    let prices = accumulate_prices(
         env,
         &config,
         pools[0].amount,
         token_precision_0,
         pools[1].amount,
         token_precision_1,
-    )? {
-        config.price0_cumulative_last = price0_cumulative_new;
-        config.price1_cumulative_last = price1_cumulative_new;
-        config.block_time_last = block_time;
-        CONFIG.save(deps.storage, &config)?;
-    }
+    )?;
+    save_cumulative_prices(deps.storage, &mut config, prices)?;
 
-    Ok(Response::new().add_messages(messages).add_attributes(vec![
-        attr("action", "provide_liquidity"),
-        attr("sender", info.sender.as_str()),
-        attr("receiver", receiver.as_str()),
-        attr("assets", format!("{}, {}", assets[0], assets[1])),
-        attr("share", share.to_string()),
-    ]))
+    Ok(Response::new()
+        .add_messages(messages)
+        .set_data(to_binary(&ProvideResponse {
+            assets: assets.to_vec(),
+            share,
+        })?)
+        .add_attributes(vec![
+            attr("action", "provide_liquidity"),
+            attr("sender", info.sender.as_str()),
+            attr("receiver", receiver.as_str()),
+            attr("assets", format!("{}, {}", assets[0], assets[1])),
+            attr("share", share.to_string()),
+        ]))
 }
 
 /// # Description
@@ -653,19 +727,15 @@ pub fn withdraw_liquidity(
     let refund_assets = get_share_in_assets(&pools, amount, total_share);
 
     // Accumulate prices for oracle
-    if let Some((price0_cumulative_new, price1_cumulative_new, block_time)) = accumulate_prices(
+    let prices = accumulate_prices(
         env,
         &config,
         pools[0].amount,
-        query_token_precision(&deps.querier, pools[0].info.clone())?,
+        config.precisions[0],
         pools[1].amount,
-        query_token_precision(&deps.querier, pools[1].info.clone())?,
-    )? {
-        config.price0_cumulative_last = price0_cumulative_new;
-        config.price1_cumulative_last = price1_cumulative_new;
-        config.block_time_last = block_time;
-        CONFIG.save(deps.storage, &config)?;
-    }
+        config.precisions[1],
+    )?;
+    save_cumulative_prices(deps.storage, &mut config, prices)?;
 
     let messages: Vec<CosmosMsg> = vec![
         refund_assets[0]
@@ -693,6 +763,10 @@ pub fn withdraw_liquidity(
 
     Ok(Response::new()
         .add_messages(messages)
+        .set_data(to_binary(&WithdrawResponse {
+            refund_assets: refund_assets.to_vec(),
+            withdrawn_share: amount,
+        })?)
         .add_attributes(attributes))
 }
 
@@ -704,11 +778,7 @@ pub fn withdraw_liquidity(
 /// * **amount** is the object of type [`Uint128`].
 ///
 /// * **total_share** is the object of type [`Uint128`].
-pub fn get_share_in_assets(
-    pools: &[Asset; 2],
-    amount: Uint128,
-    total_share: Uint128,
-) -> [Asset; 2] {
+pub fn get_share_in_assets(pools: &[Asset], amount: Uint128, total_share: Uint128) -> [Asset; 2] {
     let mut share_ratio = Decimal::zero();
     if !total_share.is_zero() {
         share_ratio = Decimal::from_ratio(amount, total_share);
@@ -746,6 +816,11 @@ pub fn get_share_in_assets(
 /// * **max_spread** is the object of type [`Option<Decimal>`]. Sets the maximum spread of the swap operation.
 ///
 /// * **to** is the object of type [`Option<Addr>`]. Sets the recipient of the swap operation.
+///
+/// * **minimum_receive** is the object of type [`Option<Uint128>`]. Sets the minimum amount of the
+/// ask asset to receive.
+///
+/// * **deadline** is the object of type [`Option<u64>`]. Sets the block time after which the swap fails.
 #[allow(clippy::too_many_arguments)]
 pub fn swap(
     deps: DepsMut,
@@ -756,10 +831,18 @@ pub fn swap(
     belief_price: Option<Decimal>,
     max_spread: Option<Decimal>,
     to: Option<Addr>,
+    minimum_receive: Option<Uint128>,
+    deadline: Option<u64>,
 ) -> Result<Response, ContractError> {
+    assert_deadline(&env, deadline)?;
     offer_asset.assert_sent_native_token_balance(&info)?;
 
     let mut config: Config = CONFIG.load(deps.storage)?;
+    assert_not_paused(
+        &deps.querier,
+        config.factory_addr.clone(),
+        env.contract.address.clone(),
+    )?;
 
     // If the asset balance is already increased
     // To calculated properly we should subtract user deposit from the pool
@@ -795,14 +878,15 @@ pub fn swap(
         &deps.querier,
         config.factory_addr.clone(),
         config.pair_info.pair_type.clone(),
+        Some(config.pair_info.contract_addr.clone()),
     )?;
 
     let offer_amount = offer_asset.amount;
     let (return_amount, spread_amount, commission_amount) = compute_swap(
         offer_pool.amount,
-        query_token_precision(&deps.querier, offer_pool.info)?,
+        get_precision(&config, &offer_pool.info)?,
         ask_pool.amount,
-        query_token_precision(&deps.querier, ask_pool.info.clone())?,
+        get_precision(&config, &ask_pool.info)?,
         offer_amount,
         fee_info.total_fee_rate,
         compute_current_amp(&config, &env)?,
@@ -824,6 +908,9 @@ pub fn swap(
     };
 
     let tax_amount = return_asset.compute_tax(&deps.querier)?;
+    if return_amount.checked_sub(tax_amount)? < minimum_receive.unwrap_or_default() {
+        return Err(ContractError::MinimumReceiveAssertion {});
+    }
 
     let receiver = to.unwrap_or_else(|| sender.clone());
 
@@ -844,19 +931,15 @@ pub fn swap(
     }
 
     // Accumulate prices for oracle
-    if let Some((price0_cumulative_new, price1_cumulative_new, block_time)) = accumulate_prices(
+    let prices = accumulate_prices(
         env,
         &config,
         pools[0].amount,
-        query_token_precision(&deps.querier, pools[0].info.clone())?,
+        config.precisions[0],
         pools[1].amount,
-        query_token_precision(&deps.querier, pools[1].info.clone())?,
-    )? {
-        config.price0_cumulative_last = price0_cumulative_new;
-        config.price1_cumulative_last = price1_cumulative_new;
-        config.block_time_last = block_time;
-        CONFIG.save(deps.storage, &config)?;
-    }
+        config.precisions[1],
+    )?;
+    save_cumulative_prices(deps.storage, &mut config, prices)?;
 
     Ok(Response::new()
         .add_messages(
@@ -864,6 +947,17 @@ pub fn swap(
             // 2. send inactive commission to collector
             messages,
         )
+        .set_data(to_binary(&SwapResponse {
+            offer_asset: offer_asset.clone(),
+            return_asset: Asset {
+                info: ask_pool.info.clone(),
+                amount: return_amount,
+            },
+            tax_amount,
+            spread_amount,
+            commission_amount,
+            maker_fee_amount,
+        })?)
         .add_attribute("action", "swap")
         .add_attribute("sender", sender.as_str())
         .add_attribute("receiver", receiver.as_str())
@@ -877,6 +971,106 @@ pub fn swap(
         .add_attribute("maker_fee_amount", maker_fee_amount.to_string()))
 }
 
+/// ## Description
+/// Lends the specified pool assets to the sender within the transaction. Sends the assets to the
+/// sender, executes the `callback` message on the sender contract and then the
+/// `AssertFlashLoanRepaid` callback which checks that the loan was repaid with the fee.
+/// The pair is locked until the loan is repaid.
+/// Returns an [`ContractError`] on failure, otherwise returns the [`Response`] with the specified attributes if the operation was successful.
+/// ## Params
+/// * **deps** is the object of type [`DepsMut`].
+///
+/// * **env** is the object of type [`Env`].
+///
+/// * **info** is the object of type [`MessageInfo`].
+///
+/// * **assets** is a vector of [`Asset`] type items. Sets the assets to borrow.
+///
+/// * **callback** is the object of type [`Binary`]. Sets the message executed on the borrower contract.
+pub fn flash_loan(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    assets: Vec<Asset>,
+    callback: Binary,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    assert_not_paused(
+        &deps.querier,
+        config.factory_addr.clone(),
+        env.contract.address.clone(),
+    )?;
+
+    let pools: Vec<Asset> = config
+        .pair_info
+        .query_pools(&deps.querier, env.contract.address.clone())?;
+
+    // Get fee info from factory
+    let fee_info = query_fee_info(
+        &deps.querier,
+        config.factory_addr.clone(),
+        config.pair_info.pair_type.clone(),
+        Some(config.pair_info.contract_addr.clone()),
+    )?;
+
+    Ok(flash_loan::flash_loan(
+        deps,
+        &env,
+        &info,
+        &pools,
+        assets,
+        &fee_info,
+        callback,
+        |expected_balances, fees| {
+            to_binary(&ExecuteMsg::AssertFlashLoanRepaid {
+                expected_balances,
+                fees,
+            })
+        },
+    )?)
+}
+
+/// ## Description
+/// Checks that the pool balances reached the expected ones, sends the Maker its share of the
+/// flash loan fees and unlocks the pair. Only the pair itself can execute it.
+/// Returns an [`ContractError`] on failure, otherwise returns the [`Response`] with the specified attributes if the operation was successful.
+/// ## Params
+/// * **deps** is the object of type [`DepsMut`].
+///
+/// * **env** is the object of type [`Env`].
+///
+/// * **info** is the object of type [`MessageInfo`].
+///
+/// * **expected_balances** is a vector of [`Asset`] type items. Sets the pool balances required once the loan is repaid.
+///
+/// * **fees** is a vector of [`Asset`] type items. Sets the flash loan fees.
+pub fn assert_flash_loan_repaid(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    expected_balances: Vec<Asset>,
+    fees: Vec<Asset>,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // Get fee info from factory
+    let fee_info = query_fee_info(
+        &deps.querier,
+        config.factory_addr.clone(),
+        config.pair_info.pair_type.clone(),
+        Some(config.pair_info.contract_addr.clone()),
+    )?;
+
+    Ok(flash_loan::assert_flash_loan_repaid(
+        deps,
+        &env,
+        &info,
+        expected_balances,
+        fees,
+        &fee_info,
+    )?)
+}
+
 /// ## Description
 /// Shifts block_time when any price is zero to not fill an accumulator with a new price to that period.
 /// ## Params
@@ -944,6 +1138,39 @@ pub fn accumulate_prices(
     Ok(Some((pcl0, pcl1, block_time)))
 }
 
+/// ## Description
+/// Saves the cumulative prices returned by [`accumulate_prices`] in the config and records them as a
+/// new price observation. Does nothing if the prices were already accumulated in the current block.
+/// ## Params
+/// * **storage** is the object of type [`Storage`].
+///
+/// * **config** is the object of type [`Config`]. This is the pair config to update.
+///
+/// * **prices** is an [`Option`] with the cumulative prices and the block time returned by
+/// [`accumulate_prices`].
+pub fn save_cumulative_prices(
+    storage: &mut dyn Storage,
+    config: &mut Config,
+    prices: Option<(Uint128, Uint128, u64)>,
+) -> StdResult<()> {
+    if let Some((price0_cumulative_last, price1_cumulative_last, block_time)) = prices {
+        config.price0_cumulative_last = price0_cumulative_last;
+        config.price1_cumulative_last = price1_cumulative_last;
+        config.block_time_last = block_time;
+        CONFIG.save(storage, config)?;
+        store_observation(
+            storage,
+            &Observation {
+                timestamp: block_time,
+                price0_cumulative: price0_cumulative_last,
+                price1_cumulative: price1_cumulative_last,
+            },
+        )?;
+    }
+
+    Ok(())
+}
+
 /// ## Description
 /// Calculates the maker commission according to the specified parameters.
 /// Returns an [`None`] if maker fee is zero, otherwise returns the [`Asset`] with the specified attributes.
@@ -998,6 +1225,9 @@ pub fn calculate_maker_fee(
 /// * **QueryMsg::Config {}** Returns information about the controls settings in a
 /// [`ConfigResponse`] object.
 /// * **QueryMsg::PendingReward {}** Returns pending reward amount for a user in a [`Asset`] object.
+///
+/// * **QueryMsg::Observe { seconds_ago }** Returns the cumulative prices at each of the specified
+/// number of seconds before the current block in an [`ObserveResponse`] object.
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
@@ -1013,6 +1243,7 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::CumulativePrices {} => to_binary(&query_cumulative_prices(deps, env)?),
         QueryMsg::Config {} => to_binary(&query_config(deps, env)?),
         QueryMsg::PendingReward { user } => to_binary(&query_pending_reward(deps, env, user)?),
+        QueryMsg::Observe { seconds_ago } => to_binary(&query_observe(deps, env, seconds_ago)?),
     }
 }
 
@@ -1065,7 +1296,7 @@ pub fn query_simulation(deps: Deps, env: Env, offer_asset: Asset) -> StdResult<S
     let config: Config = CONFIG.load(deps.storage)?;
     let contract_addr = config.pair_info.contract_addr.clone();
 
-    let pools: [Asset; 2] = config.pair_info.query_pools(&deps.querier, contract_addr)?;
+    let pools: Vec<Asset> = config.pair_info.query_pools(&deps.querier, contract_addr)?;
 
     let offer_pool: Asset;
     let ask_pool: Asset;
@@ -1086,13 +1317,14 @@ pub fn query_simulation(deps: Deps, env: Env, offer_asset: Asset) -> StdResult<S
         &deps.querier,
         config.factory_addr.clone(),
         config.pair_info.pair_type.clone(),
+        Some(config.pair_info.contract_addr.clone()),
     )?;
 
     let (return_amount, spread_amount, commission_amount) = compute_swap(
         offer_pool.amount,
-        query_token_precision(&deps.querier, offer_pool.info)?,
+        get_precision(&config, &offer_pool.info)?,
         ask_pool.amount,
-        query_token_precision(&deps.querier, ask_pool.info)?,
+        get_precision(&config, &ask_pool.info)?,
         offer_asset.amount,
         fee_info.total_fee_rate,
         compute_current_amp(&config, &env)?,
@@ -1102,6 +1334,7 @@ pub fn query_simulation(deps: Deps, env: Env, offer_asset: Asset) -> StdResult<S
         return_amount,
         spread_amount,
         commission_amount,
+        fee_rate: fee_info.total_fee_rate,
     })
 }
 
@@ -1119,7 +1352,7 @@ pub fn query_reverse_simulation(
     let config: Config = CONFIG.load(deps.storage)?;
     let contract_addr = config.pair_info.contract_addr.clone();
 
-    let pools: [Asset; 2] = config.pair_info.query_pools(&deps.querier, contract_addr)?;
+    let pools: Vec<Asset> = config.pair_info.query_pools(&deps.querier, contract_addr)?;
 
     let offer_pool: Asset;
     let ask_pool: Asset;
@@ -1140,13 +1373,14 @@ pub fn query_reverse_simulation(
         &deps.querier,
         config.factory_addr.clone(),
         config.pair_info.pair_type.clone(),
+        Some(config.pair_info.contract_addr.clone()),
     )?;
 
     let (offer_amount, spread_amount, commission_amount) = compute_offer_amount(
         offer_pool.amount,
-        query_token_precision(&deps.querier, offer_pool.info)?,
+        get_precision(&config, &offer_pool.info)?,
         ask_pool.amount,
-        query_token_precision(&deps.querier, ask_pool.info)?,
+        get_precision(&config, &ask_pool.info)?,
         ask_asset.amount,
         fee_info.total_fee_rate,
         compute_current_amp(&config, &env)?,
@@ -1176,28 +1410,86 @@ pub fn query_cumulative_prices(deps: Deps, env: Env) -> StdResult<CumulativePric
         env,
         &config,
         assets[0].amount,
-        query_token_precision(&deps.querier, assets[0].info.clone())?,
+        config.precisions[0],
         assets[1].amount,
-        query_token_precision(&deps.querier, assets[1].info.clone())?,
+        config.precisions[1],
     )? {
         price0_cumulative_last = price0_cumulative_new;
         price1_cumulative_last = price1_cumulative_new;
     }
 
+    let cumulative_prices = vec![
+        (
+            assets[0].info.clone(),
+            assets[1].info.clone(),
+            price0_cumulative_last,
+        ),
+        (
+            assets[1].info.clone(),
+            assets[0].info.clone(),
+            price1_cumulative_last,
+        ),
+    ];
+
     let resp = CumulativePricesResponse {
         assets,
         total_share,
         price0_cumulative_last,
         price1_cumulative_last,
+        cumulative_prices,
     };
 
     Ok(resp)
 }
 
+/// ## Description
+/// Returns the cumulative prices at each of the specified number of seconds before the current
+/// block in an [`ObserveResponse`] object.
+/// ## Params
+/// * **deps** is the object of type [`Deps`].
+///
+/// * **env** is the object of type [`Env`].
+///
+/// * **seconds_ago** is a vector of type [`u64`]. These are the numbers of seconds before the current block.
+pub fn query_observe(deps: Deps, env: Env, seconds_ago: Vec<u64>) -> StdResult<ObserveResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let (assets, _) = pool_info(deps, config.clone())?;
+
+    let mut current = Observation {
+        timestamp: config.block_time_last,
+        price0_cumulative: config.price0_cumulative_last,
+        price1_cumulative: config.price1_cumulative_last,
+    };
+
+    if let Some((price0_cumulative_new, price1_cumulative_new, block_time)) = accumulate_prices(
+        env,
+        &config,
+        assets[0].amount,
+        config.precisions[0],
+        assets[1].amount,
+        config.precisions[1],
+    )? {
+        current = Observation {
+            timestamp: block_time,
+            price0_cumulative: price0_cumulative_new,
+            price1_cumulative: price1_cumulative_new,
+        };
+    }
+
+    let observations = seconds_ago
+        .into_iter()
+        .map(|seconds_ago| observe(deps.storage, &current, seconds_ago))
+        .collect::<StdResult<Vec<Observation>>>()?;
+
+    Ok(ObserveResponse { observations })
+}
+
 /// ## Description
 /// Returns information about the controls settings in a [`ConfigResponse`] object.
 /// ## Params
 /// * **deps** is the object of type [`Deps`].
+///
+/// * **env** is the object of type [`Env`].
 pub fn query_config(deps: Deps, env: Env) -> StdResult<ConfigResponse> {
     let config: Config = CONFIG.load(deps.storage)?;
     Ok(ConfigResponse {
@@ -1207,6 +1499,7 @@ pub fn query_config(deps: Deps, env: Env) -> StdResult<ConfigResponse> {
             bluna_rewarder: config.bluna_rewarder,
             generator: config.generator,
         })?),
+        paused: query_pair_paused(&deps.querier, config.factory_addr, env.contract.address)?,
     })
 }
 
@@ -1444,7 +1737,7 @@ pub fn assert_max_spread(
 fn assert_slippage_tolerance(
     _slippage_tolerance: &Option<Decimal>,
     _deposits: &[Uint128; 2],
-    _pools: &[Asset; 2],
+    _pools: &[Asset],
 ) -> Result<(), ContractError> {
     //There is no slippage in the stable pool
     Ok(())
@@ -1470,6 +1763,11 @@ pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, Con
         "astroport-pair-stable" => match contract_version.version.as_ref() {
             "1.0.0" => {
                 let mut config = CONFIG.load(deps.storage)?;
+                config.precisions = query_precisions(
+                    &deps.querier,
+                    &config.pair_info.asset_infos,
+                    &config.factory_addr,
+                )?;
                 config.bluna_rewarder = addr_validate_to_lower(deps.api, &msg.bluna_rewarder)?;
                 config.generator = addr_validate_to_lower(deps.api, &msg.generator)?;
                 CONFIG.save(deps.storage, &config)?;
@@ -1493,15 +1791,50 @@ pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, Con
         .add_attribute("new_contract_version", CONTRACT_VERSION))
 }
 
+/// ## Description
+/// Returns the precisions of the specified assets.
+/// ## Params
+/// * **querier** is the object of type [`QuerierWrapper`].
+///
+/// * **asset_infos** are a slice of [`AssetInfo`] type items.
+///
+/// * **factory_addr** is the object of type [`Addr`]. The factory keeps the decimals of native tokens.
+fn query_precisions(
+    querier: &QuerierWrapper,
+    asset_infos: &[AssetInfo],
+    factory_addr: &Addr,
+) -> StdResult<Vec<u8>> {
+    asset_infos
+        .iter()
+        .map(|asset_info| query_token_precision(querier, asset_info.clone(), factory_addr))
+        .collect()
+}
+
+/// ## Description
+/// Returns the precision of the specified pool asset stored in the config.
+/// ## Params
+/// * **config** is the object of type [`Config`].
+///
+/// * **asset_info** is the object of type [`AssetInfo`].
+fn get_precision(config: &Config, asset_info: &AssetInfo) -> StdResult<u8> {
+    config
+        .pair_info
+        .asset_infos
+        .iter()
+        .position(|info| info.equal(asset_info))
+        .map(|i| config.precisions[i])
+        .ok_or_else(|| StdError::generic_err("Given asset doesn't belong to pairs"))
+}
+
 /// ## Description
 /// Returns information about the pool.
 /// ## Params
 /// * **deps** is the object of type [`Deps`].
 ///
 /// * **config** is the object of type [`Config`].
-pub fn pool_info(deps: Deps, config: Config) -> StdResult<([Asset; 2], Uint128)> {
+pub fn pool_info(deps: Deps, config: Config) -> StdResult<(Vec<Asset>, Uint128)> {
     let contract_addr = config.pair_info.contract_addr.clone();
-    let pools: [Asset; 2] = config.pair_info.query_pools(&deps.querier, contract_addr)?;
+    let pools: Vec<Asset> = config.pair_info.query_pools(&deps.querier, contract_addr)?;
     let total_share: Uint128 = query_supply(&deps.querier, config.pair_info.liquidity_token)?;
 
     Ok((pools, total_share))
@@ -1,4 +1,7 @@
 use crate::math::{MAX_AMP, MAX_AMP_CHANGE, MIN_AMP_CHANGING_TIME};
+use astroport::flash_loan::FlashLoanError;
+use astroport::pair::DeadlineExpired;
+use astroport::querier::PauseError;
 use cosmwasm_std::{OverflowError, StdError};
 use thiserror::Error;
 
@@ -12,9 +15,15 @@ pub enum ContractError {
     #[error("Unauthorized")]
     Unauthorized {},
 
+    #[error("Operation non supported")]
+    NonSupported {},
+
     #[error("Doubling assets in asset infos")]
     DoublingAssets {},
 
+    #[error("The pool must consist of exactly 2 assets")]
+    InvalidNumberOfAssets {},
+
     #[error("Event of zero transfer")]
     InvalidZeroAmount {},
 
@@ -30,6 +39,15 @@ pub enum ContractError {
     #[error("Operation exceeds max splippage tolerance")]
     MaxSlippageAssertion {},
 
+    #[error("The return amount is less than the minimum receive amount")]
+    MinimumReceiveAssertion {},
+
+    #[error("The minted LP amount is less than the minimum LP amount to receive")]
+    MinLpToReceiveAssertion {},
+
+    #[error("The deadline has passed")]
+    DeadlineExpired {},
+
     #[error("Native token balance mismatch between the argument and the transferred")]
     AssetMismatch {},
 
@@ -62,6 +80,15 @@ pub enum ContractError {
 
     #[error("Contract can't be migrated!")]
     MigrationError {},
+
+    #[error("A flash loan is in progress")]
+    FlashLoanInProgress {},
+
+    #[error("The flash loan was not repaid with the fee")]
+    FlashLoanNotRepaid {},
+
+    #[error("The pair is paused")]
+    Paused {},
 }
 
 impl From<OverflowError> for ContractError {
@@ -69,3 +96,31 @@ impl From<OverflowError> for ContractError {
         StdError::from(o).into()
     }
 }
+
+impl From<FlashLoanError> for ContractError {
+    fn from(err: FlashLoanError) -> Self {
+        match err {
+            FlashLoanError::Std(err) => ContractError::Std(err),
+            FlashLoanError::Unauthorized => ContractError::Unauthorized {},
+            FlashLoanError::InvalidZeroAmount => ContractError::InvalidZeroAmount {},
+            FlashLoanError::DoublingAssets => ContractError::DoublingAssets {},
+            FlashLoanError::AssetMismatch => ContractError::AssetMismatch {},
+            FlashLoanError::NotRepaid => ContractError::FlashLoanNotRepaid {},
+        }
+    }
+}
+
+impl From<DeadlineExpired> for ContractError {
+    fn from(_: DeadlineExpired) -> Self {
+        ContractError::DeadlineExpired {}
+    }
+}
+
+impl From<PauseError> for ContractError {
+    fn from(err: PauseError) -> Self {
+        match err {
+            PauseError::Std(err) => ContractError::Std(err),
+            PauseError::Paused => ContractError::Paused {},
+        }
+    }
+}
@@ -1,3 +1,6 @@
+// The tax queriers are only used with the `terra` feature
+#![cfg_attr(not(feature = "terra"), allow(dead_code))]
+
 use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR};
 use cosmwasm_std::{
     from_binary, from_slice, to_binary, Addr, Coin, Decimal, OwnedDeps, Querier, QuerierResult,
@@ -5,11 +8,19 @@ use cosmwasm_std::{
 };
 use std::collections::HashMap;
 
-use astroport::factory::QueryMsg::{Config, FeeInfo};
-use astroport::factory::{ConfigResponse, FeeInfoResponse};
+use astroport::factory::QueryMsg::{Config, FeeInfo, NativeTokenDecimals, PauseStatus};
+use astroport::factory::{
+    ConfigResponse, FeeInfoResponse, NativeTokenDecimalsResponse, PauseStatusResponse,
+};
 use cw20::{BalanceResponse, Cw20QueryMsg, TokenInfoResponse};
+#[cfg(feature = "terra")]
 use terra_cosmwasm::{TaxCapResponse, TaxRateResponse, TerraQuery, TerraQueryWrapper, TerraRoute};
 
+#[cfg(feature = "terra")]
+type CustomQuery = TerraQueryWrapper;
+#[cfg(not(feature = "terra"))]
+type CustomQuery = cosmwasm_std::Empty;
+
 /// mock_dependencies is a drop-in replacement for cosmwasm_std::testing::mock_dependencies
 /// this uses our CustomQuerier.
 pub fn mock_dependencies(
@@ -26,7 +37,7 @@ pub fn mock_dependencies(
 }
 
 pub struct WasmMockQuerier {
-    base: MockQuerier<TerraQueryWrapper>,
+    base: MockQuerier<CustomQuery>,
     token_querier: TokenQuerier,
     tax_querier: TaxQuerier,
 }
@@ -87,7 +98,7 @@ pub(crate) fn caps_to_map(caps: &[(&String, &Uint128)]) -> HashMap<String, Uint1
 impl Querier for WasmMockQuerier {
     fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
         // MockQuerier doesn't support Custom, so we ignore it completely here
-        let request: QueryRequest<TerraQueryWrapper> = match from_slice(bin_request) {
+        let request: QueryRequest<CustomQuery> = match from_slice(bin_request) {
             Ok(v) => v,
             Err(e) => {
                 return SystemResult::Err(SystemError::InvalidRequest {
@@ -101,8 +112,9 @@ impl Querier for WasmMockQuerier {
 }
 
 impl WasmMockQuerier {
-    pub fn handle_query(&self, request: &QueryRequest<TerraQueryWrapper>) -> QuerierResult {
+    pub fn handle_query(&self, request: &QueryRequest<CustomQuery>) -> QuerierResult {
         match &request {
+            #[cfg(feature = "terra")]
             QueryRequest::Custom(TerraQueryWrapper { route, query_data }) => {
                 if route == &TerraRoute::Treasury {
                     match query_data {
@@ -147,9 +159,16 @@ impl WasmMockQuerier {
                                 fee_address: Some(Addr::unchecked("fee_address")),
                                 generator_address: Some(Addr::unchecked("gen_address")),
                                 whitelist_code_id: 666,
+                                guardian: None,
                             })
                             .into(),
                         ),
+                        NativeTokenDecimals { .. } => SystemResult::Ok(
+                            to_binary(&NativeTokenDecimalsResponse { decimals: 6 }).into(),
+                        ),
+                        PauseStatus { .. } => SystemResult::Ok(
+                            to_binary(&PauseStatusResponse { paused: false }).into(),
+                        ),
                         _ => panic!("DO NOT ENTER HERE"),
                     }
                 } else {
@@ -209,7 +228,7 @@ impl WasmMockQuerier {
 }
 
 impl WasmMockQuerier {
-    pub fn new(base: MockQuerier<TerraQueryWrapper>) -> Self {
+    pub fn new(base: MockQuerier<CustomQuery>) -> Self {
         WasmMockQuerier {
             base,
             token_querier: TokenQuerier::default(),
@@ -18,6 +18,9 @@ pub struct Config {
     pub price0_cumulative_last: Uint128,
     /// The last cumulative price 1 asset in pool
     pub price1_cumulative_last: Uint128,
+    /// The precisions of the pool assets in the `pair_info.asset_infos` order. They are read once
+    /// at instantiation, so changing the native token decimals in the factory doesn't rescale the pool
+    pub precisions: Vec<u8>,
     pub init_amp: u64,
     pub init_amp_time: u64,
     pub next_amp: u64,
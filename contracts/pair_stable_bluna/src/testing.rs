@@ -17,7 +17,7 @@ use astroport::token::InstantiateMsg as TokenInstantiateMsg;
 use astroport::whitelist::InstantiateMsg as WhitelistInstantiateMsg;
 use cosmwasm_std::testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR};
 use cosmwasm_std::{
-    attr, to_binary, Addr, BankMsg, BlockInfo, Coin, ContractResult, CosmosMsg, Decimal,
+    attr, to_binary, Addr, BankMsg, Binary, BlockInfo, Coin, ContractResult, CosmosMsg, Decimal,
     Decimal256, DepsMut, Env, Reply, ReplyOn, StdError, SubMsg, SubMsgExecutionResponse, Timestamp,
     Uint128, WasmMsg,
 };
@@ -57,7 +57,7 @@ fn proper_initialization() {
 
     let msg = InstantiateMsg {
         factory_addr: "factory".to_string(),
-        asset_infos: [
+        asset_infos: vec![
             AssetInfo::NativeToken {
                 denom: "uusd".to_string(),
             },
@@ -169,7 +169,7 @@ fn provide_liquidity() {
     ]);
 
     let msg = InstantiateMsg {
-        asset_infos: [
+        asset_infos: vec![
             AssetInfo::NativeToken {
                 denom: "uusd".to_string(),
             },
@@ -216,6 +216,8 @@ fn provide_liquidity() {
         slippage_tolerance: None,
         auto_stake: None,
         receiver: None,
+        min_lp_to_receive: None,
+        deadline: None,
     };
 
     let env = mock_env();
@@ -286,6 +288,8 @@ fn provide_liquidity() {
         slippage_tolerance: None,
         auto_stake: None,
         receiver: None,
+        min_lp_to_receive: None,
+        deadline: None,
     };
 
     let env = mock_env();
@@ -329,7 +333,7 @@ fn withdraw_liquidity() {
     ]);
 
     let msg = InstantiateMsg {
-        asset_infos: [
+        asset_infos: vec![
             AssetInfo::NativeToken {
                 denom: "uusd".to_string(),
             },
@@ -360,7 +364,10 @@ fn withdraw_liquidity() {
     // withdraw liquidity
     let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
         sender: String::from("addr0000"),
-        msg: to_binary(&Cw20HookMsg::WithdrawLiquidity {}).unwrap(),
+        msg: to_binary(&Cw20HookMsg::WithdrawLiquidity {
+            withdraw_type: None,
+        })
+        .unwrap(),
         amount: Uint128::new(100u128),
     });
 
@@ -462,7 +469,7 @@ fn try_native_to_token() {
     ]);
 
     let msg = InstantiateMsg {
-        asset_infos: [
+        asset_infos: vec![
             AssetInfo::NativeToken {
                 denom: "uusd".to_string(),
             },
@@ -501,6 +508,8 @@ fn try_native_to_token() {
         belief_price: None,
         max_spread: Some(Decimal::percent(50)),
         to: None,
+        minimum_receive: None,
+        deadline: None,
     };
     let env = mock_env_with_block_time(1000);
     let info = mock_info(
@@ -623,7 +632,7 @@ fn try_token_to_native() {
     ]);
 
     let msg = InstantiateMsg {
-        asset_infos: [
+        asset_infos: vec![
             AssetInfo::NativeToken {
                 denom: "uusd".to_string(),
             },
@@ -662,6 +671,8 @@ fn try_token_to_native() {
         belief_price: None,
         max_spread: None,
         to: None,
+        minimum_receive: None,
+        deadline: None,
     };
     let env = mock_env_with_block_time(1000);
     let info = mock_info("addr0000", &[]);
@@ -673,9 +684,12 @@ fn try_token_to_native() {
         sender: String::from("addr0000"),
         amount: offer_amount,
         msg: to_binary(&Cw20HookMsg::Swap {
+            ask_asset_info: None,
             belief_price: None,
             max_spread: None,
             to: None,
+            minimum_receive: None,
+            deadline: None,
         })
         .unwrap(),
     });
@@ -701,14 +715,19 @@ fn try_token_to_native() {
     let expected_return_amount = expected_ret_amount
         .checked_sub(expected_commission_amount)
         .unwrap();
-    let expected_tax_amount = std::cmp::min(
-        Uint128::new(1000000u128),
-        expected_return_amount
-            .checked_sub(
-                expected_return_amount.multiply_ratio(Uint128::new(100u128), Uint128::new(101u128)),
-            )
-            .unwrap(),
-    );
+    let expected_tax_amount = if cfg!(feature = "terra") {
+        std::cmp::min(
+            Uint128::new(1000000u128),
+            expected_return_amount
+                .checked_sub(
+                    expected_return_amount
+                        .multiply_ratio(Uint128::new(100u128), Uint128::new(101u128)),
+                )
+                .unwrap(),
+        )
+    } else {
+        Uint128::zero()
+    };
     // check simulation res
     // return asset token balance as normal
     deps.querier.with_token_balances(&[
@@ -810,9 +829,12 @@ fn try_token_to_native() {
         sender: String::from("addr0000"),
         amount: offer_amount,
         msg: to_binary(&Cw20HookMsg::Swap {
+            ask_asset_info: None,
             belief_price: None,
             max_spread: None,
             to: None,
+            minimum_receive: None,
+            deadline: None,
         })
         .unwrap(),
     });
@@ -861,6 +883,7 @@ fn test_max_spread() {
     .unwrap();
 }
 
+#[cfg(feature = "terra")]
 #[test]
 fn test_deduct() {
     let mut deps = mock_dependencies(&[]);
@@ -912,7 +935,7 @@ fn test_query_pool() {
     ]);
 
     let msg = InstantiateMsg {
-        asset_infos: [
+        asset_infos: vec![
             AssetInfo::NativeToken {
                 denom: "uusd".to_string(),
             },
@@ -984,7 +1007,7 @@ fn test_query_share() {
     ]);
 
     let msg = InstantiateMsg {
-        asset_infos: [
+        asset_infos: vec![
             AssetInfo::NativeToken {
                 denom: "uusd".to_string(),
             },
@@ -1098,7 +1121,7 @@ fn test_accumulate_prices() {
             env.clone(),
             &Config {
                 pair_info: PairInfo {
-                    asset_infos: [
+                    asset_infos: vec![
                         AssetInfo::NativeToken {
                             denom: "uusd".to_string(),
                         },
@@ -1114,6 +1137,7 @@ fn test_accumulate_prices() {
                 block_time_last: case.block_time_last,
                 price0_cumulative_last: Uint128::new(case.last0),
                 price1_cumulative_last: Uint128::new(case.last1),
+                precisions: vec![6, 6],
                 init_amp: 100 * AMP_PRECISION,
                 init_amp_time: env.block.time.seconds(),
                 next_amp: 100 * AMP_PRECISION,
@@ -1268,3 +1292,150 @@ proptest! {
         );
     }
 }
+
+#[test]
+fn flash_loan() {
+    let mut deps = mock_dependencies(&[Coin {
+        denom: "uusd".to_string(),
+        amount: Uint128::new(30_000_000000),
+    }]);
+    deps.querier.with_token_balances(&[
+        (
+            &String::from("liquidity0000"),
+            &[(
+                &String::from(MOCK_CONTRACT_ADDR),
+                &Uint128::new(30_000_000000),
+            )],
+        ),
+        (
+            &String::from("asset0000"),
+            &[(
+                &String::from(MOCK_CONTRACT_ADDR),
+                &Uint128::new(20_000_000000),
+            )],
+        ),
+    ]);
+
+    let token_info = AssetInfo::Token {
+        contract_addr: Addr::unchecked("asset0000"),
+    };
+    let msg = InstantiateMsg {
+        asset_infos: vec![
+            AssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            },
+            token_info.clone(),
+        ],
+        token_code_id: 10u64,
+        factory_addr: "factory".to_string(),
+        init_params: Some(
+            to_binary(&StablePoolParams {
+                amp: 100,
+                bluna_rewarder: "bluna_rewarder".to_string(),
+                generator: "generator".to_string(),
+            })
+            .unwrap(),
+        ),
+    };
+
+    let env = mock_env_with_block_time(100);
+    instantiate(deps.as_mut(), env.clone(), mock_info("addr0000", &[]), msg).unwrap();
+    store_liquidity_token(deps.as_mut(), 1, "liquidity0000".to_string());
+
+    let msg = ExecuteMsg::FlashLoan {
+        assets: vec![Asset {
+            info: token_info.clone(),
+            amount: Uint128::new(2_000_000000),
+        }],
+        callback: Binary::from(b"callback".to_vec()),
+    };
+    let res = execute(deps.as_mut(), env.clone(), mock_info("borrower", &[]), msg).unwrap();
+
+    let repay_msg = ExecuteMsg::AssertFlashLoanRepaid {
+        expected_balances: vec![Asset {
+            info: token_info.clone(),
+            amount: Uint128::new(20_006_000000),
+        }],
+        fees: vec![Asset {
+            info: token_info,
+            amount: Uint128::new(6_000000),
+        }],
+    };
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: String::from("asset0000"),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: String::from("borrower"),
+                    amount: Uint128::new(2_000_000000),
+                })
+                .unwrap(),
+                funds: vec![],
+            }),
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: String::from("borrower"),
+                msg: Binary::from(b"callback".to_vec()),
+                funds: vec![],
+            }),
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: String::from(MOCK_CONTRACT_ADDR),
+                msg: to_binary(&repay_msg).unwrap(),
+                funds: vec![],
+            }),
+        ]
+    );
+
+    // Token swaps are rejected until the loan is repaid
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: String::from("borrower"),
+        amount: Uint128::new(1_000000),
+        msg: to_binary(&Cw20HookMsg::Swap {
+            ask_asset_info: None,
+            belief_price: None,
+            max_spread: None,
+            to: None,
+            minimum_receive: None,
+            deadline: None,
+        })
+        .unwrap(),
+    });
+    let err = execute(deps.as_mut(), env.clone(), mock_info("asset0000", &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::FlashLoanInProgress {});
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        repay_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::FlashLoanNotRepaid {});
+
+    deps.querier.with_token_balances(&[(
+        &String::from("asset0000"),
+        &[(
+            &String::from(MOCK_CONTRACT_ADDR),
+            &Uint128::new(20_006_000000),
+        )],
+    )]);
+    let res = execute(
+        deps.as_mut(),
+        env,
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        repay_msg,
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(WasmMsg::Execute {
+            contract_addr: String::from("asset0000"),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: String::from("fee_address"),
+                amount: Uint128::new(996000),
+            })
+            .unwrap(),
+            funds: vec![],
+        })]
+    );
+}
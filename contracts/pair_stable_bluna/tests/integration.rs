@@ -1,3 +1,7 @@
+// The Terra treasury tax is queried from the Terra chain, which the multi-test app doesn't
+// mock, so these tests run without the `terra` feature
+#![cfg(not(feature = "terra"))]
+
 use astroport::asset::AssetInfo;
 use astroport::factory::{InstantiateMsg as FactoryInstantiateMsg, PairConfig, PairType};
 use astroport::pair::{ConfigResponse, InstantiateMsg};
@@ -10,24 +14,22 @@ use astroport_pair_stable_bluna::math::{MAX_AMP, MAX_AMP_CHANGE, MIN_AMP_CHANGIN
 use cosmwasm_std::testing::{mock_env, MockApi, MockStorage};
 use cosmwasm_std::{from_binary, to_binary, Addr, Decimal};
 
-use terra_multi_test::{AppBuilder, BankKeeper, ContractWrapper, Executor, TerraApp, TerraMock};
+use cw_multi_test::{App, AppBuilder, BankKeeper, ContractWrapper, Executor};
 
-fn mock_app() -> TerraApp {
+fn mock_app() -> App {
     let env = mock_env();
     let api = MockApi::default();
     let bank = BankKeeper::new();
     let storage = MockStorage::new();
-    let custom = TerraMock::luna_ust_case();
 
     AppBuilder::new()
         .with_api(api)
         .with_block(env.block)
         .with_bank(bank)
         .with_storage(storage)
-        .with_custom(custom)
         .build()
 }
-fn store_token_code(app: &mut TerraApp) -> u64 {
+fn store_token_code(app: &mut App) -> u64 {
     let astro_token_contract = Box::new(ContractWrapper::new_with_empty(
         astroport_token::contract::execute,
         astroport_token::contract::instantiate,
@@ -37,7 +39,7 @@ fn store_token_code(app: &mut TerraApp) -> u64 {
     app.store_code(astro_token_contract)
 }
 
-fn store_pair_code(app: &mut TerraApp) -> u64 {
+fn store_pair_code(app: &mut App) -> u64 {
     let pair_contract = Box::new(
         ContractWrapper::new_with_empty(
             astroport_pair_stable_bluna::contract::execute,
@@ -50,7 +52,7 @@ fn store_pair_code(app: &mut TerraApp) -> u64 {
     app.store_code(pair_contract)
 }
 
-fn store_factory_code(app: &mut TerraApp) -> u64 {
+fn store_factory_code(app: &mut App) -> u64 {
     let factory_contract = Box::new(
         ContractWrapper::new_with_empty(
             astroport_factory::contract::execute,
@@ -63,7 +65,7 @@ fn store_factory_code(app: &mut TerraApp) -> u64 {
     app.store_code(factory_contract)
 }
 
-fn store_whitelist_code(app: &mut TerraApp) -> u64 {
+fn store_whitelist_code(app: &mut App) -> u64 {
     let whitelist_contract = Box::new(ContractWrapper::new_with_empty(
         astroport_whitelist::contract::execute,
         astroport_whitelist::contract::instantiate,
@@ -92,6 +94,8 @@ fn create_pair_with_same_assets() {
             total_fee_bps: 0,
             pair_type: PairType::Stable {},
             is_disabled: None,
+            permissionless: true,
+            creation_fee: None,
         }],
         token_code_id: token_contract_code_id,
         generator_address: Some(String::from("generator")),
@@ -111,7 +115,7 @@ fn create_pair_with_same_assets() {
         .unwrap();
 
     let msg = InstantiateMsg {
-        asset_infos: [
+        asset_infos: vec![
             AssetInfo::NativeToken {
                 denom: "uusd".to_string(),
             },
@@ -170,7 +174,7 @@ fn update_pair_config() {
         .unwrap();
 
     let msg = InstantiateMsg {
-        asset_infos: [
+        asset_infos: vec![
             AssetInfo::NativeToken {
                 denom: "uusd".to_string(),
             },
@@ -0,0 +1,1651 @@
+use crate::error::ContractError;
+use crate::math::{
+    calc_ask_amount, calc_offer_amount, calc_spot_price, exceeds_ratio, MAX_IN_RATIO,
+    MAX_OUT_RATIO, MIN_WEIGHT_PERCENT, WEIGHT_PRECISION,
+};
+use crate::state::{Config, CONFIG};
+
+use cosmwasm_bignumber::Decimal256;
+use cosmwasm_std::{
+    attr, entry_point, from_binary, to_binary, Addr, Binary, CosmosMsg, Decimal, Deps, DepsMut,
+    Env, MessageInfo, Reply, ReplyOn, Response, StdError, StdResult, Storage, SubMsg, Uint128,
+    Uint256, WasmMsg,
+};
+
+use crate::response::MsgInstantiateContractResponse;
+use astroport::asset::{addr_validate_to_lower, format_lp_token_name, Asset, AssetInfo, PairInfo};
+use astroport::factory::PairType;
+use astroport::flash_loan;
+use astroport::generator::Cw20HookMsg as GeneratorHookMsg;
+use astroport::observation::{observe, store_observation};
+use astroport::pair::assert_deadline;
+use astroport::pair::{
+    ConfigResponse, CumulativePricesResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg,
+    Observation, ObserveResponse, PoolResponse, ProvideResponse, QueryMsg,
+    ReverseSimulationResponse, SimulateWithdrawResponse, SimulationResponse, SwapResponse,
+    WeightedPoolConfig, WeightedPoolParams, WithdrawResponse, WithdrawType, DEFAULT_SLIPPAGE,
+    MAX_ALLOWED_SLIPPAGE, TWAP_PRECISION,
+};
+use astroport::querier::{
+    assert_not_paused, query_factory_config, query_fee_info, query_pair_paused, query_supply,
+};
+use astroport::token::InstantiateMsg as TokenInstantiateMsg;
+use cw2::set_contract_version;
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg, MinterResponse};
+use protobuf::Message;
+use std::convert::TryFrom;
+use std::str::FromStr;
+use std::vec;
+
+/// Contract name that is used for migration.
+const CONTRACT_NAME: &str = "astroport-pair-weighted";
+/// Contract version that is used for migration.
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+/// A `reply` call code ID of sub-message.
+const INSTANTIATE_TOKEN_REPLY_ID: u64 = 1;
+/// The amount of LP tokens minted for the first provision, 100 tokens with 6 decimals.
+pub const INIT_POOL_SUPPLY: Uint128 = Uint128::new(100_000_000);
+
+/// ## Description
+/// Creates a new contract with the specified parameters in the [`InstantiateMsg`].
+/// Returns the [`Response`] with the specified attributes if the operation was successful, or a [`ContractError`] if the contract was not created
+/// ## Params
+/// * **deps** is the object of type [`DepsMut`].
+///
+/// * **env** is the object of type [`Env`].
+///
+/// * **_info** is the object of type [`MessageInfo`].
+/// * **msg** is a message of type [`InstantiateMsg`] which contains the basic settings for creating a contract
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    if msg.asset_infos.len() != 2 {
+        return Err(ContractError::InvalidNumberOfAssets {});
+    }
+
+    msg.asset_infos[0].check(deps.api)?;
+    msg.asset_infos[1].check(deps.api)?;
+
+    if msg.asset_infos[0] == msg.asset_infos[1] {
+        return Err(ContractError::DoublingAssets {});
+    }
+
+    let params: WeightedPoolParams = match msg.init_params {
+        Some(params) => from_binary(&params)?,
+        None => return Err(ContractError::InitParamsNotFound {}),
+    };
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let config = Config {
+        pair_info: PairInfo {
+            contract_addr: env.contract.address.clone(),
+            liquidity_token: Addr::unchecked(""),
+            asset_infos: msg.asset_infos.clone(),
+            pair_type: PairType::Weighted {},
+        },
+        factory_addr: addr_validate_to_lower(deps.api, msg.factory_addr.as_str())?,
+        weights: normalize_weights(&params.weights, msg.asset_infos.len())?,
+        block_time_last: 0,
+        price0_cumulative_last: Uint128::zero(),
+        price1_cumulative_last: Uint128::zero(),
+    };
+
+    CONFIG.save(deps.storage, &config)?;
+
+    let token_name = format_lp_token_name(msg.asset_infos, &deps.querier)?;
+
+    // Create LP token
+    let sub_msg: Vec<SubMsg> = vec![SubMsg {
+        msg: WasmMsg::Instantiate {
+            code_id: msg.token_code_id,
+            msg: to_binary(&TokenInstantiateMsg {
+                name: token_name,
+                symbol: "uLP".to_string(),
+                decimals: 6,
+                initial_balances: vec![],
+                mint: Some(MinterResponse {
+                    minter: env.contract.address.to_string(),
+                    cap: None,
+                }),
+            })?,
+            funds: vec![],
+            admin: None,
+            label: String::from("Astroport LP token"),
+        }
+        .into(),
+        id: INSTANTIATE_TOKEN_REPLY_ID,
+        gas_limit: None,
+        reply_on: ReplyOn::Success,
+    }];
+
+    Ok(Response::new().add_submessages(sub_msg))
+}
+
+/// ## Description
+/// Returns the weights normalized so that they sum up to one. Returns a [`ContractError`] if the
+/// number of weights doesn't match the number of assets or any weight is too small.
+/// ## Params
+/// * **weights** are a slice of [`u64`] type items. Sets the relative weights of the pool assets.
+///
+/// * **assets_count** is the object of type [`usize`]. Sets the number of the pool assets.
+fn normalize_weights(weights: &[u64], assets_count: usize) -> Result<Vec<Decimal>, ContractError> {
+    if weights.len() != assets_count {
+        return Err(ContractError::InvalidWeights {});
+    }
+
+    let total_weight: Uint128 = weights.iter().map(|weight| Uint128::from(*weight)).sum();
+    if total_weight.is_zero() {
+        return Err(ContractError::InvalidWeights {});
+    }
+
+    let weights: Vec<Decimal> = weights
+        .iter()
+        .map(|weight| Decimal::from_ratio(*weight, total_weight))
+        .collect();
+
+    if weights
+        .iter()
+        .any(|weight| *weight < Decimal::percent(MIN_WEIGHT_PERCENT))
+    {
+        return Err(ContractError::InvalidWeights {});
+    }
+
+    Ok(weights)
+}
+
+/// # Description
+/// The entry point to the contract for processing the reply from the submessage
+/// # Params
+/// * **deps** is the object of type [`DepsMut`].
+///
+/// * **_env** is the object of type [`Env`].
+///
+/// * **msg** is the object of type [`Reply`].
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    let mut config: Config = CONFIG.load(deps.storage)?;
+
+    if config.pair_info.liquidity_token != Addr::unchecked("") {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let data = msg.result.unwrap().data.unwrap();
+    let res: MsgInstantiateContractResponse =
+        Message::parse_from_bytes(data.as_slice()).map_err(|_| {
+            StdError::parse_err("MsgInstantiateContractResponse", "failed to parse data")
+        })?;
+
+    config.pair_info.liquidity_token =
+        addr_validate_to_lower(deps.api, res.get_contract_address())?;
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("liquidity_token_addr", config.pair_info.liquidity_token))
+}
+
+/// ## Description
+/// Available the execute messages of the contract.
+/// ## Params
+/// * **deps** is the object of type [`Deps`].
+///
+/// * **env** is the object of type [`Env`].
+///
+/// * **info** is the object of type [`MessageInfo`].
+///
+/// * **msg** is the object of type [`ExecuteMsg`].
+///
+/// ## Queries
+/// * **ExecuteMsg::UpdateConfig { .. }** Not supported.
+///
+/// * **ExecuteMsg::Receive(msg)** Receives a message of type [`Cw20ReceiveMsg`] and processes
+/// it depending on the received template.
+///
+/// * **ExecuteMsg::ProvideLiquidity {
+///             assets,
+///             slippage_tolerance,
+///             auto_stake,
+///             receiver,
+///             min_lp_to_receive,
+///             deadline,
+///         }** Provides liquidity with the specified input parameters.
+///
+/// * **ExecuteMsg::ProvideSingleSided { .. }** Not supported.
+///
+/// * **ExecuteMsg::SwapExactOut { .. }** Not supported.
+///
+/// * **ExecuteMsg::Swap {
+///             offer_asset,
+///             ask_asset_info,
+///             belief_price,
+///             max_spread,
+///             to,
+///             minimum_receive,
+///             deadline,
+///         }** Performs an swap operation with the specified parameters.
+///
+/// * **ExecuteMsg::FlashLoan { assets, callback }** Lends the pool assets to the sender within
+/// the transaction.
+///
+/// * **ExecuteMsg::AssertFlashLoanRepaid {
+///             expected_balances,
+///             fees,
+///         }** Checks that a flash loan was repaid. Only the pair itself can execute it.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    if flash_loan::is_flash_loan_locked(deps.storage)?
+        && !matches!(msg, ExecuteMsg::AssertFlashLoanRepaid { .. })
+    {
+        return Err(ContractError::FlashLoanInProgress {});
+    }
+
+    match msg {
+        ExecuteMsg::UpdateConfig { .. } => Err(ContractError::NonSupported {}),
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
+        ExecuteMsg::FlashLoan { assets, callback } => flash_loan(deps, env, info, assets, callback),
+        ExecuteMsg::AssertFlashLoanRepaid {
+            expected_balances,
+            fees,
+        } => assert_flash_loan_repaid(deps, env, info, expected_balances, fees),
+        ExecuteMsg::ProvideSingleSided { .. } => Err(ContractError::NonSupported {}),
+        ExecuteMsg::SwapExactOut { .. } => Err(ContractError::NonSupported {}),
+        ExecuteMsg::ProvideLiquidity {
+            assets,
+            slippage_tolerance,
+            auto_stake,
+            receiver,
+            min_lp_to_receive,
+            deadline,
+        } => provide_liquidity(
+            deps,
+            env,
+            info,
+            assets,
+            slippage_tolerance,
+            auto_stake,
+            receiver,
+            min_lp_to_receive,
+            deadline,
+        ),
+        ExecuteMsg::Swap {
+            offer_asset,
+            ask_asset_info: _,
+            belief_price,
+            max_spread,
+            to,
+            minimum_receive,
+            deadline,
+        } => {
+            offer_asset.info.check(deps.api)?;
+            if !offer_asset.is_native_token() {
+                return Err(ContractError::Unauthorized {});
+            }
+
+            let to_addr = if let Some(to_addr) = to {
+                Some(addr_validate_to_lower(deps.api, &to_addr)?)
+            } else {
+                None
+            };
+
+            swap(
+                deps,
+                env,
+                info.clone(),
+                info.sender,
+                offer_asset,
+                belief_price,
+                max_spread,
+                to_addr,
+                minimum_receive,
+                deadline,
+            )
+        }
+    }
+}
+
+/// ## Description
+/// Receives a message of type [`Cw20ReceiveMsg`] and processes it depending on the received template.
+/// If the template is not found in the received message, then an [`ContractError`] is returned,
+/// otherwise returns the [`Response`] with the specified attributes if the operation was successful
+/// ## Params
+/// * **deps** is the object of type [`DepsMut`].
+///
+/// * **env** is the object of type [`Env`].
+///
+/// * **info** is the object of type [`MessageInfo`].
+///
+/// * **cw20_msg** is the object of type [`Cw20ReceiveMsg`].
+pub fn receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let contract_addr = info.sender.clone();
+    match from_binary(&cw20_msg.msg) {
+        Ok(Cw20HookMsg::Swap {
+            ask_asset_info: _,
+            belief_price,
+            max_spread,
+            to,
+            minimum_receive,
+            deadline,
+        }) => {
+            // only asset contract can execute this message
+            let mut authorized: bool = false;
+            let config: Config = CONFIG.load(deps.storage)?;
+
+            for pool in config.pair_info.asset_infos {
+                if let AssetInfo::Token { contract_addr, .. } = &pool {
+                    if contract_addr == &info.sender {
+                        authorized = true;
+                    }
+                }
+            }
+
+            if !authorized {
+                return Err(ContractError::Unauthorized {});
+            }
+
+            let to_addr = if let Some(to_addr) = to {
+                Some(addr_validate_to_lower(deps.api, to_addr.as_str())?)
+            } else {
+                None
+            };
+
+            swap(
+                deps,
+                env,
+                info,
+                Addr::unchecked(cw20_msg.sender),
+                Asset {
+                    info: AssetInfo::Token { contract_addr },
+                    amount: cw20_msg.amount,
+                },
+                belief_price,
+                max_spread,
+                to_addr,
+                minimum_receive,
+                deadline,
+            )
+        }
+        Ok(Cw20HookMsg::WithdrawLiquidity {
+            withdraw_type: None,
+        }) => withdraw_liquidity(
+            deps,
+            env,
+            info,
+            Addr::unchecked(cw20_msg.sender),
+            cw20_msg.amount,
+        ),
+        Ok(Cw20HookMsg::WithdrawLiquidity { .. }) => Err(ContractError::NonSupported {}),
+        Ok(Cw20HookMsg::SwapExactOut { .. }) => Err(ContractError::NonSupported {}),
+        Err(err) => Err(ContractError::Std(err)),
+    }
+}
+
+/// ## Description
+/// Provides liquidity with the specified input parameters.
+/// Returns an [`ContractError`] on failure, otherwise returns the [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Params
+/// * **deps** is the object of type [`DepsMut`].
+///
+/// * **env** is the object of type [`Env`].
+///
+/// * **info** is the object of type [`MessageInfo`].
+///
+/// * **slippage_tolerance** is an [`Option`] field of type [`Decimal`]. Used for sets the maximum
+/// percent of price movement.
+///
+/// * **auto_stake** is an [`Option`] field of type [`bool`]. Determines whether an autostake will
+/// be performed on the generator.
+///
+/// * **receiver** is an [`Option`] field of type  [`String`]. Sets the receiver of liquidity.
+///
+/// * **min_lp_to_receive** is an [`Option`] field of type [`Uint128`]. Sets the minimum amount of
+/// LP tokens to mint.
+///
+/// * **deadline** is an [`Option`] field of type [`u64`]. Sets the block time after which the
+/// provision fails.
+// CONTRACT - should approve contract to use the amount of token.
+#[allow(clippy::too_many_arguments)]
+pub fn provide_liquidity(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    assets: Vec<Asset>,
+    slippage_tolerance: Option<Decimal>,
+    auto_stake: Option<bool>,
+    receiver: Option<String>,
+    min_lp_to_receive: Option<Uint128>,
+    deadline: Option<u64>,
+) -> Result<Response, ContractError> {
+    assert_deadline(&env, deadline)?;
+
+    if assets.len() != 2 {
+        return Err(ContractError::InvalidNumberOfAssets {});
+    }
+
+    assets[0].info.check(deps.api)?;
+    assets[1].info.check(deps.api)?;
+
+    let auto_stake = auto_stake.unwrap_or(false);
+    for asset in assets.iter() {
+        asset.assert_sent_native_token_balance(&info)?;
+    }
+
+    let mut config: Config = CONFIG.load(deps.storage)?;
+    assert_not_paused(
+        &deps.querier,
+        config.factory_addr.clone(),
+        env.contract.address.clone(),
+    )?;
+
+    let mut pools: Vec<Asset> = config
+        .pair_info
+        .query_pools(&deps.querier, env.contract.address.clone())?;
+    let deposits = get_deposits(&assets, &pools)?;
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    for (i, pool) in pools.iter_mut().enumerate() {
+        // If the pool is token contract, then we need to execute TransferFrom msg to receive funds
+        if let AssetInfo::Token { contract_addr, .. } = &pool.info {
+            messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: contract_addr.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
+                    owner: info.sender.to_string(),
+                    recipient: env.contract.address.to_string(),
+                    amount: deposits[i],
+                })?,
+                funds: vec![],
+            }));
+        } else {
+            // If the asset is native token, balance is already increased
+            // To calculated properly we should subtract user deposit from the pool
+            pool.amount = pool.amount.checked_sub(deposits[i])?;
+        }
+    }
+
+    let total_share = query_supply(&deps.querier, config.pair_info.liquidity_token.clone())?;
+    if !total_share.is_zero() {
+        // assert slippage tolerance
+        assert_slippage_tolerance(slippage_tolerance, &deposits, &pools)?;
+    }
+
+    let share = compute_share(total_share, &deposits, &pools)?;
+    if share < min_lp_to_receive.unwrap_or_default() {
+        return Err(ContractError::MinLpToReceiveAssertion {});
+    }
+
+    // mint LP token for sender or receiver if set
+    let receiver = receiver.unwrap_or_else(|| info.sender.to_string());
+    messages.extend(mint_liquidity_token_message(
+        deps.as_ref(),
+        &config,
+        env.clone(),
+        addr_validate_to_lower(deps.api, receiver.as_str())?,
+        share,
+        auto_stake,
+    )?);
+
+    // Accumulate prices for oracle
+    let prices = accumulate_prices(env, &config, pools[0].amount, pools[1].amount)?;
+    save_cumulative_prices(deps.storage, &mut config, prices)?;
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .set_data(to_binary(&ProvideResponse {
+            assets: assets.clone(),
+            share,
+        })?)
+        .add_attributes(vec![
+            attr("action", "provide_liquidity"),
+            attr("sender", info.sender.as_str()),
+            attr("receiver", receiver.as_str()),
+            attr("assets", format!("{}, {}", assets[0], assets[1])),
+            attr("share", share.to_string()),
+        ]))
+}
+
+/// ## Description
+/// Returns the deposits ordered as the pool assets. Returns a [`ContractError`] if not every pool
+/// asset is provided with a non-zero amount.
+/// ## Params
+/// * **assets** are a slice of [`Asset`] type items. Sets the provided assets.
+///
+/// * **pools** are a slice of [`Asset`] type items. Sets the pool balances.
+fn get_deposits(assets: &[Asset], pools: &[Asset]) -> Result<[Uint128; 2], ContractError> {
+    let deposit = |pool: &Asset| {
+        assets
+            .iter()
+            .find(|a| a.info.equal(&pool.info))
+            .map(|a| a.amount)
+            .ok_or(ContractError::AssetMismatch {})
+    };
+    let deposits = [deposit(&pools[0])?, deposit(&pools[1])?];
+
+    if deposits[0].is_zero() || deposits[1].is_zero() {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+
+    Ok(deposits)
+}
+
+/// ## Description
+/// Returns the amount of LP tokens minted for the specified deposits.
+/// The first provision mints [`INIT_POOL_SUPPLY`] LP tokens, which sets the initial pool balances.
+/// Later provisions mint LP tokens in proportion to the smallest deposit relative to its pool.
+/// ## Params
+/// * **total_share** is the object of type [`Uint128`]. Sets the total amount of LP tokens.
+///
+/// * **deposits** are a slice of [`Uint128`] type items. Sets the deposits ordered as the pools.
+///
+/// * **pools** are a slice of [`Asset`] type items. Sets the pool balances before the deposit.
+fn compute_share(
+    total_share: Uint128,
+    deposits: &[Uint128],
+    pools: &[Asset],
+) -> Result<Uint128, ContractError> {
+    let share = if total_share.is_zero() {
+        INIT_POOL_SUPPLY
+    } else {
+        std::cmp::min(
+            deposits[0].multiply_ratio(total_share, pools[0].amount),
+            deposits[1].multiply_ratio(total_share, pools[1].amount),
+        )
+    };
+
+    if share.is_zero() {
+        return Err(ContractError::LiquidityAmountTooSmall {});
+    }
+
+    Ok(share)
+}
+
+/// # Description
+/// Mint LP token to beneficiary or auto deposit into generator if set.
+/// # Params
+/// * **deps** is the object of type [`Deps`].
+///
+/// * **config** is the object of type [`Config`].
+///
+/// * **env** is the object of type [`Env`].
+///
+/// * **recipient** is the object of type [`Addr`]. The recipient of the liquidity.
+///
+/// * **amount** is the object of type [`Uint128`]. The amount that will be mint to the recipient.
+///
+/// * **auto_stake** is the field of type [`bool`]. Determines whether an autostake will be performed on the generator
+fn mint_liquidity_token_message(
+    deps: Deps,
+    config: &Config,
+    env: Env,
+    recipient: Addr,
+    amount: Uint128,
+    auto_stake: bool,
+) -> Result<Vec<CosmosMsg>, ContractError> {
+    let lp_token = config.pair_info.liquidity_token.clone();
+
+    // If no auto-stake - just mint to recipient
+    if !auto_stake {
+        return Ok(vec![CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: lp_token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Mint {
+                recipient: recipient.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        })]);
+    }
+
+    // Mint to contract and stake to generator
+    let generator =
+        query_factory_config(&deps.querier, config.clone().factory_addr)?.generator_address;
+
+    if generator.is_none() {
+        return Err(ContractError::AutoStakeError {});
+    }
+
+    Ok(vec![
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: lp_token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Mint {
+                recipient: env.contract.address.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        }),
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: lp_token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: generator.unwrap().to_string(),
+                amount,
+                msg: to_binary(&GeneratorHookMsg::DepositFor(recipient))?,
+            })?,
+            funds: vec![],
+        }),
+    ])
+}
+
+/// ## Description
+/// Withdraw liquidity from the pool. Returns an [`ContractError`] on failure,
+/// otherwise returns the [`Response`] with the specified attributes if the operation was successful.
+/// ## Params
+/// * **deps** is the object of type [`DepsMut`].
+///
+/// * **env** is the object of type [`Env`].
+///
+/// * **info** is the object of type [`MessageInfo`].
+///
+/// * **sender** is the object of type [`Addr`]. Sets where liquidity will be withdrawn.
+///
+/// * **amount** is the object of type [`Uint128`]. Sets the withdrawal amount.
+pub fn withdraw_liquidity(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    sender: Addr,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let mut config: Config = CONFIG.load(deps.storage).unwrap();
+
+    if info.sender != config.pair_info.liquidity_token {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let (pools, total_share) = pool_info(deps.as_ref(), config.clone())?;
+    let refund_assets = get_share_in_assets(&pools, amount, total_share);
+
+    // Accumulate prices for oracle
+    let prices = accumulate_prices(env, &config, pools[0].amount, pools[1].amount)?;
+    save_cumulative_prices(deps.storage, &mut config, prices)?;
+
+    // update pool info
+    let messages: Vec<CosmosMsg> = vec![
+        refund_assets[0]
+            .clone()
+            .into_msg(&deps.querier, sender.clone())?,
+        refund_assets[1]
+            .clone()
+            .into_msg(&deps.querier, sender.clone())?,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: config.pair_info.liquidity_token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Burn { amount })?,
+            funds: vec![],
+        }),
+    ];
+
+    let attributes = vec![
+        attr("action", "withdraw_liquidity"),
+        attr("sender", sender.as_str()),
+        attr("withdrawn_share", amount.to_string()),
+        attr(
+            "refund_assets",
+            format!("{}, {}", refund_assets[0], refund_assets[1]),
+        ),
+    ];
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .set_data(to_binary(&WithdrawResponse {
+            refund_assets,
+            withdrawn_share: amount,
+        })?)
+        .add_attributes(attributes))
+}
+
+/// ## Description
+/// Returns the share of assets.
+/// ## Params
+/// * **pools** are an array of [`Asset`] type items.
+///
+/// * **amount** is the object of type [`Uint128`].
+///
+/// * **total_share** is the object of type [`Uint128`].
+pub fn get_share_in_assets(pools: &[Asset], amount: Uint128, total_share: Uint128) -> Vec<Asset> {
+    let mut share_ratio = Decimal::zero();
+    if !total_share.is_zero() {
+        share_ratio = Decimal::from_ratio(amount, total_share);
+    }
+
+    pools
+        .iter()
+        .map(|a| Asset {
+            info: a.info.clone(),
+            amount: a.amount * share_ratio,
+        })
+        .collect()
+}
+
+/// ## Description
+/// Performs an swap operation with the specified parameters. CONTRACT - a user must do token approval.
+/// Returns an [`ContractError`] on failure, otherwise returns the [`Response`] with the specified attributes if the operation was successful.
+/// ## Params
+/// * **deps** is the object of type [`DepsMut`].
+///
+/// * **env** is the object of type [`Env`].
+///
+/// * **info** is the object of type [`MessageInfo`].
+///
+/// * **sender** is the object of type [`Addr`]. Sets the default recipient of the swap operation.
+///
+/// * **offer_asset** is the object of type [`Asset`]. Proposed asset for swapping.
+///
+/// * **belief_price** is the object of type [`Option<Decimal>`]. Used to calculate the maximum spread.
+///
+/// * **max_spread** is the object of type [`Option<Decimal>`]. Sets the maximum spread of the swap operation.
+///
+/// * **to** is the object of type [`Option<Addr>`]. Sets the recipient of the swap operation.
+///
+/// * **minimum_receive** is the object of type [`Option<Uint128>`]. Sets the minimum amount of the
+/// ask asset to receive.
+///
+/// * **deadline** is the object of type [`Option<u64>`]. Sets the block time after which the swap fails.
+#[allow(clippy::too_many_arguments)]
+pub fn swap(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    sender: Addr,
+    offer_asset: Asset,
+    belief_price: Option<Decimal>,
+    max_spread: Option<Decimal>,
+    to: Option<Addr>,
+    minimum_receive: Option<Uint128>,
+    deadline: Option<u64>,
+) -> Result<Response, ContractError> {
+    assert_deadline(&env, deadline)?;
+    offer_asset.assert_sent_native_token_balance(&info)?;
+
+    let mut config: Config = CONFIG.load(deps.storage)?;
+    assert_not_paused(
+        &deps.querier,
+        config.factory_addr.clone(),
+        env.contract.address.clone(),
+    )?;
+
+    // If the asset balance is already increased
+    // To calculated properly we should subtract user deposit from the pool
+    let pools: Vec<Asset> = config
+        .pair_info
+        .query_pools(&deps.querier, env.clone().contract.address)?
+        .iter()
+        .map(|p| {
+            let mut p = p.clone();
+            if p.info.equal(&offer_asset.info) {
+                p.amount = p.amount.checked_sub(offer_asset.amount).unwrap();
+            }
+
+            p
+        })
+        .collect();
+
+    let (offer_index, ask_index) = select_pools(&offer_asset.info, &pools)?;
+    let ask_pool = pools[ask_index].clone();
+
+    // Get fee info from factory
+    let fee_info = query_fee_info(
+        &deps.querier,
+        config.factory_addr.clone(),
+        config.pair_info.pair_type.clone(),
+        Some(config.pair_info.contract_addr.clone()),
+    )?;
+
+    let offer_amount = offer_asset.amount;
+    let (return_amount, spread_amount, commission_amount) = compute_swap(
+        pools[offer_index].amount,
+        config.weights[offer_index],
+        ask_pool.amount,
+        config.weights[ask_index],
+        offer_amount,
+        fee_info.total_fee_rate,
+    )?;
+
+    // check max spread limit if exist
+    assert_max_spread(
+        belief_price,
+        max_spread,
+        offer_amount,
+        return_amount + commission_amount,
+        spread_amount,
+    )?;
+
+    // compute tax
+    let return_asset = Asset {
+        info: ask_pool.info.clone(),
+        amount: return_amount,
+    };
+
+    let tax_amount = return_asset.compute_tax(&deps.querier)?;
+    if return_amount.checked_sub(tax_amount)? < minimum_receive.unwrap_or_default() {
+        return Err(ContractError::MinimumReceiveAssertion {});
+    }
+
+    let receiver = to.unwrap_or_else(|| sender.clone());
+    let mut messages: Vec<CosmosMsg> =
+        vec![return_asset.into_msg(&deps.querier, receiver.clone())?];
+
+    // Maker fee
+    let mut maker_fee_amount = Uint128::new(0);
+    if let Some(fee_address) = fee_info.fee_address {
+        if let Some(f) = calculate_maker_fee(
+            ask_pool.info.clone(),
+            commission_amount,
+            fee_info.maker_fee_rate,
+        ) {
+            messages.push(f.clone().into_msg(&deps.querier, fee_address)?);
+            maker_fee_amount = f.amount;
+        }
+    }
+
+    // Accumulate prices for oracle
+    let prices = accumulate_prices(env, &config, pools[0].amount, pools[1].amount)?;
+    save_cumulative_prices(deps.storage, &mut config, prices)?;
+
+    Ok(Response::new()
+        .add_messages(
+            // 1. send collateral token from the contract to a user
+            // 2. send inactive commission to collector
+            messages,
+        )
+        .set_data(to_binary(&SwapResponse {
+            offer_asset: offer_asset.clone(),
+            return_asset: Asset {
+                info: ask_pool.info.clone(),
+                amount: return_amount,
+            },
+            tax_amount,
+            spread_amount,
+            commission_amount,
+            maker_fee_amount,
+        })?)
+        .add_attribute("action", "swap")
+        .add_attribute("sender", sender.as_str())
+        .add_attribute("receiver", receiver.as_str())
+        .add_attribute("offer_asset", offer_asset.info.to_string())
+        .add_attribute("ask_asset", ask_pool.info.to_string())
+        .add_attribute("offer_amount", offer_amount.to_string())
+        .add_attribute("return_amount", return_amount.to_string())
+        .add_attribute("tax_amount", tax_amount.to_string())
+        .add_attribute("spread_amount", spread_amount.to_string())
+        .add_attribute("commission_amount", commission_amount.to_string())
+        .add_attribute("maker_fee_amount", maker_fee_amount.to_string()))
+}
+
+/// ## Description
+/// Returns the indexes of the offer and ask pools.
+/// ## Params
+/// * **offer_asset_info** is the object of type [`AssetInfo`]. Sets the offer asset.
+///
+/// * **pools** are a slice of [`Asset`] type items. Sets the pool balances.
+fn select_pools(
+    offer_asset_info: &AssetInfo,
+    pools: &[Asset],
+) -> Result<(usize, usize), ContractError> {
+    if offer_asset_info.equal(&pools[0].info) {
+        Ok((0, 1))
+    } else if offer_asset_info.equal(&pools[1].info) {
+        Ok((1, 0))
+    } else {
+        Err(ContractError::AssetMismatch {})
+    }
+}
+
+/// ## Description
+/// Lends the specified pool assets to the sender within the transaction. Sends the assets to the
+/// sender, executes the `callback` message on the sender contract and then the
+/// `AssertFlashLoanRepaid` callback which checks that the loan was repaid with the fee.
+/// The pair is locked until the loan is repaid.
+/// Returns an [`ContractError`] on failure, otherwise returns the [`Response`] with the specified attributes if the operation was successful.
+/// ## Params
+/// * **deps** is the object of type [`DepsMut`].
+///
+/// * **env** is the object of type [`Env`].
+///
+/// * **info** is the object of type [`MessageInfo`].
+///
+/// * **assets** is a vector of [`Asset`] type items. Sets the assets to borrow.
+///
+/// * **callback** is the object of type [`Binary`]. Sets the message executed on the borrower contract.
+pub fn flash_loan(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    assets: Vec<Asset>,
+    callback: Binary,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    assert_not_paused(
+        &deps.querier,
+        config.factory_addr.clone(),
+        env.contract.address.clone(),
+    )?;
+
+    let pools: Vec<Asset> = config
+        .pair_info
+        .query_pools(&deps.querier, env.contract.address.clone())?;
+
+    // Get fee info from factory
+    let fee_info = query_fee_info(
+        &deps.querier,
+        config.factory_addr.clone(),
+        config.pair_info.pair_type.clone(),
+        Some(config.pair_info.contract_addr.clone()),
+    )?;
+
+    Ok(flash_loan::flash_loan(
+        deps,
+        &env,
+        &info,
+        &pools,
+        assets,
+        &fee_info,
+        callback,
+        |expected_balances, fees| {
+            to_binary(&ExecuteMsg::AssertFlashLoanRepaid {
+                expected_balances,
+                fees,
+            })
+        },
+    )?)
+}
+
+/// ## Description
+/// Checks that the pool balances reached the expected ones, sends the Maker its share of the
+/// flash loan fees and unlocks the pair. Only the pair itself can execute it.
+/// Returns an [`ContractError`] on failure, otherwise returns the [`Response`] with the specified attributes if the operation was successful.
+/// ## Params
+/// * **deps** is the object of type [`DepsMut`].
+///
+/// * **env** is the object of type [`Env`].
+///
+/// * **info** is the object of type [`MessageInfo`].
+///
+/// * **expected_balances** is a vector of [`Asset`] type items. Sets the pool balances required once the loan is repaid.
+///
+/// * **fees** is a vector of [`Asset`] type items. Sets the flash loan fees.
+pub fn assert_flash_loan_repaid(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    expected_balances: Vec<Asset>,
+    fees: Vec<Asset>,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // Get fee info from factory
+    let fee_info = query_fee_info(
+        &deps.querier,
+        config.factory_addr.clone(),
+        config.pair_info.pair_type.clone(),
+        Some(config.pair_info.contract_addr.clone()),
+    )?;
+
+    Ok(flash_loan::assert_flash_loan_repaid(
+        deps,
+        &env,
+        &info,
+        expected_balances,
+        fees,
+        &fee_info,
+    )?)
+}
+
+/// ## Description
+/// Shifts block_time when any price is zero to not fill an accumulator with a new price to that period.
+/// The prices are the spot prices of the weighted pool.
+/// ## Params
+/// * **env** is the object of type [`Env`].
+///
+/// * **config** is the object of type [`Config`].
+///
+/// * **x** is the balance of asset[0] within a pool
+///
+/// * **y** is the balance of asset[1] within a pool
+pub fn accumulate_prices(
+    env: Env,
+    config: &Config,
+    x: Uint128,
+    y: Uint128,
+) -> StdResult<Option<(Uint128, Uint128, u64)>> {
+    let block_time = env.block.time.seconds();
+    if block_time <= config.block_time_last {
+        return Ok(None);
+    }
+
+    // we have to shift block_time when any price is zero to not fill an accumulator with a new price to that period
+
+    let time_elapsed = Uint128::from(block_time - config.block_time_last);
+
+    let mut pcl0 = config.price0_cumulative_last;
+    let mut pcl1 = config.price1_cumulative_last;
+
+    if !x.is_zero() && !y.is_zero() {
+        let price_precision = Uint128::from(10u128.pow(TWAP_PRECISION.into()));
+        let time_price = |spot_price: Uint256| -> StdResult<Uint128> {
+            let time_price = spot_price
+                .checked_mul(time_elapsed.checked_mul(price_precision)?.into())?
+                .checked_div(Uint256::from(WEIGHT_PRECISION))?;
+            Uint128::try_from(time_price).map_err(|err| StdError::generic_err(err.to_string()))
+        };
+
+        pcl0 = config
+            .price0_cumulative_last
+            .wrapping_add(time_price(calc_spot_price(
+                x,
+                config.weights[0],
+                y,
+                config.weights[1],
+            )?)?);
+        pcl1 = config
+            .price1_cumulative_last
+            .wrapping_add(time_price(calc_spot_price(
+                y,
+                config.weights[1],
+                x,
+                config.weights[0],
+            )?)?);
+    };
+
+    Ok(Some((pcl0, pcl1, block_time)))
+}
+
+/// ## Description
+/// Saves the cumulative prices returned by [`accumulate_prices`] in the config and records them as a
+/// new price observation. Does nothing if the prices were already accumulated in the current block.
+/// ## Params
+/// * **storage** is the object of type [`Storage`].
+///
+/// * **config** is the object of type [`Config`]. This is the pair config to update.
+///
+/// * **prices** is an [`Option`] with the cumulative prices and the block time returned by
+/// [`accumulate_prices`].
+pub fn save_cumulative_prices(
+    storage: &mut dyn Storage,
+    config: &mut Config,
+    prices: Option<(Uint128, Uint128, u64)>,
+) -> StdResult<()> {
+    if let Some((price0_cumulative_last, price1_cumulative_last, block_time)) = prices {
+        config.price0_cumulative_last = price0_cumulative_last;
+        config.price1_cumulative_last = price1_cumulative_last;
+        config.block_time_last = block_time;
+        CONFIG.save(storage, config)?;
+        store_observation(
+            storage,
+            &Observation {
+                timestamp: block_time,
+                price0_cumulative: price0_cumulative_last,
+                price1_cumulative: price1_cumulative_last,
+            },
+        )?;
+    }
+
+    Ok(())
+}
+
+/// ## Description
+/// Calculates the maker commission according to the specified parameters.
+/// Returns an [`None`] if maker fee is zero, otherwise returns the [`Asset`] with the specified attributes.
+/// ## Params
+/// * **pool_info** is the object of type [`AssetInfo`]. Information about the pool for which the commission will be calculated.
+///
+/// * **commission_amount** is the object of type [`Env`]. Sets the commission amount for the pool.
+///
+/// * **maker_commission_rate** is the object of type [`MessageInfo`]. Sets the maker commission rate for the pool.
+pub fn calculate_maker_fee(
+    pool_info: AssetInfo,
+    commission_amount: Uint128,
+    maker_commission_rate: Decimal,
+) -> Option<Asset> {
+    let maker_fee: Uint128 = commission_amount * maker_commission_rate;
+    if maker_fee.is_zero() {
+        return None;
+    }
+
+    Some(Asset {
+        info: pool_info,
+        amount: maker_fee,
+    })
+}
+
+/// ## Description
+/// Available the query messages of the contract.
+/// ## Params
+/// * **deps** is the object of type [`Deps`].
+///
+/// * **_env** is the object of type [`Env`].
+///
+/// * **msg** is the object of type [`QueryMsg`].
+///
+/// ## Queries
+/// * **QueryMsg::Pair {}** Returns information about a pair in an object of type [`PairInfo`].
+///
+/// * **QueryMsg::Pool {}** Returns information about a pool in an object of type [`PoolResponse`].
+///
+/// * **QueryMsg::Share { amount }** Returns information about the share of the pool in a vector
+/// that contains objects of type [`Asset`].
+///
+/// * **QueryMsg::Simulation { offer_asset }** Returns information about the simulation of the
+/// swap in a [`SimulationResponse`] object.
+///
+/// * **QueryMsg::ReverseSimulation { ask_asset }** Returns information about the reverse simulation
+/// in a [`ReverseSimulationResponse`] object.
+///
+/// * **QueryMsg::CumulativePrices {}** Returns information about the cumulative prices in a
+/// [`CumulativePricesResponse`] object.
+///
+/// * **QueryMsg::Config {}** Returns information about the controls settings in a
+/// [`ConfigResponse`] object.
+///
+/// * **QueryMsg::SimulateWithdraw { lp_amount, withdraw_type }** Returns the assets returned and
+/// the LP tokens burned for a pro-rata withdrawal in a [`SimulateWithdrawResponse`] object.
+///
+/// * **QueryMsg::SimulateProvide { assets }** Returns the amount of LP tokens minted for providing
+/// the specified assets.
+///
+/// * **QueryMsg::Observe { seconds_ago }** Returns the cumulative prices at each of the specified
+/// number of seconds before the current block in an [`ObserveResponse`] object.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Pair {} => to_binary(&query_pair_info(deps)?),
+        QueryMsg::Pool {} => to_binary(&query_pool(deps)?),
+        QueryMsg::Share { amount } => to_binary(&query_share(deps, amount)?),
+        QueryMsg::Simulation { offer_asset, .. } => {
+            to_binary(&query_simulation(deps, offer_asset)?)
+        }
+        QueryMsg::ReverseSimulation { ask_asset, .. } => {
+            to_binary(&query_reverse_simulation(deps, ask_asset)?)
+        }
+        QueryMsg::CumulativePrices {} => to_binary(&query_cumulative_prices(deps, env)?),
+        QueryMsg::Config {} => to_binary(&query_config(deps, env)?),
+        QueryMsg::SimulateWithdraw {
+            lp_amount,
+            withdraw_type,
+        } => to_binary(&query_simulate_withdraw(deps, lp_amount, withdraw_type)?),
+        QueryMsg::SimulateProvide { assets } => to_binary(&query_simulate_provide(deps, assets)?),
+        QueryMsg::Observe { seconds_ago } => to_binary(&query_observe(deps, env, seconds_ago)?),
+    }
+}
+
+/// ## Description
+/// Returns information about a pair in an object of type [`PairInfo`].
+/// ## Params
+/// * **deps** is the object of type [`Deps`].
+pub fn query_pair_info(deps: Deps) -> StdResult<PairInfo> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    Ok(config.pair_info)
+}
+
+/// ## Description
+/// Returns information about a pool in an object of type [`PoolResponse`].
+/// ## Params
+/// * **deps** is the object of type [`Deps`].
+pub fn query_pool(deps: Deps) -> StdResult<PoolResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let (assets, total_share) = pool_info(deps, config)?;
+
+    let resp = PoolResponse {
+        assets,
+        total_share,
+    };
+
+    Ok(resp)
+}
+
+/// ## Description
+/// Returns information about the share of the pool in a vector that contains objects of type [`Asset`].
+/// ## Params
+/// * **deps** is the object of type [`Deps`].
+///
+/// * **amount** is the object of type [`Uint128`]. Sets the amount for which a share in the pool will be requested.
+pub fn query_share(deps: Deps, amount: Uint128) -> StdResult<Vec<Asset>> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let (pools, total_share) = pool_info(deps, config)?;
+    let refund_assets = get_share_in_assets(&pools, amount, total_share);
+
+    Ok(refund_assets)
+}
+
+/// ## Description
+/// Returns the assets returned and the LP tokens burned for a withdrawal in a [`SimulateWithdrawResponse`] object.
+/// Only pro-rata withdrawals are supported.
+/// ## Params
+/// * **deps** is the object of type [`Deps`].
+///
+/// * **lp_amount** is the object of type [`Uint128`]. Sets the amount of LP tokens sent for the withdrawal.
+///
+/// * **withdraw_type** is the object of type [`Option<WithdrawType>`]. Must be empty.
+pub fn query_simulate_withdraw(
+    deps: Deps,
+    lp_amount: Uint128,
+    withdraw_type: Option<WithdrawType>,
+) -> StdResult<SimulateWithdrawResponse> {
+    if withdraw_type.is_some() {
+        return Err(StdError::generic_err(
+            ContractError::NonSupported {}.to_string(),
+        ));
+    }
+
+    Ok(SimulateWithdrawResponse {
+        assets: query_share(deps, lp_amount)?,
+        burn_amount: lp_amount,
+    })
+}
+
+/// ## Description
+/// Returns the amount of LP tokens minted for providing the specified assets.
+/// ## Params
+/// * **deps** is the object of type [`Deps`].
+///
+/// * **assets** is a vector of [`Asset`] type items. Sets the assets to provide.
+pub fn query_simulate_provide(deps: Deps, assets: Vec<Asset>) -> StdResult<Uint128> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let (pools, total_share) = pool_info(deps, config)?;
+
+    get_deposits(&assets, &pools)
+        .and_then(|deposits| compute_share(total_share, &deposits, &pools))
+        .map_err(|err| StdError::generic_err(err.to_string()))
+}
+
+/// ## Description
+/// Returns information about the simulation of the swap in a [`SimulationResponse`] object.
+/// ## Params
+/// * **deps** is the object of type [`Deps`].
+///
+/// * **offer_asset** is the object of type [`Asset`].
+pub fn query_simulation(deps: Deps, offer_asset: Asset) -> StdResult<SimulationResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let contract_addr = config.pair_info.contract_addr.clone();
+
+    let pools: Vec<Asset> = config.pair_info.query_pools(&deps.querier, contract_addr)?;
+
+    let (offer_index, ask_index) = select_pools(&offer_asset.info, &pools)
+        .map_err(|_| StdError::generic_err("Given offer asset doesn't belong to pairs"))?;
+
+    // Get fee info from factory
+    let fee_info = query_fee_info(
+        &deps.querier,
+        config.factory_addr,
+        config.pair_info.pair_type,
+        Some(config.pair_info.contract_addr),
+    )?;
+
+    let (return_amount, spread_amount, commission_amount) = compute_swap(
+        pools[offer_index].amount,
+        config.weights[offer_index],
+        pools[ask_index].amount,
+        config.weights[ask_index],
+        offer_asset.amount,
+        fee_info.total_fee_rate,
+    )
+    .map_err(|err| StdError::generic_err(err.to_string()))?;
+
+    Ok(SimulationResponse {
+        return_amount,
+        spread_amount,
+        commission_amount,
+        fee_rate: fee_info.total_fee_rate,
+    })
+}
+
+/// ## Description
+/// Returns information about the reverse simulation in a [`ReverseSimulationResponse`] object.
+/// ## Params
+/// * **deps** is the object of type [`Deps`].
+///
+/// * **ask_asset** is the object of type [`Asset`].
+pub fn query_reverse_simulation(
+    deps: Deps,
+    ask_asset: Asset,
+) -> StdResult<ReverseSimulationResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let contract_addr = config.pair_info.contract_addr.clone();
+
+    let pools: Vec<Asset> = config.pair_info.query_pools(&deps.querier, contract_addr)?;
+
+    let (ask_index, offer_index) = select_pools(&ask_asset.info, &pools)
+        .map_err(|_| StdError::generic_err("Given ask asset doesn't belong to pairs"))?;
+
+    // Get fee info from factory
+    let fee_info = query_fee_info(
+        &deps.querier,
+        config.factory_addr,
+        config.pair_info.pair_type,
+        Some(config.pair_info.contract_addr),
+    )?;
+
+    let (offer_amount, spread_amount, commission_amount) = compute_offer_amount(
+        pools[offer_index].amount,
+        config.weights[offer_index],
+        pools[ask_index].amount,
+        config.weights[ask_index],
+        ask_asset.amount,
+        fee_info.total_fee_rate,
+    )
+    .map_err(|err| StdError::generic_err(err.to_string()))?;
+
+    Ok(ReverseSimulationResponse {
+        offer_amount,
+        spread_amount,
+        commission_amount,
+    })
+}
+
+/// ## Description
+/// Returns information about the cumulative prices in a [`CumulativePricesResponse`] object.
+/// ## Params
+/// * **deps** is the object of type [`Deps`].
+///
+/// * **env** is the object of type [`Env`].
+pub fn query_cumulative_prices(deps: Deps, env: Env) -> StdResult<CumulativePricesResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let (assets, total_share) = pool_info(deps, config.clone())?;
+
+    let mut price0_cumulative_last = config.price0_cumulative_last;
+    let mut price1_cumulative_last = config.price1_cumulative_last;
+
+    if let Some((price0_cumulative_new, price1_cumulative_new, _)) =
+        accumulate_prices(env, &config, assets[0].amount, assets[1].amount)?
+    {
+        price0_cumulative_last = price0_cumulative_new;
+        price1_cumulative_last = price1_cumulative_new;
+    }
+
+    let cumulative_prices = vec![
+        (
+            assets[0].info.clone(),
+            assets[1].info.clone(),
+            price0_cumulative_last,
+        ),
+        (
+            assets[1].info.clone(),
+            assets[0].info.clone(),
+            price1_cumulative_last,
+        ),
+    ];
+
+    let resp = CumulativePricesResponse {
+        assets,
+        total_share,
+        price0_cumulative_last,
+        price1_cumulative_last,
+        cumulative_prices,
+    };
+
+    Ok(resp)
+}
+
+/// ## Description
+/// Returns the cumulative prices at each of the specified number of seconds before the current
+/// block in an [`ObserveResponse`] object.
+/// ## Params
+/// * **deps** is the object of type [`Deps`].
+///
+/// * **env** is the object of type [`Env`].
+///
+/// * **seconds_ago** is a vector of type [`u64`]. These are the numbers of seconds before the current block.
+pub fn query_observe(deps: Deps, env: Env, seconds_ago: Vec<u64>) -> StdResult<ObserveResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let (assets, _) = pool_info(deps, config.clone())?;
+
+    let mut current = Observation {
+        timestamp: config.block_time_last,
+        price0_cumulative: config.price0_cumulative_last,
+        price1_cumulative: config.price1_cumulative_last,
+    };
+
+    if let Some((price0_cumulative_new, price1_cumulative_new, block_time)) =
+        accumulate_prices(env, &config, assets[0].amount, assets[1].amount)?
+    {
+        current = Observation {
+            timestamp: block_time,
+            price0_cumulative: price0_cumulative_new,
+            price1_cumulative: price1_cumulative_new,
+        };
+    }
+
+    let observations = seconds_ago
+        .into_iter()
+        .map(|seconds_ago| observe(deps.storage, &current, seconds_ago))
+        .collect::<StdResult<Vec<Observation>>>()?;
+
+    Ok(ObserveResponse { observations })
+}
+
+/// ## Description
+/// Returns information about the controls settings in a [`ConfigResponse`] object.
+/// The `params` field contains the normalized weights of the pool assets.
+/// ## Params
+/// * **deps** is the object of type [`Deps`].
+///
+/// * **env** is the object of type [`Env`].
+pub fn query_config(deps: Deps, env: Env) -> StdResult<ConfigResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        block_time_last: config.block_time_last,
+        params: Some(to_binary(&WeightedPoolConfig {
+            weights: config.weights,
+        })?),
+        paused: query_pair_paused(&deps.querier, config.factory_addr, env.contract.address)?,
+    })
+}
+
+/// ## Description
+/// Returns computed swap for the pool with specified parameters. The offer amount must not
+/// exceed half of the offer pool.
+/// ## Params
+/// * **offer_pool** is the object of type [`Uint128`]. Sets the offer pool.
+///
+/// * **offer_weight** is the object of type [`Decimal`]. Sets the weight of the offer pool.
+///
+/// * **ask_pool** is the object of type [`Uint128`]. Sets the ask pool.
+///
+/// * **ask_weight** is the object of type [`Decimal`]. Sets the weight of the ask pool.
+///
+/// * **offer_amount** is the object of type [`Uint128`]. Sets the offer amount.
+///
+/// * **commission_rate** is the object of type [`Decimal`]. Sets the commission rate.
+pub fn compute_swap(
+    offer_pool: Uint128,
+    offer_weight: Decimal,
+    ask_pool: Uint128,
+    ask_weight: Decimal,
+    offer_amount: Uint128,
+    commission_rate: Decimal,
+) -> Result<(Uint128, Uint128, Uint128), ContractError> {
+    // offer => ask
+    if exceeds_ratio(offer_amount, offer_pool, MAX_IN_RATIO)? {
+        return Err(ContractError::MaxInRatioAssertion {});
+    }
+
+    let return_amount =
+        calc_ask_amount(offer_pool, offer_weight, ask_pool, ask_weight, offer_amount)?;
+
+    // calculate spread & commission
+    let spot_price = calc_spot_price(offer_pool, offer_weight, ask_pool, ask_weight)?;
+    let expected_return = Uint128::try_from(
+        Uint256::from(offer_amount).multiply_ratio(spot_price, Uint256::from(WEIGHT_PRECISION)),
+    )
+    .map_err(|err| StdError::generic_err(err.to_string()))?;
+    let spread_amount = expected_return.saturating_sub(return_amount);
+    let commission_amount: Uint128 = return_amount * commission_rate;
+
+    // commission will be absorbed to pool
+    let return_amount: Uint128 = return_amount.checked_sub(commission_amount)?;
+
+    Ok((return_amount, spread_amount, commission_amount))
+}
+
+/// ## Description
+/// Returns computed offer amount for the pool with specified parameters. The ask amount before
+/// the commission deduction must not exceed a third of the ask pool.
+/// ## Params
+/// * **offer_pool** is the object of type [`Uint128`]. Sets the offer pool.
+///
+/// * **offer_weight** is the object of type [`Decimal`]. Sets the weight of the offer pool.
+///
+/// * **ask_pool** is the object of type [`Uint128`]. Sets the ask pool.
+///
+/// * **ask_weight** is the object of type [`Decimal`]. Sets the weight of the ask pool.
+///
+/// * **ask_amount** is the object of type [`Uint128`]. Sets the ask amount.
+///
+/// * **commission_rate** is the object of type [`Decimal`]. Sets the commission rate.
+pub fn compute_offer_amount(
+    offer_pool: Uint128,
+    offer_weight: Decimal,
+    ask_pool: Uint128,
+    ask_weight: Decimal,
+    ask_amount: Uint128,
+    commission_rate: Decimal,
+) -> Result<(Uint128, Uint128, Uint128), ContractError> {
+    // ask => offer
+    let one_minus_commission = Decimal256::one() - Decimal256::from(commission_rate);
+    let inv_one_minus_commission: Decimal = (Decimal256::one() / one_minus_commission).into();
+    let before_commission_deduction = ask_amount * inv_one_minus_commission;
+
+    if exceeds_ratio(before_commission_deduction, ask_pool, MAX_OUT_RATIO)? {
+        return Err(ContractError::MaxOutRatioAssertion {});
+    }
+
+    let offer_amount = calc_offer_amount(
+        offer_pool,
+        offer_weight,
+        ask_pool,
+        ask_weight,
+        before_commission_deduction,
+    )?;
+
+    let spot_price = calc_spot_price(offer_pool, offer_weight, ask_pool, ask_weight)?;
+    let expected_return = Uint128::try_from(
+        Uint256::from(offer_amount).multiply_ratio(spot_price, Uint256::from(WEIGHT_PRECISION)),
+    )
+    .map_err(|err| StdError::generic_err(err.to_string()))?;
+    let spread_amount = expected_return.saturating_sub(before_commission_deduction);
+    let commission_amount = before_commission_deduction * commission_rate;
+
+    Ok((offer_amount, spread_amount, commission_amount))
+}
+
+/// ## Description
+/// Returns an [`ContractError`] on failure, otherwise if `belief_price` and `max_spread` both are given, we compute new spread else we just use swap
+/// spread to check `max_spread`.
+/// ## Params
+/// * **belief_price** is the object of type [`Option<Decimal>`]. Sets the belief price.
+///
+/// * **max_spread** is the object of type [`Option<Decimal>`]. Sets the maximum spread.
+///
+/// * **offer_amount** is the object of type [`Uint128`]. Sets the offer amount.
+///
+/// * **return_amount** is the object of type [`Uint128`]. Sets the return amount.
+///
+/// * **spread_amount** is the object of type [`Uint128`]. Sets the spread amount.
+pub fn assert_max_spread(
+    belief_price: Option<Decimal>,
+    max_spread: Option<Decimal>,
+    offer_amount: Uint128,
+    return_amount: Uint128,
+    spread_amount: Uint128,
+) -> Result<(), ContractError> {
+    let default_spread = Decimal::from_str(DEFAULT_SLIPPAGE)?;
+    let max_allowed_spread = Decimal::from_str(MAX_ALLOWED_SLIPPAGE)?;
+
+    let max_spread = max_spread.unwrap_or(default_spread);
+    if max_spread.gt(&max_allowed_spread) {
+        return Err(ContractError::AllowedSpreadAssertion {});
+    }
+
+    if let Some(belief_price) = belief_price {
+        let expected_return =
+            offer_amount * Decimal::from(Decimal256::one() / Decimal256::from(belief_price));
+        let spread_amount = expected_return
+            .checked_sub(return_amount)
+            .unwrap_or_else(|_| Uint128::zero());
+
+        if return_amount < expected_return
+            && Decimal::from_ratio(spread_amount, expected_return) > max_spread
+        {
+            return Err(ContractError::MaxSpreadAssertion {});
+        }
+    } else if Decimal::from_ratio(spread_amount, return_amount + spread_amount) > max_spread {
+        return Err(ContractError::MaxSpreadAssertion {});
+    }
+
+    Ok(())
+}
+
+/// ## Description
+/// Ensures each prices are not dropped as much as slippage tolerance rate.
+/// Returns an [`ContractError`] on failure, otherwise returns [`Ok`].
+/// ## Params
+/// * **slippage_tolerance** is the object of type [`Option<Decimal>`].
+///
+/// * **deposits** are an array of [`Uint128`] type items.
+///
+/// * **pools** are an array of [`Asset`] type items.
+fn assert_slippage_tolerance(
+    slippage_tolerance: Option<Decimal>,
+    deposits: &[Uint128; 2],
+    pools: &[Asset],
+) -> Result<(), ContractError> {
+    let default_slippage = Decimal::from_str(DEFAULT_SLIPPAGE)?;
+    let max_allowed_slippage = Decimal::from_str(MAX_ALLOWED_SLIPPAGE)?;
+
+    let slippage_tolerance = slippage_tolerance.unwrap_or(default_slippage);
+    if slippage_tolerance.gt(&max_allowed_slippage) {
+        return Err(ContractError::AllowedSpreadAssertion {});
+    }
+
+    let slippage_tolerance: Decimal256 = slippage_tolerance.into();
+    let one_minus_slippage_tolerance = Decimal256::one() - slippage_tolerance;
+
+    // Ensure each prices are not dropped as much as slippage tolerance rate
+    if Decimal256::from_ratio(deposits[0].u128(), deposits[1].u128()) * one_minus_slippage_tolerance
+        > Decimal256::from_ratio(pools[0].amount.u128(), pools[1].amount.u128())
+        || Decimal256::from_ratio(deposits[1].u128(), deposits[0].u128())
+            * one_minus_slippage_tolerance
+            > Decimal256::from_ratio(pools[1].amount.u128(), pools[0].amount.u128())
+    {
+        return Err(ContractError::MaxSlippageAssertion {});
+    }
+
+    Ok(())
+}
+
+/// ## Description
+/// Used for migration of contract. Returns the default object of type [`Response`].
+/// ## Params
+/// * **_deps** is the object of type [`DepsMut`].
+///
+/// * **_env** is the object of type [`Env`].
+///
+/// * **_msg** is the object of type [`MigrateMsg`].
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+/// ## Description
+/// Returns information about the pool.
+/// ## Params
+/// * **deps** is the object of type [`Deps`].
+///
+/// * **config** is the object of type [`Config`].
+pub fn pool_info(deps: Deps, config: Config) -> StdResult<(Vec<Asset>, Uint128)> {
+    let contract_addr = config.pair_info.contract_addr.clone();
+    let pools: Vec<Asset> = config.pair_info.query_pools(&deps.querier, contract_addr)?;
+    let total_share: Uint128 = query_supply(&deps.querier, config.pair_info.liquidity_token)?;
+
+    Ok((pools, total_share))
+}
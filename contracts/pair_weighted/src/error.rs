@@ -0,0 +1,114 @@
+use crate::math::MIN_WEIGHT_PERCENT;
+use astroport::flash_loan::FlashLoanError;
+use astroport::pair::DeadlineExpired;
+use astroport::querier::PauseError;
+use cosmwasm_std::{OverflowError, StdError};
+use thiserror::Error;
+
+/// ## Description
+/// This enum describes pair weighted contract errors!
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Operation non supported")]
+    NonSupported {},
+
+    #[error("Event of zero transfer")]
+    InvalidZeroAmount {},
+
+    #[error("Insufficient amount of Liquidity")]
+    LiquidityAmountTooSmall {},
+
+    #[error("Operation exceeds max spread limit")]
+    MaxSpreadAssertion {},
+
+    #[error("Provided spread amount exceeds allowed limit")]
+    AllowedSpreadAssertion {},
+
+    #[error("Operation exceeds max splippage tolerance")]
+    MaxSlippageAssertion {},
+
+    #[error("The return amount is less than the minimum receive amount")]
+    MinimumReceiveAssertion {},
+
+    #[error("The minted LP amount is less than the minimum LP amount to receive")]
+    MinLpToReceiveAssertion {},
+
+    #[error("The deadline has passed")]
+    DeadlineExpired {},
+
+    #[error("Doubling assets in asset infos")]
+    DoublingAssets {},
+
+    #[error("The pool must consist of exactly 2 assets")]
+    InvalidNumberOfAssets {},
+
+    #[error("Asset mismatch between the requested and stored in contract")]
+    AssetMismatch {},
+
+    #[error("GeneratorAddress is not set in factory. Cannot autostake")]
+    AutoStakeError {},
+
+    #[error("You need to provide init params")]
+    InitParamsNotFound {},
+
+    #[error(
+        "A weight must be set for every asset and be at least {}% of the total weight",
+        MIN_WEIGHT_PERCENT
+    )]
+    InvalidWeights {},
+
+    #[error("The offer amount must not exceed half of the offer pool")]
+    MaxInRatioAssertion {},
+
+    #[error("The ask amount must not exceed a third of the ask pool")]
+    MaxOutRatioAssertion {},
+
+    #[error("A flash loan is in progress")]
+    FlashLoanInProgress {},
+
+    #[error("The flash loan was not repaid with the fee")]
+    FlashLoanNotRepaid {},
+
+    #[error("The pair is paused")]
+    Paused {},
+}
+
+impl From<OverflowError> for ContractError {
+    fn from(o: OverflowError) -> Self {
+        StdError::from(o).into()
+    }
+}
+
+impl From<FlashLoanError> for ContractError {
+    fn from(err: FlashLoanError) -> Self {
+        match err {
+            FlashLoanError::Std(err) => ContractError::Std(err),
+            FlashLoanError::Unauthorized => ContractError::Unauthorized {},
+            FlashLoanError::InvalidZeroAmount => ContractError::InvalidZeroAmount {},
+            FlashLoanError::DoublingAssets => ContractError::DoublingAssets {},
+            FlashLoanError::AssetMismatch => ContractError::AssetMismatch {},
+            FlashLoanError::NotRepaid => ContractError::FlashLoanNotRepaid {},
+        }
+    }
+}
+
+impl From<DeadlineExpired> for ContractError {
+    fn from(_: DeadlineExpired) -> Self {
+        ContractError::DeadlineExpired {}
+    }
+}
+
+impl From<PauseError> for ContractError {
+    fn from(err: PauseError) -> Self {
+        match err {
+            PauseError::Std(err) => ContractError::Std(err),
+            PauseError::Paused => ContractError::Paused {},
+        }
+    }
+}
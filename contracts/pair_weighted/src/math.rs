@@ -0,0 +1,243 @@
+use cosmwasm_std::{Decimal, Fraction, StdError, StdResult, Uint128, Uint256};
+use std::convert::TryFrom;
+
+/// The fixed point precision of the numbers used in the weighted math
+pub const WEIGHT_PRECISION: u128 = 1_000_000_000_000_000_000;
+/// The minimum normalized weight of a pool asset, in percent
+pub const MIN_WEIGHT_PERCENT: u64 = 1;
+/// The maximum number of iterations of the series used to compute fractional powers
+const MAX_POW_ITERATIONS: u32 = 256;
+/// The precision at which the series used to compute fractional powers stops
+const POW_PRECISION: u128 = 100_000_000;
+/// The minimum power base. The series used to compute fractional powers converges slowly for
+/// bases close to zero
+const MIN_POW_BASE: u128 = WEIGHT_PRECISION / 2;
+/// The maximum power base. The series used to compute fractional powers converges slowly for
+/// bases close to two
+const MAX_POW_BASE: u128 = WEIGHT_PRECISION * 3 / 2;
+/// The maximum offer amount as a share of the offer pool in the fixed point representation. It
+/// keeps the power base of a swap above 2/3
+pub const MAX_IN_RATIO: u128 = WEIGHT_PRECISION / 2;
+/// The maximum ask amount as a share of the ask pool in the fixed point representation. It keeps
+/// the power base of a reverse swap below 3/2
+pub const MAX_OUT_RATIO: u128 = WEIGHT_PRECISION / 3;
+
+/// ## Description
+/// Returns one in the fixed point representation.
+fn one() -> Uint256 {
+    Uint256::from(WEIGHT_PRECISION)
+}
+
+/// ## Description
+/// Returns a [`Decimal`] in the fixed point representation.
+pub fn decimal_to_fixed(value: Decimal) -> Uint256 {
+    Uint256::from(value.numerator())
+}
+
+/// ## Description
+/// Returns true if the amount exceeds the specified share of the pool.
+/// ## Params
+/// * **amount** is the object of type [`Uint128`].
+///
+/// * **pool** is the object of type [`Uint128`].
+///
+/// * **max_ratio** is the object of type [`u128`]. The maximum share in the fixed point representation.
+pub fn exceeds_ratio(amount: Uint128, pool: Uint128, max_ratio: u128) -> StdResult<bool> {
+    Ok(Uint256::from(amount).checked_mul(one())?
+        > Uint256::from(pool).checked_mul(Uint256::from(max_ratio))?)
+}
+
+/// ## Description
+/// Returns the [`Uint256`] converted to [`Uint128`], or an overflow error.
+fn to_uint128(value: Uint256) -> StdResult<Uint128> {
+    Uint128::try_from(value).map_err(|_| StdError::generic_err("Weighted math overflow"))
+}
+
+/// ## Description
+/// Multiplies two fixed point numbers, rounding half up.
+fn mul(a: Uint256, b: Uint256) -> StdResult<Uint256> {
+    Ok(a.checked_mul(b)?
+        .checked_add(one() / Uint256::from(2u8))?
+        .checked_div(one())?)
+}
+
+/// ## Description
+/// Divides two fixed point numbers, rounding half up.
+fn div(a: Uint256, b: Uint256) -> StdResult<Uint256> {
+    Ok(a.checked_mul(one())?
+        .checked_add(b / Uint256::from(2u8))?
+        .checked_div(b)?)
+}
+
+/// ## Description
+/// Returns `a` raised to the integer power `n`.
+fn pow_int(mut a: Uint256, mut n: Uint256) -> StdResult<Uint256> {
+    let mut result = one();
+    while !n.is_zero() {
+        if !n.checked_rem(Uint256::from(2u8))?.is_zero() {
+            result = mul(result, a)?;
+        }
+        n /= Uint256::from(2u8);
+        if !n.is_zero() {
+            a = mul(a, a)?;
+        }
+    }
+    Ok(result)
+}
+
+/// ## Description
+/// Returns `base` raised to the fractional power `exp` (less than one) using the binomial series
+/// `(1 + x)^exp = 1 + exp * x + exp * (exp - 1) / 2! * x^2 + ...`.
+/// The series converges for a base between 0 and 2. With `|x| <= 1/2` every term is at most half
+/// of the previous one, so the sum of the omitted terms is less than the last computed term.
+fn pow_frac(base: Uint256, exp: Uint256) -> StdResult<Uint256> {
+    let (x, x_neg) = if base >= one() {
+        (base - one(), false)
+    } else {
+        (one() - base, true)
+    };
+
+    let mut term = one();
+    let mut sum = one();
+    let mut negative = false;
+
+    for i in 1..=MAX_POW_ITERATIONS {
+        let k_minus_one = Uint256::from(i - 1).checked_mul(one())?;
+        let (c, c_neg) = if exp >= k_minus_one {
+            (exp - k_minus_one, false)
+        } else {
+            (k_minus_one - exp, true)
+        };
+
+        term = mul(term, mul(c, x)?)? / Uint256::from(i);
+        if term.is_zero() {
+            return Ok(sum);
+        }
+
+        if x_neg {
+            negative = !negative;
+        }
+        if c_neg {
+            negative = !negative;
+        }
+
+        if negative {
+            sum = sum.saturating_sub(term);
+        } else {
+            sum = sum.checked_add(term)?;
+        }
+
+        if term < Uint256::from(POW_PRECISION) {
+            return Ok(sum);
+        }
+    }
+
+    Err(StdError::generic_err("Power series did not converge"))
+}
+
+/// ## Description
+/// Returns `base` raised to the power `exp`. Both numbers are in the fixed point representation and
+/// the base must be between 1/2 and 3/2.
+/// ## Params
+/// * **base** is the object of type [`Uint256`].
+///
+/// * **exp** is the object of type [`Uint256`].
+pub fn pow(base: Uint256, exp: Uint256) -> StdResult<Uint256> {
+    if base < Uint256::from(MIN_POW_BASE) || base > Uint256::from(MAX_POW_BASE) {
+        return Err(StdError::generic_err("Power base is out of bounds"));
+    }
+
+    let whole = exp / one();
+    let remain = exp - whole * one();
+
+    let whole_pow = pow_int(base, whole)?;
+    if remain.is_zero() {
+        return Ok(whole_pow);
+    }
+
+    mul(whole_pow, pow_frac(base, remain)?)
+}
+
+/// ## Description
+/// Returns the amount of the ask asset received for the offer amount without any commission.
+/// `ask = ask_pool * (1 - (offer_pool / (offer_pool + offer)) ^ (offer_weight / ask_weight))`
+/// ## Params
+/// * **offer_pool** is the object of type [`Uint128`]. Sets the balance of the offer asset.
+///
+/// * **offer_weight** is the object of type [`Decimal`]. Sets the weight of the offer asset.
+///
+/// * **ask_pool** is the object of type [`Uint128`]. Sets the balance of the ask asset.
+///
+/// * **ask_weight** is the object of type [`Decimal`]. Sets the weight of the ask asset.
+///
+/// * **offer_amount** is the object of type [`Uint128`]. Sets the offer amount.
+pub fn calc_ask_amount(
+    offer_pool: Uint128,
+    offer_weight: Decimal,
+    ask_pool: Uint128,
+    ask_weight: Decimal,
+    offer_amount: Uint128,
+) -> StdResult<Uint128> {
+    let offer_pool = Uint256::from(offer_pool);
+    let base = div(offer_pool, offer_pool.checked_add(offer_amount.into())?)?;
+    let exp = div(decimal_to_fixed(offer_weight), decimal_to_fixed(ask_weight))?;
+    let ratio = one().saturating_sub(pow(base, exp)?);
+
+    to_uint128(Uint256::from(ask_pool).multiply_ratio(ratio, one()))
+}
+
+/// ## Description
+/// Returns the amount of the offer asset needed to receive the ask amount without any commission.
+/// `offer = offer_pool * ((ask_pool / (ask_pool - ask)) ^ (ask_weight / offer_weight) - 1)`
+/// ## Params
+/// * **offer_pool** is the object of type [`Uint128`]. Sets the balance of the offer asset.
+///
+/// * **offer_weight** is the object of type [`Decimal`]. Sets the weight of the offer asset.
+///
+/// * **ask_pool** is the object of type [`Uint128`]. Sets the balance of the ask asset.
+///
+/// * **ask_weight** is the object of type [`Decimal`]. Sets the weight of the ask asset.
+///
+/// * **ask_amount** is the object of type [`Uint128`]. Sets the ask amount.
+pub fn calc_offer_amount(
+    offer_pool: Uint128,
+    offer_weight: Decimal,
+    ask_pool: Uint128,
+    ask_weight: Decimal,
+    ask_amount: Uint128,
+) -> StdResult<Uint128> {
+    let ask_pool = Uint256::from(ask_pool);
+    let base = div(ask_pool, ask_pool.checked_sub(ask_amount.into())?)?;
+    let exp = div(decimal_to_fixed(ask_weight), decimal_to_fixed(offer_weight))?;
+    let ratio = pow(base, exp)?.saturating_sub(one());
+
+    // Round up so that the pool never gives out more than it receives
+    let numerator = Uint256::from(offer_pool).checked_mul(ratio)?;
+    let offer_amount = numerator.checked_add(one() - Uint256::from(1u8))? / one();
+
+    to_uint128(offer_amount)
+}
+
+/// ## Description
+/// Returns the spot price of the offer asset denominated in the ask asset without any commission
+/// in the fixed point representation.
+/// `price = (ask_pool / ask_weight) / (offer_pool / offer_weight)`
+/// ## Params
+/// * **offer_pool** is the object of type [`Uint128`]. Sets the balance of the offer asset.
+///
+/// * **offer_weight** is the object of type [`Decimal`]. Sets the weight of the offer asset.
+///
+/// * **ask_pool** is the object of type [`Uint128`]. Sets the balance of the ask asset.
+///
+/// * **ask_weight** is the object of type [`Decimal`]. Sets the weight of the ask asset.
+pub fn calc_spot_price(
+    offer_pool: Uint128,
+    offer_weight: Decimal,
+    ask_pool: Uint128,
+    ask_weight: Decimal,
+) -> StdResult<Uint256> {
+    let numerator = Uint256::from(ask_pool).checked_mul(decimal_to_fixed(offer_weight))?;
+    let denominator = Uint256::from(offer_pool).checked_mul(decimal_to_fixed(ask_weight))?;
+
+    Ok(numerator.checked_mul(one())?.checked_div(denominator)?)
+}
@@ -0,0 +1,1036 @@
+use crate::contract::{
+    accumulate_prices, compute_offer_amount, compute_swap, execute, instantiate, query_config,
+    query_pair_info, query_reverse_simulation, query_simulate_provide, query_simulation, reply,
+    INIT_POOL_SUPPLY,
+};
+use crate::error::ContractError;
+use crate::math::{calc_ask_amount, calc_offer_amount, pow, WEIGHT_PRECISION};
+use crate::mock_querier::mock_dependencies;
+use crate::response::MsgInstantiateContractResponse;
+use crate::state::Config;
+use astroport::asset::{Asset, AssetInfo, PairInfo};
+use astroport::factory::PairType;
+use astroport::pair::{
+    ExecuteMsg, InstantiateMsg, ReverseSimulationResponse, SimulationResponse, WeightedPoolConfig,
+    WeightedPoolParams, TWAP_PRECISION,
+};
+use astroport::token::InstantiateMsg as TokenInstantiateMsg;
+use cosmwasm_std::testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    from_binary, to_binary, Addr, BankMsg, Binary, BlockInfo, Coin, ContractResult, Decimal,
+    DepsMut, Env, Fraction, Reply, ReplyOn, StdError, SubMsg, SubMsgExecutionResponse, Timestamp,
+    Uint128, Uint256, WasmMsg,
+};
+use cw20::{Cw20ExecuteMsg, MinterResponse};
+use proptest::prelude::*;
+use protobuf::Message;
+
+fn store_liquidity_token(deps: DepsMut, msg_id: u64, contract_addr: String) {
+    let data = MsgInstantiateContractResponse {
+        contract_address: contract_addr,
+        data: vec![],
+        unknown_fields: Default::default(),
+        cached_size: Default::default(),
+    }
+    .write_to_bytes()
+    .unwrap();
+
+    let reply_msg = Reply {
+        id: msg_id,
+        result: ContractResult::Ok(SubMsgExecutionResponse {
+            events: vec![],
+            data: Some(data.into()),
+        }),
+    };
+
+    let _res = reply(deps, mock_env(), reply_msg.clone()).unwrap();
+}
+
+fn instantiate_msg(weights: Vec<u64>) -> InstantiateMsg {
+    InstantiateMsg {
+        factory_addr: String::from("factory"),
+        asset_infos: vec![
+            AssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            },
+            AssetInfo::Token {
+                contract_addr: Addr::unchecked("asset0000"),
+            },
+        ],
+        token_code_id: 10u64,
+        init_params: Some(to_binary(&WeightedPoolParams { weights }).unwrap()),
+    }
+}
+
+fn mock_env_with_block_time(time: u64) -> Env {
+    let mut env = mock_env();
+    env.block = BlockInfo {
+        height: 1,
+        time: Timestamp::from_seconds(time),
+        chain_id: "columbus".to_string(),
+    };
+    env
+}
+
+fn to_f64(value: Uint128) -> f64 {
+    value.u128() as f64
+}
+
+#[test]
+fn proper_initialization() {
+    let mut deps = mock_dependencies(&[]);
+
+    deps.querier.with_token_balances(&[(
+        &String::from("asset0000"),
+        &[(&String::from(MOCK_CONTRACT_ADDR), &Uint128::new(123u128))],
+    )]);
+
+    let env = mock_env();
+    let info = mock_info("addr0000", &[]);
+    let res = instantiate(deps.as_mut(), env, info, instantiate_msg(vec![80, 20])).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg {
+            msg: WasmMsg::Instantiate {
+                code_id: 10u64,
+                msg: to_binary(&TokenInstantiateMsg {
+                    name: "UUSD-MAPP-LP".to_string(),
+                    symbol: "uLP".to_string(),
+                    decimals: 6,
+                    initial_balances: vec![],
+                    mint: Some(MinterResponse {
+                        minter: String::from(MOCK_CONTRACT_ADDR),
+                        cap: None,
+                    }),
+                })
+                .unwrap(),
+                funds: vec![],
+                admin: None,
+                label: String::from("Astroport LP token"),
+            }
+            .into(),
+            id: 1,
+            gas_limit: None,
+            reply_on: ReplyOn::Success
+        },]
+    );
+
+    // store liquidity token
+    store_liquidity_token(deps.as_mut(), 1, "liquidity0000".to_string());
+
+    let pair_info: PairInfo = query_pair_info(deps.as_ref()).unwrap();
+    assert_eq!(Addr::unchecked("liquidity0000"), pair_info.liquidity_token);
+    assert_eq!(PairType::Weighted {}, pair_info.pair_type);
+
+    let config = query_config(deps.as_ref(), mock_env()).unwrap();
+    let params: WeightedPoolConfig = from_binary(&config.params.unwrap()).unwrap();
+    assert_eq!(
+        params.weights,
+        vec![Decimal::percent(80), Decimal::percent(20)]
+    );
+}
+
+#[test]
+fn invalid_initialization() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    let info = mock_info("addr0000", &[]);
+
+    // weights must be set for every asset
+    let err = instantiate(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        instantiate_msg(vec![100]),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidWeights {});
+
+    // every weight must be at least 1% of the total weight
+    let err = instantiate(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        instantiate_msg(vec![1, 200]),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidWeights {});
+
+    let err = instantiate(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        instantiate_msg(vec![0, 0]),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidWeights {});
+
+    let mut msg = instantiate_msg(vec![50, 50]);
+    msg.init_params = None;
+    let err = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+    assert_eq!(err, ContractError::InitParamsNotFound {});
+
+    let mut msg = instantiate_msg(vec![50, 50]);
+    msg.asset_infos[1] = msg.asset_infos[0].clone();
+    let err = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+    assert_eq!(err, ContractError::DoublingAssets {});
+
+    let mut msg = instantiate_msg(vec![30, 30, 40]);
+    msg.asset_infos.push(AssetInfo::NativeToken {
+        denom: "uluna".to_string(),
+    });
+    let err = instantiate(deps.as_mut(), env, info, msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidNumberOfAssets {});
+}
+
+#[test]
+fn provide_liquidity() {
+    let mut deps = mock_dependencies(&[Coin {
+        denom: "uusd".to_string(),
+        amount: Uint128::new(800_000000),
+    }]);
+
+    deps.querier.with_token_balances(&[
+        (
+            &String::from("asset0000"),
+            &[(&String::from(MOCK_CONTRACT_ADDR), &Uint128::new(0))],
+        ),
+        (
+            &String::from("liquidity0000"),
+            &[(&String::from(MOCK_CONTRACT_ADDR), &Uint128::new(0))],
+        ),
+    ]);
+
+    let env = mock_env();
+    let info = mock_info("addr0000", &[]);
+    instantiate(deps.as_mut(), env, info, instantiate_msg(vec![80, 20])).unwrap();
+    store_liquidity_token(deps.as_mut(), 1, "liquidity0000".to_string());
+
+    let assets = vec![
+        Asset {
+            info: AssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            },
+            amount: Uint128::new(800_000000),
+        },
+        Asset {
+            info: AssetInfo::Token {
+                contract_addr: Addr::unchecked("asset0000"),
+            },
+            amount: Uint128::new(200_000000),
+        },
+    ];
+
+    // the first provision mints the initial supply whatever the amounts are
+    let msg = ExecuteMsg::ProvideLiquidity {
+        assets: assets.clone(),
+        slippage_tolerance: None,
+        auto_stake: None,
+        receiver: None,
+        min_lp_to_receive: None,
+        deadline: None,
+    };
+    let info = mock_info(
+        "addr0000",
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(800_000000),
+        }],
+    );
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: String::from("asset0000"),
+                msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
+                    owner: String::from("addr0000"),
+                    recipient: String::from(MOCK_CONTRACT_ADDR),
+                    amount: Uint128::new(200_000000),
+                })
+                .unwrap(),
+                funds: vec![],
+            }),
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: String::from("liquidity0000"),
+                msg: to_binary(&Cw20ExecuteMsg::Mint {
+                    recipient: String::from("addr0000"),
+                    amount: INIT_POOL_SUPPLY,
+                })
+                .unwrap(),
+                funds: vec![],
+            }),
+        ]
+    );
+
+    // later provisions mint shares in proportion to the smallest relative deposit
+    deps.querier.with_balance(&[(
+        &String::from(MOCK_CONTRACT_ADDR),
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(800_000000 + 400_000000),
+        }],
+    )]);
+    deps.querier.with_token_balances(&[
+        (
+            &String::from("asset0000"),
+            &[(&String::from(MOCK_CONTRACT_ADDR), &Uint128::new(200_000000))],
+        ),
+        (
+            &String::from("liquidity0000"),
+            &[(&String::from(MOCK_CONTRACT_ADDR), &INIT_POOL_SUPPLY)],
+        ),
+    ]);
+
+    let msg = ExecuteMsg::ProvideLiquidity {
+        assets: vec![
+            Asset {
+                info: AssetInfo::NativeToken {
+                    denom: "uusd".to_string(),
+                },
+                amount: Uint128::new(400_000000),
+            },
+            Asset {
+                info: AssetInfo::Token {
+                    contract_addr: Addr::unchecked("asset0000"),
+                },
+                amount: Uint128::new(100_000000),
+            },
+        ],
+        slippage_tolerance: None,
+        auto_stake: None,
+        receiver: Some(String::from("addr0001")),
+        min_lp_to_receive: None,
+        deadline: None,
+    };
+    let info = mock_info(
+        "addr0000",
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(400_000000),
+        }],
+    );
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        res.messages[1],
+        SubMsg::new(WasmMsg::Execute {
+            contract_addr: String::from("liquidity0000"),
+            msg: to_binary(&Cw20ExecuteMsg::Mint {
+                recipient: String::from("addr0001"),
+                amount: Uint128::new(50_000000),
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+
+    // every asset must be provided
+    let msg = ExecuteMsg::ProvideLiquidity {
+        assets: vec![
+            assets[0].clone(),
+            Asset {
+                info: assets[1].info.clone(),
+                amount: Uint128::zero(),
+            },
+        ],
+        slippage_tolerance: None,
+        auto_stake: None,
+        receiver: None,
+        min_lp_to_receive: None,
+        deadline: None,
+    };
+    let info = mock_info(
+        "addr0000",
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(800_000000),
+        }],
+    );
+    let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidZeroAmount {});
+
+    // providing in another ratio than the pool fails the default slippage check
+    let msg = ExecuteMsg::ProvideLiquidity {
+        assets: vec![
+            Asset {
+                info: assets[0].info.clone(),
+                amount: Uint128::new(400_000000),
+            },
+            Asset {
+                info: assets[1].info.clone(),
+                amount: Uint128::new(200_000000),
+            },
+        ],
+        slippage_tolerance: None,
+        auto_stake: None,
+        receiver: None,
+        min_lp_to_receive: None,
+        deadline: None,
+    };
+    let info = mock_info(
+        "addr0000",
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(400_000000),
+        }],
+    );
+    let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+    assert_eq!(err, ContractError::MaxSlippageAssertion {});
+}
+
+#[test]
+fn simulate_provide() {
+    let mut deps = mock_dependencies(&[Coin {
+        denom: "uusd".to_string(),
+        amount: Uint128::new(800_000000),
+    }]);
+
+    deps.querier.with_token_balances(&[
+        (
+            &String::from("asset0000"),
+            &[(&String::from(MOCK_CONTRACT_ADDR), &Uint128::new(200_000000))],
+        ),
+        (
+            &String::from("liquidity0000"),
+            &[(&String::from("addr0000"), &INIT_POOL_SUPPLY)],
+        ),
+    ]);
+
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        instantiate_msg(vec![80, 20]),
+    )
+    .unwrap();
+    store_liquidity_token(deps.as_mut(), 1, "liquidity0000".to_string());
+
+    let share = query_simulate_provide(
+        deps.as_ref(),
+        vec![
+            Asset {
+                info: AssetInfo::Token {
+                    contract_addr: Addr::unchecked("asset0000"),
+                },
+                amount: Uint128::new(20_000000),
+            },
+            Asset {
+                info: AssetInfo::NativeToken {
+                    denom: "uusd".to_string(),
+                },
+                amount: Uint128::new(160_000000),
+            },
+        ],
+    )
+    .unwrap();
+    assert_eq!(share, Uint128::new(10_000000));
+}
+
+#[test]
+fn equal_weights_match_constant_product() {
+    let offer_pool = Uint128::new(1_000_000_000_000);
+    let ask_pool = Uint128::new(3_000_000_000_000);
+
+    for offer_amount in [1u128, 1_000000, 123_456_789_000, 500_000_000_000] {
+        let offer_amount = Uint128::new(offer_amount);
+        let (return_amount, spread_amount, commission_amount) = compute_swap(
+            offer_pool,
+            Decimal::percent(50),
+            ask_pool,
+            Decimal::percent(50),
+            offer_amount,
+            Decimal::permille(3),
+        )
+        .unwrap();
+
+        let xyk_return = ask_pool - (offer_pool * ask_pool) / (offer_pool + offer_amount);
+        let xyk_spread = offer_amount * Decimal::from_ratio(ask_pool, offer_pool) - xyk_return;
+
+        let weighted_return = return_amount + commission_amount;
+        assert!(
+            weighted_return.max(xyk_return) - weighted_return.min(xyk_return) <= Uint128::new(1)
+        );
+        assert!(spread_amount.max(xyk_spread) - spread_amount.min(xyk_spread) <= Uint128::new(1));
+        assert_eq!(commission_amount, weighted_return * Decimal::permille(3));
+    }
+}
+
+#[test]
+fn weighted_swap() {
+    let offer_pool = Uint128::new(8_000_000_000);
+    let ask_pool = Uint128::new(2_000_000_000);
+    let offer_amount = Uint128::new(100_000000);
+
+    // the 80/20 pool prices both assets equally
+    let (return_amount, spread_amount, commission_amount) = compute_swap(
+        offer_pool,
+        Decimal::percent(80),
+        ask_pool,
+        Decimal::percent(20),
+        offer_amount,
+        Decimal::zero(),
+    )
+    .unwrap();
+    assert!(commission_amount.is_zero());
+    assert_eq!(return_amount + spread_amount, offer_amount);
+
+    let expected = to_f64(ask_pool)
+        * (1.0 - (to_f64(offer_pool) / to_f64(offer_pool + offer_amount)).powf(4.0));
+    assert!((to_f64(return_amount) - expected).abs() <= 1.0);
+
+    // swapping in the other direction has a lower price impact
+    let (reverse_return_amount, reverse_spread_amount, _) = compute_swap(
+        ask_pool,
+        Decimal::percent(20),
+        offer_pool,
+        Decimal::percent(80),
+        offer_amount,
+        Decimal::zero(),
+    )
+    .unwrap();
+    let expected = to_f64(offer_pool)
+        * (1.0 - (to_f64(ask_pool) / to_f64(ask_pool + offer_amount)).powf(0.25));
+    assert!((to_f64(reverse_return_amount) - expected).abs() <= 1.0);
+    assert!(reverse_spread_amount < spread_amount);
+}
+
+#[test]
+fn reverse_swap_matches_swap() {
+    let offer_pool = Uint128::new(7_000_000_000);
+    let ask_pool = Uint128::new(3_000_000_000);
+    let commission_rate = Decimal::permille(3);
+
+    for ask_amount in [1_000000u128, 250_000000, 900_000000] {
+        let ask_amount = Uint128::new(ask_amount);
+        let (offer_amount, _, commission_amount) = compute_offer_amount(
+            offer_pool,
+            Decimal::percent(70),
+            ask_pool,
+            Decimal::percent(30),
+            ask_amount,
+            commission_rate,
+        )
+        .unwrap();
+
+        let (return_amount, _, swap_commission_amount) = compute_swap(
+            offer_pool,
+            Decimal::percent(70),
+            ask_pool,
+            Decimal::percent(30),
+            offer_amount,
+            commission_rate,
+        )
+        .unwrap();
+
+        // rounding never favours the trader
+        assert!(return_amount >= ask_amount);
+        assert!(return_amount - ask_amount <= Uint128::new(2));
+        assert!(
+            swap_commission_amount.max(commission_amount)
+                - swap_commission_amount.min(commission_amount)
+                <= Uint128::new(1)
+        );
+    }
+}
+
+#[test]
+fn trade_size_limits() {
+    let pool = Uint128::new(1_000_000_000);
+
+    let err = compute_swap(
+        pool,
+        Decimal::percent(50),
+        pool,
+        Decimal::percent(50),
+        Uint128::new(500_000001),
+        Decimal::zero(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::MaxInRatioAssertion {});
+
+    compute_swap(
+        pool,
+        Decimal::percent(50),
+        pool,
+        Decimal::percent(50),
+        Uint128::new(500_000000),
+        Decimal::zero(),
+    )
+    .unwrap();
+
+    let err = compute_offer_amount(
+        pool,
+        Decimal::percent(50),
+        pool,
+        Decimal::percent(50),
+        Uint128::new(333_333334),
+        Decimal::zero(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::MaxOutRatioAssertion {});
+
+    compute_offer_amount(
+        pool,
+        Decimal::percent(50),
+        pool,
+        Decimal::percent(50),
+        Uint128::new(333_333333),
+        Decimal::zero(),
+    )
+    .unwrap();
+
+    // The largest trades stay accurate with the most skewed weights
+    for (offer_weight, ask_weight) in [(1u64, 99u64), (99, 1)] {
+        let ratio = offer_weight as f64 / ask_weight as f64;
+
+        let (return_amount, ..) = compute_swap(
+            pool,
+            Decimal::percent(offer_weight),
+            pool,
+            Decimal::percent(ask_weight),
+            Uint128::new(500_000000),
+            Decimal::zero(),
+        )
+        .unwrap();
+        let expected = 1e9 * (1.0 - (1.0 / 1.5f64).powf(ratio));
+        assert!((to_f64(return_amount) - expected).abs() <= 1e-6 * expected.max(1e3));
+
+        let (offer_amount, ..) = compute_offer_amount(
+            pool,
+            Decimal::percent(offer_weight),
+            pool,
+            Decimal::percent(ask_weight),
+            Uint128::new(333_333333),
+            Decimal::zero(),
+        )
+        .unwrap();
+        let expected = 1e9 * ((1e9 / 666_666667.0f64).powf(1.0 / ratio) - 1.0);
+        assert!((to_f64(offer_amount) - expected).abs() <= 1e-6 * expected.max(1e3));
+    }
+}
+
+#[test]
+fn pow_bounds() {
+    let one = Uint256::from(WEIGHT_PRECISION);
+    let half = Uint256::from(WEIGHT_PRECISION / 2);
+    let exp = Uint256::from(WEIGHT_PRECISION / 3);
+
+    pow(half, exp).unwrap();
+    pow(one + half, exp).unwrap();
+
+    let err = pow(half - Uint256::from(1u8), exp).unwrap_err();
+    assert_eq!(err, StdError::generic_err("Power base is out of bounds"));
+    let err = pow(one + half + Uint256::from(1u8), exp).unwrap_err();
+    assert_eq!(err, StdError::generic_err("Power base is out of bounds"));
+}
+
+#[test]
+fn swap_and_simulations() {
+    let offer_amount = Uint128::new(10_000000);
+    let mut deps = mock_dependencies(&[Coin {
+        denom: "uusd".to_string(),
+        amount: Uint128::new(800_000000) + offer_amount,
+    }]);
+
+    deps.querier.with_token_balances(&[
+        (
+            &String::from("asset0000"),
+            &[(&String::from(MOCK_CONTRACT_ADDR), &Uint128::new(200_000000))],
+        ),
+        (
+            &String::from("liquidity0000"),
+            &[(&String::from("addr0000"), &INIT_POOL_SUPPLY)],
+        ),
+    ]);
+
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        instantiate_msg(vec![80, 20]),
+    )
+    .unwrap();
+    store_liquidity_token(deps.as_mut(), 1, "liquidity0000".to_string());
+
+    let (return_amount, spread_amount, commission_amount) = compute_swap(
+        Uint128::new(800_000000),
+        Decimal::percent(80),
+        Uint128::new(200_000000),
+        Decimal::percent(20),
+        offer_amount,
+        Decimal::permille(3),
+    )
+    .unwrap();
+
+    let msg = ExecuteMsg::Swap {
+        offer_asset: Asset {
+            info: AssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            },
+            amount: offer_amount,
+        },
+        ask_asset_info: None,
+        belief_price: None,
+        max_spread: Some(Decimal::percent(50)),
+        to: None,
+        minimum_receive: None,
+        deadline: None,
+    };
+    let info = mock_info(
+        "addr0000",
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: offer_amount,
+        }],
+    );
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        res.messages[0],
+        SubMsg::new(WasmMsg::Execute {
+            contract_addr: String::from("asset0000"),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: String::from("addr0000"),
+                amount: return_amount,
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+    assert_eq!(
+        res.messages[1],
+        SubMsg::new(WasmMsg::Execute {
+            contract_addr: String::from("asset0000"),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: String::from("fee_address"),
+                amount: commission_amount * Decimal::from_ratio(1660u128, 10000u128),
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+
+    // the default max spread rejects large trades
+    let msg = ExecuteMsg::Swap {
+        offer_asset: Asset {
+            info: AssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            },
+            amount: offer_amount,
+        },
+        ask_asset_info: None,
+        belief_price: None,
+        max_spread: None,
+        to: None,
+        minimum_receive: None,
+        deadline: None,
+    };
+    let info = mock_info(
+        "addr0000",
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: offer_amount,
+        }],
+    );
+    let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+    assert_eq!(err, ContractError::MaxSpreadAssertion {});
+
+    // the simulations use the current pool balances
+    deps.querier.with_balance(&[(
+        &String::from(MOCK_CONTRACT_ADDR),
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(800_000000),
+        }],
+    )]);
+
+    let simulation: SimulationResponse = query_simulation(
+        deps.as_ref(),
+        Asset {
+            info: AssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            },
+            amount: offer_amount,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        simulation,
+        SimulationResponse {
+            return_amount,
+            spread_amount,
+            commission_amount,
+            fee_rate: Decimal::permille(3),
+        }
+    );
+
+    let reverse_simulation: ReverseSimulationResponse = query_reverse_simulation(
+        deps.as_ref(),
+        Asset {
+            info: AssetInfo::Token {
+                contract_addr: Addr::unchecked("asset0000"),
+            },
+            amount: return_amount,
+        },
+    )
+    .unwrap();
+    assert!(reverse_simulation.offer_amount <= offer_amount);
+    assert!(offer_amount - reverse_simulation.offer_amount <= Uint128::new(10));
+}
+
+#[test]
+fn test_accumulate_prices() {
+    let price_precision = 10u128.pow(TWAP_PRECISION.into());
+    let config = Config {
+        pair_info: PairInfo {
+            asset_infos: vec![
+                AssetInfo::NativeToken {
+                    denom: "uusd".to_string(),
+                },
+                AssetInfo::Token {
+                    contract_addr: Addr::unchecked("asset0000"),
+                },
+            ],
+            contract_addr: Addr::unchecked("pair"),
+            liquidity_token: Addr::unchecked("lp_token"),
+            pair_type: PairType::Weighted {},
+        },
+        factory_addr: Addr::unchecked("factory"),
+        weights: vec![Decimal::percent(80), Decimal::percent(20)],
+        block_time_last: 1000,
+        price0_cumulative_last: Uint128::new(500 * price_precision),
+        price1_cumulative_last: Uint128::new(2000 * price_precision),
+    };
+
+    // Same block height, no changes
+    let res = accumulate_prices(
+        mock_env_with_block_time(1000),
+        &config,
+        Uint128::new(250),
+        Uint128::new(500),
+    )
+    .unwrap();
+    assert!(res.is_none());
+
+    // (500 / 0.2) / (250 / 0.8) = 8 and its inverse
+    let (price0, price1, block_time) = accumulate_prices(
+        mock_env_with_block_time(1500),
+        &config,
+        Uint128::new(250),
+        Uint128::new(500),
+    )
+    .unwrap()
+    .unwrap();
+    assert_eq!(block_time, 1500);
+    assert_eq!(price0, Uint128::new((500 + 8 * 500) * price_precision));
+    assert_eq!(
+        price1,
+        Uint128::new(2000 * price_precision + 500 * price_precision / 8)
+    );
+}
+
+#[test]
+fn flash_loan() {
+    let mut deps = mock_dependencies(&[Coin {
+        denom: "uusd".to_string(),
+        amount: Uint128::new(8_000_000000),
+    }]);
+    deps.querier.with_token_balances(&[(
+        &String::from("asset0000"),
+        &[(
+            &String::from(MOCK_CONTRACT_ADDR),
+            &Uint128::new(2_000_000000),
+        )],
+    )]);
+
+    let env = mock_env();
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr0000", &[]),
+        instantiate_msg(vec![4, 1]),
+    )
+    .unwrap();
+    store_liquidity_token(deps.as_mut(), 1, "liquidity0000".to_string());
+
+    let uusd_info = AssetInfo::NativeToken {
+        denom: "uusd".to_string(),
+    };
+
+    let msg = ExecuteMsg::FlashLoan {
+        assets: vec![Asset {
+            info: uusd_info.clone(),
+            amount: Uint128::zero(),
+        }],
+        callback: Binary::from(b"callback".to_vec()),
+    };
+    let err = execute(deps.as_mut(), env.clone(), mock_info("borrower", &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidZeroAmount {});
+
+    let msg = ExecuteMsg::FlashLoan {
+        assets: vec![Asset {
+            info: uusd_info.clone(),
+            amount: Uint128::new(1_000_000000),
+        }],
+        callback: Binary::from(b"callback".to_vec()),
+    };
+    let res = execute(deps.as_mut(), env.clone(), mock_info("borrower", &[]), msg).unwrap();
+    let repay_msg = ExecuteMsg::AssertFlashLoanRepaid {
+        expected_balances: vec![Asset {
+            info: uusd_info.clone(),
+            amount: Uint128::new(8_003_000000),
+        }],
+        fees: vec![Asset {
+            info: uusd_info.clone(),
+            amount: Uint128::new(3_000000),
+        }],
+    };
+    assert_eq!(res.messages.len(), 3);
+    assert_eq!(
+        res.messages[2],
+        SubMsg::new(WasmMsg::Execute {
+            contract_addr: String::from(MOCK_CONTRACT_ADDR),
+            msg: to_binary(&repay_msg).unwrap(),
+            funds: vec![],
+        })
+    );
+
+    // Liquidity can't be provided against the lent out reserves
+    let msg = ExecuteMsg::ProvideLiquidity {
+        assets: vec![
+            Asset {
+                info: uusd_info,
+                amount: Uint128::new(4_000000),
+            },
+            Asset {
+                info: AssetInfo::Token {
+                    contract_addr: Addr::unchecked("asset0000"),
+                },
+                amount: Uint128::new(1_000000),
+            },
+        ],
+        slippage_tolerance: None,
+        auto_stake: None,
+        receiver: None,
+        min_lp_to_receive: None,
+        deadline: None,
+    };
+    let err = execute(deps.as_mut(), env.clone(), mock_info("borrower", &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::FlashLoanInProgress {});
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        repay_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::FlashLoanNotRepaid {});
+
+    deps.querier.with_balance(&[(
+        &String::from(MOCK_CONTRACT_ADDR),
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(8_003_000000),
+        }],
+    )]);
+    let res = execute(
+        deps.as_mut(),
+        env,
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        repay_msg,
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(BankMsg::Send {
+            to_address: String::from("fee_address"),
+            amount: vec![Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::new(498000),
+            }],
+        })]
+    );
+}
+
+proptest! {
+    #[test]
+    fn pow_matches_float(
+        base in 500_000u128..1_500_000u128,
+        exp in 10_000u128..9_900_000u128,
+    ) {
+        // bases and exponents in millionths
+        let to_fixed = |value: u128| Uint256::from(value * (WEIGHT_PRECISION / 1_000_000));
+        let result = pow(to_fixed(base), to_fixed(exp)).unwrap();
+
+        let result = result.to_string().parse::<f64>().unwrap() / WEIGHT_PRECISION as f64;
+        let expected = (base as f64 / 1e6).powf(exp as f64 / 1e6);
+        prop_assert!(
+            (result - expected).abs() <= 1e-8 * expected.max(1.0),
+            "{} != {}",
+            result,
+            expected
+        );
+    }
+
+    #[test]
+    fn offer_amount_matches_float(
+        offer_pool in 1_000000u128..1_000_000_000_000_000u128,
+        ask_pool in 1_000000u128..1_000_000_000_000_000u128,
+        offer_weight in 1u64..99u64,
+        ask_share in 1u128..=333u128,
+    ) {
+        let ask_amount = ask_pool * ask_share / 1000;
+        let offer_weight = Decimal::percent(offer_weight);
+        let ask_weight = Decimal::one() - offer_weight;
+
+        let result = calc_offer_amount(
+            Uint128::new(offer_pool),
+            offer_weight,
+            Uint128::new(ask_pool),
+            ask_weight,
+            Uint128::new(ask_amount),
+        )
+        .unwrap();
+
+        let ratio = ask_weight.numerator() as f64 / offer_weight.numerator() as f64;
+        let expected = offer_pool as f64
+            * ((ask_pool as f64 / (ask_pool - ask_amount) as f64).powf(ratio) - 1.0);
+        prop_assert!(
+            (result.u128() as f64 - expected).abs() <= 1e-6 * expected.max(1e3),
+            "{} != {}",
+            result,
+            expected
+        );
+    }
+
+    #[test]
+    fn ask_amount_never_exceeds_float(
+        offer_pool in 1_000000u128..1_000_000_000_000_000u128,
+        ask_pool in 1_000000u128..1_000_000_000_000_000u128,
+        offer_weight in 1u64..99u64,
+        offer_share in 1u128..500u128,
+    ) {
+        let offer_amount = offer_pool * offer_share / 1000;
+        let offer_weight = Decimal::percent(offer_weight);
+        let ask_weight = Decimal::one() - offer_weight;
+
+        let result = calc_ask_amount(
+            Uint128::new(offer_pool),
+            offer_weight,
+            Uint128::new(ask_pool),
+            ask_weight,
+            Uint128::new(offer_amount),
+        )
+        .unwrap();
+
+        let ratio = offer_weight.numerator() as f64 / ask_weight.numerator() as f64;
+        let expected = ask_pool as f64
+            * (1.0 - (offer_pool as f64 / (offer_pool + offer_amount) as f64).powf(ratio));
+        prop_assert!(
+            (result.u128() as f64 - expected).abs() <= 1e-6 * expected.max(1e3),
+            "{} != {}",
+            result,
+            expected
+        );
+    }
+}
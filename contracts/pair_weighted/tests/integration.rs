@@ -0,0 +1,287 @@
+// The Terra treasury tax is queried from the Terra chain, which the multi-test app doesn't
+// mock, so these tests run without the `terra` feature
+#![cfg(not(feature = "terra"))]
+
+use astroport::asset::{Asset, AssetInfo, PairInfo};
+use astroport::factory::{
+    ExecuteMsg as FactoryExecuteMsg, InstantiateMsg as FactoryInstantiateMsg, PairConfig, PairType,
+    QueryMsg as FactoryQueryMsg,
+};
+use astroport::pair::{
+    ConfigResponse, Cw20HookMsg, ExecuteMsg, PoolResponse, QueryMsg, SimulationResponse,
+    WeightedPoolConfig, WeightedPoolParams,
+};
+use cosmwasm_std::testing::{mock_env, MockApi, MockStorage};
+use cosmwasm_std::{from_binary, to_binary, Addr, Coin, Decimal, Uint128};
+use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg};
+use cw_multi_test::{App, AppBuilder, BankKeeper, ContractWrapper, Executor};
+
+const OWNER: &str = "owner";
+
+fn mock_app() -> App {
+    let env = mock_env();
+    let api = MockApi::default();
+    let bank = BankKeeper::new();
+    let storage = MockStorage::new();
+
+    AppBuilder::new()
+        .with_api(api)
+        .with_block(env.block)
+        .with_bank(bank)
+        .with_storage(storage)
+        .build()
+}
+
+fn store_token_code(app: &mut App) -> u64 {
+    let astro_token_contract = Box::new(ContractWrapper::new_with_empty(
+        astroport_token::contract::execute,
+        astroport_token::contract::instantiate,
+        astroport_token::contract::query,
+    ));
+
+    app.store_code(astro_token_contract)
+}
+
+fn store_pair_code(app: &mut App) -> u64 {
+    let pair_contract = Box::new(
+        ContractWrapper::new_with_empty(
+            astroport_pair_weighted::contract::execute,
+            astroport_pair_weighted::contract::instantiate,
+            astroport_pair_weighted::contract::query,
+        )
+        .with_reply_empty(astroport_pair_weighted::contract::reply),
+    );
+
+    app.store_code(pair_contract)
+}
+
+fn store_factory_code(app: &mut App) -> u64 {
+    let factory_contract = Box::new(
+        ContractWrapper::new_with_empty(
+            astroport_factory::contract::execute,
+            astroport_factory::contract::instantiate,
+            astroport_factory::contract::query,
+        )
+        .with_reply_empty(astroport_factory::contract::reply),
+    );
+
+    app.store_code(factory_contract)
+}
+
+fn native_asset_infos() -> Vec<AssetInfo> {
+    vec![
+        AssetInfo::NativeToken {
+            denom: "uusd".to_string(),
+        },
+        AssetInfo::NativeToken {
+            denom: "uluna".to_string(),
+        },
+    ]
+}
+
+fn query_lp_balance(app: &App, lp_token: &Addr, address: &Addr) -> Uint128 {
+    let res: BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            lp_token,
+            &Cw20QueryMsg::Balance {
+                address: address.to_string(),
+            },
+        )
+        .unwrap();
+    res.balance
+}
+
+#[test]
+fn create_pair_through_factory_provide_and_swap() {
+    let mut app = mock_app();
+    let owner = Addr::unchecked(OWNER);
+
+    app.init_bank_balance(
+        &owner,
+        vec![
+            Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::new(100_000_000_000u128),
+            },
+            Coin {
+                denom: "uluna".to_string(),
+                amount: Uint128::new(100_000_000_000u128),
+            },
+        ],
+    )
+    .unwrap();
+
+    let token_code_id = store_token_code(&mut app);
+    let pair_code_id = store_pair_code(&mut app);
+    let factory_code_id = store_factory_code(&mut app);
+
+    let init_msg = FactoryInstantiateMsg {
+        fee_address: None,
+        pair_configs: vec![PairConfig {
+            code_id: pair_code_id,
+            maker_fee_bps: 0,
+            total_fee_bps: 30,
+            pair_type: PairType::Weighted {},
+            is_disabled: None,
+            permissionless: true,
+            creation_fee: None,
+        }],
+        token_code_id,
+        generator_address: Some(String::from("generator")),
+        owner: owner.to_string(),
+        whitelist_code_id: 234u64,
+    };
+
+    let factory_instance = app
+        .instantiate_contract(
+            factory_code_id,
+            owner.clone(),
+            &init_msg,
+            &[],
+            "FACTORY",
+            None,
+        )
+        .unwrap();
+
+    let msg = FactoryExecuteMsg::CreatePair {
+        pair_type: PairType::Weighted {},
+        asset_infos: native_asset_infos(),
+        init_params: Some(
+            to_binary(&WeightedPoolParams {
+                weights: vec![4, 1],
+            })
+            .unwrap(),
+        ),
+    };
+
+    app.execute_contract(owner.clone(), factory_instance.clone(), &msg, &[])
+        .unwrap();
+
+    let pair_info: PairInfo = app
+        .wrap()
+        .query_wasm_smart(
+            &factory_instance,
+            &FactoryQueryMsg::Pair {
+                asset_infos: native_asset_infos(),
+                pair_type: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(pair_info.pair_type, PairType::Weighted {});
+    let pair_instance = pair_info.contract_addr;
+
+    let config: ConfigResponse = app
+        .wrap()
+        .query_wasm_smart(&pair_instance, &QueryMsg::Config {})
+        .unwrap();
+    let params: WeightedPoolConfig = from_binary(&config.params.unwrap()).unwrap();
+    assert_eq!(
+        params.weights,
+        vec![Decimal::percent(80), Decimal::percent(20)]
+    );
+
+    let msg = ExecuteMsg::ProvideLiquidity {
+        assets: vec![
+            Asset {
+                info: native_asset_infos()[0].clone(),
+                amount: Uint128::new(8_000_000_000u128),
+            },
+            Asset {
+                info: native_asset_infos()[1].clone(),
+                amount: Uint128::new(2_000_000_000u128),
+            },
+        ],
+        slippage_tolerance: None,
+        auto_stake: None,
+        receiver: None,
+        min_lp_to_receive: None,
+        deadline: None,
+    };
+    app.execute_contract(
+        owner.clone(),
+        pair_instance.clone(),
+        &msg,
+        &[
+            Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::new(8_000_000_000u128),
+            },
+            Coin {
+                denom: "uluna".to_string(),
+                amount: Uint128::new(2_000_000_000u128),
+            },
+        ],
+    )
+    .unwrap();
+
+    let lp_balance = query_lp_balance(&app, &pair_info.liquidity_token, &owner);
+    assert_eq!(lp_balance, Uint128::new(100_000_000u128));
+
+    let offer_asset = Asset {
+        info: native_asset_infos()[0].clone(),
+        amount: Uint128::new(10_000_000u128),
+    };
+    let simulation: SimulationResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &pair_instance,
+            &QueryMsg::Simulation {
+                offer_asset: offer_asset.clone(),
+                ask_asset_info: None,
+            },
+        )
+        .unwrap();
+
+    let luna_before = app.wrap().query_balance(&owner, "uluna").unwrap().amount;
+    let msg = ExecuteMsg::Swap {
+        offer_asset,
+        ask_asset_info: None,
+        belief_price: None,
+        max_spread: Some(Decimal::percent(5)),
+        to: None,
+        minimum_receive: None,
+        deadline: None,
+    };
+    app.execute_contract(
+        owner.clone(),
+        pair_instance.clone(),
+        &msg,
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(10_000_000u128),
+        }],
+    )
+    .unwrap();
+    let luna_after = app.wrap().query_balance(&owner, "uluna").unwrap().amount;
+    assert_eq!(luna_after - luna_before, simulation.return_amount);
+
+    let pool: PoolResponse = app
+        .wrap()
+        .query_wasm_smart(&pair_instance, &QueryMsg::Pool {})
+        .unwrap();
+    assert_eq!(pool.assets[0].amount, Uint128::new(8_010_000_000u128));
+    assert_eq!(
+        pool.assets[1].amount,
+        Uint128::new(2_000_000_000u128) - simulation.return_amount
+    );
+
+    // Withdraw all the liquidity
+    let msg = Cw20ExecuteMsg::Send {
+        contract: pair_instance.to_string(),
+        amount: lp_balance,
+        msg: to_binary(&Cw20HookMsg::WithdrawLiquidity {
+            withdraw_type: None,
+        })
+        .unwrap(),
+    };
+    app.execute_contract(owner.clone(), pair_info.liquidity_token.clone(), &msg, &[])
+        .unwrap();
+
+    let pool: PoolResponse = app
+        .wrap()
+        .query_wasm_smart(&pair_instance, &QueryMsg::Pool {})
+        .unwrap();
+    assert!(pool.total_share.is_zero());
+    assert!(pool.assets[1].amount.is_zero());
+}
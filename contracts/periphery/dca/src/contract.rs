@@ -0,0 +1,523 @@
+use crate::error::ContractError;
+use crate::state::{next_order_id, read_owner_orders, Order, ORDERS, OWNER_ORDERS};
+use astroport::asset::{addr_validate_to_lower, Asset, AssetInfo, PairInfo};
+use astroport::dca::{
+    Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, OrderResponse, QueryMsg,
+};
+use astroport::oracle::{ConfigResponse as OracleConfigResponse, QueryMsg as OracleQueryMsg};
+use astroport::pair::{
+    Cw20HookMsg as PairCw20HookMsg, ExecuteMsg as PairExecuteMsg, QueryMsg as PairQueryMsg,
+    MAX_ALLOWED_SLIPPAGE,
+};
+use cosmwasm_std::{
+    attr, entry_point, from_binary, to_binary, Addr, Binary, Coin, CosmosMsg, Decimal, Deps,
+    DepsMut, Empty, Env, MessageInfo, Response, StdError, StdResult, Uint128, Uint256, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw_storage_plus::U64Key;
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+/// Contract name that is used for migration.
+const CONTRACT_NAME: &str = "astroport-dca";
+/// Contract version that is used for migration.
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// ## Description
+/// This structure describes the parameters of a new order.
+struct OrderParams {
+    pair_addr: String,
+    oracle_addr: String,
+    offer_asset: Asset,
+    ask_asset_info: AssetInfo,
+    intervals: u64,
+    interval: u64,
+    max_spread: Decimal,
+}
+
+/// ## Description
+/// Creates a new contract with the specified parameters in the [`InstantiateMsg`].
+/// Returns the default object of type [`Response`] if the operation was successful,
+/// or a [`ContractError`] if the contract was not created.
+/// ## Params
+/// * **deps** is the object of type [`DepsMut`].
+///
+/// * **_env** is the object of type [`Env`].
+///
+/// * **_info** is the object of type [`MessageInfo`].
+///
+/// * **_msg** is a message of type [`InstantiateMsg`].
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::default())
+}
+
+/// ## Description
+/// Available the execute messages of the contract.
+/// ## Params
+/// * **deps** is the object of type [`DepsMut`].
+///
+/// * **env** is the object of type [`Env`].
+///
+/// * **info** is the object of type [`MessageInfo`].
+///
+/// * **msg** is the object of type [`ExecuteMsg`].
+///
+/// ## Queries
+/// * **ExecuteMsg::Receive(msg)** Receives a message of type [`Cw20ReceiveMsg`] and processes
+/// it depending on the received template.
+///
+/// * **ExecuteMsg::CreateOrder {
+///             pair_addr,
+///             oracle_addr,
+///             offer_asset,
+///             ask_asset_info,
+///             intervals,
+///             interval,
+///             max_spread,
+///         }** Escrows the native offer asset and creates a new order.
+///
+/// * **ExecuteMsg::ExecuteSlice { order_id }** Swaps the next slice of an order.
+///
+/// * **ExecuteMsg::CancelOrder { order_id }** Returns the unspent part of an order to its owner.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
+        ExecuteMsg::CreateOrder {
+            pair_addr,
+            oracle_addr,
+            offer_asset,
+            ask_asset_info,
+            intervals,
+            interval,
+            max_spread,
+        } => {
+            if !offer_asset.is_native_token() {
+                return Err(ContractError::Unauthorized {});
+            }
+            offer_asset.assert_sent_native_token_balance(&info)?;
+
+            create_order(
+                deps,
+                env,
+                info.sender,
+                OrderParams {
+                    pair_addr,
+                    oracle_addr,
+                    offer_asset,
+                    ask_asset_info,
+                    intervals,
+                    interval,
+                    max_spread,
+                },
+            )
+        }
+        ExecuteMsg::ExecuteSlice { order_id } => execute_slice(deps, env, order_id),
+        ExecuteMsg::CancelOrder { order_id } => cancel_order(deps, info, order_id),
+    }
+}
+
+/// ## Description
+/// Receives a message of type [`Cw20ReceiveMsg`] and processes it depending on the received template.
+/// If the template is not found in the received message, then an [`ContractError`] is returned,
+/// otherwise returns the [`Response`] with the specified attributes if the operation was successful
+/// ## Params
+/// * **deps** is the object of type [`DepsMut`].
+///
+/// * **env** is the object of type [`Env`].
+///
+/// * **info** is the object of type [`MessageInfo`].
+///
+/// * **cw20_msg** is the object of type [`Cw20ReceiveMsg`].
+pub fn receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    match from_binary(&cw20_msg.msg)? {
+        Cw20HookMsg::CreateOrder {
+            pair_addr,
+            oracle_addr,
+            ask_asset_info,
+            intervals,
+            interval,
+            max_spread,
+        } => {
+            let owner = addr_validate_to_lower(deps.api, &cw20_msg.sender)?;
+
+            create_order(
+                deps,
+                env,
+                owner,
+                OrderParams {
+                    pair_addr,
+                    oracle_addr,
+                    offer_asset: Asset {
+                        info: AssetInfo::Token {
+                            contract_addr: info.sender,
+                        },
+                        amount: cw20_msg.amount,
+                    },
+                    ask_asset_info,
+                    intervals,
+                    interval,
+                    max_spread,
+                },
+            )
+        }
+    }
+}
+
+/// ## Description
+/// Creates a new order for the escrowed offer asset. The first slice can be executed right away.
+/// Returns a [`ContractError`] on failure, otherwise returns the [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Params
+/// * **deps** is the object of type [`DepsMut`].
+///
+/// * **env** is the object of type [`Env`].
+///
+/// * **owner** is the object of type [`Addr`]. The owner of the order.
+///
+/// * **params** is the object of type [`OrderParams`].
+fn create_order(
+    deps: DepsMut,
+    env: Env,
+    owner: Addr,
+    params: OrderParams,
+) -> Result<Response, ContractError> {
+    if params.intervals == 0 || params.interval == 0 {
+        return Err(ContractError::InvalidIntervals {});
+    }
+
+    if params.offer_asset.amount < Uint128::from(params.intervals) {
+        return Err(ContractError::InvalidOfferAmount {});
+    }
+
+    if params.max_spread > Decimal::from_str(MAX_ALLOWED_SLIPPAGE)? {
+        return Err(ContractError::AllowedSpreadAssertion {});
+    }
+
+    params.offer_asset.info.check(deps.api)?;
+    params.ask_asset_info.check(deps.api)?;
+
+    let pair_addr = addr_validate_to_lower(deps.api, &params.pair_addr)?;
+    let oracle_addr = addr_validate_to_lower(deps.api, &params.oracle_addr)?;
+    let pair_info: PairInfo = deps
+        .querier
+        .query_wasm_smart(&pair_addr, &PairQueryMsg::Pair {})?;
+
+    if params.offer_asset.info.equal(&params.ask_asset_info)
+        || !pair_info
+            .asset_infos
+            .iter()
+            .any(|asset_info| asset_info.equal(&params.offer_asset.info))
+        || !pair_info
+            .asset_infos
+            .iter()
+            .any(|asset_info| asset_info.equal(&params.ask_asset_info))
+    {
+        return Err(ContractError::InvalidAssets {});
+    }
+
+    // The oracle must quote the prices of the pair assets
+    let oracle_config: OracleConfigResponse = deps
+        .querier
+        .query_wasm_smart(&oracle_addr, &OracleQueryMsg::Config {})?;
+    if !pair_info.asset_infos.iter().all(|asset_info| {
+        oracle_config
+            .asset_infos
+            .iter()
+            .any(|oracle_asset_info| oracle_asset_info.equal(asset_info))
+    }) {
+        return Err(ContractError::InvalidOracle {});
+    }
+
+    let order_id = next_order_id(deps.storage)?;
+    let order = Order {
+        owner,
+        pair_addr,
+        oracle_addr,
+        offer_asset: params.offer_asset,
+        ask_asset_info: params.ask_asset_info,
+        intervals: params.intervals,
+        interval: params.interval,
+        max_spread: params.max_spread,
+        executed_intervals: 0,
+        spent_amount: Uint128::zero(),
+        next_execution_time: env.block.time.seconds(),
+    };
+    ORDERS.save(deps.storage, U64Key::new(order_id), &order)?;
+    OWNER_ORDERS.save(
+        deps.storage,
+        (&order.owner, U64Key::new(order_id)),
+        &Empty {},
+    )?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "create_order"),
+        attr("order_id", order_id.to_string()),
+        attr("owner", order.owner.as_str()),
+        attr("offer_asset", order.offer_asset.to_string()),
+        attr("ask_asset", order.ask_asset_info.to_string()),
+        attr("intervals", order.intervals.to_string()),
+        attr("interval", order.interval.to_string()),
+    ]))
+}
+
+/// ## Description
+/// Swaps the next slice of an order once its interval has elapsed. Every slice sells an equal part
+/// of the unspent amount, the last one sells the rest. The swap fails in the pair if the return
+/// amount is lower than the oracle price by more than the order max spread.
+/// Returns a [`ContractError`] on failure, otherwise returns the [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Params
+/// * **deps** is the object of type [`DepsMut`].
+///
+/// * **env** is the object of type [`Env`].
+///
+/// * **order_id** is the identifier of the order.
+pub fn execute_slice(deps: DepsMut, env: Env, order_id: u64) -> Result<Response, ContractError> {
+    let mut order = ORDERS.load(deps.storage, U64Key::new(order_id))?;
+
+    if env.block.time.seconds() < order.next_execution_time {
+        return Err(ContractError::IntervalNotElapsed(order.next_execution_time));
+    }
+
+    let remaining_amount = order.offer_asset.amount.checked_sub(order.spent_amount)?;
+    let slice_amount =
+        remaining_amount.multiply_ratio(1u128, order.intervals - order.executed_intervals);
+    let mut offer_asset = Asset {
+        info: order.offer_asset.info.clone(),
+        amount: slice_amount,
+    };
+    if offer_asset.is_native_token() {
+        // deduct tax first
+        offer_asset.amount = slice_amount.checked_sub(offer_asset.compute_tax(&deps.querier)?)?;
+    }
+
+    let expected_return: Uint256 = deps.querier.query_wasm_smart(
+        &order.oracle_addr,
+        &OracleQueryMsg::Consult {
+            token: offer_asset.info.clone(),
+            amount: offer_asset.amount,
+        },
+    )?;
+    let expected_return = Uint128::try_from(expected_return)
+        .map_err(|_| StdError::generic_err("Uint128 overflow"))?;
+    if expected_return.is_zero() {
+        return Err(StdError::generic_err("The oracle price is zero").into());
+    }
+
+    let belief_price = Decimal::from_ratio(offer_asset.amount, expected_return);
+    let swap_msg = build_swap_msg(&order, offer_asset, belief_price)?;
+
+    order.spent_amount = order.spent_amount.checked_add(slice_amount)?;
+    order.executed_intervals += 1;
+    order.next_execution_time = env.block.time.seconds() + order.interval;
+    if order.executed_intervals == order.intervals {
+        ORDERS.remove(deps.storage, U64Key::new(order_id));
+        OWNER_ORDERS.remove(deps.storage, (&order.owner, U64Key::new(order_id)));
+    } else {
+        ORDERS.save(deps.storage, U64Key::new(order_id), &order)?;
+    }
+
+    Ok(Response::new().add_message(swap_msg).add_attributes(vec![
+        attr("action", "execute_slice"),
+        attr("order_id", order_id.to_string()),
+        attr("offer_amount", slice_amount.to_string()),
+        attr("expected_return", expected_return.to_string()),
+        attr("executed_intervals", order.executed_intervals.to_string()),
+    ]))
+}
+
+/// ## Description
+/// Returns the message that swaps the specified asset in the order pair and sends the ask asset
+/// to the order owner.
+/// ## Params
+/// * **order** is the object of type [`Order`].
+///
+/// * **offer_asset** is the object of type [`Asset`]. The asset received by the pair.
+///
+/// * **belief_price** is the object of type [`Decimal`]. The oracle price of the ask asset.
+fn build_swap_msg(
+    order: &Order,
+    offer_asset: Asset,
+    belief_price: Decimal,
+) -> StdResult<CosmosMsg> {
+    match &offer_asset.info {
+        AssetInfo::NativeToken { denom } => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: order.pair_addr.to_string(),
+            funds: vec![Coin {
+                denom: denom.clone(),
+                amount: offer_asset.amount,
+            }],
+            msg: to_binary(&PairExecuteMsg::Swap {
+                offer_asset: offer_asset.clone(),
+                ask_asset_info: Some(order.ask_asset_info.clone()),
+                belief_price: Some(belief_price),
+                max_spread: Some(order.max_spread),
+                to: Some(order.owner.to_string()),
+                minimum_receive: None,
+                deadline: None,
+            })?,
+        })),
+        AssetInfo::Token { contract_addr } => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: contract_addr.to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: order.pair_addr.to_string(),
+                amount: offer_asset.amount,
+                msg: to_binary(&PairCw20HookMsg::Swap {
+                    ask_asset_info: Some(order.ask_asset_info.clone()),
+                    belief_price: Some(belief_price),
+                    max_spread: Some(order.max_spread),
+                    to: Some(order.owner.to_string()),
+                    minimum_receive: None,
+                    deadline: None,
+                })?,
+            })?,
+        })),
+    }
+}
+
+/// ## Description
+/// Returns the unspent part of an order to its owner and removes the order.
+/// Returns a [`ContractError`] on failure, otherwise returns the [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Params
+/// * **deps** is the object of type [`DepsMut`].
+///
+/// * **info** is the object of type [`MessageInfo`].
+///
+/// * **order_id** is the identifier of the order.
+pub fn cancel_order(
+    deps: DepsMut,
+    info: MessageInfo,
+    order_id: u64,
+) -> Result<Response, ContractError> {
+    let order = ORDERS.load(deps.storage, U64Key::new(order_id))?;
+
+    // permission check
+    if info.sender != order.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    ORDERS.remove(deps.storage, U64Key::new(order_id));
+    OWNER_ORDERS.remove(deps.storage, (&order.owner, U64Key::new(order_id)));
+
+    let refund_asset = Asset {
+        info: order.offer_asset.info,
+        amount: order.offer_asset.amount.checked_sub(order.spent_amount)?,
+    };
+
+    Ok(Response::new()
+        .add_message(refund_asset.clone().into_msg(&deps.querier, order.owner)?)
+        .add_attributes(vec![
+            attr("action", "cancel_order"),
+            attr("order_id", order_id.to_string()),
+            attr("refund_asset", refund_asset.to_string()),
+        ]))
+}
+
+/// ## Description
+/// Available the query messages of the contract.
+/// ## Params
+/// * **deps** is the object of type [`Deps`].
+///
+/// * **_env** is the object of type [`Env`].
+///
+/// * **msg** is the object of type [`QueryMsg`].
+///
+/// ## Queries
+/// * **QueryMsg::Order { order_id }** Returns an order and its progress.
+///
+/// * **QueryMsg::Orders { owner, start_after, limit }** Returns the orders of an owner.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Order { order_id } => to_binary(&query_order(deps, order_id)?),
+        QueryMsg::Orders {
+            owner,
+            start_after,
+            limit,
+        } => to_binary(&query_orders(deps, owner, start_after, limit)?),
+    }
+}
+
+/// ## Description
+/// Returns an order and its progress in an [`OrderResponse`] object.
+/// ## Params
+/// * **deps** is the object of type [`Deps`].
+///
+/// * **order_id** is the identifier of the order.
+pub fn query_order(deps: Deps, order_id: u64) -> StdResult<OrderResponse> {
+    let order = ORDERS.load(deps.storage, U64Key::new(order_id))?;
+
+    Ok(OrderResponse {
+        order_id,
+        owner: order.owner,
+        pair_addr: order.pair_addr,
+        oracle_addr: order.oracle_addr,
+        offer_asset: order.offer_asset,
+        ask_asset_info: order.ask_asset_info,
+        intervals: order.intervals,
+        interval: order.interval,
+        max_spread: order.max_spread,
+        executed_intervals: order.executed_intervals,
+        spent_amount: order.spent_amount,
+        next_execution_time: order.next_execution_time,
+    })
+}
+
+/// ## Description
+/// Returns the orders of an owner in a vector that contains objects of type [`OrderResponse`].
+/// ## Params
+/// * **deps** is the object of type [`Deps`].
+///
+/// * **owner** is the owner of the orders.
+///
+/// * **start_after** is an [`Option`] type. Sets the order identifier to start reading after.
+///
+/// * **limit** is an [`Option`] type. Sets the number of items to be read.
+pub fn query_orders(
+    deps: Deps,
+    owner: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<OrderResponse>> {
+    let owner = addr_validate_to_lower(deps.api, &owner)?;
+
+    read_owner_orders(deps.storage, &owner, start_after, limit)?
+        .into_iter()
+        .map(|order_id| query_order(deps, order_id))
+        .collect()
+}
+
+/// ## Description
+/// Used for migration of contract. Returns the default object of type [`Response`].
+/// ## Params
+/// * **_deps** is the object of type [`DepsMut`].
+///
+/// * **_env** is the object of type [`Env`].
+///
+/// * **_msg** is the object of type [`MigrateMsg`].
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    Ok(Response::default())
+}
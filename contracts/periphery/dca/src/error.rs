@@ -0,0 +1,37 @@
+use cosmwasm_std::{OverflowError, StdError};
+use thiserror::Error;
+
+/// ## Description
+/// This enum describes DCA contract errors!
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("The offer amount must be at least the number of intervals")]
+    InvalidOfferAmount {},
+
+    #[error("The number of intervals and the interval length must be greater than zero")]
+    InvalidIntervals {},
+
+    #[error("The pair doesn't contain the order assets")]
+    InvalidAssets {},
+
+    #[error("The oracle doesn't provide the prices of the pair assets")]
+    InvalidOracle {},
+
+    #[error("Operation exceeds max spread limit")]
+    AllowedSpreadAssertion {},
+
+    #[error("The next slice can be executed at {0}")]
+    IntervalNotElapsed(u64),
+}
+
+impl From<OverflowError> for ContractError {
+    fn from(o: OverflowError) -> Self {
+        StdError::from(o).into()
+    }
+}
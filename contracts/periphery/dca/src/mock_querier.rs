@@ -0,0 +1,183 @@
+// The tax queriers are only used with the `terra` feature
+#![cfg_attr(not(feature = "terra"), allow(dead_code))]
+
+use astroport::asset::{AssetInfo, PairInfo};
+use astroport::factory::PairType;
+use astroport::oracle::{ConfigResponse as OracleConfigResponse, QueryMsg as OracleQueryMsg};
+use astroport::pair::QueryMsg as PairQueryMsg;
+use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    from_binary, from_slice, to_binary, Addr, Coin, ContractResult, Decimal, OwnedDeps, Querier,
+    QuerierResult, QueryRequest, SystemError, SystemResult, Uint128, Uint256, WasmQuery,
+};
+use std::collections::HashMap;
+#[cfg(feature = "terra")]
+use terra_cosmwasm::{TaxCapResponse, TaxRateResponse, TerraQuery, TerraQueryWrapper, TerraRoute};
+
+#[cfg(feature = "terra")]
+type CustomQuery = TerraQueryWrapper;
+#[cfg(not(feature = "terra"))]
+type CustomQuery = cosmwasm_std::Empty;
+
+pub fn mock_dependencies(
+    contract_balance: &[Coin],
+) -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+    let custom_querier: WasmMockQuerier =
+        WasmMockQuerier::new(MockQuerier::new(&[(MOCK_CONTRACT_ADDR, contract_balance)]));
+
+    OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier: custom_querier,
+    }
+}
+
+pub struct WasmMockQuerier {
+    base: MockQuerier<CustomQuery>,
+    pair_querier: PairQuerier,
+    oracle_querier: OracleQuerier,
+    tax_querier: TaxQuerier,
+}
+
+#[derive(Clone, Default)]
+pub struct PairQuerier {
+    pairs: HashMap<String, [AssetInfo; 2]>,
+}
+
+#[derive(Clone, Default)]
+pub struct OracleQuerier {
+    // The assets of every oracle and the amount of the ask asset it returns per unit of the offer asset
+    oracles: HashMap<String, ([AssetInfo; 2], Decimal)>,
+}
+
+#[derive(Clone, Default)]
+pub struct TaxQuerier {
+    rate: Decimal,
+    caps: HashMap<String, Uint128>,
+}
+
+impl Querier for WasmMockQuerier {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        // MockQuerier doesn't support Custom, so we ignore it completely here
+        let request: QueryRequest<CustomQuery> = match from_slice(bin_request) {
+            Ok(v) => v,
+            Err(e) => {
+                return SystemResult::Err(SystemError::InvalidRequest {
+                    error: format!("Parsing query request: {}", e),
+                    request: bin_request.into(),
+                })
+            }
+        };
+        self.handle_query(&request)
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn handle_query(&self, request: &QueryRequest<CustomQuery>) -> QuerierResult {
+        match &request {
+            #[cfg(feature = "terra")]
+            QueryRequest::Custom(TerraQueryWrapper { route, query_data }) => {
+                if route == &TerraRoute::Treasury {
+                    match query_data {
+                        TerraQuery::TaxRate {} => {
+                            let res = TaxRateResponse {
+                                rate: self.tax_querier.rate,
+                            };
+                            SystemResult::Ok(ContractResult::from(to_binary(&res)))
+                        }
+                        TerraQuery::TaxCap { denom } => {
+                            let cap = self
+                                .tax_querier
+                                .caps
+                                .get(denom)
+                                .copied()
+                                .unwrap_or_default();
+                            let res = TaxCapResponse { cap };
+                            SystemResult::Ok(ContractResult::from(to_binary(&res)))
+                        }
+                        _ => panic!("DO NOT ENTER HERE"),
+                    }
+                } else {
+                    panic!("DO NOT ENTER HERE")
+                }
+            }
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg }) => {
+                if let Some((asset_infos, price)) = self.oracle_querier.oracles.get(contract_addr) {
+                    return match from_binary(msg).unwrap() {
+                        OracleQueryMsg::Consult { amount, .. } => SystemResult::Ok(
+                            ContractResult::from(to_binary(&Uint256::from(amount * *price))),
+                        ),
+                        OracleQueryMsg::Config {} => SystemResult::Ok(ContractResult::from(
+                            to_binary(&OracleConfigResponse {
+                                owner: Addr::unchecked("owner"),
+                                factory: Addr::unchecked("factory"),
+                                asset_infos: asset_infos.clone(),
+                                pair: PairInfo {
+                                    asset_infos: asset_infos.to_vec(),
+                                    contract_addr: Addr::unchecked("pair0000"),
+                                    liquidity_token: Addr::unchecked("liquidity0000"),
+                                    pair_type: PairType::Xyk {},
+                                },
+                            }),
+                        )),
+                    };
+                }
+
+                let asset_infos = match self.pair_querier.pairs.get(contract_addr) {
+                    Some(v) => v,
+                    None => {
+                        return SystemResult::Err(SystemError::Unknown {});
+                    }
+                };
+
+                match from_binary(msg).unwrap() {
+                    PairQueryMsg::Pair {} => {
+                        SystemResult::Ok(ContractResult::from(to_binary(&PairInfo {
+                            asset_infos: asset_infos.to_vec(),
+                            contract_addr: Addr::unchecked(contract_addr),
+                            liquidity_token: Addr::unchecked("liquidity0000"),
+                            pair_type: PairType::Xyk {},
+                        })))
+                    }
+                    _ => panic!("DO NOT ENTER HERE"),
+                }
+            }
+            _ => self.base.handle_query(request),
+        }
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn new(base: MockQuerier<CustomQuery>) -> Self {
+        WasmMockQuerier {
+            base,
+            pair_querier: PairQuerier::default(),
+            oracle_querier: OracleQuerier::default(),
+            tax_querier: TaxQuerier::default(),
+        }
+    }
+
+    pub fn with_pair(&mut self, pair: &str, asset_infos: [AssetInfo; 2]) {
+        self.pair_querier
+            .pairs
+            .insert(pair.to_string(), asset_infos);
+    }
+
+    // Configures an oracle of the specified assets that returns `price` units of the ask asset per
+    // unit of the offer asset
+    pub fn with_oracle(&mut self, oracle: &str, asset_infos: [AssetInfo; 2], price: Decimal) {
+        self.oracle_querier
+            .oracles
+            .insert(oracle.to_string(), (asset_infos, price));
+    }
+
+    pub fn with_tax(&mut self, rate: Decimal, caps: &[(&String, &Uint128)]) {
+        self.tax_querier = TaxQuerier {
+            rate,
+            caps: caps
+                .iter()
+                .map(|(denom, cap)| (denom.to_string(), **cap))
+                .collect(),
+        };
+    }
+}
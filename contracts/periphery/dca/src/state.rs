@@ -0,0 +1,91 @@
+use astroport::asset::{Asset, AssetInfo};
+use cosmwasm_std::{Addr, Decimal, Empty, Order as OrderBy, StdResult, Storage, Uint128};
+use cw_storage_plus::{Bound, Item, Map, U64Key};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::convert::TryInto;
+
+/// ## Description
+/// This structure describes an order that sells an asset in equal slices.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Order {
+    /// The order owner address
+    pub owner: Addr,
+    /// The pair to swap in
+    pub pair_addr: Addr,
+    /// The oracle used to check the price of every slice
+    pub oracle_addr: Addr,
+    /// The total amount to sell
+    pub offer_asset: Asset,
+    /// The asset to buy
+    pub ask_asset_info: AssetInfo,
+    /// The number of slices
+    pub intervals: u64,
+    /// The minimum time between two slices, in seconds
+    pub interval: u64,
+    /// The maximum spread of every slice relative to the oracle price
+    pub max_spread: Decimal,
+    /// The number of executed slices
+    pub executed_intervals: u64,
+    /// The offer amount spent so far
+    pub spent_amount: Uint128,
+    /// The earliest time the next slice can be executed at, in seconds
+    pub next_execution_time: u64,
+}
+
+/// ## Description
+/// Stores orders by their identifiers
+pub const ORDERS: Map<U64Key, Order> = Map::new("orders");
+
+/// ## Description
+/// Stores the identifiers of the orders of every owner
+pub const OWNER_ORDERS: Map<(&Addr, U64Key), Empty> = Map::new("owner_orders");
+
+/// ## Description
+/// Stores the identifier of the last created order
+pub const LAST_ORDER_ID: Item<u64> = Item::new("last_order_id");
+
+/// ## Description
+/// Returns the next order identifier and stores it as the last one.
+pub fn next_order_id(storage: &mut dyn Storage) -> StdResult<u64> {
+    let id = LAST_ORDER_ID.may_load(storage)?.unwrap_or_default() + 1;
+    LAST_ORDER_ID.save(storage, &id)?;
+    Ok(id)
+}
+
+//settings for pagination
+/// The maximum limit for reading orders from [`OWNER_ORDERS`]
+const MAX_LIMIT: u32 = 30;
+
+/// The default limit for reading orders from [`OWNER_ORDERS`]
+const DEFAULT_LIMIT: u32 = 10;
+
+/// ## Description
+/// Reads the order identifiers of an owner according to the specified parameters in `start_after` and `limit` variables.
+/// ## Params
+/// * **storage** is the object of type [`Storage`].
+///
+/// * **owner** is the object of type [`Addr`]. The owner of the orders.
+///
+/// * **start_after** is an [`Option`] type. Sets the order identifier to start reading after.
+///
+/// * **limit** is an [`Option`] type. Sets the number of items to be read.
+pub fn read_owner_orders(
+    storage: &dyn Storage,
+    owner: &Addr,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<u64>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive_int);
+
+    OWNER_ORDERS
+        .prefix(owner)
+        .range(storage, start, None, OrderBy::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (key, _) = item?;
+            Ok(u64::from_be_bytes(key.as_slice().try_into().unwrap()))
+        })
+        .collect()
+}
@@ -0,0 +1,403 @@
+use crate::contract::{execute, instantiate, query_order, query_orders};
+use crate::error::ContractError;
+use crate::mock_querier::mock_dependencies;
+use astroport::asset::{Asset, AssetInfo};
+use astroport::dca::{Cw20HookMsg, ExecuteMsg, InstantiateMsg};
+use astroport::pair::{Cw20HookMsg as PairCw20HookMsg, ExecuteMsg as PairExecuteMsg};
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{
+    attr, to_binary, Addr, BankMsg, Coin, CosmosMsg, Decimal, Env, StdError, SubMsg, Timestamp,
+    Uint128, WasmMsg,
+};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+
+fn asset_infos() -> [AssetInfo; 2] {
+    [
+        AssetInfo::NativeToken {
+            denom: "uusd".to_string(),
+        },
+        AssetInfo::Token {
+            contract_addr: Addr::unchecked("asset0000"),
+        },
+    ]
+}
+
+fn mock_env_with_block_time(time: u64) -> Env {
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(time);
+    env
+}
+
+fn create_order_msg(amount: u128, intervals: u64, max_spread: Decimal) -> ExecuteMsg {
+    ExecuteMsg::CreateOrder {
+        pair_addr: "pair0000".to_string(),
+        oracle_addr: "oracle0000".to_string(),
+        offer_asset: Asset {
+            info: asset_infos()[0].clone(),
+            amount: Uint128::new(amount),
+        },
+        ask_asset_info: asset_infos()[1].clone(),
+        intervals,
+        interval: 3600,
+        max_spread,
+    }
+}
+
+#[test]
+fn create_order() {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier.with_pair("pair0000", asset_infos());
+    deps.querier
+        .with_oracle("oracle0000", asset_infos(), Decimal::one());
+    // An oracle of another pool
+    deps.querier.with_oracle(
+        "oracle0001",
+        [
+            asset_infos()[0].clone(),
+            AssetInfo::NativeToken {
+                denom: "ukrw".to_string(),
+            },
+        ],
+        Decimal::one(),
+    );
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        InstantiateMsg {},
+    )
+    .unwrap();
+
+    let funds = [Coin {
+        denom: "uusd".to_string(),
+        amount: Uint128::new(100),
+    }];
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        create_order_msg(100, 4, Decimal::percent(1)),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Std(StdError::generic_err(
+            "Native token balance mismatch between the argument and the transferred"
+        ))
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &funds),
+        create_order_msg(100, 0, Decimal::percent(1)),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidIntervals {});
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &funds),
+        create_order_msg(100, 101, Decimal::percent(1)),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidOfferAmount {});
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &funds),
+        create_order_msg(100, 4, Decimal::percent(51)),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::AllowedSpreadAssertion {});
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &funds),
+        ExecuteMsg::CreateOrder {
+            pair_addr: "pair0000".to_string(),
+            oracle_addr: "oracle0000".to_string(),
+            offer_asset: Asset {
+                info: asset_infos()[0].clone(),
+                amount: Uint128::new(100),
+            },
+            ask_asset_info: AssetInfo::NativeToken {
+                denom: "ukrw".to_string(),
+            },
+            intervals: 4,
+            interval: 3600,
+            max_spread: Decimal::percent(1),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidAssets {});
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &funds),
+        ExecuteMsg::CreateOrder {
+            pair_addr: "pair0000".to_string(),
+            oracle_addr: "oracle0001".to_string(),
+            offer_asset: Asset {
+                info: asset_infos()[0].clone(),
+                amount: Uint128::new(100),
+            },
+            ask_asset_info: asset_infos()[1].clone(),
+            intervals: 4,
+            interval: 3600,
+            max_spread: Decimal::percent(1),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidOracle {});
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &funds),
+        create_order_msg(100, 4, Decimal::percent(1)),
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "create_order"),
+            attr("order_id", "1"),
+            attr("owner", "addr0000"),
+            attr("offer_asset", "100uusd"),
+            attr("ask_asset", "asset0000"),
+            attr("intervals", "4"),
+            attr("interval", "3600"),
+        ]
+    );
+}
+
+#[test]
+fn execute_slices_and_cancel() {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier.with_pair("pair0000", asset_infos());
+    deps.querier.with_oracle(
+        "oracle0000",
+        asset_infos(),
+        Decimal::from_ratio(2u128, 1u128),
+    );
+    deps.querier.with_tax(
+        Decimal::percent(1),
+        &[(&"uusd".to_string(), &Uint128::new(1_000_000_000))],
+    );
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        InstantiateMsg {},
+    )
+    .unwrap();
+
+    execute(
+        deps.as_mut(),
+        mock_env_with_block_time(1000),
+        mock_info(
+            "addr0000",
+            &[Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::new(1000_000000),
+            }],
+        ),
+        create_order_msg(1000_000000, 4, Decimal::percent(1)),
+    )
+    .unwrap();
+
+    // Anyone can execute the first slice right away
+    let res = execute(
+        deps.as_mut(),
+        mock_env_with_block_time(1000),
+        mock_info("keeper", &[]),
+        ExecuteMsg::ExecuteSlice { order_id: 1 },
+    )
+    .unwrap();
+
+    // 1% tax is deducted from the slice before the swap
+    let (offer_amount, expected_return) = if cfg!(feature = "terra") {
+        (Uint128::new(247524752), Uint128::new(495049504))
+    } else {
+        (Uint128::new(250000000), Uint128::new(500000000))
+    };
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(WasmMsg::Execute {
+            contract_addr: "pair0000".to_string(),
+            funds: vec![Coin {
+                denom: "uusd".to_string(),
+                amount: offer_amount,
+            }],
+            msg: to_binary(&PairExecuteMsg::Swap {
+                offer_asset: Asset {
+                    info: asset_infos()[0].clone(),
+                    amount: offer_amount,
+                },
+                ask_asset_info: Some(asset_infos()[1].clone()),
+                belief_price: Some(Decimal::percent(50)),
+                max_spread: Some(Decimal::percent(1)),
+                to: Some("addr0000".to_string()),
+                minimum_receive: None,
+                deadline: None,
+            })
+            .unwrap(),
+        })]
+    );
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "execute_slice"),
+            attr("order_id", "1"),
+            attr("offer_amount", "250000000"),
+            attr("expected_return", expected_return.to_string()),
+            attr("executed_intervals", "1"),
+        ]
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env_with_block_time(4599),
+        mock_info("keeper", &[]),
+        ExecuteMsg::ExecuteSlice { order_id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::IntervalNotElapsed(4600));
+
+    execute(
+        deps.as_mut(),
+        mock_env_with_block_time(4600),
+        mock_info("keeper", &[]),
+        ExecuteMsg::ExecuteSlice { order_id: 1 },
+    )
+    .unwrap();
+
+    let order = query_order(deps.as_ref(), 1).unwrap();
+    assert_eq!(order.executed_intervals, 2);
+    assert_eq!(order.spent_amount, Uint128::new(500_000000));
+    assert_eq!(order.next_execution_time, 8200);
+
+    // Only the owner can cancel an order
+    let err = execute(
+        deps.as_mut(),
+        mock_env_with_block_time(5000),
+        mock_info("keeper", &[]),
+        ExecuteMsg::CancelOrder { order_id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let refund_amount = if cfg!(feature = "terra") {
+        Uint128::new(495049504)
+    } else {
+        Uint128::new(500000000)
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env_with_block_time(5000),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::CancelOrder { order_id: 1 },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "addr0000".to_string(),
+            amount: vec![Coin {
+                denom: "uusd".to_string(),
+                amount: refund_amount,
+            }],
+        }))]
+    );
+    assert_eq!(res.attributes[2], attr("refund_asset", "500000000uusd"));
+
+    query_order(deps.as_ref(), 1).unwrap_err();
+}
+
+#[test]
+fn token_order() {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier.with_pair("pair0000", asset_infos());
+    deps.querier.with_oracle(
+        "oracle0000",
+        asset_infos(),
+        Decimal::from_ratio(1u128, 4u128),
+    );
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        InstantiateMsg {},
+    )
+    .unwrap();
+
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "addr0000".to_string(),
+        amount: Uint128::new(1000),
+        msg: to_binary(&Cw20HookMsg::CreateOrder {
+            pair_addr: "pair0000".to_string(),
+            oracle_addr: "oracle0000".to_string(),
+            ask_asset_info: asset_infos()[0].clone(),
+            intervals: 3,
+            interval: 60,
+            max_spread: Decimal::percent(2),
+        })
+        .unwrap(),
+    });
+    execute(
+        deps.as_mut(),
+        mock_env_with_block_time(1000),
+        mock_info("asset0000", &[]),
+        msg,
+    )
+    .unwrap();
+
+    let orders = query_orders(deps.as_ref(), "addr0000".to_string(), None, None).unwrap();
+    assert_eq!(orders.len(), 1);
+    assert_eq!(orders[0].offer_asset.amount, Uint128::new(1000));
+
+    // Every slice sells an equal part of the unspent amount
+    for (time, slice_amount) in [(1000, 333u128), (1060, 333), (1120, 334)] {
+        let res = execute(
+            deps.as_mut(),
+            mock_env_with_block_time(time),
+            mock_info("keeper", &[]),
+            ExecuteMsg::ExecuteSlice { order_id: 1 },
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(WasmMsg::Execute {
+                contract_addr: "asset0000".to_string(),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::Send {
+                    contract: "pair0000".to_string(),
+                    amount: Uint128::new(slice_amount),
+                    msg: to_binary(&PairCw20HookMsg::Swap {
+                        ask_asset_info: Some(asset_infos()[0].clone()),
+                        belief_price: Some(Decimal::from_ratio(slice_amount, slice_amount / 4)),
+                        max_spread: Some(Decimal::percent(2)),
+                        to: Some("addr0000".to_string()),
+                        minimum_receive: None,
+                        deadline: None,
+                    })
+                    .unwrap(),
+                })
+                .unwrap(),
+            })]
+        );
+    }
+
+    // The order is removed once all the slices are executed
+    query_order(deps.as_ref(), 1).unwrap_err();
+    assert_eq!(
+        query_orders(deps.as_ref(), "addr0000".to_string(), None, None).unwrap(),
+        vec![]
+    );
+}
@@ -0,0 +1,661 @@
+use crate::error::ContractError;
+use crate::state::{
+    create_order, next_order_id, read_orders, read_owner_orders, remove_order, Config, Order,
+    CONFIG, ORDERS, OWNERSHIP_PROPOSAL,
+};
+use astroport::asset::{addr_validate_to_lower, Asset, AssetInfo, PairInfo};
+use astroport::common::{claim_ownership, drop_ownership_proposal, propose_new_owner};
+use astroport::limit_order::{
+    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, OrderCursor,
+    OrderResponse, QueryMsg, MAX_BOUNTY_BPS,
+};
+use astroport::pair::{
+    Cw20HookMsg as PairCw20HookMsg, ExecuteMsg as PairExecuteMsg, QueryMsg as PairQueryMsg,
+    SimulationResponse, MAX_ALLOWED_SLIPPAGE,
+};
+use cosmwasm_std::{
+    attr, entry_point, from_binary, to_binary, Addr, Binary, Coin, CosmosMsg, Decimal, Deps,
+    DepsMut, Env, MessageInfo, QuerierWrapper, Response, StdError, StdResult, Uint128, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw_storage_plus::U64Key;
+use std::str::FromStr;
+
+/// Contract name that is used for migration.
+const CONTRACT_NAME: &str = "astroport-limit-order";
+/// Contract version that is used for migration.
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// The denominator of the bounty bps
+const BPS_DENOMINATOR: u128 = 10000;
+
+/// ## Description
+/// Creates a new contract with the specified parameters in the [`InstantiateMsg`].
+/// Returns the default object of type [`Response`] if the operation was successful,
+/// or a [`ContractError`] if the contract was not created.
+/// ## Params
+/// * **deps** is the object of type [`DepsMut`].
+///
+/// * **_env** is the object of type [`Env`].
+///
+/// * **_info** is the object of type [`MessageInfo`].
+///
+/// * **msg** is a message of type [`InstantiateMsg`] which contains the basic settings for creating a contract.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    if msg.bounty_bps > MAX_BOUNTY_BPS {
+        return Err(ContractError::InvalidBountyBps {});
+    }
+
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            owner: addr_validate_to_lower(deps.api, &msg.owner)?,
+            bounty_bps: msg.bounty_bps,
+        },
+    )?;
+
+    Ok(Response::default())
+}
+
+/// ## Description
+/// Available the execute messages of the contract.
+/// ## Params
+/// * **deps** is the object of type [`DepsMut`].
+///
+/// * **env** is the object of type [`Env`].
+///
+/// * **info** is the object of type [`MessageInfo`].
+///
+/// * **msg** is the object of type [`ExecuteMsg`].
+///
+/// ## Queries
+/// * **ExecuteMsg::Receive(msg)** Receives a message of type [`Cw20ReceiveMsg`] and processes
+/// it depending on the received template.
+///
+/// * **ExecuteMsg::SubmitOrder {
+///             pair_addr,
+///             offer_asset,
+///             ask_asset_info,
+///             price,
+///         }** Escrows the native offer asset and creates a new limit order.
+///
+/// * **ExecuteMsg::CancelOrder { order_id }** Returns the unfilled part of an order to its owner.
+///
+/// * **ExecuteMsg::ExecuteOrder { order_id, amount }** Fills an order once the pair reaches its price.
+///
+/// * **ExecuteMsg::UpdateConfig { bounty_bps }** Updates the contract settings.
+///
+/// * **ExecuteMsg::ProposeNewOwner { owner, expires_in }** Creates a new request to change ownership.
+///
+/// * **ExecuteMsg::DropOwnershipProposal {}** Removes a request to change ownership.
+///
+/// * **ExecuteMsg::ClaimOwnership {}** Claims ownership by the new owner.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, info, msg),
+        ExecuteMsg::SubmitOrder {
+            pair_addr,
+            offer_asset,
+            ask_asset_info,
+            price,
+        } => {
+            if !offer_asset.is_native_token() {
+                return Err(ContractError::Unauthorized {});
+            }
+            offer_asset.assert_sent_native_token_balance(&info)?;
+
+            submit_order(
+                deps,
+                info.sender,
+                pair_addr,
+                offer_asset,
+                ask_asset_info,
+                price,
+            )
+        }
+        ExecuteMsg::CancelOrder { order_id } => cancel_order(deps, info, order_id),
+        ExecuteMsg::ExecuteOrder { order_id, amount } => {
+            execute_order(deps, info, order_id, amount)
+        }
+        ExecuteMsg::UpdateConfig { bounty_bps } => update_config(deps, info, bounty_bps),
+        ExecuteMsg::ProposeNewOwner { owner, expires_in } => {
+            let config = CONFIG.load(deps.storage)?;
+
+            propose_new_owner(
+                deps,
+                info,
+                env,
+                owner,
+                expires_in,
+                config.owner,
+                OWNERSHIP_PROPOSAL,
+            )
+            .map_err(|e| e.into())
+        }
+        ExecuteMsg::DropOwnershipProposal {} => {
+            let config = CONFIG.load(deps.storage)?;
+
+            drop_ownership_proposal(deps, info, config.owner, OWNERSHIP_PROPOSAL)
+                .map_err(|e| e.into())
+        }
+        ExecuteMsg::ClaimOwnership {} => {
+            claim_ownership(deps, info, env, OWNERSHIP_PROPOSAL, |deps, new_owner| {
+                CONFIG.update::<_, StdError>(deps.storage, |mut v| {
+                    v.owner = new_owner;
+                    Ok(v)
+                })?;
+
+                Ok(())
+            })
+            .map_err(|e| e.into())
+        }
+    }
+}
+
+/// ## Description
+/// Receives a message of type [`Cw20ReceiveMsg`] and processes it depending on the received template.
+/// If the template is not found in the received message, then an [`ContractError`] is returned,
+/// otherwise returns the [`Response`] with the specified attributes if the operation was successful
+/// ## Params
+/// * **deps** is the object of type [`DepsMut`].
+///
+/// * **info** is the object of type [`MessageInfo`].
+///
+/// * **cw20_msg** is the object of type [`Cw20ReceiveMsg`].
+pub fn receive_cw20(
+    deps: DepsMut,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    match from_binary(&cw20_msg.msg)? {
+        Cw20HookMsg::SubmitOrder {
+            pair_addr,
+            ask_asset_info,
+            price,
+        } => {
+            let owner = addr_validate_to_lower(deps.api, &cw20_msg.sender)?;
+            let offer_asset = Asset {
+                info: AssetInfo::Token {
+                    contract_addr: info.sender,
+                },
+                amount: cw20_msg.amount,
+            };
+
+            submit_order(deps, owner, pair_addr, offer_asset, ask_asset_info, price)
+        }
+    }
+}
+
+/// ## Description
+/// Creates a new limit order for the escrowed offer asset. Returns a [`ContractError`] on failure,
+/// otherwise returns the [`Response`] with the specified attributes if the operation was successful.
+/// ## Params
+/// * **deps** is the object of type [`DepsMut`].
+///
+/// * **owner** is the object of type [`Addr`]. The owner of the order.
+///
+/// * **pair_addr** is the object of type [`String`]. The pair to execute the order against.
+///
+/// * **offer_asset** is the object of type [`Asset`]. The escrowed asset to sell.
+///
+/// * **ask_asset_info** is the object of type [`AssetInfo`]. The asset to buy.
+///
+/// * **price** is the object of type [`Decimal`]. The minimum amount of the ask asset received per
+/// unit of the offer asset.
+pub fn submit_order(
+    deps: DepsMut,
+    owner: Addr,
+    pair_addr: String,
+    offer_asset: Asset,
+    ask_asset_info: AssetInfo,
+    price: Decimal,
+) -> Result<Response, ContractError> {
+    if offer_asset.amount.is_zero() {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+
+    if price.is_zero() {
+        return Err(ContractError::InvalidZeroPrice {});
+    }
+
+    offer_asset.info.check(deps.api)?;
+    ask_asset_info.check(deps.api)?;
+
+    let pair_addr = addr_validate_to_lower(deps.api, &pair_addr)?;
+    let pair_info: PairInfo = deps
+        .querier
+        .query_wasm_smart(&pair_addr, &PairQueryMsg::Pair {})?;
+
+    if offer_asset.info.equal(&ask_asset_info)
+        || !pair_info
+            .asset_infos
+            .iter()
+            .any(|asset_info| asset_info.equal(&offer_asset.info))
+        || !pair_info
+            .asset_infos
+            .iter()
+            .any(|asset_info| asset_info.equal(&ask_asset_info))
+    {
+        return Err(ContractError::InvalidAssets {});
+    }
+
+    let order_id = next_order_id(deps.storage)?;
+    let order = Order {
+        owner,
+        pair_addr,
+        offer_asset,
+        ask_asset_info,
+        price,
+        filled_amount: Uint128::zero(),
+    };
+    create_order(deps.storage, order_id, &order)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "submit_order"),
+        attr("order_id", order_id.to_string()),
+        attr("owner", order.owner.as_str()),
+        attr("pair", order.pair_addr.as_str()),
+        attr("offer_asset", order.offer_asset.to_string()),
+        attr("ask_asset", order.ask_asset_info.to_string()),
+        attr("price", order.price.to_string()),
+    ]))
+}
+
+/// ## Description
+/// Returns the unfilled part of an order to its owner and removes the order.
+/// Returns a [`ContractError`] on failure, otherwise returns the [`Response`] with the specified
+/// attributes if the operation was successful.
+/// ## Params
+/// * **deps** is the object of type [`DepsMut`].
+///
+/// * **info** is the object of type [`MessageInfo`].
+///
+/// * **order_id** is the identifier of the order.
+pub fn cancel_order(
+    deps: DepsMut,
+    info: MessageInfo,
+    order_id: u64,
+) -> Result<Response, ContractError> {
+    let order = ORDERS.load(deps.storage, U64Key::new(order_id))?;
+
+    // permission check
+    if info.sender != order.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    remove_order(deps.storage, order_id, &order);
+
+    Ok(Response::new()
+        .add_message(
+            order
+                .offer_asset
+                .clone()
+                .into_msg(&deps.querier, order.owner)?,
+        )
+        .add_attributes(vec![
+            attr("action", "cancel_order"),
+            attr("order_id", order_id.to_string()),
+            attr("refund_asset", order.offer_asset.to_string()),
+        ]))
+}
+
+/// ## Description
+/// Fills an order if the pair simulation returns at least the order price for the fill amount.
+/// The executor receives the bounty from the filled offer amount, the rest is swapped in the pair
+/// and the ask asset is sent straight to the order owner. Returns a [`ContractError`] on failure,
+/// otherwise returns the [`Response`] with the specified attributes if the operation was successful.
+/// ## Params
+/// * **deps** is the object of type [`DepsMut`].
+///
+/// * **info** is the object of type [`MessageInfo`].
+///
+/// * **order_id** is the identifier of the order.
+///
+/// * **amount** is an [`Option`] type. The offer amount to fill, the whole unfilled amount by default.
+pub fn execute_order(
+    deps: DepsMut,
+    info: MessageInfo,
+    order_id: u64,
+    amount: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut order = ORDERS.load(deps.storage, U64Key::new(order_id))?;
+
+    let amount = amount.unwrap_or(order.offer_asset.amount);
+    if amount.is_zero() {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+    if amount > order.offer_asset.amount {
+        return Err(ContractError::FillAmountTooBig {});
+    }
+
+    let bounty_amount = amount.multiply_ratio(Uint128::from(config.bounty_bps), BPS_DENOMINATOR);
+    let swap_asset = Asset {
+        info: order.offer_asset.info.clone(),
+        amount: amount.checked_sub(bounty_amount)?,
+    };
+
+    let minimum_receive = amount * order.price;
+    let (swap_msg, offer_asset) =
+        build_swap_msg(&deps.querier, &order, swap_asset, minimum_receive)?;
+    let simulation: SimulationResponse = deps.querier.query_wasm_smart(
+        &order.pair_addr,
+        &PairQueryMsg::Simulation {
+            offer_asset,
+            ask_asset_info: Some(order.ask_asset_info.clone()),
+        },
+    )?;
+
+    // The bounty and the taxes are paid from the order, so the owner gets at least the order
+    // price for the whole fill amount
+    if simulation.return_amount < minimum_receive {
+        return Err(ContractError::PriceNotReached {});
+    }
+
+    let mut messages = vec![swap_msg];
+    if !bounty_amount.is_zero() {
+        messages.push(
+            Asset {
+                info: order.offer_asset.info.clone(),
+                amount: bounty_amount,
+            }
+            .into_msg(&deps.querier, info.sender.clone())?,
+        );
+    }
+
+    order.offer_asset.amount = order.offer_asset.amount.checked_sub(amount)?;
+    order.filled_amount = order.filled_amount.checked_add(amount)?;
+    if order.offer_asset.amount.is_zero() {
+        remove_order(deps.storage, order_id, &order);
+    } else {
+        ORDERS.save(deps.storage, U64Key::new(order_id), &order)?;
+    }
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        attr("action", "execute_order"),
+        attr("order_id", order_id.to_string()),
+        attr("executor", info.sender.as_str()),
+        attr("offer_amount", amount.to_string()),
+        attr("return_amount", simulation.return_amount.to_string()),
+        attr("bounty_amount", bounty_amount.to_string()),
+    ]))
+}
+
+/// ## Description
+/// Returns the message that swaps the specified asset in the order pair and sends the ask asset
+/// to the order owner, along with the offer asset the pair receives after the taxes.
+/// ## Params
+/// * **querier** is the object of type [`QuerierWrapper`].
+///
+/// * **order** is the object of type [`Order`].
+///
+/// * **swap_asset** is the object of type [`Asset`]. The asset to swap.
+///
+/// * **minimum_receive** is the object of type [`Uint128`]. The ask amount the order owner must receive.
+fn build_swap_msg(
+    querier: &QuerierWrapper,
+    order: &Order,
+    swap_asset: Asset,
+    minimum_receive: Uint128,
+) -> StdResult<(CosmosMsg, Asset)> {
+    // The order price is enforced by the pair with the minimum receive amount, so the pair
+    // spread check only needs to allow the swap
+    let max_spread = Some(Decimal::from_str(MAX_ALLOWED_SLIPPAGE)?);
+
+    match &swap_asset.info {
+        AssetInfo::NativeToken { denom } => {
+            // deduct tax first
+            let amount = swap_asset
+                .amount
+                .checked_sub(swap_asset.compute_tax(querier)?)?;
+            let offer_asset = Asset {
+                info: swap_asset.info.clone(),
+                amount,
+            };
+
+            Ok((
+                CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: order.pair_addr.to_string(),
+                    funds: vec![Coin {
+                        denom: denom.clone(),
+                        amount,
+                    }],
+                    msg: to_binary(&PairExecuteMsg::Swap {
+                        offer_asset: offer_asset.clone(),
+                        ask_asset_info: Some(order.ask_asset_info.clone()),
+                        belief_price: None,
+                        max_spread,
+                        to: Some(order.owner.to_string()),
+                        minimum_receive: Some(minimum_receive),
+                        deadline: None,
+                    })?,
+                }),
+                offer_asset,
+            ))
+        }
+        AssetInfo::Token { contract_addr } => Ok((
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: contract_addr.to_string(),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::Send {
+                    contract: order.pair_addr.to_string(),
+                    amount: swap_asset.amount,
+                    msg: to_binary(&PairCw20HookMsg::Swap {
+                        ask_asset_info: Some(order.ask_asset_info.clone()),
+                        belief_price: None,
+                        max_spread,
+                        to: Some(order.owner.to_string()),
+                        minimum_receive: Some(minimum_receive),
+                        deadline: None,
+                    })?,
+                })?,
+            }),
+            swap_asset,
+        )),
+    }
+}
+
+/// ## Description
+/// Updates the contract settings. Returns a [`ContractError`] on failure,
+/// otherwise returns the [`Response`] with the specified attributes if the operation was successful.
+/// ## Params
+/// * **deps** is the object of type [`DepsMut`].
+///
+/// * **info** is the object of type [`MessageInfo`].
+///
+/// * **bounty_bps** is an [`Option`] type. The new bounty paid to the executors of orders.
+pub fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    bounty_bps: Option<u16>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(bounty_bps) = bounty_bps {
+        if bounty_bps > MAX_BOUNTY_BPS {
+            return Err(ContractError::InvalidBountyBps {});
+        }
+        config.bounty_bps = bounty_bps;
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+/// ## Description
+/// Available the query messages of the contract.
+/// ## Params
+/// * **deps** is the object of type [`Deps`].
+///
+/// * **_env** is the object of type [`Env`].
+///
+/// * **msg** is the object of type [`QueryMsg`].
+///
+/// ## Queries
+/// * **QueryMsg::Config {}** Returns the contract configuration.
+///
+/// * **QueryMsg::Order { order_id }** Returns an order.
+///
+/// * **QueryMsg::Orders {
+///             pair_addr,
+///             offer_asset_info,
+///             start_after,
+///             limit,
+///         }** Returns the orders selling an asset in a pair sorted by price.
+///
+/// * **QueryMsg::OwnerOrders { owner, start_after, limit }** Returns the orders of an owner.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Order { order_id } => to_binary(&query_order(deps, order_id)?),
+        QueryMsg::Orders {
+            pair_addr,
+            offer_asset_info,
+            start_after,
+            limit,
+        } => to_binary(&query_orders(
+            deps,
+            pair_addr,
+            offer_asset_info,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::OwnerOrders {
+            owner,
+            start_after,
+            limit,
+        } => to_binary(&query_owner_orders(deps, owner, start_after, limit)?),
+    }
+}
+
+/// ## Description
+/// Returns the contract configuration in a [`ConfigResponse`] object.
+/// ## Params
+/// * **deps** is the object of type [`Deps`].
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+
+    Ok(ConfigResponse {
+        owner: config.owner,
+        bounty_bps: config.bounty_bps,
+    })
+}
+
+/// ## Description
+/// Returns an order in an [`OrderResponse`] object.
+/// ## Params
+/// * **deps** is the object of type [`Deps`].
+///
+/// * **order_id** is the identifier of the order.
+pub fn query_order(deps: Deps, order_id: u64) -> StdResult<OrderResponse> {
+    let order = ORDERS.load(deps.storage, U64Key::new(order_id))?;
+
+    Ok(order_response(order_id, order))
+}
+
+/// ## Description
+/// Returns the orders selling an asset in a pair, starting with the lowest price, in a vector
+/// that contains objects of type [`OrderResponse`].
+/// ## Params
+/// * **deps** is the object of type [`Deps`].
+///
+/// * **pair_addr** is the pair the orders are executed against.
+///
+/// * **offer_asset_info** is the object of type [`AssetInfo`]. The asset sold by the orders.
+///
+/// * **start_after** is an [`Option`] field of type [`OrderCursor`]. Sets the position to start reading after.
+///
+/// * **limit** is an [`Option`] type. Sets the number of items to be read.
+pub fn query_orders(
+    deps: Deps,
+    pair_addr: String,
+    offer_asset_info: AssetInfo,
+    start_after: Option<OrderCursor>,
+    limit: Option<u32>,
+) -> StdResult<Vec<OrderResponse>> {
+    let pair_addr = addr_validate_to_lower(deps.api, &pair_addr)?;
+
+    read_orders(
+        deps.storage,
+        &pair_addr,
+        &offer_asset_info,
+        start_after,
+        limit,
+    )?
+    .into_iter()
+    .map(|order_id| query_order(deps, order_id))
+    .collect()
+}
+
+/// ## Description
+/// Returns the orders of an owner in a vector that contains objects of type [`OrderResponse`].
+/// ## Params
+/// * **deps** is the object of type [`Deps`].
+///
+/// * **owner** is the owner of the orders.
+///
+/// * **start_after** is an [`Option`] type. Sets the order identifier to start reading after.
+///
+/// * **limit** is an [`Option`] type. Sets the number of items to be read.
+pub fn query_owner_orders(
+    deps: Deps,
+    owner: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<OrderResponse>> {
+    let owner = addr_validate_to_lower(deps.api, &owner)?;
+
+    read_owner_orders(deps.storage, &owner, start_after, limit)?
+        .into_iter()
+        .map(|order_id| query_order(deps, order_id))
+        .collect()
+}
+
+/// ## Description
+/// Builds an [`OrderResponse`] from a stored order.
+fn order_response(order_id: u64, order: Order) -> OrderResponse {
+    OrderResponse {
+        order_id,
+        owner: order.owner,
+        pair_addr: order.pair_addr,
+        offer_asset: order.offer_asset,
+        ask_asset_info: order.ask_asset_info,
+        price: order.price,
+        filled_amount: order.filled_amount,
+    }
+}
+
+/// ## Description
+/// Used for migration of contract. Returns the default object of type [`Response`].
+/// ## Params
+/// * **_deps** is the object of type [`DepsMut`].
+///
+/// * **_env** is the object of type [`Env`].
+///
+/// * **_msg** is the object of type [`MigrateMsg`].
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    Ok(Response::default())
+}
@@ -0,0 +1,37 @@
+use cosmwasm_std::{OverflowError, StdError};
+use thiserror::Error;
+
+/// ## Description
+/// This enum describes limit order contract errors!
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Invalid bounty bps")]
+    InvalidBountyBps {},
+
+    #[error("Event of zero transfer")]
+    InvalidZeroAmount {},
+
+    #[error("The order price must be greater than zero")]
+    InvalidZeroPrice {},
+
+    #[error("The pair doesn't contain the order assets")]
+    InvalidAssets {},
+
+    #[error("The fill amount exceeds the unfilled order amount")]
+    FillAmountTooBig {},
+
+    #[error("The pair price hasn't reached the order price")]
+    PriceNotReached {},
+}
+
+impl From<OverflowError> for ContractError {
+    fn from(o: OverflowError) -> Self {
+        StdError::from(o).into()
+    }
+}
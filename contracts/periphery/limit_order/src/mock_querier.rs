@@ -0,0 +1,162 @@
+// The tax queriers are only used with the `terra` feature
+#![cfg_attr(not(feature = "terra"), allow(dead_code))]
+
+use astroport::asset::{AssetInfo, PairInfo};
+use astroport::factory::PairType;
+use astroport::pair::{QueryMsg as PairQueryMsg, SimulationResponse};
+use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    from_binary, from_slice, to_binary, Addr, Coin, ContractResult, Decimal, Fraction, OwnedDeps,
+    Querier, QuerierResult, QueryRequest, SystemError, SystemResult, Uint128, WasmQuery,
+};
+use std::collections::HashMap;
+#[cfg(feature = "terra")]
+use terra_cosmwasm::{TaxCapResponse, TaxRateResponse, TerraQuery, TerraQueryWrapper, TerraRoute};
+
+#[cfg(feature = "terra")]
+type CustomQuery = TerraQueryWrapper;
+#[cfg(not(feature = "terra"))]
+type CustomQuery = cosmwasm_std::Empty;
+
+pub fn mock_dependencies(
+    contract_balance: &[Coin],
+) -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+    let custom_querier: WasmMockQuerier =
+        WasmMockQuerier::new(MockQuerier::new(&[(MOCK_CONTRACT_ADDR, contract_balance)]));
+
+    OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier: custom_querier,
+    }
+}
+
+pub struct WasmMockQuerier {
+    base: MockQuerier<CustomQuery>,
+    pair_querier: PairQuerier,
+    tax_querier: TaxQuerier,
+}
+
+#[derive(Clone, Default)]
+pub struct PairQuerier {
+    // The assets of every pair and the amount of the second asset returned per unit of the first one
+    pairs: HashMap<String, ([AssetInfo; 2], Decimal)>,
+}
+
+#[derive(Clone, Default)]
+pub struct TaxQuerier {
+    rate: Decimal,
+    caps: HashMap<String, Uint128>,
+}
+
+impl Querier for WasmMockQuerier {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        // MockQuerier doesn't support Custom, so we ignore it completely here
+        let request: QueryRequest<CustomQuery> = match from_slice(bin_request) {
+            Ok(v) => v,
+            Err(e) => {
+                return SystemResult::Err(SystemError::InvalidRequest {
+                    error: format!("Parsing query request: {}", e),
+                    request: bin_request.into(),
+                })
+            }
+        };
+        self.handle_query(&request)
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn handle_query(&self, request: &QueryRequest<CustomQuery>) -> QuerierResult {
+        match &request {
+            #[cfg(feature = "terra")]
+            QueryRequest::Custom(TerraQueryWrapper { route, query_data }) => {
+                if route == &TerraRoute::Treasury {
+                    match query_data {
+                        TerraQuery::TaxRate {} => {
+                            let res = TaxRateResponse {
+                                rate: self.tax_querier.rate,
+                            };
+                            SystemResult::Ok(ContractResult::from(to_binary(&res)))
+                        }
+                        TerraQuery::TaxCap { denom } => {
+                            let cap = self
+                                .tax_querier
+                                .caps
+                                .get(denom)
+                                .copied()
+                                .unwrap_or_default();
+                            let res = TaxCapResponse { cap };
+                            SystemResult::Ok(ContractResult::from(to_binary(&res)))
+                        }
+                        _ => panic!("DO NOT ENTER HERE"),
+                    }
+                } else {
+                    panic!("DO NOT ENTER HERE")
+                }
+            }
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg }) => {
+                let (asset_infos, price) = match self.pair_querier.pairs.get(contract_addr) {
+                    Some(v) => v,
+                    None => {
+                        return SystemResult::Err(SystemError::Unknown {});
+                    }
+                };
+
+                match from_binary(msg).unwrap() {
+                    PairQueryMsg::Pair {} => {
+                        SystemResult::Ok(ContractResult::from(to_binary(&PairInfo {
+                            asset_infos: asset_infos.to_vec(),
+                            contract_addr: Addr::unchecked(contract_addr),
+                            liquidity_token: Addr::unchecked("liquidity0000"),
+                            pair_type: PairType::Xyk {},
+                        })))
+                    }
+                    PairQueryMsg::Simulation { offer_asset, .. } => {
+                        let return_amount = if offer_asset.info.equal(&asset_infos[0]) {
+                            offer_asset.amount * *price
+                        } else {
+                            offer_asset
+                                .amount
+                                .multiply_ratio(price.denominator(), price.numerator())
+                        };
+                        SystemResult::Ok(ContractResult::from(to_binary(&SimulationResponse {
+                            return_amount,
+                            spread_amount: Uint128::zero(),
+                            commission_amount: Uint128::zero(),
+                            fee_rate: Decimal::zero(),
+                        })))
+                    }
+                    _ => panic!("DO NOT ENTER HERE"),
+                }
+            }
+            _ => self.base.handle_query(request),
+        }
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn new(base: MockQuerier<CustomQuery>) -> Self {
+        WasmMockQuerier {
+            base,
+            pair_querier: PairQuerier::default(),
+            tax_querier: TaxQuerier::default(),
+        }
+    }
+
+    // Configures a pair that returns `price` units of the second asset per unit of the first one
+    pub fn with_pair(&mut self, pair: &str, asset_infos: [AssetInfo; 2], price: Decimal) {
+        self.pair_querier
+            .pairs
+            .insert(pair.to_string(), (asset_infos, price));
+    }
+
+    pub fn with_tax(&mut self, rate: Decimal, caps: &[(&String, &Uint128)]) {
+        self.tax_querier = TaxQuerier {
+            rate,
+            caps: caps
+                .iter()
+                .map(|(denom, cap)| (denom.to_string(), **cap))
+                .collect(),
+        };
+    }
+}
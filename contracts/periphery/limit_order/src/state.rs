@@ -0,0 +1,194 @@
+use astroport::asset::{Asset, AssetInfo};
+use astroport::common::OwnershipProposal;
+use astroport::limit_order::OrderCursor;
+use cosmwasm_std::{Addr, Decimal, Empty, Fraction, Order as OrderBy, StdResult, Storage, Uint128};
+use cw_storage_plus::{Bound, Item, Map, PrimaryKey, U128Key, U64Key};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::convert::TryInto;
+
+/// ## Description
+/// This structure describes the main control config of the limit order contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// The contract owner address
+    pub owner: Addr,
+    /// The share of the filled offer amount paid to the executor of an order, in bps
+    pub bounty_bps: u16,
+}
+
+/// ## Description
+/// This structure describes a limit order.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Order {
+    /// The order owner address
+    pub owner: Addr,
+    /// The pair the order is executed against
+    pub pair_addr: Addr,
+    /// The unfilled part of the offer asset
+    pub offer_asset: Asset,
+    /// The asset to buy
+    pub ask_asset_info: AssetInfo,
+    /// The minimum amount of the ask asset received per unit of the offer asset
+    pub price: Decimal,
+    /// The offer amount filled so far
+    pub filled_amount: Uint128,
+}
+
+/// ## Description
+/// Stores the contract config
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// ## Description
+/// Stores the latest proposal to change contract ownership
+pub const OWNERSHIP_PROPOSAL: Item<OwnershipProposal> = Item::new("ownership_proposal");
+
+/// ## Description
+/// Stores orders by their identifiers
+pub const ORDERS: Map<U64Key, Order> = Map::new("orders");
+
+/// The part of an [`ORDERS_BY_PRICE`] key that sorts orders by price and then by identifier
+pub type PriceKey = (U128Key, U64Key);
+
+/// ## Description
+/// Stores the identifiers of the orders selling an asset in a pair, keyed by price. Use
+/// [`price_key`] to build the last part of the key
+pub const ORDERS_BY_PRICE: Map<(&Addr, &[u8], PriceKey), Empty> = Map::new("orders_by_price");
+
+/// ## Description
+/// Stores the identifiers of the orders of every owner
+pub const OWNER_ORDERS: Map<(&Addr, U64Key), Empty> = Map::new("owner_orders");
+
+/// ## Description
+/// Stores the identifier of the last created order
+pub const LAST_ORDER_ID: Item<u64> = Item::new("last_order_id");
+
+/// ## Description
+/// Returns the part of an [`ORDERS_BY_PRICE`] key that sorts orders by price and then by identifier.
+pub fn price_key(price: Decimal, order_id: u64) -> PriceKey {
+    (U128Key::new(price.numerator()), U64Key::new(order_id))
+}
+
+/// ## Description
+/// Returns the next order identifier and stores it as the last one.
+pub fn next_order_id(storage: &mut dyn Storage) -> StdResult<u64> {
+    let id = LAST_ORDER_ID.may_load(storage)?.unwrap_or_default() + 1;
+    LAST_ORDER_ID.save(storage, &id)?;
+    Ok(id)
+}
+
+/// ## Description
+/// Saves a new order along with its indexes.
+/// ## Params
+/// * **storage** is the object of type [`Storage`].
+///
+/// * **order_id** is the identifier of the order.
+///
+/// * **order** is the object of type [`Order`].
+pub fn create_order(storage: &mut dyn Storage, order_id: u64, order: &Order) -> StdResult<()> {
+    ORDERS.save(storage, U64Key::new(order_id), order)?;
+    ORDERS_BY_PRICE.save(
+        storage,
+        (
+            &order.pair_addr,
+            order.offer_asset.info.as_bytes(),
+            price_key(order.price, order_id),
+        ),
+        &Empty {},
+    )?;
+    OWNER_ORDERS.save(storage, (&order.owner, U64Key::new(order_id)), &Empty {})
+}
+
+/// ## Description
+/// Removes an order along with its indexes.
+/// ## Params
+/// * **storage** is the object of type [`Storage`].
+///
+/// * **order_id** is the identifier of the order.
+///
+/// * **order** is the object of type [`Order`].
+pub fn remove_order(storage: &mut dyn Storage, order_id: u64, order: &Order) {
+    ORDERS.remove(storage, U64Key::new(order_id));
+    ORDERS_BY_PRICE.remove(
+        storage,
+        (
+            &order.pair_addr,
+            order.offer_asset.info.as_bytes(),
+            price_key(order.price, order_id),
+        ),
+    );
+    OWNER_ORDERS.remove(storage, (&order.owner, U64Key::new(order_id)));
+}
+
+//settings for pagination
+/// The maximum limit for reading orders
+const MAX_LIMIT: u32 = 30;
+
+/// The default limit for reading orders
+const DEFAULT_LIMIT: u32 = 10;
+
+/// ## Description
+/// Reads the identifiers of the orders selling an asset in a pair, starting with the lowest price,
+/// according to the specified parameters in `start_after` and `limit` variables.
+/// ## Params
+/// * **storage** is the object of type [`Storage`].
+///
+/// * **pair_addr** is the object of type [`Addr`]. The pair the orders are executed against.
+///
+/// * **offer_asset_info** is the object of type [`AssetInfo`]. The asset sold by the orders.
+///
+/// * **start_after** is an [`Option`] field of type [`OrderCursor`]. Sets the position to start reading after.
+///
+/// * **limit** is an [`Option`] type. Sets the number of items to be read.
+pub fn read_orders(
+    storage: &dyn Storage,
+    pair_addr: &Addr,
+    offer_asset_info: &AssetInfo,
+    start_after: Option<OrderCursor>,
+    limit: Option<u32>,
+) -> StdResult<Vec<u64>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after
+        .map(|cursor| Bound::exclusive(price_key(cursor.price, cursor.order_id).joined_key()));
+
+    ORDERS_BY_PRICE
+        .prefix((pair_addr, offer_asset_info.as_bytes()))
+        .range(storage, start, None, OrderBy::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (key, _) = item?;
+            // The order identifier is stored in the last 8 bytes of the key
+            Ok(u64::from_be_bytes(key[key.len() - 8..].try_into().unwrap()))
+        })
+        .collect()
+}
+
+/// ## Description
+/// Reads the order identifiers of an owner according to the specified parameters in `start_after` and `limit` variables.
+/// ## Params
+/// * **storage** is the object of type [`Storage`].
+///
+/// * **owner** is the object of type [`Addr`]. The owner of the orders.
+///
+/// * **start_after** is an [`Option`] type. Sets the order identifier to start reading after.
+///
+/// * **limit** is an [`Option`] type. Sets the number of items to be read.
+pub fn read_owner_orders(
+    storage: &dyn Storage,
+    owner: &Addr,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<u64>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive_int);
+
+    OWNER_ORDERS
+        .prefix(owner)
+        .range(storage, start, None, OrderBy::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (key, _) = item?;
+            Ok(u64::from_be_bytes(key.as_slice().try_into().unwrap()))
+        })
+        .collect()
+}
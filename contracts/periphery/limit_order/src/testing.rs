@@ -0,0 +1,502 @@
+use crate::contract::{
+    execute, instantiate, query_config, query_order, query_orders, query_owner_orders,
+};
+use crate::error::ContractError;
+use crate::mock_querier::mock_dependencies;
+use astroport::asset::{Asset, AssetInfo};
+use astroport::limit_order::{Cw20HookMsg, ExecuteMsg, InstantiateMsg, OrderCursor};
+use astroport::pair::{Cw20HookMsg as PairCw20HookMsg, ExecuteMsg as PairExecuteMsg};
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{
+    attr, to_binary, Addr, BankMsg, Coin, CosmosMsg, Decimal, StdError, SubMsg, Uint128, WasmMsg,
+};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use std::str::FromStr;
+
+fn asset_infos() -> [AssetInfo; 2] {
+    [
+        AssetInfo::NativeToken {
+            denom: "uusd".to_string(),
+        },
+        AssetInfo::Token {
+            contract_addr: Addr::unchecked("asset0000"),
+        },
+    ]
+}
+
+#[test]
+fn proper_initialization() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        owner: "owner".to_string(),
+        bounty_bps: 1001,
+    };
+    let err = instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidBountyBps {});
+
+    let msg = InstantiateMsg {
+        owner: "owner".to_string(),
+        bounty_bps: 10,
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+
+    let config = query_config(deps.as_ref()).unwrap();
+    assert_eq!(config.owner, Addr::unchecked("owner"));
+    assert_eq!(config.bounty_bps, 10);
+
+    let msg = ExecuteMsg::UpdateConfig {
+        bounty_bps: Some(20),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+    assert_eq!(query_config(deps.as_ref()).unwrap().bounty_bps, 20);
+
+    let msg = ExecuteMsg::UpdateConfig {
+        bounty_bps: Some(1001),
+    };
+    let err = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidBountyBps {});
+}
+
+#[test]
+fn submit_and_execute_order() {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier
+        .with_pair("pair0000", asset_infos(), Decimal::from_ratio(2u128, 1u128));
+    deps.querier.with_tax(
+        Decimal::percent(1),
+        &[(&"uusd".to_string(), &Uint128::new(1_000_000_000))],
+    );
+
+    let msg = InstantiateMsg {
+        owner: "owner".to_string(),
+        bounty_bps: 10,
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+
+    let offer_asset = Asset {
+        info: asset_infos()[0].clone(),
+        amount: Uint128::new(1000_000000),
+    };
+    let msg = ExecuteMsg::SubmitOrder {
+        pair_addr: "pair0000".to_string(),
+        offer_asset: offer_asset.clone(),
+        ask_asset_info: asset_infos()[1].clone(),
+        price: Decimal::from_ratio(3u128, 1u128),
+    };
+
+    // The offer asset must be sent
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Std(StdError::generic_err(
+            "Native token balance mismatch between the argument and the transferred"
+        ))
+    );
+
+    // The pair must contain both assets
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(
+            "addr0000",
+            &[Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::new(1000_000000),
+            }],
+        ),
+        ExecuteMsg::SubmitOrder {
+            pair_addr: "pair0000".to_string(),
+            offer_asset: offer_asset.clone(),
+            ask_asset_info: AssetInfo::NativeToken {
+                denom: "ukrw".to_string(),
+            },
+            price: Decimal::from_ratio(3u128, 1u128),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidAssets {});
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(
+            "addr0000",
+            &[Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::new(1000_000000),
+            }],
+        ),
+        msg,
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "submit_order"),
+            attr("order_id", "1"),
+            attr("owner", "addr0000"),
+            attr("pair", "pair0000"),
+            attr("offer_asset", "1000000000uusd"),
+            attr("ask_asset", "asset0000"),
+            attr("price", "3"),
+        ]
+    );
+
+    // The pair price is below the order price
+    let msg = ExecuteMsg::ExecuteOrder {
+        order_id: 1,
+        amount: Some(Uint128::new(400_000000)),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("executor", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::PriceNotReached {});
+
+    // The pair price must cover the bounty
+    deps.querier.with_pair(
+        "pair0000",
+        asset_infos(),
+        Decimal::from_str("3.002").unwrap(),
+    );
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("executor", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::PriceNotReached {});
+
+    // And the tax too
+    if cfg!(feature = "terra") {
+        deps.querier.with_pair(
+            "pair0000",
+            asset_infos(),
+            Decimal::from_str("3.01").unwrap(),
+        );
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("executor", &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::PriceNotReached {});
+    }
+
+    // Partial fill
+    deps.querier
+        .with_pair("pair0000", asset_infos(), Decimal::from_str("3.1").unwrap());
+    let res = execute(deps.as_mut(), mock_env(), mock_info("executor", &[]), msg).unwrap();
+
+    // 0.1% bounty and 1% tax on the swapped amount
+    let bounty_amount = Uint128::new(400000);
+    let (swap_amount, bounty_transfer, return_amount) = if cfg!(feature = "terra") {
+        (
+            Uint128::new(395643564),
+            Uint128::new(396039),
+            Uint128::new(1226495048),
+        )
+    } else {
+        (
+            Uint128::new(399600000),
+            bounty_amount,
+            Uint128::new(1238760000),
+        )
+    };
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: "pair0000".to_string(),
+                funds: vec![Coin {
+                    denom: "uusd".to_string(),
+                    amount: swap_amount,
+                }],
+                msg: to_binary(&PairExecuteMsg::Swap {
+                    offer_asset: Asset {
+                        info: asset_infos()[0].clone(),
+                        amount: swap_amount,
+                    },
+                    ask_asset_info: Some(asset_infos()[1].clone()),
+                    belief_price: None,
+                    max_spread: Some(Decimal::percent(50)),
+                    to: Some("addr0000".to_string()),
+                    minimum_receive: Some(Uint128::new(1200_000000)),
+                    deadline: None,
+                })
+                .unwrap(),
+            }),
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "executor".to_string(),
+                amount: vec![Coin {
+                    denom: "uusd".to_string(),
+                    amount: bounty_transfer,
+                }],
+            })),
+        ]
+    );
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "execute_order"),
+            attr("order_id", "1"),
+            attr("executor", "executor"),
+            attr("offer_amount", "400000000"),
+            attr("return_amount", return_amount.to_string()),
+            attr("bounty_amount", bounty_amount.to_string()),
+        ]
+    );
+
+    let order = query_order(deps.as_ref(), 1).unwrap();
+    assert_eq!(order.offer_asset.amount, Uint128::new(600_000000));
+    assert_eq!(order.filled_amount, Uint128::new(400_000000));
+
+    // The fill amount can't exceed the unfilled amount
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("executor", &[]),
+        ExecuteMsg::ExecuteOrder {
+            order_id: 1,
+            amount: Some(Uint128::new(600_000001)),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::FillAmountTooBig {});
+
+    // Fill the rest of the order
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("executor", &[]),
+        ExecuteMsg::ExecuteOrder {
+            order_id: 1,
+            amount: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(res.attributes[3], attr("offer_amount", "600000000"));
+
+    query_order(deps.as_ref(), 1).unwrap_err();
+    assert_eq!(
+        query_owner_orders(deps.as_ref(), "addr0000".to_string(), None, None).unwrap(),
+        vec![]
+    );
+}
+
+#[test]
+fn token_orders_and_queries() {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier
+        .with_pair("pair0000", asset_infos(), Decimal::from_ratio(2u128, 1u128));
+
+    let msg = InstantiateMsg {
+        owner: "owner".to_string(),
+        bounty_bps: 0,
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+
+    // Sell asset0000 for uusd at different prices
+    for (sender, price) in [
+        ("addr0000", "0.6"),
+        ("addr0001", "0.4"),
+        ("addr0000", "0.5"),
+    ] {
+        let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: sender.to_string(),
+            amount: Uint128::new(100_000000),
+            msg: to_binary(&Cw20HookMsg::SubmitOrder {
+                pair_addr: "pair0000".to_string(),
+                ask_asset_info: asset_infos()[0].clone(),
+                price: Decimal::from_str(price).unwrap(),
+            })
+            .unwrap(),
+        });
+        execute(deps.as_mut(), mock_env(), mock_info("asset0000", &[]), msg).unwrap();
+    }
+
+    // The orders are sorted by price
+    let orders = query_orders(
+        deps.as_ref(),
+        "pair0000".to_string(),
+        asset_infos()[1].clone(),
+        None,
+        None,
+    )
+    .unwrap();
+    assert_eq!(
+        orders
+            .iter()
+            .map(|order| order.order_id)
+            .collect::<Vec<_>>(),
+        vec![2, 3, 1]
+    );
+
+    let orders = query_orders(
+        deps.as_ref(),
+        "pair0000".to_string(),
+        asset_infos()[1].clone(),
+        Some(OrderCursor {
+            price: Decimal::from_str("0.4").unwrap(),
+            order_id: 2,
+        }),
+        Some(1),
+    )
+    .unwrap();
+    assert_eq!(
+        orders
+            .iter()
+            .map(|order| order.order_id)
+            .collect::<Vec<_>>(),
+        vec![3]
+    );
+
+    // No orders sell uusd
+    assert_eq!(
+        query_orders(
+            deps.as_ref(),
+            "pair0000".to_string(),
+            asset_infos()[0].clone(),
+            None,
+            None,
+        )
+        .unwrap(),
+        vec![]
+    );
+
+    let orders = query_owner_orders(deps.as_ref(), "addr0000".to_string(), None, None).unwrap();
+    assert_eq!(
+        orders
+            .iter()
+            .map(|order| order.order_id)
+            .collect::<Vec<_>>(),
+        vec![1, 3]
+    );
+
+    // The pair returns 0.5 uusd per asset0000
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("executor", &[]),
+        ExecuteMsg::ExecuteOrder {
+            order_id: 3,
+            amount: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(WasmMsg::Execute {
+            contract_addr: "asset0000".to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: "pair0000".to_string(),
+                amount: Uint128::new(100_000000),
+                msg: to_binary(&PairCw20HookMsg::Swap {
+                    ask_asset_info: Some(asset_infos()[0].clone()),
+                    belief_price: None,
+                    max_spread: Some(Decimal::percent(50)),
+                    to: Some("addr0000".to_string()),
+                    minimum_receive: Some(Uint128::new(50_000000)),
+                    deadline: None,
+                })
+                .unwrap(),
+            })
+            .unwrap(),
+        })]
+    );
+
+    // The filled order can still be used as the cursor
+    let orders = query_orders(
+        deps.as_ref(),
+        "pair0000".to_string(),
+        asset_infos()[1].clone(),
+        Some(OrderCursor {
+            price: Decimal::from_str("0.5").unwrap(),
+            order_id: 3,
+        }),
+        None,
+    )
+    .unwrap();
+    assert_eq!(
+        orders
+            .iter()
+            .map(|order| order.order_id)
+            .collect::<Vec<_>>(),
+        vec![1]
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("executor", &[]),
+        ExecuteMsg::ExecuteOrder {
+            order_id: 1,
+            amount: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::PriceNotReached {});
+
+    // Only the owner can cancel an order
+    let msg = ExecuteMsg::CancelOrder { order_id: 1 };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0001", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let res = execute(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(WasmMsg::Execute {
+            contract_addr: "asset0000".to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "addr0000".to_string(),
+                amount: Uint128::new(100_000000),
+            })
+            .unwrap(),
+        })]
+    );
+
+    let orders = query_orders(
+        deps.as_ref(),
+        "pair0000".to_string(),
+        asset_infos()[1].clone(),
+        None,
+        None,
+    )
+    .unwrap();
+    assert_eq!(
+        orders
+            .iter()
+            .map(|order| order.order_id)
+            .collect::<Vec<_>>(),
+        vec![2]
+    );
+}
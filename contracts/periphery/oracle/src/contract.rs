@@ -2,7 +2,7 @@ use crate::error::ContractError;
 use crate::querier::{query_cumulative_prices, query_pair_info, query_prices};
 use crate::state::{Config, PriceCumulativeLast, CONFIG, PRICE_LAST};
 use astroport::asset::{addr_validate_to_lower, Asset, AssetInfo};
-use astroport::oracle::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+use astroport::oracle::{ConfigResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 use astroport::pair::TWAP_PRECISION;
 use astroport::querier::query_token_precision;
 use cosmwasm_bignumber::{Decimal256, Uint256};
@@ -47,6 +47,7 @@ pub fn instantiate(
         &deps.querier,
         factory_contract.clone(),
         msg.asset_infos.clone(),
+        msg.pair_type,
     )?;
 
     let config = Config {
@@ -156,13 +157,30 @@ pub fn update(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
 /// ## Queries
 /// * **QueryMsg::Consult { token, amount }** Validates assets and calculates a new average
 /// amount with updated precision
+///
+/// * **QueryMsg::Config {}** Returns the oracle configuration in a [`ConfigResponse`] object.
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Consult { token, amount } => to_binary(&consult(deps, token, amount)?),
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
     }
 }
 
+/// ## Description
+/// Returns the oracle configuration in a [`ConfigResponse`] object.
+/// ## Params
+/// * **deps** is the object of type [`Deps`].
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        owner: config.owner,
+        factory: config.factory,
+        asset_infos: config.asset_infos,
+        pair: config.pair,
+    })
+}
+
 /// ## Description
 /// Validates assets and calculates a new average amount with updated precision.
 /// Returns the average amount of type [`Uint256`] if the operation was successful,
@@ -187,7 +205,7 @@ fn consult(deps: Deps, token: AssetInfo, amount: Uint128) -> Result<Uint256, Std
 
     Ok(if price_average.is_zero() {
         // get precision
-        let p = query_token_precision(&deps.querier, token.clone())?;
+        let p = query_token_precision(&deps.querier, token.clone(), &config.factory)?;
         let one = Uint128::new(10_u128.pow(p.into()));
 
         let price = query_prices(
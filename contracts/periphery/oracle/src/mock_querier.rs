@@ -9,8 +9,14 @@ use cosmwasm_std::{
     QueryRequest, SystemError, SystemResult, Uint128, WasmQuery,
 };
 use std::collections::HashMap;
+#[cfg(feature = "terra")]
 use terra_cosmwasm::TerraQueryWrapper;
 
+#[cfg(feature = "terra")]
+type CustomQuery = TerraQueryWrapper;
+#[cfg(not(feature = "terra"))]
+type CustomQuery = cosmwasm_std::Empty;
+
 pub fn mock_dependencies(
     contract_balance: &[Coin],
 ) -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
@@ -25,7 +31,7 @@ pub fn mock_dependencies(
 }
 
 pub struct WasmMockQuerier {
-    base: MockQuerier<TerraQueryWrapper>,
+    base: MockQuerier<CustomQuery>,
     token_querier: TokenQuerier,
 }
 
@@ -48,7 +54,11 @@ impl TokenQuerier {
         self.pairs.insert(
             pair.to_string(),
             CumulativePricesResponse {
-                assets,
+                cumulative_prices: vec![
+                    (assets[0].info.clone(), assets[1].info.clone(), price0),
+                    (assets[1].info.clone(), assets[0].info.clone(), price1),
+                ],
+                assets: assets.to_vec(),
                 total_share: total,
                 price0_cumulative_last: price0,
                 price1_cumulative_last: price1,
@@ -60,7 +70,7 @@ impl TokenQuerier {
 impl Querier for WasmMockQuerier {
     fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
         // MockQuerier doesn't support Custom, so we ignore it completely here
-        let request: QueryRequest<TerraQueryWrapper> = match from_slice(bin_request) {
+        let request: QueryRequest<CustomQuery> = match from_slice(bin_request) {
             Ok(v) => v,
             Err(e) => {
                 return SystemResult::Err(SystemError::InvalidRequest {
@@ -74,12 +84,12 @@ impl Querier for WasmMockQuerier {
 }
 
 impl WasmMockQuerier {
-    pub fn handle_query(&self, request: &QueryRequest<TerraQueryWrapper>) -> QuerierResult {
+    pub fn handle_query(&self, request: &QueryRequest<CustomQuery>) -> QuerierResult {
         match &request {
             QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg }) => {
                 if contract_addr == "factory" {
                     match from_binary(&msg).unwrap() {
-                        Pair { asset_infos } => SystemResult::Ok(
+                        Pair { asset_infos, .. } => SystemResult::Ok(
                             to_binary(&PairInfo {
                                 asset_infos,
                                 contract_addr: Addr::unchecked("pair"),
@@ -110,7 +120,7 @@ impl WasmMockQuerier {
     }
 }
 impl WasmMockQuerier {
-    pub fn new(base: MockQuerier<TerraQueryWrapper>) -> Self {
+    pub fn new(base: MockQuerier<CustomQuery>) -> Self {
         WasmMockQuerier {
             base,
             token_querier: TokenQuerier::default(),
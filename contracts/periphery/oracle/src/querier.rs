@@ -1,5 +1,5 @@
 use astroport::asset::{Asset, AssetInfo, PairInfo};
-use astroport::factory::QueryMsg as FactoryQueryMsg;
+use astroport::factory::{PairType, QueryMsg as FactoryQueryMsg};
 use astroport::pair::{CumulativePricesResponse, QueryMsg as PairQueryMsg, SimulationResponse};
 use cosmwasm_std::{to_binary, Addr, QuerierWrapper, QueryRequest, StdResult, WasmQuery};
 
@@ -11,14 +11,20 @@ use cosmwasm_std::{to_binary, Addr, QuerierWrapper, QueryRequest, StdResult, Was
 /// * **factory_contract** is the object of type [`Addr`].
 ///
 /// * **asset_infos** is array with two items the type of [`AssetInfo`].
+///
+/// * **pair_type** is the object of type [`PairType`].
 pub fn query_pair_info(
     querier: &QuerierWrapper,
     factory_contract: Addr,
     asset_infos: [AssetInfo; 2],
+    pair_type: PairType,
 ) -> StdResult<PairInfo> {
     querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
         contract_addr: factory_contract.to_string(),
-        msg: to_binary(&FactoryQueryMsg::Pair { asset_infos })?,
+        msg: to_binary(&FactoryQueryMsg::Pair {
+            asset_infos: asset_infos.to_vec(),
+            pair_type: Some(pair_type),
+        })?,
     }))
 }
 
@@ -53,6 +59,9 @@ pub fn query_prices(
 ) -> StdResult<SimulationResponse> {
     querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
         contract_addr: pair_contract.to_string(),
-        msg: to_binary(&PairQueryMsg::Simulation { offer_asset: asset })?,
+        msg: to_binary(&PairQueryMsg::Simulation {
+            offer_asset: asset,
+            ask_asset_info: None,
+        })?,
     }))
 }
@@ -1,6 +1,7 @@
 use crate::contract::{execute, instantiate};
 use crate::mock_querier::mock_dependencies;
 use astroport::asset::{Asset, AssetInfo};
+use astroport::factory::PairType;
 use astroport::oracle::{ExecuteMsg, InstantiateMsg};
 use cosmwasm_bignumber::{Decimal256, Uint256};
 use cosmwasm_std::testing::{mock_env, mock_info};
@@ -54,6 +55,7 @@ fn oracle_overflow() {
     let instantiate_msg = InstantiateMsg {
         factory_contract: factory.to_string(),
         asset_infos: [astro_asset_info, usdc_asset_info],
+        pair_type: PairType::Xyk {},
     };
 
     //set cumulative price 192738282u128
@@ -11,7 +11,7 @@ use astroport::token::InstantiateMsg as TokenInstantiateMsg;
 use astroport::factory::{PairConfig, PairType};
 
 use astroport::oracle::QueryMsg::Consult;
-use astroport::oracle::{ExecuteMsg, InstantiateMsg};
+use astroport::oracle::{ConfigResponse, ExecuteMsg, InstantiateMsg, QueryMsg};
 use astroport::pair::{StablePoolParams, XykPoolParamsForProxy};
 
 fn mock_app() -> TerraApp {
@@ -101,6 +101,8 @@ fn instantiate_contracts(router: &mut TerraApp, owner: Addr) -> (Addr, Addr, u64
                 total_fee_bps: 0,
                 maker_fee_bps: 0,
                 is_disabled: None,
+                permissionless: true,
+                creation_fee: None,
             },
             PairConfig {
                 code_id: pair_stable_code_id,
@@ -108,6 +110,8 @@ fn instantiate_contracts(router: &mut TerraApp, owner: Addr) -> (Addr, Addr, u64
                 total_fee_bps: 0,
                 maker_fee_bps: 0,
                 is_disabled: None,
+                permissionless: true,
+                creation_fee: None,
             },
         ],
         token_code_id: 1u64,
@@ -258,7 +262,7 @@ fn create_pair(
         }
     }
 
-    let asset_infos = [assets[0].info.clone(), assets[1].info.clone()];
+    let asset_infos = vec![assets[0].info.clone(), assets[1].info.clone()];
 
     // Create pair in factory
     let res = router
@@ -299,6 +303,7 @@ fn create_pair(
             contract_addr: factory_instance.clone().to_string(),
             msg: to_binary(&astroport::factory::QueryMsg::Pair {
                 asset_infos: asset_infos.clone(),
+                pair_type: Some(PairType::Xyk {}),
             })
             .unwrap(),
         }))
@@ -333,10 +338,12 @@ fn create_pair(
             user.clone(),
             pair_info.contract_addr.clone(),
             &astroport::pair::ExecuteMsg::ProvideLiquidity {
-                assets,
+                assets: assets.to_vec(),
                 slippage_tolerance: None,
                 auto_stake: None,
                 receiver: None,
+                min_lp_to_receive: None,
+                deadline: None,
             },
             &funds,
         )
@@ -368,7 +375,7 @@ fn create_pair_stable(
         }
     }
 
-    let asset_infos = [assets[0].info.clone(), assets[1].info.clone()];
+    let asset_infos = vec![assets[0].info.clone(), assets[1].info.clone()];
 
     // Create pair in factory
     let res = router
@@ -404,6 +411,7 @@ fn create_pair_stable(
             contract_addr: factory_instance.clone().to_string(),
             msg: to_binary(&astroport::factory::QueryMsg::Pair {
                 asset_infos: asset_infos.clone(),
+                pair_type: Some(PairType::Stable {}),
             })
             .unwrap(),
         }))
@@ -438,10 +446,12 @@ fn create_pair_stable(
             user.clone(),
             pair_info.contract_addr.clone(),
             &astroport::pair::ExecuteMsg::ProvideLiquidity {
-                assets,
+                assets: assets.to_vec(),
                 slippage_tolerance: None,
                 auto_stake: None,
                 receiver: None,
+                min_lp_to_receive: None,
+                deadline: None,
             },
             &funds,
         )
@@ -512,6 +522,8 @@ fn change_provide_liquidity(
                 slippage_tolerance: Some(Decimal::percent(50)),
                 auto_stake: None,
                 receiver: None,
+                min_lp_to_receive: None,
+                deadline: None,
             },
             &vec![],
         )
@@ -699,7 +711,8 @@ fn consult_pair_stable() {
         .query(&QueryRequest::Wasm(WasmQuery::Smart {
             contract_addr: factory_instance.clone().to_string(),
             msg: to_binary(&astroport::factory::QueryMsg::Pair {
-                asset_infos: asset_infos.clone(),
+                asset_infos: asset_infos.to_vec(),
+                pair_type: Some(PairType::Stable {}),
             })
             .unwrap(),
         }))
@@ -720,6 +733,7 @@ fn consult_pair_stable() {
     let msg = InstantiateMsg {
         factory_contract: factory_instance.to_string(),
         asset_infos: asset_infos.clone(),
+        pair_type: PairType::Stable {},
     };
     let oracle_instance = router
         .instantiate_contract(
@@ -732,6 +746,14 @@ fn consult_pair_stable() {
         )
         .unwrap();
 
+    let res: ConfigResponse = router
+        .wrap()
+        .query_wasm_smart(&oracle_instance, &QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(res.factory, factory_instance);
+    assert_eq!(res.asset_infos, asset_infos);
+    assert_eq!(res.pair, pair_info);
+
     let e = router
         .execute_contract(
             owner.clone(),
@@ -337,7 +337,7 @@ fn process_received_message(
 pub fn incr_allow_for_provide_liquidity(
     deps: DepsMut,
     env: Env,
-    assets: [Asset; 2],
+    assets: Vec<Asset>,
     slippage_tolerance: Option<Decimal>,
     auto_stake: Option<bool>,
     receiver: Option<String>,
@@ -386,6 +386,8 @@ pub fn incr_allow_for_provide_liquidity(
         slippage_tolerance: slippage_tolerance,
         auto_stake: auto_stake,
         receiver: receiver,
+        min_lp_to_receive: None,
+        deadline: None,
     };
 
     // let data_msg = format!("{:?}", pl_msg).into_bytes();
@@ -414,7 +416,7 @@ pub fn incr_allow_for_provide_liquidity(
 pub fn forward_provide_liquidity_to_astro(
     deps: DepsMut,
     env: Env,
-    assets: [Asset; 2],
+    assets: Vec<Asset>,
     slippage_tolerance: Option<Decimal>,
     auto_stake: Option<bool>,
     receiver: Option<String>,
@@ -445,6 +447,8 @@ pub fn forward_provide_liquidity_to_astro(
         slippage_tolerance,
         auto_stake,
         receiver,
+        min_lp_to_receive: None,
+        deadline: None,
     };
     let exec = WasmMsg::Execute {
         contract_addr: config.pool_pair_address.to_string(),
@@ -582,7 +586,7 @@ pub fn provide_native_liquidity(
     }
 
     let config = CONFIG.load(deps.storage)?;
-    let assets = [
+    let assets = vec![
         Asset {
             info: AssetInfo::NativeToken {
                 denom: "uusd".to_string(),
@@ -648,7 +652,7 @@ pub fn transfer_native_assets_to_native_investment_receive_wallet(
 pub fn transfer_custom_assets_from_funds_owner_to_proxy(
     deps: DepsMut,
     env: Env,
-    assets: [Asset; 2],
+    assets: Vec<Asset>,
     slippage_tolerance: Option<Decimal>,
     auto_stake: Option<bool>,
     receiver: Option<String>,
@@ -767,6 +771,8 @@ pub fn transfer_custom_assets_from_funds_owner_to_proxy(
         slippage_tolerance: slippage_tolerance,
         auto_stake: auto_stake,
         receiver: receiver,
+        min_lp_to_receive: None,
+        deadline: None,
     };
 
     // Save the submessage_payload
@@ -827,7 +833,7 @@ pub fn provide_liquidity(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    assets: [Asset; 2],
+    assets: Vec<Asset>,
     slippage_tolerance: Option<Decimal>,
     auto_stake: Option<bool>,
     receiver: Option<String>,
@@ -874,6 +880,8 @@ pub fn provide_liquidity(
         slippage_tolerance: slippage_tolerance,
         auto_stake: auto_stake,
         receiver: receiver,
+        min_lp_to_receive: None,
+        deadline: None,
     };
     // Save the submessage_payload
     SUB_MESSAGE_DETAILS.save(
@@ -1178,9 +1186,12 @@ pub fn swap(
             env,
             info,
             Cw20HookMsg::Swap {
+                ask_asset_info: None,
                 belief_price: belief_price,
                 max_spread: max_spread,
                 to: to_address,
+                minimum_receive: None,
+                deadline: None,
             },
             offer_asset.amount,
             funds_to_send,
@@ -1191,9 +1202,12 @@ pub fn swap(
     offer_asset.info.check(deps.api)?;
     let swap_msg = PairExecuteMsg::Swap {
         offer_asset: offer_asset,
+        ask_asset_info: None,
         belief_price: belief_price,
         max_spread: max_spread,
         to: to_address,
+        minimum_receive: None,
+        deadline: None,
     };
     let exec = WasmMsg::Execute {
         contract_addr: config.pool_pair_address.to_string(),
@@ -1269,6 +1283,7 @@ pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractEr
                                     slippage_tolerance,
                                     auto_stake,
                                     receiver,
+                                    ..
                                 } => {
                                     if smd.next_action
                                     == SubMessageNextAction::TransferCustomAssetsFromFundsOwner
@@ -1396,6 +1411,7 @@ fn query_simulation(deps: Deps, offer_asset: Asset) -> StdResult<SimulationRespo
         config.pool_pair_address,
         &Simulation {
             offer_asset: offer_asset,
+            ask_asset_info: None,
         },
     )
 }
@@ -1405,6 +1421,7 @@ fn query_reverse_simulation(deps: Deps, ask_asset: Asset) -> StdResult<ReverseSi
     deps.querier.query_wasm_smart(
         config.pool_pair_address,
         &ReverseSimulation {
+            offer_asset_info: None,
             ask_asset: ask_asset,
         },
     )
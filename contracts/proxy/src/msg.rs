@@ -66,7 +66,7 @@ pub enum ExecuteMsg {
     /// ProvidePairForReward a user provides pair liquidity (UST + Fury balanced) and gets Fury rewards
     ProvidePairForReward {
         /// the type of asset available in [`Asset`]
-        assets: [Asset; 2],
+        assets: Vec<Asset>,
         /// the slippage tolerance for sets the maximum percent of price movement
         slippage_tolerance: Option<Decimal>,
         /// Determines whether an autostake will be performed on the generator
@@ -84,7 +84,7 @@ pub enum ExecuteMsg {
     /// ProvideLiquidity an Authorized user provides pair liquidity and gets lp_tokens
     ProvideLiquidity {
         /// the type of asset available in [`Asset`]
-        assets: [Asset; 2],
+        assets: Vec<Asset>,
         /// the slippage tolerance for sets the maximum percent of price movement
         slippage_tolerance: Option<Decimal>,
         /// Determines whether an autostake will be performed on the generator
@@ -1,5 +1,7 @@
+#[cfg(feature = "terra")]
+use cosmwasm_std::Coin;
 use cosmwasm_std::{
-    entry_point, from_binary, to_binary, Addr, Api, Binary, Coin, CosmosMsg, Deps, DepsMut, Env,
+    entry_point, from_binary, to_binary, Addr, Api, Binary, CosmosMsg, Deps, DepsMut, Env,
     MessageInfo, QueryRequest, Response, StdError, StdResult, Uint128, WasmMsg, WasmQuery,
 };
 
@@ -8,6 +10,7 @@ use crate::operations::execute_swap_operation;
 use crate::state::{Config, CONFIG};
 
 use astroport::asset::{addr_validate_to_lower, Asset, AssetInfo, PairInfo};
+use astroport::pair::assert_deadline;
 use astroport::pair::{QueryMsg as PairQueryMsg, SimulationResponse};
 use astroport::querier::query_pair_info;
 use astroport::router::{
@@ -17,6 +20,7 @@ use astroport::router::{
 use cw2::set_contract_version;
 use cw20::Cw20ReceiveMsg;
 use std::collections::HashMap;
+#[cfg(feature = "terra")]
 use terra_cosmwasm::{SwapResponse, TerraMsgWrapper, TerraQuerier};
 
 /// Contract name that is used for migration.
@@ -24,6 +28,15 @@ const CONTRACT_NAME: &str = "astroport-router";
 /// Contract version that is used for migration.
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// The custom message type of the router responses. Native swaps are executed through the Terra
+/// market module and are only available with the `terra` feature.
+#[cfg(feature = "terra")]
+pub type CustomMsg = TerraMsgWrapper;
+/// The custom message type of the router responses. Native swaps are executed through the Terra
+/// market module and are only available with the `terra` feature.
+#[cfg(not(feature = "terra"))]
+pub type CustomMsg = cosmwasm_std::Empty;
+
 /// ## Description
 /// Creates a new contract with the specified parameters in the [`InstantiateMsg`].
 /// Returns the default object of type [`Response`] if the operation was successful,
@@ -41,7 +54,7 @@ pub fn instantiate(
     _env: Env,
     _info: MessageInfo,
     msg: InstantiateMsg,
-) -> Result<Response<TerraMsgWrapper>, ContractError> {
+) -> Result<Response<CustomMsg>, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
     CONFIG.save(
@@ -72,7 +85,8 @@ pub fn instantiate(
 /// * **ExecuteMsg::ExecuteSwapOperations {
 ///             operations,
 ///             minimum_receive,
-///             to
+///             to,
+///             deadline
 ///         }** Performs swap operations with the specified parameters.
 ///
 /// * **ExecuteMsg::ExecuteSwapOperation { operation, to }** Execute swap operation.
@@ -90,13 +104,14 @@ pub fn execute(
     env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
-) -> Result<Response<TerraMsgWrapper>, ContractError> {
+) -> Result<Response<CustomMsg>, ContractError> {
     match msg {
         ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
         ExecuteMsg::ExecuteSwapOperations {
             operations,
             minimum_receive,
             to,
+            deadline,
         } => execute_swap_operations(
             deps,
             env,
@@ -105,6 +120,7 @@ pub fn execute(
             operations,
             minimum_receive,
             to,
+            deadline,
         ),
         ExecuteMsg::ExecuteSwapOperation { operation, to } => {
             execute_swap_operation(deps, env, info, operation, to)
@@ -141,13 +157,14 @@ pub fn receive_cw20(
     env: Env,
     info: MessageInfo,
     cw20_msg: Cw20ReceiveMsg,
-) -> Result<Response<TerraMsgWrapper>, ContractError> {
+) -> Result<Response<CustomMsg>, ContractError> {
     let sender = addr_validate_to_lower(deps.api, &cw20_msg.sender)?;
     match from_binary(&cw20_msg.msg)? {
         Cw20HookMsg::ExecuteSwapOperations {
             operations,
             minimum_receive,
             to,
+            deadline,
         } => {
             let to_addr = if let Some(to_addr) = to {
                 Some(addr_validate_to_lower(deps.api, to_addr.as_str())?)
@@ -163,6 +180,7 @@ pub fn receive_cw20(
                 operations,
                 minimum_receive,
                 to_addr,
+                deadline,
             )
         }
     }
@@ -170,7 +188,7 @@ pub fn receive_cw20(
 
 /// ## Description
 /// Performs swap operations with the specified parameters.
-/// Returns an [`ContractError`] on failureб otherwise returns [`Response`] with the specified messages of type [`TerraMsgWrapper`] to execute if the operation is successful.
+/// Returns an [`ContractError`] on failureб otherwise returns [`Response`] with the specified messages of type [`CustomMsg`] to execute if the operation is successful.
 /// ## Params
 /// * **deps** is the object of type [`DepsMut`].
 ///
@@ -185,6 +203,9 @@ pub fn receive_cw20(
 /// * **minimum_receive** is the object of type [`Option<Uint128>`]. Used to minimum amount assertion.
 ///
 /// * **to** is the object of type [`Option<Addr>`]. Sets the recipient of the swap operation.
+///
+/// * **deadline** is an [`Option`] field of type [`u64`]. The block time after which the swaps fail.
+#[allow(clippy::too_many_arguments)]
 pub fn execute_swap_operations(
     deps: DepsMut,
     env: Env,
@@ -193,7 +214,10 @@ pub fn execute_swap_operations(
     operations: Vec<SwapOperation>,
     minimum_receive: Option<Uint128>,
     to: Option<Addr>,
-) -> Result<Response<TerraMsgWrapper>, ContractError> {
+    deadline: Option<u64>,
+) -> Result<Response<CustomMsg>, ContractError> {
+    assert_deadline(&env, deadline)?;
+
     let operations_len = operations.len();
     if operations_len == 0 {
         return Err(ContractError::MustProvideOperations {});
@@ -215,7 +239,7 @@ pub fn execute_swap_operations(
     let target_asset_info = operations.last().unwrap().get_target_asset_info();
 
     let mut operation_index = 0;
-    let mut messages: Vec<CosmosMsg<TerraMsgWrapper>> = operations
+    let mut messages: Vec<CosmosMsg<CustomMsg>> = operations
         .into_iter()
         .map(|op| {
             operation_index += 1;
@@ -232,7 +256,7 @@ pub fn execute_swap_operations(
                 })?,
             }))
         })
-        .collect::<StdResult<Vec<CosmosMsg<TerraMsgWrapper>>>>()?;
+        .collect::<StdResult<Vec<CosmosMsg<CustomMsg>>>>()?;
 
     // Execute minimum amount assertion
     if let Some(minimum_receive) = minimum_receive {
@@ -272,7 +296,7 @@ fn assert_minimum_receive(
     prev_balance: Uint128,
     minimum_receive: Uint128,
     receiver: Addr,
-) -> Result<Response<TerraMsgWrapper>, ContractError> {
+) -> Result<Response<CustomMsg>, ContractError> {
     asset_info.check(deps.api)?;
     let receiver_balance = asset_info.query_pool(&deps.querier, receiver)?;
     let swap_amount = receiver_balance.checked_sub(prev_balance)?;
@@ -361,6 +385,7 @@ fn simulate_swap_operations(
 ) -> Result<SimulateSwapOperationsResponse, ContractError> {
     let config: Config = CONFIG.load(deps.storage)?;
     let astroport_factory = config.astroport_factory;
+    #[cfg(feature = "terra")]
     let terra_querier = TerraQuerier::new(&deps.querier);
 
     let operations_len = operations.len();
@@ -374,12 +399,17 @@ fn simulate_swap_operations(
 
     assert_operations(deps.api, &operations)?;
 
+    #[cfg(feature = "terra")]
     let mut operation_index = 0;
     let mut offer_amount = offer_amount;
     for operation in operations.into_iter() {
-        operation_index += 1;
+        #[cfg(feature = "terra")]
+        {
+            operation_index += 1;
+        }
 
         match operation {
+            #[cfg(feature = "terra")]
             SwapOperation::NativeSwap {
                 offer_denom,
                 ask_denom,
@@ -407,14 +437,20 @@ fn simulate_swap_operations(
 
                 offer_amount = res.receive.amount;
             }
+            #[cfg(not(feature = "terra"))]
+            SwapOperation::NativeSwap { .. } => {
+                return Err(ContractError::NativeSwapNotSupported {});
+            }
             SwapOperation::AstroSwap {
                 offer_asset_info,
                 ask_asset_info,
+                pair_type,
             } => {
                 let pair_info: PairInfo = query_pair_info(
                     &deps.querier,
                     astroport_factory.clone(),
                     &[offer_asset_info.clone(), ask_asset_info.clone()],
+                    pair_type,
                 )?;
 
                 // Deduct tax before querying simulation
@@ -435,6 +471,7 @@ fn simulate_swap_operations(
                                 info: offer_asset_info.clone(),
                                 amount: offer_amount,
                             },
+                            ask_asset_info: Some(ask_asset_info.clone()),
                         })?,
                     }))?;
 
@@ -484,6 +521,7 @@ fn assert_operations(api: &dyn Api, operations: &[SwapOperation]) -> Result<(),
             SwapOperation::AstroSwap {
                 offer_asset_info,
                 ask_asset_info,
+                ..
             } => (offer_asset_info.clone(), ask_asset_info.clone()),
         };
         offer_asset.check(api)?;
@@ -502,6 +540,7 @@ fn assert_operations(api: &dyn Api, operations: &[SwapOperation]) -> Result<(),
 
 #[test]
 fn test_invalid_operations() {
+    use astroport::factory::PairType;
     use cosmwasm_std::testing::mock_dependencies;
     let deps = mock_dependencies(&[]);
     // empty error
@@ -524,6 +563,7 @@ fn test_invalid_operations() {
                     ask_asset_info: AssetInfo::Token {
                         contract_addr: Addr::unchecked("asset0001"),
                     },
+                    pair_type: PairType::Xyk {},
                 },
                 SwapOperation::AstroSwap {
                     offer_asset_info: AssetInfo::Token {
@@ -532,6 +572,7 @@ fn test_invalid_operations() {
                     ask_asset_info: AssetInfo::NativeToken {
                         denom: "uluna".to_string(),
                     },
+                    pair_type: PairType::Xyk {},
                 }
             ]
         )
@@ -555,6 +596,7 @@ fn test_invalid_operations() {
                     ask_asset_info: AssetInfo::Token {
                         contract_addr: Addr::unchecked("asset0001"),
                     },
+                    pair_type: PairType::Xyk {},
                 },
                 SwapOperation::AstroSwap {
                     offer_asset_info: AssetInfo::Token {
@@ -563,6 +605,7 @@ fn test_invalid_operations() {
                     ask_asset_info: AssetInfo::NativeToken {
                         denom: "uluna".to_string(),
                     },
+                    pair_type: PairType::Xyk {},
                 },
                 SwapOperation::AstroSwap {
                     offer_asset_info: AssetInfo::NativeToken {
@@ -571,6 +614,7 @@ fn test_invalid_operations() {
                     ask_asset_info: AssetInfo::Token {
                         contract_addr: Addr::unchecked("asset0002"),
                     },
+                    pair_type: PairType::Xyk {},
                 },
             ]
         )
@@ -594,6 +638,7 @@ fn test_invalid_operations() {
                     ask_asset_info: AssetInfo::Token {
                         contract_addr: Addr::unchecked("asset0001"),
                     },
+                    pair_type: PairType::Xyk {},
                 },
                 SwapOperation::AstroSwap {
                     offer_asset_info: AssetInfo::Token {
@@ -602,6 +647,7 @@ fn test_invalid_operations() {
                     ask_asset_info: AssetInfo::NativeToken {
                         denom: "uaud".to_string(),
                     },
+                    pair_type: PairType::Xyk {},
                 },
                 SwapOperation::AstroSwap {
                     offer_asset_info: AssetInfo::NativeToken {
@@ -610,6 +656,7 @@ fn test_invalid_operations() {
                     ask_asset_info: AssetInfo::Token {
                         contract_addr: Addr::unchecked("asset0002"),
                     },
+                    pair_type: PairType::Xyk {},
                 },
             ]
         )
@@ -1,3 +1,4 @@
+use astroport::pair::DeadlineExpired;
 use cosmwasm_std::{OverflowError, StdError, Uint128};
 use thiserror::Error;
 
@@ -17,8 +18,14 @@ pub enum ContractError {
     #[error("Assertion failed; minimum receive amount: {receive}, swap amount: {amount}")]
     AssertionMinimumReceive { receive: Uint128, amount: Uint128 },
 
+    #[error("The deadline has passed")]
+    DeadlineExpired {},
+
     #[error("The limit exceeded of swap operations!")]
     SwapLimitExceeded {},
+
+    #[error("Native swaps are only supported on Terra")]
+    NativeSwapNotSupported {},
 }
 
 impl From<OverflowError> for ContractError {
@@ -26,3 +33,9 @@ impl From<OverflowError> for ContractError {
         StdError::from(o).into()
     }
 }
+
+impl From<DeadlineExpired> for ContractError {
+    fn from(_: DeadlineExpired) -> Self {
+        ContractError::DeadlineExpired {}
+    }
+}
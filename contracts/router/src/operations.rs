@@ -2,6 +2,7 @@ use cosmwasm_std::{
     to_binary, Coin, CosmosMsg, Decimal, DepsMut, Env, MessageInfo, Response, StdResult, WasmMsg,
 };
 
+use crate::contract::CustomMsg;
 use crate::error::ContractError;
 use crate::state::{Config, CONFIG};
 
@@ -10,7 +11,8 @@ use astroport::pair::ExecuteMsg as PairExecuteMsg;
 use astroport::querier::{query_balance, query_pair_info, query_token_balance};
 use astroport::router::SwapOperation;
 use cw20::Cw20ExecuteMsg;
-use terra_cosmwasm::{create_swap_msg, create_swap_send_msg, TerraMsgWrapper};
+#[cfg(feature = "terra")]
+use terra_cosmwasm::{create_swap_msg, create_swap_send_msg};
 
 /// ## Description
 /// Execute swap operation. Swap all offer asset to ask asset.
@@ -32,12 +34,13 @@ pub fn execute_swap_operation(
     info: MessageInfo,
     operation: SwapOperation,
     to: Option<String>,
-) -> Result<Response<TerraMsgWrapper>, ContractError> {
+) -> Result<Response<CustomMsg>, ContractError> {
     if env.contract.address != info.sender {
         return Err(ContractError::Unauthorized {});
     }
 
-    let messages: Vec<CosmosMsg<TerraMsgWrapper>> = match operation {
+    let messages: Vec<CosmosMsg<CustomMsg>> = match operation {
+        #[cfg(feature = "terra")]
         SwapOperation::NativeSwap {
             offer_denom,
             ask_denom,
@@ -72,16 +75,22 @@ pub fn execute_swap_operation(
                 )]
             }
         }
+        #[cfg(not(feature = "terra"))]
+        SwapOperation::NativeSwap { .. } => {
+            return Err(ContractError::NativeSwapNotSupported {});
+        }
         SwapOperation::AstroSwap {
             offer_asset_info,
             ask_asset_info,
+            pair_type,
         } => {
             let config: Config = CONFIG.load(deps.storage)?;
             let astroport_factory = config.astroport_factory;
             let pair_info: PairInfo = query_pair_info(
                 &deps.querier,
                 astroport_factory,
-                &[offer_asset_info.clone(), ask_asset_info],
+                &[offer_asset_info.clone(), ask_asset_info.clone()],
+                pair_type,
             )?;
 
             let amount = match offer_asset_info.clone() {
@@ -101,6 +110,7 @@ pub fn execute_swap_operation(
                 deps,
                 pair_info.contract_addr.to_string(),
                 offer_asset,
+                Some(ask_asset_info),
                 None,
                 to,
             )?]
@@ -112,7 +122,7 @@ pub fn execute_swap_operation(
 
 /// ## Description
 /// Creates a message with an exchange operation of type CosmosMsg for each asset.
-/// Returns the [`CosmosMsg<TerraMsgWrapper>`] with the specified attributes if the operation was successful.
+/// Returns the [`CosmosMsg<CustomMsg>`] with the specified attributes if the operation was successful.
 /// ## Params
 /// * **deps** is the object of type [`DepsMut`].
 ///
@@ -120,6 +130,8 @@ pub fn execute_swap_operation(
 ///
 /// * **offer_asset** is the object of type [`Asset`].
 ///
+/// * **ask_asset_info** is the object of type [`Option<AssetInfo>`].
+///
 /// * **max_spread** is the object of type [`Option<Decimal>`].
 ///
 /// * **to** is the object of type [`Option<String>`].
@@ -127,9 +139,10 @@ pub fn asset_into_swap_msg(
     deps: DepsMut,
     pair_contract: String,
     offer_asset: Asset,
+    ask_asset_info: Option<AssetInfo>,
     max_spread: Option<Decimal>,
     to: Option<String>,
-) -> StdResult<CosmosMsg<TerraMsgWrapper>> {
+) -> StdResult<CosmosMsg<CustomMsg>> {
     match offer_asset.info.clone() {
         AssetInfo::NativeToken { denom } => {
             // deduct tax first
@@ -144,9 +157,12 @@ pub fn asset_into_swap_msg(
                         amount,
                         ..offer_asset
                     },
+                    ask_asset_info,
                     belief_price: None,
                     max_spread,
                     to,
+                    minimum_receive: None,
+                    deadline: None,
                 })?,
             }))
         }
@@ -158,9 +174,12 @@ pub fn asset_into_swap_msg(
                 amount: offer_asset.amount,
                 msg: to_binary(&PairExecuteMsg::Swap {
                     offer_asset,
+                    ask_asset_info,
                     belief_price: None,
                     max_spread,
                     to,
+                    minimum_receive: None,
+                    deadline: None,
                 })?,
             })?,
         })),
@@ -10,11 +10,15 @@ use crate::testing::mock_querier::mock_dependencies;
 use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
 
 use astroport::asset::{Asset, AssetInfo};
+use astroport::factory::PairType;
 use astroport::pair::ExecuteMsg as PairExecuteMsg;
+#[cfg(feature = "terra")]
+use astroport::router::SimulateSwapOperationsResponse;
 use astroport::router::{
-    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg,
-    SimulateSwapOperationsResponse, SwapOperation, MAX_SWAP_OPERATIONS,
+    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg, SwapOperation,
+    MAX_SWAP_OPERATIONS,
 };
+#[cfg(feature = "terra")]
 use terra_cosmwasm::{create_swap_msg, create_swap_send_msg};
 
 #[test]
@@ -54,6 +58,7 @@ fn execute_swap_operations() {
         operations: vec![],
         minimum_receive: None,
         to: None,
+        deadline: None,
     };
 
     let env = mock_env();
@@ -61,6 +66,27 @@ fn execute_swap_operations() {
     let res = execute(deps.as_mut(), env, info, msg).unwrap_err();
     assert_eq!(res, ContractError::MustProvideOperations {});
 
+    // Swaps fail once the deadline has passed
+    let env = mock_env();
+    let msg = ExecuteMsg::ExecuteSwapOperations {
+        operations: vec![SwapOperation::AstroSwap {
+            offer_asset_info: AssetInfo::NativeToken {
+                denom: "ukrw".to_string(),
+            },
+            ask_asset_info: AssetInfo::Token {
+                contract_addr: Addr::unchecked("asset0001"),
+            },
+            pair_type: PairType::Xyk {},
+        }],
+        minimum_receive: None,
+        to: None,
+        deadline: Some(env.block.time.seconds() - 1),
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let res = execute(deps.as_mut(), env, info, msg).unwrap_err();
+    assert_eq!(res, ContractError::DeadlineExpired {});
+
     let msg = ExecuteMsg::ExecuteSwapOperations {
         operations: vec![
             SwapOperation::NativeSwap {
@@ -74,6 +100,7 @@ fn execute_swap_operations() {
                 ask_asset_info: AssetInfo::Token {
                     contract_addr: Addr::unchecked("asset0001"),
                 },
+                pair_type: PairType::Xyk {},
             },
             SwapOperation::AstroSwap {
                 offer_asset_info: AssetInfo::Token {
@@ -82,6 +109,7 @@ fn execute_swap_operations() {
                 ask_asset_info: AssetInfo::NativeToken {
                     denom: "uluna".to_string(),
                 },
+                pair_type: PairType::Xyk {},
             },
             SwapOperation::AstroSwap {
                 offer_asset_info: AssetInfo::NativeToken {
@@ -90,10 +118,12 @@ fn execute_swap_operations() {
                 ask_asset_info: AssetInfo::Token {
                     contract_addr: Addr::unchecked("asset0002"),
                 },
+                pair_type: PairType::Xyk {},
             },
         ],
         minimum_receive: Some(Uint128::from(1000000u128)),
         to: None,
+        deadline: None,
     };
 
     let env = mock_env();
@@ -132,6 +162,7 @@ fn execute_swap_operations() {
                             ask_asset_info: AssetInfo::Token {
                                 contract_addr: Addr::unchecked("asset0001"),
                             },
+                            pair_type: PairType::Xyk {},
                         },
                         to: None,
                     })
@@ -154,6 +185,7 @@ fn execute_swap_operations() {
                             ask_asset_info: AssetInfo::NativeToken {
                                 denom: "uluna".to_string(),
                             },
+                            pair_type: PairType::Xyk {},
                         },
                         to: None,
                     })
@@ -176,6 +208,7 @@ fn execute_swap_operations() {
                             ask_asset_info: AssetInfo::Token {
                                 contract_addr: Addr::unchecked("asset0002"),
                             },
+                            pair_type: PairType::Xyk {},
                         },
                         to: Some(String::from("addr0000")),
                     })
@@ -224,6 +257,7 @@ fn execute_swap_operations() {
                     ask_asset_info: AssetInfo::Token {
                         contract_addr: Addr::unchecked("asset0001"),
                     },
+                    pair_type: PairType::Xyk {},
                 },
                 SwapOperation::AstroSwap {
                     offer_asset_info: AssetInfo::Token {
@@ -232,6 +266,7 @@ fn execute_swap_operations() {
                     ask_asset_info: AssetInfo::NativeToken {
                         denom: "uluna".to_string(),
                     },
+                    pair_type: PairType::Xyk {},
                 },
                 SwapOperation::AstroSwap {
                     offer_asset_info: AssetInfo::NativeToken {
@@ -240,10 +275,12 @@ fn execute_swap_operations() {
                     ask_asset_info: AssetInfo::Token {
                         contract_addr: Addr::unchecked("asset0002"),
                     },
+                    pair_type: PairType::Xyk {},
                 },
             ],
             minimum_receive: None,
             to: Some(String::from("addr0002")),
+            deadline: None,
         })
         .unwrap(),
     });
@@ -284,6 +321,7 @@ fn execute_swap_operations() {
                             ask_asset_info: AssetInfo::Token {
                                 contract_addr: Addr::unchecked("asset0001"),
                             },
+                            pair_type: PairType::Xyk {},
                         },
                         to: None,
                     })
@@ -306,6 +344,7 @@ fn execute_swap_operations() {
                             ask_asset_info: AssetInfo::NativeToken {
                                 denom: "uluna".to_string(),
                             },
+                            pair_type: PairType::Xyk {},
                         },
                         to: None,
                     })
@@ -328,6 +367,7 @@ fn execute_swap_operations() {
                             ask_asset_info: AssetInfo::Token {
                                 contract_addr: Addr::unchecked("asset0002"),
                             },
+                            pair_type: PairType::Xyk {},
                         },
                         to: Some(String::from("addr0002")),
                     })
@@ -381,52 +421,67 @@ fn execute_swap_operation() {
     let res = execute(deps.as_mut(), env, info, msg.clone()).unwrap_err();
     assert_eq!(res, ContractError::Unauthorized {});
 
-    let env = mock_env();
-    let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
-    let res = execute(deps.as_mut(), env, info, msg).unwrap();
-    assert_eq!(
-        res.messages,
-        vec![SubMsg {
-            msg: create_swap_msg(
-                Coin {
-                    denom: "uusd".to_string(),
-                    amount: Uint128::new(1000000u128),
-                },
-                "uluna".to_string()
-            ),
-            id: 0,
-            gas_limit: None,
-            reply_on: ReplyOn::Never,
-        }],
-    );
-    // optional to address
-    // swap_send
-    let msg = ExecuteMsg::ExecuteSwapOperation {
-        operation: SwapOperation::NativeSwap {
-            offer_denom: "uusd".to_string(),
-            ask_denom: "uluna".to_string(),
-        },
-        to: Some(String::from("addr0000")),
-    };
-    let env = mock_env();
-    let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
-    let res = execute(deps.as_mut(), env, info, msg.clone()).unwrap();
-    assert_eq!(
-        res.messages,
-        vec![SubMsg {
-            msg: create_swap_send_msg(
-                String::from("addr0000"),
-                Coin {
-                    denom: "uusd".to_string(),
-                    amount: Uint128::new(952380u128), // deduct tax
-                },
-                "uluna".to_string()
-            ),
-            id: 0,
-            gas_limit: None,
-            reply_on: ReplyOn::Never,
-        }],
-    );
+    // Native swaps are executed through the Terra market module
+    #[cfg(feature = "terra")]
+    {
+        let env = mock_env();
+        let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
+        let res = execute(deps.as_mut(), env, info, msg).unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg {
+                msg: create_swap_msg(
+                    Coin {
+                        denom: "uusd".to_string(),
+                        amount: Uint128::new(1000000u128),
+                    },
+                    "uluna".to_string()
+                ),
+                id: 0,
+                gas_limit: None,
+                reply_on: ReplyOn::Never,
+            }],
+        );
+        // optional to address
+        // swap_send
+        let msg = ExecuteMsg::ExecuteSwapOperation {
+            operation: SwapOperation::NativeSwap {
+                offer_denom: "uusd".to_string(),
+                ask_denom: "uluna".to_string(),
+            },
+            to: Some(String::from("addr0000")),
+        };
+        let env = mock_env();
+        let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
+        let res = execute(deps.as_mut(), env, info, msg.clone()).unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg {
+                msg: create_swap_send_msg(
+                    String::from("addr0000"),
+                    Coin {
+                        denom: "uusd".to_string(),
+                        amount: Uint128::new(952380u128), // deduct tax
+                    },
+                    "uluna".to_string()
+                ),
+                id: 0,
+                gas_limit: None,
+                reply_on: ReplyOn::Never,
+            }],
+        );
+    }
+    #[cfg(not(feature = "terra"))]
+    {
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MOCK_CONTRACT_ADDR, &[]),
+            msg,
+        )
+        .unwrap_err();
+        assert_eq!(res, ContractError::NativeSwapNotSupported {});
+    }
 
     deps.querier
         .with_astroport_pairs(&[(&"assetuusd".to_string(), &String::from("pair"))]);
@@ -445,6 +500,7 @@ fn execute_swap_operation() {
             ask_asset_info: AssetInfo::NativeToken {
                 denom: "uusd".to_string(),
             },
+            pair_type: PairType::Xyk {},
         },
         to: Some(String::from("addr0000")),
     };
@@ -467,9 +523,14 @@ fn execute_swap_operation() {
                             },
                             amount: Uint128::new(1000000u128),
                         },
+                        ask_asset_info: Some(AssetInfo::NativeToken {
+                            denom: "uusd".to_string(),
+                        }),
                         belief_price: None,
                         max_spread: None,
                         to: Some(String::from("addr0000")),
+                        minimum_receive: None,
+                        deadline: None,
                     })
                     .unwrap()
                 })
@@ -483,6 +544,27 @@ fn execute_swap_operation() {
     );
 }
 
+#[cfg(not(feature = "terra"))]
+#[test]
+fn simulate_native_swap() {
+    let mut deps = mock_dependencies(&[]);
+    let msg = InstantiateMsg {
+        astroport_factory: String::from("astroportfactory"),
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+
+    let msg = QueryMsg::SimulateSwapOperations {
+        offer_amount: Uint128::from(1000000u128),
+        operations: vec![SwapOperation::NativeSwap {
+            offer_denom: "uusd".to_string(),
+            ask_denom: "uluna".to_string(),
+        }],
+    };
+    let res = query(deps.as_ref(), mock_env(), msg).unwrap_err();
+    assert_eq!(res, ContractError::NativeSwapNotSupported {});
+}
+
+#[cfg(feature = "terra")]
 #[test]
 fn query_buy_with_routes() {
     let mut deps = mock_dependencies(&[]);
@@ -520,6 +602,7 @@ fn query_buy_with_routes() {
                 ask_asset_info: AssetInfo::Token {
                     contract_addr: Addr::unchecked("asset0000"),
                 },
+                pair_type: PairType::Xyk {},
             },
             SwapOperation::AstroSwap {
                 offer_asset_info: AssetInfo::Token {
@@ -528,6 +611,7 @@ fn query_buy_with_routes() {
                 ask_asset_info: AssetInfo::NativeToken {
                     denom: "uluna".to_string(),
                 },
+                pair_type: PairType::Xyk {},
             },
         ],
     };
@@ -676,6 +760,7 @@ fn assert_maximum_receive_swap_operations() {
         ],
         minimum_receive: None,
         to: None,
+        deadline: None,
     };
 
     let env = mock_env();
@@ -64,6 +64,7 @@ pub fn instantiate(
         start_block: msg.start_block,
         allowed_reward_proxies,
         vesting_contract: addr_validate_to_lower(deps.api, &msg.vesting_contract)?,
+        factory: None,
     };
 
     CONFIG.save(deps.storage, &config)?;
@@ -84,7 +85,7 @@ pub fn instantiate(
 /// * **msg** is the object of type [`ExecuteMsg`].
 ///
 /// ## Queries
-/// * **ExecuteMsg::UpdateConfig { vesting_contract }** Sets a new vesting contract.
+/// * **ExecuteMsg::UpdateConfig { vesting_contract, factory }** Sets a new vesting contract and factory.
 ///
 /// * **ExecuteMsg::Add {
 ///             lp_token,
@@ -98,6 +99,8 @@ pub fn instantiate(
 ///             alloc_point,
 ///         }** Updates the given pool's ASTRO allocation point.
 ///
+/// * **ExecuteMsg::DeactivatePool { lp_token }** Sets the given pool's ASTRO allocation point to zero.
+///
 /// * **ExecuteMsg::MassUpdatePools {}** Updates reward variables for all pools.
 ///
 /// * **ExecuteMsg::UpdatePool { lp_token }** Updates reward variables of the given pool to be up-to-date.
@@ -133,9 +136,10 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::UpdateConfig { vesting_contract } => {
-            execute_update_config(deps, info, vesting_contract)
-        }
+        ExecuteMsg::UpdateConfig {
+            vesting_contract,
+            factory,
+        } => execute_update_config(deps, info, vesting_contract, factory),
         ExecuteMsg::Add {
             lp_token,
             alloc_point,
@@ -187,6 +191,31 @@ pub fn execute(
                 },
             )
         }
+        ExecuteMsg::DeactivatePool { lp_token } => {
+            let lp_token = addr_validate_to_lower(deps.api, &lp_token)?;
+
+            let cfg = CONFIG.load(deps.storage)?;
+            if info.sender != cfg.owner && Some(&info.sender) != cfg.factory.as_ref() {
+                return Err(ContractError::Unauthorized {});
+            }
+
+            // the factory deactivates the pools of all the deregistered pairs, incentivized or not
+            let pool_info = match POOL_INFO.may_load(deps.storage, &lp_token)? {
+                Some(pool_info) => pool_info,
+                None => return Ok(Response::new()),
+            };
+
+            update_rewards_and_execute(
+                deps,
+                env,
+                None,
+                ExecuteOnReply::Set {
+                    lp_token,
+                    alloc_point: Uint64::zero(),
+                    has_asset_rewards: pool_info.has_asset_rewards,
+                },
+            )
+        }
         ExecuteMsg::MassUpdatePools {} => {
             update_rewards_and_execute(deps, env, None, ExecuteOnReply::MassUpdatePools {})
         }
@@ -271,7 +300,7 @@ pub fn execute(
 }
 
 /// ## Description
-/// Sets a new vesting contract. Returns an [`ContractError`] on failure or the following [`CONFIG`]
+/// Sets a new vesting contract and factory. Returns an [`ContractError`] on failure or the following [`CONFIG`]
 /// data will be updated if successful.
 ///
 /// ## Params
@@ -280,12 +309,15 @@ pub fn execute(
 /// * **info** is the object of type [`MessageInfo`].
 ///
 /// * **vesting_contract** is an [`Option`] field object of type [`String`].
+///
+/// * **factory** is an [`Option`] field object of type [`String`].
 /// ##Executor
 /// Only owner can execute it
 pub fn execute_update_config(
     deps: DepsMut,
     info: MessageInfo,
     vesting_contract: Option<String>,
+    factory: Option<String>,
 ) -> Result<Response, ContractError> {
     let mut config = CONFIG.load(deps.storage)?;
 
@@ -298,6 +330,10 @@ pub fn execute_update_config(
         config.vesting_contract = addr_validate_to_lower(deps.api, vesting_contract.as_str())?;
     }
 
+    if let Some(factory) = factory {
+        config.factory = Some(addr_validate_to_lower(deps.api, factory.as_str())?);
+    }
+
     CONFIG.save(deps.storage, &config)?;
 
     Ok(Response::new().add_attribute("action", "update_config"))
@@ -1381,6 +1417,7 @@ fn query_config(deps: Deps) -> Result<ConfigResponse, ContractError> {
         tokens_per_block: config.tokens_per_block,
         total_alloc_point: config.total_alloc_point,
         vesting_contract: config.vesting_contract,
+        factory: config.factory,
     })
 }
 
@@ -1562,15 +1599,16 @@ pub fn calculate_rewards(env: &Env, pool: &PoolInfo, cfg: &Config) -> StdResult<
 }
 
 /// ## Description
-/// Used for migration of contract. Returns the default object of type [`Response`].
+/// Used for migration of contract. Sets the factory contract if it is specified, so the factory can
+/// deactivate the pools of the deregistered pairs. Returns the default object of type [`Response`].
 /// ## Params
-/// * **_deps** is the object of type [`DepsMut`].
+/// * **deps** is the object of type [`DepsMut`].
 ///
 /// * **_env** is the object of type [`Env`].
 ///
-/// * **_msg** is the object of type [`MigrateMsg`].
+/// * **msg** is the object of type [`MigrateMsg`].
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
     let contract_version = get_contract_version(deps.storage)?;
 
     match contract_version.contract.as_ref() {
@@ -1604,6 +1642,12 @@ pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, C
         _ => return Err(ContractError::MigrationError {}),
     };
 
+    if let Some(factory) = msg.factory {
+        let mut config = CONFIG.load(deps.storage)?;
+        config.factory = Some(addr_validate_to_lower(deps.api, &factory)?);
+        CONFIG.save(deps.storage, &config)?;
+    }
+
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
     Ok(Response::new()
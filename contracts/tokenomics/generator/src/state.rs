@@ -55,6 +55,8 @@ pub struct Config {
     pub allowed_reward_proxies: Vec<Addr>,
     /// The vesting contract from which rewards are distributed
     pub vesting_contract: Addr,
+    /// The factory contract which is allowed to deactivate pools
+    pub factory: Option<Addr>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
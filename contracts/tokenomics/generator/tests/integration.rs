@@ -2,7 +2,7 @@ use astroport::generator::{ExecuteMsg, QueryMsg};
 use astroport::{
     generator::{
         ConfigResponse, Cw20HookMsg as GeneratorHookMsg, ExecuteMsg as GeneratorExecuteMsg,
-        InstantiateMsg as GeneratorInstantiateMsg, PendingTokenResponse,
+        InstantiateMsg as GeneratorInstantiateMsg, PendingTokenResponse, PoolInfoResponse,
         QueryMsg as GeneratorQueryMsg,
     },
     generator_proxy::InstantiateMsg as ProxyInstantiateMsg,
@@ -193,6 +193,121 @@ fn disabling_pool() {
     );
 }
 
+#[test]
+fn deactivating_pool() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked(OWNER);
+    let user1 = Addr::unchecked(USER1);
+    let factory = Addr::unchecked("factory");
+
+    let token_code_id = store_token_code(&mut app);
+
+    let lp_cny_eur_instance = instantiate_token(&mut app, token_code_id, "CNY-EUR", None);
+    let lp_eur_usd_instance = instantiate_token(&mut app, token_code_id, "EUR-USD", None);
+
+    let astro_token_instance =
+        instantiate_token(&mut app, token_code_id, "ASTRO", Some(1_000_000_000_000000));
+
+    let generator_instance = instantiate_generator(&mut app, &astro_token_instance);
+
+    register_lp_tokens_in_generator(
+        &mut app,
+        &generator_instance,
+        None,
+        &[&lp_cny_eur_instance, &lp_eur_usd_instance],
+    );
+
+    mint_tokens(&mut app, &lp_cny_eur_instance, &user1, 10);
+
+    deposit_lp_tokens_to_generator(
+        &mut app,
+        &generator_instance,
+        USER1,
+        &[(&lp_cny_eur_instance, 10)],
+    );
+
+    app.update_block(|bi| next_block(bi));
+
+    check_pending_rewards(
+        &mut app,
+        &generator_instance,
+        &lp_cny_eur_instance,
+        USER1,
+        (5000000, None),
+    );
+
+    let msg = GeneratorExecuteMsg::DeactivatePool {
+        lp_token: lp_cny_eur_instance.to_string(),
+    };
+
+    // The factory can't deactivate pools until it is set in the config
+    let err = app
+        .execute_contract(factory.clone(), generator_instance.clone(), &msg, &[])
+        .unwrap_err();
+    assert_eq!(err.to_string(), "Unauthorized");
+
+    app.execute_contract(
+        owner,
+        generator_instance.clone(),
+        &GeneratorExecuteMsg::UpdateConfig {
+            vesting_contract: None,
+            factory: Some(factory.to_string()),
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(factory.clone(), generator_instance.clone(), &msg, &[])
+        .unwrap();
+
+    let res: PoolInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &generator_instance,
+            &GeneratorQueryMsg::PoolInfo {
+                lp_token: lp_cny_eur_instance.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(res.alloc_point, Uint64::zero());
+
+    // The pool doesn't accrue rewards anymore
+    app.update_block(|bi| next_block(bi));
+
+    check_pending_rewards(
+        &mut app,
+        &generator_instance,
+        &lp_cny_eur_instance,
+        USER1,
+        (5000000, None),
+    );
+
+    // Depositors can still withdraw their LP tokens along with the accrued rewards
+    let msg = GeneratorExecuteMsg::Withdraw {
+        lp_token: lp_cny_eur_instance.to_string(),
+        amount: Uint128::new(10),
+    };
+
+    app.execute_contract(user1.clone(), generator_instance.clone(), &msg, &[])
+        .unwrap();
+
+    check_token_balance(&mut app, &lp_cny_eur_instance, &generator_instance, 0);
+    check_token_balance(&mut app, &lp_cny_eur_instance, &user1, 10);
+    check_token_balance(&mut app, &astro_token_instance, &user1, 5000000);
+
+    // Pools which aren't registered are ignored
+    app.execute_contract(
+        factory,
+        generator_instance,
+        &GeneratorExecuteMsg::DeactivatePool {
+            lp_token: "unregistered_lp".to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+}
+
 #[test]
 fn set_tokens_per_block() {
     let mut app = mock_app();
@@ -257,6 +372,7 @@ fn update_config() {
 
     let msg = ExecuteMsg::UpdateConfig {
         vesting_contract: Some(new_vesting.to_string()),
+        factory: None,
     };
 
     // Assert cannot update with improper owner
@@ -10,7 +10,7 @@ use astroport::asset::{
     addr_validate_to_lower, token_asset, token_asset_info, Asset, AssetInfo, PairInfo,
 };
 use astroport::common::{claim_ownership, drop_ownership_proposal, propose_new_owner};
-use astroport::factory::UpdateAddr;
+use astroport::factory::{PairType, UpdateAddr};
 use astroport::maker::{
     AssetWithLimit, BalancesResponse, ConfigResponse, ExecuteMsg, InstantiateMsg, MigrateMsg,
     QueryMsg,
@@ -360,6 +360,7 @@ fn swap(
         &deps.querier,
         cfg.factory_contract.clone(),
         &[from_token.clone(), astro],
+        PairType::Xyk {},
     );
 
     if direct_pool.is_ok() {
@@ -376,6 +377,7 @@ fn swap(
         &deps.querier,
         cfg.factory_contract.clone(),
         &[from_token.clone(), bridge_token.clone()],
+        PairType::Xyk {},
     )
     .map_err(|_| ContractError::InvalidBridgeNoPool(from_token.clone(), bridge_token.clone()))?;
 
@@ -693,6 +695,8 @@ fn update_bridges(
     let astro = token_asset_info(cfg.astro_token_contract.clone());
     if let Some(add_bridges) = add {
         for (asset, bridge) in add_bridges {
+            asset.check(deps.api)?;
+            bridge.check(deps.api)?;
             if asset.equal(&bridge) {
                 return Err(ContractError::InvalidBridge(asset, bridge));
             }
@@ -813,7 +817,7 @@ fn query_bridges(deps: Deps, _env: Env) -> StdResult<Vec<(String, String)>> {
 /// * **deps** is the object of type [`Deps`].
 ///
 /// * **contract_addr** is the object of type [`Addr`]. Sets the pair contract address.
-pub fn query_pair(deps: Deps, contract_addr: Addr) -> StdResult<[AssetInfo; 2]> {
+pub fn query_pair(deps: Deps, contract_addr: Addr) -> StdResult<Vec<AssetInfo>> {
     let res: PairInfo = deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
         contract_addr: String::from(contract_addr),
         msg: to_binary(&PairQueryMsg::Pair {})?,
@@ -2,7 +2,8 @@ use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
 use cosmwasm_std::{from_binary, Addr, Decimal, Uint128, Uint64};
 
 use crate::contract::{execute, instantiate, query};
-use crate::state::{Config, CONFIG};
+use crate::state::{Config, BRIDGES, CONFIG};
+use astroport::asset::AssetInfo;
 use astroport::maker::{ConfigResponse, ExecuteMsg, InstantiateMsg, QueryMsg};
 use std::str::FromStr;
 
@@ -135,3 +136,54 @@ fn update_owner() {
         from_binary(&query(deps.as_ref(), env.clone(), QueryMsg::Config {}).unwrap()).unwrap();
     assert_eq!(new_owner, config.owner);
 }
+
+#[test]
+fn update_bridges_validates_assets() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        owner: String::from("owner"),
+        factory_contract: String::from("factory"),
+        staking_contract: String::from("staking"),
+        governance_contract: None,
+        governance_percent: None,
+        astro_token_contract: String::from("astro-token"),
+        max_spread: None,
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+
+    let valid = AssetInfo::NativeToken {
+        denom: String::from("uluna"),
+    };
+    let invalid_denom = AssetInfo::NativeToken {
+        denom: String::from("ULUNA"),
+    };
+    let invalid_token = AssetInfo::Token {
+        contract_addr: Addr::unchecked("Token0000"),
+    };
+
+    for (asset, bridge, err) in [
+        (
+            invalid_denom.clone(),
+            valid.clone(),
+            "Generic error: Native token denom ULUNA should be lowercase",
+        ),
+        (
+            valid.clone(),
+            invalid_token.clone(),
+            "Generic error: Address Token0000 should be lowercase",
+        ),
+    ] {
+        let msg = ExecuteMsg::UpdateBridges {
+            add: Some(vec![(asset, bridge)]),
+            remove: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap_err();
+        assert_eq!(res.to_string(), err);
+    }
+
+    assert!(BRIDGES
+        .may_load(deps.as_ref().storage, invalid_denom.to_string())
+        .unwrap()
+        .is_none());
+}
@@ -1,6 +1,7 @@
 use crate::error::ContractError;
 use crate::state::{Config, BRIDGES};
 use astroport::asset::{Asset, AssetInfo, PairInfo};
+use astroport::factory::PairType;
 use astroport::maker::ExecuteMsg;
 use astroport::pair::Cw20HookMsg;
 use astroport::querier::query_pair_info;
@@ -31,9 +32,12 @@ pub fn build_swap_msg(
             contract_addr: pool.contract_addr.to_string(),
             msg: to_binary(&astroport::pair::ExecuteMsg::Swap {
                 offer_asset,
+                ask_asset_info: None,
                 belief_price: None,
                 max_spread: Some(cfg.max_spread),
                 to: None,
+                minimum_receive: None,
+                deadline: None,
             })?,
             funds: vec![Coin {
                 denom: from.to_string(),
@@ -47,9 +51,12 @@ pub fn build_swap_msg(
                 contract: pool.contract_addr.to_string(),
                 amount: amount_in,
                 msg: to_binary(&Cw20HookMsg::Swap {
+                    ask_asset_info: None,
                     belief_price: None,
                     max_spread: Some(cfg.max_spread),
                     to: None,
+                    minimum_receive: None,
+                    deadline: None,
                 })?,
             })?,
             funds: vec![],
@@ -98,6 +105,7 @@ pub fn validate_bridge(
         &deps.querier,
         cfg.factory_contract.clone(),
         &[from_token.clone(), bridge_token.clone()],
+        PairType::Xyk {},
     )
     .map_err(|_| ContractError::InvalidBridgeNoPool(from_token.clone(), bridge_token.clone()))?;
 
@@ -106,6 +114,7 @@ pub fn validate_bridge(
         &deps.querier,
         cfg.factory_contract.clone(),
         &[bridge_token.clone(), astro_token.clone()],
+        PairType::Xyk {},
     );
 
     if astro_pool.is_err() {
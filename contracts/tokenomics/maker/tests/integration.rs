@@ -99,6 +99,8 @@ fn instantiate_contracts(
             total_fee_bps: 0,
             maker_fee_bps: 0,
             is_disabled: None,
+            permissionless: true,
+            creation_fee: None,
         }],
         token_code_id: 1u64,
         fee_address: None,
@@ -269,7 +271,7 @@ fn create_pair(
         }
     }
 
-    let asset_infos = [assets[0].info.clone(), assets[1].info.clone()];
+    let asset_infos = vec![assets[0].info.clone(), assets[1].info.clone()];
 
     // Create pair in factory
     let res = router
@@ -305,6 +307,7 @@ fn create_pair(
             contract_addr: factory_instance.clone().to_string(),
             msg: to_binary(&astroport::factory::QueryMsg::Pair {
                 asset_infos: asset_infos.clone(),
+                pair_type: None,
             })
             .unwrap(),
         }))
@@ -349,10 +352,12 @@ fn create_pair(
             user.clone(),
             pair_info.contract_addr.clone(),
             &astroport::pair::ExecuteMsg::ProvideLiquidity {
-                assets,
+                assets: assets.to_vec(),
                 slippage_tolerance: None,
                 auto_stake: None,
                 receiver: None,
+                min_lp_to_receive: None,
+                deadline: None,
             },
             &funds,
         )
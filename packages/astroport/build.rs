@@ -0,0 +1,33 @@
+use std::env;
+
+/// The tax rate charged with the `fixed-tax` feature if `ASTROPORT_FIXED_TAX_RATE_BPS` is not set
+const DEFAULT_FIXED_TAX_RATE_BPS: u64 = 10;
+
+/// Validates the fixed tax settings and passes them to the package. An invalid setting fails the
+/// build instead of the tax computation.
+fn main() {
+    println!("cargo:rerun-if-env-changed=ASTROPORT_FIXED_TAX_RATE_BPS");
+    println!("cargo:rerun-if-env-changed=ASTROPORT_FIXED_TAX_CAP");
+
+    let rate_bps = match env::var("ASTROPORT_FIXED_TAX_RATE_BPS") {
+        Ok(rate_bps) => rate_bps
+            .trim()
+            .parse::<u64>()
+            .expect("ASTROPORT_FIXED_TAX_RATE_BPS must be a number of bps"),
+        Err(_) => DEFAULT_FIXED_TAX_RATE_BPS,
+    };
+    if rate_bps > 10_000 {
+        panic!("ASTROPORT_FIXED_TAX_RATE_BPS must not exceed 10000 bps");
+    }
+
+    let cap = env::var("ASTROPORT_FIXED_TAX_CAP")
+        .map(|cap| cap.trim().to_string())
+        .unwrap_or_default();
+    if !cap.is_empty() {
+        cap.parse::<u128>()
+            .expect("ASTROPORT_FIXED_TAX_CAP must be an amount of tokens");
+    }
+
+    println!("cargo:rustc-env=ASTROPORT_FIXED_TAX_RATE_BPS={}", rate_bps);
+    println!("cargo:rustc-env=ASTROPORT_FIXED_TAX_CAP={}", cap);
+}
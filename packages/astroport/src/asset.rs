@@ -9,6 +9,7 @@ use cosmwasm_std::{
     StdResult, Uint128, WasmMsg,
 };
 use cw20::Cw20ExecuteMsg;
+#[cfg(feature = "terra")]
 use terra_cosmwasm::TerraQuerier;
 
 /// ## Description
@@ -30,6 +31,135 @@ impl fmt::Display for Asset {
 /// Decimal points
 static DECIMAL_FRACTION: Uint128 = Uint128::new(1_000_000_000_000_000_000u128);
 
+/// The tax rate charged on native token transfers with the `fixed-tax` feature, in bps. It is set
+/// with the `ASTROPORT_FIXED_TAX_RATE_BPS` environment variable at build time and defaults to 10
+pub const FIXED_TAX_RATE_BPS: &str = env!("ASTROPORT_FIXED_TAX_RATE_BPS");
+
+/// The maximum tax of a single native token transfer with the `fixed-tax` feature. It is set with
+/// the `ASTROPORT_FIXED_TAX_CAP` environment variable at build time. No cap is applied if it is empty
+pub const FIXED_TAX_CAP: &str = env!("ASTROPORT_FIXED_TAX_CAP");
+
+/// ## Description
+/// This enum describes the tax charged on native token transfers. With the `terra` feature the tax
+/// rate and cap are queried from the Terra treasury module. Without it the `fixed-tax` feature
+/// charges the [`FIXED_TAX_RATE_BPS`] set at build time capped by [`FIXED_TAX_CAP`], otherwise no tax is charged.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TaxPolicy {
+    /// The tax rate and cap are queried from the Terra treasury module
+    #[cfg(feature = "terra")]
+    Terra,
+    /// No tax is charged
+    Zero,
+    /// A fixed tax rate with an optional cap per transfer
+    FixedRate { rate: Decimal, cap: Option<Uint128> },
+}
+
+impl TaxPolicy {
+    /// ## Description
+    /// Returns the tax policy the package is built with.
+    pub fn current() -> Self {
+        #[cfg(feature = "terra")]
+        {
+            TaxPolicy::Terra
+        }
+        #[cfg(all(not(feature = "terra"), feature = "fixed-tax"))]
+        {
+            Self::from_settings(FIXED_TAX_RATE_BPS, FIXED_TAX_CAP)
+                .expect("The fixed tax settings are validated by the build script")
+        }
+        #[cfg(not(any(feature = "terra", feature = "fixed-tax")))]
+        {
+            TaxPolicy::Zero
+        }
+    }
+
+    /// ## Description
+    /// Returns a fixed rate policy if the rate is not zero. Otherwise returns [`TaxPolicy::Zero`].
+    /// ## Params
+    /// * **rate_bps** is the object of type [`u64`]. The tax rate in bps.
+    ///
+    /// * **cap** is an [`Option`] of type [`u128`]. The maximum tax of a single transfer.
+    pub fn fixed_rate(rate_bps: u64, cap: Option<u128>) -> Self {
+        if rate_bps == 0 {
+            return TaxPolicy::Zero;
+        }
+
+        TaxPolicy::FixedRate {
+            rate: Decimal::from_ratio(rate_bps, 10_000u64),
+            cap: cap.map(Uint128::new),
+        }
+    }
+
+    /// ## Description
+    /// Parses the fixed tax settings and returns the matching policy, see [`TaxPolicy::fixed_rate`].
+    /// ## Params
+    /// * **rate_bps** is the object of type [`str`]. The tax rate in bps.
+    ///
+    /// * **cap** is the object of type [`str`]. The maximum tax of a single transfer, no cap if it is empty.
+    pub fn from_settings(rate_bps: &str, cap: &str) -> StdResult<Self> {
+        let rate_bps = rate_bps
+            .trim()
+            .parse::<u64>()
+            .map_err(|_| StdError::generic_err(format!("Invalid fixed tax rate: {}", rate_bps)))?;
+        if rate_bps > 10_000 {
+            return Err(StdError::generic_err(format!(
+                "Invalid fixed tax rate: {}",
+                rate_bps
+            )));
+        }
+
+        let cap = cap.trim();
+        let cap = if cap.is_empty() {
+            None
+        } else {
+            let err = StdError::generic_err(format!("Invalid fixed tax cap: {}", cap));
+            Some(cap.parse::<u128>().map_err(|_| err)?)
+        };
+
+        Ok(Self::fixed_rate(rate_bps, cap))
+    }
+
+    /// ## Description
+    /// Calculates and returns the tax for transferring an amount of a native token.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    ///
+    /// * **querier** is an object of type [`QuerierWrapper`]
+    ///
+    /// * **denom** is the denom of the native token.
+    ///
+    /// * **amount** is the object of type [`Uint128`]. The transferred amount including the tax.
+    #[cfg_attr(not(feature = "terra"), allow(unused_variables))]
+    pub fn compute_tax(
+        &self,
+        querier: &QuerierWrapper,
+        denom: &str,
+        amount: Uint128,
+    ) -> StdResult<Uint128> {
+        let (tax_rate, tax_cap) = match self {
+            #[cfg(feature = "terra")]
+            TaxPolicy::Terra => {
+                let terra_querier = TerraQuerier::new(querier);
+                (
+                    terra_querier.query_tax_rate()?.rate,
+                    Some(terra_querier.query_tax_cap(denom.to_string())?.cap),
+                )
+            }
+            TaxPolicy::Zero => return Ok(Uint128::zero()),
+            TaxPolicy::FixedRate { rate, cap } => (*rate, *cap),
+        };
+
+        let tax = amount.checked_sub(amount.multiply_ratio(
+            DECIMAL_FRACTION,
+            DECIMAL_FRACTION * tax_rate + DECIMAL_FRACTION,
+        ))?;
+        Ok(match tax_cap {
+            Some(tax_cap) => std::cmp::min(tax, tax_cap),
+            None => tax,
+        })
+    }
+}
+
 impl Asset {
     /// ## Description
     /// Returns true if the token is native. Otherwise returns false.
@@ -40,24 +170,15 @@ impl Asset {
     }
 
     /// ## Description
-    /// Calculates and returns a tax for a chain's native token. For other tokens it returns zero.
+    /// Calculates and returns a tax for a chain's native token according to the current
+    /// [`TaxPolicy`]. For other tokens it returns zero.
     /// ## Params
     /// * **self** is the type of the caller object.
     ///
     /// * **querier** is an object of type [`QuerierWrapper`]
     pub fn compute_tax(&self, querier: &QuerierWrapper) -> StdResult<Uint128> {
-        let amount = self.amount;
         if let AssetInfo::NativeToken { denom } = &self.info {
-            let terra_querier = TerraQuerier::new(querier);
-            let tax_rate: Decimal = (terra_querier.query_tax_rate()?).rate;
-            let tax_cap: Uint128 = (terra_querier.query_tax_cap(denom.to_string())?).cap;
-            Ok(std::cmp::min(
-                (amount.checked_sub(amount.multiply_ratio(
-                    DECIMAL_FRACTION,
-                    DECIMAL_FRACTION * tax_rate + DECIMAL_FRACTION,
-                )))?,
-                tax_cap,
-            ))
+            TaxPolicy::current().compute_tax(querier, denom, self.amount)
         } else {
             Ok(Uint128::zero())
         }
@@ -239,7 +360,8 @@ impl AssetInfo {
     }
 
     /// ## Description
-    /// Returns [`Ok`] if the token of type [`AssetInfo`] is in lowercase and valid. Otherwise returns [`Err`].
+    /// Returns [`Ok`] if the token of type [`AssetInfo`] is valid. Otherwise returns [`Err`].
+    /// Token addresses must be in lowercase, native denoms are checked with [`validate_native_denom`].
     /// ## Params
     /// * **self** is the type of the caller object.
     ///
@@ -250,12 +372,7 @@ impl AssetInfo {
                 addr_validate_to_lower(api, contract_addr.as_str())?;
             }
             AssetInfo::NativeToken { denom } => {
-                if denom != &denom.to_lowercase() {
-                    return Err(StdError::generic_err(format!(
-                        "Native token denom {} should be lowercase",
-                        denom
-                    )));
-                }
+                validate_native_denom(api, denom)?;
             }
         }
         Ok(())
@@ -266,11 +383,12 @@ impl AssetInfo {
 /// This structure stores the main parameters for an Astroport pair
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct PairInfo {
-    /// Asset information for the two assets in the pool
-    pub asset_infos: [AssetInfo; 2],
+    /// Asset information for the assets in the pool
+    pub asset_infos: Vec<AssetInfo>,
     /// Pair contract address
     pub contract_addr: Addr,
-    /// Pair LP token address
+    /// Pair LP token address. It is empty for pairs without a fungible LP token, such as the
+    /// concentrated liquidity pair which tracks liquidity in range positions
     pub liquidity_token: Addr,
     /// The pool type (xyk, stableswap etc) available in [`PairType`]
     pub pair_type: PairType,
@@ -289,17 +407,16 @@ impl PairInfo {
         &self,
         querier: &QuerierWrapper,
         contract_addr: Addr,
-    ) -> StdResult<[Asset; 2]> {
-        Ok([
-            Asset {
-                amount: self.asset_infos[0].query_pool(querier, contract_addr.clone())?,
-                info: self.asset_infos[0].clone(),
-            },
-            Asset {
-                amount: self.asset_infos[1].query_pool(querier, contract_addr)?,
-                info: self.asset_infos[1].clone(),
-            },
-        ])
+    ) -> StdResult<Vec<Asset>> {
+        self.asset_infos
+            .iter()
+            .map(|asset_info| {
+                Ok(Asset {
+                    amount: asset_info.query_pool(querier, contract_addr.clone())?,
+                    info: asset_info.clone(),
+                })
+            })
+            .collect()
     }
 }
 
@@ -319,16 +436,98 @@ pub fn addr_validate_to_lower(api: &dyn Api, addr: &str) -> StdResult<Addr> {
     api.addr_validate(addr)
 }
 
+/// The length of the hash in an IBC denom
+const IBC_HASH_LENGTH: usize = 64;
+/// The maximum length of a native denom
+const DENOM_MAX_LENGTH: usize = 128;
+/// The minimum length of a native denom
+const DENOM_MIN_LENGTH: usize = 3;
+/// The maximum length of a token factory subdenom
+const SUBDENOM_MAX_LENGTH: usize = 44;
+
+/// ## Description
+/// Returns [`Ok`] if the native denom is valid. Otherwise returns [`Err`]. The following denoms
+/// are accepted:
+/// * IBC denoms in the `ibc/{hash}` form, where the hash consists of 64 uppercase hex characters.
+///
+/// * Token factory denoms in the `factory/{creator}/{subdenom}` form, where the creator is a valid
+/// lowercase address and the subdenom is up to 44 alphanumeric characters or `.`, `_`, `-`, `/`.
+///
+/// * Lowercase denoms of 3 to 128 characters that start with a letter and contain only letters,
+/// digits or `.`, `_`, `-`.
+/// ## Params
+/// * **api** is an object of type [`Api`]
+///
+/// * **denom** is the denom to validate
+pub fn validate_native_denom(api: &dyn Api, denom: &str) -> StdResult<()> {
+    let invalid_denom = |reason: &str| {
+        Err(StdError::generic_err(format!(
+            "Native token denom {} is invalid: {}",
+            denom, reason
+        )))
+    };
+
+    if let Some(hash) = denom.strip_prefix("ibc/") {
+        if hash.len() != IBC_HASH_LENGTH
+            || !hash
+                .chars()
+                .all(|c| c.is_ascii_digit() || ('A'..='F').contains(&c))
+        {
+            return invalid_denom("the IBC hash should be 64 uppercase hex characters");
+        }
+        return Ok(());
+    }
+
+    if let Some(rest) = denom.strip_prefix("factory/") {
+        let (creator, subdenom) = match rest.split_once('/') {
+            Some(parts) => parts,
+            None => return invalid_denom("expected factory/{creator}/{subdenom}"),
+        };
+        addr_validate_to_lower(api, creator)?;
+        if subdenom.is_empty()
+            || subdenom.len() > SUBDENOM_MAX_LENGTH
+            || !subdenom
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || ['.', '_', '-', '/'].contains(&c))
+        {
+            return invalid_denom("malformed subdenom");
+        }
+        if denom.len() > DENOM_MAX_LENGTH {
+            return invalid_denom("too long");
+        }
+        return Ok(());
+    }
+
+    if denom != denom.to_lowercase() {
+        return Err(StdError::generic_err(format!(
+            "Native token denom {} should be lowercase",
+            denom
+        )));
+    }
+    if denom.len() < DENOM_MIN_LENGTH || denom.len() > DENOM_MAX_LENGTH {
+        return invalid_denom("the length should be between 3 and 128 characters");
+    }
+    if !denom.starts_with(|c: char| c.is_ascii_lowercase())
+        || !denom
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || ['.', '_', '-'].contains(&c))
+    {
+        return invalid_denom("unexpected characters");
+    }
+
+    Ok(())
+}
+
 const TOKEN_SYMBOL_MAX_LENGTH: usize = 4;
 
 /// ## Description
 /// Returns a formatted LP token name
 /// ## Params
-/// * **asset_infos** is an array with two items the type of [`AssetInfo`].
+/// * **asset_infos** is a vector with items of type [`AssetInfo`].
 ///
 /// * **querier** is an object of type [`QuerierWrapper`].
 pub fn format_lp_token_name(
-    asset_infos: [AssetInfo; 2],
+    asset_infos: Vec<AssetInfo>,
     querier: &QuerierWrapper,
 ) -> StdResult<String> {
     let mut short_symbols: Vec<String> = vec![];
@@ -345,7 +544,19 @@ pub fn format_lp_token_name(
         }
         short_symbols.push(short_symbol);
     }
-    Ok(format!("{}-{}-LP", short_symbols[0], short_symbols[1]).to_uppercase())
+    Ok(format!("{}-LP", short_symbols.join("-")).to_uppercase())
+}
+
+/// ## Description
+/// Returns the assets formatted as a comma separated string.
+/// ## Params
+/// * **assets** are a slice of [`Asset`] type items.
+pub fn format_assets(assets: &[Asset]) -> String {
+    assets
+        .iter()
+        .map(|asset| asset.to_string())
+        .collect::<Vec<String>>()
+        .join(", ")
 }
 
 pub fn native_asset(denom: String, amount: Uint128) -> Asset {
@@ -0,0 +1,117 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::asset::{Asset, AssetInfo};
+
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use cw20::Cw20ReceiveMsg;
+
+/// ## Description
+/// This structure describes the parameters used for creating a contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {}
+
+/// ## Description
+/// This structure describes the execute messages available in the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// ## Description
+    /// Receives a message of type [`Cw20ReceiveMsg`]
+    Receive(Cw20ReceiveMsg),
+    /// CreateOrder escrows the native offer asset and creates a new order that sells it in equal
+    /// slices
+    CreateOrder {
+        /// The pair to swap in
+        pair_addr: String,
+        /// The oracle used to check the price of every slice
+        oracle_addr: String,
+        /// The total amount to sell
+        offer_asset: Asset,
+        /// The asset to buy
+        ask_asset_info: AssetInfo,
+        /// The number of slices
+        intervals: u64,
+        /// The minimum time between two slices, in seconds
+        interval: u64,
+        /// The maximum spread of every slice relative to the oracle price
+        max_spread: Decimal,
+    },
+    /// ExecuteSlice swaps the next slice of an order. Anyone can execute a slice once the interval
+    /// has elapsed
+    ExecuteSlice { order_id: u64 },
+    /// CancelOrder returns the unspent part of an order to its owner
+    CancelOrder { order_id: u64 },
+}
+
+/// ## Description
+/// This structure describes a CW20 hook message.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// CreateOrder escrows the sent tokens and creates a new order that sells them in equal slices
+    CreateOrder {
+        /// The pair to swap in
+        pair_addr: String,
+        /// The oracle used to check the price of every slice
+        oracle_addr: String,
+        /// The asset to buy
+        ask_asset_info: AssetInfo,
+        /// The number of slices
+        intervals: u64,
+        /// The minimum time between two slices, in seconds
+        interval: u64,
+        /// The maximum spread of every slice relative to the oracle price
+        max_spread: Decimal,
+    },
+}
+
+/// ## Description
+/// This structure describes the query messages available in the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns an order in an [`OrderResponse`] object.
+    Order { order_id: u64 },
+    /// Returns the orders of an owner in a vector that contains objects of type [`OrderResponse`].
+    Orders {
+        owner: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+}
+
+/// ## Description
+/// This structure describes a custom struct used to return an order and its progress.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OrderResponse {
+    pub order_id: u64,
+    /// The order owner address
+    pub owner: Addr,
+    /// The pair to swap in
+    pub pair_addr: Addr,
+    /// The oracle used to check the price of every slice
+    pub oracle_addr: Addr,
+    /// The total amount to sell
+    pub offer_asset: Asset,
+    /// The asset to buy
+    pub ask_asset_info: AssetInfo,
+    /// The number of slices
+    pub intervals: u64,
+    /// The minimum time between two slices, in seconds
+    pub interval: u64,
+    /// The maximum spread of every slice relative to the oracle price
+    pub max_spread: Decimal,
+    /// The number of executed slices
+    pub executed_intervals: u64,
+    /// The offer amount spent so far
+    pub spent_amount: Uint128,
+    /// The earliest time the next slice can be executed at, in seconds
+    pub next_execution_time: u64,
+}
+
+/// ## Description
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
@@ -1,5 +1,5 @@
-use crate::asset::{AssetInfo, PairInfo};
-use cosmwasm_std::{Addr, Binary};
+use crate::asset::{Asset, AssetInfo, PairInfo};
+use cosmwasm_std::{Addr, Binary, Decimal};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter, Result};
@@ -8,9 +8,11 @@ use std::fmt::{Display, Formatter, Result};
 /// This enum describes available pair types.
 /// ## Available pool types
 /// ```
-/// # use astroport::factory::PairType::{Custom, Stable, Xyk};
+/// # use astroport::factory::PairType::{Concentrated, Custom, Stable, Weighted, Xyk};
 /// Xyk {};
 /// Stable {};
+/// Concentrated {};
+/// Weighted {};
 /// Custom(String::from("Custom"));
 /// ```
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -20,6 +22,10 @@ pub enum PairType {
     Xyk {},
     /// Stable pair type
     Stable {},
+    /// Concentrated liquidity pair type
+    Concentrated {},
+    /// Weighted pair type
+    Weighted {},
     /// Custom pair type
     Custom(String),
 }
@@ -30,6 +36,8 @@ impl Display for PairType {
         match self {
             PairType::Xyk {} => fmt.write_str("xyk"),
             PairType::Stable {} => fmt.write_str("stable"),
+            PairType::Concentrated {} => fmt.write_str("concentrated"),
+            PairType::Weighted {} => fmt.write_str("weighted"),
             PairType::Custom(pair_type) => fmt.write_str(format!("custom-{}", pair_type).as_str()),
         }
     }
@@ -50,6 +58,12 @@ pub struct PairConfig {
     /// Whether a pair type is disabled or not. If it is disabled, new pairs cannot be
     /// created, but existing ones can still read the pair configuration
     pub is_disabled: Option<bool>,
+    /// Whether anyone can create pairs of this type. If not, only the owner can create them.
+    /// The factory migration sets it for the pair types stored before the flag existed
+    #[serde(default)]
+    pub permissionless: bool,
+    /// The fee charged to create a pair of this type. It is forwarded to the Maker contract
+    pub creation_fee: Option<Asset>,
 }
 
 impl PairConfig {
@@ -62,6 +76,26 @@ impl PairConfig {
     }
 }
 
+/// ## Description
+/// This structure stores the fees charged by a specific pair. They override the fees of the pair type.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PairFees {
+    /// The total fees (in bps) charged by the pair
+    pub total_fee_bps: u16,
+    /// The amount of fees (in bps) collected by the Maker contract from the pair
+    pub maker_fee_bps: u16,
+}
+
+impl PairFees {
+    /// ## Description
+    /// This method is used to check fee bps.
+    /// ## Params
+    /// `&self` is the type of the caller object.
+    pub fn valid_fee_bps(&self) -> bool {
+        self.total_fee_bps <= 10_000 && self.maker_fee_bps <= 10_000
+    }
+}
+
 /// ## Description
 /// This structure stores the basic settings for creating a new factory contract.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -95,25 +129,91 @@ pub enum ExecuteMsg {
         generator_address: Option<String>,
         /// CW1 whitelist contract code id used to store 3rd party rewards for staking Astroport LP tokens
         whitelist_code_id: Option<u64>,
+        /// Sets or removes the guardian that is allowed to pause pairs
+        guardian: Option<UpdateAddr>,
     },
     /// UpdatePairConfig updates the config for a pair type.
     UpdatePairConfig {
         /// New [`PairConfig`] settings for a pair type
         config: PairConfig,
     },
-    /// CreatePair instantiates a new pair contract.
+    /// UpdatePairFees sets or removes the fee override for a specific pair.
+    UpdatePairFees {
+        /// The pair for which we set the fees
+        pair_addr: String,
+        /// New [`PairFees`] for the pair. If not set, the pair falls back to its pair type fees
+        fees: Option<PairFees>,
+    },
+    /// UpdatePauseStatus pauses or unpauses a specific pair or all the pairs. Paused pairs reject swaps
+    /// and liquidity provisions but still allow withdrawals. The guardian can only pause.
+    UpdatePauseStatus {
+        /// The pair to pause or unpause. If not set, all the pairs are paused or unpaused
+        pair_addr: Option<String>,
+        /// Whether the pair is paused
+        paused: bool,
+    },
+    /// UpdateNativeTokenDecimals sets or removes the number of decimals of a native token.
+    /// Existing stable pairs keep the precisions they stored at creation.
+    UpdateNativeTokenDecimals {
+        /// The native token denom
+        denom: String,
+        /// The number of decimals of the token. If not set, the token falls back to the default precision
+        decimals: Option<u8>,
+    },
+    /// CreatePair instantiates a new pair contract. The creation fee of the pair type must be sent
+    /// along with the message if it is a native token, or approved to the factory if it is a CW20 token.
     CreatePair {
         /// The pair type (exposed in [`PairType`])
         pair_type: PairType,
-        /// The two assets to create the pool for
-        asset_infos: [AssetInfo; 2],
+        /// The assets to create the pool for
+        asset_infos: Vec<AssetInfo>,
         /// Optional binary serialised parameters for custom pool types
         init_params: Option<Binary>,
     },
-    /// Deregister removes a previously created pair.
+    /// CreatePairAndProvide instantiates a new pair contract and provides the initial liquidity
+    /// in the same transaction. Besides the creation fee, the native assets must be sent along with
+    /// the message and the CW20 assets must be approved to the factory.
+    CreatePairAndProvide {
+        /// The pair type (exposed in [`PairType`])
+        pair_type: PairType,
+        /// The assets to create the pool for and their initial amounts
+        assets: Vec<Asset>,
+        /// Optional binary serialised parameters for custom pool types
+        init_params: Option<Binary>,
+        /// The slippage tolerance of the liquidity provision
+        slippage_tolerance: Option<Decimal>,
+        /// Determines whether the initial LP tokens are auto_staked in the Generator contract
+        auto_stake: Option<bool>,
+        /// The receiver of the initial LP tokens. It defaults to the sender
+        receiver: Option<String>,
+    },
+    /// Deregister removes a previously created pair, leaves it paused for withdrawals only
+    /// and deactivates its pool in the generator.
     Deregister {
         /// The assets for which we deregister a pool
-        asset_infos: [AssetInfo; 2],
+        asset_infos: Vec<AssetInfo>,
+        /// The type of the pool. If it isn't specified, the first pool of the assets ordered by type is deregistered
+        pair_type: Option<PairType>,
+    },
+    /// MigratePairs migrates a page of the registered pairs of a pair type to a new code ID.
+    /// The factory must be the admin of the pairs.
+    MigratePairs {
+        /// The code ID to migrate the pairs to
+        code_id: u64,
+        /// The type of the pairs to migrate
+        pair_type: PairType,
+        /// The pair address to start migrating after
+        start_after: Option<String>,
+        /// The number of pairs to migrate
+        limit: Option<u32>,
+        /// The migrate message sent to every pair
+        msg: Binary,
+    },
+    /// MigratePairKeys moves a page of the pairs registered before the pairs were keyed by type.
+    /// It is executed after the contract migration until no pairs are left to move.
+    MigratePairKeys {
+        /// The number of pairs to move
+        limit: Option<u32>,
     },
     /// ProposeNewOwner creates a proposal to change contract ownership.
     /// The validity period for the proposal is set in the `expires_in` variable.
@@ -139,12 +239,21 @@ pub enum QueryMsg {
     /// Pair returns information about a specific pair according to the specified assets.
     Pair {
         /// The assets for which we return a pair
-        asset_infos: [AssetInfo; 2],
+        asset_infos: Vec<AssetInfo>,
+        /// The type of the pair. If it isn't specified, the first pair of the assets ordered by type is returned
+        pair_type: Option<PairType>,
+    },
+    /// PairVariants returns all the pairs of the specified assets, one per pair type, using a [`PairsResponse`] object.
+    PairVariants {
+        /// The assets for which we return the pairs
+        asset_infos: Vec<AssetInfo>,
     },
     /// Pairs returns an array of pairs and their information according to the specified parameters in `start_after` and `limit` variables.
     Pairs {
-        /// The pair item to start reading from. It is an [`Option`] type that accepts two [`AssetInfo`] elements.
-        start_after: Option<[AssetInfo; 2]>,
+        /// The pair item to start reading from. It is an [`Option`] type that accepts a vector of [`AssetInfo`] elements.
+        start_after: Option<Vec<AssetInfo>>,
+        /// The type of the pair item to start reading from. If it isn't specified, reading starts after all the pairs of `start_after`
+        start_after_pair_type: Option<PairType>,
         /// The number of pairs to read and return. It is an [`Option`] type.
         limit: Option<u32>,
     },
@@ -152,6 +261,53 @@ pub enum QueryMsg {
     FeeInfo {
         /// The pair type for which we return fee information. Pair type is a [`PairType`] struct
         pair_type: PairType,
+        /// The pair for which we return fee information. If the pair has a fee override, it is returned instead of the pair type fees
+        pair_addr: Option<String>,
+    },
+    /// CreationFee returns the creation fee of a pair type using a [`CreationFeeResponse`] object.
+    CreationFee {
+        /// The pair type for which we return the creation fee
+        pair_type: PairType,
+    },
+    /// NativeTokenDecimals returns the number of decimals of a native token using a [`NativeTokenDecimalsResponse`] object.
+    NativeTokenDecimals {
+        /// The native token denom
+        denom: String,
+    },
+    /// PairsByAsset returns an array of pairs which contain the specified asset using a [`PairsResponse`] object.
+    PairsByAsset {
+        /// The asset for which we return the pairs
+        asset_info: AssetInfo,
+        /// The pair address to start reading after. It is an [`Option`] type.
+        start_after: Option<String>,
+        /// The number of pairs to read and return. It is an [`Option`] type.
+        limit: Option<u32>,
+    },
+    /// PairsByType returns an array of pairs of the specified type using a [`PairsResponse`] object.
+    PairsByType {
+        /// The pair type for which we return the pairs
+        pair_type: PairType,
+        /// The pair address to start reading after. It is an [`Option`] type.
+        start_after: Option<String>,
+        /// The number of pairs to read and return. It is an [`Option`] type.
+        limit: Option<u32>,
+    },
+    /// PairsCount returns the total number of pairs registered in the factory using a [`PairsCountResponse`] object.
+    PairsCount {},
+    /// PauseStatus returns whether a pair is paused using a [`PauseStatusResponse`] object.
+    PauseStatus {
+        /// The pair for which we return the pause status. If not set, the status of all the pairs is returned
+        pair_addr: Option<String>,
+    },
+    /// PairsToMigrate returns the pairs of the specified type which don't run the code ID of their pair type
+    /// using a [`PairsToMigrateResponse`] object.
+    PairsToMigrate {
+        /// The pair type for which we return the pairs
+        pair_type: PairType,
+        /// The pair address to start reading after. It is an [`Option`] type.
+        start_after: Option<String>,
+        /// The number of pairs to read and return. It is an [`Option`] type.
+        limit: Option<u32>,
     },
 }
 
@@ -171,6 +327,8 @@ pub struct ConfigResponse {
     pub generator_address: Option<Addr>,
     /// CW1 whitelist contract code id used to store 3rd party rewards for staking Astroport LP tokens
     pub whitelist_code_id: u64,
+    /// Address that is allowed to pause pairs
+    pub guardian: Option<Addr>,
 }
 
 /// ## Description
@@ -200,6 +358,58 @@ pub struct FeeInfoResponse {
     pub maker_fee_bps: u16,
 }
 
+/// ## Description
+/// A custom struct for each query response that returns the creation fee of a pair type.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CreationFeeResponse {
+    /// Whether anyone can create pairs of this type
+    pub permissionless: bool,
+    /// The fee charged to create a pair of this type
+    pub creation_fee: Option<Asset>,
+}
+
+/// ## Description
+/// A custom struct for each query response that returns the decimals of a native token.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct NativeTokenDecimalsResponse {
+    /// The number of decimals of the token
+    pub decimals: u8,
+}
+
+/// ## Description
+/// A custom struct for each query response that returns the number of registered pairs.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PairsCountResponse {
+    /// The number of pairs registered in the factory
+    pub count: u64,
+}
+
+/// ## Description
+/// A custom struct for each query response that returns whether a pair is paused.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PauseStatusResponse {
+    /// Whether swaps and liquidity provisions are halted
+    pub paused: bool,
+}
+
+/// ## Description
+/// This structure describes the code ID a pair runs.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PairCodeId {
+    /// The pair contract address
+    pub contract_addr: Addr,
+    /// The code ID the pair runs. It is unknown for pairs created before the factory recorded code IDs
+    pub code_id: Option<u64>,
+}
+
+/// ## Description
+/// A custom struct for each query response that returns the pairs which don't run the code ID of their pair type.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PairsToMigrateResponse {
+    /// The pairs and the code IDs they run
+    pub pairs: Vec<PairCodeId>,
+}
+
 /// ## Description
 /// This is an enum used for setting and removing a contract address.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -0,0 +1,193 @@
+use crate::asset::{format_assets, Asset};
+use crate::querier::FeeInfo;
+use cosmwasm_std::{
+    attr, Binary, CosmosMsg, DepsMut, Env, MessageInfo, OverflowError, Response, StdError,
+    StdResult, Storage, WasmMsg,
+};
+use cw_storage_plus::Item;
+
+/// ## Description
+/// Set while a flash loan is in progress. The pair only accepts the repayment check until it is cleared
+pub const FLASH_LOAN_LOCK: Item<bool> = Item::new("flash_loan_lock");
+
+/// ## Description
+/// This enum describes the flash loan errors. Pairs map them onto their own contract errors.
+#[derive(Debug, PartialEq)]
+pub enum FlashLoanError {
+    Std(StdError),
+    Unauthorized,
+    InvalidZeroAmount,
+    DoublingAssets,
+    AssetMismatch,
+    NotRepaid,
+}
+
+impl From<StdError> for FlashLoanError {
+    fn from(err: StdError) -> Self {
+        FlashLoanError::Std(err)
+    }
+}
+
+impl From<OverflowError> for FlashLoanError {
+    fn from(err: OverflowError) -> Self {
+        FlashLoanError::Std(err.into())
+    }
+}
+
+/// ## Description
+/// Returns `true` if a flash loan is in progress.
+/// ## Params
+/// * **storage** is the object of type [`Storage`].
+pub fn is_flash_loan_locked(storage: &dyn Storage) -> StdResult<bool> {
+    Ok(FLASH_LOAN_LOCK.may_load(storage)?.unwrap_or_default())
+}
+
+/// ## Description
+/// Lends the specified pool assets to the sender within the transaction. Sends the assets to the
+/// sender, executes the `callback` message on the sender contract and then the repayment check
+/// built by `assert_repaid_msg` from the expected pool balances and the fees.
+/// The pair is locked until the loan is repaid.
+/// ## Params
+/// * **deps** is the object of type [`DepsMut`].
+///
+/// * **env** is the object of type [`Env`].
+///
+/// * **info** is the object of type [`MessageInfo`].
+///
+/// * **pools** are a slice of [`Asset`] type items. The balances of the pool assets.
+///
+/// * **assets** is a vector of [`Asset`] type items. Sets the assets to borrow.
+///
+/// * **fee_info** is the object of type [`FeeInfo`]. The fees of the pair.
+///
+/// * **callback** is the object of type [`Binary`]. Sets the message executed on the borrower contract.
+///
+/// * **assert_repaid_msg** builds the pair's `AssertFlashLoanRepaid` message from the expected balances and the fees.
+#[allow(clippy::too_many_arguments)]
+pub fn flash_loan(
+    deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    pools: &[Asset],
+    assets: Vec<Asset>,
+    fee_info: &FeeInfo,
+    callback: Binary,
+    assert_repaid_msg: impl FnOnce(Vec<Asset>, Vec<Asset>) -> StdResult<Binary>,
+) -> Result<Response, FlashLoanError> {
+    if assets.is_empty() {
+        return Err(FlashLoanError::InvalidZeroAmount);
+    }
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    let mut expected_balances: Vec<Asset> = vec![];
+    let mut fees: Vec<Asset> = vec![];
+    for (i, asset) in assets.iter().enumerate() {
+        asset.info.check(deps.api)?;
+        if asset.amount.is_zero() {
+            return Err(FlashLoanError::InvalidZeroAmount);
+        }
+        if assets[..i].iter().any(|a| a.info.equal(&asset.info)) {
+            return Err(FlashLoanError::DoublingAssets);
+        }
+
+        let pool = pools
+            .iter()
+            .find(|pool| pool.info.equal(&asset.info))
+            .ok_or(FlashLoanError::AssetMismatch)?;
+
+        let fee_amount = asset.amount * fee_info.total_fee_rate;
+        expected_balances.push(Asset {
+            info: asset.info.clone(),
+            amount: pool.amount.checked_add(fee_amount)?,
+        });
+        fees.push(Asset {
+            info: asset.info.clone(),
+            amount: fee_amount,
+        });
+        messages.push(asset.clone().into_msg(&deps.querier, info.sender.clone())?);
+    }
+
+    messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: info.sender.to_string(),
+        msg: callback,
+        funds: vec![],
+    }));
+    messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: env.contract.address.to_string(),
+        msg: assert_repaid_msg(expected_balances, fees.clone())?,
+        funds: vec![],
+    }));
+
+    FLASH_LOAN_LOCK.save(deps.storage, &true)?;
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        attr("action", "flash_loan"),
+        attr("borrower", info.sender.as_str()),
+        attr("assets", format_assets(&assets)),
+        attr("fees", format_assets(&fees)),
+    ]))
+}
+
+/// ## Description
+/// Checks that the pool balances reached the expected ones, sends the Maker its share of the
+/// flash loan fees and unlocks the pair. Only the pair itself can execute it.
+/// ## Params
+/// * **deps** is the object of type [`DepsMut`].
+///
+/// * **env** is the object of type [`Env`].
+///
+/// * **info** is the object of type [`MessageInfo`].
+///
+/// * **expected_balances** is a vector of [`Asset`] type items. Sets the pool balances required once the loan is repaid.
+///
+/// * **fees** is a vector of [`Asset`] type items. Sets the flash loan fees.
+///
+/// * **fee_info** is the object of type [`FeeInfo`]. The fees of the pair.
+pub fn assert_flash_loan_repaid(
+    deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    expected_balances: Vec<Asset>,
+    fees: Vec<Asset>,
+    fee_info: &FeeInfo,
+) -> Result<Response, FlashLoanError> {
+    if info.sender != env.contract.address {
+        return Err(FlashLoanError::Unauthorized);
+    }
+
+    for expected in expected_balances.iter() {
+        let balance = expected
+            .info
+            .query_pool(&deps.querier, env.contract.address.clone())?;
+        if balance < expected.amount {
+            return Err(FlashLoanError::NotRepaid);
+        }
+    }
+
+    // Maker fee
+    let mut messages: Vec<CosmosMsg> = vec![];
+    let mut maker_fees: Vec<Asset> = vec![];
+    if let Some(fee_address) = &fee_info.fee_address {
+        for fee in fees {
+            let maker_fee = Asset {
+                info: fee.info,
+                amount: fee.amount * fee_info.maker_fee_rate,
+            };
+            if !maker_fee.amount.is_zero() {
+                messages.push(
+                    maker_fee
+                        .clone()
+                        .into_msg(&deps.querier, fee_address.clone())?,
+                );
+                maker_fees.push(maker_fee);
+            }
+        }
+    }
+
+    FLASH_LOAN_LOCK.save(deps.storage, &false)?;
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        attr("action", "assert_flash_loan_repaid"),
+        attr("maker_fees", format_assets(&maker_fees)),
+    ]))
+}
@@ -25,12 +25,14 @@ pub struct InstantiateMsg {
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
     /// ## Description
-    /// Update the address of the ASTRO vesting contract
+    /// Update the address of the ASTRO vesting contract and of the factory
     /// ## Executor
     /// Only the owner can execute it
     UpdateConfig {
         /// The new vesting contract address
         vesting_contract: Option<String>,
+        /// The new factory contract address which is allowed to deactivate pools
+        factory: Option<String>,
     },
     /// ## Description
     /// Add a new generator for a LP token
@@ -59,6 +61,15 @@ pub enum ExecuteMsg {
         has_asset_rewards: bool,
     },
     /// ## Description
+    /// Sets the given pool's ASTRO allocation point to zero. Its depositors can still withdraw
+    /// their LP tokens. Pools which aren't registered are ignored
+    /// ## Executor
+    /// Only the owner or the factory can execute this.
+    DeactivatePool {
+        /// The address of the LP token contract whose pool we deactivate
+        lp_token: String,
+    },
+    /// ## Description
     /// Updates reward variables for multiple pools
     MassUpdatePools {},
     /// ## Description
@@ -226,12 +237,17 @@ pub struct ConfigResponse {
     pub allowed_reward_proxies: Vec<Addr>,
     /// The ASTRO vesting contract address
     pub vesting_contract: Addr,
+    /// The factory contract address which is allowed to deactivate pools
+    pub factory: Option<Addr>,
 }
 
 /// ## Description
 /// This structure describes a migration message.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct MigrateMsg {}
+pub struct MigrateMsg {
+    /// The factory contract which is allowed to deactivate pools
+    pub factory: Option<String>,
+}
 
 /// ## Description
 /// This structure describes custom hooks for the CW20.
@@ -1,11 +1,16 @@
 pub mod asset;
 pub mod common;
+pub mod dca;
 pub mod factory;
+pub mod flash_loan;
 pub mod generator;
 pub mod generator_proxy;
+pub mod limit_order;
 pub mod maker;
+pub mod observation;
 pub mod oracle;
 pub mod pair;
+pub mod pair_concentrated;
 pub mod pair_stable_bluna;
 pub mod querier;
 pub mod router;
@@ -0,0 +1,152 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::asset::{Asset, AssetInfo};
+
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use cw20::Cw20ReceiveMsg;
+
+/// The maximum bounty that can be paid to the executor of an order (10%)
+pub const MAX_BOUNTY_BPS: u16 = 1000;
+
+/// ## Description
+/// This structure describes the parameters used for creating a contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// The contract owner address
+    pub owner: String,
+    /// The share of the filled offer amount paid to the executor of an order, in bps
+    pub bounty_bps: u16,
+}
+
+/// ## Description
+/// This structure describes the execute messages available in the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// ## Description
+    /// Receives a message of type [`Cw20ReceiveMsg`]
+    Receive(Cw20ReceiveMsg),
+    /// SubmitOrder escrows the native offer asset and creates a new limit order
+    SubmitOrder {
+        /// The pair to execute the order against
+        pair_addr: String,
+        /// The asset to sell
+        offer_asset: Asset,
+        /// The asset to buy
+        ask_asset_info: AssetInfo,
+        /// The minimum amount of the ask asset received per unit of the offer asset
+        price: Decimal,
+    },
+    /// CancelOrder returns the unfilled part of an order to its owner
+    CancelOrder { order_id: u64 },
+    /// ExecuteOrder swaps the order offer asset once the pair simulation reaches the order price.
+    /// Anyone can execute an order and receive the bounty
+    ExecuteOrder {
+        order_id: u64,
+        /// The offer amount to fill. The whole unfilled amount is filled if not specified
+        amount: Option<Uint128>,
+    },
+    /// UpdateConfig updates the contract settings
+    UpdateConfig { bounty_bps: Option<u16> },
+    /// Creates a request to change ownership.
+    ProposeNewOwner {
+        /// The newly proposed owner
+        owner: String,
+        /// The validity period of the offer to change the owner
+        expires_in: u64,
+    },
+    /// Removes a request to change ownership.
+    DropOwnershipProposal {},
+    /// Claims ownership of the contract
+    ClaimOwnership {},
+}
+
+/// ## Description
+/// This structure describes a CW20 hook message.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// SubmitOrder escrows the sent tokens and creates a new limit order
+    SubmitOrder {
+        /// The pair to execute the order against
+        pair_addr: String,
+        /// The asset to buy
+        ask_asset_info: AssetInfo,
+        /// The minimum amount of the ask asset received per unit of the offer asset
+        price: Decimal,
+    },
+}
+
+/// ## Description
+/// This structure describes the query messages available in the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns the contract configuration in a [`ConfigResponse`] object.
+    Config {},
+    /// Returns an order in an [`OrderResponse`] object.
+    Order { order_id: u64 },
+    /// Returns the orders selling an asset in a pair in a vector that contains objects of type
+    /// [`OrderResponse`]. The orders are sorted by price, starting with the lowest one
+    Orders {
+        pair_addr: String,
+        offer_asset_info: AssetInfo,
+        /// The position to start reading after, built from the price and the identifier of the
+        /// last returned order
+        start_after: Option<OrderCursor>,
+        limit: Option<u32>,
+    },
+    /// Returns the orders of an owner in a vector that contains objects of type [`OrderResponse`].
+    OwnerOrders {
+        owner: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+}
+
+/// ## Description
+/// This structure describes a position in the orders of a pair, which are sorted by price and then
+/// by identifier. The order doesn't need to exist anymore.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OrderCursor {
+    /// The order price
+    pub price: Decimal,
+    /// The order identifier
+    pub order_id: u64,
+}
+
+/// ## Description
+/// This structure describes a custom struct used to return the contract configuration.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    /// The contract owner address
+    pub owner: Addr,
+    /// The share of the filled offer amount paid to the executor of an order, in bps
+    pub bounty_bps: u16,
+}
+
+/// ## Description
+/// This structure describes a custom struct used to return a limit order.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OrderResponse {
+    pub order_id: u64,
+    /// The order owner address
+    pub owner: Addr,
+    /// The pair the order is executed against
+    pub pair_addr: Addr,
+    /// The unfilled part of the offer asset
+    pub offer_asset: Asset,
+    /// The asset to buy
+    pub ask_asset_info: AssetInfo,
+    /// The minimum amount of the ask asset received per unit of the offer asset
+    pub price: Decimal,
+    /// The offer amount filled so far
+    pub filled_amount: Uint128,
+}
+
+/// ## Description
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
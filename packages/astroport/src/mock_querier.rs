@@ -1,3 +1,6 @@
+// The tax queriers are only used with the `terra` feature
+#![cfg_attr(not(feature = "terra"), allow(dead_code))]
+
 use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR};
 use cosmwasm_std::{
     from_binary, from_slice, to_binary, Coin, Decimal, OwnedDeps, Querier, QuerierResult,
@@ -9,8 +12,14 @@ use std::collections::HashMap;
 use crate::asset::PairInfo;
 use crate::factory::QueryMsg as FactoryQueryMsg;
 use cw20::{BalanceResponse, Cw20QueryMsg, TokenInfoResponse};
+#[cfg(feature = "terra")]
 use terra_cosmwasm::{TaxCapResponse, TaxRateResponse, TerraQuery, TerraQueryWrapper, TerraRoute};
 
+#[cfg(feature = "terra")]
+type CustomQuery = TerraQueryWrapper;
+#[cfg(not(feature = "terra"))]
+type CustomQuery = cosmwasm_std::Empty;
+
 /// mock_dependencies is a drop-in replacement for cosmwasm_std::testing::mock_dependencies
 /// This uses the Astroport CustomQuerier.
 pub fn mock_dependencies(
@@ -116,7 +125,7 @@ pub(crate) fn pairs_to_map(pairs: &[(&String, &PairInfo)]) -> HashMap<String, Pa
 impl Querier for WasmMockQuerier {
     fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
         // MockQuerier doesn't support Custom, so we ignore it completely here
-        let request: QueryRequest<TerraQueryWrapper> = match from_slice(bin_request) {
+        let request: QueryRequest<CustomQuery> = match from_slice(bin_request) {
             Ok(v) => v,
             Err(e) => {
                 return SystemResult::Err(SystemError::InvalidRequest {
@@ -130,7 +139,7 @@ impl Querier for WasmMockQuerier {
 }
 
 impl WasmMockQuerier {
-    pub fn handle_query(&self, request: &QueryRequest<TerraQueryWrapper>) -> QuerierResult {
+    pub fn handle_query(&self, request: &QueryRequest<CustomQuery>) -> QuerierResult {
         match self.handler {
             QueryHandler::Default => self.query_handler.execute(request),
             QueryHandler::Cw20 => self.cw20_query_handler.execute(request),
@@ -143,7 +152,7 @@ struct CW20QueryHandler {
 }
 
 impl CW20QueryHandler {
-    pub fn execute(&self, request: &QueryRequest<TerraQueryWrapper>) -> QuerierResult {
+    pub fn execute(&self, request: &QueryRequest<CustomQuery>) -> QuerierResult {
         match &request {
             QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg }) => {
                 match from_binary(&msg).unwrap() {
@@ -199,14 +208,15 @@ impl CW20QueryHandler {
 }
 
 struct DefaultQueryHandler {
-    base: MockQuerier<TerraQueryWrapper>,
+    base: MockQuerier<CustomQuery>,
     tax_querier: TaxQuerier,
     astroport_factory_querier: AstroportFactoryQuerier,
 }
 
 impl DefaultQueryHandler {
-    pub fn execute(&self, request: &QueryRequest<TerraQueryWrapper>) -> QuerierResult {
+    pub fn execute(&self, request: &QueryRequest<CustomQuery>) -> QuerierResult {
         match &request {
+            #[cfg(feature = "terra")]
             QueryRequest::Custom(TerraQueryWrapper { route, query_data }) => {
                 if &TerraRoute::Treasury == route {
                     match query_data {
@@ -236,7 +246,7 @@ impl DefaultQueryHandler {
                 contract_addr: _,
                 msg,
             }) => match from_binary(&msg).unwrap() {
-                FactoryQueryMsg::Pair { asset_infos } => {
+                FactoryQueryMsg::Pair { asset_infos, .. } => {
                     let key = asset_infos[0].to_string() + asset_infos[1].to_string().as_str();
                     match self.astroport_factory_querier.pairs.get(&key) {
                         Some(v) => SystemResult::Ok(to_binary(&v).into()),
@@ -254,7 +264,7 @@ impl DefaultQueryHandler {
 }
 
 impl WasmMockQuerier {
-    pub fn new(base: MockQuerier<TerraQueryWrapper>) -> Self {
+    pub fn new(base: MockQuerier<CustomQuery>) -> Self {
         WasmMockQuerier {
             query_handler: DefaultQueryHandler {
                 base,
@@ -0,0 +1,142 @@
+use crate::pair::Observation;
+use cosmwasm_std::{StdError, StdResult, Storage};
+use cw_storage_plus::{Item, Map, U32Key};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// The maximum number of observations kept by a pair. The oldest observation is overwritten once
+/// the buffer is full
+pub const OBSERVATIONS_SIZE: u32 = 3000;
+
+/// ## Description
+/// This structure describes the position of the latest observation in the ring buffer.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ObservationsIndex {
+    /// The slot of the latest observation
+    pub latest: u32,
+    /// The number of stored observations
+    pub count: u32,
+}
+
+/// ## Description
+/// Stores the observations by their slot in the ring buffer
+pub const OBSERVATIONS: Map<U32Key, Observation> = Map::new("observations");
+
+/// ## Description
+/// Stores the position of the latest observation in the ring buffer
+pub const OBSERVATIONS_INDEX: Item<ObservationsIndex> = Item::new("observations_index");
+
+/// ## Description
+/// Stores a new observation in the ring buffer, overwriting the oldest one if the buffer is full.
+/// ## Params
+/// * **storage** is the object of type [`Storage`].
+///
+/// * **observation** is the object of type [`Observation`]. This is the observation to store.
+pub fn store_observation(storage: &mut dyn Storage, observation: &Observation) -> StdResult<()> {
+    let index = match OBSERVATIONS_INDEX.may_load(storage)? {
+        Some(index) => ObservationsIndex {
+            latest: (index.latest + 1) % OBSERVATIONS_SIZE,
+            count: (index.count + 1).min(OBSERVATIONS_SIZE),
+        },
+        None => ObservationsIndex {
+            latest: 0,
+            count: 1,
+        },
+    };
+
+    OBSERVATIONS.save(storage, U32Key::new(index.latest), observation)?;
+    OBSERVATIONS_INDEX.save(storage, &index)
+}
+
+/// ## Description
+/// Returns the cumulative prices at `seconds_ago` seconds before the current observation. The prices
+/// are interpolated between the two observations surrounding the requested time.
+/// ## Params
+/// * **storage** is the object of type [`Storage`].
+///
+/// * **current** is the object of type [`Observation`]. These are the cumulative prices at the current block time.
+///
+/// * **seconds_ago** is the object of type [`u64`]. This is the number of seconds before the current block time.
+pub fn observe(
+    storage: &dyn Storage,
+    current: &Observation,
+    seconds_ago: u64,
+) -> StdResult<Observation> {
+    let target = current
+        .timestamp
+        .checked_sub(seconds_ago)
+        .ok_or_else(|| StdError::generic_err("The requested time is before the genesis"))?;
+    if seconds_ago == 0 {
+        return Ok(current.clone());
+    }
+
+    let index = OBSERVATIONS_INDEX
+        .may_load(storage)?
+        .ok_or_else(|| StdError::generic_err("The pair has no observations"))?;
+
+    let latest = OBSERVATIONS.load(storage, U32Key::new(index.latest))?;
+    if target >= latest.timestamp {
+        return Ok(interpolate(&latest, current, target));
+    }
+
+    // The oldest observation is in the slot following the latest one once the buffer is full
+    let oldest_slot = (index.latest + 1) % index.count;
+    let slot = |position: u32| U32Key::new((oldest_slot + position) % index.count);
+
+    let oldest = OBSERVATIONS.load(storage, slot(0))?;
+    if target < oldest.timestamp {
+        return Err(StdError::generic_err(
+            "The requested time is older than the oldest observation",
+        ));
+    }
+
+    // Binary search for the latest observation made at or before the requested time
+    let (mut low, mut high) = (0, index.count - 1);
+    while high - low > 1 {
+        let middle = (low + high) / 2;
+        if OBSERVATIONS.load(storage, slot(middle))?.timestamp <= target {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+
+    let before = OBSERVATIONS.load(storage, slot(low))?;
+    let after = OBSERVATIONS.load(storage, slot(high))?;
+
+    Ok(interpolate(&before, &after, target))
+}
+
+/// ## Description
+/// Returns the cumulative prices at the `target` time between two observations. The pool price is
+/// constant between two consecutive observations, so the cumulative prices grow linearly.
+/// ## Params
+/// * **before** is the object of type [`Observation`]. This is the observation made at or before the target time.
+///
+/// * **after** is the object of type [`Observation`]. This is the observation made after the target time.
+///
+/// * **target** is the object of type [`u64`]. This is the time to return the cumulative prices for.
+fn interpolate(before: &Observation, after: &Observation, target: u64) -> Observation {
+    if target == before.timestamp {
+        return before.clone();
+    }
+
+    let time_elapsed = target - before.timestamp;
+    let time_delta = after.timestamp - before.timestamp;
+
+    Observation {
+        timestamp: target,
+        price0_cumulative: before.price0_cumulative.wrapping_add(
+            after
+                .price0_cumulative
+                .wrapping_sub(before.price0_cumulative)
+                .multiply_ratio(time_elapsed, time_delta),
+        ),
+        price1_cumulative: before.price1_cumulative.wrapping_add(
+            after
+                .price1_cumulative
+                .wrapping_sub(before.price1_cumulative)
+                .multiply_ratio(time_elapsed, time_delta),
+        ),
+    }
+}
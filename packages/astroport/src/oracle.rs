@@ -1,5 +1,6 @@
-use crate::asset::AssetInfo;
-use cosmwasm_std::Uint128;
+use crate::asset::{AssetInfo, PairInfo};
+use crate::factory::PairType;
+use cosmwasm_std::{Addr, Uint128};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -11,6 +12,8 @@ pub struct InstantiateMsg {
     pub factory_contract: String,
     /// The assets that have a pool for which this contract provides price feeds
     pub asset_infos: [AssetInfo; 2],
+    /// The type of the pool for which this contract provides price feeds
+    pub pair_type: PairType,
 }
 
 /// ## Description
@@ -34,6 +37,22 @@ pub enum QueryMsg {
         /// The amount of tokens for which to compute the token price
         amount: Uint128,
     },
+    /// Returns the oracle configuration in a [`ConfigResponse`] object
+    Config {},
+}
+
+/// ## Description
+/// This structure describes the response of the [`QueryMsg::Config`] query.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    /// The contract address that used for controls settings
+    pub owner: Addr,
+    /// The factory contract address
+    pub factory: Addr,
+    /// The assets for which the oracle provides price feeds
+    pub asset_infos: [AssetInfo; 2],
+    /// The pool the prices are accumulated from
+    pub pair: PairInfo,
 }
 
 /// ## Description
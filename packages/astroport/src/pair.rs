@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::asset::{Asset, AssetInfo};
 
-use cosmwasm_std::{Binary, Decimal, Uint128};
+use cosmwasm_std::{Addr, Binary, Decimal, Env, Fraction, Uint128};
 use cw20::Cw20ReceiveMsg;
 
 /// The default swap slippage
@@ -18,8 +18,8 @@ pub const TWAP_PRECISION: u8 = 6;
 /// This structure describes the parameters used for creating a contract.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
-    /// Information about the two assets in the pool
-    pub asset_infos: [AssetInfo; 2],
+    /// Information about the assets in the pool
+    pub asset_infos: Vec<AssetInfo>,
     /// The token contract code ID used for the tokens in the pool
     pub token_code_id: u64,
     /// The factory contract address
@@ -39,7 +39,23 @@ pub enum ExecuteMsg {
     /// ProvideLiquidity allows someone to provide liquidity in the pool
     ProvideLiquidity {
         /// The assets available in the pool
-        assets: [Asset; 2],
+        assets: Vec<Asset>,
+        /// The slippage tolerance that allows liquidity provision only if the price in the pool doesn't move too much
+        slippage_tolerance: Option<Decimal>,
+        /// Determines whether the LP tokens minted for the user is auto_staked in the Generator contract
+        auto_stake: Option<bool>,
+        /// The receiver of LP tokens
+        receiver: Option<String>,
+        /// The minimum amount of LP tokens to mint, otherwise the provision fails
+        min_lp_to_receive: Option<Uint128>,
+        /// The block time (in seconds) after which the provision fails
+        deadline: Option<u64>,
+    },
+    /// ProvideSingleSided allows someone to provide liquidity with only one of the pool assets.
+    /// The optimal part of the asset is swapped to the other pool asset before the provision
+    ProvideSingleSided {
+        /// The asset to provide
+        asset: Asset,
         /// The slippage tolerance that allows liquidity provision only if the price in the pool doesn't move too much
         slippage_tolerance: Option<Decimal>,
         /// Determines whether the LP tokens minted for the user is auto_staked in the Generator contract
@@ -50,12 +66,43 @@ pub enum ExecuteMsg {
     /// Swap performs a swap in the pool
     Swap {
         offer_asset: Asset,
+        /// The asset to receive. Required for pools with more than two assets
+        ask_asset_info: Option<AssetInfo>,
         belief_price: Option<Decimal>,
         max_spread: Option<Decimal>,
         to: Option<String>,
+        /// The minimum amount of the ask asset to receive, otherwise the swap fails
+        minimum_receive: Option<Uint128>,
+        /// The block time (in seconds) after which the swap fails
+        deadline: Option<u64>,
+    },
+    /// SwapExactOut swaps the native coin sent along with the message for exactly `ask_asset`.
+    /// The unused part of the sent coin is refunded
+    SwapExactOut {
+        /// The asset to receive
+        ask_asset: Asset,
+        /// The maximum amount of the sent coin to swap
+        max_offer_amount: Uint128,
+        to: Option<String>,
     },
     /// Update the pair configuration
     UpdateConfig { params: Binary },
+    /// FlashLoan lends pool assets to the sender within the transaction. The assets are sent to
+    /// the sender, the `callback` message is executed on the sender contract, then the pair checks
+    /// that the assets were returned together with the flash loan fee
+    FlashLoan {
+        /// The assets to borrow
+        assets: Vec<Asset>,
+        /// The message executed on the borrower contract once the assets are sent
+        callback: Binary,
+    },
+    /// Callback checking that a flash loan was repaid. Only the pair itself can execute it
+    AssertFlashLoanRepaid {
+        /// The pool balances required once the loan is repaid
+        expected_balances: Vec<Asset>,
+        /// The flash loan fees paid for each borrowed asset
+        fees: Vec<Asset>,
+    },
 }
 
 /// ## Description
@@ -65,12 +112,48 @@ pub enum ExecuteMsg {
 pub enum Cw20HookMsg {
     /// Swap a given amount of asset
     Swap {
+        /// The asset to receive. Required for pools with more than two assets
+        ask_asset_info: Option<AssetInfo>,
         belief_price: Option<Decimal>,
         max_spread: Option<Decimal>,
         to: Option<String>,
+        /// The minimum amount of the ask asset to receive, otherwise the swap fails
+        minimum_receive: Option<Uint128>,
+        /// The block time (in seconds) after which the swap fails
+        deadline: Option<u64>,
+    },
+    /// Swap the sent tokens for exactly `ask_asset`. The unused part of the tokens is refunded
+    SwapExactOut {
+        /// The asset to receive
+        ask_asset: Asset,
+        /// The maximum amount of the sent tokens to swap
+        max_offer_amount: Uint128,
+        to: Option<String>,
     },
     /// Withdraw liquidity from the pool
-    WithdrawLiquidity {},
+    WithdrawLiquidity {
+        /// The way the liquidity is withdrawn. The assets are returned pro-rata if not specified
+        withdraw_type: Option<WithdrawType>,
+    },
+}
+
+/// ## Description
+/// This enum describes the ways liquidity can be withdrawn from a stable pool.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WithdrawType {
+    /// Withdraws the whole share in a single pool asset. The withdrawal fails if less than
+    /// `min_amount` is returned
+    SingleAsset {
+        asset_info: AssetInfo,
+        min_amount: Option<Uint128>,
+    },
+    /// Withdraws exactly the specified asset amounts. No more than `max_burn_amount` LP tokens
+    /// (the sent amount by default) are burned, the rest of the LP tokens are returned
+    Imbalanced {
+        assets: Vec<Asset>,
+        max_burn_amount: Option<Uint128>,
+    },
 }
 
 /// ## Description
@@ -87,19 +170,44 @@ pub enum QueryMsg {
     /// Returns information about the share of the pool in a vector that contains objects of type [`Asset`].
     Share { amount: Uint128 },
     /// Returns information about a swap simulation in a [`SimulationResponse`] object.
-    Simulation { offer_asset: Asset },
-    /// Returns information about cumulative prices in a [`CumulativePricesResponse`] object.
-    ReverseSimulation { ask_asset: Asset },
+    Simulation {
+        offer_asset: Asset,
+        ask_asset_info: Option<AssetInfo>,
+    },
+    /// Returns information about a reverse swap simulation in a [`ReverseSimulationResponse`] object.
+    ReverseSimulation {
+        offer_asset_info: Option<AssetInfo>,
+        ask_asset: Asset,
+    },
     /// Returns information about the cumulative prices in a [`CumulativePricesResponse`] object
     CumulativePrices {},
+    /// Returns the assets returned and the LP tokens burned for a withdrawal in a [`SimulateWithdrawResponse`] object.
+    SimulateWithdraw {
+        lp_amount: Uint128,
+        withdraw_type: Option<WithdrawType>,
+    },
+    /// Returns the amount of LP tokens minted for providing the specified assets in a [`Uint128`] object.
+    SimulateProvide { assets: Vec<Asset> },
+    /// Returns the cumulative prices at each of the specified number of seconds before the current block
+    /// in an [`ObserveResponse`] object
+    Observe { seconds_ago: Vec<u64> },
 }
 
 /// ## Description
 /// This structure describes a custom struct used to return a query result
-/// with the total amount of LP tokens and the two assets in a specific pool
+/// with the assets returned and the amount of LP tokens burned for a withdrawal
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SimulateWithdrawResponse {
+    pub assets: Vec<Asset>,
+    pub burn_amount: Uint128,
+}
+
+/// ## Description
+/// This structure describes a custom struct used to return a query result
+/// with the total amount of LP tokens and the assets in a specific pool
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct PoolResponse {
-    pub assets: [Asset; 2],
+    pub assets: Vec<Asset>,
     pub total_share: Uint128,
 }
 
@@ -110,6 +218,9 @@ pub struct PoolResponse {
 pub struct ConfigResponse {
     pub block_time_last: u64,
     pub params: Option<Binary>,
+    /// Whether swaps and liquidity provisions are paused in the factory
+    #[serde(default)]
+    pub paused: bool,
 }
 
 /// ## Description
@@ -119,6 +230,9 @@ pub struct SimulationResponse {
     pub return_amount: Uint128,
     pub spread_amount: Uint128,
     pub commission_amount: Uint128,
+    /// The fee rate charged on the swap
+    #[serde(default)]
+    pub fee_rate: Decimal,
 }
 
 /// ## Description
@@ -130,15 +244,77 @@ pub struct ReverseSimulationResponse {
     pub commission_amount: Uint128,
 }
 
+/// ## Description
+/// This structure is set as the response data of a swap
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SwapResponse {
+    /// The asset taken from the sender
+    pub offer_asset: Asset,
+    /// The asset sent to the receiver. The tax is deducted from this amount on transfer
+    pub return_asset: Asset,
+    /// The tax charged on the return asset transfer
+    pub tax_amount: Uint128,
+    /// The difference between the expected and the actual return amount
+    pub spread_amount: Uint128,
+    /// The commission charged on the swap, deducted from the return amount
+    pub commission_amount: Uint128,
+    /// The part of the commission sent to the Maker contract
+    pub maker_fee_amount: Uint128,
+}
+
+/// ## Description
+/// This structure is set as the response data of a liquidity provision
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ProvideResponse {
+    /// The assets deposited in the pool
+    pub assets: Vec<Asset>,
+    /// The amount of LP tokens minted for the receiver
+    pub share: Uint128,
+}
+
+/// ## Description
+/// This structure is set as the response data of a liquidity withdrawal
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct WithdrawResponse {
+    /// The assets sent to the sender
+    pub refund_assets: Vec<Asset>,
+    /// The amount of LP tokens burned
+    pub withdrawn_share: Uint128,
+}
+
 /// ## Description
 /// This structure describes a custom struct used to return a query reponse
-/// containing cumulative prices for the two assets in the pool
+/// containing cumulative prices for the assets in the pool
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct CumulativePricesResponse {
-    pub assets: [Asset; 2],
+    pub assets: Vec<Asset>,
     pub total_share: Uint128,
+    /// Cumulative price of the first asset denominated in the second one
     pub price0_cumulative_last: Uint128,
+    /// Cumulative price of the second asset denominated in the first one
     pub price1_cumulative_last: Uint128,
+    /// Cumulative prices for every (offer, ask) combination of the pool assets
+    pub cumulative_prices: Vec<(AssetInfo, AssetInfo, Uint128)>,
+}
+
+/// ## Description
+/// This structure describes the cumulative prices of the pool assets at a specific time
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Observation {
+    /// The time of the observation in seconds
+    pub timestamp: u64,
+    /// Cumulative price of the first asset denominated in the second one
+    pub price0_cumulative: Uint128,
+    /// Cumulative price of the second asset denominated in the first one
+    pub price1_cumulative: Uint128,
+}
+
+/// ## Description
+/// This structure describes a custom struct used to return a query response
+/// containing the observations for each requested time
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ObserveResponse {
+    pub observations: Vec<Observation>,
 }
 
 /// ## Description
@@ -157,13 +333,36 @@ pub struct StablePoolParams {
 #[serde(rename_all = "snake_case")]
 pub struct StablePoolConfig {
     pub amp: Decimal,
+    /// The dynamic fee settings. The fee set in the factory is charged if they are not set
+    pub dynamic_fee: Option<DynamicFeeParams>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum StablePoolUpdateParams {
-    StartChangingAmp { next_amp: u64, next_amp_time: u64 },
+    StartChangingAmp {
+        next_amp: u64,
+        next_amp_time: u64,
+    },
     StopChangingAmp {},
+    /// Enables the dynamic fee mode with the given settings or disables it if they are not set
+    UpdateDynamicFee {
+        params: Option<DynamicFeeParams>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct WeightedPoolParams {
+    /// The relative weights of the pool assets, ordered as the assets
+    pub weights: Vec<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct WeightedPoolConfig {
+    /// The normalized weights of the pool assets, ordered as the assets
+    pub weights: Vec<Decimal>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -172,3 +371,104 @@ pub struct XykPoolParamsForProxy {
     pub proxy: String,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum XykPoolUpdateParams {
+    /// Enables the dynamic fee mode with the given settings or disables it if they are not set
+    UpdateDynamicFee { params: Option<DynamicFeeParams> },
+    /// Sets the proxy contracts allowed to swap and provide liquidity or allows any sender if they are not set
+    UpdateProxies { proxies: Option<Vec<String>> },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct XykPoolConfig {
+    /// The dynamic fee settings. The fee set in the factory is charged if they are not set
+    pub dynamic_fee: Option<DynamicFeeParams>,
+    /// The proxy contracts allowed to swap and provide liquidity. Any sender is allowed if they are not set
+    pub proxies: Option<Vec<Addr>>,
+}
+
+/// ## Description
+/// This structure describes the settings of the dynamic fee mode. The swap fee grows linearly from
+/// `min_fee_bps` to `max_fee_bps` as the pool price moves away from its price at the start of the
+/// current window.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct DynamicFeeParams {
+    /// The fee (in bps) charged while the price doesn't move
+    pub min_fee_bps: u16,
+    /// The fee (in bps) charged once the price moved by `max_price_change` or more
+    pub max_fee_bps: u16,
+    /// The relative price movement at which the fee reaches `max_fee_bps`
+    pub max_price_change: Decimal,
+    /// The length (in seconds) of the window over which the price movement is measured
+    pub window: u64,
+}
+
+impl DynamicFeeParams {
+    /// ## Description
+    /// Checks that the fee bounds are ordered and smaller than or equal to 10,000 bps, and that
+    /// the price movement and the window are not zero.
+    /// ## Params
+    /// `&self` is the type of the caller object.
+    pub fn is_valid(&self) -> bool {
+        self.min_fee_bps <= self.max_fee_bps
+            && self.max_fee_bps <= 10_000
+            && !self.max_price_change.is_zero()
+            && self.window > 0
+    }
+
+    /// ## Description
+    /// Returns the fee rate charged when the pool price moved from `snapshot_price` to `price`
+    /// within the current window.
+    /// ## Params
+    /// `&self` is the type of the caller object.
+    ///
+    /// `snapshot_price` is the object of type [`Decimal`]. This is the price at the start of the window.
+    ///
+    /// `price` is the object of type [`Decimal`]. This is the current price.
+    pub fn fee_rate(&self, snapshot_price: Decimal, price: Decimal) -> Decimal {
+        let mut price_change = Decimal::zero();
+        if !snapshot_price.is_zero() {
+            let diff = if price > snapshot_price {
+                price - snapshot_price
+            } else {
+                snapshot_price - price
+            };
+            price_change = Decimal::from_ratio(diff.numerator(), snapshot_price.numerator());
+        }
+
+        let ratio = if price_change >= self.max_price_change {
+            Decimal::one()
+        } else {
+            Decimal::from_ratio(price_change.numerator(), self.max_price_change.numerator())
+        };
+
+        Decimal::from_ratio(self.min_fee_bps, 10_000u128)
+            + Decimal::from_ratio(
+                u128::from(self.max_fee_bps - self.min_fee_bps) * ratio.numerator(),
+                10_000 * ratio.denominator(),
+            )
+    }
+}
+
+/// ## Description
+/// This structure describes the error of the operations executed after their deadline. Pairs and
+/// the router map it onto their own contract errors.
+#[derive(Debug, PartialEq)]
+pub struct DeadlineExpired;
+
+/// ## Description
+/// Returns a [`DeadlineExpired`] error if the block time is past the specified deadline.
+/// ## Params
+/// * **env** is the object of type [`Env`].
+///
+/// * **deadline** is an [`Option`] field of type [`u64`]. This is the block time after which the
+/// operation fails.
+pub fn assert_deadline(env: &Env, deadline: Option<u64>) -> Result<(), DeadlineExpired> {
+    match deadline {
+        Some(deadline) if env.block.time.seconds() > deadline => Err(DeadlineExpired),
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,176 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::asset::{Asset, AssetInfo};
+
+use cosmwasm_std::{Addr, Decimal, Uint128, Uint256};
+use cw20::Cw20ReceiveMsg;
+
+/// The lowest tick that can be used as a position bound
+pub const MIN_TICK: i32 = -887272;
+/// The highest tick that can be used as a position bound
+pub const MAX_TICK: i32 = 887272;
+
+/// ## Description
+/// This structure describes the parameters used for creating a concentrated liquidity pair.
+/// The parameters are passed to the pair in the `init_params` field of the [`crate::pair::InstantiateMsg`].
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ConcentratedPoolParams {
+    /// Only ticks divisible by the tick spacing can be used as position bounds
+    pub tick_spacing: u32,
+    /// The initial price of the first pool asset denominated in the second one
+    pub initial_price: Decimal,
+}
+
+/// ## Description
+/// This structure describes the configuration returned in the `params` field of the
+/// [`crate::pair::ConfigResponse`] of a concentrated liquidity pair.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ConcentratedPoolConfig {
+    pub tick_spacing: u32,
+}
+
+/// ## Description
+/// This structure describes the execute messages available in the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// ## Description
+    /// Receives a message of type [`Cw20ReceiveMsg`]
+    Receive(Cw20ReceiveMsg),
+    /// CreatePosition opens a new liquidity position in the price range between two ticks
+    CreatePosition {
+        /// The lower tick of the position range
+        tick_lower: i32,
+        /// The upper tick of the position range
+        tick_upper: i32,
+        /// The maximum amounts of the pool assets to deposit. Native tokens that are not used are refunded
+        assets: Vec<Asset>,
+        /// The minimum amount of liquidity the position must receive
+        min_liquidity: Option<Uint128>,
+        /// The owner of the new position
+        receiver: Option<String>,
+    },
+    /// IncreaseLiquidity adds liquidity to an existing position
+    IncreaseLiquidity {
+        position_id: u64,
+        /// The maximum amounts of the pool assets to deposit. Native tokens that are not used are refunded
+        assets: Vec<Asset>,
+        /// The minimum amount of liquidity the position must receive
+        min_liquidity: Option<Uint128>,
+    },
+    /// DecreaseLiquidity withdraws liquidity from a position together with all the fees it accrued
+    DecreaseLiquidity {
+        position_id: u64,
+        /// The amount of liquidity to withdraw
+        liquidity: Uint128,
+        /// The receiver of the withdrawn assets
+        receiver: Option<String>,
+    },
+    /// CollectFees sends the fees accrued by a position to its owner
+    CollectFees {
+        position_id: u64,
+        /// The receiver of the fees
+        receiver: Option<String>,
+    },
+    /// TransferPosition changes the owner of a position
+    TransferPosition { position_id: u64, recipient: String },
+    /// Swap performs a swap in the pool
+    Swap {
+        offer_asset: Asset,
+        /// The asset to receive. Ignored, as the pool always has two assets
+        ask_asset_info: Option<AssetInfo>,
+        belief_price: Option<Decimal>,
+        max_spread: Option<Decimal>,
+        to: Option<String>,
+    },
+}
+
+/// ## Description
+/// This structure describes a CW20 hook message.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// Swap a given amount of asset
+    Swap {
+        /// The asset to receive. Ignored, as the pool always has two assets
+        ask_asset_info: Option<AssetInfo>,
+        belief_price: Option<Decimal>,
+        max_spread: Option<Decimal>,
+        to: Option<String>,
+    },
+}
+
+/// ## Description
+/// This structure describes the query messages available in the contract.
+/// The queries shared with the other pair types use the same format.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns information about a pair in an object of type [`crate::asset::PairInfo`].
+    Pair {},
+    /// Returns the pool balances and the active liquidity in a [`crate::pair::PoolResponse`] object.
+    Pool {},
+    /// Returns contract configuration settings in a [`crate::pair::ConfigResponse`] object.
+    Config {},
+    /// Returns information about a swap simulation in a [`crate::pair::SimulationResponse`] object.
+    Simulation {
+        offer_asset: Asset,
+        ask_asset_info: Option<AssetInfo>,
+    },
+    /// Returns information about a reverse swap simulation in a [`crate::pair::ReverseSimulationResponse`] object.
+    ReverseSimulation {
+        offer_asset_info: Option<AssetInfo>,
+        ask_asset: Asset,
+    },
+    /// Returns information about the cumulative prices in a [`crate::pair::CumulativePricesResponse`] object.
+    CumulativePrices {},
+    /// Returns the cumulative prices at each of the specified number of seconds before the current
+    /// block in a [`crate::pair::ObserveResponse`] object.
+    Observe { seconds_ago: Vec<u64> },
+    /// Returns the current price, tick and active liquidity in a [`PoolStateResponse`] object.
+    PoolState {},
+    /// Returns information about a position in a [`PositionResponse`] object.
+    Position { position_id: u64 },
+    /// Returns the positions of an owner in a vector that contains objects of type [`PositionResponse`].
+    Positions {
+        owner: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+}
+
+/// ## Description
+/// This structure describes a custom struct used to return the state of a concentrated liquidity pool.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PoolStateResponse {
+    /// The square root of the price as a Q64.96 fixed point number
+    pub sqrt_price_x96: Uint256,
+    /// The current price of the first pool asset denominated in the second one
+    pub price: Decimal,
+    /// The tick of the current price
+    pub tick: i32,
+    /// The liquidity of the positions which ranges contain the current price
+    pub liquidity: Uint128,
+    /// The fee growth per unit of liquidity of the first pool asset as a Q128.128 fixed point number
+    pub fee_growth_global0_x128: Uint256,
+    /// The fee growth per unit of liquidity of the second pool asset as a Q128.128 fixed point number
+    pub fee_growth_global1_x128: Uint256,
+}
+
+/// ## Description
+/// This structure describes a custom struct used to return information about a liquidity position.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PositionResponse {
+    pub position_id: u64,
+    pub owner: Addr,
+    pub tick_lower: i32,
+    pub tick_upper: i32,
+    pub liquidity: Uint128,
+    /// The assets returned if all the position liquidity is withdrawn at the current price
+    pub assets: Vec<Asset>,
+    /// The fees accrued by the position that are not collected yet
+    pub fees: Vec<Asset>,
+}
@@ -24,6 +24,10 @@ pub enum ExecuteMsg {
         auto_stake: Option<bool>,
         /// The receiver of LP tokens
         receiver: Option<String>,
+        /// The minimum amount of LP tokens to mint, otherwise the provision fails
+        min_lp_to_receive: Option<Uint128>,
+        /// The block time (in seconds) after which the provision fails
+        deadline: Option<u64>,
     },
     /// Swap performs a swap in the pool
     Swap {
@@ -31,9 +35,29 @@ pub enum ExecuteMsg {
         belief_price: Option<Decimal>,
         max_spread: Option<Decimal>,
         to: Option<String>,
+        /// The minimum amount of the ask asset to receive, otherwise the swap fails
+        minimum_receive: Option<Uint128>,
+        /// The block time (in seconds) after which the swap fails
+        deadline: Option<u64>,
     },
     /// Update the pair configuration
     UpdateConfig { params: Binary },
+    /// FlashLoan lends pool assets to the sender within the transaction. The assets are sent to
+    /// the sender, the `callback` message is executed on the sender contract, then the pair checks
+    /// that the assets were returned together with the flash loan fee
+    FlashLoan {
+        /// The assets to borrow
+        assets: Vec<Asset>,
+        /// The message executed on the borrower contract once the assets are sent
+        callback: Binary,
+    },
+    /// Callback checking that a flash loan was repaid. Only the pair itself can execute it
+    AssertFlashLoanRepaid {
+        /// The pool balances required once the loan is repaid
+        expected_balances: Vec<Asset>,
+        /// The flash loan fees paid for each borrowed asset
+        fees: Vec<Asset>,
+    },
     /// Claims bLUNA rewards and sends them to the specified receiver
     ClaimReward {
         /// An address which will receive the bLUNA reward
@@ -79,6 +103,9 @@ pub enum QueryMsg {
     CumulativePrices {},
     /// Returns pending token rewards that can be claimed by a specific user in a [`PendingRewardResponse`] object.
     PendingReward { user: String },
+    /// Returns the cumulative prices at each of the specified number of seconds before the current block
+    /// in an [`ObserveResponse`] object
+    Observe { seconds_ago: Vec<u64> },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
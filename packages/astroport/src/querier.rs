@@ -1,19 +1,20 @@
 use crate::asset::{Asset, AssetInfo, PairInfo};
 use crate::factory::{
-    ConfigResponse as FactoryConfigResponse, FeeInfoResponse, PairType, PairsResponse,
-    QueryMsg as FactoryQueryMsg,
+    ConfigResponse as FactoryConfigResponse, FeeInfoResponse, NativeTokenDecimalsResponse,
+    PairType, PairsResponse, PauseStatusResponse, QueryMsg as FactoryQueryMsg,
 };
 use crate::pair::{QueryMsg as PairQueryMsg, ReverseSimulationResponse, SimulationResponse};
 
 use cosmwasm_std::{
     to_binary, Addr, AllBalanceResponse, BalanceResponse, BankQuery, Coin, Decimal, QuerierWrapper,
-    QueryRequest, StdResult, Uint128, WasmQuery,
+    QueryRequest, StdError, StdResult, Uint128, WasmQuery,
 };
 
 use cw20::{BalanceResponse as Cw20BalanceResponse, Cw20QueryMsg, TokenInfoResponse};
 
 // It's defined at https://github.com/terra-money/core/blob/d8e277626e74f9d6417dcd598574686882f0274c/types/assets/assets.go#L15
-const NATIVE_TOKEN_PRECISION: u8 = 6;
+/// The precision of native tokens which aren't registered in the factory
+pub const NATIVE_TOKEN_PRECISION: u8 = 6;
 
 /// ## Description
 /// Returns a native token's balance for a specific account.
@@ -108,14 +109,28 @@ pub fn query_supply(querier: &QuerierWrapper, contract_addr: Addr) -> StdResult<
 }
 
 /// ## Description
-/// Returns the number of decimals that a token has.
+/// Returns the number of decimals that a token has. The decimals of native tokens are read from
+/// the registry kept by the factory.
 /// ## Params
 /// * **querier** is the object of type [`QuerierWrapper`].
 ///
 /// * **asset_info** is the object of type [`AssetInfo`] and contains the asset details for a specific token.
-pub fn query_token_precision(querier: &QuerierWrapper, asset_info: AssetInfo) -> StdResult<u8> {
+///
+/// * **factory_contract** is an object of type [`Addr`] which is the Astroport factory contract address.
+pub fn query_token_precision(
+    querier: &QuerierWrapper,
+    asset_info: AssetInfo,
+    factory_contract: &Addr,
+) -> StdResult<u8> {
     Ok(match asset_info {
-        AssetInfo::NativeToken { denom: _ } => NATIVE_TOKEN_PRECISION,
+        AssetInfo::NativeToken { denom } => {
+            let res: NativeTokenDecimalsResponse = querier.query_wasm_smart(
+                factory_contract,
+                &FactoryQueryMsg::NativeTokenDecimals { denom },
+            )?;
+
+            res.decimals
+        }
         AssetInfo::Token { contract_addr } => {
             let res: TokenInfoResponse =
                 querier.query_wasm_smart(contract_addr, &Cw20QueryMsg::TokenInfo {})?;
@@ -141,6 +156,64 @@ pub fn query_factory_config(
     }))
 }
 
+/// ## Description
+/// Returns whether the specified pair is paused in the factory, either by itself or along with all the pairs.
+/// ## Params
+/// * **querier** is the object of type [`QuerierWrapper`].
+///
+/// * **factory_contract** is an object of type [`Addr`] which is the Astroport factory contract address.
+///
+/// * **pair_addr** is the object of type [`Addr`]. This is the pair we return the pause status for.
+pub fn query_pair_paused(
+    querier: &QuerierWrapper,
+    factory_contract: Addr,
+    pair_addr: Addr,
+) -> StdResult<bool> {
+    let res: PauseStatusResponse = querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: factory_contract.to_string(),
+        msg: to_binary(&FactoryQueryMsg::PauseStatus {
+            pair_addr: Some(pair_addr.to_string()),
+        })?,
+    }))?;
+
+    Ok(res.paused)
+}
+
+/// ## Description
+/// This enum describes the errors of [`assert_not_paused`]. Pairs map it onto their own contract
+/// errors.
+#[derive(Debug, PartialEq)]
+pub enum PauseError {
+    Std(StdError),
+    Paused,
+}
+
+impl From<StdError> for PauseError {
+    fn from(err: StdError) -> Self {
+        PauseError::Std(err)
+    }
+}
+
+/// ## Description
+/// Returns a [`PauseError::Paused`] error if the specified pair is paused in the factory.
+/// ## Params
+/// * **querier** is the object of type [`QuerierWrapper`].
+///
+/// * **factory_contract** is an object of type [`Addr`] which is the Astroport factory contract address.
+///
+/// * **pair_addr** is the object of type [`Addr`]. This is the pair we check the pause status for.
+pub fn assert_not_paused(
+    querier: &QuerierWrapper,
+    factory_contract: Addr,
+    pair_addr: Addr,
+) -> Result<(), PauseError> {
+    if query_pair_paused(querier, factory_contract, pair_addr)? {
+        return Err(PauseError::Paused);
+    }
+
+    Ok(())
+}
+
 /// ## Description
 /// This structure holds parameters that describe the fee structure for a pool.
 pub struct FeeInfo {
@@ -153,21 +226,28 @@ pub struct FeeInfo {
 }
 
 /// ## Description
-/// Returns the fee information for a specific pair type.
+/// Returns the fee information for a specific pair type. If `pair_addr` is set and the factory holds
+/// a fee override for that pair, the override is returned instead of the pair type defaults.
 /// ## Params
 /// * **querier** is the object of type [`QuerierWrapper`].
 ///
 /// * **factory_contract** is the object of type [`Addr`].
 ///
 /// * **pair_type** is the object of type [`PairType`]. This is the pair type we return information for.
+///
+/// * **pair_addr** is an [`Option`] field of type [`Addr`]. This is the pair we return information for.
 pub fn query_fee_info(
     querier: &QuerierWrapper,
     factory_contract: Addr,
     pair_type: PairType,
+    pair_addr: Option<Addr>,
 ) -> StdResult<FeeInfo> {
     let res: FeeInfoResponse = querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
         contract_addr: factory_contract.to_string(),
-        msg: to_binary(&FactoryQueryMsg::FeeInfo { pair_type })?,
+        msg: to_binary(&FactoryQueryMsg::FeeInfo {
+            pair_type,
+            pair_addr: pair_addr.map(|addr| addr.to_string()),
+        })?,
     }))?;
 
     Ok(FeeInfo {
@@ -178,22 +258,26 @@ pub fn query_fee_info(
 }
 
 /// ## Description
-/// Accepts two tokens as input and returns a pair's information.
+/// Accepts the pool assets as input and returns a pair's information.
 /// ## Params
 /// * **querier** is the object of type [`QuerierWrapper`].
 ///
 /// * **factory_contract** is an object of type [`Addr`] and it's the Astroport factory contract address
 ///
-/// * **asset_infos** is an array that contains two items of type [`AssetInfo`].
+/// * **asset_infos** is a slice that contains items of type [`AssetInfo`].
+///
+/// * **pair_type** is the object of type [`PairType`]. The factory registers a pair of every type for the same assets.
 pub fn query_pair_info(
     querier: &QuerierWrapper,
     factory_contract: Addr,
-    asset_infos: &[AssetInfo; 2],
+    asset_infos: &[AssetInfo],
+    pair_type: PairType,
 ) -> StdResult<PairInfo> {
     querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
         contract_addr: factory_contract.to_string(),
         msg: to_binary(&FactoryQueryMsg::Pair {
-            asset_infos: asset_infos.clone(),
+            asset_infos: asset_infos.to_vec(),
+            pair_type: Some(pair_type),
         })?,
     }))
 }
@@ -205,18 +289,22 @@ pub fn query_pair_info(
 ///
 /// * **factory_contract** is an object of type [`Addr`] and represents the Astroport factory contract address.
 ///
-/// * **start_after** is an [`Option`] field that contains an array with two items of type [`AssetInfo`].
+/// * **start_after** is an [`Option`] field that contains a vector with items of type [`AssetInfo`].
 ///
 /// * **limit** is an [`Option`] field of type [`u32`] which is the maximum amount of pairs for which to return information.
 pub fn query_pairs_info(
     querier: &QuerierWrapper,
     factory_contract: Addr,
-    start_after: Option<[AssetInfo; 2]>,
+    start_after: Option<Vec<AssetInfo>>,
     limit: Option<u32>,
 ) -> StdResult<PairsResponse> {
     querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
         contract_addr: factory_contract.to_string(),
-        msg: to_binary(&FactoryQueryMsg::Pairs { start_after, limit })?,
+        msg: to_binary(&FactoryQueryMsg::Pairs {
+            start_after,
+            start_after_pair_type: None,
+            limit,
+        })?,
     }))
 }
 
@@ -237,6 +325,7 @@ pub fn simulate(
         contract_addr: pair_contract.to_string(),
         msg: to_binary(&PairQueryMsg::Simulation {
             offer_asset: offer_asset.clone(),
+            ask_asset_info: None,
         })?,
     }))
 }
@@ -257,6 +346,7 @@ pub fn reverse_simulate(
     querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
         contract_addr: pair_contract.to_string(),
         msg: to_binary(&PairQueryMsg::ReverseSimulation {
+            offer_asset_info: None,
             ask_asset: ask_asset.clone(),
         })?,
     }))
@@ -5,6 +5,7 @@ use cosmwasm_std::{Addr, Uint128};
 use cw20::Cw20ReceiveMsg;
 
 use crate::asset::AssetInfo;
+use crate::factory::PairType;
 
 pub const MAX_SWAP_OPERATIONS: usize = 50;
 
@@ -34,9 +35,18 @@ pub enum SwapOperation {
         offer_asset_info: AssetInfo,
         /// Information about the asset we swap to
         ask_asset_info: AssetInfo,
+        /// The type of the pool to swap in. Operations that don't set it swap in XYK pools
+        #[serde(default = "default_pair_type")]
+        pair_type: PairType,
     },
 }
 
+/// ## Description
+/// Returns the pair type of the swap operations that don't set it.
+fn default_pair_type() -> PairType {
+    PairType::Xyk {}
+}
+
 impl SwapOperation {
     pub fn get_target_asset_info(&self) -> AssetInfo {
         match self {
@@ -60,6 +70,8 @@ pub enum ExecuteMsg {
         operations: Vec<SwapOperation>,
         minimum_receive: Option<Uint128>,
         to: Option<Addr>,
+        /// The block time (in seconds) after which the swaps fail
+        deadline: Option<u64>,
     },
 
     /// Internal use
@@ -88,6 +100,8 @@ pub enum Cw20HookMsg {
         minimum_receive: Option<Uint128>,
         ///
         to: Option<String>,
+        /// The block time (in seconds) after which the swaps fail
+        deadline: Option<u64>,
     },
 }
 
@@ -1,13 +1,19 @@
-use crate::asset::{format_lp_token_name, Asset, AssetInfo, PairInfo};
+use crate::asset::{
+    format_lp_token_name, validate_native_denom, Asset, AssetInfo, PairInfo, TaxPolicy,
+};
 use crate::mock_querier::mock_dependencies;
+use crate::observation::{observe, store_observation, OBSERVATIONS_INDEX, OBSERVATIONS_SIZE};
+use crate::pair::Observation;
 use crate::querier::{
     query_all_balances, query_balance, query_pair_info, query_supply, query_token_balance,
 };
 
 use crate::factory::PairType;
 use crate::DecimalCheckedOps;
-use cosmwasm_std::testing::MOCK_CONTRACT_ADDR;
-use cosmwasm_std::{to_binary, Addr, BankMsg, Coin, CosmosMsg, Decimal, Uint128, WasmMsg};
+use cosmwasm_std::testing::{MockStorage, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    to_binary, Addr, BankMsg, Coin, CosmosMsg, Decimal, StdError, Uint128, WasmMsg,
+};
 use cw20::Cw20ExecuteMsg;
 
 #[test]
@@ -157,6 +163,138 @@ fn test_asset_info() {
     );
 }
 
+#[test]
+fn test_native_denom_validation() {
+    let deps = mock_dependencies(&[]);
+    let api = deps.as_ref().api;
+
+    for denom in [
+        "uusd",
+        "uluna",
+        "stake-1.x_y",
+        "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2",
+        "factory/creator0000/ufoo",
+        "factory/creator0000/pool/1",
+    ] {
+        validate_native_denom(api, denom).unwrap();
+        AssetInfo::NativeToken {
+            denom: denom.to_string(),
+        }
+        .check(api)
+        .unwrap();
+    }
+
+    for denom in [
+        "",
+        "us",
+        "UUSD",
+        "1usd",
+        "u usd",
+        "uusd/1",
+        "ibc/",
+        "ibc/27394fb092d2eccd56123c74f36e4c1f926001ceada9ca97ea622b25f41e5eb2",
+        "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB",
+        "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EBZ",
+        "factory/creator0000",
+        "factory/creator0000/",
+        "factory/Creator0000/ufoo",
+        "factory/creator0000/u foo",
+        "factory/creator0000/aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa",
+    ] {
+        validate_native_denom(api, denom).unwrap_err();
+        AssetInfo::NativeToken {
+            denom: denom.to_string(),
+        }
+        .check(api)
+        .unwrap_err();
+    }
+}
+
+#[test]
+fn test_tax_policy() {
+    let deps = mock_dependencies(&[]);
+    let querier = deps.as_ref().querier;
+    let amount = Uint128::new(123123u128);
+
+    assert_eq!(TaxPolicy::fixed_rate(0, Some(1000)), TaxPolicy::Zero);
+    assert_eq!(
+        TaxPolicy::fixed_rate(100, Some(1000)),
+        TaxPolicy::FixedRate {
+            rate: Decimal::percent(1),
+            cap: Some(Uint128::new(1000u128)),
+        }
+    );
+    assert_eq!(
+        TaxPolicy::from_settings("25", "").unwrap(),
+        TaxPolicy::FixedRate {
+            rate: Decimal::from_ratio(25u64, 10_000u64),
+            cap: None,
+        }
+    );
+    assert_eq!(
+        TaxPolicy::from_settings(" 100 ", "1000").unwrap(),
+        TaxPolicy::fixed_rate(100, Some(1000))
+    );
+    assert_eq!(TaxPolicy::from_settings("0", "").unwrap(), TaxPolicy::Zero);
+    assert_eq!(
+        TaxPolicy::from_settings("ten", "").unwrap_err(),
+        StdError::generic_err("Invalid fixed tax rate: ten")
+    );
+    assert_eq!(
+        TaxPolicy::from_settings("10001", "").unwrap_err(),
+        StdError::generic_err("Invalid fixed tax rate: 10001")
+    );
+    assert_eq!(
+        TaxPolicy::from_settings("10", "-1").unwrap_err(),
+        StdError::generic_err("Invalid fixed tax cap: -1")
+    );
+    #[cfg(all(not(feature = "terra"), feature = "fixed-tax"))]
+    {
+        // CI also runs this build with a non-default rate, see ASTROPORT_FIXED_TAX_RATE_BPS
+        let rate_bps: u64 = option_env!("ASTROPORT_FIXED_TAX_RATE_BPS")
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert_eq!(
+            TaxPolicy::current(),
+            TaxPolicy::fixed_rate(rate_bps, crate::asset::FIXED_TAX_CAP.parse().ok())
+        );
+        assert_eq!(
+            TaxPolicy::current()
+                .compute_tax(&querier, "uusd", amount)
+                .unwrap(),
+            amount - amount.multiply_ratio(10_000u128, 10_000u128 + rate_bps as u128)
+        );
+    }
+    #[cfg(not(any(feature = "terra", feature = "fixed-tax")))]
+    assert_eq!(TaxPolicy::current(), TaxPolicy::Zero);
+
+    assert_eq!(
+        TaxPolicy::Zero
+            .compute_tax(&querier, "uusd", amount)
+            .unwrap(),
+        Uint128::zero()
+    );
+    assert_eq!(
+        TaxPolicy::FixedRate {
+            rate: Decimal::percent(1),
+            cap: None,
+        }
+        .compute_tax(&querier, "uusd", amount)
+        .unwrap(),
+        Uint128::new(1220u128)
+    );
+    assert_eq!(
+        TaxPolicy::FixedRate {
+            rate: Decimal::percent(1),
+            cap: Some(Uint128::new(1000u128)),
+        }
+        .compute_tax(&querier, "uusd", amount)
+        .unwrap(),
+        Uint128::new(1000u128)
+    );
+}
+
 #[test]
 fn test_asset() {
     let mut deps = mock_dependencies(&[Coin {
@@ -178,6 +316,12 @@ fn test_asset() {
         Decimal::percent(1),
         &[(&"uusd".to_string(), &Uint128::new(1000000u128))],
     );
+    // The treasury tax is only charged with the `terra` feature
+    let tax = if cfg!(feature = "terra") {
+        Uint128::new(1220u128)
+    } else {
+        Uint128::zero()
+    };
 
     let token_asset = Asset {
         amount: Uint128::new(123123u128),
@@ -201,7 +345,7 @@ fn test_asset() {
         native_token_asset
             .compute_tax(&deps.as_ref().querier)
             .unwrap(),
-        Uint128::new(1220u128)
+        tax
     );
 
     assert_eq!(
@@ -210,7 +354,7 @@ fn test_asset() {
             .unwrap(),
         Coin {
             denom: "uusd".to_string(),
-            amount: Uint128::new(121903u128),
+            amount: Uint128::new(123123u128) - tax,
         }
     );
 
@@ -237,7 +381,7 @@ fn test_asset() {
             to_address: String::from("addr0000"),
             amount: vec![Coin {
                 denom: "uusd".to_string(),
-                amount: Uint128::new(121903u128),
+                amount: Uint128::new(123123u128) - tax,
             }]
         })
     );
@@ -250,7 +394,7 @@ fn query_astroport_pair_contract() {
     deps.querier.with_astroport_pairs(&[(
         &"asset0000uusd".to_string(),
         &PairInfo {
-            asset_infos: [
+            asset_infos: vec![
                 AssetInfo::Token {
                     contract_addr: Addr::unchecked("asset0000"),
                 },
@@ -275,6 +419,7 @@ fn query_astroport_pair_contract() {
                 denom: "uusd".to_string(),
             },
         ],
+        PairType::Xyk {},
     )
     .unwrap();
 
@@ -288,7 +433,7 @@ fn test_format_lp_token_name() {
     deps.querier.with_astroport_pairs(&[(
         &"asset0000uusd".to_string(),
         &PairInfo {
-            asset_infos: [
+            asset_infos: vec![
                 AssetInfo::Token {
                     contract_addr: Addr::unchecked("asset0000"),
                 },
@@ -313,6 +458,7 @@ fn test_format_lp_token_name() {
                 denom: "uusd".to_string(),
             },
         ],
+        PairType::Xyk {},
     )
     .unwrap();
 
@@ -324,7 +470,24 @@ fn test_format_lp_token_name() {
     deps.querier.with_cw20_query_handler();
 
     let lp_name = format_lp_token_name(pair_info.asset_infos, &deps.as_ref().querier).unwrap();
-    assert_eq!(lp_name, "MAPP-UUSD-LP")
+    assert_eq!(lp_name, "MAPP-UUSD-LP");
+
+    let lp_name = format_lp_token_name(
+        vec![
+            AssetInfo::Token {
+                contract_addr: Addr::unchecked("asset0000"),
+            },
+            AssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            },
+            AssetInfo::NativeToken {
+                denom: "ukrw".to_string(),
+            },
+        ],
+        &deps.as_ref().querier,
+    )
+    .unwrap();
+    assert_eq!(lp_name, "MAPP-UUSD-UKRW-LP")
 }
 
 #[test]
@@ -352,3 +515,67 @@ fn test_decimal_checked_ops() {
             .is_err()
     );
 }
+
+fn observation(timestamp: u64) -> Observation {
+    Observation {
+        timestamp,
+        price0_cumulative: Uint128::from(timestamp * 3),
+        price1_cumulative: Uint128::from(timestamp * 5),
+    }
+}
+
+#[test]
+fn observations_ring_buffer() {
+    let mut storage = MockStorage::new();
+
+    // No observations yet
+    assert_eq!(
+        observe(&storage, &observation(1000), 100).unwrap_err(),
+        StdError::generic_err("The pair has no observations")
+    );
+
+    // Fill the buffer past its size so the oldest observations are overwritten
+    let total = OBSERVATIONS_SIZE as u64 + 10;
+    for i in 0..total {
+        store_observation(&mut storage, &observation(i * 10)).unwrap();
+    }
+
+    let index = OBSERVATIONS_INDEX.load(&storage).unwrap();
+    assert_eq!(index.count, OBSERVATIONS_SIZE);
+    assert_eq!(index.latest, 9);
+
+    let current = observation(total * 10 + 5);
+
+    // The current prices are returned as is
+    assert_eq!(observe(&storage, &current, 0).unwrap(), current);
+
+    // Interpolated between the latest observation and the current prices
+    assert_eq!(
+        observe(&storage, &current, 3).unwrap(),
+        observation(total * 10 + 2)
+    );
+
+    // Interpolated between two stored observations
+    assert_eq!(
+        observe(&storage, &current, current.timestamp - 12345).unwrap(),
+        observation(12345)
+    );
+
+    // Exactly at the oldest stored observation
+    assert_eq!(
+        observe(&storage, &current, current.timestamp - 100).unwrap(),
+        observation(100)
+    );
+
+    // Older than the oldest stored observation
+    assert_eq!(
+        observe(&storage, &current, current.timestamp - 95).unwrap_err(),
+        StdError::generic_err("The requested time is older than the oldest observation")
+    );
+
+    // Before the genesis
+    assert_eq!(
+        observe(&storage, &current, current.timestamp + 1).unwrap_err(),
+        StdError::generic_err("The requested time is before the genesis")
+    );
+}